@@ -3,8 +3,10 @@ use nova_vm::ecmascript::{
     execution::{agent::ExceptionType, Agent, JsResult},
     types::{InternalMethods, IntoValue, Object, PropertyDescriptor, PropertyKey, String, Value},
 };
-use nova_vm::engine::context::GcScope;
+use nova_vm::engine::{context::GcScope, CodeCache};
+use nova_vm::heap::HeapStatistics;
 use oxc_diagnostics::OxcDiagnostic;
+use std::path::PathBuf;
 
 /// Initialize the global object with the built-in functions.
 pub fn initialize_global_object(agent: &mut Agent, global: Object, mut gc: GcScope) {
@@ -155,3 +157,54 @@ pub fn exit_with_parse_errors(errors: Vec<OxcDiagnostic>, source_path: &str, sou
 
     std::process::exit(1);
 }
+
+/// Print heap statistics as a table, skipping empty heap vectors.
+pub fn print_heap_statistics(stats: &HeapStatistics) {
+    println!(
+        "{:<32} {:>12} {:>12} {:>14} {:>14}",
+        "Heap kind", "Live", "Slots", "Bytes", "Owned bytes"
+    );
+    for kind in stats.kinds.iter().filter(|kind| kind.slot_count > 0) {
+        println!(
+            "{:<32} {:>12} {:>12} {:>14} {:>14}",
+            kind.name, kind.live_count, kind.slot_count, kind.bytes, kind.owned_bytes
+        );
+    }
+    println!(
+        "{:<32} {:>12} {:>12} {:>14}",
+        "Total", "", "", stats.total_bytes
+    );
+    println!();
+    println!("GC runs: {}", stats.gc_count);
+    println!("GC collected entries: {}", stats.gc_collected_count);
+    println!("GC total pause: {:?}", stats.gc_total_pause);
+    println!("GC max pause: {:?}", stats.gc_max_pause);
+}
+
+/// Bytecode cache that keeps each entry in a file of a directory.
+pub struct FileCodeCache {
+    dir: PathBuf,
+}
+
+impl FileCodeCache {
+    pub fn new(dir: PathBuf) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn entry_path(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{:016x}.novacode", key))
+    }
+}
+
+impl CodeCache for FileCodeCache {
+    fn get(&self, key: u64) -> Option<Vec<u8>> {
+        std::fs::read(self.entry_path(key)).ok()
+    }
+
+    fn set(&self, key: u64, bytecode: &[u8]) {
+        // Failing to write an entry only means that the code is compiled
+        // again on the next run.
+        let _ = std::fs::write(self.entry_path(key), bytecode);
+    }
+}
@@ -8,7 +8,9 @@ use std::{cell::RefCell, collections::VecDeque, fmt::Debug};
 
 use clap::{Parser as ClapParser, Subcommand};
 use cliclack::{input, intro, set_theme};
-use helper::{exit_with_parse_errors, initialize_global_object};
+use helper::{
+    exit_with_parse_errors, initialize_global_object, print_heap_statistics, FileCodeCache,
+};
 use nova_vm::{
     ecmascript::{
         execution::{
@@ -18,7 +20,7 @@ use nova_vm::{
         scripts_and_modules::script::{parse_script, script_evaluation},
         types::{Object, String as JsString},
     },
-    engine::context::GcScope,
+    engine::{context::GcScope, CodeCache},
 };
 use oxc_parser::Parser;
 use oxc_semantic::{SemanticBuilder, SemanticBuilderReturn};
@@ -49,6 +51,29 @@ enum Command {
         #[arg(long)]
         nogc: bool,
 
+        /// Collect garbage at every instruction and poison collected heap
+        /// slots, to catch stale heap references in native code
+        #[arg(long)]
+        gc_stress: bool,
+
+        /// Drop the parsed code of source code that is not being run at every
+        /// garbage collection, and parse it again when it is needed
+        #[arg(long)]
+        discard_parsed_code: bool,
+
+        /// Print heap and garbage collection statistics after the run
+        #[arg(long)]
+        heap_stats: bool,
+
+        /// Write a heap snapshot for Chrome DevTools to the given path after
+        /// the run
+        #[arg(long, value_name = "PATH")]
+        heap_snapshot: Option<String>,
+
+        /// Cache compiled bytecode in the given directory
+        #[arg(long, value_name = "DIR")]
+        code_cache: Option<String>,
+
         #[arg(short, long)]
         no_strict: bool,
 
@@ -64,6 +89,7 @@ enum Command {
 #[derive(Default)]
 struct CliHostHooks {
     promise_job_queue: RefCell<VecDeque<Job>>,
+    code_cache: Option<FileCodeCache>,
 }
 
 // RefCell doesn't implement Debug
@@ -85,6 +111,12 @@ impl HostHooks for CliHostHooks {
     fn enqueue_promise_job(&self, job: Job) {
         self.promise_job_queue.borrow_mut().push_back(job);
     }
+
+    fn code_cache(&self) -> Option<&dyn CodeCache> {
+        self.code_cache
+            .as_ref()
+            .map(|code_cache| code_cache as &dyn CodeCache)
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -116,13 +148,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             verbose,
             no_strict,
             nogc,
+            gc_stress,
+            discard_parsed_code,
+            heap_stats,
+            heap_snapshot,
+            code_cache,
             paths,
         } => {
-            let host_hooks: &CliHostHooks = &*Box::leak(Box::default());
+            let code_cache = code_cache
+                .map(|dir| FileCodeCache::new(dir.into()))
+                .transpose()?;
+            let host_hooks: &CliHostHooks = &*Box::leak(Box::new(CliHostHooks {
+                code_cache,
+                ..Default::default()
+            }));
             let mut agent = GcAgent::new(
                 Options {
                     disable_gc: nogc,
                     print_internals: verbose,
+                    gc_stress,
+                    discard_parsed_code,
+                    ..Default::default()
                 },
                 host_hooks,
             );
@@ -198,6 +244,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     },
                 )?;
             }
+            if heap_stats {
+                print_heap_statistics(&agent.heap_statistics());
+            }
+            if let Some(path) = heap_snapshot {
+                agent.gc();
+                agent.write_heap_snapshot(std::fs::File::create(path)?)?;
+            }
             agent.remove_realm(realm);
         }
         Command::Repl {} => {
@@ -206,6 +259,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Options {
                     disable_gc: false,
                     print_internals: true,
+                    ..Default::default()
                 },
                 host_hooks,
             );
@@ -1050,7 +1050,7 @@ pub(crate) fn create_list_from_array_like(
     mut gc: GcScope,
 ) -> JsResult<Vec<Value>> {
     match obj {
-        Value::Array(array) => Ok(array
+        Value::Array(array) if array.has_slice(agent) => Ok(array
             .as_slice(agent)
             .iter()
             .map(|el| el.unwrap_or(Value::Undefined))
@@ -1680,15 +1680,16 @@ pub(crate) fn copy_data_properties(
     };
     // Reserve space in the target's vectors.
     {
-        let new_size = agent[target]
-            .keys
+        let shape = agent[target].shape;
+        let new_size = shape
+            .keys(agent)
             .len()
             .checked_add(u32::try_from(keys.len()).unwrap())
             .unwrap();
+        shape.reserve(&mut agent.heap, new_size);
         let Heap {
             elements, objects, ..
         } = &mut agent.heap;
-        objects[target].keys.reserve(elements, new_size);
         objects[target].values.reserve(elements, new_size);
     }
 
@@ -8,7 +8,7 @@ use crate::ecmascript::abstract_operations::type_conversion::to_numeric_primitiv
 use crate::ecmascript::builtins::proxy::abstract_operations::{
     validate_non_revoked_proxy, NonRevokedProxy,
 };
-use crate::ecmascript::types::{InternalSlots, Numeric, Primitive, PropertyKey};
+use crate::ecmascript::types::{Numeric, Primitive, PropertyKey};
 use crate::engine::context::{GcScope, NoGcScope};
 use crate::engine::TryResult;
 use crate::heap::WellKnownSymbolIndexes;
@@ -150,30 +150,30 @@ pub(crate) fn try_is_extensible(agent: &mut Agent, o: Object, gc: NoGcScope) ->
 /// argument (an ECMAScript language value) and returns either a normal completion containing a Boolean or a throw completion.
 pub(crate) fn is_reg_exp(agent: &mut Agent, argument: Value, gc: GcScope) -> JsResult<bool> {
     // 1. If argument is not an Object, return false.
-    if !argument.is_object() {
+    let Ok(argument) = Object::try_from(argument) else {
         return Ok(false);
-    }
+    };
+    #[cfg(feature = "regexp")]
+    let has_reg_exp_matcher = matches!(argument, Object::RegExp(_));
+    #[cfg(not(feature = "regexp"))]
+    let has_reg_exp_matcher = false;
 
     // 2. Let matcher be ? Get(argument, %Symbol.match%).
     let matcher = get(
         agent,
-        Object::internal_prototype(Object::try_from(argument).unwrap(), agent).unwrap(),
+        argument,
         PropertyKey::Symbol(WellKnownSymbolIndexes::Match.into()),
         gc,
     )?;
 
     // 3. If matcher is not undefined, return ToBoolean(matcher).
-    if matcher.is_undefined() {
+    if !matcher.is_undefined() {
         return Ok(to_boolean(agent, matcher));
     }
 
     // 4. If argument has a [[RegExpMatcher]] internal slot, return true.
-    if let Value::RegExp(_) = argument {
-        return Ok(true);
-    }
-
     // 5. Return false.
-    Ok(false)
+    Ok(has_reg_exp_matcher)
 }
 
 /// ### [7.2.5 IsExtensible ( O )](https://tc39.es/ecma262/#sec-isextensible-o)
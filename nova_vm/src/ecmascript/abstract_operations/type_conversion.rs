@@ -1192,7 +1192,7 @@ pub(crate) fn to_property_key<'a>(
 /// - Safe integet bigints
 /// - Negative zero
 /// - Stringified safe integers
-/// - Strings
+/// - Strings, interning rope strings in place
 /// - Symbols
 /// - undefined
 /// - null
@@ -1202,11 +1202,16 @@ pub(crate) fn to_property_key<'a>(
 /// If a complex case is found, the function returns None to indicate that the
 /// caller should handle the uncommon case.
 pub(crate) fn to_property_key_simple<'a>(
-    agent: &Agent,
+    agent: &mut Agent,
     argument: impl IntoValue,
-    _: NoGcScope<'a, '_>,
+    gc: NoGcScope<'a, '_>,
 ) -> TryResult<PropertyKey<'a>> {
-    let argument = argument.into_value();
+    let mut argument = argument.into_value();
+    if let Value::String(x) = argument {
+        // Property keys are compared by identity: Rope strings are flattened
+        // and interned in place.
+        argument = String::String(x).intern(agent, gc).into_value();
+    }
     match argument {
         Value::String(_) | Value::SmallString(_) => {
             let (str, string_key) = match &argument {
@@ -11,12 +11,13 @@ use crate::{
         execution::{Agent, RealmIdentifier},
         types::{
             BuiltinFunctionHeapData, IntoFunction, IntoObject, IntoValue, Object, ObjectHeapData,
-            OrdinaryObject, PropertyKey, String, Value, BUILTIN_STRING_MEMORY,
+            ObjectShapeRecord, OrdinaryObject, PropertyKey, String, Value, BUILTIN_STRING_MEMORY,
         },
     },
     heap::{
         element_array::ElementDescriptor,
         indexes::{BuiltinFunctionIndex, ObjectIndex},
+        CreateHeapData,
     },
 };
 
@@ -603,18 +604,14 @@ impl<'agent>
                 .function_prototype()
                 .into_object(),
         );
+        let shape = agent.heap.create(ObjectShapeRecord::new(prototype, keys));
         let slot = agent
             .heap
             .objects
             .get_mut(object_index.unwrap().get_index())
             .unwrap();
         assert!(slot.is_none());
-        *slot = Some(ObjectHeapData {
-            extensible: true,
-            prototype,
-            keys,
-            values,
-        });
+        *slot = Some(ObjectHeapData::new(true, shape, values));
 
         let data = BuiltinFunctionHeapData {
             object_index,
@@ -673,18 +670,14 @@ impl<'agent>
 
         let (keys, values) = agent.heap.elements.create_with_stuff(properties);
 
+        let shape = agent.heap.create(ObjectShapeRecord::new(prototype.0, keys));
         let slot = agent
             .heap
             .objects
             .get_mut(object_index.unwrap().get_index())
             .unwrap();
         assert!(slot.is_none());
-        *slot = Some(ObjectHeapData {
-            extensible: true,
-            prototype: prototype.0,
-            keys,
-            values,
-        });
+        *slot = Some(ObjectHeapData::new(true, shape, values));
 
         let data = BuiltinFunctionHeapData {
             object_index,
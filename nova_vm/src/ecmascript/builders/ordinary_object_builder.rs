@@ -7,11 +7,11 @@ use crate::{
         builtins::{Builtin, BuiltinFunction, BuiltinGetter, BuiltinIntrinsic},
         execution::{Agent, RealmIdentifier},
         types::{
-            IntoFunction, IntoObject, IntoValue, ObjectHeapData, OrdinaryObject, PropertyKey,
-            Value, BUILTIN_STRING_MEMORY,
+            IntoFunction, IntoObject, IntoValue, ObjectHeapData, ObjectShapeRecord, OrdinaryObject,
+            PropertyKey, Value, BUILTIN_STRING_MEMORY,
         },
     },
-    heap::{element_array::ElementDescriptor, indexes::ObjectIndex},
+    heap::{element_array::ElementDescriptor, indexes::ObjectIndex, CreateHeapData},
 };
 
 use super::{
@@ -263,6 +263,7 @@ impl<'agent, P> OrdinaryObjectBuilder<'agent, P, CreatorProperties> {
 impl<'agent> OrdinaryObjectBuilder<'agent, NoPrototype, NoProperties> {
     pub fn build(self) -> OrdinaryObject<'static> {
         let (keys, values) = self.agent.heap.elements.create_with_stuff(vec![]);
+        let shape = self.agent.heap.create(ObjectShapeRecord::new(None, keys));
         let slot = self
             .agent
             .heap
@@ -270,12 +271,7 @@ impl<'agent> OrdinaryObjectBuilder<'agent, NoPrototype, NoProperties> {
             .get_mut(self.this.get_index())
             .unwrap();
         assert!(slot.is_none());
-        *slot = Some(ObjectHeapData {
-            extensible: self.extensible,
-            prototype: None,
-            keys,
-            values,
-        });
+        *slot = Some(ObjectHeapData::new(self.extensible, shape, values));
         self.this
     }
 }
@@ -285,6 +281,10 @@ impl<'agent, T: IntoObject<'static>>
 {
     pub fn build(self) -> OrdinaryObject<'static> {
         let (keys, values) = self.agent.heap.elements.create_with_stuff(vec![]);
+        let shape = self.agent.heap.create(ObjectShapeRecord::new(
+            Some(self.prototype.0.into_object()),
+            keys,
+        ));
         let slot = self
             .agent
             .heap
@@ -292,12 +292,7 @@ impl<'agent, T: IntoObject<'static>>
             .get_mut(self.this.get_index())
             .unwrap();
         assert!(slot.is_none());
-        *slot = Some(ObjectHeapData {
-            extensible: self.extensible,
-            prototype: Some(self.prototype.0.into_object()),
-            keys,
-            values,
-        });
+        *slot = Some(ObjectHeapData::new(self.extensible, shape, values));
         self.this
     }
 }
@@ -321,6 +316,7 @@ impl<'agent> OrdinaryObjectBuilder<'agent, NoPrototype, CreatorProperties> {
             .heap
             .elements
             .create_with_stuff(self.properties.0);
+        let shape = self.agent.heap.create(ObjectShapeRecord::new(None, keys));
         let slot = self
             .agent
             .heap
@@ -328,12 +324,7 @@ impl<'agent> OrdinaryObjectBuilder<'agent, NoPrototype, CreatorProperties> {
             .get_mut(self.this.get_index())
             .unwrap();
         assert!(slot.is_none());
-        *slot = Some(ObjectHeapData {
-            extensible: self.extensible,
-            prototype: None,
-            keys,
-            values,
-        });
+        *slot = Some(ObjectHeapData::new(self.extensible, shape, values));
         self.this
     }
 }
@@ -359,6 +350,10 @@ impl<'agent, T: IntoObject<'static>>
             .heap
             .elements
             .create_with_stuff(self.properties.0);
+        let shape = self.agent.heap.create(ObjectShapeRecord::new(
+            Some(self.prototype.0.into_object()),
+            keys,
+        ));
         let slot = self
             .agent
             .heap
@@ -366,12 +361,7 @@ impl<'agent, T: IntoObject<'static>>
             .get_mut(self.this.get_index())
             .unwrap();
         assert!(slot.is_none());
-        *slot = Some(ObjectHeapData {
-            extensible: self.extensible,
-            prototype: Some(self.prototype.0.into_object()),
-            keys,
-            values,
-        });
+        *slot = Some(ObjectHeapData::new(self.extensible, shape, values));
         self.this
     }
 }
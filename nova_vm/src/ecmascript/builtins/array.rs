@@ -23,7 +23,7 @@ use crate::{
         },
         execution::{Agent, JsResult, ProtoIntrinsics},
         types::{
-            Function, InternalMethods, InternalSlots, IntoObject, IntoValue, Object,
+            Function, InternalMethods, InternalSlots, IntoObject, IntoValue, Number, Object,
             OrdinaryObject, PropertyDescriptor, PropertyKey, Value, BUILTIN_STRING_MEMORY,
         },
     },
@@ -33,9 +33,10 @@ use crate::{
         unwrap_try, Scoped, TryResult,
     },
     heap::{
-        element_array::{ElementArrays, ElementDescriptor},
+        element_array::{ElementArrays, ElementDescriptor, ElementsVector},
         indexes::ArrayIndex,
-        CreateHeapData, Heap, HeapMarkAndSweep, WorkQueues,
+        CreateHeapData, Heap, HeapMarkAndSweep, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WorkQueues,
     },
 };
 
@@ -120,6 +121,26 @@ impl<'a> Array<'a> {
         agent[self].elements.is_trivial(agent)
     }
 
+    /// An array has a slice of element values unless its elements are stored
+    /// sparsely or as raw doubles. Arrays without a slice are never simple,
+    /// trivial or dense.
+    pub(crate) fn has_slice(self, agent: &impl Index<Array<'a>, Output = ArrayHeapData>) -> bool {
+        agent[self].elements.has_slice()
+    }
+
+    /// Move double elements into generic element storage. This is done
+    /// before anything but a plain number element is stored in the array.
+    pub(crate) fn make_elements_generic(self, agent: &mut Agent) {
+        let mut elements: ElementsVector = agent[self].elements.into();
+        if !elements.is_float64() {
+            return;
+        }
+        agent.heap.make_elements_generic(&mut elements);
+        let array_elements = &mut agent[self].elements;
+        array_elements.cap = elements.cap;
+        array_elements.elements_index = elements.elements_index;
+    }
+
     // This method creates a "shallow clone" of the elements of a simple array (no descriptors).
     // If array is not simple, this cloned array will do some odd things (e.g. getter/setter indexes become holes)
     pub(crate) fn to_cloned(self, agent: &mut Agent) -> Self {
@@ -177,17 +198,54 @@ impl<'a> Array<'a> {
         }
     }
 
+    /// Get the value of an element. Holes and accessor properties have no
+    /// value.
+    #[inline]
+    pub(crate) fn get_element_value(self, agent: &mut Agent, index: u32) -> Option<Value> {
+        let elements = agent[self].elements;
+        agent.heap.get_element_value(elements.into(), index)
+    }
+
+    /// Panics if the array is sparse or has double elements.
     #[inline]
     pub(crate) fn as_slice(self, arena: &impl ArrayHeapIndexable<'a>) -> &[Option<Value>] {
         let elements = arena[self].elements;
         &arena.as_ref()[elements]
     }
 
+    /// Panics if the array is sparse or has double elements.
     #[inline]
     pub(crate) fn as_mut_slice(self, agent: &mut Agent) -> &mut [Option<Value>] {
         let elements = agent[self].elements;
         &mut agent[elements]
     }
+
+    /// Get the elements of an array with double elements and no holes.
+    #[inline]
+    pub(crate) fn as_f64_slice(self, arena: &impl ArrayHeapIndexable<'a>) -> Option<&[f64]> {
+        let elements = arena[self].elements;
+        if !elements.is_float64() {
+            return None;
+        }
+        arena.as_ref().get_f64_slice(elements.into())
+    }
+
+    /// Panics if the array does not have double elements. Holes are padded
+    /// up to the array's length and can be moved but not read.
+    #[inline]
+    pub(crate) fn as_mut_f64_slice(self, agent: &mut Agent) -> &mut [f64] {
+        let elements = agent[self].elements;
+        agent.heap.elements.get_f64_slice_mut(elements.into())
+    }
+
+    /// Remove an element of an array with double elements, shifting all
+    /// following elements down by one.
+    pub(crate) fn remove_f64_element(self, agent: &mut Agent, index: u32) {
+        let mut elements: ElementsVector = agent[self].elements.into();
+        debug_assert!(elements.is_float64());
+        elements.remove(&mut agent.heap.elements, index as usize);
+        agent[self].elements.len = elements.len();
+    }
 }
 
 impl IntoValue for Array<'_> {
@@ -312,10 +370,8 @@ impl<'a> InternalMethods<'a> for Array<'a> {
                 return TryResult::Continue(None);
             }
             let elements = elements.into();
-            let index = index as usize;
-            // We checked that we're within the vector bounds.
-            let value = *agent.heap.elements.get(elements).get(index).unwrap();
-            let descriptor = agent.heap.elements.get_descriptor(elements, index);
+            let value = agent.heap.get_element_value(elements, index);
+            let descriptor = agent.heap.elements.get_descriptor(elements, index as usize);
             return if value.is_none() && descriptor.is_none() {
                 TryResult::Continue(None)
             } else {
@@ -372,12 +428,23 @@ impl<'a> InternalMethods<'a> for Array<'a> {
             // b. Assert: IsDataDescriptor(lengthDesc) is true.
             // c. Assert: lengthDesc.[[Configurable]] is false.
             // d. Let length be lengthDesc.[[Value]].
-            let elements = agent[self].elements;
+            let mut elements = agent[self].elements;
             let length = elements.len();
             let length_writable = elements.len_writable;
             // e. Assert: length is a non-negative integral Number.
             // f. Let index be ! ToUint32(P).
             let index = index as u32;
+            if elements.is_float64() {
+                if let Some(succeeded) =
+                    try_define_float64_element(agent, self, index, &property_descriptor)
+                {
+                    return TryResult::Continue(succeeded);
+                }
+                // Anything but a plain number element makes the elements
+                // generic.
+                self.make_elements_generic(agent);
+                elements = agent[self].elements;
+            }
             if index >= length {
                 // g. If index ≥ length and lengthDesc.[[Writable]] is false, return false.
                 if !length_writable {
@@ -387,7 +454,9 @@ impl<'a> InternalMethods<'a> for Array<'a> {
                     elements, arrays, ..
                 } = &mut agent.heap;
                 let array_heap_data = &mut arrays[self];
-                array_heap_data.elements.reserve(elements, index + 1);
+                array_heap_data
+                    .elements
+                    .reserve_with_holes(elements, index + 1);
                 let value = property_descriptor.value;
                 let element_descriptor =
                     ElementDescriptor::from_property_descriptor(property_descriptor);
@@ -407,13 +476,22 @@ impl<'a> InternalMethods<'a> for Array<'a> {
                 TryResult::Continue(true)
             } else {
                 // h. Let succeeded be ! OrdinaryDefineOwnProperty(A, P, Desc).
-                return TryResult::Continue(ordinary_define_own_property_for_array(
+                let succeeded = ordinary_define_own_property_for_array(
                     agent,
                     elements,
                     index,
                     property_descriptor,
                     gc,
-                ));
+                );
+                if elements.is_sparse() {
+                    // Filling in holes may have made the elements dense
+                    // enough to go back to a dense backing store.
+                    let Heap {
+                        elements, arrays, ..
+                    } = &mut agent.heap;
+                    arrays[self].elements.densify(elements);
+                }
+                TryResult::Continue(succeeded)
             }
         } else {
             let backing_object = self
@@ -530,22 +608,19 @@ impl<'a> InternalMethods<'a> for Array<'a> {
                     TryResult::Continue(Value::Undefined)
                 };
             }
-            // Index has been checked to be between 0 <= idx < len; indexing should never fail.
-            let element = agent[elements][index as usize];
+            let element = agent.heap.get_element_value(elements.into(), index);
             if let Some(element) = element {
                 TryResult::Continue(element)
             } else {
-                let (descriptors, _) = agent
+                let descriptor = agent
                     .heap
                     .elements
-                    .get_descriptors_and_slice(elements.into());
-                if let Some(descriptors) = descriptors {
-                    if let Some(descriptor) = descriptors.get(&index) {
-                        if let Some(_getter) = descriptor.getter_function(gc) {
-                            // 7. Return ? Call(getter, Receiver).
-                            // return call_function(agent, getter, receiver, None, gc);
-                            return TryResult::Break(());
-                        }
+                    .get_descriptor(elements.into(), index as usize);
+                if let Some(descriptor) = descriptor {
+                    if let Some(_getter) = descriptor.getter_function(gc) {
+                        // 7. Return ? Call(getter, Receiver).
+                        // return call_function(agent, getter, receiver, None, gc);
+                        return TryResult::Break(());
                     }
                 }
                 if let Some(prototype) = self.internal_prototype(agent) {
@@ -592,21 +667,18 @@ impl<'a> InternalMethods<'a> for Array<'a> {
                     Ok(Value::Undefined)
                 };
             }
-            // Index has been checked to be between 0 <= idx < len; indexing should never fail.
-            let element = agent[elements][index as usize];
+            let element = agent.heap.get_element_value(elements.into(), index);
             if let Some(element) = element {
                 Ok(element)
             } else {
-                let (descriptors, _) = agent
+                let descriptor = agent
                     .heap
                     .elements
-                    .get_descriptors_and_slice(elements.into());
-                if let Some(descriptors) = descriptors {
-                    if let Some(descriptor) = descriptors.get(&index) {
-                        if let Some(getter) = descriptor.getter_function(gc.nogc()) {
-                            // 7. Return ? Call(getter, Receiver).
-                            return call_function(agent, getter.unbind(), receiver, None, gc);
-                        }
+                    .get_descriptor(elements.into(), index as usize);
+                if let Some(descriptor) = descriptor {
+                    if let Some(getter) = descriptor.getter_function(gc.nogc()) {
+                        // 7. Return ? Call(getter, Receiver).
+                        return call_function(agent, getter.unbind(), receiver, None, gc);
                     }
                 }
                 if let Some(prototype) = self.internal_prototype(agent) {
@@ -644,21 +716,18 @@ impl<'a> InternalMethods<'a> for Array<'a> {
             if index >= elements.len() {
                 return TryResult::Continue(true);
             }
-            let (descriptors, slice) = agent
-                .heap
-                .elements
-                .get_descriptors_and_slice_mut(elements.into());
-            if let Some(descriptors) = descriptors {
-                if let Some(descriptor) = descriptors.get(&index) {
-                    if !descriptor.is_configurable() {
-                        // Unconfigurable property.
-                        return TryResult::Continue(false);
-                    }
-                    descriptors.remove(&index);
+            let elements = elements.into();
+            if let Some(descriptor) = agent.heap.elements.get_descriptor(elements, index as usize) {
+                if !descriptor.is_configurable() {
+                    // Unconfigurable property.
+                    return TryResult::Continue(false);
                 }
+                agent
+                    .heap
+                    .elements
+                    .set_descriptor(elements, index as usize, None);
             }
-            // Index has been checked to be between 0 <= idx < len; indexing should never fail.
-            slice[index as usize] = None;
+            agent.heap.elements.set_value(elements, index, None);
             TryResult::Continue(true)
         } else {
             TryResult::Continue(
@@ -682,15 +751,32 @@ impl<'a> InternalMethods<'a> for Array<'a> {
             Default::default()
         };
         let elements = agent[self].elements;
-        let mut keys = Vec::with_capacity(elements.len() as usize + backing_keys.len());
+        let mut keys = if !elements.has_slice() {
+            // Sparse arrays can be huge: Only visit the elements present.
+            let indexes = if elements.is_sparse() {
+                agent.heap.elements.sparse[elements.elements_index].keys()
+            } else {
+                agent.heap.elements.float64[elements.elements_index].keys()
+            };
+            let mut keys = Vec::with_capacity(indexes.len() + backing_keys.len());
+            keys.extend(
+                indexes
+                    .into_iter()
+                    .map(|index| PropertyKey::Integer(index.into())),
+            );
+            keys
+        } else {
+            let mut keys = Vec::with_capacity(elements.len() as usize + backing_keys.len());
 
-        let elements_data = &agent[elements];
+            let elements_data = &agent[elements];
 
-        for (index, value) in elements_data.iter().enumerate() {
-            if value.is_some() {
-                keys.push(PropertyKey::Integer((index as u32).into()))
+            for (index, value) in elements_data.iter().enumerate() {
+                if value.is_some() {
+                    keys.push(PropertyKey::Integer((index as u32).into()))
+                }
             }
-        }
+            keys
+        };
 
         keys.extend(backing_keys);
 
@@ -772,6 +858,70 @@ impl HeapMarkAndSweep for Array<'static> {
     }
 }
 
+impl SnapshotData for Array<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
+/// Define an element of an array with double elements without making the
+/// elements generic. Returns `None` if the descriptor does not describe a
+/// writable, enumerable and configurable number data element, or if the
+/// elements would have to grow sparse.
+fn try_define_float64_element(
+    agent: &mut Agent,
+    array: Array,
+    index: u32,
+    descriptor: &PropertyDescriptor,
+) -> Option<bool> {
+    if descriptor.get.is_some() || descriptor.set.is_some() {
+        return None;
+    }
+    let value = match descriptor.value {
+        Some(value) => Some(Number::try_from(value).ok()?.into_f64(agent)),
+        None => None,
+    };
+    let elements: ElementsVector = agent[array].elements.into();
+    let attributes = [
+        descriptor.writable,
+        descriptor.enumerable,
+        descriptor.configurable,
+    ];
+    if agent.heap.elements.get_f64(elements, index).is_some() {
+        // Double elements are writable, enumerable and configurable.
+        if attributes.contains(&Some(false)) {
+            return None;
+        }
+        if let Some(value) = value {
+            agent.heap.elements.set_f64(elements, index, Some(value));
+        }
+        return Some(true);
+    }
+    // New elements default to unwritable, unenumerable and unconfigurable,
+    // and to an undefined value.
+    let value = value?;
+    if attributes.iter().any(|attribute| *attribute != Some(true)) {
+        return None;
+    }
+    // The array is not extensible, or cannot grow past its length.
+    if !agent[array].elements.writable() {
+        return Some(false);
+    }
+    if index >= elements.len() {
+        if elements.should_grow_sparse(index + 1) {
+            return None;
+        }
+        agent[array].elements.len = index + 1;
+    }
+    let elements = agent[array].elements.into();
+    agent.heap.elements.set_f64(elements, index, Some(value));
+    Some(true)
+}
+
 fn ordinary_define_own_property_for_array(
     agent: &mut Agent,
     elements: SealableElementsVector,
@@ -781,13 +931,12 @@ fn ordinary_define_own_property_for_array(
 ) -> bool {
     let descriptor_value = descriptor.value;
 
-    let (descriptors, slice) = agent
-        .heap
-        .elements
-        .get_descriptors_and_slice(elements.into());
-    let current_value = slice[index as usize];
+    let current_value = agent.heap.elements.get_value(elements.into(), index);
     let current_descriptor = {
-        let descriptor = descriptors.and_then(|descriptors| descriptors.get(&index).copied());
+        let descriptor = agent
+            .heap
+            .elements
+            .get_descriptor(elements.into(), index as usize);
         if current_value.is_some() && descriptor.is_none() {
             Some(ElementDescriptor::WritableEnumerableConfigurableData)
         } else {
@@ -810,20 +959,12 @@ fn ordinary_define_own_property_for_array(
             //    [[Enumerable]], and [[Configurable]] attributes are set to the value of the
             //    corresponding field in Desc if Desc has that field, or to the attribute's default
             //    value otherwise.
-            let (descriptors, _) = agent
-                .heap
-                .elements
-                .get_descriptors_and_slice_mut(elements.into());
             let elem_descriptor = ElementDescriptor::from_property_descriptor(descriptor).unwrap();
-            if let Some(descriptors) = descriptors {
-                descriptors.insert(index, elem_descriptor);
-            } else {
-                agent.heap.elements.set_descriptor(
-                    elements.into(),
-                    index as usize,
-                    Some(elem_descriptor),
-                )
-            }
+            agent.heap.elements.set_descriptor(
+                elements.into(),
+                index as usize,
+                Some(elem_descriptor),
+            );
         }
         // d. Else,
         else {
@@ -831,23 +972,16 @@ fn ordinary_define_own_property_for_array(
             //    [[Enumerable]], and [[Configurable]] attributes are set to the value of the
             //    corresponding field in Desc if Desc has that field, or to the attribute's default
             //    value otherwise.
-            let (descriptors, slice) = agent
-                .heap
-                .elements
-                .get_descriptors_and_slice_mut(elements.into());
-            slice[index as usize] = Some(descriptor_value.unwrap_or(Value::Undefined));
-            let elem_descriptor = ElementDescriptor::from_property_descriptor(descriptor);
-            if let Some(descriptor) = elem_descriptor {
-                if let Some(descriptors) = descriptors {
-                    descriptors.insert(index, descriptor);
-                } else {
-                    agent.heap.elements.set_descriptor(
-                        elements.into(),
-                        index as usize,
-                        Some(descriptor),
-                    )
-                }
-            }
+            agent.heap.elements.set_value(
+                elements.into(),
+                index,
+                Some(descriptor_value.unwrap_or(Value::Undefined)),
+            );
+            agent.heap.elements.set_descriptor(
+                elements.into(),
+                index as usize,
+                ElementDescriptor::from_property_descriptor(descriptor),
+            );
         }
 
         // e. Return true.
@@ -951,20 +1085,11 @@ fn ordinary_define_own_property_for_array(
                 ElementDescriptor::new_with_get_set_ec(get, set, enumerable, configurable)
             }
         };
-        let (descriptors, slice) = agent
+        agent.heap.elements.set_value(elements.into(), index, None);
+        agent
             .heap
             .elements
-            .get_descriptors_and_slice_mut(elements.into());
-        slice[index as usize] = None;
-        if let Some(descriptors) = descriptors {
-            descriptors.insert(index, new_descriptor);
-        } else {
-            agent.heap.elements.set_descriptor(
-                elements.into(),
-                index as usize,
-                Some(new_descriptor),
-            )
-        }
+            .set_descriptor(elements.into(), index as usize, Some(new_descriptor));
     }
     // b. Else if IsAccessorDescriptor(current) is true and IsDataDescriptor(Desc) is true, then
     else if current_is_accessor_descriptor && descriptor.is_data_descriptor() {
@@ -987,20 +1112,20 @@ fn ordinary_define_own_property_for_array(
         //     .enumerable = enumerable,
         //     .configurable = configurable,
         // });
-        let (descriptors, slice) = agent
-            .heap
-            .elements
-            .get_descriptors_and_slice_mut(elements.into());
-        if let Some(elem_descriptor) = ElementDescriptor::new_with_wec(
+        let elem_descriptor = ElementDescriptor::new_with_wec(
             descriptor.writable.unwrap_or(false),
             enumerable,
             configurable,
-        ) {
-            descriptors.unwrap().insert(index, elem_descriptor);
-        } else {
-            descriptors.unwrap().remove(&index);
-        }
-        slice[index as usize] = Some(descriptor.value.unwrap_or(Value::Undefined));
+        );
+        agent
+            .heap
+            .elements
+            .set_descriptor(elements.into(), index as usize, elem_descriptor);
+        agent.heap.elements.set_value(
+            elements.into(),
+            index,
+            Some(descriptor.value.unwrap_or(Value::Undefined)),
+        );
     }
     // c. Else,
     else {
@@ -1013,24 +1138,15 @@ fn ordinary_define_own_property_for_array(
         descriptor.set = descriptor.set.or(current_setter).map(Function::unbind);
         descriptor.enumerable = Some(descriptor.enumerable.unwrap_or(current_enumerable));
         descriptor.configurable = Some(descriptor.configurable.unwrap_or(current_configurable));
-        let (descriptors, slice) = agent
+        agent
             .heap
             .elements
-            .get_descriptors_and_slice_mut(elements.into());
-        slice[index as usize] = result_value;
-        if let Some(elem_descriptor) = ElementDescriptor::from_property_descriptor(descriptor) {
-            if let Some(descriptors) = descriptors {
-                descriptors.insert(index, elem_descriptor);
-            } else {
-                agent.heap.elements.set_descriptor(
-                    elements.into(),
-                    index as usize,
-                    Some(elem_descriptor),
-                )
-            }
-        } else if let Some(descriptors) = descriptors {
-            descriptors.remove(&index);
-        }
+            .set_value(elements.into(), index, result_value);
+        agent.heap.elements.set_descriptor(
+            elements.into(),
+            index as usize,
+            ElementDescriptor::from_property_descriptor(descriptor),
+        );
     }
 
     true
@@ -16,7 +16,7 @@ use crate::{
         execution::{agent::ExceptionType, Agent, JsResult},
         types::{IntoObject, Number, Object, PropertyDescriptor, Value, BUILTIN_STRING_MEMORY},
     },
-    heap::{indexes::ArrayIndex, Heap, WellKnownSymbolIndexes},
+    heap::{element_array::ElementsVector, indexes::ArrayIndex, Heap, WellKnownSymbolIndexes},
 };
 
 use super::{data::SealableElementsVector, Array, ArrayHeapData};
@@ -27,6 +27,11 @@ use super::{data::SealableElementsVector, Array, ArrayHeapData};
 /// integer) and optional argument proto (an Object) and returns either a
 /// normal completion containing an Array exotic object or a throw completion.
 /// It is used to specify the creation of new Arrays.
+///
+/// The `capacity` is the number of elements the caller expects to fill in:
+/// Long arrays expected to stay mostly holes get sparse element storage.
+/// Other arrays of only holes, as created by `new Array(n)`, are usually
+/// filled in with numbers and start out with double element storage.
 pub(crate) fn array_create<'a>(
     agent: &mut Agent,
     length: usize,
@@ -42,6 +47,7 @@ pub(crate) fn array_create<'a>(
             gc,
         ));
     }
+    agent.check_heap_limit(capacity.saturating_mul(std::mem::size_of::<Option<Value>>()))?;
     // 2. If proto is not present, set proto to %Array.prototype%.
     let object_index = if let Some(proto) = proto {
         if proto
@@ -60,10 +66,16 @@ pub(crate) fn array_create<'a>(
     };
     // 3. Let A be MakeBasicObject(« [[Prototype]], [[Extensible]] »).
     // 5. Set A.[[DefineOwnProperty]] as specified in 10.4.2.1.
-    let mut elements = agent
-        .heap
-        .elements
-        .allocate_elements_with_capacity(capacity);
+    let mut elements = if ElementsVector::should_be_sparse(length as u32, capacity as u32) {
+        agent.heap.elements.allocate_sparse_elements(length as u32)
+    } else if capacity == 0 && length > 0 {
+        agent.heap.elements.allocate_float64_elements(length as u32)
+    } else {
+        agent
+            .heap
+            .elements
+            .allocate_elements_with_capacity(capacity.max(length))
+    };
     elements.len = length as u32;
     let data = ArrayHeapData {
         // 4. Set A.[[Prototype]] to proto.
@@ -211,6 +223,11 @@ pub(crate) fn array_set_length(
     let gc = gc.into_nogc();
     let a = a.get(agent).bind(gc);
     // 6. Set newLenDesc.[[Value]] to newLen.
+    let current_elements: ElementsVector = agent[a].elements.into();
+    if current_elements.is_float64() && current_elements.should_grow_sparse(new_len) {
+        // Double elements cannot be stored sparsely.
+        a.make_elements_generic(agent);
+    }
     // 7. Let oldLenDesc be OrdinaryGetOwnProperty(A, "length").
     let Heap {
         arrays, elements, ..
@@ -233,7 +250,9 @@ pub(crate) fn array_set_length(
     // 11. If newLen ≥ oldLen, then
     if new_len >= old_len {
         // a. Return ! OrdinaryDefineOwnProperty(A, "length", newLenDesc).
-        array_heap_data.elements.reserve(elements, new_len);
+        array_heap_data
+            .elements
+            .reserve_with_holes(elements, new_len);
         array_heap_data.elements.len = new_len;
         array_heap_data.elements.len_writable = new_len_writable;
         return Ok(true);
@@ -243,6 +262,15 @@ pub(crate) fn array_set_length(
     array_heap_data.elements.len = new_len;
     // 17. For each own property key P of A such that P is an array index and ! ToUint32(P) ≥ newLen, in descending numeric index order, do
     debug_assert!(old_len > new_len);
+    if !old_elements.has_slice() {
+        // Sparse elements may number in the billions and double elements have
+        // no descriptors: Drop them all at once. If a non-configurable sparse
+        // element is in the way, the truncation stops and the length is left
+        // one past it. Steps 17.b.i-iv.
+        let delete_succeeded = array_heap_data.elements.truncate_unsliced(elements);
+        array_heap_data.elements.len_writable &= new_len_writable;
+        return Ok(delete_succeeded);
+    }
     for i in new_len + 1..old_len {
         // a. Let deleteSucceeded be ! A.[[Delete]](P).
         let elements = &mut elements[old_elements];
@@ -315,6 +343,11 @@ pub(crate) fn array_try_set_length(
         return TryResult::Break(());
     }
     // 6. Set newLenDesc.[[Value]] to newLen.
+    let current_elements: ElementsVector = agent[a].elements.into();
+    if current_elements.is_float64() && current_elements.should_grow_sparse(new_len) {
+        // Double elements cannot be stored sparsely.
+        a.make_elements_generic(agent);
+    }
     // 7. Let oldLenDesc be OrdinaryGetOwnProperty(A, "length").
     let Heap {
         arrays, elements, ..
@@ -337,7 +370,9 @@ pub(crate) fn array_try_set_length(
     // 11. If newLen ≥ oldLen, then
     if new_len >= old_len {
         // a. Return ! OrdinaryDefineOwnProperty(A, "length", newLenDesc).
-        array_heap_data.elements.reserve(elements, new_len);
+        array_heap_data
+            .elements
+            .reserve_with_holes(elements, new_len);
         array_heap_data.elements.len = new_len;
         array_heap_data.elements.len_writable = new_len_writable;
         return TryResult::Continue(true);
@@ -347,6 +382,15 @@ pub(crate) fn array_try_set_length(
     array_heap_data.elements.len = new_len;
     // 17. For each own property key P of A such that P is an array index and ! ToUint32(P) ≥ newLen, in descending numeric index order, do
     debug_assert!(old_len > new_len);
+    if !old_elements.has_slice() {
+        // Sparse elements may number in the billions and double elements have
+        // no descriptors: Drop them all at once. If a non-configurable sparse
+        // element is in the way, the truncation stops and the length is left
+        // one past it. Steps 17.b.i-iv.
+        let delete_succeeded = array_heap_data.elements.truncate_unsliced(elements);
+        array_heap_data.elements.len_writable &= new_len_writable;
+        return TryResult::Continue(delete_succeeded);
+    }
     for i in new_len + 1..old_len {
         // a. Let deleteSucceeded be ! A.[[Delete]](P).
         let elements = &mut elements[old_elements];
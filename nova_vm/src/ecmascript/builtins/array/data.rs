@@ -7,7 +7,8 @@ use crate::{
     heap::{
         element_array::{ElementArrayKey, ElementArrays, ElementDescriptor, ElementsVector},
         indexes::ElementIndex,
-        CompactionLists, HeapMarkAndSweep, WorkQueues,
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WorkQueues,
     },
 };
 
@@ -42,6 +43,23 @@ impl SealableElementsVector {
         self.len_writable
     }
 
+    /// A sealable elements vector is sparse if its elements are stored in a
+    /// map instead of a dense backing store.
+    pub(crate) fn is_sparse(&self) -> bool {
+        self.cap == ElementArrayKey::Sparse
+    }
+
+    /// A sealable elements vector is double if its elements are numbers
+    /// stored as raw doubles.
+    pub(crate) fn is_float64(&self) -> bool {
+        self.cap == ElementArrayKey::Float64
+    }
+
+    /// Sparse and double sealable elements vectors have no slice of values.
+    pub(crate) fn has_slice(&self) -> bool {
+        !self.is_sparse() && !self.is_float64()
+    }
+
     /// A sealable elements vector is simple if it contains no accessor descriptors.
     pub(crate) fn is_simple(&self, agent: &impl AsRef<ElementArrays>) -> bool {
         let elements_vector: ElementsVector = (*self).into();
@@ -87,6 +105,29 @@ impl SealableElementsVector {
         self.len = elements_vector.len;
         self.elements_index = elements_vector.elements_index;
     }
+
+    pub(crate) fn reserve_with_holes(&mut self, elements: &mut ElementArrays, new_len: u32) {
+        let mut elements_vector: ElementsVector = (*self).into();
+        elements_vector.reserve_with_holes(elements, new_len);
+        self.cap = elements_vector.cap;
+        self.elements_index = elements_vector.elements_index;
+    }
+
+    pub(crate) fn densify(&mut self, elements: &mut ElementArrays) {
+        let mut elements_vector: ElementsVector = (*self).into();
+        elements_vector.densify(elements);
+        self.cap = elements_vector.cap;
+        self.elements_index = elements_vector.elements_index;
+    }
+
+    pub(crate) fn truncate_unsliced(&mut self, elements: &mut ElementArrays) -> bool {
+        let mut elements_vector: ElementsVector = (*self).into();
+        let deleted_all = elements_vector.truncate_unsliced(elements);
+        self.len = elements_vector.len;
+        self.cap = elements_vector.cap;
+        self.elements_index = elements_vector.elements_index;
+        deleted_all
+    }
 }
 
 impl Default for SealableElementsVector {
@@ -138,6 +179,30 @@ impl HeapMarkAndSweep for SealableElementsVector {
     }
 }
 
+impl SnapshotData for SealableElementsVector {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            elements_index,
+            cap,
+            len,
+            len_writable,
+        } = self;
+        elements_index.serialize(writer)?;
+        cap.serialize(writer)?;
+        len.serialize(writer)?;
+        len_writable.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            elements_index: SnapshotData::deserialize(reader)?,
+            cap: SnapshotData::deserialize(reader)?,
+            len: SnapshotData::deserialize(reader)?,
+            len_writable: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
+
 impl HeapMarkAndSweep for ArrayHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
@@ -157,3 +222,21 @@ impl HeapMarkAndSweep for ArrayHeapData {
         elements.sweep_values(compactions);
     }
 }
+
+impl SnapshotData for ArrayHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            object_index,
+            elements,
+        } = self;
+        object_index.serialize(writer)?;
+        elements.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            object_index: SnapshotData::deserialize(reader)?,
+            elements: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
@@ -20,7 +20,7 @@ use crate::{
     },
     heap::{
         indexes::{ArrayBufferIndex, IntoBaseIndex},
-        CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, WorkQueues,
+        CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, OwnedAllocations, WorkQueues,
     },
 };
 
@@ -286,6 +286,7 @@ impl HeapMarkAndSweep for ArrayBuffer<'static> {
 
 impl CreateHeapData<ArrayBufferHeapData, ArrayBuffer<'static>> for Heap {
     fn create(&mut self, data: ArrayBufferHeapData) -> ArrayBuffer<'static> {
+        self.owned_bytes += data.owned_bytes();
         self.array_buffers.push(Some(data));
         ArrayBuffer::from(ArrayBufferIndex::last(&self.array_buffers))
     }
@@ -15,7 +15,7 @@ use crate::{
             BUILTIN_STRING_MEMORY,
         },
     },
-    heap::indexes::ArrayBufferIndex,
+    heap::CreateHeapData,
     Heap,
 };
 
@@ -68,6 +68,7 @@ pub(crate) fn allocate_array_buffer<'a>(
     //      a. If it is not possible to create a Data Block block consisting of maxByteLength bytes, throw a RangeError exception.
     //      b. NOTE: Resizable ArrayBuffers are designed to be implementable with in-place growth. Implementations may throw if, for example, virtual memory cannot be reserved up front.
     //      c. Set obj.[[ArrayBufferMaxByteLength]] to maxByteLength.
+    agent.check_heap_limit(usize::try_from(byte_length).unwrap_or(usize::MAX))?;
     let block = DataBlock::create_byte_data_block(agent, byte_length, gc)?;
     // 6. Set obj.[[ArrayBufferData]] to block.
     // 7. Set obj.[[ArrayBufferByteLength]] to byteLength.
@@ -76,11 +77,8 @@ pub(crate) fn allocate_array_buffer<'a>(
     } else {
         ArrayBufferHeapData::new_fixed_length(block)
     };
-    agent.heap.array_buffers.push(Some(obj));
     // 9. Return obj.
-    Ok(ArrayBuffer(ArrayBufferIndex::last(
-        &agent.heap.array_buffers,
-    )))
+    Ok(agent.heap.create(obj))
 }
 
 /// ### [25.1.3.2 ArrayBufferByteLength ( arrayBuffer, order )](https://tc39.es/ecma262/#sec-arraybufferbytelength)
@@ -4,7 +4,7 @@
 
 use crate::{
     ecmascript::types::{DataBlock, OrdinaryObject},
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{CompactionLists, HeapMarkAndSweep, OwnedAllocations, WorkQueues},
 };
 
 // TODO: Investigate if the common case is that the byte length is less than
@@ -228,6 +228,12 @@ impl ArrayBufferHeapData {
     }
 }
 
+impl OwnedAllocations for ArrayBufferHeapData {
+    fn owned_bytes(&self) -> usize {
+        self.buffer.data_block.len()
+    }
+}
+
 impl HeapMarkAndSweep for ArrayBufferHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
@@ -26,7 +26,7 @@ use crate::{
     },
     heap::{
         indexes::BoundFunctionIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep,
-        WorkQueues,
+        SnapshotData, SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
     },
 };
 
@@ -459,6 +459,16 @@ impl HeapMarkAndSweep for BoundFunction<'static> {
     }
 }
 
+impl SnapshotData for BoundFunction<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl HeapMarkAndSweep for BoundFunctionHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
@@ -492,3 +502,33 @@ impl HeapMarkAndSweep for BoundFunctionHeapData {
         bound_arguments.sweep_values(compactions);
     }
 }
+
+impl SnapshotData for BoundFunctionHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            object_index,
+            length,
+            bound_target_function,
+            bound_this,
+            bound_arguments,
+            name,
+        } = self;
+        object_index.serialize(writer)?;
+        length.serialize(writer)?;
+        bound_target_function.serialize(writer)?;
+        bound_this.serialize(writer)?;
+        bound_arguments.serialize(writer)?;
+        name.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            object_index: SnapshotData::deserialize(reader)?,
+            length: SnapshotData::deserialize(reader)?,
+            bound_target_function: SnapshotData::deserialize(reader)?,
+            bound_this: SnapshotData::deserialize(reader)?,
+            bound_arguments: SnapshotData::deserialize(reader)?,
+            name: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
@@ -391,6 +391,7 @@ fn builtin_call_or_construct<'a>(
     new_target: Function,
     gc: GcScope<'a, '_>,
 ) -> JsResult<Object<'a>> {
+    agent.check_call_depth(true, gc.nogc())?;
     let f = f.bind(gc.nogc());
     // 1. Let callerContext be the running execution context.
     let caller_context = agent.running_execution_context();
@@ -26,7 +26,8 @@ use crate::{
     heap::{
         indexes::BuiltinFunctionIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep,
         IntrinsicConstructorIndexes, IntrinsicFunctionIndexes, ObjectEntry,
-        ObjectEntryPropertyDescriptor, WorkQueues,
+        ObjectEntryPropertyDescriptor, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WorkQueues,
     },
 };
 
@@ -424,6 +425,7 @@ pub(crate) fn builtin_call_or_construct(
     new_target: Option<Function>,
     gc: GcScope,
 ) -> JsResult<Value> {
+    agent.check_call_depth(true, gc.nogc())?;
     let f = f.bind(gc.nogc());
     let new_target = new_target.map(|f| f.bind(gc.nogc()));
     // 1. Let callerContext be the running execution context.
@@ -638,6 +640,16 @@ impl HeapMarkAndSweep for BuiltinFunction<'static> {
     }
 }
 
+impl SnapshotData for BuiltinFunction<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl HeapMarkAndSweep for BuiltinFunctionHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
@@ -665,3 +677,42 @@ impl HeapMarkAndSweep for BuiltinFunctionHeapData {
         object_index.sweep_values(compactions);
     }
 }
+
+impl SnapshotData for Behaviour {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        // Function pointers are written as their stable builtin index.
+        writer.builtin_index(*self)?.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let index = u32::deserialize(reader)?;
+        reader.builtin(index)
+    }
+}
+
+impl SnapshotData for BuiltinFunctionHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            object_index,
+            length,
+            realm,
+            initial_name,
+            behaviour,
+        } = self;
+        object_index.serialize(writer)?;
+        length.serialize(writer)?;
+        realm.serialize(writer)?;
+        initial_name.serialize(writer)?;
+        behaviour.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            object_index: SnapshotData::deserialize(reader)?,
+            length: SnapshotData::deserialize(reader)?,
+            realm: SnapshotData::deserialize(reader)?,
+            initial_name: SnapshotData::deserialize(reader)?,
+            behaviour: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
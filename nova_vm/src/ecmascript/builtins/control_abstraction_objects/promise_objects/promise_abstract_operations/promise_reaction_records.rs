@@ -152,15 +152,31 @@ impl Rootable for PromiseReaction {
 impl HeapMarkAndSweep for PromiseReactionRecord {
     fn mark_values(&self, queues: &mut crate::heap::WorkQueues) {
         self.capability.mark_values(queues);
-        if let PromiseReactionHandler::JobCallback(_) = self.handler {
-            todo!();
-        }
+        self.handler.mark_values(queues);
     }
 
     fn sweep_values(&mut self, compactions: &crate::heap::CompactionLists) {
         self.capability.sweep_values(compactions);
-        if let PromiseReactionHandler::JobCallback(_) = self.handler {
-            todo!();
+        self.handler.sweep_values(compactions);
+    }
+}
+
+impl HeapMarkAndSweep for PromiseReactionHandler {
+    fn mark_values(&self, queues: &mut crate::heap::WorkQueues) {
+        match self {
+            Self::JobCallback(function) => function.mark_values(queues),
+            Self::Await(await_reaction) => await_reaction.mark_values(queues),
+            Self::AsyncGenerator(async_generator) => async_generator.mark_values(queues),
+            Self::Empty => {}
+        }
+    }
+
+    fn sweep_values(&mut self, compactions: &crate::heap::CompactionLists) {
+        match self {
+            Self::JobCallback(function) => function.sweep_values(compactions),
+            Self::Await(await_reaction) => await_reaction.sweep_values(compactions),
+            Self::AsyncGenerator(async_generator) => async_generator.sweep_values(compactions),
+            Self::Empty => {}
         }
     }
 }
@@ -19,7 +19,8 @@ use crate::{
         Scoped,
     },
     heap::{
-        indexes::DateIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, WorkQueues,
+        indexes::DateIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, SnapshotData,
+        SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
     },
 };
 
@@ -198,6 +199,16 @@ impl HeapMarkAndSweep for Date<'static> {
     }
 }
 
+impl SnapshotData for Date<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl CreateHeapData<DateHeapData, Date<'static>> for Heap {
     fn create(&mut self, data: DateHeapData) -> Date<'static> {
         self.dates.push(Some(data));
@@ -4,9 +4,12 @@
 
 use crate::{
     ecmascript::types::OrdinaryObject,
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
+    },
 };
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone, Copy)]
 pub struct DateHeapData {
@@ -40,3 +43,37 @@ impl HeapMarkAndSweep for DateHeapData {
         object_index.sweep_values(compactions);
     }
 }
+
+impl SnapshotData for DateHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self { object_index, date } = self;
+        object_index.serialize(writer)?;
+        // The time is written as its distance from the Unix epoch.
+        let time = date.map(|date| match date.duration_since(UNIX_EPOCH) {
+            Ok(duration) => (false, duration),
+            Err(err) => (true, err.duration()),
+        });
+        time.map(|(before_epoch, duration)| {
+            (before_epoch, (duration.as_secs(), duration.subsec_nanos()))
+        })
+        .serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let object_index = SnapshotData::deserialize(reader)?;
+        let time = Option::<(bool, (u64, u32))>::deserialize(reader)?;
+        let date = match time {
+            Some((before_epoch, (secs, nanos))) => {
+                let duration = Duration::new(secs, nanos);
+                let date = if before_epoch {
+                    UNIX_EPOCH.checked_sub(duration)
+                } else {
+                    UNIX_EPOCH.checked_add(duration)
+                };
+                Some(date.ok_or(SnapshotError::Malformed)?)
+            }
+            None => None,
+        };
+        Ok(Self { object_index, date })
+    }
+}
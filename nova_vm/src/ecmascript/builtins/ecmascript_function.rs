@@ -23,7 +23,10 @@ use crate::{
             ExecutionContext, FunctionEnvironmentIndex, JsResult, PrivateEnvironmentIndex,
             ProtoIntrinsics, RealmIdentifier, ThisBindingStatus,
         },
-        scripts_and_modules::{source_code::SourceCode, ScriptOrModule},
+        scripts_and_modules::{
+            source_code::{ParsedCodeDependent, SourceCode},
+            ScriptOrModule,
+        },
         syntax_directed_operations::function_definitions::{
             evaluate_async_function_body, evaluate_function_body, evaluate_generator_body,
         },
@@ -37,10 +40,10 @@ use crate::{
             BUILTIN_STRING_MEMORY,
         },
     },
-    engine::Executable,
+    engine::{Executable, FunctionNodes},
     heap::{
         indexes::ECMAScriptFunctionIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep,
-        WorkQueues,
+        SnapshotData, SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
     },
 };
 use crate::{
@@ -189,13 +192,15 @@ pub(crate) struct ECMAScriptFunctionObjectHeapData {
     /// \[\[FormalParameters]]
     ///
     /// SAFETY: SourceCode owns the Allocator into which this refers to.
-    /// Our GC algorithm keeps it alive as long as this function is alive.
+    /// Our GC algorithm keeps it alive as long as this function is alive. If
+    /// the parsed code is discarded, this is only valid again after the
+    /// source code is parsed again: Borrow it through
+    /// [`SourceCode::parsed_code`].
     pub formal_parameters: NonNull<FormalParameters<'static>>,
 
     /// \[\[ECMAScriptCode]]
     ///
-    /// SAFETY: SourceCode owns the Allocator into which this refers to.
-    /// Our GC algorithm keeps it alive as long as this function is alive.
+    /// SAFETY: See \[\[FormalParameters]].
     pub ecmascript_code: NonNull<FunctionBody<'static>>,
 
     /// True if the function body is a ConciseBody (can only be true for arrow
@@ -253,6 +258,24 @@ pub(crate) struct OrdinaryFunctionCreateParams<'agent, 'program, 'gc> {
     pub private_env: Option<PrivateEnvironmentIndex>,
 }
 
+impl ECMAScriptFunctionObjectHeapData {
+    /// Point the function at the nodes of its source code's new parsed code
+    /// after the source code was parsed again. The nodes are found by the
+    /// function's source text, which is the span of its function node.
+    /// Returns None and leaves the function as is if the nodes are not found.
+    pub(crate) fn relink_parsed_code(&mut self, nodes: &FunctionNodes) -> Option<()> {
+        let (params, body) = if let Some(function) = nodes.function(self.source_text) {
+            (&*function.params, function.body.as_deref()?)
+        } else {
+            let arrow_function = nodes.arrow_function(self.source_text)?;
+            (&*arrow_function.params, &*arrow_function.body)
+        };
+        self.formal_parameters = NonNull::from(params);
+        self.ecmascript_code = NonNull::from(body);
+        Some(())
+    }
+}
+
 impl Index<ECMAScriptFunction<'_>> for Agent {
     type Output = ECMAScriptFunctionHeapData;
 
@@ -507,6 +530,7 @@ impl<'a> InternalMethods<'a> for ECMAScriptFunction<'a> {
         arguments_list: ArgumentsList,
         gc: GcScope,
     ) -> JsResult<Value> {
+        agent.check_call_depth(false, gc.nogc())?;
         // 1. Let callerContext be the running execution context.
         let _ = agent.running_execution_context();
         // 2. Let calleeContext be PrepareForOrdinaryCall(F, undefined).
@@ -561,6 +585,7 @@ impl<'a> InternalMethods<'a> for ECMAScriptFunction<'a> {
         new_target: Function,
         mut gc: GcScope<'gc, '_>,
     ) -> JsResult<Object<'gc>> {
+        agent.check_call_depth(false, gc.nogc())?;
         let mut self_fn = self.bind(gc.nogc());
         let mut new_target = new_target.bind(gc.nogc());
         // 2. Let kind be F.[[ConstructorKind]].
@@ -797,14 +822,9 @@ pub(crate) fn evaluate_body(
             )
         }
         (false, false) => {
-            // SAFETY: AS the ECMAScriptFunction is alive in the heap, its referred
-            // SourceCode must be as well. Thus the Allocator is live as well, and no
-            // other references to it can exist.
-            if unsafe { heap_data.ecmascript_code.as_ref() }
-                .statements
-                .is_empty()
-                && unsafe { heap_data.formal_parameters.as_ref() }.is_simple_parameter_list()
-            {
+            let parsed_code = heap_data.source_code.parsed_code(agent, gc.nogc());
+            let (params, body) = parsed_code.function(agent, function_object);
+            if body.statements.is_empty() && params.is_simple_parameter_list() {
                 // Optimisation: Empty body and only simple parameters means no code will effectively run.
                 return Ok(Value::Undefined);
             }
@@ -1010,7 +1030,7 @@ pub(crate) fn make_constructor<'a>(
         Function::BuiltinConstructorFunction(_) => unreachable!(),
         Function::BuiltinPromiseResolvingFunction(_) => todo!(),
         Function::BuiltinPromiseCollectorFunction => todo!(),
-        Function::BuiltinProxyRevokerFunction => todo!(),
+        Function::BuiltinProxyRevokerFunction(_) => unreachable!(),
     }
     // 5. If prototype is not present, then
     let prototype = prototype.unwrap_or_else(|| {
@@ -1130,7 +1150,7 @@ pub(crate) fn set_function_name<'a>(
         Function::BuiltinConstructorFunction(_) => unreachable!(),
         Function::BuiltinPromiseResolvingFunction(_) => todo!(),
         Function::BuiltinPromiseCollectorFunction => todo!(),
-        Function::BuiltinProxyRevokerFunction => todo!(),
+        Function::BuiltinProxyRevokerFunction(_) => unreachable!(),
     }
 }
 
@@ -1190,10 +1210,24 @@ impl HeapMarkAndSweep for ECMAScriptFunction<'static> {
     }
 }
 
+impl SnapshotData for ECMAScriptFunction<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl CreateHeapData<ECMAScriptFunctionHeapData, ECMAScriptFunction<'static>> for Heap {
     fn create(&mut self, data: ECMAScriptFunctionHeapData) -> ECMAScriptFunction<'static> {
+        let source_code = data.ecmascript_function.source_code;
         self.ecmascript_functions.push(Some(data));
-        ECMAScriptFunction(ECMAScriptFunctionIndex::last(&self.ecmascript_functions))
+        let function =
+            ECMAScriptFunction(ECMAScriptFunctionIndex::last(&self.ecmascript_functions));
+        source_code.add_dependent(self, ParsedCodeDependent::Function(function));
+        function
     }
 }
 
@@ -1270,3 +1304,134 @@ impl HeapMarkAndSweep for ECMAScriptFunctionHeapData {
         source_code.sweep_values(compactions);
     }
 }
+
+impl SnapshotData for ConstructorStatus {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        (*self as u8).serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        match u8::deserialize(reader)? {
+            0 => Ok(ConstructorStatus::NonConstructor),
+            1 => Ok(ConstructorStatus::ConstructorFunction),
+            2 => Ok(ConstructorStatus::BaseClass),
+            3 => Ok(ConstructorStatus::DerivedClass),
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+}
+
+impl SnapshotData for ThisMode {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        (*self as u8).serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        match u8::deserialize(reader)? {
+            0 => Ok(ThisMode::Lexical),
+            1 => Ok(ThisMode::Strict),
+            2 => Ok(ThisMode::Global),
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+}
+
+impl SnapshotData for ECMAScriptFunctionHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            object_index,
+            length,
+            ecmascript_function,
+            // Bytecode is compiled again on the function's next call.
+            compiled_bytecode: _,
+            name,
+        } = self;
+        let ECMAScriptFunctionObjectHeapData {
+            environment,
+            private_environment,
+            formal_parameters,
+            ecmascript_code,
+            is_concise_arrow_function,
+            is_async,
+            is_generator,
+            constructor_status,
+            realm,
+            script_or_module,
+            this_mode,
+            strict,
+            home_object,
+            source_text,
+            source_code,
+        } = ecmascript_function;
+        if private_environment.is_some() {
+            return Err(SnapshotError::UnsupportedHeapData("private environments"));
+        }
+        object_index.serialize(writer)?;
+        length.serialize(writer)?;
+        name.serialize(writer)?;
+        environment.serialize(writer)?;
+        // The AST nodes are found by their span in the parsed source code
+        // when the snapshot is read.
+        // SAFETY: SourceCode keeps the AST nodes alive.
+        unsafe { formal_parameters.as_ref() }
+            .span
+            .serialize(writer)?;
+        unsafe { ecmascript_code.as_ref() }.span.serialize(writer)?;
+        is_concise_arrow_function.serialize(writer)?;
+        is_async.serialize(writer)?;
+        is_generator.serialize(writer)?;
+        constructor_status.serialize(writer)?;
+        realm.serialize(writer)?;
+        script_or_module.serialize(writer)?;
+        this_mode.serialize(writer)?;
+        strict.serialize(writer)?;
+        home_object.serialize(writer)?;
+        source_text.serialize(writer)?;
+        source_code.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let object_index = SnapshotData::deserialize(reader)?;
+        let length = SnapshotData::deserialize(reader)?;
+        let name = SnapshotData::deserialize(reader)?;
+        let environment = SnapshotData::deserialize(reader)?;
+        let formal_parameters_span = Span::deserialize(reader)?;
+        let ecmascript_code_span = Span::deserialize(reader)?;
+        let is_concise_arrow_function = SnapshotData::deserialize(reader)?;
+        let is_async = SnapshotData::deserialize(reader)?;
+        let is_generator = SnapshotData::deserialize(reader)?;
+        let constructor_status = SnapshotData::deserialize(reader)?;
+        let realm = SnapshotData::deserialize(reader)?;
+        let script_or_module = SnapshotData::deserialize(reader)?;
+        let this_mode = SnapshotData::deserialize(reader)?;
+        let strict = SnapshotData::deserialize(reader)?;
+        let home_object = SnapshotData::deserialize(reader)?;
+        let source_text = SnapshotData::deserialize(reader)?;
+        let source_code = SourceCode::deserialize(reader)?;
+        let source_code_index = source_code.get_index();
+        Ok(Self {
+            object_index,
+            length,
+            ecmascript_function: ECMAScriptFunctionObjectHeapData {
+                environment,
+                private_environment: None,
+                formal_parameters: reader
+                    .formal_parameters(source_code_index, formal_parameters_span)?,
+                ecmascript_code: reader.function_body(source_code_index, ecmascript_code_span)?,
+                is_concise_arrow_function,
+                is_async,
+                is_generator,
+                constructor_status,
+                realm,
+                script_or_module,
+                this_mode,
+                strict,
+                home_object,
+                source_text,
+                source_code,
+            },
+            compiled_bytecode: None,
+            name,
+        })
+    }
+}
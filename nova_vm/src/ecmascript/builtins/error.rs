@@ -16,12 +16,14 @@ use crate::{
         execution::{agent::ExceptionType, Agent, JsResult, ProtoIntrinsics},
         types::{
             InternalMethods, InternalSlots, IntoObject, IntoValue, Object, ObjectHeapData,
-            OrdinaryObject, PropertyDescriptor, PropertyKey, String, Value, BUILTIN_STRING_MEMORY,
+            ObjectShape, OrdinaryObject, PropertyDescriptor, PropertyKey, String, Value,
+            BUILTIN_STRING_MEMORY,
         },
     },
     heap::{
         indexes::ErrorIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, ObjectEntry,
-        ObjectEntryPropertyDescriptor, WorkQueues,
+        ObjectEntryPropertyDescriptor, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WorkQueues,
     },
 };
 
@@ -148,25 +150,15 @@ impl<'a> InternalSlots<'a> for Error<'a> {
                 configurable: true,
             },
         });
-        let (keys, values) =
-            if let (Some(message_entry), Some(cause_entry)) = (message_entry, cause_entry) {
-                agent
-                    .heap
-                    .elements
-                    .create_object_entries(&[message_entry, cause_entry])
-            } else if let Some(message_entry) = message_entry {
-                agent.heap.elements.create_object_entries(&[message_entry])
-            } else if let Some(cause_entry) = cause_entry {
-                agent.heap.elements.create_object_entries(&[cause_entry])
-            } else {
-                agent.heap.elements.create_object_entries(&[])
-            };
-        let backing_object = agent.heap.create(ObjectHeapData {
-            extensible: true,
-            prototype: Some(prototype),
-            keys,
-            values,
-        });
+        let entries: Vec<ObjectEntry> =
+            [message_entry, cause_entry].into_iter().flatten().collect();
+        let shape = ObjectShape::from_keys(
+            &mut agent.heap,
+            Some(prototype),
+            entries.iter().map(|entry| entry.key),
+        );
+        let values = agent.heap.elements.create_object_entry_values(&entries);
+        let backing_object = agent.heap.create(ObjectHeapData::new(true, shape, values));
         self.set_backing_object(agent, backing_object);
         backing_object
     }
@@ -474,6 +466,16 @@ impl HeapMarkAndSweep for Error<'static> {
     }
 }
 
+impl SnapshotData for Error<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl CreateHeapData<ErrorHeapData, Error<'static>> for Heap {
     fn create(&mut self, data: ErrorHeapData) -> Error<'static> {
         self.errors.push(Some(data));
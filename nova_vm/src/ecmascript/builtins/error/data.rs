@@ -7,7 +7,10 @@ use crate::{
         execution::agent::ExceptionType,
         types::{OrdinaryObject, String, Value},
     },
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
+    },
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -60,3 +63,28 @@ impl HeapMarkAndSweep for ErrorHeapData {
         cause.sweep_values(compactions);
     }
 }
+
+impl SnapshotData for ErrorHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            object_index,
+            kind,
+            message,
+            cause,
+        } = self;
+        object_index.serialize(writer)?;
+        (*kind as u16).serialize(writer)?;
+        message.serialize(writer)?;
+        cause.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            object_index: SnapshotData::deserialize(reader)?,
+            kind: ExceptionType::try_from(u16::deserialize(reader)?)
+                .map_err(|_| SnapshotError::Malformed)?,
+            message: SnapshotData::deserialize(reader)?,
+            cause: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
@@ -25,7 +25,7 @@ use crate::{
         execution::{agent::ExceptionType, Agent, JsResult, RealmIdentifier},
         types::{
             Function, InternalSlots, IntoFunction, IntoObject, IntoValue, Number, ObjectHeapData,
-            OrdinaryObject, PropertyKey, String, Value, BUILTIN_STRING_MEMORY,
+            ObjectShape, OrdinaryObject, PropertyKey, String, Value, BUILTIN_STRING_MEMORY,
         },
     },
     heap::{
@@ -375,8 +375,10 @@ impl FunctionPrototype {
                 "class { [ native code ] }",
                 gc.nogc(),
             )),
-            Function::BuiltinPromiseResolvingFunction(_) => {
-                // Promise resolving functions have no initial name.
+            Function::BuiltinPromiseResolvingFunction(_)
+            | Function::BuiltinProxyRevokerFunction(_) => {
+                // Promise resolving and Proxy revocation functions have no
+                // initial name.
                 Ok(Value::from_static_str(
                     agent,
                     "function () { [ native code ] }",
@@ -384,7 +386,6 @@ impl FunctionPrototype {
                 ))
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
         }
 
         // NOTE: NativeFunction means the following string:
@@ -501,16 +502,14 @@ fn create_throw_type_error_backing_object(
             configurable: false,
         },
     };
-    let (keys, values) = agent
-        .heap
-        .elements
-        .create_object_entries(&[length_entry, name_entry]);
-
-    agent.heap.create(ObjectHeapData {
-        // The value of the [[Extensible]] internal slot of this function is false.
-        extensible: false,
-        prototype: Some(prototype),
-        keys,
-        values,
-    })
+    let entries = [length_entry, name_entry];
+    let shape = ObjectShape::from_keys(
+        &mut agent.heap,
+        Some(prototype),
+        entries.iter().map(|entry| entry.key),
+    );
+    let values = agent.heap.elements.create_object_entry_values(&entries);
+
+    // The value of the [[Extensible]] internal slot of this function is false.
+    agent.heap.create(ObjectHeapData::new(false, shape, values))
 }
@@ -537,23 +537,23 @@ impl ObjectConstructor {
                 && entries_array.is_simple(agent)
                 && entries_array.is_dense(agent)
             {
-                let entries_elements = &agent[agent[entries_array].elements];
+                let entries_elements = agent[entries_array].elements;
+                let entries_len = entries_elements.len() as usize;
                 // Note: Separate vector for keys to detect duplicates.
                 // This is optimal until ~20 keys, after which a HashMap would
                 // be better.
-                let mut entry_keys: Vec<PropertyKey> = Vec::with_capacity(entries_elements.len());
-                let mut object_entries: Vec<ObjectEntry> =
-                    Vec::with_capacity(entries_elements.len());
+                let mut entry_keys: Vec<PropertyKey> = Vec::with_capacity(entries_len);
+                let mut object_entries: Vec<ObjectEntry> = Vec::with_capacity(entries_len);
                 // Fast path is valid if each entry in the array is itself a
                 // simple and dense array that contains a valid property key
                 // and value.
                 // If these expectations are invalidated, we must go back to
                 // the generic iterator path.
                 let mut valid = true;
-                for entry_element in entries_elements {
+                for index in 0..entries_len {
                     // SAFETY: Array is a simple, dense array. All values are
                     // defined.
-                    let entry_element = entry_element.unwrap();
+                    let entry_element = agent[entries_elements][index].unwrap();
                     let entry_element_array =
                         if let Value::Array(entry_element_array) = entry_element {
                             // Note: We check length to equal 2 because it's
@@ -574,12 +574,12 @@ impl ObjectConstructor {
                         };
                     let key_value_elements = &agent[agent[entry_element_array].elements];
                     let key = key_value_elements.first().unwrap().unwrap();
+                    let value = key_value_elements.last().unwrap().unwrap();
                     let key = to_property_key_simple(agent, key, gc.nogc());
                     let TryResult::Continue(key) = key else {
                         valid = false;
                         break;
                     };
-                    let value = key_value_elements.last().unwrap().unwrap();
                     let entry = ObjectEntry::new_data_entry(key, value);
                     let existing = entry_keys
                         .iter()
@@ -616,7 +616,7 @@ impl ObjectConstructor {
         );
         // 3. Assert: obj is an extensible ordinary object with no own properties.
         let obj = OrdinaryObject::try_from(obj).unwrap();
-        debug_assert!(agent[obj].keys.is_empty());
+        debug_assert!(agent[obj].shape.keys(agent).is_empty());
         // 4. Let closure be a new Abstract Closure with parameters (key,
         //    value) that captures obj and performs the following steps when
         //    called:
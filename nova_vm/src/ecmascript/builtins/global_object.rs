@@ -2,16 +2,19 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::fmt::Write;
+
 use ahash::AHashSet;
 use oxc_ast::ast::{BindingIdentifier, Program, VariableDeclarationKind};
 use oxc_ecmascript::BoundNames;
 use oxc_span::SourceType;
+use wtf8::{CodePoint, Wtf8, Wtf8Buf};
 
 use crate::ecmascript::abstract_operations::type_conversion::{
     is_trimmable_whitespace, to_int32, to_int32_number, to_number_primitive, to_string,
 };
 use crate::ecmascript::types::Primitive;
-use crate::engine::context::GcScope;
+use crate::engine::context::{GcScope, NoGcScope};
 use crate::{
     ecmascript::{
         abstract_operations::type_conversion::to_number,
@@ -341,9 +344,14 @@ pub fn perform_eval(
         // a. Set result to Completion(Evaluation of body).
         // 30. If result is a normal completion and result.[[Value]] is empty, then
         // a. Set result to NormalCompletion(undefined).
+        let collections = agent.heap.gc_statistics.collections;
         let result = Vm::execute(agent, exe, None, gc.reborrow()).into_js_result();
-        // SAFETY: No one can access the bytecode anymore.
-        unsafe { exe.try_drop(agent) };
+        // If garbage was collected during execution, the bytecode index is
+        // stale and the bytecode is left for the next collection.
+        if agent.heap.gc_statistics.collections == collections {
+            // SAFETY: No one can access the bytecode anymore.
+            unsafe { exe.try_drop(agent) };
+        }
         result
     } else {
         Err(result.err().unwrap())
@@ -1009,53 +1017,231 @@ impl GlobalObject {
         }
     }
 
+    /// ### [19.2.6.1 decodeURI ( encodedURI )](https://tc39.es/ecma262/#sec-decodeuri-encodeduri)
+    ///
+    /// This function computes a new version of a URI in which each escape
+    /// sequence and UTF-8 encoding of the sort that might be introduced by
+    /// the encodeURI function is replaced with the UTF-16 encoding of the
+    /// code point that it represents. Escape sequences that could not have
+    /// been introduced by encodeURI are not replaced.
     fn decode_uri(
-        _agent: &mut Agent,
+        agent: &mut Agent,
         _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let encoded_uri = arguments.get(0);
+
+        // 1. Let uriString be ? ToString(encodedURI).
+        let uri_string = to_string(agent, encoded_uri, gc.reborrow())?
+            .unbind()
+            .bind(gc.nogc());
+
+        // 2. Let preserveEscapeSet be ";/?:@&=+$,#".
+        // 3. Return ? Decode(uriString, preserveEscapeSet).
+        decode(agent, uri_string, ";/?:@&=+$,#", gc.nogc()).map(|s| s.into_value())
     }
+
+    /// ### [19.2.6.2 decodeURIComponent ( encodedURIComponent )](https://tc39.es/ecma262/#sec-decodeuricomponent-encodeduricomponent)
+    ///
+    /// This function computes a new version of a URI in which each escape
+    /// sequence and UTF-8 encoding of the sort that might be introduced by
+    /// the encodeURIComponent function is replaced with the UTF-16 encoding
+    /// of the code point that it represents.
     fn decode_uri_component(
-        _agent: &mut Agent,
+        agent: &mut Agent,
         _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let encoded_uri_component = arguments.get(0);
+
+        // 1. Let componentString be ? ToString(encodedURIComponent).
+        let component_string = to_string(agent, encoded_uri_component, gc.reborrow())?
+            .unbind()
+            .bind(gc.nogc());
+
+        // 2. Let preserveEscapeSet be the empty String.
+        // 3. Return ? Decode(componentString, preserveEscapeSet).
+        decode(agent, component_string, "", gc.nogc()).map(|s| s.into_value())
     }
+
+    /// ### [19.2.6.3 encodeURI ( uri )](https://tc39.es/ecma262/#sec-encodeuri-uri)
+    ///
+    /// This function computes a new version of a UTF-16 encoded (6.1.4) URI
+    /// in which each instance of certain code points is replaced by one, two,
+    /// three, or four escape sequences representing the UTF-8 encoding of the
+    /// code point.
     fn encode_uri(
-        _agent: &mut Agent,
+        agent: &mut Agent,
         _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let uri = arguments.get(0);
+
+        // 1. Let uriString be ? ToString(uri).
+        let uri_string = to_string(agent, uri, gc.reborrow())?
+            .unbind()
+            .bind(gc.nogc());
+
+        // 2. Let extraUnescaped be ";/?:@&=+$,#".
+        // 3. Return ? Encode(uriString, extraUnescaped).
+        encode(agent, uri_string, ";/?:@&=+$,#", gc.nogc()).map(|s| s.into_value())
     }
+
+    /// ### [19.2.6.4 encodeURIComponent ( uriComponent )](https://tc39.es/ecma262/#sec-encodeuricomponent-uricomponent)
+    ///
+    /// This function computes a new version of a UTF-16 encoded (6.1.4) URI
+    /// in which each instance of certain code points is replaced by one, two,
+    /// three, or four escape sequences representing the UTF-8 encoding of the
+    /// code point.
     fn encode_uri_component(
-        _agent: &mut Agent,
+        agent: &mut Agent,
         _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let uri_component = arguments.get(0);
+
+        // 1. Let componentString be ? ToString(uriComponent).
+        let component_string = to_string(agent, uri_component, gc.reborrow())?
+            .unbind()
+            .bind(gc.nogc());
+
+        // 2. Let extraUnescaped be the empty String.
+        // 3. Return ? Encode(componentString, extraUnescaped).
+        encode(agent, component_string, "", gc.nogc()).map(|s| s.into_value())
     }
+
+    /// ### [B.2.1.1 escape ( string )](https://tc39.es/ecma262/#sec-escape-string)
+    ///
+    /// This function is a property of the global object. It computes a new
+    /// version of a String value in which certain code units have been
+    /// replaced by a hexadecimal escape sequence.
     fn escape(
-        _agent: &mut Agent,
+        agent: &mut Agent,
         _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Set string to ? ToString(string).
+        let string = to_string(agent, arguments.get(0), gc.reborrow())?
+            .unbind()
+            .bind(gc.nogc());
+
+        // 2. Let len be the length of string.
+        // 3. Let R be the empty String.
+        let mut r = std::string::String::with_capacity(string.len(agent));
+
+        // 4. Let unescapedSet be the string-concatenation of the ASCII word characters and "@*+-./".
+        let is_unescaped = |c: u16| {
+            u8::try_from(c).is_ok_and(|c| c.is_ascii_alphanumeric() || b"_@*+-./".contains(&c))
+        };
+
+        // 5. Let k be 0.
+        // 6. Repeat, while k < len,
+        // a. Let C be the code unit at index k within string.
+        for c in string.as_wtf8(agent).to_ill_formed_utf16() {
+            // b. If unescapedSet contains C, then
+            if is_unescaped(c) {
+                // i. Let S be C.
+                r.push(c as u8 as char);
+            } else if c < 256 {
+                // c. Else,
+                // i. Let n be the numeric value of C.
+                // ii. If n < 256, then
+                // 1. Let hex be the String representation of n, formatted as an uppercase hexadecimal number.
+                // 2. Let S be the string-concatenation of "%" and StringPad(hex, 2, "0", start).
+                write!(r, "%{:02X}", c).unwrap();
+            } else {
+                // iii. Else,
+                // 1. Let hex be the String representation of n, formatted as an uppercase hexadecimal number.
+                // 2. Let S be the string-concatenation of "%u" and StringPad(hex, 4, "0", start).
+                write!(r, "%u{:04X}", c).unwrap();
+            }
+            // d. Set R to the string-concatenation of R and S.
+            // e. Set k to k + 1.
+        }
+
+        // 7. Return R.
+        Ok(String::from_string(agent, r, gc.nogc()).into_value())
     }
+
+    /// ### [B.2.1.2 unescape ( string )](https://tc39.es/ecma262/#sec-unescape-string)
+    ///
+    /// This function is a property of the global object. It computes a new
+    /// version of a String value in which each escape sequence of the sort
+    /// that might be introduced by the escape function is replaced with the
+    /// code unit that it represents.
     fn unescape(
-        _agent: &mut Agent,
+        agent: &mut Agent,
         _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Set string to ? ToString(string).
+        let string = to_string(agent, arguments.get(0), gc.reborrow())?
+            .unbind()
+            .bind(gc.nogc());
+
+        let string = string
+            .as_wtf8(agent)
+            .to_ill_formed_utf16()
+            .collect::<Vec<u16>>();
+        let hex_digit = |c: u16| char::from_u32(c as u32).and_then(|c| c.to_digit(16));
+        let hex_digits = |digits: &[u16]| {
+            digits
+                .iter()
+                .try_fold(0u16, |n, &c| Some((n << 4) | hex_digit(c)? as u16))
+        };
+
+        // 2. Let len be the length of string.
+        let len = string.len();
+        // 3. Let R be the empty String.
+        let mut r = Vec::with_capacity(len);
+        // 4. Let k be 0.
+        let mut k = 0;
+        // 5. Repeat, while k < len,
+        while k < len {
+            // a. Let C be the code unit at index k within string.
+            let mut c = string[k];
+            // b. If C is the code unit 0x0025 (PERCENT SIGN), then
+            if c == u16::from(b'%') {
+                // i. Let hexDigits be the empty String.
+                // ii. Let optionalAdvance be 0.
+                // iii. If k + 5 < len and the code unit at index k + 1 within string is the code unit 0x0075 (LATIN SMALL LETTER U), then
+                let (n, optional_advance) = if k + 5 < len && string[k + 1] == u16::from(b'u') {
+                    // 1. Set hexDigits to the substring of string from k + 2 to k + 6.
+                    // 2. Set optionalAdvance to 5.
+                    (hex_digits(&string[k + 2..k + 6]), 5)
+                } else if k + 3 <= len {
+                    // iv. Else if k + 3 ≤ len, then
+                    // 1. Set hexDigits to the substring of string from k + 1 to k + 3.
+                    // 2. Set optionalAdvance to 2.
+                    (hex_digits(&string[k + 1..k + 3]), 2)
+                } else {
+                    (None, 0)
+                };
+                // v. Let parseResult be ParseText(hexDigits, HexDigits[~Sep]).
+                // vi. If parseResult is a Parse Node, then
+                if let Some(n) = n {
+                    // 1. Let n be the MV of parseResult.
+                    // 2. Set C to the code unit whose numeric value is n.
+                    c = n;
+                    // 3. Set k to k + optionalAdvance.
+                    k += optional_advance;
+                }
+            }
+            // c. Set R to the string-concatenation of R and C.
+            r.push(c);
+            // d. Set k to k + 1.
+            k += 1;
+        }
+
+        // 6. Return R.
+        let r = Wtf8Buf::from_ill_formed_utf16(&r);
+        Ok(String::from_wtf8_buf(agent, r, gc.nogc()).into_value())
     }
 
     pub(crate) fn create_intrinsic(agent: &mut Agent, realm: RealmIdentifier) {
@@ -1084,3 +1270,258 @@ impl GlobalObject {
             .build();
     }
 }
+
+/// ### [19.2.6.5 Encode ( string, extraUnescaped )](https://tc39.es/ecma262/#sec-encode)
+///
+/// The abstract operation Encode takes arguments string (a String) and
+/// extraUnescaped (a String) and returns either a normal completion
+/// containing a String or a throw completion. It performs URI encoding and
+/// escaping, interpreting string as a sequence of UTF-16 encoded code points
+/// as described in 6.1.4. If a character is identified as unreserved in RFC
+/// 2396 or appears in extraUnescaped, it is not escaped.
+fn encode<'gc>(
+    agent: &mut Agent,
+    string: String,
+    extra_unescaped: &str,
+    gc: NoGcScope<'gc, '_>,
+) -> JsResult<String<'gc>> {
+    // 1. Let len be the length of string.
+    // 2. Let R be the empty String.
+    let mut r = std::string::String::with_capacity(string.len(agent));
+    // 3. Let alwaysUnescaped be the string-concatenation of the ASCII word characters and "-.!~*'()".
+    // 4. Let unescapedSet be the string-concatenation of alwaysUnescaped and extraUnescaped.
+    let is_unescaped = |c: char| {
+        c.is_ascii_alphanumeric() || "_-.!~*'()".contains(c) || extra_unescaped.contains(c)
+    };
+    let mut malformed = false;
+    // 5. Let k be 0.
+    // 6. Repeat, while k < len,
+    for cp in string.as_wtf8(agent).code_points() {
+        // a. Let C be the code unit at index k within string.
+        // c. Else,
+        // i. Let cp be CodePointAt(string, k).
+        // ii. If cp.[[IsUnpairedSurrogate]] is true, throw a URIError exception.
+        let Some(c) = cp.to_char() else {
+            malformed = true;
+            break;
+        };
+        // b. If unescapedSet contains C, then
+        if is_unescaped(c) {
+            // i. Set k to k + 1.
+            // ii. Set R to the string-concatenation of R and C.
+            r.push(c);
+            continue;
+        }
+        // iii. Set k to k + cp.[[CodeUnitCount]].
+        // iv. Let Octets be the List of octets resulting by applying the UTF-8 transformation to cp.[[CodePoint]].
+        let mut octets = [0; 4];
+        // v. For each element octet of Octets, do
+        for octet in c.encode_utf8(&mut octets).bytes() {
+            // 1. Let hex be the String representation of octet, formatted as an uppercase hexadecimal number.
+            // 2. Set R to the string-concatenation of R, "%", and StringPad(hex, 2, "0", start).
+            write!(r, "%{:02X}", octet).unwrap();
+        }
+    }
+    if malformed {
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::UriError,
+            "URI malformed",
+            gc,
+        ));
+    }
+    // 7. Return R.
+    Ok(String::from_string(agent, r, gc))
+}
+
+/// ### [19.2.6.6 Decode ( string, preserveEscapeSet )](https://tc39.es/ecma262/#sec-decode)
+///
+/// The abstract operation Decode takes arguments string (a String) and
+/// preserveEscapeSet (a String) and returns either a normal completion
+/// containing a String or a throw completion. It performs URI unescaping and
+/// decoding, preserving any escape sequences that correspond to Basic Latin
+/// characters in preserveEscapeSet.
+fn decode<'gc>(
+    agent: &mut Agent,
+    string: String,
+    preserve_escape_set: &str,
+    gc: NoGcScope<'gc, '_>,
+) -> JsResult<String<'gc>> {
+    let Some(r) = decode_wtf8(string.as_wtf8(agent), preserve_escape_set) else {
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::UriError,
+            "URI malformed",
+            gc,
+        ));
+    };
+    Ok(String::from_wtf8_buf(agent, r, gc))
+}
+
+/// Performs the steps of Decode on WTF-8 data, returning None where the
+/// specification throws a URIError.
+fn decode_wtf8(string: &Wtf8, preserve_escape_set: &str) -> Option<Wtf8Buf> {
+    let percent_sign = u32::from(b'%');
+    // 1. Let len be the length of string.
+    // 2. Let R be the empty String.
+    let mut r = Wtf8Buf::with_capacity(string.len());
+    // 3. Let k be 0.
+    let mut code_points = string.code_points();
+    // 4. Repeat, while k < len,
+    // a. Let C be the code unit at index k within string.
+    while let Some(c) = code_points.next() {
+        // c. If C is the code unit 0x0025 (PERCENT SIGN), then
+        if c.to_u32() != percent_sign {
+            // b. Let S be C.
+            // d. Set R to the string-concatenation of R and S.
+            r.push(c);
+            // e. Set k to k + 1.
+            continue;
+        }
+        // i. If k + 3 > len, throw a URIError exception.
+        // ii. Let escape be the substring of string from k to k + 3.
+        // iii. Let B be ParseHexOctet(string, k + 1).
+        // iv. If B is not an integer, throw a URIError exception.
+        // v. Set k to k + 2.
+        let (b, escape) = parse_hex_octet(&mut code_points)?;
+        // vi. Let n be the number of leading 1 bits in B.
+        let n = b.leading_ones() as usize;
+        // vii. If n = 0, then
+        if n == 0 {
+            // 1. Let asciiChar be the code unit whose numeric value is B.
+            let ascii_char = char::from(b);
+            // 2. If preserveEscapeSet contains asciiChar, set S to escape. Otherwise, set S to asciiChar.
+            if preserve_escape_set.contains(ascii_char) {
+                r.push_char('%');
+                r.push_char(escape[0]);
+                r.push_char(escape[1]);
+            } else {
+                r.push_char(ascii_char);
+            }
+            continue;
+        }
+        // viii. Else,
+        // 1. If n = 1 or n > 4, throw a URIError exception.
+        if n == 1 || n > 4 {
+            return None;
+        }
+        // 2. Let Octets be « B ».
+        let mut octets = [b, 0, 0, 0];
+        // 3. Let j be 1.
+        // 4. Repeat, while j < n,
+        for octet in octets.iter_mut().take(n).skip(1) {
+            // a. Set k to k + 1.
+            // b. If k + 3 > len, throw a URIError exception.
+            // c. If the code unit at index k within string is not the code unit 0x0025 (PERCENT SIGN), throw a URIError exception.
+            if code_points.next()?.to_u32() != percent_sign {
+                return None;
+            }
+            // d. Let continuationByte be ParseHexOctet(string, k + 1).
+            // e. If continuationByte is not an integer, throw a URIError exception.
+            // f. Append continuationByte to Octets.
+            // g. Set k to k + 2.
+            // h. Set j to j + 1.
+            *octet = parse_hex_octet(&mut code_points)?.0;
+        }
+        // 5. Assert: The length of Octets is n.
+        // 6. If Octets does not contain a valid UTF-8 encoding of a Unicode code point, throw a URIError exception.
+        // 7. Let V be the code point obtained by applying the UTF-8 transformation to Octets, that is, from a List of octets into a 21-bit value.
+        let v = std::str::from_utf8(&octets[..n]).ok()?;
+        // 8. Set S to UTF16EncodeCodePoint(V).
+        r.push_str(v);
+    }
+    // 5. Return R.
+    Some(r)
+}
+
+/// ### [19.2.6.7 ParseHexOctet ( string, position )](https://tc39.es/ecma262/#sec-parsehexoctet)
+///
+/// The abstract operation ParseHexOctet takes arguments string (a String) and
+/// position (a non-negative integer) and returns either a non-negative
+/// integer or a non-empty List of SyntaxError objects. It parses a sequence
+/// of two hexadecimal characters at the specified position in string into an
+/// unsigned 8-bit integer.
+///
+/// This variant consumes the two characters from a code point iterator and
+/// returns them alongside the parsed value.
+fn parse_hex_octet(code_points: &mut impl Iterator<Item = CodePoint>) -> Option<(u8, [char; 2])> {
+    // 1. Let len be the length of string.
+    // 2. Assert: position + 2 ≤ len.
+    // 3. Let hexDigits be the substring of string from position to position + 2.
+    let hi = code_points.next()?.to_char()?;
+    let lo = code_points.next()?.to_char()?;
+    // 4. Let parseResult be ParseText(hexDigits, HexDigits[~Sep]).
+    // 5. If parseResult is not a Parse Node, return parseResult.
+    // 6. Let n be the unsigned 8-bit value corresponding with the MV of parseResult.
+    let n = (hi.to_digit(16)? << 4) | lo.to_digit(16)?;
+    // 7. Return n.
+    Some((n as u8, [hi, lo]))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::TestAgent;
+
+    #[test]
+    fn encode_and_decode_uri() {
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval("encodeURIComponent('a b&c/ä€😀')"),
+            "a%20b%26c%2F%C3%A4%E2%82%AC%F0%9F%98%80"
+        );
+        assert_eq!(
+            agent.eval("encodeURI('http://x.y/a b?c=d#e')"),
+            "http://x.y/a%20b?c=d#e"
+        );
+        assert_eq!(
+            agent.eval("decodeURIComponent('a%20b%26c%2F%C3%A4%E2%82%AC%F0%9F%98%80')"),
+            "a b&c/ä€😀"
+        );
+        assert_eq!(agent.eval("decodeURI('%3Fa%20b%23')"), "%3Fa b%23");
+        assert_eq!(
+            agent.eval_err("encodeURIComponent(unescape('%uD800'))"),
+            "URIError: URI malformed"
+        );
+        assert_eq!(
+            agent.eval_err("decodeURI('%E2%82')"),
+            "URIError: URI malformed"
+        );
+        assert_eq!(
+            agent.eval_err("decodeURI('%C0%80')"),
+            "URIError: URI malformed"
+        );
+    }
+
+    #[cfg(feature = "annex-b-global")]
+    #[test]
+    fn decode_preserves_lone_surrogates() {
+        let mut agent = TestAgent::new();
+        // Decode only rejects surrogates spelled out as UTF-8, so a lone
+        // surrogate must come from the source string itself.
+        assert_eq!(
+            agent.eval("var s = decodeURIComponent(unescape('%uDC00') + '%41'); s.length"),
+            "2"
+        );
+        assert_eq!(agent.eval("escape(s)"), "%uDC00A");
+    }
+
+    #[cfg(feature = "annex-b-global")]
+    #[test]
+    fn escape_and_unescape() {
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval("typeof escape + typeof unescape"),
+            "functionfunction"
+        );
+        assert_eq!(agent.eval("escape('a b+ä€')"), "a%20b+%E4%u20AC");
+        assert_eq!(agent.eval("unescape('a%20b+%E4%u20AC')"), "a b+ä€");
+        assert_eq!(agent.eval("unescape('%u12%zz%')"), "%u12%zz%");
+        assert_eq!(agent.eval("unescape('%uD800').length"), "1");
+        assert_eq!(
+            agent.eval("escape(unescape('%uD800abcdefgh'))"),
+            "%uD800abcdefgh"
+        );
+        assert_eq!(
+            agent.eval("unescape('%uD800') + unescape('%uDC00') === '\\uD800\\uDC00'"),
+            "true"
+        );
+    }
+}
@@ -165,7 +165,8 @@ impl ArrayConstructor {
                 let array = array_create(
                     agent,
                     int_len as usize,
-                    int_len as usize,
+                    // The array starts out as all holes.
+                    0,
                     proto.map(|p| p.get(agent)),
                     gc.nogc(),
                 )
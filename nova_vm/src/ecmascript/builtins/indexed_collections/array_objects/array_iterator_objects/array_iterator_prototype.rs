@@ -226,8 +226,8 @@ impl ArrayIteratorPrototype {
                 let fast_path_result = match array {
                     Object::Array(array) => {
                         assert!(ARRAY_INDEX_RANGE.contains(&index.into_i64()));
-                        let idx = usize::try_from(index.into_i64()).unwrap();
-                        array.as_slice(agent)[idx]
+                        let idx = u32::try_from(index.into_i64()).unwrap();
+                        array.get_element_value(agent, idx)
                     }
                     _ => None,
                 };
@@ -243,8 +243,8 @@ impl ArrayIteratorPrototype {
                 let fast_path_result = match array {
                     Object::Array(array) if agent[array].object_index.is_none() => {
                         assert!(ARRAY_INDEX_RANGE.contains(&index.into_i64()));
-                        let idx = usize::try_from(index.into_i64()).unwrap();
-                        array.as_slice(agent)[idx]
+                        let idx = u32::try_from(index.into_i64()).unwrap();
+                        array.get_element_value(agent, idx)
                     }
                     _ => None,
                 };
@@ -40,7 +40,7 @@ use crate::{
             Value, BUILTIN_STRING_MEMORY,
         },
     },
-    heap::{Heap, IntrinsicFunctionIndexes, WellKnownSymbolIndexes},
+    heap::{element_array::ElementsVector, Heap, IntrinsicFunctionIndexes, WellKnownSymbolIndexes},
     SmallInteger,
 };
 
@@ -508,9 +508,9 @@ impl ArrayPrototype {
         ) = (this_value, target, start, end)
         {
             // Fast path: Array with integer parameters, array is trivial
-            // (no descriptors). Holes can exist, we'll just copy them
-            // equivalently.
-            if array.is_trivial(agent) {
+            // (no descriptors) or has double elements. Holes can exist, we'll
+            // just copy them equivalently.
+            if array.is_trivial(agent) || agent[array].elements.is_float64() {
                 let len = array.len(agent) as i64;
 
                 let relative_target = target.into_i64();
@@ -539,8 +539,14 @@ impl ArrayPrototype {
                 };
 
                 let count = (final_end - from).min(len as isize - to);
-                let data = array.as_mut_slice(agent);
-                data.copy_within((from as usize)..((from + count) as usize), to as usize);
+                let source = (from as usize)..((from + count) as usize);
+                if array.has_slice(agent) {
+                    array.as_mut_slice(agent).copy_within(source, to as usize);
+                } else {
+                    array
+                        .as_mut_f64_slice(agent)
+                        .copy_within(source, to as usize);
+                }
 
                 return Ok(array.into_value());
             }
@@ -786,8 +792,13 @@ impl ArrayPrototype {
         ) = (this_value, start, end)
         {
             // Fast path: If the array is simple (no descriptors) and dense (no
-            // holes) then we can write directly into the backing memory.
-            if array.is_simple(agent) && array.is_dense(agent) {
+            // holes) then we can write directly into the backing memory. Double
+            // elements can be written directly if the value is a number.
+            let f64_value = match Number::try_from(value) {
+                Ok(number) if array.as_f64_slice(agent).is_some() => Some(number.into_f64(agent)),
+                _ => None,
+            };
+            if f64_value.is_some() || (array.is_simple(agent) && array.is_dense(agent)) {
                 let len = array.len(agent) as usize;
 
                 let relative_start = if let Value::Integer(start) = start {
@@ -814,8 +825,18 @@ impl ArrayPrototype {
                     len
                 };
 
-                let data = array.as_mut_slice(agent);
-                data[k..final_end].fill(Some(value));
+                if let Some(f64_value) = f64_value {
+                    // Canonicalise NaNs so that no value is mistaken for a hole.
+                    let f64_value = if f64_value.is_nan() {
+                        f64::NAN
+                    } else {
+                        f64_value
+                    };
+                    array.as_mut_f64_slice(agent)[k..final_end].fill(f64_value);
+                } else {
+                    let data = array.as_mut_slice(agent);
+                    data[k..final_end].fill(Some(value));
+                }
                 return Ok(value.into_value());
             }
         };
@@ -1324,8 +1345,26 @@ impl ArrayPrototype {
             } else {
                 0
             };
-            let data = &array.as_slice(agent)[k..];
-            let mut found_hole = false;
+            // Double elements without holes can be compared directly.
+            if let Some(values) = array.as_f64_slice(agent) {
+                let Ok(search_element) = Number::try_from(search_element) else {
+                    return Ok(false.into());
+                };
+                let search_element = search_element.into_f64(agent);
+                let found = values.get(k..).unwrap_or_default().iter().any(|value| {
+                    *value == search_element || (value.is_nan() && search_element.is_nan())
+                });
+                return Ok(found.into());
+            }
+            // Sparse arrays are full of holes that would require looking
+            // through the prototype chain, and double elements with holes
+            // have no slice: Leave them to the generic path.
+            let mut found_hole = !array.has_slice(agent);
+            let data: &[Option<Value>] = if found_hole {
+                &[]
+            } else {
+                &array.as_slice(agent)[k..]
+            };
             for element_k in data {
                 if let Some(element_k) = element_k {
                     if same_value_zero(agent, search_element, *element_k) {
@@ -1447,8 +1486,31 @@ impl ArrayPrototype {
             } else {
                 0
             };
-            let data = &array.as_slice(agent)[k..];
-            let mut found_hole = false;
+            // Double elements without holes can be compared directly.
+            if let Some(values) = array.as_f64_slice(agent) {
+                let Ok(search_element) = Number::try_from(search_element) else {
+                    return Ok((-1).into());
+                };
+                let search_element = search_element.into_f64(agent);
+                let found = values
+                    .get(k..)
+                    .unwrap_or_default()
+                    .iter()
+                    .position(|value| *value == search_element);
+                return Ok(match found {
+                    Some(index) => (k as u32 + index as u32).into(),
+                    None => (-1).into(),
+                });
+            }
+            // Sparse arrays are full of holes that would require looking
+            // through the prototype chain, and double elements with holes
+            // have no slice: Leave them to the generic path.
+            let mut found_hole = !array.has_slice(agent);
+            let data: &[Option<Value>] = if found_hole {
+                &[]
+            } else {
+                &array.as_slice(agent)[k..]
+            };
             for (index, element_k) in data.iter().enumerate() {
                 if let Some(element_k) = element_k {
                     if is_strictly_equal(agent, search_element, *element_k) {
@@ -1671,8 +1733,29 @@ impl ArrayPrototype {
             } else {
                 last
             };
-            let data = &array.as_slice(agent)[..=k];
-            let mut found_hole = false;
+            // Double elements without holes can be compared directly.
+            if let Some(values) = array.as_f64_slice(agent) {
+                let Ok(search_element) = Number::try_from(search_element) else {
+                    return Ok((-1).into());
+                };
+                let search_element = search_element.into_f64(agent);
+                let found = values[..=k]
+                    .iter()
+                    .rposition(|value| *value == search_element);
+                return Ok(match found {
+                    Some(index) => (index as u32).into(),
+                    None => (-1).into(),
+                });
+            }
+            // Sparse arrays are full of holes that would require looking
+            // through the prototype chain, and double elements with holes
+            // have no slice: Leave them to the generic path.
+            let mut found_hole = !array.has_slice(agent);
+            let data: &[Option<Value>] = if found_hole {
+                &[]
+            } else {
+                &array.as_slice(agent)[..=k]
+            };
             for (index, element_k) in data.iter().enumerate().rev() {
                 if let Some(element_k) = element_k {
                     if is_strictly_equal(agent, search_element, *element_k) {
@@ -1891,6 +1974,16 @@ impl ArrayPrototype {
                 }
                 // Last element was a hole; this means we'd need to look into
                 // the prototype chain. We're not going to do that.
+            } else if agent[array].elements.is_float64() && array.length_writable(agent) {
+                // Fast path: Double elements have no descriptors either.
+                let len = array.len(agent);
+                if let Some(last_element) = len
+                    .checked_sub(1)
+                    .and_then(|last| array.get_element_value(agent, last))
+                {
+                    array.remove_f64_element(agent, len - 1);
+                    return Ok(last_element);
+                }
             }
         }
         // 1. Let O be ? ToObject(this value).
@@ -2320,6 +2413,10 @@ impl ArrayPrototype {
                 array.as_mut_slice(agent).reverse();
                 return Ok(array.into_value());
             }
+            if array.as_f64_slice(agent).is_some() {
+                array.as_mut_f64_slice(agent).reverse();
+                return Ok(array.into_value());
+            }
         }
 
         // 1. Let O be ? ToObject(this value).
@@ -2469,6 +2566,13 @@ impl ArrayPrototype {
                     unreachable!();
                 }
             }
+            if array.as_f64_slice(agent).is_some() && array.length_writable(agent) {
+                // Fast path: Double elements without holes have no
+                // descriptors either.
+                let first = array.get_element_value(agent, 0).unwrap();
+                array.remove_f64_element(agent, 0);
+                return Ok(first);
+            }
         }
         // 1. Let O be ? ToObject(this value).
         let o = to_object(agent, this_value, gc.nogc())?.scope(agent, gc.nogc());
@@ -3151,6 +3255,11 @@ impl ArrayPrototype {
                 cloned_array.as_mut_slice(agent).reverse();
                 return Ok(cloned_array.into_value());
             }
+            if array.as_f64_slice(agent).is_some() {
+                let cloned_array = array.to_cloned(agent);
+                cloned_array.as_mut_f64_slice(agent).reverse();
+                return Ok(cloned_array.into_value());
+            }
         }
 
         // 1. Let O be ? ToObject(this value).
@@ -3331,6 +3440,31 @@ impl ArrayPrototype {
                 });
                 return Ok(final_len.unwrap().into());
             }
+            if let Ok(final_len) = final_len {
+                let elements: ElementsVector = agent[array].elements.into();
+                if array.as_f64_slice(agent).is_some()
+                    && array.length_writable(agent)
+                    && !elements.should_grow_sparse(final_len)
+                    && items.iter().all(|item| item.is_number())
+                {
+                    // Fast path: Double elements without holes have no
+                    // descriptors either. Copy old items to the end of the
+                    // array and set the new numbers to the front.
+                    agent[array].elements.len = final_len;
+                    array
+                        .as_mut_f64_slice(agent)
+                        .copy_within(..len as usize, arg_count);
+                    let elements = agent[array].elements.into();
+                    for (index, item) in items.iter().enumerate() {
+                        let value = Number::try_from(*item).unwrap().into_f64(agent);
+                        agent
+                            .heap
+                            .elements
+                            .set_f64(elements, index as u32, Some(value));
+                    }
+                    return Ok(final_len.into());
+                }
+            }
         }
         // 1. Let O be ? ToObject(this value).
         let o = to_object(agent, this_value, gc.nogc())?.scope(agent, gc.nogc());
@@ -3432,7 +3566,12 @@ impl ArrayPrototype {
         // Fast path: Array is dense and contains no descriptors. No JS
         // functions can thus be called by with.
         if let (Value::Array(array), Value::Integer(index)) = (this_value, index) {
-            if array.is_trivial(agent) && array.is_dense(agent) {
+            // Double elements without holes can take a number directly.
+            let f64_value = match Number::try_from(value) {
+                Ok(number) if array.as_f64_slice(agent).is_some() => Some(number.into_f64(agent)),
+                _ => None,
+            };
+            if f64_value.is_some() || (array.is_trivial(agent) && array.is_dense(agent)) {
                 let relative_index = index.into_i64();
                 let len = array.len(agent) as i64;
                 let actual_index = if relative_index >= 0 {
@@ -3449,7 +3588,15 @@ impl ArrayPrototype {
                 }
                 // Fast path: Set new value in cloned array.
                 let cloned_array = array.to_cloned(agent);
-                cloned_array.as_mut_slice(agent)[actual_index as usize] = Some(value);
+                if let Some(f64_value) = f64_value {
+                    let elements = agent[cloned_array].elements.into();
+                    agent
+                        .heap
+                        .elements
+                        .set_f64(elements, actual_index as u32, Some(f64_value));
+                } else {
+                    cloned_array.as_mut_slice(agent)[actual_index as usize] = Some(value);
+                }
                 return Ok(cloned_array.into());
             }
         }
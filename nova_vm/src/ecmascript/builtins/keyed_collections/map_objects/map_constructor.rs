@@ -16,7 +16,7 @@ use crate::{
                 get_iterator, if_abrupt_close_iterator, iterator_close, iterator_step_value,
             },
             operations_on_objects::{call_function, get, get_method},
-            testing_and_comparison::is_callable,
+            testing_and_comparison::{is_callable, same_value},
         },
         builders::builtin_function_builder::BuiltinFunctionBuilder,
         builtins::{
@@ -273,7 +273,11 @@ pub fn add_entries_from_iterable_map_constructor<'a>(
                             let next_index = keys.len() as u32;
                             let entry = map_data.entry(
                                 key_hash,
-                                |hash_equal_index| keys[*hash_equal_index as usize].unwrap() == key,
+                                |hash_equal_index| {
+                                    let found_key = keys[*hash_equal_index as usize].unwrap();
+                                    // Quick check: Equal keys have the same value.
+                                    found_key == key || same_value(&primitive_heap, found_key, key)
+                                },
                                 |index_to_hash| hasher(keys[*index_to_hash as usize].unwrap()),
                             );
                             match entry {
@@ -14,7 +14,7 @@ use crate::{
                 get_iterator, if_abrupt_close_iterator, iterator_step_value,
             },
             operations_on_objects::{call_function, get, get_method},
-            testing_and_comparison::is_callable,
+            testing_and_comparison::{is_callable, same_value},
         },
         builders::builtin_function_builder::BuiltinFunctionBuilder,
         builtins::{
@@ -161,7 +161,11 @@ impl SetConstructor {
                     let next_index = values.len() as u32;
                     let entry = set_data.entry(
                         value_hash,
-                        |hash_equal_index| values[*hash_equal_index as usize].unwrap() == value,
+                        |hash_equal_index| {
+                            let found_value = values[*hash_equal_index as usize].unwrap();
+                            // Quick check: Equal values have the same value.
+                            found_value == value || same_value(&primitive_heap, found_value, value)
+                        },
                         |index_to_hash| hasher(values[*index_to_hash as usize].unwrap()),
                     );
                     match entry {
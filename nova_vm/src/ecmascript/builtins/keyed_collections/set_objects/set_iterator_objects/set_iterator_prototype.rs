@@ -12,12 +12,12 @@ use crate::{
         builders::ordinary_object_builder::OrdinaryObjectBuilder,
         builtins::{
             indexed_collections::array_objects::array_iterator_objects::array_iterator::CollectionIteratorKind,
-            ArgumentsList, Builtin,
+            ArgumentsList, Builtin, BuiltinIntrinsic,
         },
         execution::{agent::ExceptionType, Agent, JsResult, RealmIdentifier},
         types::{IntoValue, String, Value, BUILTIN_STRING_MEMORY},
     },
-    heap::WellKnownSymbolIndexes,
+    heap::{IntrinsicFunctionIndexes, WellKnownSymbolIndexes},
 };
 
 pub(crate) struct SetIteratorPrototype;
@@ -31,6 +31,9 @@ impl Builtin for SetIteratorPrototypeNext {
     const BEHAVIOUR: crate::ecmascript::builtins::Behaviour =
         crate::ecmascript::builtins::Behaviour::Regular(SetIteratorPrototype::next);
 }
+impl BuiltinIntrinsic for SetIteratorPrototypeNext {
+    const INDEX: IntrinsicFunctionIndexes = IntrinsicFunctionIndexes::SetIteratorPrototypeNext;
+}
 
 impl SetIteratorPrototype {
     fn next(
@@ -105,7 +108,7 @@ impl SetIteratorPrototype {
         OrdinaryObjectBuilder::new_intrinsic_object(agent, realm, this)
             .with_property_capacity(2)
             .with_prototype(iterator_prototype)
-            .with_builtin_function_property::<SetIteratorPrototypeNext>()
+            .with_builtin_intrinsic_function_property::<SetIteratorPrototypeNext>()
             .with_property(|builder| {
                 builder
                     .with_key(WellKnownSymbolIndexes::ToStringTag.into())
@@ -2,32 +2,35 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use std::hash::Hasher;
-
-use ahash::AHasher;
-
 use crate::engine::context::{GcScope, NoGcScope};
+use crate::engine::{Scoped, TryResult};
 use crate::{
     ecmascript::{
         abstract_operations::{
-            operations_on_objects::call_function,
-            testing_and_comparison::{is_callable, same_value},
+            operations_on_iterator_objects::{
+                get_iterator_from_method, iterator_close, iterator_step_value,
+            },
+            operations_on_objects::{call_function, get, try_get},
+            testing_and_comparison::is_callable,
+            type_conversion::{
+                to_boolean, to_integer_or_infinity_number, to_number, IntegerOrInfinity,
+            },
         },
         builders::ordinary_object_builder::OrdinaryObjectBuilder,
         builtins::{
             indexed_collections::array_objects::array_iterator_objects::array_iterator::CollectionIteratorKind,
             keyed_collections::map_objects::map_prototype::canonicalize_keyed_collection_key,
             keyed_collections::set_objects::set_iterator_objects::set_iterator::SetIterator,
-            set::{
-                data::{SetData, SetHeapData},
-                Set,
-            },
+            set::{data::SetHeapData, Set},
             ArgumentsList, Behaviour, Builtin, BuiltinGetter, BuiltinIntrinsic,
         },
         execution::{agent::ExceptionType, Agent, JsResult, RealmIdentifier},
-        types::{IntoValue, Number, PropertyKey, String, Value, BUILTIN_STRING_MEMORY},
+        types::{
+            Function, IntoFunction, IntoValue, Number, Object, PropertyKey, String, Value,
+            BUILTIN_STRING_MEMORY,
+        },
     },
-    heap::{Heap, IntrinsicFunctionIndexes, PrimitiveHeap, WellKnownSymbolIndexes},
+    heap::{CreateHeapData, Heap, IntrinsicFunctionIndexes, PrimitiveHeap, WellKnownSymbolIndexes},
 };
 
 pub(crate) struct SetPrototype;
@@ -50,6 +53,12 @@ impl Builtin for SetPrototypeDelete {
     const LENGTH: u8 = 1;
     const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::delete);
 }
+struct SetPrototypeDifference;
+impl Builtin for SetPrototypeDifference {
+    const NAME: String<'static> = BUILTIN_STRING_MEMORY.difference;
+    const LENGTH: u8 = 1;
+    const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::difference);
+}
 struct SetPrototypeEntries;
 impl Builtin for SetPrototypeEntries {
     const NAME: String<'static> = BUILTIN_STRING_MEMORY.entries;
@@ -68,6 +77,33 @@ impl Builtin for SetPrototypeHas {
     const LENGTH: u8 = 1;
     const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::has);
 }
+impl BuiltinIntrinsic for SetPrototypeHas {
+    const INDEX: IntrinsicFunctionIndexes = IntrinsicFunctionIndexes::SetPrototypeHas;
+}
+struct SetPrototypeIntersection;
+impl Builtin for SetPrototypeIntersection {
+    const NAME: String<'static> = BUILTIN_STRING_MEMORY.intersection;
+    const LENGTH: u8 = 1;
+    const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::intersection);
+}
+struct SetPrototypeIsDisjointFrom;
+impl Builtin for SetPrototypeIsDisjointFrom {
+    const NAME: String<'static> = BUILTIN_STRING_MEMORY.isDisjointFrom;
+    const LENGTH: u8 = 1;
+    const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::is_disjoint_from);
+}
+struct SetPrototypeIsSubsetOf;
+impl Builtin for SetPrototypeIsSubsetOf {
+    const NAME: String<'static> = BUILTIN_STRING_MEMORY.isSubsetOf;
+    const LENGTH: u8 = 1;
+    const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::is_subset_of);
+}
+struct SetPrototypeIsSupersetOf;
+impl Builtin for SetPrototypeIsSupersetOf {
+    const NAME: String<'static> = BUILTIN_STRING_MEMORY.isSupersetOf;
+    const LENGTH: u8 = 1;
+    const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::is_superset_of);
+}
 struct SetPrototypeGetSize;
 impl Builtin for SetPrototypeGetSize {
     const NAME: String<'static> = BUILTIN_STRING_MEMORY.get_size;
@@ -76,6 +112,18 @@ impl Builtin for SetPrototypeGetSize {
     const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::get_size);
 }
 impl BuiltinGetter for SetPrototypeGetSize {}
+struct SetPrototypeSymmetricDifference;
+impl Builtin for SetPrototypeSymmetricDifference {
+    const NAME: String<'static> = BUILTIN_STRING_MEMORY.symmetricDifference;
+    const LENGTH: u8 = 1;
+    const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::symmetric_difference);
+}
+struct SetPrototypeUnion;
+impl Builtin for SetPrototypeUnion {
+    const NAME: String<'static> = BUILTIN_STRING_MEMORY.union;
+    const LENGTH: u8 = 1;
+    const BEHAVIOUR: Behaviour = Behaviour::Regular(SetPrototype::union);
+}
 struct SetPrototypeValues;
 impl Builtin for SetPrototypeValues {
     const NAME: String<'static> = BUILTIN_STRING_MEMORY.values;
@@ -111,34 +159,12 @@ impl SetPrototype {
         // 3. Set value to CanonicalizeKeyedCollectionKey(value).
         let value = canonicalize_keyed_collection_key(numbers, arguments.get(0));
 
-        let SetData {
-            values, set_data, ..
-        } = &mut sets[s].borrow_mut(&primitive_heap);
-        let set_data = set_data.get_mut();
-        let hasher = |value: Value| {
-            let mut hasher = AHasher::default();
-            value.hash(&primitive_heap, &mut hasher);
-            hasher.finish()
-        };
-
-        let value_hash = hasher(value);
-
         // 4. For each element e of S.[[SetData]], do
         // a. If e is not empty and SameValue(e, value) is true, then
-        if let hashbrown::hash_table::Entry::Vacant(entry) = set_data.entry(
-            value_hash,
-            |hash_equal_index| {
-                let found_value = values[*hash_equal_index as usize].unwrap();
-                // Quick check: Equal values have the same value.
-                found_value == value || same_value(&primitive_heap, found_value, value)
-            },
-            |index_to_hash| hasher(values[*index_to_hash as usize].unwrap()),
-        ) {
-            // 5. Append value to S.[[SetData]].
-            let index = u32::try_from(values.len()).unwrap();
-            entry.insert(index);
-            values.push(Some(value));
-        }
+        // 5. Append value to S.[[SetData]].
+        sets[s]
+            .borrow_mut(&primitive_heap)
+            .append(&primitive_heap, value);
         // i. Return S.
         // 6. Return S.
         Ok(s.into_value())
@@ -197,33 +223,119 @@ impl SetPrototype {
 
         // 3. Set value to CanonicalizeKeyedCollectionKey(value).
         let value = canonicalize_keyed_collection_key(numbers, arguments.get(0));
-        let mut hasher = AHasher::default();
-        let value_hash = {
-            value.hash(&primitive_heap, &mut hasher);
-            hasher.finish()
-        };
-        let SetData {
-            values, set_data, ..
-        } = &mut sets[s].borrow_mut(&primitive_heap);
-        let set_data = set_data.get_mut();
         // 4. For each element e of S.[[SetData]], do
-        if let Ok(entry) = set_data.find_entry(value_hash, |hash_equal_index| {
-            let found_value = values[*hash_equal_index as usize].unwrap();
-            // Quick check: Equal keys have the same value.
-            found_value == value || same_value(&primitive_heap, found_value, value)
-        }) {
-            // a. If e is not EMPTY and SameValue(e, value) is true, then
-            let index = *entry.get() as usize;
-            // i. Replace the element of S.[[SetData]] whose value is e with
-            // an element whose value is EMPTY.
-            values[index] = None;
-            let _ = entry.remove();
-            // ii. Return true.
-            Ok(true.into())
+        // a. If e is not EMPTY and SameValue(e, value) is true, then
+        // i. Replace the element of S.[[SetData]] whose value is e with an
+        // element whose value is EMPTY.
+        // ii. Return true.
+        // 5. Return false.
+        let found = sets[s]
+            .borrow_mut(&primitive_heap)
+            .remove(&primitive_heap, value);
+        Ok(found.into())
+    }
+
+    /// ### [24.2.4.5 Set.prototype.difference ( other )](https://tc39.es/ecma262/#sec-set.prototype.difference)
+    fn difference(
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
+    ) -> JsResult<Value> {
+        let other = arguments.get(0);
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[SetData]]).
+        let o = require_set_data_internal_slot(agent, this_value, gc.nogc())?;
+        let scoped_o = o.scope(agent, gc.nogc());
+        // 3. Let otherRec be ? GetSetRecord(other).
+        let other_rec = get_set_record(agent, other, gc.reborrow())?;
+        let o = scoped_o.get(agent).bind(gc.nogc());
+        if let Some(other) = other_rec.as_unobservable_set(agent, gc.nogc()) {
+            // Fast path: Calling otherRec.[[Has]] and iterating
+            // otherRec.[[Keys]] are both unobservable, so both branches of
+            // the algorithm produce the elements of O that are not in other.
+            let Heap {
+                bigints,
+                numbers,
+                strings,
+                sets,
+                ..
+            } = &agent.heap;
+            let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+            let other_data = sets[other].borrow(&primitive_heap);
+            let result_values = sets[o]
+                .borrow(&primitive_heap)
+                .values
+                .iter()
+                .flatten()
+                .copied()
+                .filter(|e| !other_data.has(&primitive_heap, *e))
+                .collect::<Vec<_>>();
+            let result_set_data = SetHeapData::from_unique_values(result_values, &primitive_heap);
+            return Ok(agent.heap.create(result_set_data).into_value());
+        }
+        // 4. Let resultSetData be a copy of O.[[SetData]].
+        let result = copy_set(agent, o, gc.nogc()).scope(agent, gc.nogc());
+        // 5. If SetDataSize(O.[[SetData]]) ≤ otherRec.[[Size]], then
+        if i64::from(agent[o].size()) <= other_rec.size.into_i64() {
+            // a. Let thisSize be the number of elements in O.[[SetData]].
+            let this_size = agent[o].values().len() as u32;
+            // b. Let index be 0.
+            let mut index = 0;
+            // c. Repeat, while index < thisSize,
+            while index < this_size {
+                // i. Let e be resultSetData[index].
+                let e = agent[result.get(agent)].values()[index as usize];
+                // ii. If e is not EMPTY, then
+                if let Some(e) = e {
+                    // 1. Let inOther be ToBoolean(? Call(otherRec.[[Has]], otherRec.[[SetObject]], « e »)).
+                    let in_other = call_function(
+                        agent,
+                        other_rec.has.get(agent),
+                        other_rec.set_object.get(agent).into_value(),
+                        Some(ArgumentsList(&[e])),
+                        gc.reborrow(),
+                    )?;
+                    let in_other = to_boolean(agent, in_other);
+                    // 2. If inOther is true, then
+                    if in_other {
+                        // a. Set resultSetData[index] to EMPTY.
+                        // Note: resultSetData is not exposed to JavaScript, so
+                        // the value at index must still be e.
+                        let result = result.get(agent);
+                        let e = agent[result].values()[index as usize].unwrap();
+                        set_data_remove(agent, result, e);
+                    }
+                }
+                // iii. Set index to index + 1.
+                index += 1;
+            }
         } else {
-            // 5. Return false.
-            Ok(false.into())
+            // 6. Else,
+            // a. Let keysIter be ? GetIteratorFromMethod(otherRec.[[SetObject]], otherRec.[[Keys]]).
+            let mut keys_iter = get_iterator_from_method(
+                agent,
+                other_rec.set_object.get(agent).into_value(),
+                other_rec.keys.get(agent),
+                gc.reborrow(),
+            )?;
+            // b. Let next be NOT-STARTED.
+            // c. Repeat, while next is not DONE,
+            // i. Set next to ? IteratorStepValue(keysIter).
+            // ii. If next is not DONE, then
+            while let Some(next) = iterator_step_value(agent, &mut keys_iter, gc.reborrow())? {
+                // 1. Set next to CanonicalizeKeyedCollectionKey(next).
+                let next = canonicalize_keyed_collection_key(agent, next);
+                // 2. Let valueIndex be SetDataIndex(resultSetData, next).
+                // 3. If valueIndex is not NOT-FOUND, then
+                // a. Set resultSetData[valueIndex] to EMPTY.
+                set_data_remove(agent, result.get(agent), next);
+            }
         }
+        // 7. Let result be OrdinaryObjectCreate(%Set.prototype%, « [[SetData]] »).
+        // 8. Set result.[[SetData]] to resultSetData.
+        // 9. Return result.
+        Ok(result.get(agent).into_value())
     }
 
     fn entries(
@@ -351,31 +463,379 @@ impl SetPrototype {
             ..
         } = &agent.heap;
         let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
-        let SetData {
-            values, set_data, ..
-        } = &sets[s].borrow(&primitive_heap);
-        let set_data = set_data.borrow();
 
         // 3. Set value to CanonicalizeKeyedCollectionKey(value).
         let value = canonicalize_keyed_collection_key(&primitive_heap, arguments.get(0));
-        let mut hasher = AHasher::default();
-        let value_hash = {
-            value.hash(&primitive_heap, &mut hasher);
-            hasher.finish()
-        };
         // 4. For each element e of S.[[SetData]], do
         // a. If e is not EMPTY and SameValue(e, value) is true, return true.
-        let found = set_data
-            .find(value_hash, |hash_equal_index| {
-                let found_value = values[*hash_equal_index as usize].unwrap();
-                // Quick check: Equal values have the same value.
-                found_value == value || same_value(&primitive_heap, found_value, value)
-            })
-            .is_some();
         // 5. Return false.
+        let found = sets[s].borrow(&primitive_heap).has(&primitive_heap, value);
         Ok(found.into())
     }
 
+    /// ### [24.2.4.9 Set.prototype.intersection ( other )](https://tc39.es/ecma262/#sec-set.prototype.intersection)
+    fn intersection(
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
+    ) -> JsResult<Value> {
+        let other = arguments.get(0);
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[SetData]]).
+        let o = require_set_data_internal_slot(agent, this_value, gc.nogc())?;
+        let scoped_o = o.scope(agent, gc.nogc());
+        // 3. Let otherRec be ? GetSetRecord(other).
+        let other_rec = get_set_record(agent, other, gc.reborrow())?;
+        let o = scoped_o.get(agent).bind(gc.nogc());
+        let this_is_smaller = i64::from(agent[o].size()) <= other_rec.size.into_i64();
+        if let Some(other) = other_rec.as_unobservable_set(agent, gc.nogc()) {
+            // Fast path: Calling otherRec.[[Has]] and iterating
+            // otherRec.[[Keys]] are both unobservable. The result contains
+            // the elements of the smaller Set that are in the larger one, in
+            // the smaller Set's order.
+            let Heap {
+                bigints,
+                numbers,
+                strings,
+                sets,
+                ..
+            } = &agent.heap;
+            let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+            let this_data = sets[o].borrow(&primitive_heap);
+            let other_data = sets[other].borrow(&primitive_heap);
+            let (iterated, checked) = if this_is_smaller {
+                (this_data, other_data)
+            } else {
+                (other_data, this_data)
+            };
+            let result_values = iterated
+                .values
+                .iter()
+                .flatten()
+                .copied()
+                .filter(|e| checked.has(&primitive_heap, *e))
+                .collect::<Vec<_>>();
+            let result_set_data = SetHeapData::from_unique_values(result_values, &primitive_heap);
+            return Ok(agent.heap.create(result_set_data).into_value());
+        }
+        // 4. Let resultSetData be a new empty List.
+        let result = agent
+            .heap
+            .create(SetHeapData::default())
+            .scope(agent, gc.nogc());
+        // 5. If SetDataSize(O.[[SetData]]) ≤ otherRec.[[Size]], then
+        if this_is_smaller {
+            // a. Let thisSize be the number of elements in O.[[SetData]].
+            let mut this_size = agent[o].values().len() as u32;
+            // b. Let index be 0.
+            let mut index = 0;
+            let mut scoped_e = Value::Undefined.scope(agent, gc.nogc());
+            // c. Repeat, while index < thisSize,
+            while index < this_size {
+                // i. Let e be O.[[SetData]][index].
+                let e = agent[scoped_o.get(agent)].values()[index as usize];
+                // ii. Set index to index + 1.
+                index += 1;
+                // iii. If e is not EMPTY, then
+                let Some(e) = e else {
+                    continue;
+                };
+                scoped_e.replace(agent, e);
+                // 1. Let inOther be ToBoolean(? Call(otherRec.[[Has]], otherRec.[[SetObject]], « e »)).
+                let in_other = call_function(
+                    agent,
+                    other_rec.has.get(agent),
+                    other_rec.set_object.get(agent).into_value(),
+                    Some(ArgumentsList(&[e])),
+                    gc.reborrow(),
+                )?;
+                let in_other = to_boolean(agent, in_other);
+                // 2. If inOther is true, then
+                if in_other {
+                    // a. NOTE: It is possible for earlier calls to
+                    //    otherRec.[[Has]] to remove and re-add an element of
+                    //    O.[[SetData]], which can cause elements to be visited
+                    //    more than once during this iteration.
+                    // b. If SetDataHas(resultSetData, e) is false, then
+                    // i. Append e to resultSetData.
+                    set_data_append(agent, result.get(agent), scoped_e.get(agent));
+                }
+                // 3. NOTE: The number of elements in O.[[SetData]] may have
+                //    increased during execution of otherRec.[[Has]].
+                // 4. Set thisSize to the number of elements in O.[[SetData]].
+                this_size = agent[scoped_o.get(agent)].values().len() as u32;
+            }
+        } else {
+            // 6. Else,
+            // a. Let keysIter be ? GetIteratorFromMethod(otherRec.[[SetObject]], otherRec.[[Keys]]).
+            let mut keys_iter = get_iterator_from_method(
+                agent,
+                other_rec.set_object.get(agent).into_value(),
+                other_rec.keys.get(agent),
+                gc.reborrow(),
+            )?;
+            // b. Let next be NOT-STARTED.
+            // c. Repeat, while next is not DONE,
+            // i. Set next to ? IteratorStepValue(keysIter).
+            // ii. If next is not DONE, then
+            while let Some(next) = iterator_step_value(agent, &mut keys_iter, gc.reborrow())? {
+                // 1. Set next to CanonicalizeKeyedCollectionKey(next).
+                let next = canonicalize_keyed_collection_key(agent, next);
+                // 2. Let inThis be SetDataHas(O.[[SetData]], next).
+                // 3. If inThis is true, then
+                if set_data_has(agent, scoped_o.get(agent), next) {
+                    // a. NOTE: Because other is an arbitrary object, it is
+                    //    possible for its "keys" iterator to produce the same
+                    //    value more than once.
+                    // b. If SetDataHas(resultSetData, next) is false, then
+                    // i. Append next to resultSetData.
+                    set_data_append(agent, result.get(agent), next);
+                }
+            }
+        }
+        // 7. Let result be OrdinaryObjectCreate(%Set.prototype%, « [[SetData]] »).
+        // 8. Set result.[[SetData]] to resultSetData.
+        // 9. Return result.
+        Ok(result.get(agent).into_value())
+    }
+
+    /// ### [24.2.4.10 Set.prototype.isDisjointFrom ( other )](https://tc39.es/ecma262/#sec-set.prototype.isdisjointfrom)
+    fn is_disjoint_from(
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
+    ) -> JsResult<Value> {
+        let other = arguments.get(0);
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[SetData]]).
+        let o = require_set_data_internal_slot(agent, this_value, gc.nogc())?;
+        let scoped_o = o.scope(agent, gc.nogc());
+        // 3. Let otherRec be ? GetSetRecord(other).
+        let other_rec = get_set_record(agent, other, gc.reborrow())?;
+        let o = scoped_o.get(agent).bind(gc.nogc());
+        let this_is_smaller = i64::from(agent[o].size()) <= other_rec.size.into_i64();
+        if let Some(other) = other_rec.as_unobservable_set(agent, gc.nogc()) {
+            // Fast path: Calling otherRec.[[Has]] and iterating
+            // otherRec.[[Keys]] are both unobservable.
+            let Heap {
+                bigints,
+                numbers,
+                strings,
+                sets,
+                ..
+            } = &agent.heap;
+            let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+            let this_data = sets[o].borrow(&primitive_heap);
+            let other_data = sets[other].borrow(&primitive_heap);
+            let (iterated, checked) = if this_is_smaller {
+                (this_data, other_data)
+            } else {
+                (other_data, this_data)
+            };
+            let is_disjoint = !iterated
+                .values
+                .iter()
+                .flatten()
+                .any(|e| checked.has(&primitive_heap, *e));
+            return Ok(is_disjoint.into());
+        }
+        // 4. If SetDataSize(O.[[SetData]]) ≤ otherRec.[[Size]], then
+        if this_is_smaller {
+            // a. Let thisSize be the number of elements in O.[[SetData]].
+            let mut this_size = agent[o].values().len() as u32;
+            // b. Let index be 0.
+            let mut index = 0;
+            // c. Repeat, while index < thisSize,
+            while index < this_size {
+                // i. Let e be O.[[SetData]][index].
+                let e = agent[scoped_o.get(agent)].values()[index as usize];
+                // ii. Set index to index + 1.
+                index += 1;
+                // iii. If e is not EMPTY, then
+                let Some(e) = e else {
+                    continue;
+                };
+                // 1. Let inOther be ToBoolean(? Call(otherRec.[[Has]], otherRec.[[SetObject]], « e »)).
+                let in_other = call_function(
+                    agent,
+                    other_rec.has.get(agent),
+                    other_rec.set_object.get(agent).into_value(),
+                    Some(ArgumentsList(&[e])),
+                    gc.reborrow(),
+                )?;
+                // 2. If inOther is true, return false.
+                if to_boolean(agent, in_other) {
+                    return Ok(false.into());
+                }
+                // 3. NOTE: The number of elements in O.[[SetData]] may have
+                //    increased during execution of otherRec.[[Has]].
+                // 4. Set thisSize to the number of elements in O.[[SetData]].
+                this_size = agent[scoped_o.get(agent)].values().len() as u32;
+            }
+        } else {
+            // 5. Else,
+            // a. Let keysIter be ? GetIteratorFromMethod(otherRec.[[SetObject]], otherRec.[[Keys]]).
+            let mut keys_iter = get_iterator_from_method(
+                agent,
+                other_rec.set_object.get(agent).into_value(),
+                other_rec.keys.get(agent),
+                gc.reborrow(),
+            )?;
+            // b. Let next be NOT-STARTED.
+            // c. Repeat, while next is not DONE,
+            // i. Set next to ? IteratorStepValue(keysIter).
+            // ii. If next is not DONE, then
+            while let Some(next) = iterator_step_value(agent, &mut keys_iter, gc.reborrow())? {
+                // 1. If SetDataHas(O.[[SetData]], next) is true, then
+                let next = canonicalize_keyed_collection_key(agent, next);
+                if set_data_has(agent, scoped_o.get(agent), next) {
+                    // a. Perform ? IteratorClose(keysIter, NormalCompletion(UNUSED)).
+                    // b. Return false.
+                    return iterator_close(agent, &keys_iter, Ok(false.into()), gc.reborrow());
+                }
+            }
+        }
+        // 6. Return true.
+        Ok(true.into())
+    }
+
+    /// ### [24.2.4.11 Set.prototype.isSubsetOf ( other )](https://tc39.es/ecma262/#sec-set.prototype.issubsetof)
+    fn is_subset_of(
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
+    ) -> JsResult<Value> {
+        let other = arguments.get(0);
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[SetData]]).
+        let o = require_set_data_internal_slot(agent, this_value, gc.nogc())?;
+        let scoped_o = o.scope(agent, gc.nogc());
+        // 3. Let otherRec be ? GetSetRecord(other).
+        let other_rec = get_set_record(agent, other, gc.reborrow())?;
+        let o = scoped_o.get(agent).bind(gc.nogc());
+        // 4. If SetDataSize(O.[[SetData]]) > otherRec.[[Size]], return false.
+        if i64::from(agent[o].size()) > other_rec.size.into_i64() {
+            return Ok(false.into());
+        }
+        if let Some(other) = other_rec.as_unobservable_set(agent, gc.nogc()) {
+            // Fast path: Calling otherRec.[[Has]] is unobservable.
+            let Heap {
+                bigints,
+                numbers,
+                strings,
+                sets,
+                ..
+            } = &agent.heap;
+            let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+            let other_data = sets[other].borrow(&primitive_heap);
+            let is_subset = sets[o]
+                .borrow(&primitive_heap)
+                .values
+                .iter()
+                .flatten()
+                .all(|e| other_data.has(&primitive_heap, *e));
+            return Ok(is_subset.into());
+        }
+        // 5. Let thisSize be the number of elements in O.[[SetData]].
+        let mut this_size = agent[o].values().len() as u32;
+        // 6. Let index be 0.
+        let mut index = 0;
+        // 7. Repeat, while index < thisSize,
+        while index < this_size {
+            // a. Let e be O.[[SetData]][index].
+            let e = agent[scoped_o.get(agent)].values()[index as usize];
+            // b. Set index to index + 1.
+            index += 1;
+            // c. If e is not EMPTY, then
+            let Some(e) = e else {
+                continue;
+            };
+            // i. Let inOther be ToBoolean(? Call(otherRec.[[Has]], otherRec.[[SetObject]], « e »)).
+            let in_other = call_function(
+                agent,
+                other_rec.has.get(agent),
+                other_rec.set_object.get(agent).into_value(),
+                Some(ArgumentsList(&[e])),
+                gc.reborrow(),
+            )?;
+            // ii. If inOther is false, return false.
+            if !to_boolean(agent, in_other) {
+                return Ok(false.into());
+            }
+            // iii. NOTE: The number of elements in O.[[SetData]] may have
+            //      increased during execution of otherRec.[[Has]].
+            // iv. Set thisSize to the number of elements in O.[[SetData]].
+            this_size = agent[scoped_o.get(agent)].values().len() as u32;
+        }
+        // 8. Return true.
+        Ok(true.into())
+    }
+
+    /// ### [24.2.4.12 Set.prototype.isSupersetOf ( other )](https://tc39.es/ecma262/#sec-set.prototype.issupersetof)
+    fn is_superset_of(
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
+    ) -> JsResult<Value> {
+        let other = arguments.get(0);
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[SetData]]).
+        let o = require_set_data_internal_slot(agent, this_value, gc.nogc())?;
+        let scoped_o = o.scope(agent, gc.nogc());
+        // 3. Let otherRec be ? GetSetRecord(other).
+        let other_rec = get_set_record(agent, other, gc.reborrow())?;
+        let o = scoped_o.get(agent).bind(gc.nogc());
+        // 4. If SetDataSize(O.[[SetData]]) < otherRec.[[Size]], return false.
+        if i64::from(agent[o].size()) < other_rec.size.into_i64() {
+            return Ok(false.into());
+        }
+        if let Some(other) = other_rec.as_unobservable_set(agent, gc.nogc()) {
+            // Fast path: Iterating otherRec.[[Keys]] is unobservable.
+            let Heap {
+                bigints,
+                numbers,
+                strings,
+                sets,
+                ..
+            } = &agent.heap;
+            let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+            let this_data = sets[o].borrow(&primitive_heap);
+            let is_superset = sets[other]
+                .borrow(&primitive_heap)
+                .values
+                .iter()
+                .flatten()
+                .all(|next| this_data.has(&primitive_heap, *next));
+            return Ok(is_superset.into());
+        }
+        // 5. Let keysIter be ? GetIteratorFromMethod(otherRec.[[SetObject]], otherRec.[[Keys]]).
+        let mut keys_iter = get_iterator_from_method(
+            agent,
+            other_rec.set_object.get(agent).into_value(),
+            other_rec.keys.get(agent),
+            gc.reborrow(),
+        )?;
+        // 6. Let next be NOT-STARTED.
+        // 7. Repeat, while next is not DONE,
+        // a. Set next to ? IteratorStepValue(keysIter).
+        // b. If next is not DONE, then
+        while let Some(next) = iterator_step_value(agent, &mut keys_iter, gc.reborrow())? {
+            // i. If SetDataHas(O.[[SetData]], next) is false, then
+            let next = canonicalize_keyed_collection_key(agent, next);
+            if !set_data_has(agent, scoped_o.get(agent), next) {
+                // 1. Perform ? IteratorClose(keysIter, NormalCompletion(UNUSED)).
+                // 2. Return false.
+                return iterator_close(agent, &keys_iter, Ok(false.into()), gc.reborrow());
+            }
+        }
+        // 8. Return true.
+        Ok(true.into())
+    }
+
     /// ### [24.2.4.14 get Set.prototype.size](https://tc39.es/ecma262/#sec-get-set.prototype.size)
     ///
     /// Set.prototype.size is an accessor property whose set accessor function
@@ -396,6 +856,162 @@ impl SetPrototype {
         Ok(Number::from(size).into_value())
     }
 
+    /// ### [24.2.4.15 Set.prototype.symmetricDifference ( other )](https://tc39.es/ecma262/#sec-set.prototype.symmetricdifference)
+    fn symmetric_difference(
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
+    ) -> JsResult<Value> {
+        let other = arguments.get(0);
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[SetData]]).
+        let o = require_set_data_internal_slot(agent, this_value, gc.nogc())?;
+        let scoped_o = o.scope(agent, gc.nogc());
+        // 3. Let otherRec be ? GetSetRecord(other).
+        let other_rec = get_set_record(agent, other, gc.reborrow())?;
+        let o = scoped_o.get(agent).bind(gc.nogc());
+        if let Some(other) = other_rec.as_unobservable_set(agent, gc.nogc()) {
+            // Fast path: Iterating otherRec.[[Keys]] is unobservable. The
+            // result contains the elements of O that are not in other,
+            // followed by the elements of other that are not in O.
+            let Heap {
+                bigints,
+                numbers,
+                strings,
+                sets,
+                ..
+            } = &agent.heap;
+            let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+            let this_data = sets[o].borrow(&primitive_heap);
+            let other_data = sets[other].borrow(&primitive_heap);
+            let mut result_values = this_data
+                .values
+                .iter()
+                .flatten()
+                .copied()
+                .filter(|e| !other_data.has(&primitive_heap, *e))
+                .collect::<Vec<_>>();
+            result_values.extend(
+                other_data
+                    .values
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .filter(|next| !this_data.has(&primitive_heap, *next)),
+            );
+            let result_set_data = SetHeapData::from_unique_values(result_values, &primitive_heap);
+            return Ok(agent.heap.create(result_set_data).into_value());
+        }
+        // 4. Let keysIter be ? GetIteratorFromMethod(otherRec.[[SetObject]], otherRec.[[Keys]]).
+        let mut keys_iter = get_iterator_from_method(
+            agent,
+            other_rec.set_object.get(agent).into_value(),
+            other_rec.keys.get(agent),
+            gc.reborrow(),
+        )?;
+        // 5. Let resultSetData be a copy of O.[[SetData]].
+        let o = scoped_o.get(agent).bind(gc.nogc());
+        let result = copy_set(agent, o, gc.nogc()).scope(agent, gc.nogc());
+        // 6. Let next be NOT-STARTED.
+        // 7. Repeat, while next is not DONE,
+        // a. Set next to ? IteratorStepValue(keysIter).
+        // b. If next is not DONE, then
+        while let Some(next) = iterator_step_value(agent, &mut keys_iter, gc.reborrow())? {
+            // i. Set next to CanonicalizeKeyedCollectionKey(next).
+            let next = canonicalize_keyed_collection_key(agent, next);
+            // ii. Let resultIndex be SetDataIndex(resultSetData, next).
+            // iii. If resultIndex is NOT-FOUND, let alreadyInResult be false.
+            //      Otherwise let alreadyInResult be true.
+            // iv. If SetDataHas(O.[[SetData]], next) is true, then
+            if set_data_has(agent, scoped_o.get(agent), next) {
+                // 1. If alreadyInResult is true, set resultSetData[resultIndex] to EMPTY.
+                set_data_remove(agent, result.get(agent), next);
+            } else {
+                // v. Else,
+                // 1. If alreadyInResult is false, append next to resultSetData.
+                set_data_append(agent, result.get(agent), next);
+            }
+        }
+        // 8. Let result be OrdinaryObjectCreate(%Set.prototype%, « [[SetData]] »).
+        // 9. Set result.[[SetData]] to resultSetData.
+        // 10. Return result.
+        Ok(result.get(agent).into_value())
+    }
+
+    /// ### [24.2.4.16 Set.prototype.union ( other )](https://tc39.es/ecma262/#sec-set.prototype.union)
+    fn union(
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
+    ) -> JsResult<Value> {
+        let other = arguments.get(0);
+        // 1. Let O be the this value.
+        // 2. Perform ? RequireInternalSlot(O, [[SetData]]).
+        let o = require_set_data_internal_slot(agent, this_value, gc.nogc())?;
+        let scoped_o = o.scope(agent, gc.nogc());
+        // 3. Let otherRec be ? GetSetRecord(other).
+        let other_rec = get_set_record(agent, other, gc.reborrow())?;
+        let o = scoped_o.get(agent).bind(gc.nogc());
+        if let Some(other) = other_rec.as_unobservable_set(agent, gc.nogc()) {
+            // Fast path: Iterating otherRec.[[Keys]] is unobservable. The
+            // result contains the elements of O followed by the elements of
+            // other that are not in O.
+            let Heap {
+                bigints,
+                numbers,
+                strings,
+                sets,
+                ..
+            } = &agent.heap;
+            let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+            let this_data = sets[o].borrow(&primitive_heap);
+            let other_data = sets[other].borrow(&primitive_heap);
+            let mut result_values = this_data
+                .values
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>();
+            result_values.extend(
+                other_data
+                    .values
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .filter(|next| !this_data.has(&primitive_heap, *next)),
+            );
+            let result_set_data = SetHeapData::from_unique_values(result_values, &primitive_heap);
+            return Ok(agent.heap.create(result_set_data).into_value());
+        }
+        // 4. Let keysIter be ? GetIteratorFromMethod(otherRec.[[SetObject]], otherRec.[[Keys]]).
+        let mut keys_iter = get_iterator_from_method(
+            agent,
+            other_rec.set_object.get(agent).into_value(),
+            other_rec.keys.get(agent),
+            gc.reborrow(),
+        )?;
+        // 5. Let resultSetData be a copy of O.[[SetData]].
+        let o = scoped_o.get(agent).bind(gc.nogc());
+        let result = copy_set(agent, o, gc.nogc()).scope(agent, gc.nogc());
+        // 6. Let next be NOT-STARTED.
+        // 7. Repeat, while next is not DONE,
+        // a. Set next to ? IteratorStepValue(keysIter).
+        // b. If next is not DONE, then
+        while let Some(next) = iterator_step_value(agent, &mut keys_iter, gc.reborrow())? {
+            // i. Set next to CanonicalizeKeyedCollectionKey(next).
+            let next = canonicalize_keyed_collection_key(agent, next);
+            // ii. If SetDataHas(resultSetData, next) is false, then
+            // 1. Append next to resultSetData.
+            set_data_append(agent, result.get(agent), next);
+        }
+        // 8. Let result be OrdinaryObjectCreate(%Set.prototype%, « [[SetData]] »).
+        // 9. Set result.[[SetData]] to resultSetData.
+        // 10. Return result.
+        Ok(result.get(agent).into_value())
+    }
+
     fn values(
         agent: &mut Agent,
         this_value: Value,
@@ -420,15 +1036,20 @@ impl SetPrototype {
         let set_prototype_values = intrinsics.set_prototype_values();
 
         OrdinaryObjectBuilder::new_intrinsic_object(agent, realm, this)
-            .with_property_capacity(12)
+            .with_property_capacity(19)
             .with_prototype(object_prototype)
             .with_builtin_function_property::<SetPrototypeAdd>()
             .with_builtin_function_property::<SetPrototypeClear>()
             .with_constructor_property(set_constructor)
             .with_builtin_function_property::<SetPrototypeDelete>()
+            .with_builtin_function_property::<SetPrototypeDifference>()
             .with_builtin_function_property::<SetPrototypeEntries>()
             .with_builtin_function_property::<SetPrototypeForEach>()
-            .with_builtin_function_property::<SetPrototypeHas>()
+            .with_builtin_intrinsic_function_property::<SetPrototypeHas>()
+            .with_builtin_function_property::<SetPrototypeIntersection>()
+            .with_builtin_function_property::<SetPrototypeIsDisjointFrom>()
+            .with_builtin_function_property::<SetPrototypeIsSubsetOf>()
+            .with_builtin_function_property::<SetPrototypeIsSupersetOf>()
             .with_property(|builder| {
                 builder
                     .with_key(BUILTIN_STRING_MEMORY.keys.to_property_key())
@@ -438,6 +1059,8 @@ impl SetPrototype {
                     .build()
             })
             .with_builtin_function_getter_property::<SetPrototypeGetSize>()
+            .with_builtin_function_property::<SetPrototypeSymmetricDifference>()
+            .with_builtin_function_property::<SetPrototypeUnion>()
             .with_builtin_intrinsic_function_property::<SetPrototypeValues>()
             .with_property(|builder| {
                 builder
@@ -487,3 +1110,345 @@ fn set_data_size(set_data: &SetHeapData) -> u32 {
     // 3. Return count.
     set_data.size()
 }
+
+/// ### [24.2.1.1 Set Records](https://tc39.es/ecma262/#sec-set-records)
+///
+/// A Set Record is a Record value used to encapsulate the interface of a Set
+/// or similar object.
+struct SetRecord<'a> {
+    /// \[\[SetObject]]
+    ///
+    /// The Set or similar object.
+    set_object: Scoped<'a, Object<'static>>,
+    /// \[\[Size]]
+    ///
+    /// The reported size of the object.
+    size: IntegerOrInfinity,
+    /// \[\[Has]]
+    ///
+    /// The has method of the object.
+    has: Scoped<'a, Function<'static>>,
+    /// \[\[Keys]]
+    ///
+    /// The keys method of the object.
+    keys: Scoped<'a, Function<'static>>,
+}
+
+impl SetRecord<'_> {
+    /// Returns the \[\[SetObject]] as a Set if calling \[\[Has]] and
+    /// iterating \[\[Keys]] cannot be observed by JavaScript: The object is a
+    /// Set, \[\[Has]] and \[\[Keys]] are %Set.prototype.has% and
+    /// %Set.prototype.values%, and %SetIteratorPrototype%.next has not been
+    /// replaced. In that case the Set's data can be accessed directly.
+    fn as_unobservable_set<'a>(&self, agent: &mut Agent, gc: NoGcScope<'a, '_>) -> Option<Set<'a>> {
+        let Object::Set(set) = self.set_object.get(agent) else {
+            return None;
+        };
+        let intrinsics = agent.current_realm().intrinsics();
+        let set_prototype_has = intrinsics.set_prototype_has().into_function();
+        let set_prototype_values = intrinsics.set_prototype_values().into_function();
+        let set_iterator_prototype = intrinsics.set_iterator_prototype();
+        let set_iterator_prototype_next = intrinsics.set_iterator_prototype_next().into_value();
+        if self.has.get(agent) != set_prototype_has || self.keys.get(agent) != set_prototype_values
+        {
+            return None;
+        }
+        let next = try_get(
+            agent,
+            set_iterator_prototype,
+            BUILTIN_STRING_MEMORY.next.into(),
+            gc,
+        );
+        if next != TryResult::Continue(set_iterator_prototype_next) {
+            return None;
+        }
+        Some(set.bind(gc))
+    }
+}
+
+/// ### [24.2.1.2 GetSetRecord ( obj )](https://tc39.es/ecma262/#sec-getsetrecord)
+///
+/// The abstract operation GetSetRecord takes argument obj (an ECMAScript
+/// language value) and returns either a normal completion containing a Set
+/// Record or a throw completion.
+fn get_set_record<'a>(
+    agent: &mut Agent,
+    obj: Value,
+    mut gc: GcScope<'_, 'a>,
+) -> JsResult<SetRecord<'a>> {
+    // 1. If obj is not an Object, throw a TypeError exception.
+    let Ok(obj) = Object::try_from(obj) else {
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::TypeError,
+            "Argument is not an object",
+            gc.nogc(),
+        ));
+    };
+    let obj = obj.scope(agent, gc.nogc());
+    // 2. Let rawSize be ? Get(obj, "size").
+    let raw_size = get(
+        agent,
+        obj.get(agent),
+        BUILTIN_STRING_MEMORY.size.into(),
+        gc.reborrow(),
+    )?;
+    // 3. Let numSize be ? ToNumber(rawSize).
+    // 4. NOTE: If rawSize is undefined, then numSize will be NaN.
+    let num_size = to_number(agent, raw_size, gc.reborrow())?
+        .unbind()
+        .bind(gc.nogc());
+    // 5. If numSize is NaN, throw a TypeError exception.
+    if num_size.is_nan(agent) {
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::TypeError,
+            "Set-like size is NaN",
+            gc.nogc(),
+        ));
+    }
+    // 6. Let intSize be ! ToIntegerOrInfinity(numSize).
+    let int_size = to_integer_or_infinity_number(agent, num_size, gc.nogc());
+    // 7. If intSize < 0, throw a RangeError exception.
+    if int_size.is_negative() {
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::RangeError,
+            "Set-like size is negative",
+            gc.nogc(),
+        ));
+    }
+    // 8. Let has be ? Get(obj, "has").
+    let has = get(
+        agent,
+        obj.get(agent),
+        BUILTIN_STRING_MEMORY.has.into(),
+        gc.reborrow(),
+    )?;
+    // 9. If IsCallable(has) is false, throw a TypeError exception.
+    let Some(has) = is_callable(has, gc.nogc()) else {
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::TypeError,
+            "Set-like has is not a function",
+            gc.nogc(),
+        ));
+    };
+    let has = has.scope(agent, gc.nogc());
+    // 10. Let keys be ? Get(obj, "keys").
+    let keys = get(
+        agent,
+        obj.get(agent),
+        BUILTIN_STRING_MEMORY.keys.into(),
+        gc.reborrow(),
+    )?;
+    // 11. If IsCallable(keys) is false, throw a TypeError exception.
+    let Some(keys) = is_callable(keys, gc.nogc()) else {
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::TypeError,
+            "Set-like keys is not a function",
+            gc.nogc(),
+        ));
+    };
+    let keys = keys.scope(agent, gc.nogc());
+    // 12. Return a new Set Record { [[SetObject]]: obj, [[Size]]: intSize, [[Has]]: has, [[Keys]]: keys }.
+    Ok(SetRecord {
+        set_object: obj,
+        size: int_size,
+        has,
+        keys,
+    })
+}
+
+/// ### [24.2.1.3 SetDataHas ( setData, value )](https://tc39.es/ecma262/#sec-setdatahas)
+///
+/// > NOTE: The value must already be canonicalized.
+fn set_data_has(agent: &Agent, s: Set, value: Value) -> bool {
+    let Heap {
+        bigints,
+        numbers,
+        strings,
+        sets,
+        ..
+    } = &agent.heap;
+    let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+    sets[s].borrow(&primitive_heap).has(&primitive_heap, value)
+}
+
+/// Append value to the \[\[SetData]] of the Set if SetDataHas is false.
+///
+/// > NOTE: The value must already be canonicalized.
+fn set_data_append(agent: &mut Agent, s: Set, value: Value) {
+    let Heap {
+        bigints,
+        numbers,
+        strings,
+        sets,
+        ..
+    } = &mut agent.heap;
+    let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+    sets[s]
+        .borrow_mut(&primitive_heap)
+        .append(&primitive_heap, value);
+}
+
+/// Replace the element of the \[\[SetData]] of the Set whose value is value
+/// with EMPTY.
+///
+/// > NOTE: The value must already be canonicalized.
+fn set_data_remove(agent: &mut Agent, s: Set, value: Value) {
+    let Heap {
+        bigints,
+        numbers,
+        strings,
+        sets,
+        ..
+    } = &mut agent.heap;
+    let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+    sets[s]
+        .borrow_mut(&primitive_heap)
+        .remove(&primitive_heap, value);
+}
+
+/// Create a new Set whose \[\[SetData]] is a copy of the \[\[SetData]] of s.
+fn copy_set<'a>(agent: &mut Agent, s: Set, gc: NoGcScope<'a, '_>) -> Set<'a> {
+    let Heap {
+        bigints,
+        numbers,
+        strings,
+        sets,
+        ..
+    } = &agent.heap;
+    let primitive_heap = PrimitiveHeap::new(bigints, numbers, strings);
+    let set_data = sets[s].copy_set_data(&primitive_heap);
+    agent.heap.create(set_data).bind(gc)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::TestAgent;
+
+    #[test]
+    fn union_keeps_this_order_then_other_order() {
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval("[...new Set([3, 1]).union(new Set([2, 1, 4]))].join()"),
+            "3,1,2,4"
+        );
+        assert_eq!(
+            agent.eval("[...new Set([3, 1]).union(new Map([[2, 0], [1, 0]]))].join()"),
+            "3,1,2"
+        );
+    }
+
+    #[test]
+    fn intersection_iterates_the_smaller_set() {
+        let mut agent = TestAgent::new();
+        // This is smaller: The result is in the order of this.
+        assert_eq!(
+            agent.eval("[...new Set([1, 2, 3]).intersection(new Set([4, 3, 2, 5]))].join()"),
+            "2,3"
+        );
+        // Other is smaller: The result is in the order of other.
+        assert_eq!(
+            agent.eval("[...new Set([1, 2, 3]).intersection(new Set([3, 2]))].join()"),
+            "3,2"
+        );
+    }
+
+    #[test]
+    fn fast_paths_use_the_reported_size_of_a_set() {
+        let mut agent = TestAgent::new();
+        agent.eval(
+            "var small = new Set([3, 2]);
+            Object.defineProperty(small, 'size', { value: 0 });
+            var big = new Set([2, 3]);
+            Object.defineProperty(big, 'size', { value: Infinity });",
+        );
+        assert_eq!(
+            agent.eval("[...new Set([2, 3]).intersection(small)].join()"),
+            "3,2"
+        );
+        assert_eq!(
+            agent.eval("[...new Set([3, 2]).intersection(big)].join()"),
+            "3,2"
+        );
+        assert_eq!(agent.eval("new Set([2]).isSubsetOf(small)"), "false");
+        assert_eq!(agent.eval("new Set([2]).isSubsetOf(big)"), "true");
+        assert_eq!(agent.eval("new Set([2, 3, 4]).isSupersetOf(big)"), "false");
+        assert_eq!(agent.eval("new Set([2, 3, 4]).isSupersetOf(small)"), "true");
+        assert_eq!(agent.eval("new Set([1]).isDisjointFrom(small)"), "true");
+        assert_eq!(agent.eval("new Set([2]).isDisjointFrom(big)"), "false");
+    }
+
+    #[test]
+    fn set_like_with_lying_size_picks_the_observable_path() {
+        let mut agent = TestAgent::new();
+        agent.eval(
+            "var calls = [];
+            var setLike = {
+                size: Infinity,
+                has(v) { calls.push('has ' + v); return v === 2; },
+                keys() { calls.push('keys'); return [2, 5][Symbol.iterator](); },
+            };",
+        );
+        // SetDataSize(O) ≤ size: Only has is called, for every element of O.
+        assert_eq!(
+            agent.eval("[...new Set([1, 2]).difference(setLike)].join() + ';' + calls.join()"),
+            "1;has 1,has 2"
+        );
+        // SetDataSize(O) > size: Only keys is called, even though the keys
+        // produce more values than the size claims.
+        assert_eq!(
+            agent.eval(
+                "calls = []; setLike.size = 0;
+                [...new Set([1, 2]).difference(setLike)].join() + ';' + calls.join()"
+            ),
+            "1;keys"
+        );
+        assert_eq!(
+            agent.eval("calls = []; new Set([1, 2]).isSubsetOf(setLike) + ';' + calls.join()"),
+            "false;"
+        );
+        assert_eq!(
+            agent.eval(
+                "calls = []; setLike.size = 3;
+                new Set([1, 2]).isSupersetOf(setLike) + ';' + calls.join()"
+            ),
+            "false;"
+        );
+        assert_eq!(
+            agent.eval(
+                "calls = []; setLike.size = 0;
+                [...new Set([1]).symmetricDifference(setLike)].join() + ';' + calls.join()"
+            ),
+            "1,2,5;keys"
+        );
+    }
+
+    #[test]
+    fn overridden_has_disables_the_fast_path() {
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval(
+                "var other = new Set([1, 2, 3]);
+                other.has = () => true;
+                new Set([1, 2, 4]).isSubsetOf(other)"
+            ),
+            "true"
+        );
+    }
+
+    #[test]
+    fn invalid_set_record_size_throws() {
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval_err("new Set().union({ has() {}, keys() {} })"),
+            "TypeError: Set-like size is NaN"
+        );
+        assert_eq!(
+            agent.eval_err("new Set().union({ size: -1, has() {}, keys() {} })"),
+            "RangeError: Set-like size is negative"
+        );
+        assert_eq!(
+            agent.eval_err("new Set().union({ size: 0, has: 1, keys() {} })"),
+            "TypeError: Set-like has is not a function"
+        );
+    }
+}
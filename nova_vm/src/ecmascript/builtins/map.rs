@@ -18,7 +18,7 @@ use crate::{
     },
     heap::{
         indexes::{BaseIndex, MapIndex},
-        CompactionLists, CreateHeapData, HeapMarkAndSweep, WorkQueues,
+        CompactionLists, CreateHeapData, HeapMarkAndSweep, OwnedAllocations, WorkQueues,
     },
     Heap,
 };
@@ -190,6 +190,7 @@ impl Rootable for Map<'_> {
 
 impl CreateHeapData<MapHeapData, Map<'static>> for Heap {
     fn create(&mut self, data: MapHeapData) -> Map<'static> {
+        self.owned_bytes += data.owned_bytes();
         self.maps.push(Some(data));
         Map(MapIndex::last(&self.maps))
     }
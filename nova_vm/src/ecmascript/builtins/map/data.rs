@@ -7,7 +7,9 @@ use crate::{
         bigint::HeapBigInt, HeapNumber, HeapPrimitive, HeapString, OrdinaryObject, Value,
         BIGINT_DISCRIMINANT, NUMBER_DISCRIMINANT, STRING_DISCRIMINANT,
     },
-    heap::{CompactionLists, HeapMarkAndSweep, PrimitiveHeapIndexable, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, OwnedAllocations, PrimitiveHeapIndexable, WorkQueues,
+    },
 };
 use ahash::AHasher;
 use hashbrown::{hash_table::Entry, HashTable};
@@ -167,6 +169,19 @@ fn rehash_map_data(
     }
 }
 
+impl OwnedAllocations for MapHeapData {
+    fn owned_bytes(&self) -> usize {
+        let MapData {
+            keys,
+            values,
+            map_data,
+            needs_primitive_rehashing: _,
+        } = &self.map_data;
+        (keys.capacity() + values.capacity()) * size_of::<Option<Value>>()
+            + map_data.borrow().capacity() * size_of::<u32>()
+    }
+}
+
 impl HeapMarkAndSweep for MapHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
@@ -5,6 +5,7 @@
 use crate::engine::context::{GcScope, NoGcScope};
 use crate::{
     ecmascript::{
+        abstract_operations::type_conversion::to_integer_or_infinity,
         builders::ordinary_object_builder::OrdinaryObjectBuilder,
         builtins::{primitive_objects::PrimitiveObjectData, ArgumentsList, Builtin},
         execution::{agent::ExceptionType, Agent, JsResult, RealmIdentifier},
@@ -55,20 +56,40 @@ impl BigIntPrototype {
         Self::to_string(agent, this_value, arguments, gc)
     }
 
+    /// ### [21.2.3.3 BigInt.prototype.toString ( \[ radix \] )](https://tc39.es/ecma262/#sec-bigint.prototype.tostring)
     fn to_string(
         agent: &mut Agent,
         this_value: Value,
         arguments: ArgumentsList,
-        gc: GcScope,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        let _x = this_big_int_value(agent, this_value, gc.nogc())?;
         let radix = arguments.get(0);
-        if radix.is_undefined() || radix == Value::from(10u8) {
-            // BigInt::to_string_radix_10(agent, x).map(|result| result.into_value())
-            todo!();
+        // 1. Let x be ? ThisBigIntValue(this value).
+        let mut x = this_big_int_value(agent, this_value, gc.nogc())?
+            .unbind()
+            .bind(gc.nogc());
+        let radix_mv = if radix.is_undefined() {
+            // 2. If radix is undefined, let radixMV be 10.
+            10
+        } else if let Value::Integer(radix) = radix {
+            radix.into_i64()
         } else {
-            todo!();
+            // 3. Else, let radixMV be ? ToIntegerOrInfinity(radix).
+            let scoped_x = x.scope(agent, gc.nogc());
+            let radix_mv = to_integer_or_infinity(agent, radix, gc.reborrow())?.into_i64();
+            x = scoped_x.get(agent).bind(gc.nogc());
+            radix_mv
+        };
+        // 4. If radixMV is not in the inclusive interval from 2 to 36, throw a RangeError exception.
+        if !(2..=36).contains(&radix_mv) {
+            return Err(agent.throw_exception_with_static_message(
+                ExceptionType::RangeError,
+                "radix must be an integer at least 2 and no greater than 36",
+                gc.nogc(),
+            ));
         }
+        // 5. Return BigInt::toString(x, radixMV).
+        Ok(BigInt::to_string_radix(agent, x, radix_mv as u32, gc.nogc()).into_value())
     }
 
     fn value_of(
@@ -28,13 +28,14 @@ use crate::{
             BUILTIN_STRING_MEMORY,
         },
     },
-    heap::{CompactionLists, CreateHeapData, HeapMarkAndSweep, WellKnownSymbolIndexes, WorkQueues},
+    heap::{
+        CompactionLists, CreateHeapData, HeapMarkAndSweep, SnapshotData, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WellKnownSymbolIndexes, WorkQueues,
+    },
 };
 
 #[cfg(feature = "date")]
 use super::date::data::DateHeapData;
-#[cfg(feature = "regexp")]
-use super::regexp::RegExpHeapData;
 #[cfg(feature = "shared-array-buffer")]
 use super::shared_array_buffer::data::SharedArrayBufferHeapData;
 use super::{
@@ -44,7 +45,9 @@ use super::{
     indexed_collections::array_objects::array_iterator_objects::array_iterator::ArrayIteratorHeapData,
     keyed_collections::map_objects::map_iterator_objects::map_iterator::MapIteratorHeapData,
     map::data::MapHeapData, module::Module, primitive_objects::PrimitiveObjectHeapData,
-    promise::data::PromiseHeapData, ArrayHeapData,
+    promise::data::PromiseHeapData,
+    text_processing::string_objects::string_iterator_objects::string_iterator::StringIteratorHeapData,
+    ArrayHeapData,
 };
 #[cfg(feature = "array-buffer")]
 use super::{
@@ -55,6 +58,11 @@ use super::{
     keyed_collections::set_objects::set_iterator_objects::set_iterator::SetIteratorHeapData,
     set::data::SetHeapData,
 };
+#[cfg(feature = "regexp")]
+use super::{
+    regexp::RegExpHeapData,
+    text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIteratorHeapData,
+};
 #[cfg(feature = "weak-refs")]
 use super::{
     weak_map::data::WeakMapHeapData, weak_ref::data::WeakRefHeapData,
@@ -1097,7 +1105,7 @@ pub(crate) fn ordinary_own_property_keys<'a>(
     object: OrdinaryObject<'a>,
     _: NoGcScope<'a, '_>,
 ) -> Vec<PropertyKey<'a>> {
-    let object_keys = agent[object].keys;
+    let object_keys = agent[object].shape.keys(agent);
     // 1. Let keys be a new empty List.
     let mut integer_keys = vec![];
     let mut keys = Vec::with_capacity(object_keys.len() as usize);
@@ -1105,10 +1113,11 @@ pub(crate) fn ordinary_own_property_keys<'a>(
 
     // 3. For each own property key P of O such that P is a String and P is not an array index, in
     //    ascending chronological order of property creation, do
-    for key in agent[object_keys].iter() {
+    // Removed keys of dictionary mode shapes leave tombstones behind.
+    for key in agent[object_keys].iter().flatten() {
         // SAFETY: Keys are all PropertyKeys reinterpreted as Values without
         // conversion.
-        let key = unsafe { PropertyKey::from_value_unchecked(key.unwrap()) };
+        let key = unsafe { PropertyKey::from_value_unchecked(*key) };
         match key {
             PropertyKey::Integer(integer_key) => {
                 let key_value = integer_key.into_i64();
@@ -1140,7 +1149,7 @@ pub(crate) fn ordinary_own_property_keys<'a>(
         keys.extend(symbol_keys.iter().map(|key| PropertyKey::Symbol(*key)));
     }
 
-    debug_assert_eq!(keys.len() as u32, object_keys.len());
+    debug_assert_eq!(keys.len(), agent[object_keys].iter().flatten().count());
 
     // 5. Return keys.
     keys
@@ -1243,6 +1252,10 @@ pub(crate) fn ordinary_object_create_with_intrinsics<'a>(
                 String::EMPTY_STRING,
             ))
             .into_object(),
+        ProtoIntrinsics::StringIterator => agent
+            .heap
+            .create(StringIteratorHeapData::default())
+            .into_object(),
         ProtoIntrinsics::Symbol => agent
             .heap
             .create(PrimitiveObjectHeapData::new_symbol_object(Symbol::from(
@@ -1334,6 +1347,11 @@ pub(crate) fn ordinary_object_create_with_intrinsics<'a>(
         ProtoIntrinsics::Promise => agent.heap.create(PromiseHeapData::default()).into_object(),
         #[cfg(feature = "regexp")]
         ProtoIntrinsics::RegExp => agent.heap.create(RegExpHeapData::default()).into_object(),
+        #[cfg(feature = "regexp")]
+        ProtoIntrinsics::RegExpStringIterator => agent
+            .heap
+            .create(RegExpStringIteratorHeapData::default())
+            .into_object(),
         #[cfg(feature = "set")]
         ProtoIntrinsics::Set => agent.heap.create(SetHeapData::default()).into_object(),
         #[cfg(feature = "set")]
@@ -1505,6 +1523,8 @@ pub(crate) fn get_prototype_from_constructor<'a>(
             ProtoIntrinsics::ReferenceError => Some(intrinsics.reference_error().into_function()),
             #[cfg(feature = "regexp")]
             ProtoIntrinsics::RegExp => Some(intrinsics.reg_exp().into_function()),
+            #[cfg(feature = "regexp")]
+            ProtoIntrinsics::RegExpStringIterator => None,
             #[cfg(feature = "set")]
             ProtoIntrinsics::Set => Some(intrinsics.set().into_function()),
             #[cfg(feature = "set")]
@@ -1514,6 +1534,7 @@ pub(crate) fn get_prototype_from_constructor<'a>(
                 Some(intrinsics.shared_array_buffer().into_function())
             }
             ProtoIntrinsics::String => Some(intrinsics.string().into_function()),
+            ProtoIntrinsics::StringIterator => None,
             ProtoIntrinsics::Symbol => Some(intrinsics.symbol().into_function()),
             ProtoIntrinsics::SyntaxError => Some(intrinsics.syntax_error().into_function()),
             ProtoIntrinsics::TypeError => Some(intrinsics.type_error().into_function()),
@@ -1601,3 +1622,13 @@ impl HeapMarkAndSweep for OrdinaryObject<'static> {
         compactions.objects.shift_index(&mut self.0);
     }
 }
+
+impl SnapshotData for OrdinaryObject<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
@@ -26,7 +26,7 @@ use crate::{
     engine::small_f64::SmallF64,
     heap::{
         indexes::PrimitiveObjectIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep,
-        WorkQueues,
+        SnapshotData, SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
     },
     SmallInteger,
 };
@@ -720,6 +720,43 @@ impl HeapMarkAndSweep for PrimitiveObjectHeapData {
     }
 }
 
+impl SnapshotData for PrimitiveObjectHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self { object_index, data } = self;
+        object_index.serialize(writer)?;
+        // The primitive data is written as the equivalent Value.
+        let value = match *data {
+            PrimitiveObjectData::Boolean(data) => Value::Boolean(data),
+            PrimitiveObjectData::String(data) => Value::String(data),
+            PrimitiveObjectData::SmallString(data) => Value::SmallString(data),
+            PrimitiveObjectData::Symbol(data) => Value::Symbol(data),
+            PrimitiveObjectData::Number(data) => Value::Number(data),
+            PrimitiveObjectData::Integer(data) => Value::Integer(data),
+            PrimitiveObjectData::Float(data) => Value::SmallF64(data),
+            PrimitiveObjectData::BigInt(data) => Value::BigInt(data),
+            PrimitiveObjectData::SmallBigInt(data) => Value::SmallBigInt(data),
+        };
+        value.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let object_index = SnapshotData::deserialize(reader)?;
+        let data = match Value::deserialize(reader)? {
+            Value::Boolean(data) => PrimitiveObjectData::Boolean(data),
+            Value::String(data) => PrimitiveObjectData::String(data),
+            Value::SmallString(data) => PrimitiveObjectData::SmallString(data),
+            Value::Symbol(data) => PrimitiveObjectData::Symbol(data),
+            Value::Number(data) => PrimitiveObjectData::Number(data),
+            Value::Integer(data) => PrimitiveObjectData::Integer(data),
+            Value::SmallF64(data) => PrimitiveObjectData::Float(data),
+            Value::BigInt(data) => PrimitiveObjectData::BigInt(data),
+            Value::SmallBigInt(data) => PrimitiveObjectData::SmallBigInt(data),
+            _ => return Err(SnapshotError::Malformed),
+        };
+        Ok(Self { object_index, data })
+    }
+}
+
 impl HeapMarkAndSweep for PrimitiveObject<'static> {
     fn mark_values(&self, queues: &mut WorkQueues) {
         queues.primitive_objects.push(*self);
@@ -730,6 +767,16 @@ impl HeapMarkAndSweep for PrimitiveObject<'static> {
     }
 }
 
+impl SnapshotData for PrimitiveObject<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl CreateHeapData<PrimitiveObjectHeapData, PrimitiveObject<'static>> for Heap {
     fn create(&mut self, data: PrimitiveObjectHeapData) -> PrimitiveObject<'static> {
         self.primitive_objects.push(Some(data));
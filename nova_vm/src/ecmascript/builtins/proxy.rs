@@ -17,7 +17,7 @@ use crate::{
                 call, call_function, construct, create_array_from_list,
                 create_property_key_list_from_array_like, get_object_method, try_get_object_method,
             },
-            testing_and_comparison::{is_constructor, is_extensible, same_value},
+            testing_and_comparison::{is_callable, is_constructor, is_extensible, same_value},
             type_conversion::to_boolean,
         },
         builtins::ArgumentsList,
@@ -35,7 +35,8 @@ use crate::{
     },
     heap::{
         indexes::{BaseIndex, ProxyIndex},
-        CreateHeapData, Heap, HeapMarkAndSweep,
+        CreateHeapData, Heap, HeapMarkAndSweep, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter,
     },
 };
 
@@ -43,6 +44,7 @@ use super::ordinary::is_compatible_property_descriptor;
 
 pub(crate) mod abstract_operations;
 pub mod data;
+pub mod revoker;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(transparent)]
@@ -1648,14 +1650,19 @@ pub(crate) fn proxy_create<'a>(
         ));
     };
     // 3. Let P be MakeBasicObject(« [[ProxyHandler]], [[ProxyTarget]] »).
-    let p = agent.heap.create(ProxyHeapData {
-        proxy_target: Some(target),
-        proxy_handler: Some(handler),
-    });
     // 4. Set P's essential internal methods, except for [[Call]] and
     // [[Construct]], to the definitions specified in 10.5.
     // 5. If IsCallable(target) is true, then
     // a. Set P.[[Call]] as specified in 10.5.12.
+    let callable = match target {
+        Object::Proxy(target) => agent[target].callable,
+        _ => is_callable(target, gc).is_some(),
+    };
+    let p = agent.heap.create(ProxyHeapData {
+        proxy_target: Some(target),
+        proxy_handler: Some(handler),
+        callable,
+    });
     // b. If IsConstructor(target) is true, then
     // i. Set P.[[Construct]] as specified in 10.5.13.
     // 6. Set P.[[ProxyTarget]] to target.
@@ -1727,3 +1734,13 @@ impl HeapMarkAndSweep for Proxy<'static> {
         compactions.proxys.shift_index(&mut self.0);
     }
 }
+
+impl SnapshotData for Proxy<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
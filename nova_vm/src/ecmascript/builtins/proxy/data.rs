@@ -4,7 +4,10 @@
 
 use crate::{
     ecmascript::types::Object,
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WorkQueues,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -13,6 +16,9 @@ pub struct ProxyHeapData {
     pub(crate) proxy_target: Option<Object<'static>>,
     /// [[ProxyHandler]]
     pub(crate) proxy_handler: Option<Object<'static>>,
+    /// Whether the proxy has a [[Call]] internal method. This is decided by
+    /// IsCallable(target) in ProxyCreate and is unaffected by revocation.
+    pub(crate) callable: bool,
 }
 
 impl HeapMarkAndSweep for ProxyHeapData {
@@ -26,3 +32,24 @@ impl HeapMarkAndSweep for ProxyHeapData {
         self.proxy_handler.sweep_values(compactions);
     }
 }
+
+impl SnapshotData for ProxyHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            proxy_target,
+            proxy_handler,
+            callable,
+        } = self;
+        proxy_target.serialize(writer)?;
+        proxy_handler.serialize(writer)?;
+        callable.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            proxy_target: SnapshotData::deserialize(reader)?,
+            proxy_handler: SnapshotData::deserialize(reader)?,
+            callable: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
@@ -0,0 +1,376 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::ops::{Index, IndexMut};
+
+use crate::ecmascript::types::{function_try_get, function_try_has_property, function_try_set};
+use crate::engine::context::{GcScope, NoGcScope};
+use crate::engine::rootable::{HeapRootData, HeapRootRef, Rootable};
+use crate::engine::{Scoped, TryResult};
+use crate::{
+    ecmascript::{
+        builtins::{proxy::Proxy, ArgumentsList},
+        execution::{Agent, JsResult, ProtoIntrinsics},
+        types::{
+            function_create_backing_object, function_internal_define_own_property,
+            function_internal_delete, function_internal_get, function_internal_get_own_property,
+            function_internal_has_property, function_internal_own_property_keys,
+            function_internal_set, Function, FunctionInternalProperties, InternalMethods,
+            InternalSlots, IntoFunction, IntoObject, IntoValue, Object, OrdinaryObject,
+            PropertyDescriptor, PropertyKey, String, Value,
+        },
+    },
+    heap::{indexes::BaseIndex, CreateHeapData, Heap, HeapMarkAndSweep},
+};
+
+/// ### [28.2.2.1.1 Proxy Revocation Functions](https://tc39.es/ecma262/#sec-proxy-revocation-functions)
+///
+/// A Proxy revocation function is an anonymous built-in function that has the
+/// ability to invalidate a specific Proxy object. It has a
+/// \[\[RevocableProxy\]\] internal slot.
+///
+/// The "length" property of a Proxy revocation function is +0𝔽.
+#[derive(Debug, Clone, Copy)]
+pub struct ProxyRevokerFunctionHeapData {
+    pub(crate) object_index: Option<OrdinaryObject<'static>>,
+    /// \[\[RevocableProxy\]\]
+    pub(crate) revocable_proxy: Option<Proxy<'static>>,
+}
+
+pub(crate) type BuiltinProxyRevokerFunctionIndex<'a> = BaseIndex<'a, ProxyRevokerFunctionHeapData>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BuiltinProxyRevokerFunction<'a>(pub(crate) BuiltinProxyRevokerFunctionIndex<'a>);
+
+impl BuiltinProxyRevokerFunction<'_> {
+    /// Unbind this BuiltinProxyRevokerFunction from its current lifetime. This is necessary to use
+    /// the BuiltinProxyRevokerFunction as a parameter in a call that can perform garbage
+    /// collection.
+    pub fn unbind(self) -> BuiltinProxyRevokerFunction<'static> {
+        unsafe {
+            std::mem::transmute::<BuiltinProxyRevokerFunction, BuiltinProxyRevokerFunction<'static>>(
+                self,
+            )
+        }
+    }
+
+    // Bind this BuiltinProxyRevokerFunction to the garbage collection lifetime. This enables Rust's
+    // borrow checker to verify that your BuiltinProxyRevokerFunctions cannot not be invalidated by
+    // garbage collection being performed.
+    //
+    // This function is best called with the form
+    // ```rs
+    // let number = number.bind(&gc);
+    // ```
+    // to make sure that the unbound BuiltinProxyRevokerFunction cannot be used after binding.
+    pub const fn bind<'gc>(self, _: NoGcScope<'gc, '_>) -> BuiltinProxyRevokerFunction<'gc> {
+        unsafe {
+            std::mem::transmute::<BuiltinProxyRevokerFunction, BuiltinProxyRevokerFunction<'gc>>(
+                self,
+            )
+        }
+    }
+
+    pub fn scope<'scope>(
+        self,
+        agent: &mut Agent,
+        gc: NoGcScope<'_, 'scope>,
+    ) -> Scoped<'scope, BuiltinProxyRevokerFunction<'static>> {
+        Scoped::new(agent, self.unbind(), gc)
+    }
+
+    pub(crate) const fn _def() -> Self {
+        Self(BaseIndex::from_u32_index(0))
+    }
+
+    pub(crate) const fn get_index(self) -> usize {
+        self.0.into_index()
+    }
+}
+
+impl<'a> From<BuiltinProxyRevokerFunction<'a>> for Function<'a> {
+    fn from(value: BuiltinProxyRevokerFunction<'a>) -> Self {
+        Self::BuiltinProxyRevokerFunction(value)
+    }
+}
+
+impl<'a> IntoFunction<'a> for BuiltinProxyRevokerFunction<'a> {
+    fn into_function(self) -> Function<'a> {
+        self.into()
+    }
+}
+
+impl<'a> From<BuiltinProxyRevokerFunction<'a>> for Object<'a> {
+    fn from(value: BuiltinProxyRevokerFunction) -> Self {
+        Self::BuiltinProxyRevokerFunction(value.unbind())
+    }
+}
+
+impl<'a> IntoObject<'a> for BuiltinProxyRevokerFunction<'a> {
+    fn into_object(self) -> Object<'a> {
+        self.into()
+    }
+}
+
+impl From<BuiltinProxyRevokerFunction<'_>> for Value {
+    fn from(value: BuiltinProxyRevokerFunction) -> Self {
+        Self::BuiltinProxyRevokerFunction(value.unbind())
+    }
+}
+
+impl IntoValue for BuiltinProxyRevokerFunction<'_> {
+    fn into_value(self) -> Value {
+        self.into()
+    }
+}
+
+impl<'a> FunctionInternalProperties<'a> for BuiltinProxyRevokerFunction<'a> {
+    fn get_name(self, _: &Agent) -> String<'static> {
+        String::EMPTY_STRING
+    }
+
+    fn get_length(self, _: &Agent) -> u8 {
+        0
+    }
+}
+
+impl<'a> InternalSlots<'a> for BuiltinProxyRevokerFunction<'a> {
+    const DEFAULT_PROTOTYPE: ProtoIntrinsics = ProtoIntrinsics::Function;
+
+    #[inline(always)]
+    fn get_backing_object(self, agent: &Agent) -> Option<OrdinaryObject<'static>> {
+        agent[self].object_index
+    }
+
+    fn set_backing_object(self, agent: &mut Agent, backing_object: OrdinaryObject<'static>) {
+        assert!(agent[self]
+            .object_index
+            .replace(backing_object.unbind())
+            .is_none());
+    }
+
+    fn create_backing_object(self, agent: &mut Agent) -> OrdinaryObject<'static> {
+        function_create_backing_object(self, agent)
+    }
+}
+
+impl<'a> InternalMethods<'a> for BuiltinProxyRevokerFunction<'a> {
+    fn try_get_own_property(
+        self,
+        agent: &mut Agent,
+        property_key: PropertyKey,
+        _gc: NoGcScope,
+    ) -> TryResult<Option<PropertyDescriptor>> {
+        TryResult::Continue(function_internal_get_own_property(
+            self,
+            agent,
+            property_key,
+        ))
+    }
+
+    fn try_define_own_property(
+        self,
+        agent: &mut Agent,
+        property_key: PropertyKey,
+        property_descriptor: PropertyDescriptor,
+        gc: NoGcScope,
+    ) -> TryResult<bool> {
+        TryResult::Continue(function_internal_define_own_property(
+            self,
+            agent,
+            property_key,
+            property_descriptor,
+            gc,
+        ))
+    }
+
+    fn try_has_property(
+        self,
+        agent: &mut Agent,
+        property_key: PropertyKey,
+        gc: NoGcScope,
+    ) -> TryResult<bool> {
+        function_try_has_property(self, agent, property_key, gc)
+    }
+
+    fn internal_has_property(
+        self,
+        agent: &mut Agent,
+        property_key: PropertyKey,
+        gc: GcScope,
+    ) -> JsResult<bool> {
+        function_internal_has_property(self, agent, property_key, gc)
+    }
+
+    fn try_get(
+        self,
+        agent: &mut Agent,
+        property_key: PropertyKey,
+        receiver: Value,
+        gc: NoGcScope,
+    ) -> TryResult<Value> {
+        function_try_get(self, agent, property_key, receiver, gc)
+    }
+
+    fn internal_get(
+        self,
+        agent: &mut Agent,
+        property_key: PropertyKey,
+        receiver: Value,
+        gc: GcScope,
+    ) -> JsResult<Value> {
+        function_internal_get(self, agent, property_key, receiver, gc)
+    }
+
+    fn try_set(
+        self,
+        agent: &mut Agent,
+        property_key: PropertyKey,
+        value: Value,
+        receiver: Value,
+        gc: NoGcScope,
+    ) -> TryResult<bool> {
+        function_try_set(self, agent, property_key, value, receiver, gc)
+    }
+
+    fn internal_set(
+        self,
+        agent: &mut Agent,
+        property_key: PropertyKey,
+        value: Value,
+        receiver: Value,
+        gc: GcScope,
+    ) -> JsResult<bool> {
+        function_internal_set(self, agent, property_key, value, receiver, gc)
+    }
+
+    fn try_delete(
+        self,
+        agent: &mut Agent,
+        property_key: PropertyKey,
+        gc: NoGcScope,
+    ) -> TryResult<bool> {
+        TryResult::Continue(function_internal_delete(self, agent, property_key, gc))
+    }
+
+    fn try_own_property_keys<'gc>(
+        self,
+        agent: &mut Agent,
+        gc: NoGcScope<'gc, '_>,
+    ) -> TryResult<Vec<PropertyKey<'gc>>> {
+        TryResult::Continue(function_internal_own_property_keys(self, agent, gc))
+    }
+
+    fn internal_call(
+        self,
+        agent: &mut Agent,
+        _this_value: Value,
+        _args: ArgumentsList,
+        _gc: GcScope,
+    ) -> JsResult<Value> {
+        // 1. Let F be the active function object.
+        // 2. Let p be F.[[RevocableProxy]].
+        // 3. If p is null, return undefined.
+        // 4. Set F.[[RevocableProxy]] to null.
+        let Some(p) = agent[self].revocable_proxy.take() else {
+            return Ok(Value::Undefined);
+        };
+        // 5. Assert: p is a Proxy exotic object.
+        // 6. Set p.[[ProxyTarget]] to null.
+        agent[p].proxy_target = None;
+        // 7. Set p.[[ProxyHandler]] to null.
+        agent[p].proxy_handler = None;
+        // 8. Return undefined.
+        Ok(Value::Undefined)
+    }
+}
+
+impl Index<BuiltinProxyRevokerFunction<'_>> for Agent {
+    type Output = ProxyRevokerFunctionHeapData;
+
+    fn index(&self, index: BuiltinProxyRevokerFunction) -> &Self::Output {
+        &self.heap.proxy_revoker_functions[index]
+    }
+}
+
+impl IndexMut<BuiltinProxyRevokerFunction<'_>> for Agent {
+    fn index_mut(&mut self, index: BuiltinProxyRevokerFunction) -> &mut Self::Output {
+        &mut self.heap.proxy_revoker_functions[index]
+    }
+}
+
+impl Index<BuiltinProxyRevokerFunction<'_>> for Vec<Option<ProxyRevokerFunctionHeapData>> {
+    type Output = ProxyRevokerFunctionHeapData;
+
+    fn index(&self, index: BuiltinProxyRevokerFunction) -> &Self::Output {
+        self.get(index.get_index())
+            .expect("BuiltinProxyRevokerFunction out of bounds")
+            .as_ref()
+            .expect("BuiltinProxyRevokerFunction slot empty")
+    }
+}
+
+impl IndexMut<BuiltinProxyRevokerFunction<'_>> for Vec<Option<ProxyRevokerFunctionHeapData>> {
+    fn index_mut(&mut self, index: BuiltinProxyRevokerFunction) -> &mut Self::Output {
+        self.get_mut(index.get_index())
+            .expect("BuiltinProxyRevokerFunction out of bounds")
+            .as_mut()
+            .expect("BuiltinProxyRevokerFunction slot empty")
+    }
+}
+
+impl Rootable for BuiltinProxyRevokerFunction<'_> {
+    type RootRepr = HeapRootRef;
+
+    fn to_root_repr(value: Self) -> Result<Self::RootRepr, HeapRootData> {
+        Err(HeapRootData::BuiltinProxyRevokerFunction(value.unbind()))
+    }
+
+    fn from_root_repr(value: &Self::RootRepr) -> Result<Self, HeapRootRef> {
+        Err(*value)
+    }
+
+    fn from_heap_ref(heap_ref: HeapRootRef) -> Self::RootRepr {
+        heap_ref
+    }
+
+    fn from_heap_data(heap_data: HeapRootData) -> Option<Self> {
+        match heap_data {
+            HeapRootData::BuiltinProxyRevokerFunction(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+impl CreateHeapData<ProxyRevokerFunctionHeapData, BuiltinProxyRevokerFunction<'static>> for Heap {
+    fn create(
+        &mut self,
+        data: ProxyRevokerFunctionHeapData,
+    ) -> BuiltinProxyRevokerFunction<'static> {
+        self.proxy_revoker_functions.push(Some(data));
+        BuiltinProxyRevokerFunction(BaseIndex::last(&self.proxy_revoker_functions))
+    }
+}
+
+impl HeapMarkAndSweep for BuiltinProxyRevokerFunction<'static> {
+    fn mark_values(&self, queues: &mut crate::heap::WorkQueues) {
+        queues.proxy_revoker_functions.push(*self);
+    }
+
+    fn sweep_values(&mut self, compactions: &crate::heap::CompactionLists) {
+        compactions.proxy_revoker_functions.shift_index(&mut self.0);
+    }
+}
+
+impl HeapMarkAndSweep for ProxyRevokerFunctionHeapData {
+    fn mark_values(&self, queues: &mut crate::heap::WorkQueues) {
+        self.object_index.mark_values(queues);
+        self.revocable_proxy.mark_values(queues);
+    }
+
+    fn sweep_values(&mut self, compactions: &crate::heap::CompactionLists) {
+        self.object_index.sweep_values(compactions);
+        self.revocable_proxy.sweep_values(compactions);
+    }
+}
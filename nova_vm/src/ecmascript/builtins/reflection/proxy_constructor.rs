@@ -3,8 +3,9 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::ecmascript::builtins::proxy::proxy_create;
+use crate::ecmascript::builtins::proxy::revoker::ProxyRevokerFunctionHeapData;
 use crate::ecmascript::execution::agent::ExceptionType;
-use crate::ecmascript::types::IntoValue;
+use crate::ecmascript::types::{IntoObject, IntoValue};
 use crate::engine::context::GcScope;
 use crate::{
     ecmascript::{
@@ -13,7 +14,7 @@ use crate::{
         execution::{Agent, JsResult, RealmIdentifier},
         types::{Object, String, Value, BUILTIN_STRING_MEMORY},
     },
-    heap::IntrinsicConstructorIndexes,
+    heap::{CreateHeapData, IntrinsicConstructorIndexes, ObjectEntry},
 };
 
 pub(crate) struct ProxyConstructor;
@@ -61,13 +62,46 @@ impl ProxyConstructor {
         proxy_create(agent, target, handler, gc).map(|proxy| proxy.into_value())
     }
 
+    /// ### [28.2.2.1 Proxy.revocable ( target, handler )](https://tc39.es/ecma262/#sec-proxy.revocable)
     fn revocable(
-        _agent: &mut Agent,
+        agent: &mut Agent,
         _this_value: Value,
-        _arguments: ArgumentsList,
-        _gc: GcScope,
+        arguments: ArgumentsList,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let gc = gc.into_nogc();
+        let target = arguments.get(0);
+        let handler = arguments.get(1);
+        // 1. Let proxy be ? ProxyCreate(target, handler).
+        let proxy = proxy_create(agent, target, handler, gc)?;
+        // 2. Let revokerClosure be a new Abstract Closure with no parameters
+        //    that captures nothing and performs the following steps when
+        //    called:
+        // 3. Let revoker be CreateBuiltinFunction(revokerClosure, 0, "", « [[RevocableProxy]] »).
+        // 4. Set revoker.[[RevocableProxy]] to proxy.
+        let revoker = agent.heap.create(ProxyRevokerFunctionHeapData {
+            object_index: None,
+            revocable_proxy: Some(proxy.unbind()),
+        });
+        // 5. Let result be OrdinaryObjectCreate(%Object.prototype%).
+        // 6. Perform ! CreateDataPropertyOrThrow(result, "proxy", proxy).
+        // 7. Perform ! CreateDataPropertyOrThrow(result, "revoke", revoker).
+        let result = agent.heap.create_object_with_prototype(
+            agent
+                .current_realm()
+                .intrinsics()
+                .object_prototype()
+                .into_object(),
+            &[
+                ObjectEntry::new_data_entry(BUILTIN_STRING_MEMORY.proxy.into(), proxy.into_value()),
+                ObjectEntry::new_data_entry(
+                    BUILTIN_STRING_MEMORY.revoke.into(),
+                    revoker.into_value(),
+                ),
+            ],
+        );
+        // 8. Return result.
+        Ok(result.into_value())
     }
 
     pub(crate) fn create_intrinsic(agent: &mut Agent, realm: RealmIdentifier) {
@@ -77,3 +111,50 @@ impl ProxyConstructor {
             .build();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::TestAgent;
+
+    #[test]
+    fn proxy_revocable() {
+        let mut agent = TestAgent::new();
+        agent.eval("var r = Proxy.revocable({ a: 1 }, {});");
+        assert_eq!(agent.eval("Object.keys(r).join()"), "proxy,revoke");
+        assert_eq!(agent.eval("r.proxy.a"), "1");
+        assert_eq!(
+            agent.eval("r.revoke.length + ' ' + typeof r.revoke"),
+            "0 function"
+        );
+        assert_eq!(agent.eval("r.revoke.name === ''"), "true");
+        assert_eq!(agent.eval("r.revoke()"), "undefined");
+        // Revoking an already revoked proxy is a no-op.
+        assert_eq!(agent.eval("r.revoke()"), "undefined");
+        assert!(agent.eval_err("r.proxy.a").starts_with("TypeError"));
+        assert!(agent.eval_err("new r.revoke()").starts_with("TypeError"));
+        assert!(agent
+            .eval_err("Proxy.revocable(1, {})")
+            .starts_with("TypeError"));
+    }
+
+    #[test]
+    fn typeof_proxy() {
+        let mut agent = TestAgent::new();
+        assert_eq!(agent.eval("typeof new Proxy({}, {})"), "object");
+        assert_eq!(
+            agent.eval("typeof new Proxy(function () {}, {})"),
+            "function"
+        );
+        assert_eq!(
+            agent.eval("typeof new Proxy(new Proxy(() => {}, {}), {})"),
+            "function"
+        );
+        // A revoked proxy keeps the callability of its former target.
+        agent.eval(
+            "var o = Proxy.revocable({}, {}); var f = Proxy.revocable(() => {}, {}); \
+             o.revoke(); f.revoke();",
+        );
+        assert_eq!(agent.eval("typeof o.proxy"), "object");
+        assert_eq!(agent.eval("typeof f.proxy"), "function");
+    }
+}
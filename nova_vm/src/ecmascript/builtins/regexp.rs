@@ -12,7 +12,8 @@ use crate::{
         execution::{Agent, JsResult, ProtoIntrinsics},
         types::{
             InternalMethods, InternalSlots, IntoObject, IntoValue, Object, ObjectHeapData,
-            OrdinaryObject, PropertyDescriptor, PropertyKey, Value, BUILTIN_STRING_MEMORY,
+            ObjectShape, OrdinaryObject, PropertyDescriptor, PropertyKey, Value,
+            BUILTIN_STRING_MEMORY,
         },
     },
     engine::{
@@ -127,7 +128,7 @@ impl<'a> InternalSlots<'a> for RegExp<'a> {
         assert!(self.get_backing_object(agent).is_none());
         let prototype = self.internal_prototype(agent);
         let last_index = agent[self].last_index;
-        let (keys, values) = agent.heap.elements.create_object_entries(&[ObjectEntry {
+        let entries = [ObjectEntry {
             key: BUILTIN_STRING_MEMORY.lastIndex.into(),
             value: ObjectEntryPropertyDescriptor::Data {
                 value: last_index
@@ -137,13 +138,14 @@ impl<'a> InternalSlots<'a> for RegExp<'a> {
                 enumerable: false,
                 configurable: false,
             },
-        }]);
-        let backing_object = agent.heap.create(ObjectHeapData {
-            extensible: true,
+        }];
+        let shape = ObjectShape::from_keys(
+            &mut agent.heap,
             prototype,
-            keys,
-            values,
-        });
+            entries.iter().map(|entry| entry.key),
+        );
+        let values = agent.heap.elements.create_object_entry_values(&entries);
+        let backing_object = agent.heap.create(ObjectHeapData::new(true, shape, values));
         self.set_backing_object(agent, backing_object);
         backing_object
     }
@@ -318,7 +320,7 @@ impl<'a> InternalMethods<'a> for RegExp<'a> {
                 // If we we set a value that is not a valid index or undefined,
                 // we need to create the backing object and set the actual
                 // value there.
-                if !new_last_index.is_valid() && value.is_undefined() {
+                if !new_last_index.is_valid() && !value.is_undefined() {
                     unwrap_try(self.create_backing_object(agent).try_set(
                         agent,
                         property_key,
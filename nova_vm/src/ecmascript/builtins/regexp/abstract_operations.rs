@@ -2,43 +2,61 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::cell::OnceCell;
+
 use oxc_ast::ast::RegExpFlags;
+use regress::Regex;
+use wtf8::Wtf8;
 
-use crate::ecmascript::abstract_operations::operations_on_objects::{set, try_set};
-use crate::ecmascript::types::IntoObject;
+use crate::ecmascript::abstract_operations::operations_on_objects::{
+    call_function, create_array_from_list, get, set, try_create_data_property_or_throw, try_get,
+    try_set,
+};
+use crate::ecmascript::abstract_operations::testing_and_comparison::is_callable;
+use crate::ecmascript::abstract_operations::type_conversion::{to_length, to_string};
+use crate::ecmascript::builtins::ordinary::ordinary_object_create_with_intrinsics;
+use crate::ecmascript::builtins::{ArgumentsList, Array};
+use crate::ecmascript::execution::agent::ExceptionType;
+use crate::ecmascript::types::{IntoObject, IntoValue, Object, PropertyKey, Value};
 use crate::engine::context::{GcScope, NoGcScope};
+use crate::engine::unwrap_try;
 use crate::{
     ecmascript::{
         builtins::ordinary::ordinary_create_from_constructor,
-        execution::{Agent, JsResult, ProtoIntrinsics},
+        execution::{agent::JsError, Agent, JsResult, ProtoIntrinsics},
         types::{Function, String, BUILTIN_STRING_MEMORY},
     },
     heap::CreateHeapData,
 };
 
+use super::data::RegExpMatcher;
 use super::{RegExp, RegExpHeapData, RegExpLastIndex};
 
-/// ### [22.2.3.1 RegExpCreate ( P, F )]()
+/// ### [22.2.3.1 RegExpCreate ( P, F )](https://tc39.es/ecma262/#sec-regexpcreate)
 ///
 /// The abstract operation RegExpCreate takes arguments P (an ECMAScript
 /// language value) and F (a String or undefined) and returns either a normal
 /// completion containing an Object or a throw completion.
 ///
-/// This is a variant for RegExp literal creation that cannot fail and skips
-/// all of the abstract operation busy-work.
-pub(crate) fn reg_exp_create_literal<'a>(
+/// > NOTE: The caller is responsible for performing ToString(P). The pattern
+/// > is not validated here: RegExp literals are created while compiling
+/// > bytecode where no error can be thrown, so the \[\[RegExpMatcher]] is
+/// > compiled on first use. Other callers should call
+/// > [`reg_exp_compile_matcher`] to throw pattern errors eagerly.
+pub(crate) fn reg_exp_create<'a>(
     agent: &mut Agent,
     p: String,
     f: Option<RegExpFlags>,
     gc: NoGcScope<'a, '_>,
 ) -> RegExp<'a> {
-    //     1. Let obj be ! RegExpAlloc(%RegExp%).
-    //     2. Return ? RegExpInitialize(obj, P, F).
+    // 1. Let obj be ! RegExpAlloc(%RegExp%).
+    // 2. Return ? RegExpInitialize(obj, P, F).
     let f = f.unwrap_or(RegExpFlags::empty());
     agent
         .heap
         .create(RegExpHeapData {
             object_index: None,
+            reg_exp_matcher: OnceCell::new(),
             original_source: p.unbind(),
             original_flags: f,
             last_index: RegExpLastIndex::ZERO,
@@ -46,7 +64,7 @@ pub(crate) fn reg_exp_create_literal<'a>(
         .bind(gc)
 }
 
-/// ### [22.2.3.2 RegExpAlloc ( newTarget )]()
+/// ### [22.2.3.2 RegExpAlloc ( newTarget )](https://tc39.es/ecma262/#sec-regexpalloc)
 ///
 /// The abstract operation RegExpAlloc takes argument newTarget (a constructor)
 /// and returns either a normal completion containing an Object or a throw
@@ -70,12 +88,52 @@ pub(crate) fn reg_exp_alloc<'a>(
     Ok(obj)
 }
 
-/// ### [22.2.3.3 RegExpInitialize ( obj, pattern, flags )]()
+/// ### [22.2.3.3 RegExpInitialize ( obj, pattern, flags )](https://tc39.es/ecma262/#sec-regexpinitialize)
 ///
 /// The abstract operation RegExpInitialize takes arguments obj (an Object),
 /// pattern (an ECMAScript language value), and flags (an ECMAScript language
 /// value) and returns either a normal completion containing an Object or a
 /// throw completion.
+pub(crate) fn reg_exp_initialize<'a>(
+    agent: &mut Agent,
+    obj: RegExp,
+    pattern: Value,
+    flags: Value,
+    mut gc: GcScope<'a, '_>,
+) -> JsResult<RegExp<'a>> {
+    let obj = obj.scope(agent, gc.nogc());
+    // 1. If pattern is undefined, let P be the empty String.
+    let p = if pattern.is_undefined() {
+        String::EMPTY_STRING
+    } else {
+        // 2. Else, let P be ? ToString(pattern).
+        to_string(agent, pattern, gc.reborrow())?.unbind()
+    };
+    let p = p.scope(agent, gc.nogc());
+    // 3. If flags is undefined, let F be the empty String.
+    let f = if flags.is_undefined() {
+        RegExpFlags::empty()
+    } else {
+        // 4. Else, let F be ? ToString(flags).
+        let f = to_string(agent, flags, gc.reborrow())?;
+        // 5. If F contains any code unit other than "d", "g", "i", "m", "s",
+        //    "u", "v", or "y", or if F contains any code unit more than once,
+        //    throw a SyntaxError exception.
+        match parse_reg_exp_flags(f.as_str(agent)) {
+            Some(f) => f,
+            None => {
+                let message = format!("Invalid regular expression flags '{}'", f.as_str(agent));
+                return Err(agent.throw_exception(ExceptionType::SyntaxError, message, gc.nogc()));
+            }
+        }
+    };
+    reg_exp_initialize_from_string(agent, obj.get(agent), p.get(agent), Some(f), gc)
+}
+
+/// ### [22.2.3.3 RegExpInitialize ( obj, pattern, flags )](https://tc39.es/ecma262/#sec-regexpinitialize)
+///
+/// RegExpInitialize with the pattern and flags already converted; this
+/// performs steps 6 onwards.
 pub(crate) fn reg_exp_initialize_from_string<'a>(
     agent: &mut Agent,
     obj: RegExp,
@@ -87,8 +145,6 @@ pub(crate) fn reg_exp_initialize_from_string<'a>(
     let p = p.bind(gc.nogc());
     //     3. If flags is undefined, let F be the empty String.
     let f = flags.unwrap_or(RegExpFlags::empty());
-    //     4. Else, let F be ? ToString(flags).
-    //     5. If F contains any code unit other than "d", "g", "i", "m", "s", "u", "v", or "y", or if F contains any code unit more than once, throw a SyntaxError exception.
     //     6. If F contains "i", let i be true; else let i be false.
     //     7. If F contains "m", let m be true; else let m be false.
     //     8. If F contains "s", let s be true; else let s be false.
@@ -99,7 +155,11 @@ pub(crate) fn reg_exp_initialize_from_string<'a>(
     //     12. Else,
     //         a. Let patternText be the result of interpreting each of P's 16-bit elements as a Unicode BMP code point. UTF-16 decoding is not applied to the elements.
     //     13. Let parseResult be ParsePattern(patternText, u, v).
+    let parse_result = parse_pattern(p.as_wtf8(agent), f);
     //     14. If parseResult is a non-empty List of SyntaxError objects, throw a SyntaxError exception.
+    if let Err(err) = &parse_result {
+        return Err(throw_pattern_syntax_error(agent, p, f, err, gc.nogc()));
+    }
     //     15. Assert: parseResult is a Pattern Parse Node.
     //     16. Set obj.[[OriginalSource]] to P.
     agent[obj].original_source = p.unbind();
@@ -109,6 +169,7 @@ pub(crate) fn reg_exp_initialize_from_string<'a>(
     //     19. Let rer be the RegExp Record { [[IgnoreCase]]: i, [[Multiline]]: m, [[DotAll]]: s, [[Unicode]]: u, [[UnicodeSets]]: v, [[CapturingGroupsCount]]: capturingGroupsCount }.
     //     20. Set obj.[[RegExpRecord]] to rer.
     //     21. Set obj.[[RegExpMatcher]] to CompilePattern of parseResult with argument rer.
+    agent[obj].reg_exp_matcher = OnceCell::from(parse_result);
     //     22. Perform ? Set(obj, "lastIndex", +0𝔽, true).
     //     23. Return obj.
     if try_set(
@@ -136,20 +197,626 @@ pub(crate) fn reg_exp_initialize_from_string<'a>(
     }
 }
 
-/// ### [22.2.3.4 Static Semantics: ParsePattern ( patternText, u, v )]()
+/// ### [22.2.3.4 Static Semantics: ParsePattern ( patternText, u, v )](https://tc39.es/ecma262/#sec-parsepattern)
 ///
 /// The abstract operation ParsePattern takes arguments patternText (a sequence of Unicode code points), u (a Boolean), and v (a Boolean) and returns a Parse Node or a non-empty List of SyntaxError objects.
 ///
+/// This also performs CompilePattern: the result is the \[\[RegExpMatcher]]
+/// of the pattern.
+///
 /// > #### Note
 /// > This section is amended in B.1.2.9.
-pub(crate) fn parse_pattern() {
-    //     1. If v is true and u is true, then
-    //         a. Let parseResult be a List containing one or more SyntaxError objects.
-    //     2. Else if v is true, then
-    //         a. Let parseResult be ParseText(patternText, Pattern[+UnicodeMode, +UnicodeSetsMode, +NamedCaptureGroups]).
-    //     3. Else if u is true, then
-    //         a. Let parseResult be ParseText(patternText, Pattern[+UnicodeMode, ~UnicodeSetsMode, +NamedCaptureGroups]).
-    //     4. Else,
-    //         a. Let parseResult be ParseText(patternText, Pattern[~UnicodeMode, ~UnicodeSetsMode, +NamedCaptureGroups]).
-    //     5. Return parseResult.
+pub(crate) fn parse_pattern(
+    pattern_text: &Wtf8,
+    flags: RegExpFlags,
+) -> Result<RegExpMatcher, regress::Error> {
+    let u = flags.contains(RegExpFlags::U);
+    let v = flags.contains(RegExpFlags::V);
+    let regress_flags = regress::Flags {
+        icase: flags.contains(RegExpFlags::I),
+        multiline: flags.contains(RegExpFlags::M),
+        dot_all: flags.contains(RegExpFlags::S),
+        no_opt: false,
+        unicode: u,
+        unicode_sets: v,
+    };
+    let regex = if u && v {
+        // 1. If v is true and u is true, then
+        //     a. Let parseResult be a List containing one or more SyntaxError objects.
+        return Err(regress::Error {
+            text: "Invalid regular expression flags".to_string(),
+        });
+    } else if u || v {
+        // 2. Else if v is true, then
+        //     a. Let parseResult be ParseText(patternText, Pattern[+UnicodeMode, +UnicodeSetsMode, +NamedCaptureGroups]).
+        // 3. Else if u is true, then
+        //     a. Let parseResult be ParseText(patternText, Pattern[+UnicodeMode, ~UnicodeSetsMode, +NamedCaptureGroups]).
+        Regex::from_unicode(
+            pattern_text.code_points().map(|cp| cp.to_u32()),
+            regress_flags,
+        )?
+    } else {
+        // 4. Else,
+        //     a. Let parseResult be ParseText(patternText, Pattern[~UnicodeMode, ~UnicodeSetsMode, +NamedCaptureGroups]).
+        Regex::from_unicode(
+            pattern_text.to_ill_formed_utf16().map(u32::from),
+            regress_flags,
+        )?
+    };
+    // 5. Return parseResult.
+    Ok(RegExpMatcher {
+        regex,
+        group_names: capturing_group_names(pattern_text, v),
+    })
+}
+
+/// Returns the GroupName of each capturing group of a syntactically valid
+/// pattern in the order of their left parentheses, ie. in the order of
+/// CountLeftCapturingParensBefore.
+fn capturing_group_names(pattern_text: &Wtf8, unicode_sets: bool) -> Box<[Option<Box<str>>]> {
+    let pattern = pattern_text
+        .code_points()
+        .map(|cp| cp.to_char_lossy())
+        .collect::<Vec<_>>();
+    let mut names = Vec::new();
+    // Parentheses inside a character class are literal characters. Classes
+    // only nest in UnicodeSets mode.
+    let mut class_depth = 0usize;
+    let mut i = 0;
+    while i < pattern.len() {
+        match pattern[i] {
+            '\\' => i += 1,
+            '[' if class_depth == 0 || unicode_sets => class_depth += 1,
+            ']' if class_depth > 0 => class_depth -= 1,
+            '(' if class_depth == 0 => match pattern.get(i + 1) {
+                Some('?') => {
+                    if pattern.get(i + 2) == Some(&'<')
+                        && !matches!(pattern.get(i + 3), Some('=') | Some('!'))
+                    {
+                        let name = pattern[i + 3..]
+                            .iter()
+                            .take_while(|c| **c != '>')
+                            .collect::<std::string::String>();
+                        names.push(Some(name.into_boxed_str()));
+                    }
+                }
+                _ => names.push(None),
+            },
+            _ => {}
+        }
+        i += 1;
+    }
+    names.into_boxed_slice()
+}
+
+/// Parses the F argument of RegExpInitialize. Returns None if F contains any
+/// code unit other than "d", "g", "i", "m", "s", "u", "v", or "y", or if F
+/// contains any code unit more than once.
+pub(crate) fn parse_reg_exp_flags(f: &str) -> Option<RegExpFlags> {
+    let mut flags = RegExpFlags::empty();
+    for c in f.chars() {
+        let flag = match c {
+            'd' => RegExpFlags::D,
+            'g' => RegExpFlags::G,
+            'i' => RegExpFlags::I,
+            'm' => RegExpFlags::M,
+            's' => RegExpFlags::S,
+            'u' => RegExpFlags::U,
+            'v' => RegExpFlags::V,
+            'y' => RegExpFlags::Y,
+            _ => return None,
+        };
+        if flags.contains(flag) {
+            return None;
+        }
+        flags |= flag;
+    }
+    Some(flags)
+}
+
+/// Returns the flags in the order that `get RegExp.prototype.flags` returns
+/// them in.
+pub(crate) fn reg_exp_flags_to_string(flags: RegExpFlags) -> std::string::String {
+    [
+        (RegExpFlags::D, 'd'),
+        (RegExpFlags::G, 'g'),
+        (RegExpFlags::I, 'i'),
+        (RegExpFlags::M, 'm'),
+        (RegExpFlags::S, 's'),
+        (RegExpFlags::U, 'u'),
+        (RegExpFlags::V, 'v'),
+        (RegExpFlags::Y, 'y'),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| flags.contains(*flag))
+    .map(|(_, c)| c)
+    .collect()
+}
+
+fn throw_pattern_syntax_error(
+    agent: &mut Agent,
+    p: String,
+    f: RegExpFlags,
+    err: &regress::Error,
+    gc: NoGcScope,
+) -> JsError {
+    let message = format!(
+        "Invalid regular expression: /{}/{}: {}",
+        p.as_str(agent),
+        reg_exp_flags_to_string(f),
+        err.text
+    );
+    agent.throw_exception(ExceptionType::SyntaxError, message, gc)
+}
+
+/// Compiles the \[\[RegExpMatcher]] of R if it was created without one,
+/// throwing a SyntaxError if its pattern is invalid.
+pub(crate) fn reg_exp_compile_matcher(agent: &mut Agent, r: RegExp, gc: NoGcScope) -> JsResult<()> {
+    let data = &agent[r];
+    let source = data.original_source;
+    let flags = data.original_flags;
+    let result = data
+        .reg_exp_matcher
+        .get_or_init(|| parse_pattern(source.as_wtf8(agent), flags));
+    if let Err(err) = result {
+        let err = err.clone();
+        return Err(throw_pattern_syntax_error(agent, source, flags, &err, gc));
+    }
+    Ok(())
+}
+
+/// ### [22.2.6.13.1 EscapeRegExpPattern ( P, F )](https://tc39.es/ecma262/#sec-escaperegexppattern)
+///
+/// The abstract operation EscapeRegExpPattern takes arguments P (a String)
+/// and F (a String) and returns a String.
+pub(crate) fn escape_reg_exp_pattern(p: &str) -> std::string::String {
+    // 1. Let S be a String in the form of a Pattern[~UnicodeMode] (Pattern[+UnicodeMode] if F contains "u" or "v") equivalent to P interpreted as UTF-16 encoded Unicode code points (6.1.4), in which certain code points are escaped as described below. S may or may not differ from P; however, the Abstract Closure that would result from evaluating S as a Pattern[~UnicodeMode] (Pattern[+UnicodeMode] if F contains "u" or "v") must behave identically to the Abstract Closure given by the constructed object's [[RegExpMatcher]] internal slot. Multiple calls to this abstract operation using the same values for P and F must produce identical results.
+    // 2. The code points / or any LineTerminator occurring in the pattern shall be escaped in S as necessary to ensure that the string-concatenation of "/", S, "/", and F can be parsed (in an appropriate lexical context) as a RegularExpressionLiteral that behaves identically to the constructed regular expression. For example, if P is "/", then S could be "\/" or "/", among other possibilities, but not "/", because /// followed by F would be parsed as a SingleLineComment rather than a RegularExpressionLiteral. If P is the empty String, this specification can be met by letting S be "(?:)".
+    if p.is_empty() {
+        return "(?:)".to_string();
+    }
+    let mut s = std::string::String::with_capacity(p.len());
+    let mut in_class = false;
+    let mut chars = p.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                s.push('\\');
+                if let Some(c) = chars.next() {
+                    s.push(c);
+                }
+                continue;
+            }
+            '/' if !in_class => s.push_str("\\/"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\u{2028}' => s.push_str("\\u2028"),
+            '\u{2029}' => s.push_str("\\u2029"),
+            c => s.push(c),
+        }
+        match c {
+            '[' => in_class = true,
+            ']' => in_class = false,
+            _ => {}
+        }
+    }
+    // 3. Return S.
+    s
+}
+
+/// ### [22.2.7.1 RegExpExec ( R, S )](https://tc39.es/ecma262/#sec-regexpexec)
+///
+/// The abstract operation RegExpExec takes arguments R (an Object) and S (a
+/// String) and returns either a normal completion containing either an Object
+/// or null, or a throw completion.
+pub(crate) fn reg_exp_exec<'a>(
+    agent: &mut Agent,
+    r: Object,
+    s: String,
+    mut gc: GcScope<'a, '_>,
+) -> JsResult<Option<Object<'a>>> {
+    let r = r.bind(gc.nogc());
+    let s = s.bind(gc.nogc());
+    let scoped_r = r.scope(agent, gc.nogc());
+    let scoped_s = s.scope(agent, gc.nogc());
+    // 1. Let exec be ? Get(R, "exec").
+    let exec = get(
+        agent,
+        r.unbind(),
+        BUILTIN_STRING_MEMORY.exec.into(),
+        gc.reborrow(),
+    )?;
+    // 2. If IsCallable(exec) is true, then
+    if let Some(exec) = is_callable(exec, gc.nogc()) {
+        // a. Let result be ? Call(exec, R, « S »).
+        let result = call_function(
+            agent,
+            exec.unbind(),
+            scoped_r.get(agent).into_value(),
+            Some(ArgumentsList(&[scoped_s.get(agent).into_value()])),
+            gc.reborrow(),
+        )?;
+        let gc = gc.into_nogc();
+        // b. If result is not an Object and result is not null, throw a
+        //    TypeError exception.
+        // c. Return result.
+        return match result {
+            Value::Null => Ok(None),
+            _ => match Object::try_from(result) {
+                Ok(result) => Ok(Some(result.bind(gc))),
+                Err(_) => Err(agent.throw_exception_with_static_message(
+                    ExceptionType::TypeError,
+                    "RegExp exec method returned something other than an Object or null",
+                    gc,
+                )),
+            },
+        };
+    }
+    // 3. Perform ? RequireInternalSlot(R, [[RegExpMatcher]]).
+    let Object::RegExp(r) = scoped_r.get(agent) else {
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::TypeError,
+            "RegExp exec method called on incompatible receiver",
+            gc.nogc(),
+        ));
+    };
+    // 4. Return ? RegExpBuiltinExec(R, S).
+    Ok(reg_exp_builtin_exec(agent, r, scoped_s.get(agent), gc)?.map(|a| a.into_object()))
+}
+
+/// ### [22.2.7.2 RegExpBuiltinExec ( R, S )](https://tc39.es/ecma262/#sec-regexpbuiltinexec)
+///
+/// The abstract operation RegExpBuiltinExec takes arguments R (an initialized
+/// RegExp instance) and S (a String) and returns either a normal completion
+/// containing either an Array exotic object or null, or a throw completion.
+pub(crate) fn reg_exp_builtin_exec<'a>(
+    agent: &mut Agent,
+    r: RegExp,
+    s: String,
+    mut gc: GcScope<'a, '_>,
+) -> JsResult<Option<Array<'a>>> {
+    let r = r.bind(gc.nogc());
+    let s = s.bind(gc.nogc());
+    // 2. Let lastIndex be ℝ(? ToLength(! Get(R, "lastIndex"))).
+    let last_index = unwrap_try(try_get(
+        agent,
+        r,
+        BUILTIN_STRING_MEMORY.lastIndex.into(),
+        gc.nogc(),
+    ));
+    let (r, s, last_index) = if let Value::Integer(last_index) = last_index {
+        (
+            r.unbind(),
+            s.unbind(),
+            last_index.into_i64().max(0) as usize,
+        )
+    } else {
+        let scoped_r = r.scope(agent, gc.nogc());
+        let scoped_s = s.scope(agent, gc.nogc());
+        let last_index = to_length(agent, last_index, gc.reborrow())? as usize;
+        (scoped_r.get(agent), scoped_s.get(agent), last_index)
+    };
+    let gc = gc.into_nogc();
+    let r = r.bind(gc);
+    let s = s.bind(gc);
+    // 3. Let flags be R.[[OriginalFlags]].
+    let flags = agent[r].original_flags;
+    // 4. If flags contains "g", let global be true; else let global be false.
+    let global = flags.contains(RegExpFlags::G);
+    // 5. If flags contains "y", let sticky be true; else let sticky be false.
+    let sticky = flags.contains(RegExpFlags::Y);
+    // 6. If flags contains "d", let hasIndices be true; else let hasIndices be false.
+    let has_indices = flags.contains(RegExpFlags::D);
+    // 7. If global is false and sticky is false, set lastIndex to 0.
+    let last_index = if !global && !sticky { 0 } else { last_index };
+    // 8. Let matcher be R.[[RegExpMatcher]].
+    reg_exp_compile_matcher(agent, r, gc)?;
+    let matcher = agent[r].reg_exp_matcher.get().unwrap().as_ref().unwrap();
+    // 9. If flags contains "u" or flags contains "v", let fullUnicode be true; else let fullUnicode be false.
+    let full_unicode = flags.intersects(RegExpFlags::U.union(RegExpFlags::V));
+    // 11. If fullUnicode is true, let input be StringToCodePoints(S).
+    //     Otherwise, let input be a List whose elements are the code units
+    //     that are the elements of S.
+    // NOTE: The matcher decodes surrogate pairs itself in full Unicode mode,
+    // so match indices are always code unit indices into S.
+    let input = s.to_utf16(agent);
+    // 1. Let length be the length of S.
+    let length = input.len();
+    // 13. Repeat, while matchSucceeded is false,
+    //     a. If lastIndex > length, then
+    //         i. If global is true or sticky is true, then
+    //             1. Perform ? Set(R, "lastIndex", +0𝔽, true).
+    //         ii. Return null.
+    //     b. Let inputIndex be the index into input of the character that was obtained from element lastIndex of S.
+    //     c. Let r be matcher(input, inputIndex).
+    //     d. If r is failure, then
+    //         i. If sticky is true, then
+    //             1. Perform ? Set(R, "lastIndex", +0𝔽, true).
+    //             2. Return null.
+    //         ii. Set lastIndex to AdvanceStringIndex(S, lastIndex, fullUnicode).
+    //     e. Else,
+    //         i. Assert: r is a MatchState.
+    //         ii. Set matchSucceeded to true.
+    let found = if last_index > length {
+        None
+    } else if full_unicode {
+        matcher.regex.find_from_utf16(&input, last_index).next()
+    } else {
+        matcher.regex.find_from_ucs2(&input, last_index).next()
+    };
+    let found = found.filter(|found| !sticky || found.start() == last_index);
+    let group_names = matcher.group_names.clone();
+    let Some(found) = found else {
+        if global || sticky {
+            set_last_index(agent, r, 0, gc)?;
+        }
+        return Ok(None);
+    };
+    let last_index = found.start();
+    // 14. Let e be r.[[EndIndex]].
+    // 15. If fullUnicode is true, set e to GetStringIndex(S, e).
+    let e = found.end();
+    // 16. If global is true or sticky is true, then
+    if global || sticky {
+        // a. Perform ? Set(R, "lastIndex", 𝔽(e), true).
+        set_last_index(agent, r, e, gc)?;
+    }
+    // 17. Let n be the number of elements in r.[[Captures]].
+    // 22. Let A be ! ArrayCreate(n + 1).
+    // 29. Let matchedSubstr be GetMatchString(S, match).
+    // 30. Perform ! CreateDataPropertyOrThrow(A, "0", matchedSubstr).
+    // 33. For each integer i such that 1 ≤ i ≤ n, in ascending order, do
+    //     a. Let captureI be ith element of r.[[Captures]].
+    //     b. If captureI is undefined, let capturedValue be undefined.
+    //     c. Else,
+    //         iii. Let capturedValue be GetMatchString(S, capture).
+    //     d. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(i)), capturedValue).
+    let captured_values = found
+        .groups()
+        .map(|capture| {
+            capture.map_or(Value::Undefined, |capture| {
+                String::from_utf16_lossy(agent, &input[capture], gc).into_value()
+            })
+        })
+        .collect::<Vec<_>>();
+    let a = create_array_from_list(agent, &captured_values, gc);
+    // 24. Perform ! CreateDataPropertyOrThrow(A, "index", 𝔽(lastIndex)).
+    unwrap_try(try_create_data_property_or_throw(
+        agent,
+        a,
+        BUILTIN_STRING_MEMORY.index.into(),
+        Value::try_from(last_index as i64).unwrap(),
+        gc,
+    ))
+    .unwrap();
+    // 25. Perform ! CreateDataPropertyOrThrow(A, "input", S).
+    unwrap_try(try_create_data_property_or_throw(
+        agent,
+        a,
+        BUILTIN_STRING_MEMORY.input.into(),
+        s.into_value(),
+        gc,
+    ))
+    .unwrap();
+    // 31. If R contains any GroupName, then
+    //     a. Let groups be OrdinaryObjectCreate(null).
+    //     b. Let hasGroups be true.
+    // 32. Else,
+    //     a. Let groups be undefined.
+    //     b. Let hasGroups be false.
+    let groups = if group_names.iter().any(Option::is_some) {
+        Some(ordinary_object_create_with_intrinsics(
+            agent, None, None, gc,
+        ))
+    } else {
+        None
+    };
+    // 33. Perform ! CreateDataPropertyOrThrow(A, "groups", groups).
+    unwrap_try(try_create_data_property_or_throw(
+        agent,
+        a,
+        BUILTIN_STRING_MEMORY.groups.into(),
+        groups.map_or(Value::Undefined, |groups| groups.into_value()),
+        gc,
+    ))
+    .unwrap();
+    if let Some(groups) = groups {
+        for (name, captured_value) in group_names.iter().zip(&captured_values[1..]) {
+            // e. If the ith capture of R was defined with a GroupName, then
+            if let Some(name) = name {
+                // i. Let s be the CapturingGroupName of that GroupName.
+                let s = PropertyKey::from_str(agent, name, gc);
+                // ii. Perform ! CreateDataPropertyOrThrow(groups, s, capturedValue).
+                unwrap_try(try_create_data_property_or_throw(
+                    agent,
+                    groups,
+                    s,
+                    *captured_value,
+                    gc,
+                ))
+                .unwrap();
+            }
+        }
+    }
+    // 34. If hasIndices is true, then
+    if has_indices {
+        // a. Let indicesArray be MakeMatchIndicesIndexPairArray(S, indices, groupNames, hasGroups).
+        let indices_array =
+            make_match_indices_index_pair_array(agent, &found, &group_names, groups.is_some(), gc);
+        // b. Perform ! CreateDataPropertyOrThrow(A, "indices", indicesArray).
+        unwrap_try(try_create_data_property_or_throw(
+            agent,
+            a,
+            BUILTIN_STRING_MEMORY.indices.into(),
+            indices_array.into_value(),
+            gc,
+        ))
+        .unwrap();
+    }
+    // 35. Return A.
+    Ok(Some(a))
+}
+
+/// Perform ? Set(R, "lastIndex", 𝔽(index), true) on a RegExp instance.
+fn set_last_index(agent: &mut Agent, r: RegExp, index: usize, gc: NoGcScope) -> JsResult<()> {
+    unwrap_try(try_set(
+        agent,
+        r.into_object(),
+        BUILTIN_STRING_MEMORY.lastIndex.into(),
+        Value::try_from(index as i64).unwrap(),
+        true,
+        gc,
+    ))
+}
+
+/// ### [22.2.7.3 AdvanceStringIndex ( S, index, unicode )](https://tc39.es/ecma262/#sec-advancestringindex)
+///
+/// The abstract operation AdvanceStringIndex takes arguments S (a String),
+/// index (a non-negative integer), and unicode (a Boolean) and returns an
+/// integer.
+pub(crate) fn advance_string_index(s: &[u16], index: usize, unicode: bool) -> usize {
+    // 1. Assert: index ≤ 2**53 - 1.
+    // 2. If unicode is false, return index + 1.
+    if !unicode {
+        return index + 1;
+    }
+    // 3. Let length be the length of S.
+    // 4. If index + 1 ≥ length, return index + 1.
+    if index + 1 >= s.len() {
+        return index + 1;
+    }
+    // 5. Let cp be CodePointAt(S, index).
+    // 6. Return index + cp.[[CodeUnitCount]].
+    if (0xD800..=0xDBFF).contains(&s[index]) && (0xDC00..=0xDFFF).contains(&s[index + 1]) {
+        index + 2
+    } else {
+        index + 1
+    }
+}
+
+/// Performs the steps shared by RegExp.prototype\[%Symbol.match%],
+/// RegExp.prototype\[%Symbol.replace%] and %RegExpStringIteratorPrototype%.next
+/// when a global match was the empty String:
+///
+/// 1. Let thisIndex be ℝ(? ToLength(? Get(rx, "lastIndex"))).
+/// 2. Let nextIndex be AdvanceStringIndex(S, thisIndex, fullUnicode).
+/// 3. Perform ? Set(rx, "lastIndex", 𝔽(nextIndex), true).
+pub(crate) fn advance_last_index(
+    agent: &mut Agent,
+    rx: Object,
+    s: String,
+    full_unicode: bool,
+    mut gc: GcScope,
+) -> JsResult<()> {
+    let rx = rx.bind(gc.nogc()).scope(agent, gc.nogc());
+    let s = s.bind(gc.nogc()).scope(agent, gc.nogc());
+    let this_index = get(
+        agent,
+        rx.get(agent),
+        BUILTIN_STRING_MEMORY.lastIndex.into(),
+        gc.reborrow(),
+    )?;
+    let this_index = to_length(agent, this_index, gc.reborrow())? as usize;
+    let next_index = advance_string_index(&s.get(agent).to_utf16(agent), this_index, full_unicode);
+    set(
+        agent,
+        rx.get(agent),
+        BUILTIN_STRING_MEMORY.lastIndex.into(),
+        Value::try_from(next_index as i64).unwrap(),
+        true,
+        gc,
+    )
+}
+
+/// ### [22.2.7.8 MakeMatchIndicesIndexPairArray ( S, indices, groupNames, hasGroups )](https://tc39.es/ecma262/#sec-makematchindicesindexpairarray)
+///
+/// The abstract operation MakeMatchIndicesIndexPairArray takes arguments S
+/// (a String), indices (a List of either Match Records or undefined),
+/// groupNames (a List of either Strings or undefined), and hasGroups (a
+/// Boolean) and returns an Array.
+fn make_match_indices_index_pair_array<'a>(
+    agent: &mut Agent,
+    indices: &regress::Match,
+    group_names: &[Option<Box<str>>],
+    has_groups: bool,
+    gc: NoGcScope<'a, '_>,
+) -> Array<'a> {
+    // 8. For each integer i such that 0 ≤ i < n, in ascending order, do
+    //     a. Let matchIndices be indices[i].
+    //     b. If matchIndices is not undefined, then
+    //         i. Let matchIndexPair be GetMatchIndexPair(S, matchIndices).
+    //     c. Else,
+    //         i. Let matchIndexPair be undefined.
+    let match_index_pairs = indices
+        .groups()
+        .map(|match_indices| {
+            match_indices.map_or(Value::Undefined, |match_indices| {
+                create_array_from_list(
+                    agent,
+                    &[
+                        Value::try_from(match_indices.start as i64).unwrap(),
+                        Value::try_from(match_indices.end as i64).unwrap(),
+                    ],
+                    gc,
+                )
+                .into_value()
+            })
+        })
+        .collect::<Vec<_>>();
+    // 4. Let A be ! ArrayCreate(n).
+    // 8.d. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(i)), matchIndexPair).
+    let a = create_array_from_list(agent, &match_index_pairs, gc);
+    // 6. If hasGroups is true, then
+    //     a. Let groups be OrdinaryObjectCreate(null).
+    // 7. Else,
+    //     a. Let groups be undefined.
+    let groups = if has_groups {
+        Some(ordinary_object_create_with_intrinsics(
+            agent, None, None, gc,
+        ))
+    } else {
+        None
+    };
+    // 8. Perform ! CreateDataPropertyOrThrow(A, "groups", groups).
+    unwrap_try(try_create_data_property_or_throw(
+        agent,
+        a,
+        BUILTIN_STRING_MEMORY.groups.into(),
+        groups.map_or(Value::Undefined, |groups| groups.into_value()),
+        gc,
+    ))
+    .unwrap();
+    if let Some(groups) = groups {
+        // 8.e. If i > 0 and groupNames[i - 1] is not undefined, then
+        for (name, match_index_pair) in group_names.iter().zip(&match_index_pairs[1..]) {
+            if let Some(name) = name {
+                // i. Assert: groups is not undefined.
+                // ii. Perform ! CreateDataPropertyOrThrow(groups, groupNames[i - 1], matchIndexPair).
+                let name = PropertyKey::from_str(agent, name, gc);
+                unwrap_try(try_create_data_property_or_throw(
+                    agent,
+                    groups,
+                    name,
+                    *match_index_pair,
+                    gc,
+                ))
+                .unwrap();
+            }
+        }
+    }
+    // 9. Return A.
+    a
+}
+
+/// Creates a RegExp from a pattern String as %RegExp%(P, F) would, throwing a
+/// SyntaxError if the pattern is invalid.
+pub(crate) fn reg_exp_create_and_validate<'a>(
+    agent: &mut Agent,
+    p: String,
+    f: Option<RegExpFlags>,
+    gc: NoGcScope<'a, '_>,
+) -> JsResult<RegExp<'a>> {
+    let rx = reg_exp_create(agent, p, f, gc);
+    reg_exp_compile_matcher(agent, rx, gc)?;
+    Ok(rx)
 }
@@ -2,7 +2,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::cell::OnceCell;
+
 use oxc_ast::ast::RegExpFlags;
+use regress::Regex;
 
 use crate::{
     ecmascript::types::{OrdinaryObject, PropertyDescriptor, String, Value},
@@ -98,10 +101,22 @@ impl RegExpLastIndex {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// ### \[\[RegExpMatcher]]
+///
+/// The compiled form of a RegExp pattern, together with the names of its
+/// capturing groups in left-to-right order.
+#[derive(Debug, Clone)]
+pub(crate) struct RegExpMatcher {
+    pub(crate) regex: Regex,
+    pub(crate) group_names: Box<[Option<Box<str>>]>,
+}
+
+#[derive(Debug, Clone)]
 pub struct RegExpHeapData {
     pub(crate) object_index: Option<OrdinaryObject<'static>>,
-    // _regex: RegExp,
+    /// The pattern is compiled on first use: RegExp literals are created
+    /// while compiling bytecode, where a pattern error cannot be thrown.
+    pub(crate) reg_exp_matcher: OnceCell<Result<RegExpMatcher, regress::Error>>,
     pub(crate) original_source: String<'static>,
     pub(crate) original_flags: RegExpFlags,
     pub(crate) last_index: RegExpLastIndex,
@@ -111,6 +126,7 @@ impl Default for RegExpHeapData {
     fn default() -> Self {
         Self {
             object_index: Default::default(),
+            reg_exp_matcher: OnceCell::new(),
             original_source: String::EMPTY_STRING,
             original_flags: RegExpFlags::empty(),
             last_index: Default::default(),
@@ -122,6 +138,7 @@ impl HeapMarkAndSweep for RegExpHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
             object_index,
+            reg_exp_matcher: _,
             original_source,
             original_flags: _,
             last_index: _,
@@ -133,6 +150,7 @@ impl HeapMarkAndSweep for RegExpHeapData {
     fn sweep_values(&mut self, compactions: &CompactionLists) {
         let Self {
             object_index,
+            reg_exp_matcher: _,
             original_source,
             original_flags: _,
             last_index: _,
@@ -14,7 +14,7 @@ use crate::{
     engine::{context::NoGcScope, rootable::HeapRootData, Scoped},
     heap::{
         indexes::{BaseIndex, SetIndex},
-        CompactionLists, CreateHeapData, HeapMarkAndSweep, WorkQueues,
+        CompactionLists, CreateHeapData, HeapMarkAndSweep, OwnedAllocations, WorkQueues,
     },
     Heap,
 };
@@ -190,6 +190,7 @@ impl TryFrom<HeapRootData> for Set<'_> {
 
 impl CreateHeapData<SetHeapData, Set<'static>> for Heap {
     fn create(&mut self, data: SetHeapData) -> Set<'static> {
+        self.owned_bytes += data.owned_bytes();
         self.sets.push(Some(data));
         Set(SetIndex::last(&self.sets))
     }
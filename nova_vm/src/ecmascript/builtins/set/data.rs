@@ -3,11 +3,16 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::{
-    ecmascript::types::{
-        bigint::HeapBigInt, HeapNumber, HeapString, OrdinaryObject, Value, BIGINT_DISCRIMINANT,
-        NUMBER_DISCRIMINANT, STRING_DISCRIMINANT,
+    ecmascript::{
+        abstract_operations::testing_and_comparison::same_value,
+        types::{
+            bigint::HeapBigInt, HeapNumber, HeapString, OrdinaryObject, Value, BIGINT_DISCRIMINANT,
+            NUMBER_DISCRIMINANT, STRING_DISCRIMINANT,
+        },
+    },
+    heap::{
+        CompactionLists, HeapMarkAndSweep, OwnedAllocations, PrimitiveHeapIndexable, WorkQueues,
     },
-    heap::{CompactionLists, HeapMarkAndSweep, PrimitiveHeapIndexable, WorkQueues},
 };
 use ahash::AHasher;
 use hashbrown::{hash_table::Entry, HashTable};
@@ -61,6 +66,47 @@ impl SetHeapData {
         self.set_data.rehash_if_needed(arena);
         &mut self.set_data
     }
+
+    /// Create a new SetHeapData containing a copy of this Set's
+    /// \[\[SetData]]. Empty slots are copied over as well, so indexes into
+    /// the copy match indexes into the original.
+    pub(crate) fn copy_set_data(&self, arena: &impl PrimitiveHeapIndexable) -> Self {
+        let SetData {
+            values, set_data, ..
+        } = self.borrow(arena);
+        Self {
+            object_index: None,
+            set_data: SetData {
+                values: values.clone(),
+                set_data: RefCell::new(set_data.borrow().clone()),
+                needs_primitive_rehashing: AtomicBool::new(false),
+            },
+        }
+    }
+
+    /// Create a new SetHeapData from a list of values.
+    ///
+    /// The values must already be canonicalized and must be unique according
+    /// to SameValue.
+    pub(crate) fn from_unique_values(
+        values: Vec<Value>,
+        arena: &impl PrimitiveHeapIndexable,
+    ) -> Self {
+        let mut set_data = HashTable::with_capacity(values.len());
+        for (index, value) in values.iter().enumerate() {
+            set_data.insert_unique(hash_value(arena, *value), index as u32, |index_to_hash| {
+                hash_value(arena, values[*index_to_hash as usize])
+            });
+        }
+        Self {
+            object_index: None,
+            set_data: SetData {
+                values: values.into_iter().map(Some).collect(),
+                set_data: RefCell::new(set_data),
+                needs_primitive_rehashing: AtomicBool::new(false),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -79,6 +125,95 @@ pub(crate) struct SetData {
 }
 
 impl SetData {
+    /// ### [24.2.1.4 SetDataIndex ( setData, value )](https://tc39.es/ecma262/#sec-setdataindex)
+    ///
+    /// The abstract operation SetDataIndex takes arguments setData (a List of
+    /// either ECMAScript language values or EMPTY) and value (an ECMAScript
+    /// language value) and returns a non-negative integer or NOT-FOUND.
+    ///
+    /// > NOTE: The value must already be canonicalized.
+    pub(crate) fn index_of(
+        &self,
+        arena: &impl PrimitiveHeapIndexable,
+        value: Value,
+    ) -> Option<u32> {
+        let SetData {
+            values, set_data, ..
+        } = self;
+        let value_hash = hash_value(arena, value);
+        set_data
+            .borrow()
+            .find(value_hash, |hash_equal_index| {
+                let found_value = values[*hash_equal_index as usize].unwrap();
+                // Quick check: Equal values have the same value.
+                found_value == value || same_value(arena, found_value, value)
+            })
+            .copied()
+    }
+
+    /// ### [24.2.1.3 SetDataHas ( setData, value )](https://tc39.es/ecma262/#sec-setdatahas)
+    ///
+    /// The abstract operation SetDataHas takes arguments setData (a List of
+    /// either ECMAScript language values or EMPTY) and value (an ECMAScript
+    /// language value) and returns a Boolean.
+    ///
+    /// > NOTE: The value must already be canonicalized.
+    pub(crate) fn has(&self, arena: &impl PrimitiveHeapIndexable, value: Value) -> bool {
+        self.index_of(arena, value).is_some()
+    }
+
+    /// Append value to the SetData if it is not already present. Returns true
+    /// if the value was appended.
+    ///
+    /// > NOTE: The value must already be canonicalized.
+    pub(crate) fn append(&mut self, arena: &impl PrimitiveHeapIndexable, value: Value) -> bool {
+        let SetData {
+            values, set_data, ..
+        } = self;
+        let set_data = set_data.get_mut();
+        let value_hash = hash_value(arena, value);
+        if let Entry::Vacant(entry) = set_data.entry(
+            value_hash,
+            |hash_equal_index| {
+                let found_value = values[*hash_equal_index as usize].unwrap();
+                // Quick check: Equal values have the same value.
+                found_value == value || same_value(arena, found_value, value)
+            },
+            |index_to_hash| hash_value(arena, values[*index_to_hash as usize].unwrap()),
+        ) {
+            let index = u32::try_from(values.len()).unwrap();
+            entry.insert(index);
+            values.push(Some(value));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Replace the element of the SetData whose value is value with EMPTY.
+    /// Returns true if the value was found.
+    ///
+    /// > NOTE: The value must already be canonicalized.
+    pub(crate) fn remove(&mut self, arena: &impl PrimitiveHeapIndexable, value: Value) -> bool {
+        let SetData {
+            values, set_data, ..
+        } = self;
+        let set_data = set_data.get_mut();
+        let value_hash = hash_value(arena, value);
+        if let Ok(entry) = set_data.find_entry(value_hash, |hash_equal_index| {
+            let found_value = values[*hash_equal_index as usize].unwrap();
+            // Quick check: Equal values have the same value.
+            found_value == value || same_value(arena, found_value, value)
+        }) {
+            let index = *entry.get() as usize;
+            values[index] = None;
+            let _ = entry.remove();
+            true
+        } else {
+            false
+        }
+    }
+
     fn rehash_if_needed(&self, arena: &impl PrimitiveHeapIndexable) {
         if !self.needs_primitive_rehashing.load(Ordering::Relaxed) {
             return;
@@ -107,6 +242,12 @@ impl SetData {
     }
 }
 
+fn hash_value(arena: &impl PrimitiveHeapIndexable, value: Value) -> u64 {
+    let mut hasher = AHasher::default();
+    value.hash(arena, &mut hasher);
+    hasher.finish()
+}
+
 fn rehash_set_data(
     values: &[Option<Value>],
     set_data: &mut HashTable<u32>,
@@ -166,6 +307,18 @@ fn rehash_set_data(
     }
 }
 
+impl OwnedAllocations for SetHeapData {
+    fn owned_bytes(&self) -> usize {
+        let SetData {
+            values,
+            set_data,
+            needs_primitive_rehashing: _,
+        } = &self.set_data;
+        values.capacity() * size_of::<Option<Value>>()
+            + set_data.borrow().capacity() * size_of::<u32>()
+    }
+}
+
 impl HeapMarkAndSweep for SetHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
@@ -4,4 +4,4 @@
 
 pub(crate) mod regexp_constructor;
 pub(crate) mod regexp_prototype;
-pub(crate) mod regexp_string_iterator_prototype;
+pub(crate) mod regexp_string_iterator_objects;
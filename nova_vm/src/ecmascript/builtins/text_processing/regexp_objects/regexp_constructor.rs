@@ -2,7 +2,12 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::ecmascript::abstract_operations::operations_on_objects::get;
+use crate::ecmascript::abstract_operations::testing_and_comparison::{is_reg_exp, same_value};
 use crate::ecmascript::builders::builtin_function_builder::BuiltinFunctionBuilder;
+use crate::ecmascript::builtins::regexp::{
+    reg_exp_alloc, reg_exp_flags_to_string, reg_exp_initialize,
+};
 use crate::ecmascript::builtins::ArgumentsList;
 use crate::ecmascript::builtins::Behaviour;
 use crate::ecmascript::builtins::Builtin;
@@ -12,7 +17,9 @@ use crate::ecmascript::execution::Agent;
 use crate::ecmascript::execution::JsResult;
 use crate::ecmascript::execution::RealmIdentifier;
 
+use crate::ecmascript::types::Function;
 use crate::ecmascript::types::IntoObject;
+use crate::ecmascript::types::IntoValue;
 use crate::ecmascript::types::Object;
 use crate::ecmascript::types::PropertyKey;
 use crate::ecmascript::types::String;
@@ -44,14 +51,100 @@ impl Builtin for RegExpGetSpecies {
 impl BuiltinGetter for RegExpGetSpecies {}
 
 impl RegExpConstructor {
+    /// ### [22.2.4.1 RegExp ( pattern, flags )](https://tc39.es/ecma262/#sec-regexp-pattern-flags)
     fn constructor(
-        _agent: &mut Agent,
+        agent: &mut Agent,
         _this_value: Value,
-        _arguments: ArgumentsList,
-        _new_target: Option<Object>,
-        _gc: GcScope,
+        arguments: ArgumentsList,
+        new_target: Option<Object>,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!();
+        let pattern = arguments.get(0).scope(agent, gc.nogc());
+        let flags = arguments.get(1).scope(agent, gc.nogc());
+        let new_target = new_target.map(|new_target| new_target.scope(agent, gc.nogc()));
+        // 1. Let patternIsRegExp be ? IsRegExp(pattern).
+        let pattern_is_reg_exp = is_reg_exp(agent, pattern.get(agent), gc.reborrow())?;
+        let new_target = if let Some(new_target) = new_target {
+            // 3. Else,
+            // a. Let newTarget be NewTarget.
+            Function::try_from(new_target.get(agent)).unwrap()
+        } else {
+            // 2. If NewTarget is undefined, then
+            // a. Let newTarget be the active function object.
+            let new_target = agent.active_function_object(gc.nogc());
+            // b. If patternIsRegExp is true and flags is undefined, then
+            if pattern_is_reg_exp && flags.get(agent).is_undefined() {
+                let new_target = new_target.scope(agent, gc.nogc());
+                // i. Let patternConstructor be ? Get(pattern, "constructor").
+                let pattern_constructor = get(
+                    agent,
+                    Object::try_from(pattern.get(agent)).unwrap(),
+                    BUILTIN_STRING_MEMORY.constructor.into(),
+                    gc.reborrow(),
+                )?;
+                // ii. If SameValue(newTarget, patternConstructor) is true, return pattern.
+                if same_value(agent, new_target.get(agent), pattern_constructor) {
+                    return Ok(pattern.get(agent));
+                }
+                new_target.get(agent)
+            } else {
+                new_target.unbind()
+            }
+        };
+        let new_target = new_target.scope(agent, gc.nogc());
+        let (p, f) = if let Value::RegExp(pattern) = pattern.get(agent) {
+            // 4. If pattern is an Object and pattern has a [[RegExpMatcher]]
+            //    internal slot, then
+            // a. Let P be pattern.[[OriginalSource]].
+            let p = agent[pattern].original_source.into_value();
+            // b. If flags is undefined, let F be pattern.[[OriginalFlags]].
+            // c. Else, let F be flags.
+            let f = if flags.get(agent).is_undefined() {
+                let f = reg_exp_flags_to_string(agent[pattern].original_flags);
+                String::from_string(agent, f, gc.nogc()).into_value()
+            } else {
+                flags.get(agent)
+            };
+            (p.scope(agent, gc.nogc()), f.scope(agent, gc.nogc()))
+        } else if pattern_is_reg_exp {
+            // 5. Else if patternIsRegExp is true, then
+            let pattern = Object::try_from(pattern.get(agent))
+                .unwrap()
+                .scope(agent, gc.nogc());
+            // a. Let P be ? Get(pattern, "source").
+            let p = get(
+                agent,
+                pattern.get(agent),
+                BUILTIN_STRING_MEMORY.source.into(),
+                gc.reborrow(),
+            )?
+            .scope(agent, gc.nogc());
+            // b. If flags is undefined, then
+            let f = if flags.get(agent).is_undefined() {
+                // i. Let F be ? Get(pattern, "flags").
+                get(
+                    agent,
+                    pattern.get(agent),
+                    BUILTIN_STRING_MEMORY.flags.into(),
+                    gc.reborrow(),
+                )?
+                .scope(agent, gc.nogc())
+            } else {
+                // c. Else,
+                // i. Let F be flags.
+                flags
+            };
+            (p, f)
+        } else {
+            // 6. Else,
+            // a. Let P be pattern.
+            // b. Let F be flags.
+            (pattern, flags)
+        };
+        // 7. Let O be ? RegExpAlloc(newTarget).
+        let o = reg_exp_alloc(agent, new_target.get(agent), gc.reborrow())?.unbind();
+        // 8. Return ? RegExpInitialize(O, P, F).
+        reg_exp_initialize(agent, o, p.get(agent), f.get(agent), gc).map(|o| o.into_value())
     }
 
     fn get_species(
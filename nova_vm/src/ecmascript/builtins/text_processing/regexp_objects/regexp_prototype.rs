@@ -2,15 +2,38 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use oxc_ast::ast::RegExpFlags;
+
 use crate::ecmascript::abstract_operations::type_conversion::to_boolean;
-use crate::engine::context::GcScope;
+use crate::ecmascript::builtins::regexp::abstract_operations::{
+    advance_last_index, advance_string_index, escape_reg_exp_pattern, reg_exp_builtin_exec,
+    reg_exp_exec, reg_exp_flags_to_string,
+};
+use crate::ecmascript::builtins::text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIteratorHeapData;
+use crate::ecmascript::builtins::text_processing::string_objects::string_prototype::get_substitution;
+use crate::engine::context::{GcScope, NoGcScope};
+use crate::engine::Scoped;
+use crate::heap::CreateHeapData;
+use crate::SmallInteger;
 use crate::{
     ecmascript::{
-        abstract_operations::{operations_on_objects::get, type_conversion::to_string},
+        abstract_operations::{
+            operations_on_objects::{
+                call_function, construct, create_array_from_list, create_array_from_scoped_list,
+                get, length_of_array_like, set,
+            },
+            testing_and_comparison::{is_callable, same_value},
+            type_conversion::{
+                to_integer_or_infinity, to_length, to_object, to_string, to_uint32,
+            },
+        },
         builders::ordinary_object_builder::OrdinaryObjectBuilder,
         builtins::{ArgumentsList, Behaviour, Builtin, BuiltinGetter, BuiltinIntrinsic},
         execution::{agent::ExceptionType, Agent, JsResult, RealmIdentifier},
-        types::{Object, PropertyKey, String, Value, BUILTIN_STRING_MEMORY},
+        types::{
+            IntoFunction, IntoValue, Number, Object, PropertyKey, String, Value,
+            BUILTIN_STRING_MEMORY,
+        },
     },
     heap::{IntrinsicFunctionIndexes, WellKnownSymbolIndexes},
 };
@@ -162,22 +185,41 @@ impl Builtin for RegExpPrototypeGetUnicodeSets {
 impl BuiltinGetter for RegExpPrototypeGetUnicodeSets {}
 
 impl RegExpPrototype {
+    /// ### [22.2.6.2 RegExp.prototype.exec ( string )](https://tc39.es/ecma262/#sec-regexp.prototype.exec)
     fn exec(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. Perform ? RequireInternalSlot(R, [[RegExpMatcher]]).
+        let Value::RegExp(r) = this_value else {
+            return Err(agent.throw_exception_with_static_message(
+                ExceptionType::TypeError,
+                "RegExp.prototype.exec called on incompatible receiver",
+                gc.nogc(),
+            ));
+        };
+        let r = r.bind(gc.nogc()).scope(agent, gc.nogc());
+        // 3. Let S be ? ToString(string).
+        let s = to_string(agent, arguments.get(0), gc.reborrow())?.unbind();
+        // 4. Return ? RegExpBuiltinExec(R, S).
+        Ok(reg_exp_builtin_exec(agent, r.get(agent), s, gc)?
+            .map_or(Value::Null, |a| a.into_value()))
     }
 
+    /// ### [22.2.6.3 get RegExp.prototype.dotAll](https://tc39.es/ecma262/#sec-get-regexp.prototype.dotAll)
     fn get_dot_all(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. Let cu be the code unit 0x0073 (LATIN SMALL LETTER S).
+        // 3. Return ? RegExpHasFlag(R, cu).
+        reg_exp_has_flag(agent, this_value, RegExpFlags::S, gc.nogc())
     }
 
     /// ### [22.2.6.4 get RegExp.prototype.flags](https://tc39.es/ecma262/#sec-get-regexp.prototype.flags)
@@ -261,6 +303,7 @@ impl RegExpPrototype {
         // 11. If multiline is true, append the code unit 0x006D (LATIN SMALL LETTER M) to codeUnits.
         if multiline {
             code_units[i] = b'm';
+            i += 1;
         };
 
         // 12. Let dotAll be ToBoolean(? Get(R, "dotAll")).
@@ -319,7 +362,7 @@ impl RegExpPrototype {
 
         // 19. If sticky is true, append the code unit 0x0079 (LATIN SMALL LETTER Y) to codeUnits.
         if sticky {
-            code_units[i] = b'v';
+            code_units[i] = b'y';
             i += 1;
         };
 
@@ -328,112 +371,753 @@ impl RegExpPrototype {
         Ok(Value::from_string(agent, res.to_string(), gc.nogc()))
     }
 
+    /// ### [22.2.6.5 get RegExp.prototype.global](https://tc39.es/ecma262/#sec-get-regexp.prototype.global)
     fn get_global(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. Let cu be the code unit 0x0067 (LATIN SMALL LETTER G).
+        // 3. Return ? RegExpHasFlag(R, cu).
+        reg_exp_has_flag(agent, this_value, RegExpFlags::G, gc.nogc())
     }
 
+    /// ### [22.2.6.6 get RegExp.prototype.hasIndices](https://tc39.es/ecma262/#sec-get-regexp.prototype.hasIndices)
     fn get_has_indices(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. Let cu be the code unit 0x0064 (LATIN SMALL LETTER D).
+        // 3. Return ? RegExpHasFlag(R, cu).
+        reg_exp_has_flag(agent, this_value, RegExpFlags::D, gc.nogc())
     }
 
+    /// ### [22.2.6.7 get RegExp.prototype.ignoreCase](https://tc39.es/ecma262/#sec-get-regexp.prototype.ignorecase)
     fn get_ignore_case(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. Let cu be the code unit 0x0069 (LATIN SMALL LETTER I).
+        // 3. Return ? RegExpHasFlag(R, cu).
+        reg_exp_has_flag(agent, this_value, RegExpFlags::I, gc.nogc())
     }
 
+    /// ### [22.2.6.8 RegExp.prototype \[ %Symbol.match% \] ( string )](https://tc39.es/ecma262/#sec-regexp.prototype-%symbol.match%)
     fn r#match(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let rx be the this value.
+        // 2. If rx is not an Object, throw a TypeError exception.
+        let rx = this_object(agent, this_value, gc.nogc())?.scope(agent, gc.nogc());
+        // 3. Let S be ? ToString(string).
+        let s = to_string(agent, arguments.get(0), gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
+        // 4. Let flags be ? ToString(? Get(rx, "flags")).
+        let flags = get(
+            agent,
+            rx.get(agent),
+            BUILTIN_STRING_MEMORY.flags.into(),
+            gc.reborrow(),
+        )?;
+        let flags = to_string(agent, flags, gc.reborrow())?;
+        let flags = flags.as_str(agent);
+        let global = flags.contains('g');
+        let full_unicode = flags.contains('u') || flags.contains('v');
+        // 5. If flags does not contain "g", then
+        if !global {
+            // a. Return ? RegExpExec(rx, S).
+            return Ok(reg_exp_exec(agent, rx.get(agent), s.get(agent), gc)?
+                .map_or(Value::Null, |o| o.into_value()));
+        }
+        // 6. Else,
+        // a. If flags contains "u" or flags contains "v", let fullUnicode be true. Otherwise, let fullUnicode be false.
+        // b. Perform ? Set(rx, "lastIndex", +0𝔽, true).
+        set(
+            agent,
+            rx.get(agent),
+            BUILTIN_STRING_MEMORY.lastIndex.into(),
+            Value::Integer(SmallInteger::zero()),
+            true,
+            gc.reborrow(),
+        )?;
+        // c. Let A be ! ArrayCreate(0).
+        // d. Let n be 0.
+        let mut a: Vec<Scoped<Value>> = vec![];
+        // e. Repeat,
+        loop {
+            // i. Let result be ? RegExpExec(rx, S).
+            let result = reg_exp_exec(agent, rx.get(agent), s.get(agent), gc.reborrow())?;
+            // ii. If result is null, then
+            let Some(result) = result else {
+                // 1. If n = 0, return null.
+                if a.is_empty() {
+                    return Ok(Value::Null);
+                }
+                // 2. Return A.
+                return Ok(create_array_from_scoped_list(agent, a, gc.nogc()).into_value());
+            };
+            // iii. Else,
+            // 1. Let matchStr be ? ToString(? Get(result, "0")).
+            let match_str = get(agent, result.unbind(), 0.into(), gc.reborrow())?;
+            let match_str = to_string(agent, match_str, gc.reborrow())?
+                .unbind()
+                .bind(gc.nogc());
+            // 2. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(n)), matchStr).
+            let is_empty = match_str.is_empty_string();
+            a.push(match_str.into_value().scope(agent, gc.nogc()));
+            // 3. If matchStr is the empty String, then
+            if is_empty {
+                // a. Let thisIndex be ℝ(? ToLength(? Get(rx, "lastIndex"))).
+                // b. Let nextIndex be AdvanceStringIndex(S, thisIndex, fullUnicode).
+                // c. Perform ? Set(rx, "lastIndex", 𝔽(nextIndex), true).
+                advance_last_index(
+                    agent,
+                    rx.get(agent),
+                    s.get(agent),
+                    full_unicode,
+                    gc.reborrow(),
+                )?;
+            }
+            // 4. Set n to n + 1.
+        }
     }
 
+    /// ### [22.2.6.9 RegExp.prototype \[ %Symbol.matchAll% \] ( string )](https://tc39.es/ecma262/#sec-regexp-prototype-%symbol.matchall%)
     fn match_all(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. If R is not an Object, throw a TypeError exception.
+        let r = this_object(agent, this_value, gc.nogc())?.scope(agent, gc.nogc());
+        // 3. Let S be ? ToString(string).
+        let s = to_string(agent, arguments.get(0), gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
+        // 4. Let C be ? SpeciesConstructor(R, %RegExp%).
+        // NOTE: We're ignoring species and subclasses.
+        let c = agent.current_realm().intrinsics().reg_exp();
+        // 5. Let flags be ? ToString(? Get(R, "flags")).
+        let flags = get(
+            agent,
+            r.get(agent),
+            BUILTIN_STRING_MEMORY.flags.into(),
+            gc.reborrow(),
+        )?;
+        let flags = to_string(agent, flags, gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
+        // 6. Let matcher be ? Construct(C, « R, flags »).
+        let matcher = construct(
+            agent,
+            c.into_function(),
+            Some(ArgumentsList(&[
+                r.get(agent).into_value(),
+                flags.get(agent).into_value(),
+            ])),
+            None,
+            gc.reborrow(),
+        )?
+        .unbind()
+        .scope(agent, gc.nogc());
+        // 7. Let lastIndex be ? ToLength(? Get(R, "lastIndex")).
+        let last_index = get(
+            agent,
+            r.get(agent),
+            BUILTIN_STRING_MEMORY.lastIndex.into(),
+            gc.reborrow(),
+        )?;
+        let last_index = to_length(agent, last_index, gc.reborrow())?;
+        // 8. Perform ? Set(matcher, "lastIndex", lastIndex, true).
+        set(
+            agent,
+            matcher.get(agent),
+            BUILTIN_STRING_MEMORY.lastIndex.into(),
+            Value::try_from(last_index).unwrap(),
+            true,
+            gc.reborrow(),
+        )?;
+        // 9. If flags contains "g", let global be true.
+        // 10. Else, let global be false.
+        let global = flags.as_str(agent).contains('g');
+        // 11. If flags contains "u" or flags contains "v", let fullUnicode be true.
+        // 12. Else, let fullUnicode be false.
+        let full_unicode = flags.as_str(agent).contains('u') || flags.as_str(agent).contains('v');
+        // 13. Return CreateRegExpStringIterator(matcher, S, global, fullUnicode).
+        let iterator = agent.heap.create(RegExpStringIteratorHeapData {
+            object_index: None,
+            iterating_reg_exp: Some(matcher.get(agent)),
+            iterated_string: s.get(agent),
+            global,
+            unicode: full_unicode,
+        });
+        Ok(iterator.into_value())
     }
 
+    /// ### [22.2.6.10 get RegExp.prototype.multiline](https://tc39.es/ecma262/#sec-get-regexp.prototype.multiline)
     fn get_multiline(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. Let cu be the code unit 0x006D (LATIN SMALL LETTER M).
+        // 3. Return ? RegExpHasFlag(R, cu).
+        reg_exp_has_flag(agent, this_value, RegExpFlags::M, gc.nogc())
     }
 
+    /// ### [22.2.6.11 RegExp.prototype \[ %Symbol.replace% \] ( string, replaceValue )](https://tc39.es/ecma262/#sec-regexp.prototype-%symbol.replace%)
     fn replace(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let replace_value = arguments.get(1);
+        // 1. Let rx be the this value.
+        // 2. If rx is not an Object, throw a TypeError exception.
+        let rx = this_object(agent, this_value, gc.nogc())?.scope(agent, gc.nogc());
+        // 3. Let S be ? ToString(string).
+        let s = to_string(agent, arguments.get(0), gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
+        // 5. Let functionalReplace be IsCallable(replaceValue).
+        let functional_replace =
+            is_callable(replace_value, gc.nogc()).map(|f| f.unbind().scope(agent, gc.nogc()));
+        // 6. If functionalReplace is false, then
+        let replace_template = if functional_replace.is_none() {
+            // a. Set replaceValue to ? ToString(replaceValue).
+            Some(to_string(agent, replace_value, gc.reborrow())?.to_utf16(agent))
+        } else {
+            None
+        };
+        // 7. Let flags be ? ToString(? Get(rx, "flags")).
+        let flags = get(
+            agent,
+            rx.get(agent),
+            BUILTIN_STRING_MEMORY.flags.into(),
+            gc.reborrow(),
+        )?;
+        let flags = to_string(agent, flags, gc.reborrow())?;
+        let flags = flags.as_str(agent);
+        // 8. If flags contains "g", let global be true. Otherwise, let global be false.
+        let global = flags.contains('g');
+        // 9. If global is true, then
+        // a. If flags contains "u" or flags contains "v", let fullUnicode be true. Otherwise, let fullUnicode be false.
+        let full_unicode = flags.contains('u') || flags.contains('v');
+        if global {
+            // b. Perform ? Set(rx, "lastIndex", +0𝔽, true).
+            set(
+                agent,
+                rx.get(agent),
+                BUILTIN_STRING_MEMORY.lastIndex.into(),
+                Value::Integer(SmallInteger::zero()),
+                true,
+                gc.reborrow(),
+            )?;
+        }
+        // 10. Let results be a new empty List.
+        let mut results: Vec<Scoped<Object<'static>>> = vec![];
+        // 11. Let done be false.
+        // 12. Repeat, while done is false,
+        loop {
+            // a. Let result be ? RegExpExec(rx, S).
+            let result = reg_exp_exec(agent, rx.get(agent), s.get(agent), gc.reborrow())?;
+            // b. If result is null, set done to true.
+            let Some(result) = result else {
+                break;
+            };
+            // c. Else,
+            // i. Append result to results.
+            let result = result.unbind();
+            results.push(result.scope(agent, gc.nogc()));
+            // ii. If global is false, set done to true.
+            if !global {
+                break;
+            }
+            // iii. Else,
+            // 1. Let matchStr be ? ToString(? Get(result, "0")).
+            let match_str = get(agent, result, 0.into(), gc.reborrow())?;
+            let match_str = to_string(agent, match_str, gc.reborrow())?;
+            // 2. If matchStr is the empty String, then
+            if match_str.is_empty_string() {
+                // a. Let thisIndex be ℝ(? ToLength(? Get(rx, "lastIndex"))).
+                // b. Let nextIndex be AdvanceStringIndex(S, thisIndex, fullUnicode).
+                // c. Perform ? Set(rx, "lastIndex", 𝔽(nextIndex), true).
+                advance_last_index(
+                    agent,
+                    rx.get(agent),
+                    s.get(agent),
+                    full_unicode,
+                    gc.reborrow(),
+                )?;
+            }
+        }
+        let s_units = s.get(agent).to_utf16(agent);
+        // 4. Let lengthS be the length of S.
+        let length_s = s_units.len();
+        // 13. Let accumulatedResult be the empty String.
+        let mut accumulated_result: Vec<u16> = Vec::with_capacity(length_s);
+        // 14. Let nextSourcePosition be 0.
+        let mut next_source_position = 0;
+        // 15. For each element result of results, do
+        for result in results.iter() {
+            // a. Let resultLength be ? LengthOfArrayLike(result).
+            let result_length = length_of_array_like(agent, result.get(agent), gc.reborrow())?;
+            // b. Let nCaptures be max(resultLength - 1, 0).
+            let n_captures = (result_length - 1).max(0) as usize;
+            // c. Let matched be ? ToString(? Get(result, "0")).
+            let matched = get(agent, result.get(agent), 0.into(), gc.reborrow())?;
+            let matched = to_string(agent, matched, gc.reborrow())?.to_utf16(agent);
+            // d. Let matchLength be the length of matched.
+            let match_length = matched.len();
+            // e. Let position be ? ToIntegerOrInfinity(? Get(result, "index")).
+            let position = get(
+                agent,
+                result.get(agent),
+                BUILTIN_STRING_MEMORY.index.into(),
+                gc.reborrow(),
+            )?;
+            let position = to_integer_or_infinity(agent, position, gc.reborrow())?;
+            // f. Set position to the result of clamping position between 0 and lengthS.
+            let position = position.into_i64().clamp(0, length_s as i64) as usize;
+            // g. Let captures be a new empty List.
+            let mut captures: Vec<Option<Vec<u16>>> = Vec::with_capacity(n_captures);
+            // h. Let n be 1.
+            // i. Repeat, while n ≤ nCaptures,
+            for n in 1..=n_captures {
+                // i. Let capN be ? Get(result, ! ToString(𝔽(n))).
+                let cap_n = get(
+                    agent,
+                    result.get(agent),
+                    PropertyKey::try_from(n).unwrap(),
+                    gc.reborrow(),
+                )?;
+                // ii. If capN is not undefined, then
+                let cap_n = if cap_n.is_undefined() {
+                    None
+                } else {
+                    // 1. Set capN to ? ToString(capN).
+                    Some(to_string(agent, cap_n, gc.reborrow())?.to_utf16(agent))
+                };
+                // iii. Append capN to captures.
+                // iv. NOTE: When n = 1, the preceding step puts the first
+                //     element into captures (at index 0). More generally, the
+                //     nth capture (the first captured substring) is at index
+                //     n - 1.
+                captures.push(cap_n);
+                // v. Set n to n + 1.
+            }
+            // j. Let namedCaptures be ? Get(result, "groups").
+            let named_captures = get(
+                agent,
+                result.get(agent),
+                BUILTIN_STRING_MEMORY.groups.into(),
+                gc.reborrow(),
+            )?;
+            let replacement = if let Some(functional_replace) = &functional_replace {
+                // k. If functionalReplace is true, then
+                // i. Let replacerArgs be the list-concatenation of « matched », captures, and « 𝔽(position), S ».
+                let mut replacer_args: Vec<Value> = Vec::with_capacity(n_captures + 4);
+                replacer_args
+                    .push(String::from_utf16_lossy(agent, &matched, gc.nogc()).into_value());
+                for capture in captures.iter() {
+                    replacer_args.push(match capture {
+                        Some(capture) => {
+                            String::from_utf16_lossy(agent, capture, gc.nogc()).into_value()
+                        }
+                        None => Value::Undefined,
+                    });
+                }
+                replacer_args.push(Number::from(position as u32).into_value());
+                replacer_args.push(s.get(agent).into_value());
+                // ii. If namedCaptures is not undefined, then
+                if !named_captures.is_undefined() {
+                    // 1. Append namedCaptures to replacerArgs.
+                    replacer_args.push(named_captures);
+                }
+                // iii. Let replacementValue be ? Call(replaceValue, undefined, replacerArgs).
+                let replacement_value = call_function(
+                    agent,
+                    functional_replace.get(agent),
+                    Value::Undefined,
+                    Some(ArgumentsList(&replacer_args)),
+                    gc.reborrow(),
+                )?;
+                // iv. Let replacement be ? ToString(replacementValue).
+                to_string(agent, replacement_value, gc.reborrow())?.to_utf16(agent)
+            } else {
+                // l. Else,
+                // i. If namedCaptures is not undefined, then
+                let named_captures = if named_captures.is_undefined() {
+                    None
+                } else {
+                    // 1. Set namedCaptures to ? ToObject(namedCaptures).
+                    Some(
+                        to_object(agent, named_captures, gc.nogc())?
+                            .unbind()
+                            .scope(agent, gc.nogc()),
+                    )
+                };
+                // ii. Let replacement be ? GetSubstitution(matched, S, position, captures, namedCaptures, replaceValue).
+                get_substitution(
+                    agent,
+                    &matched,
+                    &s_units,
+                    position,
+                    &captures,
+                    named_captures,
+                    replace_template.as_ref().unwrap(),
+                    gc.reborrow(),
+                )?
+            };
+            // m. If position ≥ nextSourcePosition, then
+            if position >= next_source_position {
+                // i. NOTE: position should not normally move backwards. If it
+                //    does, it is an indication of an ill-behaving RegExp
+                //    subclass or use of an access triggered side-effect to
+                //    change the global flag or other characteristics of rx. In
+                //    such cases, the corresponding substitution is ignored.
+                // ii. Set accumulatedResult to the string-concatenation of accumulatedResult, the substring of S from nextSourcePosition to position, and replacement.
+                accumulated_result.extend_from_slice(&s_units[next_source_position..position]);
+                accumulated_result.extend_from_slice(&replacement);
+                // iii. Set nextSourcePosition to position + matchLength.
+                next_source_position = position + match_length;
+            }
+        }
+        // 16. If nextSourcePosition ≥ lengthS, return accumulatedResult.
+        // 17. Return the string-concatenation of accumulatedResult and the substring of S from nextSourcePosition.
+        if next_source_position < length_s {
+            accumulated_result.extend_from_slice(&s_units[next_source_position..]);
+        }
+        Ok(String::from_utf16_lossy(agent, &accumulated_result, gc.nogc()).into_value())
     }
 
+    /// ### [22.2.6.12 RegExp.prototype \[ %Symbol.search% \] ( string )](https://tc39.es/ecma262/#sec-regexp.prototype-%symbol.search%)
     fn search(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let rx be the this value.
+        // 2. If rx is not an Object, throw a TypeError exception.
+        let rx = this_object(agent, this_value, gc.nogc())?.scope(agent, gc.nogc());
+        // 3. Let S be ? ToString(string).
+        let s = to_string(agent, arguments.get(0), gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
+        // 4. Let previousLastIndex be ? Get(rx, "lastIndex").
+        let previous_last_index = get(
+            agent,
+            rx.get(agent),
+            BUILTIN_STRING_MEMORY.lastIndex.into(),
+            gc.reborrow(),
+        )?
+        .scope(agent, gc.nogc());
+        // 5. If SameValue(previousLastIndex, +0𝔽) is false, then
+        if !same_value(
+            agent,
+            previous_last_index.get(agent),
+            Value::Integer(SmallInteger::zero()),
+        ) {
+            // a. Perform ? Set(rx, "lastIndex", +0𝔽, true).
+            set(
+                agent,
+                rx.get(agent),
+                BUILTIN_STRING_MEMORY.lastIndex.into(),
+                Value::Integer(SmallInteger::zero()),
+                true,
+                gc.reborrow(),
+            )?;
+        }
+        // 6. Let result be ? RegExpExec(rx, S).
+        let result = reg_exp_exec(agent, rx.get(agent), s.get(agent), gc.reborrow())?
+            .map(|result| result.unbind());
+        let result = result.map(|result| result.scope(agent, gc.nogc()));
+        // 7. Let currentLastIndex be ? Get(rx, "lastIndex").
+        let current_last_index = get(
+            agent,
+            rx.get(agent),
+            BUILTIN_STRING_MEMORY.lastIndex.into(),
+            gc.reborrow(),
+        )?;
+        // 8. If SameValue(currentLastIndex, previousLastIndex) is false, then
+        if !same_value(agent, current_last_index, previous_last_index.get(agent)) {
+            // a. Perform ? Set(rx, "lastIndex", previousLastIndex, true).
+            set(
+                agent,
+                rx.get(agent),
+                BUILTIN_STRING_MEMORY.lastIndex.into(),
+                previous_last_index.get(agent),
+                true,
+                gc.reborrow(),
+            )?;
+        }
+        // 9. If result is null, return -1𝔽.
+        let Some(result) = result else {
+            return Ok((-1).into());
+        };
+        // 10. Return ? Get(result, "index").
+        get(
+            agent,
+            result.get(agent),
+            BUILTIN_STRING_MEMORY.index.into(),
+            gc,
+        )
     }
 
+    /// ### [22.2.6.13 get RegExp.prototype.source](https://tc39.es/ecma262/#sec-get-regexp.prototype.source)
     fn get_source(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let gc = gc.into_nogc();
+        // 1. Let R be the this value.
+        // 2. If R is not an Object, throw a TypeError exception.
+        // 3. If R does not have an [[OriginalSource]] internal slot, then
+        let Value::RegExp(r) = this_value else {
+            // a. If SameValue(R, %RegExp.prototype%) is true, return "(?:)".
+            if is_reg_exp_prototype(agent, this_value) {
+                return Ok(String::from_small_string("(?:)").into_value());
+            }
+            // b. Otherwise, throw a TypeError exception.
+            return Err(agent.throw_exception_with_static_message(
+                ExceptionType::TypeError,
+                "RegExp.prototype.source getter called on incompatible receiver",
+                gc,
+            ));
+        };
+        // 4. Assert: R has an [[OriginalFlags]] internal slot.
+        // 5. Let src be R.[[OriginalSource]].
+        // 6. Let flags be R.[[OriginalFlags]].
+        // 7. Return EscapeRegExpPattern(src, flags).
+        let src = escape_reg_exp_pattern(agent[r].original_source.as_str(agent));
+        Ok(String::from_string(agent, src, gc).into_value())
     }
 
+    /// ### [22.2.6.14 RegExp.prototype \[ %Symbol.split% \] ( string, limit )](https://tc39.es/ecma262/#sec-regexp.prototype-%symbol.split%)
+    ///
+    /// > #### Note
+    /// >
+    /// > The "lastIndex" and "global" properties of this RegExp object are
+    /// > ignored when performing the search. The "lastIndex" property is left
+    /// > unchanged.
     fn split(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let limit = arguments.get(1);
+        // 1. Let rx be the this value.
+        // 2. If rx is not an Object, throw a TypeError exception.
+        let rx = this_object(agent, this_value, gc.nogc())?.scope(agent, gc.nogc());
+        // 3. Let S be ? ToString(string).
+        let s = to_string(agent, arguments.get(0), gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
+        // 4. Let C be ? SpeciesConstructor(rx, %RegExp%).
+        // NOTE: We're ignoring species and subclasses.
+        let c = agent.current_realm().intrinsics().reg_exp();
+        // 5. Let flags be ? ToString(? Get(rx, "flags")).
+        let flags = get(
+            agent,
+            rx.get(agent),
+            BUILTIN_STRING_MEMORY.flags.into(),
+            gc.reborrow(),
+        )?;
+        let flags = to_string(agent, flags, gc.reborrow())?
+            .unbind()
+            .bind(gc.nogc());
+        let flags_str = flags.as_str(agent);
+        // 6. If flags contains "u" or flags contains "v", let unicodeMatching be true.
+        // 7. Else, let unicodeMatching be false.
+        let unicode_matching = flags_str.contains('u') || flags_str.contains('v');
+        // 8. If flags contains "y", let newFlags be flags.
+        // 9. Else, let newFlags be the string-concatenation of flags and "y".
+        let new_flags = if flags_str.contains('y') {
+            flags
+        } else {
+            let new_flags = format!("{flags_str}y");
+            String::from_string(agent, new_flags, gc.nogc())
+        };
+        // 10. Let splitter be ? Construct(C, « rx, newFlags »).
+        let splitter = construct(
+            agent,
+            c.into_function(),
+            Some(ArgumentsList(&[
+                rx.get(agent).into_value(),
+                new_flags.into_value(),
+            ])),
+            None,
+            gc.reborrow(),
+        )?
+        .unbind()
+        .scope(agent, gc.nogc());
+        // 11. Let A be ! ArrayCreate(0).
+        // 12. Let lengthA be 0.
+        let mut a: Vec<Scoped<Value>> = vec![];
+        // 13. If limit is undefined, let lim be 2**32 - 1; else let lim be ℝ(? ToUint32(limit)).
+        let lim = if limit.is_undefined() {
+            u32::MAX
+        } else {
+            to_uint32(agent, limit, gc.reborrow())?
+        } as usize;
+        // 14. If lim = 0, return A.
+        if lim == 0 {
+            return Ok(create_array_from_list(agent, &[], gc.nogc()).into_value());
+        }
+        // 15. If S is the empty String, then
+        if s.get(agent).is_empty_string() {
+            // a. Let z be ? RegExpExec(splitter, S).
+            let z = reg_exp_exec(agent, splitter.get(agent), s.get(agent), gc.reborrow())?;
+            // b. If z is not null, return A.
+            if z.is_some() {
+                return Ok(create_array_from_list(agent, &[], gc.nogc()).into_value());
+            }
+            // c. Perform ! CreateDataPropertyOrThrow(A, "0", S).
+            // d. Return A.
+            return Ok(
+                create_array_from_list(agent, &[s.get(agent).into_value()], gc.nogc()).into_value(),
+            );
+        }
+        let s_units = s.get(agent).to_utf16(agent);
+        // 16. Let size be the length of S.
+        let size = s_units.len();
+        // 17. Let p be 0.
+        let mut p = 0;
+        // 18. Let q be p.
+        let mut q = p;
+        // 19. Repeat, while q < size,
+        while q < size {
+            // a. Perform ? Set(splitter, "lastIndex", 𝔽(q), true).
+            set(
+                agent,
+                splitter.get(agent),
+                BUILTIN_STRING_MEMORY.lastIndex.into(),
+                Value::try_from(q as i64).unwrap(),
+                true,
+                gc.reborrow(),
+            )?;
+            // b. Let z be ? RegExpExec(splitter, S).
+            let z = reg_exp_exec(agent, splitter.get(agent), s.get(agent), gc.reborrow())?;
+            // c. If z is null, then
+            let Some(z) = z else {
+                // i. Set q to AdvanceStringIndex(S, q, unicodeMatching).
+                q = advance_string_index(&s_units, q, unicode_matching);
+                continue;
+            };
+            let z = z.unbind().scope(agent, gc.nogc());
+            // d. Else,
+            // i. Let e be ℝ(? ToLength(? Get(splitter, "lastIndex"))).
+            let e = get(
+                agent,
+                splitter.get(agent),
+                BUILTIN_STRING_MEMORY.lastIndex.into(),
+                gc.reborrow(),
+            )?;
+            let e = to_length(agent, e, gc.reborrow())? as usize;
+            // ii. Set e to min(e, size).
+            let e = e.min(size);
+            // iii. If e = p, then
+            if e == p {
+                // 1. Set q to AdvanceStringIndex(S, q, unicodeMatching).
+                q = advance_string_index(&s_units, q, unicode_matching);
+                continue;
+            }
+            // iv. Else,
+            // 1. Let T be the substring of S from p to q.
+            let t = String::from_utf16_lossy(agent, &s_units[p..q], gc.nogc());
+            // 2. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(lengthA)), T).
+            // 3. Set lengthA to lengthA + 1.
+            a.push(t.into_value().scope(agent, gc.nogc()));
+            // 4. If lengthA = lim, return A.
+            if a.len() == lim {
+                return Ok(create_array_from_scoped_list(agent, a, gc.nogc()).into_value());
+            }
+            // 5. Set p to e.
+            p = e;
+            // 6. Let numberOfCaptures be ? LengthOfArrayLike(z).
+            let number_of_captures = length_of_array_like(agent, z.get(agent), gc.reborrow())?;
+            // 7. Set numberOfCaptures to max(numberOfCaptures - 1, 0).
+            let number_of_captures = (number_of_captures - 1).max(0) as usize;
+            // 8. Let i be 1.
+            // 9. Repeat, while i ≤ numberOfCaptures,
+            for i in 1..=number_of_captures {
+                // a. Let nextCapture be ? Get(z, ! ToString(𝔽(i))).
+                let next_capture = get(
+                    agent,
+                    z.get(agent),
+                    PropertyKey::try_from(i).unwrap(),
+                    gc.reborrow(),
+                )?;
+                // b. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(lengthA)), nextCapture).
+                // c. Set i to i + 1.
+                // d. Set lengthA to lengthA + 1.
+                a.push(next_capture.scope(agent, gc.nogc()));
+                // e. If lengthA = lim, return A.
+                if a.len() == lim {
+                    return Ok(create_array_from_scoped_list(agent, a, gc.nogc()).into_value());
+                }
+            }
+            // 10. Set q to p.
+            q = p;
+        }
+        // 20. Let T be the substring of S from p to size.
+        let t = String::from_utf16_lossy(agent, &s_units[p..size], gc.nogc());
+        // 21. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(lengthA)), T).
+        a.push(t.into_value().scope(agent, gc.nogc()));
+        // 22. Return A.
+        Ok(create_array_from_scoped_list(agent, a, gc.nogc()).into_value())
     }
 
+    /// ### [22.2.6.15 get RegExp.prototype.sticky](https://tc39.es/ecma262/#sec-get-regexp.prototype.sticky)
     fn get_sticky(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. Let cu be the code unit 0x0079 (LATIN SMALL LETTER Y).
+        // 3. Return ? RegExpHasFlag(R, cu).
+        reg_exp_has_flag(agent, this_value, RegExpFlags::Y, gc.nogc())
     }
 
+    /// ### [22.2.6.16 RegExp.prototype.test ( S )](https://tc39.es/ecma262/#sec-regexp.prototype.test)
     fn test(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        arguments: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. If R is not an Object, throw a TypeError exception.
+        let r = this_object(agent, this_value, gc.nogc())?.scope(agent, gc.nogc());
+        // 3. Let string be ? ToString(S).
+        let string = to_string(agent, arguments.get(0), gc.reborrow())?.unbind();
+        // 4. Let match be ? RegExpExec(R, string).
+        let r#match = reg_exp_exec(agent, r.get(agent), string, gc)?;
+        // 5. If match is not null, return true; else return false.
+        Ok(r#match.is_some().into())
     }
 
     /// ### [22.2.6.17 RegExp.prototype.toString ( )](https://tc39.es/ecma262/#sec-regexp.prototype.tostring)
@@ -462,16 +1146,14 @@ impl RegExpPrototype {
             // Fast path for RegExp objects: This is not actually proper as it
             // does not take into account prototype mutations.
             let data = &agent[r];
-            let string_length = data.original_source.len(agent);
-            let flags_length = data.original_flags.iter().count();
+            let source = escape_reg_exp_pattern(data.original_source.as_str(agent));
+            let flags = reg_exp_flags_to_string(data.original_flags);
             let mut regexp_string =
-                std::string::String::with_capacity(1 + string_length + 1 + flags_length);
+                std::string::String::with_capacity(1 + source.len() + 1 + flags.len());
             regexp_string.push('/');
-            regexp_string.push_str(data.original_source.as_str(agent));
+            regexp_string.push_str(&source);
             regexp_string.push('/');
-            data.original_flags.iter_names().for_each(|(flag, _)| {
-                regexp_string.push_str(flag);
-            });
+            regexp_string.push_str(&flags);
             return Ok(String::from_string(agent, regexp_string, gc.nogc()).into_value());
         }
         // 3. Let pattern be ? ToString(? Get(R, "source")).
@@ -495,22 +1177,30 @@ impl RegExpPrototype {
         Ok(result.into_value())
     }
 
+    /// ### [22.2.6.18 get RegExp.prototype.unicode](https://tc39.es/ecma262/#sec-get-regexp.prototype.unicode)
     fn get_unicode(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. Let cu be the code unit 0x0075 (LATIN SMALL LETTER U).
+        // 3. Return ? RegExpHasFlag(R, cu).
+        reg_exp_has_flag(agent, this_value, RegExpFlags::U, gc.nogc())
     }
 
+    /// ### [22.2.6.19 get RegExp.prototype.unicodeSets](https://tc39.es/ecma262/#sec-get-regexp.prototype.unicodesets)
     fn get_unicode_sets(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let R be the this value.
+        // 2. Let cu be the code unit 0x0076 (LATIN SMALL LETTER V).
+        // 3. Return ? RegExpHasFlag(R, cu).
+        reg_exp_has_flag(agent, this_value, RegExpFlags::V, gc.nogc())
     }
 
     pub(crate) fn create_intrinsic(agent: &mut Agent, realm: RealmIdentifier) {
@@ -544,3 +1234,183 @@ impl RegExpPrototype {
             .build();
     }
 }
+
+/// ### [22.2.6.4.1 RegExpHasFlag ( R, codeUnit )](https://tc39.es/ecma262/#sec-regexphasflag)
+///
+/// The abstract operation RegExpHasFlag takes arguments R (an ECMAScript
+/// language value) and codeUnit (a code unit) and returns either a normal
+/// completion containing either a Boolean or undefined, or a throw
+/// completion.
+fn reg_exp_has_flag(
+    agent: &mut Agent,
+    r: Value,
+    flag: RegExpFlags,
+    gc: NoGcScope,
+) -> JsResult<Value> {
+    // 1. If R is not an Object, throw a TypeError exception.
+    // 2. If R does not have an [[OriginalFlags]] internal slot, then
+    let Value::RegExp(r) = r else {
+        // a. If SameValue(R, %RegExp.prototype%) is true, return undefined.
+        if is_reg_exp_prototype(agent, r) {
+            return Ok(Value::Undefined);
+        }
+        // b. Otherwise, throw a TypeError exception.
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::TypeError,
+            "RegExp flag getter called on incompatible receiver",
+            gc,
+        ));
+    };
+    // 3. Let flags be R.[[OriginalFlags]].
+    // 4. If flags contains codeUnit, return true.
+    // 5. Return false.
+    Ok(agent[r].original_flags.contains(flag).into())
+}
+
+/// Returns true if the value is the current Realm's %RegExp.prototype%.
+fn is_reg_exp_prototype(agent: &Agent, value: Value) -> bool {
+    let reg_exp_prototype = agent.current_realm().intrinsics().reg_exp_prototype();
+    matches!(value, Value::Object(value) if value == reg_exp_prototype)
+}
+
+/// Throws a TypeError if the this value of a RegExp.prototype method is not
+/// an Object.
+fn this_object<'a>(
+    agent: &mut Agent,
+    this_value: Value,
+    gc: NoGcScope<'a, '_>,
+) -> JsResult<Object<'a>> {
+    Object::try_from(this_value).map_err(|_| {
+        agent.throw_exception_with_static_message(
+            ExceptionType::TypeError,
+            "RegExp method called on incompatible receiver",
+            gc,
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::TestAgent;
+
+    #[test]
+    fn exec_returns_index_input_and_groups() {
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval("var m = /(?<y>\\d{4})-(\\d\\d)/.exec('on 2024-05!'); [m[0], m[1], m[2], m.index, m.input, m.groups.y].join()"),
+            "2024-05,2024,05,3,on 2024-05!,2024"
+        );
+        assert_eq!(agent.eval("String(/x/.exec('abc'))"), "null");
+        assert_eq!(
+            agent.eval("var m = /b(c)?/d.exec('abd'); m.indices.length + ':' + m.indices[0].join() + ':' + m.indices[1]"),
+            "2:1,2:undefined"
+        );
+    }
+
+    #[test]
+    fn global_and_sticky_use_last_index() {
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval("var r = /a/g; [r.test('aba'), r.lastIndex, r.test('aba'), r.lastIndex, r.test('aba'), r.lastIndex].join()"),
+            "true,1,true,3,false,0"
+        );
+        assert_eq!(
+            agent.eval("var r = /b/y; [r.test('ab'), r.lastIndex, (r.lastIndex = 1, r.test('ab')), r.lastIndex].join()"),
+            "false,0,true,2"
+        );
+    }
+
+    #[test]
+    fn flag_getters_and_source() {
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval("var r = /a\\/b/gimsuy; [r.global, r.ignoreCase, r.multiline, r.dotAll, r.unicode, r.sticky, r.hasIndices, r.unicodeSets, r.flags, r.source].join()"),
+            "true,true,true,true,true,true,false,false,gimsuy,a\\/b"
+        );
+        assert_eq!(
+            agent.eval("[String(RegExp.prototype.global), RegExp.prototype.source, RegExp.prototype.flags, String(RegExp.prototype)].join()"),
+            "undefined,(?:),,/(?:)/"
+        );
+        assert_eq!(agent.eval("String(new RegExp(''))"), "/(?:)/");
+        assert_eq!(
+            agent.eval_err(
+                "Object.getOwnPropertyDescriptor(RegExp.prototype, 'global').get.call({})"
+            ),
+            "TypeError: RegExp flag getter called on incompatible receiver"
+        );
+    }
+
+    #[test]
+    fn match_search_and_match_all() {
+        let mut agent = TestAgent::new();
+        assert_eq!(agent.eval("'a1b22c333'.match(/\\d+/g).join()"), "1,22,333");
+        assert_eq!(agent.eval("'a1b22'.match(/\\d+/)[0]"), "1");
+        assert_eq!(agent.eval("String('abc'.match(/\\d/g))"), "null");
+        assert_eq!(agent.eval("'abc'.match(/(?:)/g).length"), "4");
+        assert_eq!(agent.eval("'abcabc'.search(/c/)"), "2");
+        assert_eq!(agent.eval("'abc'.search(/x/)"), "-1");
+        assert_eq!(
+            agent.eval("var r = /c/g; r.lastIndex = 5; 'abcabc'.search(r) + ',' + r.lastIndex"),
+            "2,5"
+        );
+        assert_eq!(
+            agent.eval("[...'a1b2'.matchAll(/[a-z](\\d)/g)].map(m => m[1] + '@' + m.index).join()"),
+            "1@0,2@2"
+        );
+        assert_eq!(
+            agent.eval("var it = 'aa'.matchAll(/a/g); it.next(); it.next(); var r = it.next(); String(r.value) + ',' + r.done"),
+            "undefined,true"
+        );
+    }
+
+    #[test]
+    fn replace_with_patterns_and_functions() {
+        let mut agent = TestAgent::new();
+        assert_eq!(agent.eval("'aXbX'.replace(/X/, '-')"), "a-bX");
+        assert_eq!(agent.eval("'aXbX'.replace(/X/g, '-')"), "a-b-");
+        assert_eq!(
+            agent.eval("'john smith'.replace(/(\\w+)\\s(\\w+)/, '$2, $1 [$&] $$ $3')"),
+            "smith, john [john smith] $ $3"
+        );
+        assert_eq!(agent.eval("'abc'.replace(/b/, \"[$`|$']\")"), "a[a|c]c");
+        assert_eq!(
+            agent.eval("'2024-05'.replace(/(?<y>\\d+)-(?<m>\\d+)/, '$<m>/$<y>$<none>')"),
+            "05/2024"
+        );
+        assert_eq!(
+            agent.eval("'a1b2'.replace(/(\\d)/g, (m, d, i, s) => `[${m}${d}${i}${s}]`)"),
+            "a[111a1b2]b[223a1b2]"
+        );
+        assert_eq!(agent.eval("'abc'.replace(/(?:)/g, '-')"), "-a-b-c-");
+    }
+
+    #[test]
+    fn split_by_regexp() {
+        let mut agent = TestAgent::new();
+        assert_eq!(agent.eval("'a, b,c'.split(/,\\s*/).join('|')"), "a|b|c");
+        assert_eq!(agent.eval("'a1b2c'.split(/(\\d)/).join('|')"), "a|1|b|2|c");
+        assert_eq!(agent.eval("'abc'.split(/(?:)/, 2).join('|')"), "a|b");
+        assert_eq!(agent.eval("''.split(/x/).length"), "1");
+        assert_eq!(agent.eval("''.split(/(?:)/).length"), "0");
+    }
+
+    #[test]
+    fn constructor_and_invalid_patterns() {
+        let mut agent = TestAgent::new();
+        assert_eq!(agent.eval("String(new RegExp('a+', 'gi'))"), "/a+/gi");
+        assert_eq!(agent.eval("String(RegExp(/a/g, 'y'))"), "/a/y");
+        assert_eq!(agent.eval("var r = /a/; RegExp(r) === r"), "true");
+        assert_eq!(
+            agent.eval_err("new RegExp('(')"),
+            "SyntaxError: Invalid regular expression: /(/: Unbalanced parenthesis"
+        );
+        assert_eq!(
+            agent.eval_err("new RegExp('a', 'gg')"),
+            "SyntaxError: Invalid regular expression flags 'gg'"
+        );
+        assert_eq!(
+            agent.eval_err("'a'.match('(')"),
+            "SyntaxError: Invalid regular expression: /(/: Unbalanced parenthesis"
+        );
+    }
+}
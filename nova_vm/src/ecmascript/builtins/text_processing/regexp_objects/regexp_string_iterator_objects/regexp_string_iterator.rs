@@ -0,0 +1,246 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::ops::{Index, IndexMut};
+
+use crate::{
+    ecmascript::{
+        execution::{Agent, ProtoIntrinsics},
+        types::{
+            InternalMethods, InternalSlots, IntoObject, IntoValue, Object, OrdinaryObject, String,
+            Value,
+        },
+    },
+    engine::{context::NoGcScope, rootable::HeapRootData, Scoped},
+    heap::{
+        indexes::RegExpStringIteratorIndex, CompactionLists, CreateHeapData, Heap,
+        HeapMarkAndSweep, WorkQueues,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RegExpStringIterator<'a>(RegExpStringIteratorIndex<'a>);
+
+impl RegExpStringIterator<'_> {
+    /// Unbind this RegExpStringIterator from its current lifetime. This is necessary
+    /// to use the RegExpStringIterator as a parameter in a call that can perform
+    /// garbage collection.
+    pub fn unbind(self) -> RegExpStringIterator<'static> {
+        unsafe { std::mem::transmute::<Self, RegExpStringIterator<'static>>(self) }
+    }
+
+    // Bind this RegExpStringIterator to the garbage collection lifetime. This
+    // enables Rust's borrow checker to verify that your RegExpStringIterators cannot
+    // not be invalidated by garbage collection being performed.
+    //
+    // This function is best called with the form
+    // ```rs
+    // let regexp_string_iterator = regexp_string_iterator.bind(&gc);
+    // ```
+    // to make sure that the unbound RegExpStringIterator cannot be used after
+    // binding.
+    pub const fn bind<'gc>(self, _: NoGcScope<'gc, '_>) -> RegExpStringIterator<'gc> {
+        unsafe { std::mem::transmute::<Self, RegExpStringIterator<'gc>>(self) }
+    }
+
+    pub fn scope<'scope>(
+        self,
+        agent: &mut Agent,
+        gc: NoGcScope<'_, 'scope>,
+    ) -> Scoped<'scope, RegExpStringIterator<'static>> {
+        Scoped::new(agent, self.unbind(), gc)
+    }
+
+    /// # Do not use this
+    /// This is only for Value discriminant creation.
+    pub(crate) const fn _def() -> Self {
+        Self(RegExpStringIteratorIndex::from_u32_index(0))
+    }
+
+    pub(crate) fn get_index(self) -> usize {
+        self.0.into_index()
+    }
+}
+
+impl IntoValue for RegExpStringIterator<'_> {
+    fn into_value(self) -> Value {
+        self.into()
+    }
+}
+
+impl<'a> IntoObject<'a> for RegExpStringIterator<'a> {
+    fn into_object(self) -> Object<'a> {
+        self.into()
+    }
+}
+
+impl<'a> From<RegExpStringIterator<'a>> for Object<'a> {
+    fn from(value: RegExpStringIterator) -> Self {
+        Self::RegExpStringIterator(value.unbind())
+    }
+}
+
+impl From<RegExpStringIterator<'_>> for Value {
+    fn from(value: RegExpStringIterator) -> Self {
+        Self::RegExpStringIterator(value.unbind())
+    }
+}
+
+impl TryFrom<Value> for RegExpStringIterator<'_> {
+    type Error = ();
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::RegExpStringIterator(data) => Ok(data),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<'a> TryFrom<Object<'a>> for RegExpStringIterator<'a> {
+    type Error = ();
+
+    fn try_from(value: Object<'a>) -> Result<Self, Self::Error> {
+        match value {
+            Object::RegExpStringIterator(data) => Ok(data),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<'a> InternalSlots<'a> for RegExpStringIterator<'a> {
+    const DEFAULT_PROTOTYPE: ProtoIntrinsics = ProtoIntrinsics::RegExpStringIterator;
+
+    fn get_backing_object(self, agent: &Agent) -> Option<OrdinaryObject<'static>> {
+        agent[self].object_index
+    }
+
+    fn set_backing_object(self, agent: &mut Agent, backing_object: OrdinaryObject<'static>) {
+        assert!(agent[self]
+            .object_index
+            .replace(backing_object.unbind())
+            .is_none());
+    }
+}
+
+impl<'a> InternalMethods<'a> for RegExpStringIterator<'a> {}
+
+impl Index<RegExpStringIterator<'_>> for Agent {
+    type Output = RegExpStringIteratorHeapData;
+
+    fn index(&self, index: RegExpStringIterator) -> &Self::Output {
+        &self.heap.regexp_string_iterators[index]
+    }
+}
+
+impl IndexMut<RegExpStringIterator<'_>> for Agent {
+    fn index_mut(&mut self, index: RegExpStringIterator) -> &mut Self::Output {
+        &mut self.heap.regexp_string_iterators[index]
+    }
+}
+
+impl Index<RegExpStringIterator<'_>> for Vec<Option<RegExpStringIteratorHeapData>> {
+    type Output = RegExpStringIteratorHeapData;
+
+    fn index(&self, index: RegExpStringIterator) -> &Self::Output {
+        self.get(index.get_index())
+            .expect("RegExpStringIterator out of bounds")
+            .as_ref()
+            .expect("RegExpStringIterator slot empty")
+    }
+}
+
+impl IndexMut<RegExpStringIterator<'_>> for Vec<Option<RegExpStringIteratorHeapData>> {
+    fn index_mut(&mut self, index: RegExpStringIterator) -> &mut Self::Output {
+        self.get_mut(index.get_index())
+            .expect("RegExpStringIterator out of bounds")
+            .as_mut()
+            .expect("RegExpStringIterator slot empty")
+    }
+}
+
+impl TryFrom<HeapRootData> for RegExpStringIterator<'_> {
+    type Error = ();
+
+    #[inline]
+    fn try_from(value: HeapRootData) -> Result<Self, Self::Error> {
+        if let HeapRootData::RegExpStringIterator(value) = value {
+            Ok(value)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl CreateHeapData<RegExpStringIteratorHeapData, RegExpStringIterator<'static>> for Heap {
+    fn create(&mut self, data: RegExpStringIteratorHeapData) -> RegExpStringIterator<'static> {
+        self.regexp_string_iterators.push(Some(data));
+        RegExpStringIterator(RegExpStringIteratorIndex::last(
+            &self.regexp_string_iterators,
+        ))
+    }
+}
+
+impl HeapMarkAndSweep for RegExpStringIterator<'static> {
+    fn mark_values(&self, queues: &mut WorkQueues) {
+        queues.regexp_string_iterators.push(*self);
+    }
+
+    fn sweep_values(&mut self, compactions: &CompactionLists) {
+        compactions.regexp_string_iterators.shift_index(&mut self.0);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RegExpStringIteratorHeapData {
+    pub(crate) object_index: Option<OrdinaryObject<'static>>,
+    /// \[\[IteratingRegExp]]. This is set to None when the iterator is done.
+    pub(crate) iterating_reg_exp: Option<Object<'static>>,
+    /// \[\[IteratedString]]
+    pub(crate) iterated_string: String<'static>,
+    /// \[\[Global]]
+    pub(crate) global: bool,
+    /// \[\[Unicode]]
+    pub(crate) unicode: bool,
+}
+
+impl Default for RegExpStringIteratorHeapData {
+    fn default() -> Self {
+        Self {
+            object_index: None,
+            iterating_reg_exp: None,
+            iterated_string: String::EMPTY_STRING,
+            global: false,
+            unicode: false,
+        }
+    }
+}
+
+impl HeapMarkAndSweep for RegExpStringIteratorHeapData {
+    fn mark_values(&self, queues: &mut WorkQueues) {
+        let Self {
+            object_index,
+            iterating_reg_exp,
+            iterated_string,
+            global: _,
+            unicode: _,
+        } = self;
+        object_index.mark_values(queues);
+        iterating_reg_exp.mark_values(queues);
+        iterated_string.mark_values(queues);
+    }
+
+    fn sweep_values(&mut self, compactions: &CompactionLists) {
+        let Self {
+            object_index,
+            iterating_reg_exp,
+            iterated_string,
+            global: _,
+            unicode: _,
+        } = self;
+        object_index.sweep_values(compactions);
+        iterating_reg_exp.sweep_values(compactions);
+        iterated_string.sweep_values(compactions);
+    }
+}
@@ -0,0 +1,138 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::engine::context::GcScope;
+use crate::{
+    ecmascript::{
+        abstract_operations::{
+            operations_on_iterator_objects::create_iter_result_object, operations_on_objects::get,
+            type_conversion::to_string,
+        },
+        builders::ordinary_object_builder::OrdinaryObjectBuilder,
+        builtins::{
+            regexp::abstract_operations::{advance_last_index, reg_exp_exec},
+            ArgumentsList, Builtin,
+        },
+        execution::{agent::ExceptionType, Agent, JsResult, RealmIdentifier},
+        types::{String, Value, BUILTIN_STRING_MEMORY},
+    },
+    heap::WellKnownSymbolIndexes,
+};
+
+pub(crate) struct RegExpStringIteratorPrototype;
+
+struct RegExpStringIteratorPrototypeNext;
+impl Builtin for RegExpStringIteratorPrototypeNext {
+    const NAME: String<'static> = BUILTIN_STRING_MEMORY.next;
+
+    const LENGTH: u8 = 0;
+
+    const BEHAVIOUR: crate::ecmascript::builtins::Behaviour =
+        crate::ecmascript::builtins::Behaviour::Regular(RegExpStringIteratorPrototype::next);
+}
+
+impl RegExpStringIteratorPrototype {
+    /// ### [22.2.9.2.1 %RegExpStringIteratorPrototype%.next ( )](https://tc39.es/ecma262/#sec-%regexpstringiteratorprototype%.next)
+    fn next(
+        agent: &mut Agent,
+        this_value: Value,
+        _arguments: ArgumentsList,
+        mut gc: GcScope,
+    ) -> JsResult<Value> {
+        // 27.5.3.2 GeneratorValidate ( generator, generatorBrand )
+        // 3. If generator.[[GeneratorBrand]] is not generatorBrand, throw a TypeError exception.
+        let Value::RegExpStringIterator(iterator) = this_value else {
+            return Err(agent.throw_exception_with_static_message(
+                ExceptionType::TypeError,
+                "RegExpStringIterator expected",
+                gc.nogc(),
+            ));
+        };
+        let iterator = iterator.bind(gc.nogc());
+
+        // 22.2.9.1 CreateRegExpStringIterator ( R, S, global, fullUnicode )
+        // NOTE: We set [[IteratingRegExp]] to None when the generator in the
+        // spec text has returned.
+        let data = &agent[iterator];
+        let Some(r) = data.iterating_reg_exp else {
+            return Ok(
+                create_iter_result_object(agent, Value::Undefined, true, gc.nogc()).into_value(),
+            );
+        };
+        let s = data.iterated_string;
+        let global = data.global;
+        let full_unicode = data.unicode;
+        let iterator = iterator.scope(agent, gc.nogc());
+        let r = r.scope(agent, gc.nogc());
+        let s = s.scope(agent, gc.nogc());
+
+        // a. Repeat,
+        // i. Let match be ? RegExpExec(R, S).
+        let Some(r#match) = reg_exp_exec(agent, r.get(agent), s.get(agent), gc.reborrow())? else {
+            // ii. If match is null, then
+            // 1. Return undefined.
+            let iterator = iterator.get(agent);
+            agent[iterator].iterating_reg_exp = None;
+            return Ok(
+                create_iter_result_object(agent, Value::Undefined, true, gc.nogc()).into_value(),
+            );
+        };
+        let r#match = r#match.unbind().scope(agent, gc.nogc());
+        // iii. If global is false, then
+        if !global {
+            // 1. Perform ? GeneratorYield(CreateIteratorResultObject(match, false)).
+            // 2. Return undefined.
+            let iterator = iterator.get(agent);
+            agent[iterator].iterating_reg_exp = None;
+            return Ok(create_iter_result_object(
+                agent,
+                r#match.get(agent).into_value(),
+                false,
+                gc.nogc(),
+            )
+            .into_value());
+        }
+        // iv. Let matchStr be ? ToString(? Get(match, "0")).
+        let match_str = get(agent, r#match.get(agent), 0.into(), gc.reborrow())?;
+        let match_str = to_string(agent, match_str, gc.reborrow())?;
+        // v. If matchStr is the empty String, then
+        if match_str.is_empty_string() {
+            // 1. Let thisIndex be ℝ(? ToLength(? Get(R, "lastIndex"))).
+            // 2. Let nextIndex be AdvanceStringIndex(S, thisIndex, fullUnicode).
+            // 3. Perform ? Set(R, "lastIndex", 𝔽(nextIndex), true).
+            advance_last_index(
+                agent,
+                r.get(agent),
+                s.get(agent),
+                full_unicode,
+                gc.reborrow(),
+            )?;
+        }
+        // vi. Perform ? GeneratorYield(CreateIteratorResultObject(match, false)).
+        Ok(
+            create_iter_result_object(agent, r#match.get(agent).into_value(), false, gc.nogc())
+                .into_value(),
+        )
+    }
+
+    pub(crate) fn create_intrinsic(agent: &mut Agent, realm: RealmIdentifier) {
+        let intrinsics = agent.get_realm(realm).intrinsics();
+        let this = intrinsics.reg_exp_string_iterator_prototype();
+        let iterator_prototype = intrinsics.iterator_prototype();
+
+        OrdinaryObjectBuilder::new_intrinsic_object(agent, realm, this)
+            .with_property_capacity(2)
+            .with_prototype(iterator_prototype)
+            .with_builtin_function_property::<RegExpStringIteratorPrototypeNext>()
+            .with_property(|builder| {
+                builder
+                    .with_key(WellKnownSymbolIndexes::ToStringTag.into())
+                    .with_value_readonly(BUILTIN_STRING_MEMORY.RegExp_String_Iterator.into_value())
+                    .with_enumerable(false)
+                    .with_configurable(true)
+                    .build()
+            })
+            .build();
+    }
+}
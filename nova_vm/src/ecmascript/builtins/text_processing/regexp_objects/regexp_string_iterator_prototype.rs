@@ -1,57 +0,0 @@
-// This Source Code Form is subject to the terms of the Mozilla Public
-// License, v. 2.0. If a copy of the MPL was not distributed with this
-// file, You can obtain one at https://mozilla.org/MPL/2.0/.
-
-use crate::engine::context::GcScope;
-use crate::{
-    ecmascript::{
-        builders::ordinary_object_builder::OrdinaryObjectBuilder,
-        builtins::{ArgumentsList, Builtin},
-        execution::{Agent, JsResult, RealmIdentifier},
-        types::{String, Value, BUILTIN_STRING_MEMORY},
-    },
-    heap::WellKnownSymbolIndexes,
-};
-
-pub(crate) struct RegExpStringIteratorPrototype;
-
-struct RegExpStringIteratorPrototypeNext;
-impl Builtin for RegExpStringIteratorPrototypeNext {
-    const NAME: String<'static> = BUILTIN_STRING_MEMORY.next;
-
-    const LENGTH: u8 = 0;
-
-    const BEHAVIOUR: crate::ecmascript::builtins::Behaviour =
-        crate::ecmascript::builtins::Behaviour::Regular(RegExpStringIteratorPrototype::next);
-}
-
-impl RegExpStringIteratorPrototype {
-    fn next(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _arguments: ArgumentsList,
-        _gc: GcScope,
-    ) -> JsResult<Value> {
-        todo!();
-    }
-
-    pub(crate) fn create_intrinsic(agent: &mut Agent, realm: RealmIdentifier) {
-        let intrinsics = agent.get_realm(realm).intrinsics();
-        let this = intrinsics.reg_exp_string_iterator_prototype();
-        let iterator_prototype = intrinsics.iterator_prototype();
-
-        OrdinaryObjectBuilder::new_intrinsic_object(agent, realm, this)
-            .with_property_capacity(2)
-            .with_prototype(iterator_prototype)
-            .with_builtin_function_property::<RegExpStringIteratorPrototypeNext>()
-            .with_property(|builder| {
-                builder
-                    .with_key(WellKnownSymbolIndexes::ToStringTag.into())
-                    .with_value_readonly(BUILTIN_STRING_MEMORY.RegExp_String_Iterator.into_value())
-                    .with_enumerable(false)
-                    .with_configurable(true)
-                    .build()
-            })
-            .build();
-    }
-}
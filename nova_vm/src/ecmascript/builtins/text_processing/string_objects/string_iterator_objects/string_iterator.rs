@@ -0,0 +1,230 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::ops::{Index, IndexMut};
+
+use crate::{
+    ecmascript::{
+        execution::{Agent, ProtoIntrinsics},
+        types::{
+            InternalMethods, InternalSlots, IntoObject, IntoValue, Object, OrdinaryObject, String,
+            Value,
+        },
+    },
+    engine::{context::NoGcScope, rootable::HeapRootData, Scoped},
+    heap::{
+        indexes::StringIteratorIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep,
+        WorkQueues,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StringIterator<'a>(StringIteratorIndex<'a>);
+
+impl StringIterator<'_> {
+    /// Unbind this StringIterator from its current lifetime. This is necessary
+    /// to use the StringIterator as a parameter in a call that can perform
+    /// garbage collection.
+    pub fn unbind(self) -> StringIterator<'static> {
+        unsafe { std::mem::transmute::<Self, StringIterator<'static>>(self) }
+    }
+
+    // Bind this StringIterator to the garbage collection lifetime. This
+    // enables Rust's borrow checker to verify that your StringIterators cannot
+    // not be invalidated by garbage collection being performed.
+    //
+    // This function is best called with the form
+    // ```rs
+    // let string_iterator = string_iterator.bind(&gc);
+    // ```
+    // to make sure that the unbound StringIterator cannot be used after
+    // binding.
+    pub const fn bind<'gc>(self, _: NoGcScope<'gc, '_>) -> StringIterator<'gc> {
+        unsafe { std::mem::transmute::<Self, StringIterator<'gc>>(self) }
+    }
+
+    pub fn scope<'scope>(
+        self,
+        agent: &mut Agent,
+        gc: NoGcScope<'_, 'scope>,
+    ) -> Scoped<'scope, StringIterator<'static>> {
+        Scoped::new(agent, self.unbind(), gc)
+    }
+
+    /// # Do not use this
+    /// This is only for Value discriminant creation.
+    pub(crate) const fn _def() -> Self {
+        Self(StringIteratorIndex::from_u32_index(0))
+    }
+
+    pub(crate) fn get_index(self) -> usize {
+        self.0.into_index()
+    }
+
+    pub(crate) fn from_string(agent: &mut Agent, string: String) -> Self {
+        agent.heap.create(StringIteratorHeapData {
+            object_index: None,
+            s: Some(string.unbind()),
+            position: 0,
+        })
+    }
+}
+
+impl IntoValue for StringIterator<'_> {
+    fn into_value(self) -> Value {
+        self.into()
+    }
+}
+
+impl<'a> IntoObject<'a> for StringIterator<'a> {
+    fn into_object(self) -> Object<'a> {
+        self.into()
+    }
+}
+
+impl<'a> From<StringIterator<'a>> for Object<'a> {
+    fn from(value: StringIterator) -> Self {
+        Self::StringIterator(value.unbind())
+    }
+}
+
+impl From<StringIterator<'_>> for Value {
+    fn from(value: StringIterator) -> Self {
+        Self::StringIterator(value.unbind())
+    }
+}
+
+impl TryFrom<Value> for StringIterator<'_> {
+    type Error = ();
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::StringIterator(data) => Ok(data),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<'a> TryFrom<Object<'a>> for StringIterator<'a> {
+    type Error = ();
+
+    fn try_from(value: Object<'a>) -> Result<Self, Self::Error> {
+        match value {
+            Object::StringIterator(data) => Ok(data),
+            _ => Err(()),
+        }
+    }
+}
+
+impl<'a> InternalSlots<'a> for StringIterator<'a> {
+    const DEFAULT_PROTOTYPE: ProtoIntrinsics = ProtoIntrinsics::StringIterator;
+
+    fn get_backing_object(self, agent: &Agent) -> Option<OrdinaryObject<'static>> {
+        agent[self].object_index
+    }
+
+    fn set_backing_object(self, agent: &mut Agent, backing_object: OrdinaryObject<'static>) {
+        assert!(agent[self]
+            .object_index
+            .replace(backing_object.unbind())
+            .is_none());
+    }
+}
+
+impl<'a> InternalMethods<'a> for StringIterator<'a> {}
+
+impl Index<StringIterator<'_>> for Agent {
+    type Output = StringIteratorHeapData;
+
+    fn index(&self, index: StringIterator) -> &Self::Output {
+        &self.heap.string_iterators[index]
+    }
+}
+
+impl IndexMut<StringIterator<'_>> for Agent {
+    fn index_mut(&mut self, index: StringIterator) -> &mut Self::Output {
+        &mut self.heap.string_iterators[index]
+    }
+}
+
+impl Index<StringIterator<'_>> for Vec<Option<StringIteratorHeapData>> {
+    type Output = StringIteratorHeapData;
+
+    fn index(&self, index: StringIterator) -> &Self::Output {
+        self.get(index.get_index())
+            .expect("StringIterator out of bounds")
+            .as_ref()
+            .expect("StringIterator slot empty")
+    }
+}
+
+impl IndexMut<StringIterator<'_>> for Vec<Option<StringIteratorHeapData>> {
+    fn index_mut(&mut self, index: StringIterator) -> &mut Self::Output {
+        self.get_mut(index.get_index())
+            .expect("StringIterator out of bounds")
+            .as_mut()
+            .expect("StringIterator slot empty")
+    }
+}
+
+impl TryFrom<HeapRootData> for StringIterator<'_> {
+    type Error = ();
+
+    #[inline]
+    fn try_from(value: HeapRootData) -> Result<Self, Self::Error> {
+        if let HeapRootData::StringIterator(value) = value {
+            Ok(value)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl CreateHeapData<StringIteratorHeapData, StringIterator<'static>> for Heap {
+    fn create(&mut self, data: StringIteratorHeapData) -> StringIterator<'static> {
+        self.string_iterators.push(Some(data));
+        StringIterator(StringIteratorIndex::last(&self.string_iterators))
+    }
+}
+
+impl HeapMarkAndSweep for StringIterator<'static> {
+    fn mark_values(&self, queues: &mut WorkQueues) {
+        queues.string_iterators.push(*self);
+    }
+
+    fn sweep_values(&mut self, compactions: &CompactionLists) {
+        compactions.string_iterators.shift_index(&mut self.0);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StringIteratorHeapData {
+    pub(crate) object_index: Option<OrdinaryObject<'static>>,
+    /// The iterated String. This is set to None when the iterator is done.
+    pub(crate) s: Option<String<'static>>,
+    /// The WTF-8 byte index of the next code point to yield.
+    pub(crate) position: usize,
+}
+
+impl HeapMarkAndSweep for StringIteratorHeapData {
+    fn mark_values(&self, queues: &mut WorkQueues) {
+        let Self {
+            object_index,
+            s,
+            position: _,
+        } = self;
+        object_index.mark_values(queues);
+        s.mark_values(queues);
+    }
+
+    fn sweep_values(&mut self, compactions: &CompactionLists) {
+        let Self {
+            object_index,
+            s,
+            position: _,
+        } = self;
+        object_index.sweep_values(compactions);
+        s.sweep_values(compactions);
+    }
+}
@@ -0,0 +1,133 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use small_string::SmallString;
+use wtf8::Wtf8Buf;
+
+use crate::engine::context::GcScope;
+use crate::{
+    ecmascript::{
+        abstract_operations::operations_on_iterator_objects::create_iter_result_object,
+        builders::ordinary_object_builder::OrdinaryObjectBuilder,
+        builtins::{ArgumentsList, Builtin},
+        execution::{agent::ExceptionType, Agent, JsResult, RealmIdentifier},
+        types::{String, Value, BUILTIN_STRING_MEMORY},
+    },
+    heap::WellKnownSymbolIndexes,
+};
+
+pub(crate) struct StringIteratorPrototype;
+
+struct StringIteratorPrototypeNext;
+impl Builtin for StringIteratorPrototypeNext {
+    const NAME: String<'static> = BUILTIN_STRING_MEMORY.next;
+
+    const LENGTH: u8 = 0;
+
+    const BEHAVIOUR: crate::ecmascript::builtins::Behaviour =
+        crate::ecmascript::builtins::Behaviour::Regular(StringIteratorPrototype::next);
+}
+
+impl StringIteratorPrototype {
+    /// ### [22.1.5.1.1 %StringIteratorPrototype%.next ( )](https://tc39.es/ecma262/#sec-%stringiteratorprototype%.next)
+    fn next(
+        agent: &mut Agent,
+        this_value: Value,
+        _arguments: ArgumentsList,
+        gc: GcScope,
+    ) -> JsResult<Value> {
+        let gc = gc.into_nogc();
+        // 27.5.3.2 GeneratorValidate ( generator, generatorBrand )
+        // 3. If generator.[[GeneratorBrand]] is not generatorBrand, throw a TypeError exception.
+        let Value::StringIterator(iterator) = this_value else {
+            return Err(agent.throw_exception_with_static_message(
+                ExceptionType::TypeError,
+                "StringIterator expected",
+                gc,
+            ));
+        };
+        let iterator = iterator.bind(gc);
+
+        // 22.1.3.36 String.prototype [ %Symbol.iterator% ] ( )
+        // NOTE: We set `s` to None when the generator in the spec text has returned.
+        let Some(s) = agent[iterator].s else {
+            return Ok(create_iter_result_object(agent, Value::Undefined, true, gc).into_value());
+        };
+        let position = agent[iterator].position;
+
+        // a. Let len be the length of s.
+        // c. Repeat, while position < len,
+        // i. Let cp be CodePointAt(s, position).
+        let Some(cp) = s.as_wtf8(agent).slice_from(position).code_points().next() else {
+            // d. Return undefined.
+            agent[iterator].s = None;
+            return Ok(create_iter_result_object(agent, Value::Undefined, true, gc).into_value());
+        };
+        // ii. Let nextIndex be position + cp.[[CodeUnitCount]].
+        // iii. Let resultString be the substring of s from position to nextIndex.
+        // iv. Set position to nextIndex.
+        let (result_string, byte_len) = match cp.to_char() {
+            Some(ch) => (
+                String::from(SmallString::from_code_point(ch)),
+                ch.len_utf8(),
+            ),
+            None => {
+                // A lone surrogate is a code point of its own, 3 bytes long
+                // in WTF-8.
+                let mut buf = Wtf8Buf::with_capacity(3);
+                buf.push(cp);
+                (String::from_wtf8_buf(agent, buf, gc), 3)
+            }
+        };
+        agent[iterator].position = position + byte_len;
+        // v. Perform ? GeneratorYield(CreateIteratorResultObject(resultString, false)).
+        Ok(create_iter_result_object(agent, result_string.into_value(), false, gc).into_value())
+    }
+
+    pub(crate) fn create_intrinsic(agent: &mut Agent, realm: RealmIdentifier) {
+        let intrinsics = agent.get_realm(realm).intrinsics();
+        let this = intrinsics.string_iterator_prototype();
+        let iterator_prototype = intrinsics.iterator_prototype();
+
+        OrdinaryObjectBuilder::new_intrinsic_object(agent, realm, this)
+            .with_property_capacity(2)
+            .with_prototype(iterator_prototype)
+            .with_builtin_function_property::<StringIteratorPrototypeNext>()
+            .with_property(|builder| {
+                builder
+                    .with_key(WellKnownSymbolIndexes::ToStringTag.into())
+                    .with_value_readonly(BUILTIN_STRING_MEMORY.String_Iterator.into_value())
+                    .with_enumerable(false)
+                    .with_configurable(true)
+                    .build()
+            })
+            .build();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::TestAgent;
+
+    #[test]
+    fn next_yields_code_points() {
+        let mut agent = TestAgent::new();
+        assert_eq!(agent.eval("[...'a😀b'].join('|')"), "a|😀|b");
+        // Lone surrogates are yielded as strings of one code unit.
+        agent.eval("var lead = unescape('%uD800'); var trail = unescape('%uDC00');");
+        assert_eq!(
+            agent.eval("[...(lead + 'a' + trail)].map(s => s.length + ':' + escape(s)).join()"),
+            "1:%uD800,1:a,1:%uDC00"
+        );
+        assert_eq!(
+            agent.eval("var parts = [...(trail + lead)]; parts[0] === trail && parts[1] === lead"),
+            "true"
+        );
+        // A surrogate pair split across strings is joined again.
+        assert_eq!(
+            agent.eval("[...(unescape('%uD83D') + unescape('%uDE00'))].length"),
+            "1"
+        );
+    }
+}
@@ -4,23 +4,28 @@
 
 use std::{cmp::max, collections::VecDeque, iter::repeat, str::FromStr};
 
+use oxc_ast::ast::RegExpFlags;
 use small_string::SmallString;
 use unicode_normalization::{
     is_nfc_quick, is_nfd_quick, is_nfkc_quick, is_nfkd_quick, IsNormalized, UnicodeNormalization,
 };
 
+#[cfg(feature = "regexp")]
+use crate::ecmascript::abstract_operations::operations_on_objects::invoke;
 use crate::ecmascript::abstract_operations::testing_and_comparison::is_reg_exp;
 use crate::ecmascript::abstract_operations::type_conversion::{
     to_integer_or_infinity_number, to_string_primitive, try_to_integer_or_infinity, try_to_string,
 };
-use crate::ecmascript::types::Primitive;
+#[cfg(feature = "regexp")]
+use crate::ecmascript::builtins::regexp::abstract_operations::reg_exp_create_and_validate;
+use crate::ecmascript::types::{Object, Primitive};
 use crate::engine::context::{GcScope, NoGcScope};
-use crate::engine::TryResult;
+use crate::engine::{Scoped, TryResult};
 use crate::SmallInteger;
 use crate::{
     ecmascript::{
         abstract_operations::{
-            operations_on_objects::{call_function, create_array_from_list, get_method},
+            operations_on_objects::{call_function, create_array_from_list, get, get_method},
             testing_and_comparison::{is_callable, require_object_coercible},
             type_conversion::{
                 is_trimmable_whitespace, to_integer_or_infinity, to_length, to_number, to_string,
@@ -30,6 +35,7 @@ use crate::{
         builders::ordinary_object_builder::OrdinaryObjectBuilder,
         builtins::{
             primitive_objects::{PrimitiveObjectData, PrimitiveObjectHeapData},
+            text_processing::string_objects::string_iterator_objects::string_iterator::StringIterator,
             ArgumentsList, Array, Behaviour, Builtin, BuiltinIntrinsic,
         },
         execution::{agent::ExceptionType, Agent, JsResult, RealmIdentifier},
@@ -945,31 +951,150 @@ impl StringPrototype {
         }
     }
 
+    /// ### [22.1.3.12 String.prototype.localeCompare ( that \[ , reserved1 \[ , reserved2 \] \] )](https://tc39.es/ecma262/#sec-string.prototype.localecompare)
+    ///
+    /// An ECMAScript implementation that does not include the ECMA-402 API
+    /// implements this method as an implementation-defined comparison. Nova
+    /// compares the canonically equivalent (NFC) forms of the two Strings by
+    /// code point, so that canonically equivalent Strings compare as equal.
     fn locale_compare(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        args: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let that = args.get(0);
+
+        // 1. Let O be ? RequireObjectCoercible(this value).
+        let o = require_object_coercible(agent, this_value, gc.nogc())?;
+
+        // 2. Let S be ? ToString(O).
+        let mut s = if let TryResult::Continue(s) = try_to_string(agent, o, gc.nogc()) {
+            s?
+        } else {
+            to_string(agent, o, gc.reborrow())?.unbind().bind(gc.nogc())
+        };
+
+        // 3. Let thatValue be ? ToString(that).
+        let that_value =
+            if let TryResult::Continue(that_value) = try_to_string(agent, that, gc.nogc()) {
+                that_value?
+            } else {
+                let s_root = s.scope(agent, gc.nogc());
+                let that_value = to_string(agent, that, gc.reborrow())?
+                    .unbind()
+                    .bind(gc.nogc());
+                s = s_root.get(agent).bind(gc.nogc());
+                that_value
+            };
+
+        let result = s.as_str(agent).nfc().cmp(that_value.as_str(agent).nfc());
+        Ok(Number::from(result as i8).into_value())
     }
 
+    /// ### [22.1.3.13 String.prototype.match ( regexp )](https://tc39.es/ecma262/#sec-string.prototype.match)
     fn r#match(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        args: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let regexp = args.get(0);
+
+        // 1. Let O be ? RequireObjectCoercible(this value).
+        let o = require_object_coercible(agent, this_value, gc.nogc())?;
+
+        // 2. If regexp is neither undefined nor null, then
+        if !regexp.is_undefined() && !regexp.is_null() {
+            // a. Let matcher be ? GetMethod(regexp, %Symbol.match%).
+            let symbol = WellKnownSymbolIndexes::Match.into();
+            let matcher = get_method(agent, regexp, symbol, gc.reborrow())?;
+
+            // b. If matcher is not undefined, then
+            if let Some(matcher) = matcher {
+                // i. Return ? Call(matcher, regexp, « O »).
+                return call_function(
+                    agent,
+                    matcher.unbind(),
+                    regexp,
+                    Some(ArgumentsList(&[o])),
+                    gc.reborrow(),
+                );
+            }
+        }
+
+        // 3. Let S be ? ToString(O).
+        let s = to_string(agent, o, gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
+
+        // 4. Let rx be ? RegExpCreate(regexp, undefined).
+        // 5. Return ? Invoke(rx, %Symbol.match%, « S »).
+        reg_exp_create_and_invoke(
+            agent,
+            regexp,
+            None,
+            WellKnownSymbolIndexes::Match,
+            s,
+            gc.reborrow(),
+        )
     }
 
+    /// ### [22.1.3.14 String.prototype.matchAll ( regexp )](https://tc39.es/ecma262/#sec-string.prototype.matchall)
     fn match_all(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        args: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let regexp = args.get(0);
+
+        // 1. Let O be ? RequireObjectCoercible(this value).
+        let o = require_object_coercible(agent, this_value, gc.nogc())?;
+
+        // 2. If regexp is neither undefined nor null, then
+        if !regexp.is_undefined() && !regexp.is_null() {
+            // a. Let isRegExp be ? IsRegExp(regexp).
+            // b. If isRegExp is true, then
+            if is_reg_exp(agent, regexp, gc.reborrow())? {
+                // i. Let flags be ? Get(regexp, "flags").
+                // ii. Perform ? RequireObjectCoercible(flags).
+                // iii. If ? ToString(flags) does not contain "g", throw a TypeError exception.
+                require_global_flag(agent, regexp, gc.reborrow())?;
+            }
+
+            // c. Let matcher be ? GetMethod(regexp, %Symbol.matchAll%).
+            let symbol = WellKnownSymbolIndexes::MatchAll.into();
+            let matcher = get_method(agent, regexp, symbol, gc.reborrow())?;
+
+            // d. If matcher is not undefined, then
+            if let Some(matcher) = matcher {
+                // i. Return ? Call(matcher, regexp, « O »).
+                return call_function(
+                    agent,
+                    matcher.unbind(),
+                    regexp,
+                    Some(ArgumentsList(&[o])),
+                    gc.reborrow(),
+                );
+            }
+        }
+
+        // 3. Let S be ? ToString(O).
+        let s = to_string(agent, o, gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
+
+        // 4. Let rx be ? RegExpCreate(regexp, "g").
+        // 5. Return ? Invoke(rx, %Symbol.matchAll%, « S »).
+        reg_exp_create_and_invoke(
+            agent,
+            regexp,
+            Some(RegExpFlags::G),
+            WellKnownSymbolIndexes::MatchAll,
+            s,
+            gc.reborrow(),
+        )
     }
 
     /// ### [22.1.3.15 String.prototype.normalize ( \[ form \] )](https://tc39.es/ecma262/#sec-string.prototype.normalize)
@@ -1111,6 +1236,7 @@ impl StringPrototype {
             return Ok(s.into_value());
         }
 
+        agent.check_heap_limit(s.len(agent).saturating_mul(n as usize))?;
         // 6. Return the String value that is made from n copies of S appended together.
         Ok(Value::from_string(
             agent,
@@ -1150,77 +1276,84 @@ impl StringPrototype {
             }
         }
 
-        // 3. Let s be ? ToString(O).
-        let mut s = to_string(agent, o, gc.reborrow())?.unbind().bind(gc.nogc());
-        let s_root = s.scope(agent, gc.nogc());
+        // 3. Let string be ? ToString(O).
+        let s = to_string(agent, o, gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
 
         // 4. Let searchString be ? ToString(searchValue).
         let search_string = to_string(agent, search_value, gc.reborrow())?
             .unbind()
-            .bind(gc.nogc());
-
-        s = s_root.get(agent).bind(gc.nogc());
+            .scope(agent, gc.nogc());
 
         // 5. Let functionalReplace be IsCallable(replaceValue).
-        if let Some(functional_replace) = is_callable(replace_value, gc.nogc()) {
-            // 7. Let searchLength be the length of searchString.
-            let search_length = search_string.len(agent);
-
-            // 8. Let position be StringIndexOf(s, searchString, 0).
-            let position = if let Some(position) = s.as_str(agent).find(search_string.as_str(agent))
-            {
-                position
-            } else {
-                // 9. If position is not-found, return s.
-                return Ok(s.into_value());
-            };
-
-            // Let replacement be ? ToString(? Call(replaceValue, undefined, « searchString, 𝔽(position), string »)).
-            let args = &[
-                search_string.unbind().into_value(),
-                Number::from(position as u32).into_value(),
-                s.into_value().unbind(),
-            ];
-            let result = call_function(
-                agent,
-                functional_replace.unbind(),
-                Value::Undefined,
-                Some(ArgumentsList(args)),
-                gc.reborrow(),
-            )?;
-
-            let result = to_string(agent, result, gc.reborrow())?
-                .unbind()
-                .bind(gc.nogc());
+        let functional_replace =
+            is_callable(replace_value, gc.nogc()).map(|f| f.unbind().scope(agent, gc.nogc()));
 
-            s = s_root.get(agent).bind(gc.nogc());
+        // 6. If functionalReplace is false, then
+        let replace_template = if functional_replace.is_none() {
+            // a. Set replaceValue to ? ToString(replaceValue).
+            Some(to_string(agent, replace_value, gc.reborrow())?.to_utf16(agent))
+        } else {
+            None
+        };
 
-            // 10. Let preceding be the substring of s from 0 to position.
-            // 11. Let following be the substring of s from position + searchLength.
-            // 12. If functionalReplace is true,
-            let preceding = &s.as_str(agent)[0..position].to_owned();
-            let following = &s.as_str(agent)[position + search_length..].to_owned();
+        let string = s.get(agent).to_utf16(agent);
+        let search = search_string.get(agent).to_utf16(agent);
 
-            // 14. Return the string-concatenation of preceding, replacement, and following.
-            let concatenated_result = format!("{}{}{}", preceding, result.as_str(agent), following);
-            return Ok(String::from_string(agent, concatenated_result, gc.nogc()).into_value());
-        }
+        // 7. Let searchLength be the length of searchString.
+        let search_length = search.len();
 
-        let search_string_root = search_string.scope(agent, gc.nogc());
+        // 8. Let position be StringIndexOf(string, searchString, 0).
+        // 9. If position is not-found, return string.
+        let Some(position) = string_index_of(&string, &search, 0) else {
+            return Ok(s.get(agent).into_value());
+        };
 
-        // 6. If functionalReplace is false, Set replaceValue to ? ToString(replaceValue).
-        let replace_string = to_string(agent, replace_value, gc.reborrow())?
-            .unbind()
-            .bind(gc.nogc());
-        s = s_root.get(agent).bind(gc.nogc());
+        // 10. Let preceding be the substring of string from 0 to position.
+        let preceding = &string[..position];
+        // 11. Let following be the substring of string from position + searchLength.
+        let following = &string[position + search_length..];
 
-        let search_string = search_string_root.get(agent).bind(gc.nogc());
+        let replacement = if let Some(functional_replace) = functional_replace {
+            // 12. If functionalReplace is true, then
+            // a. Let replacement be ? ToString(? Call(replaceValue, undefined, « searchString, 𝔽(position), string »)).
+            let result = call_function(
+                agent,
+                functional_replace.get(agent),
+                Value::Undefined,
+                Some(ArgumentsList(&[
+                    search_string.get(agent).into_value(),
+                    Number::from(position as u32).into_value(),
+                    s.get(agent).into_value(),
+                ])),
+                gc.reborrow(),
+            )?;
+            to_string(agent, result, gc.reborrow())?.to_utf16(agent)
+        } else {
+            // 13. Else,
+            // a. Assert: replaceValue is a String.
+            // b. Let captures be a new empty List.
+            // c. Let replacement be ! GetSubstitution(searchString, string, position, captures, undefined, replaceValue).
+            get_substitution(
+                agent,
+                &search,
+                &string,
+                position,
+                &[],
+                None,
+                &replace_template.unwrap(),
+                gc.reborrow(),
+            )
+            .unwrap()
+        };
 
-        // Everything are strings: `"foo".replace("o", "a")` => use rust's replace
-        let result =
-            s.as_str(agent)
-                .replacen(search_string.as_str(agent), replace_string.as_str(agent), 1);
-        Ok(String::from_string(agent, result, gc.nogc()).into_value())
+        // 14. Return the string-concatenation of preceding, replacement, and following.
+        let mut result = Vec::with_capacity(preceding.len() + replacement.len() + following.len());
+        result.extend_from_slice(preceding);
+        result.extend_from_slice(&replacement);
+        result.extend_from_slice(following);
+        Ok(String::from_utf16_lossy(agent, &result, gc.nogc()).into_value())
     }
 
     /// ### [22.1.3.20 String.prototype.replaceAll ( searchValue, replaceValue )](https://tc39.es/ecma262/multipage/text-processing.html#sec-string.prototype.replaceall)
@@ -1238,15 +1371,13 @@ impl StringPrototype {
 
         // 2. If searchValue is neither undefined nor null, then
         if !search_value.is_null() && !search_value.is_undefined() {
-            // a. Let isRegExp be ? IsRegExp(searchValue).
-            let is_reg_exp = false;
-
+            // a. Let isRegExp be ? IsRegExp(searchValue).
             // b. If isRegExp is true, then
-            if is_reg_exp {
+            if is_reg_exp(agent, search_value, gc.reborrow())? {
                 // i. Let flags be ? Get(searchValue, "flags").
                 // ii. Perform ? RequireObjectCoercible(flags).
                 // iii. If ? ToString(flags) does not contain "g", throw a TypeError exception.
-                todo!();
+                require_global_flag(agent, search_value, gc.reborrow())?;
             }
 
             // c. Let replacer be ? GetMethod(searchValue, %Symbol.replace%).
@@ -1265,110 +1396,161 @@ impl StringPrototype {
             }
         }
 
-        // 3. Let s be ? ToString(O).
-        let mut s = to_string(agent, o, gc.reborrow())?.unbind().bind(gc.nogc());
-        let s_root = s.scope(agent, gc.nogc());
+        // 3. Let string be ? ToString(O).
+        let s = to_string(agent, o, gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
 
         // 4. Let searchString be ? ToString(searchValue).
-        let mut search_string = to_string(agent, search_value, gc.reborrow())?
+        let search_string = to_string(agent, search_value, gc.reborrow())?
             .unbind()
-            .bind(gc.nogc());
-        let search_string_root = search_string.scope(agent, gc.nogc());
-
-        s = s_root.get(agent).bind(gc.nogc());
+            .scope(agent, gc.nogc());
 
         // 5. Let functionalReplace be IsCallable(replaceValue).
-        if let Some(functional_replace) = is_callable(replace_value, gc.nogc()) {
-            // 7. Let searchLength be the length of searchString.
-            let search_length = search_string.len(agent);
+        let functional_replace =
+            is_callable(replace_value, gc.nogc()).map(|f| f.unbind().scope(agent, gc.nogc()));
 
-            // 8. Let advanceBy be max(1, searchLength).
-            let advance_by = max(1, search_length);
+        // 6. If functionalReplace is false, then
+        let replace_template = if functional_replace.is_none() {
+            // a. Set replaceValue to ? ToString(replaceValue).
+            Some(to_string(agent, replace_value, gc.reborrow())?.to_utf16(agent))
+        } else {
+            None
+        };
 
-            // 9. Let matchPositions be a new empty List.
-            let mut match_positions: Vec<usize> = vec![];
+        let string = s.get(agent).to_utf16(agent);
+        let search = search_string.get(agent).to_utf16(agent);
 
-            // 10. Let position be StringIndexOf(s, searchString, 0).
-            let search_str = search_string.as_str(agent);
-            let subject = s.as_str(agent).to_owned();
-            let mut position = 0;
+        // 7. Let searchLength be the length of searchString.
+        let search_length = search.len();
 
-            // 11. Repeat, while position is not not-found,
-            while let Some(pos) = subject[position..].find(search_str) {
-                match_positions.push(position + pos);
-                position += advance_by + pos;
-            }
+        // 8. Let advanceBy be max(1, searchLength).
+        let advance_by = max(1, search_length);
 
-            // If none has found, return s.
-            if match_positions.is_empty() {
-                return Ok(s.into_value());
-            }
+        // 9. Let matchPositions be a new empty List.
+        let mut match_positions: Vec<usize> = vec![];
+
+        // 10. Let position be StringIndexOf(string, searchString, 0).
+        let mut position = string_index_of(&string, &search, 0);
 
-            // 12. Let endOfLastMatch be 0.
-            let mut end_of_last_match = 0;
+        // 11. Repeat, while position is not not-found,
+        while let Some(p) = position {
+            // a. Append position to matchPositions.
+            match_positions.push(p);
+            // b. Set position to StringIndexOf(string, searchString, position + advanceBy).
+            position = string_index_of(&string, &search, p + advance_by);
+        }
+
+        // NOTE: If nothing was found, string is returned unchanged.
+        if match_positions.is_empty() {
+            return Ok(s.get(agent).into_value());
+        }
 
-            // 13. Let result be the empty String.
-            let mut result = std::string::String::with_capacity(subject.len());
+        // 12. Let endOfLastMatch be 0.
+        let mut end_of_last_match = 0;
 
-            // 14. For each element p of matchPositions, do
-            let functional_replace = functional_replace.scope(agent, gc.nogc());
-            for p in match_positions {
-                search_string = search_string_root.get(agent);
-                s = s_root.get(agent);
-                // b. let replacement be ? ToString(? Call(replaceValue, undefined, « searchString, 𝔽(p), string »)).
+        // 13. Let result be the empty String.
+        let mut result: Vec<u16> = Vec::with_capacity(string.len());
+
+        // 14. For each element p of matchPositions, do
+        for p in match_positions {
+            // a. Let preserved be the substring of string from endOfLastMatch to p.
+            let preserved = &string[end_of_last_match..p];
+            let replacement = if let Some(functional_replace) = &functional_replace {
+                // b. If functionalReplace is true, then
+                // i. Let replacement be ? ToString(? Call(replaceValue, undefined, « searchString, 𝔽(p), string »)).
                 let replacement = call_function(
                     agent,
                     functional_replace.get(agent),
                     Value::Undefined,
                     Some(ArgumentsList(&[
-                        search_string.into_value(),
-                        Number::from(position as u32).into_value(),
-                        s.into_value(),
+                        search_string.get(agent).into_value(),
+                        Number::from(p as u32).into_value(),
+                        s.get(agent).into_value(),
                     ])),
                     gc.reborrow(),
                 )?;
+                to_string(agent, replacement, gc.reborrow())?.to_utf16(agent)
+            } else {
+                // c. Else,
+                // i. Assert: replaceValue is a String.
+                // ii. Let captures be a new empty List.
+                // iii. Let replacement be ! GetSubstitution(searchString, string, p, captures, undefined, replaceValue).
+                get_substitution(
+                    agent,
+                    &search,
+                    &string,
+                    p,
+                    &[],
+                    None,
+                    replace_template.as_ref().unwrap(),
+                    gc.reborrow(),
+                )
+                .unwrap()
+            };
+            // d. Set result to the string-concatenation of result, preserved, and replacement.
+            result.extend_from_slice(preserved);
+            result.extend_from_slice(&replacement);
+            // e. Set endOfLastMatch to p + searchLength.
+            end_of_last_match = p + search_length;
+        }
 
-                // a. Let preserved be the substring of string from endOfLastMatch to p.
-                let preserved = &subject[end_of_last_match..p];
-                // d. Set result to the string-concatenation of result, preserved, and replacement.
-                let replacement_str = replacement.to_string(agent, gc.reborrow())?;
-                let replacement_str = replacement_str.as_str(agent);
-                result.reserve(preserved.len() + replacement_str.len());
-                result.push_str(preserved);
-                result.push_str(replacement_str);
-                end_of_last_match = p + search_length;
-            }
-
-            // 15. If endOfLastMatch < the length of string, set result to the string-concatenation of result and the substring of string from endOfLastMatch.
-            if end_of_last_match < subject.len() {
-                let preserved = &subject[end_of_last_match..];
-                result.push_str(preserved);
-            }
-
-            // 16. Return result.
-            return Ok(String::from_string(agent, result, gc.nogc()).into_value());
+        // 15. If endOfLastMatch < the length of string, then
+        if end_of_last_match < string.len() {
+            // a. Set result to the string-concatenation of result and the substring of string from endOfLastMatch.
+            result.extend_from_slice(&string[end_of_last_match..]);
         }
 
-        // 6. If functionalReplace is false, Set replaceValue to ? ToString(replaceValue).
-        let replace_string = to_string(agent, replace_value, gc.reborrow())?
-            .unbind()
-            .bind(gc.nogc());
-        // Everything are strings: `"foo".replaceAll("o", "a")` => use rust's replace
-        search_string = search_string_root.get(agent).bind(gc.nogc());
-        s = s_root.get(agent).bind(gc.nogc());
-        let result = s
-            .as_str(agent)
-            .replace(search_string.as_str(agent), replace_string.as_str(agent));
-        Ok(String::from_string(agent, result, gc.nogc()).into_value())
+        // 16. Return result.
+        Ok(String::from_utf16_lossy(agent, &result, gc.nogc()).into_value())
     }
 
+    /// ### [22.1.3.21 String.prototype.search ( regexp )](https://tc39.es/ecma262/#sec-string.prototype.search)
     fn search(
-        _agent: &mut Agent,
-        _this_value: Value,
-        _: ArgumentsList,
-        _gc: GcScope,
+        agent: &mut Agent,
+        this_value: Value,
+        args: ArgumentsList,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        let regexp = args.get(0);
+
+        // 1. Let O be ? RequireObjectCoercible(this value).
+        let o = require_object_coercible(agent, this_value, gc.nogc())?;
+
+        // 2. If regexp is neither undefined nor null, then
+        if !regexp.is_undefined() && !regexp.is_null() {
+            // a. Let searcher be ? GetMethod(regexp, %Symbol.search%).
+            let symbol = WellKnownSymbolIndexes::Search.into();
+            let searcher = get_method(agent, regexp, symbol, gc.reborrow())?;
+
+            // b. If searcher is not undefined, then
+            if let Some(searcher) = searcher {
+                // i. Return ? Call(searcher, regexp, « O »).
+                return call_function(
+                    agent,
+                    searcher.unbind(),
+                    regexp,
+                    Some(ArgumentsList(&[o])),
+                    gc.reborrow(),
+                );
+            }
+        }
+
+        // 3. Let string be ? ToString(O).
+        let string = to_string(agent, o, gc.reborrow())?
+            .unbind()
+            .scope(agent, gc.nogc());
+
+        // 4. Let rx be ? RegExpCreate(regexp, undefined).
+        // 5. Return ? Invoke(rx, %Symbol.search%, « string »).
+        reg_exp_create_and_invoke(
+            agent,
+            regexp,
+            None,
+            WellKnownSymbolIndexes::Search,
+            string,
+            gc.reborrow(),
+        )
     }
 
     fn slice(
@@ -1469,11 +1651,14 @@ impl StringPrototype {
         // 2. If separator is neither undefined nor null, then
         let separator = args.get(0);
 
-        if matches!(separator, Value::Undefined | Value::Null) {
+        if !matches!(separator, Value::Undefined | Value::Null) {
+            // a. Let splitter be ? GetMethod(separator, %Symbol.split%).
             let symbol = WellKnownSymbolIndexes::Split.into();
+            let splitter = get_method(agent, separator, symbol, gc.reborrow())?;
 
-            // If splitter is not undefined, then return ? Call(splitter, separator, « O, limit »).
-            if let Ok(Some(splitter)) = get_method(agent, separator, symbol, gc.reborrow()) {
+            // b. If splitter is not undefined, then
+            if let Some(splitter) = splitter {
+                // i. Return ? Call(splitter, separator, « O, limit »).
                 return call_function(
                     agent,
                     splitter.unbind(),
@@ -1904,13 +2089,25 @@ impl StringPrototype {
         this_string_value(agent, this_value, gc.nogc()).map(|string| string.into_value())
     }
 
+    /// ### [22.1.3.36 String.prototype \[ %Symbol.iterator% \] ( )](https://tc39.es/ecma262/#sec-string.prototype-%symbol.iterator%)
     fn iterator(
-        _agent: &mut Agent,
-        _this_value: Value,
+        agent: &mut Agent,
+        this_value: Value,
         _: ArgumentsList,
-        _gc: GcScope,
+        mut gc: GcScope,
     ) -> JsResult<Value> {
-        todo!()
+        // 1. Let O be ? RequireObjectCoercible(this value).
+        let o = require_object_coercible(agent, this_value, gc.nogc())?;
+        // 2. Let s be ? ToString(O).
+        let s = if let TryResult::Continue(s) = try_to_string(agent, o, gc.nogc()) {
+            s?
+        } else {
+            to_string(agent, o, gc.reborrow())?.unbind().bind(gc.nogc())
+        };
+        // 3. Let closure be a new Abstract Closure with no parameters that
+        //    captures s and performs the following steps when called:
+        // 4. Return CreateIteratorFromClosure(closure, "%StringIteratorPrototype%", %StringIteratorPrototype%).
+        Ok(StringIterator::from_string(agent, s).into_value())
     }
 
     /// ### [B.2.2.1 String.prototype.substr ( start, length )](https://tc39.es/ecma262/#sec-string.prototype.substr)
@@ -2233,6 +2430,287 @@ impl StringPrototype {
     }
 }
 
+/// Performs the steps shared by String.prototype.replaceAll and
+/// String.prototype.matchAll when their argument is a RegExp:
+///
+/// 1. Let flags be ? Get(regexp, "flags").
+/// 2. Perform ? RequireObjectCoercible(flags).
+/// 3. If ? ToString(flags) does not contain "g", throw a TypeError exception.
+fn require_global_flag(agent: &mut Agent, regexp: Value, mut gc: GcScope) -> JsResult<()> {
+    // NOTE: IsRegExp returned true, so regexp must be an object.
+    let regexp = Object::try_from(regexp).unwrap();
+    let flags = get(
+        agent,
+        regexp,
+        BUILTIN_STRING_MEMORY.flags.into(),
+        gc.reborrow(),
+    )?;
+    let flags = require_object_coercible(agent, flags, gc.nogc())?;
+    let flags = to_string(agent, flags, gc.reborrow())?
+        .unbind()
+        .bind(gc.nogc());
+    if !flags.as_str(agent).contains('g') {
+        return Err(agent.throw_exception_with_static_message(
+            ExceptionType::TypeError,
+            "RegExp must have the global flag",
+            gc.nogc(),
+        ));
+    }
+    Ok(())
+}
+
+/// ### [6.1.4.1 StringIndexOf ( string, searchValue, fromIndex )](https://tc39.es/ecma262/#sec-stringindexof)
+///
+/// The string and search value are given as UTF-16 code units, and the
+/// returned index is a code unit index.
+fn string_index_of(string: &[u16], search_value: &[u16], from_index: usize) -> Option<usize> {
+    // 1. Let len be the length of string.
+    let len = string.len();
+    // 2. If searchValue is the empty String and fromIndex ≤ len, return fromIndex.
+    if search_value.is_empty() {
+        return if from_index <= len {
+            Some(from_index)
+        } else {
+            None
+        };
+    }
+    // 3. Let searchLen be the length of searchValue.
+    // 4. For each integer i such that fromIndex ≤ i ≤ len - searchLen, in ascending order, do
+    // a. Let candidate be the substring of string from i to i + searchLen.
+    // b. If candidate is searchValue, return i.
+    // 5. Return not-found.
+    string
+        .get(from_index..)?
+        .windows(search_value.len())
+        .position(|candidate| candidate == search_value)
+        .map(|i| i + from_index)
+}
+
+/// ### [22.1.3.19.1 GetSubstitution ( matched, str, position, captures, namedCaptures, replacementTemplate )](https://tc39.es/ecma262/#sec-getsubstitution)
+///
+/// All strings are given as UTF-16 code units. A `None` capture corresponds
+/// to an undefined capture in the specification.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn get_substitution(
+    agent: &mut Agent,
+    matched: &[u16],
+    str: &[u16],
+    position: usize,
+    captures: &[Option<Vec<u16>>],
+    named_captures: Option<Scoped<'_, Object<'static>>>,
+    replacement_template: &[u16],
+    mut gc: GcScope,
+) -> JsResult<Vec<u16>> {
+    const DOLLAR: u16 = b'$' as u16;
+    const BACKTICK: u16 = b'`' as u16;
+    const AMPERSAND: u16 = b'&' as u16;
+    const APOSTROPHE: u16 = b'\'' as u16;
+    const LESS_THAN: u16 = b'<' as u16;
+    const GREATER_THAN: u16 = b'>' as u16;
+    const ZERO: u16 = b'0' as u16;
+    const NINE: u16 = b'9' as u16;
+
+    // 1. Let stringLength be the length of str.
+    let string_length = str.len();
+    // 2. Assert: position ≤ stringLength.
+    debug_assert!(position <= string_length);
+    // 3. Let result be the empty String.
+    let mut result = Vec::with_capacity(replacement_template.len());
+    // 4. Let templateRemainder be replacementTemplate.
+    let mut template_remainder = replacement_template;
+    // 5. Repeat, while templateRemainder is not the empty String,
+    while !template_remainder.is_empty() {
+        // a. NOTE: The following steps isolate ref (a prefix of
+        //    templateRemainder), determine refReplacement (its replacement),
+        //    and then append that replacement to result.
+        let ref_length = match template_remainder {
+            // b. If templateRemainder starts with "$$", then
+            [DOLLAR, DOLLAR, ..] => {
+                // i. Let ref be "$$".
+                // ii. Let refReplacement be "$".
+                result.push(DOLLAR);
+                2
+            }
+            // c. Else if templateRemainder starts with "$`", then
+            [DOLLAR, BACKTICK, ..] => {
+                // i. Let ref be "$`".
+                // ii. Let refReplacement be the substring of str from 0 to position.
+                result.extend_from_slice(&str[..position]);
+                2
+            }
+            // d. Else if templateRemainder starts with "$&", then
+            [DOLLAR, AMPERSAND, ..] => {
+                // i. Let ref be "$&".
+                // ii. Let refReplacement be matched.
+                result.extend_from_slice(matched);
+                2
+            }
+            // e. Else if templateRemainder starts with "$'" (0x0024 (DOLLAR SIGN) followed by 0x0027 (APOSTROPHE)), then
+            [DOLLAR, APOSTROPHE, ..] => {
+                // i. Let ref be "$'".
+                // ii. Let matchLength be the length of matched.
+                // iii. Let tailPos be position + matchLength.
+                let tail_pos = position + matched.len();
+                // iv. Let refReplacement be the substring of str from min(tailPos, stringLength).
+                // v. NOTE: tailPos can exceed stringLength only if this abstract
+                //    operation was invoked by a call to the intrinsic @@replace
+                //    method of %RegExp.prototype% on an object whose "exec"
+                //    property is not the intrinsic %RegExp.prototype.exec%.
+                result.extend_from_slice(&str[tail_pos.min(string_length)..]);
+                2
+            }
+            // f. Else if templateRemainder starts with "$" followed by 1 or more decimal digits, then
+            [DOLLAR, first @ ZERO..=NINE, rest @ ..] => {
+                let first = (first - ZERO) as usize;
+                // i. If templateRemainder starts with "$" followed by 2 or more decimal digits, let digitCount be 2. Otherwise, let digitCount be 1.
+                // ii. Let digits be the substring of templateRemainder from 1 to 1 + digitCount.
+                // iii. Let index be ℝ(StringToNumber(digits)).
+                // iv. Assert: 0 ≤ index ≤ 99.
+                // v. Let captureLen be the number of elements in captures.
+                let capture_len = captures.len();
+                let (digit_count, index) = match rest {
+                    [second @ ZERO..=NINE, ..]
+                        // vi. If index > captureLen and digitCount = 2, then
+                        //     1. NOTE: When a two-digit replacement pattern
+                        //        specifies an index exceeding the count of
+                        //        capturing groups, it is reinterpreted as a
+                        //        one-digit replacement pattern followed by a
+                        //        literal digit.
+                        //     2. Set digitCount to 1.
+                        //     3. Set digits to the substring of digits from 0 to 1.
+                        //     4. Set index to ℝ(StringToNumber(digits)).
+                        if first * 10 + ((second - ZERO) as usize) <= capture_len =>
+                    {
+                        (2, first * 10 + ((second - ZERO) as usize))
+                    }
+                    _ => (1, first),
+                };
+                // vii. Let ref be the substring of templateRemainder from 0 to 1 + digitCount.
+                let ref_length = 1 + digit_count;
+                // viii. If 1 ≤ index ≤ captureLen, then
+                if (1..=capture_len).contains(&index) {
+                    // 1. Let capture be captures[index - 1].
+                    // 2. If capture is undefined, then
+                    //    a. Let refReplacement be the empty String.
+                    // 3. Else,
+                    //    a. Let refReplacement be capture.
+                    if let Some(capture) = &captures[index - 1] {
+                        result.extend_from_slice(capture);
+                    }
+                } else {
+                    // ix. Else,
+                    // 1. Let refReplacement be ref.
+                    result.extend_from_slice(&template_remainder[..ref_length]);
+                }
+                ref_length
+            }
+            // g. Else if templateRemainder starts with "$<", then
+            [DOLLAR, LESS_THAN, ..] => {
+                // i. Let gtPos be StringIndexOf(templateRemainder, ">", 0).
+                let gt_pos = string_index_of(template_remainder, &[GREATER_THAN], 0);
+                match (gt_pos, &named_captures) {
+                    // ii. If gtPos is not-found or namedCaptures is undefined, then
+                    (None, _) | (_, None) => {
+                        // 1. Let ref be "$<".
+                        // 2. Let refReplacement be ref.
+                        result.extend_from_slice(&[DOLLAR, LESS_THAN]);
+                        2
+                    }
+                    // iii. Else,
+                    (Some(gt_pos), Some(named_captures)) => {
+                        // 1. Let ref be the substring of templateRemainder from 0 to gtPos + 1.
+                        // 2. Let groupName be the substring of templateRemainder from 2 to gtPos.
+                        let group_name = String::from_utf16_lossy(
+                            agent,
+                            &template_remainder[2..gt_pos],
+                            gc.nogc(),
+                        );
+                        // 3. Assert: namedCaptures is an Object.
+                        // 4. Let capture be ? Get(namedCaptures, groupName).
+                        let capture = get(
+                            agent,
+                            named_captures.get(agent),
+                            PropertyKey::from(group_name.unbind()),
+                            gc.reborrow(),
+                        )?;
+                        // 5. If capture is undefined, then
+                        //    a. Let refReplacement be the empty String.
+                        // 6. Else,
+                        //    a. Let refReplacement be ? ToString(capture).
+                        if !capture.is_undefined() {
+                            let capture = to_string(agent, capture, gc.reborrow())?;
+                            result.extend(capture.as_wtf8(agent).to_ill_formed_utf16());
+                        }
+                        gt_pos + 1
+                    }
+                }
+            }
+            // h. Else,
+            _ => {
+                // i. Let ref be the substring of templateRemainder from 0 to 1.
+                // ii. Let refReplacement be ref.
+                result.push(template_remainder[0]);
+                1
+            }
+        };
+        // i. Let refLength be the length of ref.
+        // j. Set templateRemainder to the substring of templateRemainder from refLength.
+        template_remainder = &template_remainder[ref_length..];
+        // k. Set result to the string-concatenation of result and refReplacement.
+    }
+    // 6. Return result.
+    Ok(result)
+}
+
+/// Performs the final steps shared by String.prototype.match,
+/// String.prototype.matchAll and String.prototype.search:
+///
+/// 1. Let rx be ? RegExpCreate(regexp, flags).
+/// 2. Return ? Invoke(rx, method, « s »).
+#[cfg(feature = "regexp")]
+fn reg_exp_create_and_invoke(
+    agent: &mut Agent,
+    regexp: Value,
+    flags: Option<RegExpFlags>,
+    method: WellKnownSymbolIndexes,
+    s: Scoped<'_, String<'static>>,
+    mut gc: GcScope,
+) -> JsResult<Value> {
+    // NOTE: RegExpInitialize performs ToString(pattern) as its first
+    // observable step, so we may perform it before RegExpAlloc.
+    let p = if regexp.is_undefined() {
+        String::EMPTY_STRING
+    } else {
+        to_string(agent, regexp, gc.reborrow())?.unbind()
+    };
+    let rx = reg_exp_create_and_validate(agent, p, flags, gc.nogc())?
+        .unbind()
+        .into_value();
+    invoke(
+        agent,
+        rx,
+        method.into(),
+        Some(ArgumentsList(&[s.get(agent).into_value()])),
+        gc,
+    )
+}
+
+#[cfg(not(feature = "regexp"))]
+fn reg_exp_create_and_invoke(
+    agent: &mut Agent,
+    _regexp: Value,
+    _flags: Option<RegExpFlags>,
+    _method: WellKnownSymbolIndexes,
+    _s: Scoped<'_, String<'static>>,
+    gc: GcScope,
+) -> JsResult<Value> {
+    Err(agent.throw_exception_with_static_message(
+        ExceptionType::TypeError,
+        "RegExp is not supported",
+        gc.nogc(),
+    ))
+}
+
 /// ### [22.1.3.17.1 StringPaddingBuiltinsImpl ( O, maxLength, fillString, placement )](https://tc39.es/ecma262/#sec-stringpaddingbuiltinsimpl)
 ///
 /// The abstract operation StringPaddingBuiltinsImpl takes arguments O (an
@@ -2523,3 +3001,54 @@ fn unicode_normalize(s: &str, f: NormalizeForm) -> Option<std::string::String> {
         },
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::TestAgent;
+
+    #[test]
+    fn replace_uses_get_substitution() {
+        let mut agent = TestAgent::new();
+        assert_eq!(agent.eval("'abcb'.replace('b', '[$&$$]')"), "a[b$]cb");
+        assert_eq!(agent.eval("'abc'.replace('b', \"[$`|$']\")"), "a[a|c]c");
+        assert_eq!(agent.eval("'abc'.replace('b', '$1$<x>')"), "a$1$<x>c");
+        assert_eq!(
+            agent.eval("'abc'.replace('b', (m, i, s) => m + i + s)"),
+            "ab1abcc"
+        );
+        // Positions are UTF-16 code unit indices.
+        assert_eq!(agent.eval("'😀b'.replace('b', (m, i) => i)"), "😀2");
+    }
+
+    #[test]
+    fn replace_all_uses_get_substitution() {
+        let mut agent = TestAgent::new();
+        assert_eq!(agent.eval("'abab'.replaceAll('b', '($&)')"), "a(b)a(b)");
+        assert_eq!(agent.eval("'ab'.replaceAll('', '-')"), "-a-b-");
+        assert_eq!(
+            agent.eval("'😀x😀x'.replaceAll('x', (m, i) => i)"),
+            "😀2😀5"
+        );
+        assert_eq!(agent.eval("'a.b.c'.replaceAll(/\\./g, '$$')"), "a$b$c");
+        assert_eq!(
+            agent.eval_err("'abc'.replaceAll(/b/, '')"),
+            "TypeError: RegExp must have the global flag"
+        );
+    }
+
+    #[test]
+    fn split_calls_separator_splitter() {
+        let mut agent = TestAgent::new();
+        assert_eq!(agent.eval("'a,b,,c'.split(',').join('|')"), "a|b||c");
+        assert_eq!(
+            agent.eval("'abc'.split({ [Symbol.split](s, lim) { return s + lim; } }, 2)"),
+            "abc2"
+        );
+        assert_eq!(
+            agent.eval_err(
+                "'abc'.split({ get [Symbol.split]() { throw new TypeError('splitter'); } })"
+            ),
+            "TypeError: splitter"
+        );
+    }
+}
@@ -6,6 +6,7 @@ pub mod agent;
 mod default_host_hooks;
 mod environments;
 mod execution_context;
+mod interrupts;
 mod realm;
 
 pub use agent::{Agent, JsResult};
@@ -18,6 +19,8 @@ pub(crate) use environments::{
     PrivateEnvironmentIndex, ThisBindingStatus,
 };
 pub(crate) use execution_context::*;
+pub use interrupts::InterruptHandle;
+pub(crate) use interrupts::InterruptState;
 #[cfg(test)]
 pub(crate) use realm::{create_realm, set_realm_global_object};
 pub(crate) use realm::{
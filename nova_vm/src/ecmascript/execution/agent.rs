@@ -10,51 +10,148 @@
 use ahash::AHashMap;
 
 use super::{
-    environments::{get_identifier_reference, try_get_identifier_reference}, initialize_default_realm, initialize_host_defined_realm, EnvironmentIndex, ExecutionContext, Realm, RealmIdentifier
+    environments::{get_identifier_reference, try_get_identifier_reference}, initialize_default_realm, initialize_host_defined_realm, EnvironmentIndex, ExecutionContext, InterruptHandle, InterruptState, Realm, RealmIdentifier
 };
 use crate::{
     ecmascript::{
         abstract_operations::type_conversion::to_string,
-        builtins::{control_abstraction_objects::promise_objects::promise_abstract_operations::promise_jobs::{PromiseReactionJob, PromiseResolveThenableJob}, error::ErrorHeapData, promise::Promise},
-        scripts_and_modules::ScriptOrModule,
+        builtins::{control_abstraction_objects::promise_objects::promise_abstract_operations::promise_jobs::{PromiseReactionJob, PromiseResolveThenableJob}, error::ErrorHeapData, promise::Promise, Behaviour},
+        scripts_and_modules::{source_code::ensure_all_parsed, ScriptOrModule},
         types::{Function, IntoValue, Object, Reference, String, Symbol, Value},
-    }, engine::{context::{GcScope, NoGcScope}, rootable::HeapRootData, TryResult, Vm}, heap::{heap_gc::heap_gc, CreateHeapData, HeapMarkAndSweep, PrimitiveHeapIndexable}, Heap
+    }, engine::{context::{GcScope, NoGcScope}, rootable::HeapRootData, CodeCache, TryResult, Vm}, heap::{heap_gc::heap_gc, read_startup_snapshot, write_startup_snapshot, CreateHeapData, DEFAULT_GC_GROWTH_FACTOR, HeapMarkAndSweep, HeapStatistics, PrimitiveHeapIndexable, SnapshotError, write_heap_snapshot}, Heap
 };
-use std::{any::Any, cell::RefCell, ptr::NonNull};
+use std::{any::Any, cell::RefCell, ptr::NonNull, sync::Arc};
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Options {
     pub disable_gc: bool,
     pub print_internals: bool,
+    /// Factor by which the heap may grow after a garbage collection before
+    /// the VM automatically triggers the next one.
+    pub gc_growth_factor: f64,
+    /// Heap size in bytes past which execution is terminated, unless
+    /// [`HostHooks::near_heap_limit`] raises the limit.
+    pub max_heap_size: Option<usize>,
+    /// Number of bytecode instructions a single [`GcAgent::run_in_realm`]
+    /// call may execute before execution is terminated.
+    pub instruction_budget: Option<u64>,
+    /// Maximum depth of the execution context stack when calling an
+    /// ECMAScript function. Exceeding it throws a RangeError.
+    ///
+    /// How much native stack a call uses depends on the build, so calls are
+    /// also limited by [`Options::max_stack_size`].
+    pub max_call_depth: usize,
+    /// Maximum native stack in bytes that calls may use, measured from where
+    /// the outermost [`GcAgent::run_in_realm`] call was entered. Exceeding it
+    /// throws a RangeError like exceeding [`Options::max_call_depth`].
+    ///
+    /// The thread running the Agent needs a stack of this size plus
+    /// [`STACK_SIZE_HEADROOM`] for builtin calls and the stack used by the
+    /// embedder's own frames. The default fits the 2 MiB stack of threads
+    /// started with [`std::thread::spawn`].
+    pub max_stack_size: usize,
+    /// Collect garbage at every bytecode instruction, and poison the heap
+    /// slots of collected data instead of compacting the heap. A stale index
+    /// held across a collection then panics on its next use. This is very
+    /// slow and the heap never shrinks: Use it only to validate native code.
+    pub gc_stress: bool,
+    /// Drop the parsed code of all source code that is not being run at every
+    /// garbage collection, so that long-lived Agents do not keep every parsed
+    /// AST alive. The source code is parsed again when a function from it is
+    /// first compiled or its bytecode is next run, which trades parsing time
+    /// for memory.
+    pub discard_parsed_code: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            disable_gc: false,
+            print_internals: false,
+            gc_growth_factor: DEFAULT_GC_GROWTH_FACTOR,
+            max_heap_size: None,
+            instruction_budget: None,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            max_stack_size: DEFAULT_MAX_STACK_SIZE,
+            gc_stress: false,
+            discard_parsed_code: false,
+        }
+    }
 }
 
+/// Default value of [`Options::max_call_depth`].
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1024;
+
+/// Default value of [`Options::max_stack_size`].
+///
+/// In release builds a call from ECMAScript code uses about 2 KiB of native
+/// stack, so this limit is reached after about 500 calls, before
+/// [`DEFAULT_MAX_CALL_DEPTH`]. Debug builds use 30 KiB or more per call and
+/// reach it after a few dozen calls.
+pub const DEFAULT_MAX_STACK_SIZE: usize = 1024 * 1024;
+
+/// Number of execution contexts above [`Options::max_call_depth`] that
+/// builtin function calls may still use. This keeps builtins working near the
+/// limit, eg. when the code catching the RangeError wants to report it.
+const CALL_DEPTH_HEADROOM: usize = 64;
+
+/// Native stack in bytes above [`Options::max_stack_size`] that builtin
+/// function calls may still use, for the same reason as
+/// [`CALL_DEPTH_HEADROOM`].
+pub const STACK_SIZE_HEADROOM: usize = 256 * 1024;
+
 pub type JsResult<T> = std::result::Result<T, JsError>;
 
-#[derive(Debug, Default, Clone, Copy)]
-#[repr(transparent)]
-pub struct JsError(Value);
+#[derive(Debug, Clone, Copy)]
+pub struct JsError(JsErrorKind);
+
+#[derive(Debug, Clone, Copy)]
+enum JsErrorKind {
+    /// An ECMAScript value was thrown.
+    Throw(Value),
+    /// Execution was terminated, see [`Agent::is_terminating`].
+    Termination,
+}
 
 impl JsError {
     pub(crate) fn new(value: Value) -> Self {
-        Self(value)
+        Self(JsErrorKind::Throw(value))
+    }
+
+    pub(crate) fn termination() -> Self {
+        Self(JsErrorKind::Termination)
     }
 
+    /// Returns true if the error terminated execution instead of being thrown
+    /// by ECMAScript code.
+    pub fn is_termination(self) -> bool {
+        matches!(self.0, JsErrorKind::Termination)
+    }
+
+    /// Get the thrown value. A termination error has the value undefined.
     pub fn value(self) -> Value {
-        self.0
+        match self.0 {
+            JsErrorKind::Throw(value) => value,
+            JsErrorKind::Termination => Value::Undefined,
+        }
     }
 
     pub fn to_string<'gc>(self, agent: &mut Agent, gc: GcScope<'gc, '_>) -> String<'gc> {
-        to_string(agent, self.0, gc).unwrap()
+        to_string(agent, self.value(), gc).unwrap()
     }
 }
 
 impl HeapMarkAndSweep for JsError {
     fn mark_values(&self, queues: &mut crate::heap::WorkQueues) {
-        self.0.mark_values(queues);
+        if let JsErrorKind::Throw(value) = &self.0 {
+            value.mark_values(queues);
+        }
     }
 
     fn sweep_values(&mut self, compactions: &crate::heap::CompactionLists) {
-        self.0.sweep_values(compactions);
+        if let JsErrorKind::Throw(value) = &mut self.0 {
+            value.sweep_values(compactions);
+        }
     }
 }
 
@@ -133,6 +230,24 @@ pub trait HostHooks: std::fmt::Debug {
         // The default implementation of HostPromiseRejectionTracker is to return unused.
     }
 
+    /// Called when the heap has grown past [`Options::max_heap_size`] and
+    /// garbage collection could not bring it back under the limit. Return a
+    /// new limit larger than `heap_size` to continue execution, or `None` to
+    /// terminate it.
+    ///
+    /// The default implementation terminates execution.
+    fn near_heap_limit(&self, _heap_size: usize, _heap_limit: usize) -> Option<usize> {
+        None
+    }
+
+    /// Get the cache that compiled bytecode of Scripts and functions is
+    /// stored in and loaded from, if any.
+    ///
+    /// The default implementation does not cache bytecode.
+    fn code_cache(&self) -> Option<&dyn CodeCache> {
+        None
+    }
+
     /// Get access to the Host data, useful to share state between calls of built-in functions.
     ///
     /// Note: This will panic if not implemented manually.
@@ -189,6 +304,56 @@ impl GcAgent {
         }
     }
 
+    /// Boots a new Agent from a startup snapshot created with
+    /// [`GcAgent::create_startup_snapshot`], returning the Agent and the
+    /// roots of the Realms that were alive when the snapshot was created.
+    ///
+    /// Builtin functions are re-linked by their index in the list of the
+    /// engine's own builtins followed by `external_builtins`, which must
+    /// therefore match the list that the snapshot was created with.
+    pub fn from_startup_snapshot(
+        options: Options,
+        host_hooks: &'static dyn HostHooks,
+        snapshot: &[u8],
+        external_builtins: &[Behaviour],
+    ) -> Result<(Self, Vec<RealmRoot>), SnapshotError> {
+        let mut agent = Agent::new(options, host_hooks);
+        let realm_roots = read_startup_snapshot(&mut agent, snapshot, external_builtins)?;
+        let roots = realm_roots
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.is_some())
+            .map(|(index, _)| {
+                let index = u8::try_from(index).map_err(|_| SnapshotError::Malformed)?;
+                Ok(RealmRoot { index })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((Self { agent, realm_roots }, roots))
+    }
+
+    /// Serializes the Agent's heap, including all rooted Realms, into a
+    /// startup snapshot that [`GcAgent::from_startup_snapshot`] can boot a
+    /// new Agent from. Garbage is collected first.
+    ///
+    /// Builtin functions created by the host, for instance with
+    /// [`create_builtin_function`](crate::ecmascript::builtins::create_builtin_function),
+    /// must be listed in `external_builtins`. Heap data that cannot be
+    /// snapshotted, such as pending Promises or host defined data, results in
+    /// an error.
+    pub fn create_startup_snapshot(
+        &mut self,
+        external_builtins: &[Behaviour],
+    ) -> Result<Vec<u8>, SnapshotError> {
+        // The snapshot needs a compacted heap, which the poisoning sweep of
+        // GC stress mode does not produce.
+        let gc_stress = std::mem::replace(&mut self.agent.options.gc_stress, false);
+        self.gc();
+        self.agent.options.gc_stress = gc_stress;
+        // Functions are written with the spans of their parsed code.
+        ensure_all_parsed(&mut self.agent);
+        write_startup_snapshot(&self.agent, &self.realm_roots, external_builtins)
+    }
+
     /// Creates a new Realm
     ///
     /// The Realm will not be removed by garbage collection until
@@ -247,13 +412,36 @@ impl GcAgent {
             .as_ref()
             .expect(error_message);
         assert!(self.agent.execution_context_stack.is_empty());
+        self.agent.executed_instructions = 0;
         let result = self.agent.run_in_realm(realm, func);
+        // Termination only unwinds up to here.
+        self.agent.terminating = false;
         assert!(self.agent.execution_context_stack.is_empty());
         assert!(self.agent.vm_stack.is_empty());
         self.agent.stack_refs.borrow_mut().clear();
         result
     }
 
+    /// Get statistics of the Agent's heap and garbage collection.
+    pub fn heap_statistics(&self) -> HeapStatistics {
+        HeapStatistics::new(&self.agent.heap)
+    }
+
+    /// Write a snapshot of the Agent's heap in the `.heapsnapshot` format of
+    /// Chrome DevTools.
+    ///
+    /// The snapshot contains every entry in the heap, including garbage that
+    /// has not been collected yet; call [`GcAgent::gc`] first to leave it out.
+    pub fn write_heap_snapshot(&self, writer: impl std::io::Write) -> std::io::Result<()> {
+        write_heap_snapshot(&self.agent, &self.realm_roots, writer)
+    }
+
+    /// Get a handle for interrupting code running in this Agent from
+    /// another thread.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle::new(self.agent.interrupts.clone())
+    }
+
     pub fn gc(&mut self) {
         if self.agent.options.disable_gc {
             // GC is disabled; no-op
@@ -281,6 +469,16 @@ pub struct Agent {
     pub(crate) stack_refs: RefCell<Vec<HeapRootData>>,
     /// Temporary storage for on-stack VMs.
     pub(crate) vm_stack: Vec<NonNull<Vm>>,
+    /// True if execution is being terminated; see [`Agent::is_terminating`].
+    pub(crate) terminating: bool,
+    /// Interrupt requests made through [`InterruptHandle`]s.
+    pub(crate) interrupts: Arc<InterruptState>,
+    /// Number of bytecode instructions executed in the current
+    /// [`GcAgent::run_in_realm`] call.
+    pub(crate) executed_instructions: u64,
+    /// Native stack address at which the outermost [`Agent::run_in_realm`]
+    /// call was entered. Stack use is measured from here.
+    pub(crate) stack_base: Option<usize>,
 }
 
 impl Agent {
@@ -294,6 +492,47 @@ impl Agent {
             execution_context_stack: Vec::new(),
             stack_refs: RefCell::new(Vec::with_capacity(64)),
             vm_stack: Vec::with_capacity(16),
+            terminating: false,
+            interrupts: Default::default(),
+            executed_instructions: 0,
+            stack_base: None,
+        }
+    }
+
+    /// Returns true if execution is being terminated.
+    ///
+    /// A terminating Agent unwinds all ECMAScript code on the stack: The
+    /// termination error, see [`JsError::is_termination`], cannot be caught
+    /// by `try` statements and `finally` blocks are not run. The state is
+    /// cleared when [`GcAgent::run_in_realm`] returns, while the error it
+    /// returns stays a termination error.
+    pub fn is_terminating(&self) -> bool {
+        self.terminating
+    }
+
+    /// Start terminating execution, returning the error to unwind with.
+    pub(crate) fn terminate(&mut self) -> JsError {
+        self.terminating = true;
+        JsError::termination()
+    }
+
+    /// Check that allocating `additional_bytes` keeps the heap below
+    /// [`Options::max_heap_size`]. If it would not, the host is asked to raise
+    /// the limit and execution is terminated if it does not.
+    pub(crate) fn check_heap_limit(&mut self, additional_bytes: usize) -> JsResult<()> {
+        let Some(heap_limit) = self.options.max_heap_size else {
+            return Ok(());
+        };
+        let heap_size = self.heap.allocated_bytes().saturating_add(additional_bytes);
+        if heap_size < heap_limit {
+            return Ok(());
+        }
+        match self.host_hooks.near_heap_limit(heap_size, heap_limit) {
+            Some(new_limit) if new_limit > heap_size => {
+                self.options.max_heap_size = Some(new_limit);
+                Ok(())
+            }
+            _ => Err(self.terminate()),
         }
     }
 
@@ -346,6 +585,10 @@ impl Agent {
         F: for<'agent, 'gc, 'scope> FnOnce(&'agent mut Agent, GcScope<'gc, 'scope>) -> R,
     {
         let execution_stack_depth_before_call = self.execution_context_stack.len();
+        let outermost = self.stack_base.is_none();
+        if outermost {
+            self.stack_base = Some(stack_address());
+        }
         self.execution_context_stack.push(ExecutionContext {
             ecmascript_code: None,
             function: None,
@@ -361,6 +604,9 @@ impl Agent {
             execution_stack_depth_before_call + 1
         );
         self.execution_context_stack.pop();
+        if outermost {
+            self.stack_base = None;
+        }
         result
     }
 
@@ -396,6 +642,29 @@ impl Agent {
             .into_value()
     }
 
+    /// Throw a RangeError if calling a function would exceed
+    /// [`Options::max_call_depth`] or [`Options::max_stack_size`]. Calls to
+    /// builtin functions are allowed a small headroom above the limits.
+    pub(crate) fn check_call_depth(&mut self, builtin: bool, gc: NoGcScope) -> JsResult<()> {
+        let mut max_call_depth = self.options.max_call_depth;
+        let mut max_stack_size = self.options.max_stack_size;
+        if builtin {
+            max_call_depth = max_call_depth.saturating_add(CALL_DEPTH_HEADROOM);
+            max_stack_size = max_stack_size.saturating_add(STACK_SIZE_HEADROOM);
+        }
+        let stack_size = self
+            .stack_base
+            .map_or(0, |stack_base| stack_base.abs_diff(stack_address()));
+        if self.execution_context_stack.len() >= max_call_depth || stack_size >= max_stack_size {
+            return Err(self.throw_exception_with_static_message(
+                ExceptionType::RangeError,
+                "Maximum call stack size exceeded",
+                gc,
+            ));
+        }
+        Ok(())
+    }
+
     /// ### [5.2.3.2 Throw an Exception](https://tc39.es/ecma262/#sec-throw-an-exception)
     pub fn throw_exception_with_static_message(
         &mut self,
@@ -403,7 +672,7 @@ impl Agent {
         message: &'static str,
         gc: NoGcScope,
     ) -> JsError {
-        JsError(
+        JsError::new(
             self.create_exception_with_static_message(kind, message, gc)
                 .unbind(),
         )
@@ -416,7 +685,7 @@ impl Agent {
         gc: NoGcScope,
     ) -> JsError {
         let message = String::from_string(self, message, gc).unbind();
-        JsError(
+        JsError::new(
             self.heap
                 .create(ErrorHeapData::new(kind, Some(message), None))
                 .into_value(),
@@ -428,7 +697,7 @@ impl Agent {
         kind: ExceptionType,
         message: String,
     ) -> JsError {
-        JsError(
+        JsError::new(
             self.heap
                 .create(ErrorHeapData::new(kind, Some(message.unbind()), None))
                 .into_value(),
@@ -588,3 +857,199 @@ impl TryFrom<u16> for ExceptionType {
 }
 
 impl PrimitiveHeapIndexable for Agent {}
+
+/// Address of a local in the calling function's native stack frame.
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&marker) as *const u8 as usize
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
+
+    use super::{HostHooks, Job, Options, DEFAULT_MAX_CALL_DEPTH};
+    use crate::test_utils::{eval_script, TestAgent};
+
+    /// Limit the heap to `extra` bytes above its current size.
+    fn limit_heap(agent: &mut TestAgent, extra: usize) {
+        agent.run(|agent, _| {
+            agent.options.max_heap_size = Some(agent.heap.allocated_bytes() + extra);
+        });
+    }
+
+    /// Evaluate the source text and return true if it was terminated.
+    fn is_terminated(agent: &mut TestAgent, source_text: &str) -> bool {
+        agent.run(|agent, gc| {
+            eval_script(agent, source_text, gc).is_err_and(|err| err.is_termination())
+        })
+    }
+
+    #[test]
+    fn heap_limit_terminates_execution() {
+        let mut agent = TestAgent::new();
+        limit_heap(&mut agent, 1024 * 1024);
+        assert!(is_terminated(
+            &mut agent,
+            "var a = []; while (true) a.push('x'.repeat(1024) + a.length);"
+        ));
+        // Termination ends with the run: The agent can be used again.
+        agent.eval("a = null");
+        assert_eq!(agent.eval("1 + 1"), "2");
+    }
+
+    #[test]
+    fn termination_is_uncatchable() {
+        let mut agent = TestAgent::new();
+        limit_heap(&mut agent, 1024 * 1024);
+        assert!(is_terminated(
+            &mut agent,
+            "var caught = false;
+            try {
+                var a = [];
+                while (true) a.push('x'.repeat(1024) + a.length);
+            } catch {
+                caught = true;
+            }"
+        ));
+        assert_eq!(agent.eval("caught"), "false");
+    }
+
+    #[test]
+    fn thrown_undefined_is_not_termination() {
+        let mut agent = TestAgent::new();
+        assert!(!is_terminated(&mut agent, "throw undefined"));
+    }
+
+    #[test]
+    fn large_allocations_check_heap_limit() {
+        let mut agent = TestAgent::new();
+        limit_heap(&mut agent, 1024 * 1024);
+        assert!(is_terminated(
+            &mut agent,
+            "new ArrayBuffer(16 * 1024 * 1024)"
+        ));
+        assert!(is_terminated(&mut agent, "'x'.repeat(1024 * 1024 * 1024)"));
+        assert!(is_terminated(
+            &mut agent,
+            "var s = 'x'.repeat(1024); while (true) s += s;"
+        ));
+        // Allocations below the limit are unaffected.
+        assert_eq!(agent.eval("new ArrayBuffer(1024).byteLength"), "1024");
+    }
+
+    #[test]
+    fn near_heap_limit_can_raise_limit() {
+        #[derive(Debug)]
+        struct RaisingHostHooks {
+            calls: AtomicUsize,
+        }
+
+        impl HostHooks for RaisingHostHooks {
+            fn enqueue_promise_job(&self, _job: Job) {}
+
+            fn near_heap_limit(&self, heap_size: usize, _heap_limit: usize) -> Option<usize> {
+                self.calls.fetch_add(1, Ordering::Relaxed);
+                Some(heap_size * 2)
+            }
+        }
+
+        static HOOKS: RaisingHostHooks = RaisingHostHooks {
+            calls: AtomicUsize::new(0),
+        };
+        let mut agent = TestAgent::with_host_hooks(Options::default(), &HOOKS);
+        limit_heap(&mut agent, 0);
+        assert_eq!(
+            agent.eval("new ArrayBuffer(16 * 1024 * 1024).byteLength"),
+            (16 * 1024 * 1024).to_string()
+        );
+        assert!(HOOKS.calls.load(Ordering::Relaxed) > 0);
+    }
+
+    /// Run the test on a thread with enough native stack for the default call
+    /// depth limit in debug builds. The native stack limit is lifted so that
+    /// the call depth limit is reached first.
+    fn with_large_stack(test: impl FnOnce(Options) + Send + 'static) {
+        let options = Options {
+            max_stack_size: usize::MAX,
+            ..Default::default()
+        };
+        thread::Builder::new()
+            .stack_size(256 * 1024 * 1024)
+            .spawn(move || test(options))
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn deep_recursion_throws_range_error() {
+        with_large_stack(|options| {
+            let mut agent = TestAgent::with_options(options);
+            assert_eq!(
+                agent.eval_err("function f() { return f() + 1; } f()"),
+                "RangeError: Maximum call stack size exceeded"
+            );
+            assert_eq!(
+                agent.eval(
+                    "var depth = 0;
+                    function g() { depth++; g(); }
+                    var result;
+                    try { g(); } catch (e) { result = e instanceof RangeError && depth; }
+                    result"
+                ),
+                (DEFAULT_MAX_CALL_DEPTH - 2).to_string()
+            );
+        });
+    }
+
+    #[test]
+    fn deep_recursion_through_builtins_throws_range_error() {
+        with_large_stack(|options| {
+            let mut agent = TestAgent::with_options(options);
+            assert_eq!(
+                agent.eval_err("function f() { return [0].map(f); } f()"),
+                "RangeError: Maximum call stack size exceeded"
+            );
+        });
+    }
+
+    #[test]
+    fn deep_recursion_fits_default_thread_stack() {
+        thread::spawn(|| {
+            let mut agent = TestAgent::new();
+            assert_eq!(
+                agent.eval_err("function f() { return f() + 1; } f()"),
+                "RangeError: Maximum call stack size exceeded"
+            );
+            assert_eq!(
+                agent.eval_err("function g() { return [0].map(g); } g()"),
+                "RangeError: Maximum call stack size exceeded"
+            );
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn call_depth_limit_is_configurable() {
+        let mut agent = TestAgent::with_options(Options {
+            max_call_depth: 16,
+            ..Default::default()
+        });
+        assert_eq!(
+            agent.eval(
+                "var depth = 0;
+                function f() { depth++; f(); }
+                var result;
+                try { f(); } catch (e) { result = e.message + ' at ' + depth; }
+                result"
+            ),
+            "Maximum call stack size exceeded at 14"
+        );
+    }
+}
@@ -46,7 +46,10 @@ use crate::engine::context::{GcScope, NoGcScope};
 use crate::engine::TryResult;
 use crate::{
     ecmascript::types::{Base, Object, Reference, String, Value},
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
+    },
 };
 
 use super::{Agent, JsResult};
@@ -117,6 +120,20 @@ macro_rules! create_environment_index {
             }
         }
 
+        impl SnapshotData for $index {
+            fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+                self.into_u32_index().serialize(writer)
+            }
+
+            fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+                let index = u32::deserialize(reader)?;
+                if index == u32::MAX {
+                    return Err(SnapshotError::Malformed);
+                }
+                Ok(Self::from_u32_index(index))
+            }
+        }
+
         impl std::ops::Index<$index> for Agent {
             type Output = $name;
 
@@ -640,6 +657,43 @@ impl HeapMarkAndSweep for EnvironmentIndex {
     }
 }
 
+impl SnapshotData for EnvironmentIndex {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        match self {
+            EnvironmentIndex::Declarative(idx) => {
+                0u8.serialize(writer)?;
+                idx.serialize(writer)
+            }
+            EnvironmentIndex::Function(idx) => {
+                1u8.serialize(writer)?;
+                idx.serialize(writer)
+            }
+            EnvironmentIndex::Global(idx) => {
+                2u8.serialize(writer)?;
+                idx.serialize(writer)
+            }
+            EnvironmentIndex::Object(idx) => {
+                3u8.serialize(writer)?;
+                idx.serialize(writer)
+            }
+        }
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        match u8::deserialize(reader)? {
+            0 => Ok(EnvironmentIndex::Declarative(SnapshotData::deserialize(
+                reader,
+            )?)),
+            1 => Ok(EnvironmentIndex::Function(SnapshotData::deserialize(
+                reader,
+            )?)),
+            2 => Ok(EnvironmentIndex::Global(SnapshotData::deserialize(reader)?)),
+            3 => Ok(EnvironmentIndex::Object(SnapshotData::deserialize(reader)?)),
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Environments {
     pub(crate) declarative: Vec<Option<DeclarativeEnvironment>>,
@@ -661,6 +715,37 @@ impl Default for Environments {
     }
 }
 
+impl SnapshotData for Environments {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            declarative,
+            function,
+            global,
+            object,
+            private,
+        } = self;
+        // Private environments only exist while class bodies are evaluated
+        // or are captured by class element functions.
+        if private.iter().any(Option::is_some) {
+            return Err(SnapshotError::UnsupportedHeapData("private environments"));
+        }
+        declarative.serialize(writer)?;
+        function.serialize(writer)?;
+        global.serialize(writer)?;
+        object.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            declarative: SnapshotData::deserialize(reader)?,
+            function: SnapshotData::deserialize(reader)?,
+            global: SnapshotData::deserialize(reader)?,
+            object: SnapshotData::deserialize(reader)?,
+            private: Vec::with_capacity(0),
+        })
+    }
+}
+
 /// ### Try [9.1.2.1 GetIdentifierReference ( env, name, strict )](https://tc39.es/ecma262/#sec-getidentifierreference)
 ///
 /// The abstract operation GetIdentifierReference takes arguments env (an
@@ -686,6 +771,7 @@ pub(crate) fn try_get_identifier_reference<'a>(
             strict,
             // [[ThisValue]]: EMPTY
             this_value: None,
+            property_cache: None,
         });
         // }.
     };
@@ -705,6 +791,7 @@ pub(crate) fn try_get_identifier_reference<'a>(
             strict,
             // [[ThisValue]]: EMPTY
             this_value: None,
+            property_cache: None,
         })
         // }.
     }
@@ -744,6 +831,7 @@ pub(crate) fn get_identifier_reference<'a, 'b>(
             strict,
             // [[ThisValue]]: EMPTY
             this_value: None,
+            property_cache: None,
         });
         // }.
     };
@@ -772,6 +860,7 @@ pub(crate) fn get_identifier_reference<'a, 'b>(
             strict,
             // [[ThisValue]]: EMPTY
             this_value: None,
+            property_cache: None,
         })
         // }.
     }
@@ -11,7 +11,10 @@ use crate::{
         types::{Object, String, Value},
     },
     engine::context::NoGcScope,
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WorkQueues,
+    },
 };
 
 /// ### [9.1.1.1 Declarative Environment Records](https://tc39.es/ecma262/#sec-declarative-environment-records)
@@ -388,6 +391,48 @@ impl DeclarativeEnvironmentIndex {
     }
 }
 
+impl SnapshotData for DeclarativeEnvironment {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            outer_env,
+            bindings,
+        } = self;
+        outer_env.serialize(writer)?;
+        bindings.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            outer_env: SnapshotData::deserialize(reader)?,
+            bindings: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
+
+impl SnapshotData for Binding {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            value,
+            strict,
+            mutable,
+            deletable,
+        } = self;
+        value.serialize(writer)?;
+        strict.serialize(writer)?;
+        mutable.serialize(writer)?;
+        deletable.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            value: SnapshotData::deserialize(reader)?,
+            strict: SnapshotData::deserialize(reader)?,
+            mutable: SnapshotData::deserialize(reader)?,
+            deletable: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
+
 impl HeapMarkAndSweep for DeclarativeEnvironmentIndex {
     fn mark_values(&self, queues: &mut WorkQueues) {
         queues.declarative_environments.push(*self);
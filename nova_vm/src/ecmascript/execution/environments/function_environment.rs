@@ -14,7 +14,10 @@ use crate::{
         execution::{agent::ExceptionType, Agent, JsResult},
         types::{Function, InternalMethods, IntoFunction, IntoValue, Object, String, Value},
     },
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
+    },
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -101,6 +104,53 @@ impl HeapMarkAndSweep for FunctionEnvironment {
     }
 }
 
+impl SnapshotData for ThisBindingStatus {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let tag: u8 = match self {
+            ThisBindingStatus::Lexical => 0,
+            ThisBindingStatus::Initialized => 1,
+            ThisBindingStatus::Uninitialized => 2,
+        };
+        tag.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        match u8::deserialize(reader)? {
+            0 => Ok(ThisBindingStatus::Lexical),
+            1 => Ok(ThisBindingStatus::Initialized),
+            2 => Ok(ThisBindingStatus::Uninitialized),
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+}
+
+impl SnapshotData for FunctionEnvironment {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            this_value,
+            this_binding_status,
+            function_object,
+            new_target,
+            declarative_environment,
+        } = self;
+        this_value.serialize(writer)?;
+        this_binding_status.serialize(writer)?;
+        function_object.serialize(writer)?;
+        new_target.serialize(writer)?;
+        declarative_environment.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            this_value: SnapshotData::deserialize(reader)?,
+            this_binding_status: SnapshotData::deserialize(reader)?,
+            function_object: SnapshotData::deserialize(reader)?,
+            new_target: SnapshotData::deserialize(reader)?,
+            declarative_environment: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
+
 /// ### [9.1.2.4 NewFunctionEnvironment ( F, newTarget )](https://tc39.es/ecma262/#sec-newfunctionenvironment)
 ///
 /// The abstract operation NewFunctionEnvironment takes arguments F (an
@@ -436,7 +486,7 @@ impl FunctionEnvironmentIndex {
             Function::BuiltinConstructorFunction(_) => unreachable!(),
             Function::BuiltinPromiseResolvingFunction(_) => unreachable!(),
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(_) => unreachable!(),
         }
     }
 
@@ -462,7 +512,7 @@ impl FunctionEnvironmentIndex {
             Function::BuiltinConstructorFunction(_) => unreachable!(),
             Function::BuiltinPromiseResolvingFunction(_) => unreachable!(),
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(_) => unreachable!(),
         };
         // 3. Assert: home is an ordinary object.
         let home = OrdinaryObject::try_from(home).unwrap();
@@ -27,7 +27,10 @@ use crate::{
         context::{GcScope, NoGcScope},
         TryResult,
     },
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WorkQueues,
+    },
 };
 
 /// ### [9.1.1.4 Global Environment Records](https://tc39.es/ecma262/#sec-global-environment-records)
@@ -146,6 +149,30 @@ impl HeapMarkAndSweep for GlobalEnvironment {
     }
 }
 
+impl SnapshotData for GlobalEnvironment {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            object_record,
+            global_this_value,
+            declarative_record,
+            var_names,
+        } = self;
+        object_record.serialize(writer)?;
+        global_this_value.serialize(writer)?;
+        declarative_record.serialize(writer)?;
+        var_names.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            object_record: SnapshotData::deserialize(reader)?,
+            global_this_value: SnapshotData::deserialize(reader)?,
+            declarative_record: SnapshotData::deserialize(reader)?,
+            var_names: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
+
 impl GlobalEnvironmentIndex {
     /// ### Try [9.1.1.4.1 HasBinding ( N )](https://tc39.es/ecma262/#sec-global-environment-records-hasbinding-n)
     ///
@@ -17,7 +17,10 @@ use crate::{
         execution::{agent::ExceptionType, Agent, JsResult},
         types::{InternalMethods, Object, PropertyDescriptor, PropertyKey, String, Value},
     },
-    heap::{CompactionLists, HeapMarkAndSweep, WellKnownSymbolIndexes, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WellKnownSymbolIndexes, WorkQueues,
+    },
 };
 
 /// ### [9.1.1.2 Object Environment Records](https://tc39.es/ecma262/#sec-object-environment-records)
@@ -100,6 +103,27 @@ impl HeapMarkAndSweep for ObjectEnvironment {
     }
 }
 
+impl SnapshotData for ObjectEnvironment {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            binding_object,
+            is_with_environment,
+            outer_env,
+        } = self;
+        binding_object.serialize(writer)?;
+        is_with_environment.serialize(writer)?;
+        outer_env.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            binding_object: SnapshotData::deserialize(reader)?,
+            is_with_environment: SnapshotData::deserialize(reader)?,
+            outer_env: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
+
 impl ObjectEnvironmentIndex {
     /// ### Try [9.1.1.2.1 HasBinding ( N )](https://tc39.es/ecma262/#sec-object-environment-records-hasbinding-n)
     ///
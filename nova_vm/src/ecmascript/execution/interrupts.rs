@@ -0,0 +1,166 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! ## Interrupting running ECMAScript code
+//!
+//! An [`InterruptHandle`] lets another thread terminate the code running in
+//! an Agent or have a callback run on the Agent's thread. Requests are only
+//! picked up by the bytecode VM at loop back-edges and on entry to function
+//! code: A long-running native builtin cannot be interrupted.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use super::{Agent, JsResult};
+
+type InterruptCallback = Box<dyn FnOnce(&mut Agent) + Send>;
+
+#[derive(Default)]
+pub(crate) struct InterruptState {
+    /// Set when any request is pending; the only part checked on the fast
+    /// path.
+    pending: AtomicBool,
+    terminate: AtomicBool,
+    callbacks: Mutex<Vec<InterruptCallback>>,
+}
+
+impl std::fmt::Debug for InterruptState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InterruptState")
+            .field("pending", &self.pending)
+            .field("terminate", &self.terminate)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Handle for interrupting an Agent from another thread.
+///
+/// Obtained from [`GcAgent::interrupt_handle`](super::agent::GcAgent::interrupt_handle).
+#[derive(Debug, Clone)]
+pub struct InterruptHandle(Arc<InterruptState>);
+
+impl InterruptHandle {
+    pub(crate) fn new(state: Arc<InterruptState>) -> Self {
+        Self(state)
+    }
+
+    /// Request termination of the currently running ECMAScript code. The
+    /// termination cannot be caught by the code; see
+    /// [`Agent::is_terminating`].
+    ///
+    /// A request made while no code is running terminates the next run.
+    pub fn terminate_execution(&self) {
+        self.0.terminate.store(true, Ordering::Relaxed);
+        self.0.pending.store(true, Ordering::Release);
+    }
+
+    /// Request the callback to be called on the Agent's thread the next time
+    /// the running ECMAScript code checks for interrupts.
+    pub fn request_callback(&self, callback: impl FnOnce(&mut Agent) + Send + 'static) {
+        self.0.callbacks.lock().unwrap().push(Box::new(callback));
+        self.0.pending.store(true, Ordering::Release);
+    }
+}
+
+impl Agent {
+    /// Handle pending interrupt requests and enforce the instruction budget.
+    /// Returns an error if execution should terminate.
+    pub(crate) fn check_interrupts(&mut self) -> JsResult<()> {
+        if self
+            .options
+            .instruction_budget
+            .is_some_and(|budget| self.executed_instructions > budget)
+        {
+            return Err(self.terminate());
+        }
+        if !self.interrupts.pending.swap(false, Ordering::Acquire) {
+            return Ok(());
+        }
+        let callbacks = std::mem::take(&mut *self.interrupts.callbacks.lock().unwrap());
+        for callback in callbacks {
+            callback(self);
+        }
+        if self.interrupts.terminate.swap(false, Ordering::Relaxed) {
+            return Err(self.terminate());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    use crate::{
+        ecmascript::execution::agent::Options,
+        test_utils::{eval_script, TestAgent},
+    };
+
+    /// Evaluate the source text and return true if it was terminated.
+    fn is_terminated(agent: &mut TestAgent, source_text: &str) -> bool {
+        agent.run(|agent, gc| {
+            eval_script(agent, source_text, gc).is_err_and(|err| err.is_termination())
+        })
+    }
+
+    #[test]
+    fn instruction_budget_terminates_loop() {
+        let mut agent = TestAgent::with_options(Options {
+            instruction_budget: Some(10_000),
+            ..Default::default()
+        });
+        assert!(is_terminated(
+            &mut agent,
+            "try { while (true) {} } catch {}"
+        ));
+        // The budget applies to each run separately.
+        assert_eq!(agent.eval("let i = 0; while (i < 100) i++; i"), "100");
+    }
+
+    #[test]
+    fn terminate_execution_from_another_thread() {
+        let mut agent = TestAgent::new();
+        let handle = agent.interrupt_handle();
+        let terminator = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            handle.terminate_execution();
+        });
+        assert!(is_terminated(
+            &mut agent,
+            "try { while (true) {} } catch {}"
+        ));
+        terminator.join().unwrap();
+        assert_eq!(agent.eval("1 + 1"), "2");
+    }
+
+    #[test]
+    fn terminate_execution_before_run() {
+        let mut agent = TestAgent::new();
+        agent.interrupt_handle().terminate_execution();
+        assert!(is_terminated(&mut agent, "while (true) {}"));
+        assert_eq!(agent.eval("1 + 1"), "2");
+    }
+
+    #[test]
+    fn request_callback_runs_on_agent() {
+        let mut agent = TestAgent::new();
+        let called = Arc::new(AtomicBool::new(false));
+        let flag = called.clone();
+        agent.interrupt_handle().request_callback(move |agent| {
+            assert!(!agent.execution_context_stack.is_empty());
+            flag.store(true, Ordering::Relaxed);
+        });
+        assert_eq!(agent.eval("let i = 0; while (i < 10) i++; i"), "10");
+        assert!(called.load(Ordering::Relaxed));
+    }
+}
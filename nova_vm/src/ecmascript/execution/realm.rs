@@ -16,7 +16,10 @@ use crate::{
             BUILTIN_STRING_MEMORY,
         },
     },
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
+    },
 };
 pub(crate) use intrinsics::Intrinsics;
 pub(crate) use intrinsics::ProtoIntrinsics;
@@ -109,6 +112,16 @@ impl HeapMarkAndSweep for RealmIdentifier {
     }
 }
 
+impl SnapshotData for RealmIdentifier {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?, PhantomData))
+    }
+}
+
 /// ### [9.3 Realms](https://tc39.es/ecma262/#sec-code-realms)
 ///
 /// Before it is evaluated, all ECMAScript code must be associated with a
@@ -206,6 +219,38 @@ impl HeapMarkAndSweep for Realm {
     }
 }
 
+impl SnapshotData for Realm {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            agent_signifier: _,
+            intrinsics,
+            global_object,
+            global_env,
+            template_map: _,
+            loaded_modules: _,
+            host_defined,
+        } = self;
+        if host_defined.is_some() {
+            return Err(SnapshotError::HostDefined);
+        }
+        intrinsics.serialize(writer)?;
+        global_object.serialize(writer)?;
+        global_env.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            agent_signifier: PhantomData,
+            intrinsics: SnapshotData::deserialize(reader)?,
+            global_object: SnapshotData::deserialize(reader)?,
+            global_env: SnapshotData::deserialize(reader)?,
+            template_map: (),
+            loaded_modules: (),
+            host_defined: None,
+        })
+    }
+}
+
 /// ### [9.3.1 CreateRealm ( )](https://tc39.es/ecma262/#sec-createrealm)
 ///
 /// The abstract operation CreateRealm takes no arguments and returns a Realm
@@ -501,6 +546,34 @@ pub(crate) fn set_default_global_bindings<'a>(
         define_property_or_throw(agent, global, name, desc, gc.reborrow())?;
     }
 
+    // B.2.1 Additional Properties of the Global Object
+    #[cfg(feature = "annex-b-global")]
+    {
+        // B.2.1.1 escape ( string )
+        let name = PropertyKey::from(BUILTIN_STRING_MEMORY.escape);
+        let value = agent.get_realm(realm_id).intrinsics().escape();
+        let desc = PropertyDescriptor {
+            value: Some(value.into_value()),
+            writable: Some(true),
+            enumerable: Some(false),
+            configurable: Some(true),
+            ..Default::default()
+        };
+        define_property_or_throw(agent, global, name, desc, gc.reborrow())?;
+
+        // B.2.1.2 unescape ( string )
+        let name = PropertyKey::from(BUILTIN_STRING_MEMORY.unescape);
+        let value = agent.get_realm(realm_id).intrinsics().unescape();
+        let desc = PropertyDescriptor {
+            value: Some(value.into_value()),
+            writable: Some(true),
+            enumerable: Some(false),
+            configurable: Some(true),
+            ..Default::default()
+        };
+        define_property_or_throw(agent, global, name, desc, gc.reborrow())?;
+    }
+
     // 19.3 Constructor Properties of the Global Object
     {
         // 19.3.1 AggregateError ( . . . )
@@ -40,7 +40,7 @@ use crate::ecmascript::builtins::structured_data::shared_array_buffer_objects::{
 #[cfg(feature = "regexp")]
 use crate::ecmascript::builtins::text_processing::regexp_objects::{
     regexp_constructor::RegExpConstructor, regexp_prototype::RegExpPrototype,
-    regexp_string_iterator_prototype::RegExpStringIteratorPrototype,
+    regexp_string_iterator_objects::regexp_string_iterator_prototype::RegExpStringIteratorPrototype,
 };
 #[cfg(feature = "array-buffer")]
 use crate::ecmascript::builtins::{
@@ -104,7 +104,7 @@ use crate::{
             reflection::{proxy_constructor::ProxyConstructor, reflect_object::ReflectObject},
             text_processing::string_objects::{
                 string_constructor::StringConstructor,
-                string_iterator_objects::StringIteratorPrototype,
+                string_iterator_objects::string_iterator_prototype::StringIteratorPrototype,
                 string_prototype::StringPrototype,
             },
             Array, BuiltinFunction,
@@ -146,7 +146,8 @@ use crate::{
         indexes::{ArrayIndex, BuiltinFunctionIndex, ObjectIndex, PrimitiveObjectIndex},
         intrinsic_function_count, intrinsic_object_count, intrinsic_primitive_object_count,
         CompactionLists, HeapMarkAndSweep, IntrinsicConstructorIndexes, IntrinsicFunctionIndexes,
-        IntrinsicObjectIndexes, IntrinsicPrimitiveObjectIndexes, WorkQueues,
+        IntrinsicObjectIndexes, IntrinsicPrimitiveObjectIndexes, SnapshotData, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
     },
 };
 #[derive(Debug, Clone)]
@@ -208,6 +209,8 @@ pub enum ProtoIntrinsics {
     ReferenceError,
     #[cfg(feature = "regexp")]
     RegExp,
+    #[cfg(feature = "regexp")]
+    RegExpStringIterator,
     #[cfg(feature = "set")]
     Set,
     #[cfg(feature = "set")]
@@ -215,6 +218,7 @@ pub enum ProtoIntrinsics {
     #[cfg(feature = "shared-array-buffer")]
     SharedArrayBuffer,
     String,
+    StringIterator,
     Symbol,
     SyntaxError,
     TypeError,
@@ -393,6 +397,7 @@ impl Intrinsics {
             ProtoIntrinsics::RangeError => self.range_error_prototype().into(),
             ProtoIntrinsics::ReferenceError => self.reference_error_prototype().into(),
             ProtoIntrinsics::String => self.string_prototype().into(),
+            ProtoIntrinsics::StringIterator => self.string_iterator_prototype().into(),
             ProtoIntrinsics::Symbol => self.symbol_prototype().into(),
             ProtoIntrinsics::SyntaxError => self.syntax_error_prototype().into(),
             ProtoIntrinsics::TypeError => self.type_error_prototype().into(),
@@ -429,6 +434,10 @@ impl Intrinsics {
             ProtoIntrinsics::Promise => self.promise_prototype().into(),
             #[cfg(feature = "regexp")]
             ProtoIntrinsics::RegExp => self.reg_exp_prototype().into(),
+            #[cfg(feature = "regexp")]
+            ProtoIntrinsics::RegExpStringIterator => {
+                self.reg_exp_string_iterator_prototype().into()
+            }
             #[cfg(feature = "set")]
             ProtoIntrinsics::Set => self.set_prototype().into(),
             #[cfg(feature = "set")]
@@ -1288,6 +1297,13 @@ impl Intrinsics {
             .into()
     }
 
+    /// %Set.prototype.has%
+    pub(crate) fn set_prototype_has(&self) -> BuiltinFunction<'static> {
+        IntrinsicFunctionIndexes::SetPrototypeHas
+            .get_builtin_function_index(self.builtin_function_index_base)
+            .into()
+    }
+
     /// %Set.prototype.values%
     pub(crate) fn set_prototype_values(&self) -> BuiltinFunction<'static> {
         IntrinsicFunctionIndexes::SetPrototypeValues
@@ -1320,6 +1336,13 @@ impl Intrinsics {
             .into()
     }
 
+    /// %SetIteratorPrototype.next%
+    pub(crate) fn set_iterator_prototype_next(&self) -> BuiltinFunction<'static> {
+        IntrinsicFunctionIndexes::SetIteratorPrototypeNext
+            .get_builtin_function_index(self.builtin_function_index_base)
+            .into()
+    }
+
     /// %SharedArrayBuffer.prototype%
     #[cfg(feature = "shared-array-buffer")]
     pub(crate) fn shared_array_buffer_prototype(&self) -> OrdinaryObject<'static> {
@@ -1763,10 +1786,12 @@ impl HeapMarkAndSweep for Intrinsics {
         self.reg_exp().mark_values(queues);
         #[cfg(feature = "regexp")]
         self.reg_exp_string_iterator_prototype().mark_values(queues);
+        self.set_prototype_has().mark_values(queues);
         self.set_prototype_values().mark_values(queues);
         self.set_prototype().mark_values(queues);
         self.set().mark_values(queues);
         self.set_iterator_prototype().mark_values(queues);
+        self.set_iterator_prototype_next().mark_values(queues);
         #[cfg(feature = "shared-array-buffer")]
         self.shared_array_buffer_prototype().mark_values(queues);
         #[cfg(feature = "shared-array-buffer")]
@@ -1842,3 +1867,27 @@ impl HeapMarkAndSweep for Intrinsics {
             .shift_index(builtin_function_index_base);
     }
 }
+
+impl SnapshotData for Intrinsics {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            object_index_base,
+            primitive_object_index_base,
+            array_prototype,
+            builtin_function_index_base,
+        } = self;
+        object_index_base.serialize(writer)?;
+        primitive_object_index_base.serialize(writer)?;
+        array_prototype.serialize(writer)?;
+        builtin_function_index_base.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            object_index_base: SnapshotData::deserialize(reader)?,
+            primitive_object_index_base: SnapshotData::deserialize(reader)?,
+            array_prototype: SnapshotData::deserialize(reader)?,
+            builtin_function_index_base: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
@@ -2,7 +2,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::heap::{CompactionLists, HeapMarkAndSweep, WorkQueues};
+use crate::heap::{
+    CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader, SnapshotResult,
+    SnapshotWriter, WorkQueues,
+};
 
 use self::script::ScriptIdentifier;
 
@@ -33,3 +36,16 @@ impl HeapMarkAndSweep for ScriptOrModule {
         }
     }
 }
+
+impl SnapshotData for ScriptOrModule {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        match self {
+            ScriptOrModule::Script(idx) => idx.serialize(writer),
+            ScriptOrModule::Module(_) => Err(SnapshotError::UnsupportedHeapData("modules")),
+        }
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(ScriptOrModule::Script(SnapshotData::deserialize(reader)?))
+    }
+}
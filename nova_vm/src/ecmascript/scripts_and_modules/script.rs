@@ -21,7 +21,10 @@ use crate::{
         types::{IntoValue, String, Value, BUILTIN_STRING_MEMORY},
     },
     engine::{Executable, Vm},
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
+    },
 };
 use ahash::AHashSet;
 use oxc_ast::ast::{BindingIdentifier, Program, VariableDeclarationKind};
@@ -116,6 +119,16 @@ impl HeapMarkAndSweep for ScriptIdentifier {
     }
 }
 
+impl SnapshotData for ScriptIdentifier {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.into_u32().serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self::from_u32(u32::deserialize(reader)?))
+    }
+}
+
 /// ### [16.1.4 Script Records](https://tc39.es/ecma262/#sec-script-records)
 ///
 /// A Script Record encapsulates information about a script being evaluated.
@@ -189,6 +202,37 @@ impl HeapMarkAndSweep for Script {
     }
 }
 
+impl SnapshotData for Script {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            realm,
+            ecmascript_code: _,
+            loaded_modules: _,
+            host_defined,
+            source_code,
+        } = self;
+        if host_defined.is_some() {
+            return Err(SnapshotError::HostDefined);
+        }
+        realm.serialize(writer)?;
+        source_code.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let realm = SnapshotData::deserialize(reader)?;
+        let source_code = SourceCode::deserialize(reader)?;
+        // The Program was parsed again when its SourceCode was read.
+        let ecmascript_code = ManuallyDrop::new(reader.take_program(source_code.get_index())?);
+        Ok(Self {
+            realm,
+            ecmascript_code,
+            loaded_modules: (),
+            host_defined: None,
+            source_code,
+        })
+    }
+}
+
 /// ### [16.1.5 ParseScript ( sourceText, realm, hostDefined )](https://tc39.es/ecma262/#sec-parse-script)
 ///
 /// The abstract operation ParseScript takes arguments sourceText (ECMAScript
@@ -303,10 +347,15 @@ pub fn script_evaluation(agent: &mut Agent, script: Script, mut gc: GcScope) ->
         // a. Set result to Completion(Evaluation of script).
         // b. If result.[[Type]] is normal and result.[[Value]] is empty, then
         // i. Set result to NormalCompletion(undefined).
+        let collections = agent.heap.gc_statistics.collections;
         let result = Vm::execute(agent, bytecode, None, gc).into_js_result();
-        // SAFETY: The bytecode is not accessible by anyone and no one will try
-        // to re-run it.
-        unsafe { bytecode.try_drop(agent) };
+        // If garbage was collected during execution, the bytecode index is
+        // stale and the bytecode is left for the next collection.
+        if agent.heap.gc_statistics.collections == collections {
+            // SAFETY: The bytecode is not accessible by anyone and no one will
+            // try to re-run it.
+            unsafe { bytecode.try_drop(agent) };
+        }
         result
     } else {
         Err(result.err().unwrap())
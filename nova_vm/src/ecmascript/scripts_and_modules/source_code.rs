@@ -6,24 +6,37 @@
 //! `eval(source)` source strings alive after the eval call for the case where
 //! that the eval call defines functions. Those functions will refer to the
 //! SourceCode for their function source text.
+//!
+//! The SourceCode also owns the parsed code of the source. With
+//! [`Options::discard_parsed_code`], garbage collection drops the parsed code
+//! of source codes that are not being run. The whole source is parsed again
+//! when its parsed code is next needed, and the functions, scripts and
+//! bytecode listed as depending on it are pointed at the new parsed code by
+//! their span.
+//!
+//! [`Options::discard_parsed_code`]: crate::ecmascript::execution::agent::Options::discard_parsed_code
 
-use std::{fmt::Debug, ops::Index, ptr::NonNull};
+use std::{fmt::Debug, marker::PhantomData, mem::ManuallyDrop, ops::Index, ptr::NonNull};
 
 use oxc_allocator::Allocator;
-use oxc_ast::ast::Program;
+use oxc_ast::ast::{FormalParameters, FunctionBody, Program};
 use oxc_diagnostics::OxcDiagnostic;
 use oxc_parser::{Parser, ParserReturn};
 use oxc_semantic::{SemanticBuilder, SemanticBuilderReturn};
 use oxc_span::SourceType;
 
+use super::script::ScriptIdentifier;
 use crate::{
     ecmascript::{
+        builtins::ECMAScriptFunction,
         execution::Agent,
-        types::{HeapString, String},
+        types::{HeapString, String, StringHeapData},
     },
-    engine::context::NoGcScope,
+    engine::{context::NoGcScope, Executable, FunctionNodes},
     heap::{
-        indexes::BaseIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, WorkQueues,
+        indexes::{BaseIndex, ECMAScriptFunctionIndex},
+        CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, OwnedAllocations, SnapshotData,
+        SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
     },
 };
 
@@ -119,7 +132,9 @@ impl SourceCode {
         let program = unsafe { std::mem::transmute::<Program, Program<'static>>(program) };
         let source_code = agent.heap.create(SourceCodeHeapData {
             source: source.unbind(),
-            allocator,
+            source_type,
+            allocator: Some(allocator),
+            dependents: Vec::new(),
         });
 
         Ok((program, source_code))
@@ -129,6 +144,107 @@ impl SourceCode {
         agent[agent[self].source].as_str()
     }
 
+    /// Returns true if the parsed code of the source code was not discarded.
+    pub(crate) fn is_parsed(self, agent: &Agent) -> bool {
+        agent[self].allocator.is_some()
+    }
+
+    /// Parses the source code again if its parsed code was discarded, and
+    /// points its dependents at the new parsed code.
+    ///
+    /// This must be called before using the parsed code of a source code that
+    /// is not being run by an execution context on the stack. Native code
+    /// borrowing parsed code should use [`SourceCode::parsed_code`] instead.
+    pub(crate) fn ensure_parsed(self, agent: &mut Agent) {
+        if self.is_parsed(agent) {
+            return;
+        }
+        let source_type = agent[self].source_type;
+        // SAFETY: The source string is kept alive by the SourceCode, and the
+        // string's buffer does not move when the heap strings vector does.
+        let source_text =
+            unsafe { std::mem::transmute::<&str, &'static str>(self.get_source_text(agent)) };
+        let (allocator, program) =
+            parse_program(source_text, source_type).expect("Source code could not be parsed again");
+        let nodes = FunctionNodes::of_program(&program);
+        let mut program = Some(program);
+        let Heap {
+            source_codes,
+            ecmascript_functions,
+            executables,
+            scripts,
+            owned_bytes,
+            ..
+        } = &mut agent.heap;
+        let data = source_codes[self.get_index()].as_mut().unwrap();
+        data.allocator = Some(allocator);
+        *owned_bytes += data.owned_bytes();
+        for dependent in data.dependents.iter() {
+            // The source text is parsed the same way every time, so all nodes
+            // are always found again.
+            let relinked = match *dependent {
+                ParsedCodeDependent::Function(function) => ecmascript_functions
+                    .get_mut(function.get_index())
+                    .and_then(Option::as_mut)
+                    .and_then(|heap_data| heap_data.ecmascript_function.relink_parsed_code(&nodes)),
+                ParsedCodeDependent::Executable(executable) => executables
+                    .get_mut(executable.get_index())
+                    .and_then(|heap_data| heap_data.relink_parsed_code(&nodes)),
+                ParsedCodeDependent::Script(script) => scripts
+                    .get_mut(script.into_index())
+                    .and_then(Option::as_mut)
+                    .zip(program.take())
+                    .map(|(script, program)| {
+                        script.ecmascript_code = ManuallyDrop::new(program);
+                    }),
+            };
+            debug_assert!(
+                relinked.is_some(),
+                "{dependent:?} not found in the parsed code"
+            );
+        }
+    }
+
+    /// Borrow the parsed code of the source code, parsing it again first if it
+    /// was discarded. Parsed code is only discarded during garbage
+    /// collection, so the borrow is valid for as long as the garbage
+    /// collector cannot run.
+    pub(crate) fn parsed_code<'a>(
+        self,
+        agent: &mut Agent,
+        _gc: NoGcScope<'a, '_>,
+    ) -> ParsedCode<'a> {
+        self.ensure_parsed(agent);
+        ParsedCode {
+            source_code: self,
+            _gc: PhantomData,
+        }
+    }
+
+    /// List heap data referring to the parsed code of the source code, so
+    /// that it is pointed at the new parsed code if the source code is parsed
+    /// again.
+    pub(crate) fn add_dependent(self, heap: &mut Heap, dependent: ParsedCodeDependent) {
+        heap.source_codes[self.get_index()]
+            .as_mut()
+            .expect("SourceCode slot empty")
+            .dependents
+            .push(dependent);
+    }
+
+    /// Remove heap data that was dropped before garbage collection from the
+    /// dependents of the source code.
+    pub(crate) fn remove_dependent(self, heap: &mut Heap, dependent: ParsedCodeDependent) {
+        let dependents = &mut heap.source_codes[self.get_index()]
+            .as_mut()
+            .expect("SourceCode slot empty")
+            .dependents;
+        // Dropped heap data is the latest allocated, so it is found last.
+        if let Some(index) = dependents.iter().rposition(|entry| *entry == dependent) {
+            dependents.remove(index);
+        }
+    }
+
     pub(crate) fn get_index(self) -> usize {
         self.0.into_index()
     }
@@ -141,17 +257,234 @@ pub(crate) struct SourceCodeHeapData {
     /// string was small-string optimised and on the stack, then those
     /// references would necessarily and definitely be invalid.
     source: HeapString<'static>,
-    /// The arena that contains the parsed data of the eval source.
-    allocator: NonNull<Allocator>,
+    /// The type the source was parsed as.
+    source_type: SourceType,
+    /// The arena that contains the parsed data of the eval source. None if
+    /// the parsed data was discarded.
+    allocator: Option<NonNull<Allocator>>,
+    /// The heap data referring to the parsed data. These are weak references:
+    /// Dependents keep their source code alive but not the other way around.
+    dependents: Vec<ParsedCodeDependent>,
+}
+
+/// Heap data referring to nodes in the parsed code of a source code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ParsedCodeDependent {
+    Function(ECMAScriptFunction<'static>),
+    Executable(Executable),
+    Script(ScriptIdentifier),
+}
+
+/// A borrow of the parsed code of a source code, valid while garbage
+/// collection cannot run.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ParsedCode<'a> {
+    source_code: SourceCode,
+    _gc: PhantomData<&'a ()>,
+}
+
+impl<'a> ParsedCode<'a> {
+    /// Borrow the formal parameters and body of a function of the source
+    /// code.
+    pub(crate) fn function(
+        self,
+        agent: &Agent,
+        function: ECMAScriptFunction,
+    ) -> (&'a FormalParameters<'static>, &'a FunctionBody<'static>) {
+        let data = &agent[function].ecmascript_function;
+        debug_assert_eq!(data.source_code, self.source_code);
+        // SAFETY: The function's nodes are in the parsed code of its source
+        // code, which was parsed and cannot be discarded or garbage collected
+        // during 'a.
+        unsafe {
+            (
+                data.formal_parameters.as_ref(),
+                data.ecmascript_code.as_ref(),
+            )
+        }
+    }
 }
 
 unsafe impl Send for SourceCodeHeapData {}
 
+impl SourceCodeHeapData {
+    /// Drop the parsed code of the source code.
+    ///
+    /// ### Safety
+    ///
+    /// The parsed code must not be used until the source code is parsed again
+    /// with [`SourceCode::ensure_parsed`].
+    unsafe fn discard_parsed_code(&mut self) {
+        if let Some(mut allocator) = self.allocator.take() {
+            // SAFETY: The caller guarantees that the parsed code is not used.
+            drop(unsafe { Box::from_raw(allocator.as_mut()) });
+        }
+    }
+
+    /// Drop the dependents that were not marked by garbage collection.
+    pub(crate) fn retain_marked_dependents(
+        &mut self,
+        ecmascript_functions: &[bool],
+        executables: &[bool],
+        scripts: &[bool],
+    ) {
+        self.dependents.retain(|dependent| match *dependent {
+            ParsedCodeDependent::Function(function) => ecmascript_functions[function.get_index()],
+            ParsedCodeDependent::Executable(executable) => executables[executable.get_index()],
+            ParsedCodeDependent::Script(script) => scripts[script.into_index()],
+        });
+    }
+
+    /// Write the source code into a startup snapshot. The parsed code and its
+    /// dependents are not written: The source code is parsed again when the
+    /// snapshot is read.
+    pub(crate) fn serialize_snapshot(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            source,
+            source_type,
+            allocator: _,
+            dependents: _,
+        } = self;
+        source.serialize(writer)?;
+        source_type.is_module().serialize(writer)?;
+        source_type.is_typescript().serialize(writer)
+    }
+
+    /// Read a source code from a startup snapshot and parse it again. The
+    /// parsed Program is registered with the reader so that Scripts and
+    /// functions can find their parsed code.
+    pub(crate) fn deserialize_snapshot(
+        reader: &mut SnapshotReader,
+        strings: &[Option<StringHeapData>],
+        index: usize,
+    ) -> SnapshotResult<Self> {
+        let source = HeapString::deserialize(reader)?;
+        let source_type = if bool::deserialize(reader)? {
+            SourceType::default().with_module(true)
+        } else {
+            SourceType::default().with_script(true)
+        };
+        let source_type = source_type.with_typescript(bool::deserialize(reader)?);
+        let source_text = strings
+            .get(source.get_index())
+            .and_then(Option::as_ref)
+            .and_then(|data| data.as_wtf8().as_str())
+            .ok_or(SnapshotError::Malformed)?;
+        // SAFETY: The source string is kept alive by the SourceCode, and the
+        // string's buffer does not move when the heap strings vector does.
+        let source_text = unsafe { std::mem::transmute::<&str, &'static str>(source_text) };
+        // The source text parsed when the snapshot was created.
+        let (allocator, program) =
+            parse_program(source_text, source_type).ok_or(SnapshotError::Incompatible)?;
+        reader.add_program(index, program);
+        Ok(Self {
+            source,
+            source_type,
+            allocator: Some(allocator),
+            dependents: Vec::new(),
+        })
+    }
+}
+
+/// List the Scripts and functions read from a startup snapshot as dependents
+/// of their source codes. Startup snapshots contain no bytecode.
+pub(crate) fn add_snapshot_dependents(heap: &mut Heap) -> SnapshotResult<()> {
+    let Heap {
+        source_codes,
+        scripts,
+        ecmascript_functions,
+        ..
+    } = heap;
+    let scripts = scripts.iter().enumerate().filter_map(|(index, script)| {
+        let script = script.as_ref()?;
+        let dependent = ParsedCodeDependent::Script(ScriptIdentifier::from_index(index));
+        Some((script.source_code, dependent))
+    });
+    let functions = ecmascript_functions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, function)| {
+            let function = function.as_ref()?;
+            let dependent = ParsedCodeDependent::Function(ECMAScriptFunction::from(
+                ECMAScriptFunctionIndex::from_index(index),
+            ));
+            Some((function.ecmascript_function.source_code, dependent))
+        });
+    for (source_code, dependent) in scripts.chain(functions) {
+        source_codes
+            .get_mut(source_code.get_index())
+            .and_then(Option::as_mut)
+            .ok_or(SnapshotError::Malformed)?
+            .dependents
+            .push(dependent);
+    }
+    Ok(())
+}
+
+/// Parse source text that parsed without errors before into a new arena. The
+/// returned Program refers to the arena, which the caller must keep alive for
+/// as long as the Program or any of its nodes are used.
+fn parse_program(
+    source_text: &'static str,
+    source_type: SourceType,
+) -> Option<(NonNull<Allocator>, Program<'static>)> {
+    let mut allocator = NonNull::from(Box::leak(Box::default()));
+    // SAFETY: Parser is dropped before allocator.
+    let parser = Parser::new(unsafe { allocator.as_mut() }, source_text, source_type);
+    let ParserReturn {
+        errors, program, ..
+    } = parser.parse();
+    if !errors.is_empty() {
+        // Drop program before dropping allocator.
+        #[allow(clippy::drop_non_drop)]
+        drop(program);
+        // SAFETY: No references to allocator exist anymore. It is safe to
+        // drop it.
+        drop(unsafe { Box::from_raw(allocator.as_mut()) });
+        return None;
+    }
+    // SAFETY: The Program refers to the allocator, which is returned to the
+    // caller.
+    let program = unsafe { std::mem::transmute::<Program, Program<'static>>(program) };
+    Some((allocator, program))
+}
+
+/// Drop the parsed code of all source codes that are not being run by an
+/// execution context on the stack. Native code may hold references to the
+/// parsed code of the source codes being run, but all other uses of parsed
+/// code go through [`SourceCode::ensure_parsed`].
+pub(crate) fn discard_parsed_code(agent: &mut Agent) {
+    let mut is_running = vec![false; agent.heap.source_codes.len()];
+    for context in agent.execution_context_stack.iter() {
+        if let Some(code) = &context.ecmascript_code {
+            is_running[code.source_code.get_index()] = true;
+        }
+    }
+    for (data, is_running) in agent.heap.source_codes.iter_mut().zip(is_running) {
+        if let (Some(data), false) = (data, is_running) {
+            // SAFETY: The source code is not being run, so its parsed code is
+            // only used after it is parsed again.
+            unsafe { data.discard_parsed_code() };
+        }
+    }
+}
+
+/// Parse all source codes whose parsed code was discarded.
+pub(crate) fn ensure_all_parsed(agent: &mut Agent) {
+    for index in 0..agent.heap.source_codes.len() {
+        if agent.heap.source_codes[index].is_some() {
+            SourceCode(SourceCodeIndex::from_index(index)).ensure_parsed(agent);
+        }
+    }
+}
+
 impl Debug for SourceCodeHeapData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SourceCodeHeapData")
             .field("source", &self.source)
+            .field("source_type", &self.source_type)
             .field("allocator", &"[binary data]")
+            .field("dependents", &self.dependents)
             .finish()
     }
 }
@@ -160,7 +493,7 @@ impl Drop for SourceCodeHeapData {
     fn drop(&mut self) {
         // SAFETY: All references to this SourceCode should have been dropped
         // before we drop this.
-        drop(unsafe { Box::from_raw(self.allocator.as_mut()) });
+        unsafe { self.discard_parsed_code() };
     }
 }
 
@@ -179,16 +512,32 @@ impl Index<SourceCode> for Agent {
 
 impl CreateHeapData<SourceCodeHeapData, SourceCode> for Heap {
     fn create(&mut self, data: SourceCodeHeapData) -> SourceCode {
+        self.owned_bytes += data.owned_bytes();
         self.source_codes.push(Some(data));
         SourceCode(SourceCodeIndex::last(&self.source_codes))
     }
 }
 
+impl OwnedAllocations for SourceCodeHeapData {
+    fn owned_bytes(&self) -> usize {
+        let parsed_code = self.allocator.map_or(0, |allocator| {
+            // SAFETY: The allocator is uniquely owned by the SourceCode and
+            // only dropped together with it or when the parsed code is
+            // discarded.
+            unsafe { allocator.as_ref() }.allocated_bytes()
+        });
+        parsed_code + self.dependents.capacity() * std::mem::size_of::<ParsedCodeDependent>()
+    }
+}
+
 impl HeapMarkAndSweep for SourceCodeHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
             source,
+            source_type: _,
             allocator: _,
+            // Dependents are not kept alive by their source code.
+            dependents: _,
         } = self;
         source.mark_values(queues);
     }
@@ -196,9 +545,18 @@ impl HeapMarkAndSweep for SourceCodeHeapData {
     fn sweep_values(&mut self, compactions: &CompactionLists) {
         let Self {
             source,
+            source_type: _,
             allocator: _,
+            dependents,
         } = self;
         source.sweep_values(compactions);
+        for dependent in dependents.iter_mut() {
+            match dependent {
+                ParsedCodeDependent::Function(function) => function.sweep_values(compactions),
+                ParsedCodeDependent::Executable(executable) => executable.sweep_values(compactions),
+                ParsedCodeDependent::Script(script) => script.sweep_values(compactions),
+            }
+        }
     }
 }
 
@@ -211,3 +569,211 @@ impl HeapMarkAndSweep for SourceCode {
         compactions.source_codes.shift_index(&mut self.0);
     }
 }
+
+impl SnapshotData for SourceCode {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, collections::VecDeque};
+
+    use super::{ParsedCodeDependent, SourceCode};
+    use crate::{
+        ecmascript::{
+            execution::agent::{HostHooks, Job, Options},
+            types::Value,
+        },
+        engine::FunctionNodes,
+        test_utils::{eval_script, TestAgent},
+    };
+
+    fn discarding_options() -> Options {
+        Options {
+            discard_parsed_code: true,
+            ..Default::default()
+        }
+    }
+
+    /// Get the source code of the function that the expression evaluates to.
+    /// Source codes move during garbage collection, so this is looked up
+    /// again after every collection.
+    fn source_code_of(agent: &mut TestAgent, expression: &str) -> SourceCode {
+        agent.run(|agent, gc| {
+            let Value::ECMAScriptFunction(function) = eval_script(agent, expression, gc).unwrap()
+            else {
+                panic!("{expression} is not an ECMAScript function");
+            };
+            agent[function].ecmascript_function.source_code
+        })
+    }
+
+    fn is_parsed(agent: &mut TestAgent, expression: &str) -> bool {
+        let source_code = source_code_of(agent, expression);
+        agent.run(|agent, _| source_code.is_parsed(agent))
+    }
+
+    fn function_dependents(agent: &mut TestAgent, expression: &str) -> usize {
+        let source_code = source_code_of(agent, expression);
+        agent.run(|agent, _| {
+            agent[source_code]
+                .dependents
+                .iter()
+                .filter(|dependent| matches!(dependent, ParsedCodeDependent::Function(_)))
+                .count()
+        })
+    }
+
+    #[test]
+    fn idle_source_code_is_discarded_and_parsed_again() {
+        let mut agent = TestAgent::with_options(discarding_options());
+        agent.eval(
+            "function add(a, b) { return a + b; }
+            class Point {
+                constructor(x) { this.value = x; }
+                get x() { return this.value; }
+                static of(x) { return new Point(x); }
+                scaled(factor) { return new Point(this.x * factor); }
+            }
+            var object = { method() { return 'method'; }, arrow: () => 'arrow' };
+            function makeCounter() { let n = 0; return () => ++n; }
+            var counter = makeCounter();
+            counter();",
+        );
+        assert!(is_parsed(&mut agent, "add"));
+        agent.gc();
+        assert!(!is_parsed(&mut agent, "add"));
+        // Compiled and never compiled functions of the discarded source code
+        // both keep working.
+        assert_eq!(
+            agent.eval(
+                "add(1, 2) + Point.of(2).scaled(3).x + object.method() + object.arrow()
+                + counter() + makeCounter()()"
+            ),
+            "9methodarrow21"
+        );
+        assert!(is_parsed(&mut agent, "add"));
+    }
+
+    #[test]
+    fn parsed_code_is_kept_by_default() {
+        let mut agent = TestAgent::new();
+        agent.eval("function add(a, b) { return a + b; }");
+        agent.gc();
+        assert!(is_parsed(&mut agent, "add"));
+        assert_eq!(agent.eval("add(1, 2)"), "3");
+    }
+
+    #[test]
+    fn suspended_generator_resumes_after_discard() {
+        let mut agent = TestAgent::with_options(discarding_options());
+        assert_eq!(
+            agent.eval(
+                "function* numbers() {
+                    const double = x => x * 2;
+                    yield 1;
+                    yield double(21);
+                    yield function () { return 3; }();
+                }
+                var iterator = numbers();
+                iterator.next().value"
+            ),
+            "1"
+        );
+        agent.gc();
+        assert!(!is_parsed(&mut agent, "numbers"));
+        assert_eq!(
+            agent.eval(
+                "[iterator.next().value, iterator.next().value, iterator.next().done].join()"
+            ),
+            "42,3,true"
+        );
+        assert!(is_parsed(&mut agent, "numbers"));
+    }
+
+    #[derive(Default)]
+    struct QueueingHostHooks {
+        promise_jobs: RefCell<VecDeque<Job>>,
+    }
+
+    impl std::fmt::Debug for QueueingHostHooks {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("QueueingHostHooks")
+                .field("promise_jobs", &self.promise_jobs.borrow().len())
+                .finish()
+        }
+    }
+
+    impl HostHooks for QueueingHostHooks {
+        fn enqueue_promise_job(&self, job: Job) {
+            self.promise_jobs.borrow_mut().push_back(job);
+        }
+    }
+
+    impl QueueingHostHooks {
+        fn run_jobs(&self, agent: &mut TestAgent) {
+            agent.run(|agent, mut gc| loop {
+                let Some(job) = self.promise_jobs.borrow_mut().pop_front() else {
+                    break;
+                };
+                job.run(agent, gc.reborrow()).unwrap();
+            });
+        }
+    }
+
+    #[test]
+    fn suspended_async_function_resumes_after_discard() {
+        let hooks: &'static QueueingHostHooks = Box::leak(Box::default());
+        let mut agent = TestAgent::with_host_hooks(discarding_options(), hooks);
+        agent.eval(
+            "function increment(x) { return x + 1; }
+            var resolve;
+            var result;
+            async function f() {
+                const value = await new Promise(r => { resolve = r; });
+                result = increment(value) + (function () { return 0; })();
+            }
+            f();",
+        );
+        hooks.run_jobs(&mut agent);
+        agent.gc();
+        assert!(!is_parsed(&mut agent, "f"));
+        agent.eval("resolve(41);");
+        hooks.run_jobs(&mut agent);
+        assert_eq!(agent.eval("result"), "42");
+        assert!(is_parsed(&mut agent, "f"));
+    }
+
+    #[test]
+    fn dependents_do_not_keep_functions_alive() {
+        let mut agent = TestAgent::new();
+        agent.eval("var functions = []; for (let i = 0; i < 10; i++) functions.push(() => i);");
+        assert_eq!(function_dependents(&mut agent, "functions[0]"), 10);
+        agent.eval("functions.length = 1;");
+        agent.gc();
+        assert_eq!(function_dependents(&mut agent, "functions[0]"), 1);
+        assert_eq!(agent.eval("functions[0]()"), "0");
+    }
+
+    #[test]
+    fn missing_nodes_leave_function_unchanged() {
+        let mut agent = TestAgent::new();
+        agent.eval("function f() { return 1; }");
+        agent.run(|agent, gc| {
+            let Value::ECMAScriptFunction(function) = eval_script(agent, "f", gc).unwrap() else {
+                unreachable!()
+            };
+            let relinked = agent[function]
+                .ecmascript_function
+                .relink_parsed_code(&FunctionNodes::default());
+            assert!(relinked.is_none());
+        });
+        assert_eq!(agent.eval("f()"), "1");
+    }
+}
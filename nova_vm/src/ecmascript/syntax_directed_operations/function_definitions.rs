@@ -35,6 +35,7 @@ use crate::{
             Agent, ECMAScriptCodeEvaluationState, EnvironmentIndex, JsResult,
             PrivateEnvironmentIndex, ProtoIntrinsics,
         },
+        scripts_and_modules::source_code::SourceCode,
         types::{
             IntoFunction, IntoObject, IntoValue, Object, PropertyDescriptor, PropertyKey, String,
             Value, BUILTIN_STRING_MEMORY,
@@ -44,6 +45,7 @@ use crate::{
     heap::CreateHeapData,
 };
 use oxc_ast::ast::{self};
+use oxc_span::Span;
 
 /// ### [15.1.2 Static Semantics: ContainsExpression](https://tc39.es/ecma262/#sec-static-semantics-containsexpression)
 /// The syntax-directed operation ContainsExpression takes no arguments and returns a Boolean.
@@ -258,24 +260,27 @@ pub(crate) struct CompileFunctionBodyData<'a> {
     pub(crate) is_strict: bool,
     pub(crate) is_lexical: bool,
     pub(crate) is_concise_body: bool,
+    /// Source code and span of the function's source text, which the
+    /// function body's bytecode is cached by. None if the bytecode is cached
+    /// as part of the surrounding code, as with class constructors.
+    pub(crate) source_text: Option<(SourceCode, Span)>,
 }
 
-impl CompileFunctionBodyData<'static> {
-    fn new(agent: &mut Agent, function: ECMAScriptFunction) -> Self {
+impl<'a> CompileFunctionBodyData<'a> {
+    fn new(agent: &mut Agent, function: ECMAScriptFunction, gc: NoGcScope<'a, '_>) -> Self {
+        let source_code = agent[function].ecmascript_function.source_code;
+        let (params, body) = source_code.parsed_code(agent, gc).function(agent, function);
         let ecmascript_function = &agent[function].ecmascript_function;
-        // SAFETY: We're alive so SourceCode must be too.
-        let (params, body) = unsafe {
-            (
-                ecmascript_function.formal_parameters.as_ref(),
-                ecmascript_function.ecmascript_code.as_ref(),
-            )
-        };
         CompileFunctionBodyData {
             params,
             body,
             is_strict: ecmascript_function.strict,
             is_lexical: ecmascript_function.this_mode == ThisMode::Lexical,
             is_concise_body: ecmascript_function.is_concise_arrow_function,
+            source_text: Some((
+                ecmascript_function.source_code,
+                ecmascript_function.source_text,
+            )),
         }
     }
 }
@@ -298,7 +303,7 @@ pub(crate) fn evaluate_function_body(
     let exe = if let Some(exe) = agent[function_object].compiled_bytecode {
         exe
     } else {
-        let data = CompileFunctionBodyData::new(agent, function_object);
+        let data = CompileFunctionBodyData::new(agent, function_object, gc.nogc());
         let exe = Executable::compile_function_body(agent, data, gc.nogc());
         agent[function_object].compiled_bytecode = Some(exe);
         exe
@@ -328,7 +333,7 @@ pub(crate) fn evaluate_async_function_body<'a>(
     let exe = if let Some(exe) = agent[function_object].compiled_bytecode {
         exe
     } else {
-        let data = CompileFunctionBodyData::new(agent, function_object);
+        let data = CompileFunctionBodyData::new(agent, function_object, gc.nogc());
         let exe = Executable::compile_function_body(agent, data, gc.nogc());
         agent[function_object].compiled_bytecode = Some(exe);
         exe
@@ -415,8 +420,7 @@ pub(crate) fn evaluate_generator_body(
     };
 
     // 4. Perform GeneratorStart(G, FunctionBody).
-    // SAFETY: We're alive so SourceCode must be too.
-    let data = CompileFunctionBodyData::new(agent, scoped_function_object.get(agent));
+    let data = CompileFunctionBodyData::new(agent, scoped_function_object.get(agent), gc);
     let executable = Executable::compile_function_body(agent, data, gc);
     agent[generator].generator_state = Some(GeneratorState::Suspended(SuspendedGeneratorState {
         vm_or_args: VmOrArguments::Arguments(arguments_list.0.into()),
@@ -465,7 +469,7 @@ pub(crate) fn evaluate_async_generator_body(
     let executable = if let Some(exe) = agent[function_object].compiled_bytecode {
         exe
     } else {
-        let data = CompileFunctionBodyData::new(agent, function_object);
+        let data = CompileFunctionBodyData::new(agent, function_object, gc.nogc());
         let exe = Executable::compile_function_body(agent, data, gc.nogc());
         agent[function_object].compiled_bytecode = Some(exe);
         exe
@@ -4,15 +4,22 @@
 
 use std::ops::Deref;
 
-use oxc_ast::ast::{
-    BindingIdentifier, BlockStatement, Class, Declaration, ExportDefaultDeclarationKind,
-    ForStatementInit, ForStatementLeft, Function, FunctionBody, LabeledStatement, Program,
-    Statement, StaticBlock, SwitchCase, SwitchStatement, VariableDeclaration,
-    VariableDeclarationKind, VariableDeclarator,
+use ahash::AHashMap;
+use oxc_ast::{
+    ast::{
+        ArrowFunctionExpression, BindingIdentifier, BlockStatement, Class, Declaration,
+        ExportDefaultDeclarationKind, ForStatementInit, ForStatementLeft, FormalParameters,
+        Function, FunctionBody, IdentifierReference, LabeledStatement, Program, Statement,
+        StaticBlock, SwitchCase, SwitchStatement, VariableDeclaration, VariableDeclarationKind,
+        VariableDeclarator, WithStatement,
+    },
+    visit::walk,
+    Visit,
 };
 use oxc_ecmascript::BoundNames;
 
 use oxc_span::Atom;
+use oxc_syntax::scope::ScopeFlags;
 
 /// ### [8.2.4 Static Semantics: LexicallyDeclaredNames](https://tc39.es/ecma262/#sec-static-semantics-lexicallydeclarednames)
 ///
@@ -1466,3 +1473,122 @@ impl<'a> TopLevelVarScopedDeclarations<'a> for LabeledStatement<'a> {
         }
     }
 }
+
+/// Usage of a single name within a function, as found by
+/// [`function_name_usage`].
+#[derive(Debug, Default)]
+pub(crate) struct NameUsage {
+    /// Number of declarations of the name in the function itself.
+    pub(crate) declarations: u32,
+    /// True if the name is referenced from a nested function or class.
+    pub(crate) captured: bool,
+    /// Source position of the earliest reference to the name in the function
+    /// itself.
+    pub(crate) first_reference: Option<u32>,
+}
+
+/// Usage of all names declared or referenced within a function.
+#[derive(Debug, Default)]
+pub(crate) struct FunctionNameUsage {
+    pub(crate) names: AHashMap<Box<str>, NameUsage>,
+    /// True if the function contains `with` or a possible direct `eval`, in
+    /// which case any name may be resolved dynamically.
+    pub(crate) has_dynamic_scope: bool,
+}
+
+/// Find the declarations and references of every name in a function's
+/// parameters and body, and whether they are captured by nested functions
+/// or classes.
+///
+/// Declarations inside nested functions and classes are not counted, but
+/// references from them mark the name as captured.
+pub(crate) fn function_name_usage(
+    params: &FormalParameters,
+    body: &FunctionBody,
+) -> FunctionNameUsage {
+    let mut analysis = NameUsageAnalysis {
+        usage: FunctionNameUsage::default(),
+        function_depth: 0,
+    };
+    analysis.visit_formal_parameters(params);
+    analysis.visit_function_body(body);
+    analysis.usage
+}
+
+struct NameUsageAnalysis {
+    usage: FunctionNameUsage,
+    /// Depth of nested functions and classes being visited.
+    function_depth: u32,
+}
+
+impl NameUsageAnalysis {
+    fn name(&mut self, name: &str) -> &mut NameUsage {
+        let names = &mut self.usage.names;
+        if !names.contains_key(name) {
+            names.insert(name.into(), NameUsage::default());
+        }
+        names.get_mut(name).unwrap()
+    }
+
+    fn declare(&mut self, identifier: &BindingIdentifier) {
+        self.name(&identifier.name).declarations += 1;
+    }
+}
+
+impl<'a> Visit<'a> for NameUsageAnalysis {
+    fn visit_binding_identifier(&mut self, it: &BindingIdentifier<'a>) {
+        if self.function_depth == 0 {
+            self.declare(it);
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, it: &IdentifierReference<'a>) {
+        if it.name == "eval" {
+            // Any call through an `eval` reference may be a direct eval.
+            self.usage.has_dynamic_scope = true;
+        }
+        let nested = self.function_depth > 0;
+        let start = it.span.start;
+        let usage = self.name(&it.name);
+        if nested {
+            usage.captured = true;
+        } else if usage.first_reference.map_or(true, |first| start < first) {
+            usage.first_reference = Some(start);
+        }
+    }
+
+    fn visit_with_statement(&mut self, it: &WithStatement<'a>) {
+        self.usage.has_dynamic_scope = true;
+        walk::walk_with_statement(self, it);
+    }
+
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        // The name of a function declaration is bound in the enclosing scope.
+        if self.function_depth == 0 && it.is_declaration() {
+            if let Some(id) = &it.id {
+                self.declare(id);
+            }
+        }
+        self.function_depth += 1;
+        walk::walk_function(self, it, flags);
+        self.function_depth -= 1;
+    }
+
+    fn visit_arrow_function_expression(&mut self, it: &ArrowFunctionExpression<'a>) {
+        self.function_depth += 1;
+        walk::walk_arrow_function_expression(self, it);
+        self.function_depth -= 1;
+    }
+
+    fn visit_class(&mut self, it: &Class<'a>) {
+        // The name of a class declaration is bound in the enclosing scope.
+        if self.function_depth == 0 && it.is_declaration() {
+            if let Some(id) = &it.id {
+                self.declare(id);
+            }
+        }
+        self.function_depth += 1;
+        walk::walk_class(self, it);
+        self.function_depth -= 1;
+    }
+}
@@ -34,6 +34,7 @@ pub use object::{
     bind_property_keys, scope_property_keys, unbind_property_keys, InternalMethods, InternalSlots,
     IntoObject, Object, ObjectHeapData, OrdinaryObject, PropertyKey,
 };
+pub(crate) use object::{sweep_object_shape_roots, ObjectShape, ObjectShapeRecord};
 pub(crate) use primitive::HeapPrimitive;
 pub use primitive::Primitive;
 pub use string::{HeapString, String, StringHeapData, BUILTIN_STRINGS_LIST, BUILTIN_STRING_MEMORY};
@@ -43,8 +44,6 @@ pub use value::Value;
 pub(crate) use value::DATE_DISCRIMINANT;
 #[cfg(feature = "proposal-float16array")]
 pub(crate) use value::FLOAT_16_ARRAY_DISCRIMINANT;
-#[cfg(feature = "regexp")]
-pub(crate) use value::REGEXP_DISCRIMINANT;
 #[cfg(feature = "shared-array-buffer")]
 pub(crate) use value::SHARED_ARRAY_BUFFER_DISCRIMINANT;
 pub(crate) use value::{
@@ -59,7 +58,7 @@ pub(crate) use value::{
     INTEGER_DISCRIMINANT, ITERATOR_DISCRIMINANT, MAP_DISCRIMINANT, MAP_ITERATOR_DISCRIMINANT,
     MODULE_DISCRIMINANT, NUMBER_DISCRIMINANT, OBJECT_DISCRIMINANT, PROMISE_DISCRIMINANT,
     PROXY_DISCRIMINANT, SMALL_BIGINT_DISCRIMINANT, SMALL_STRING_DISCRIMINANT, STRING_DISCRIMINANT,
-    SYMBOL_DISCRIMINANT,
+    STRING_ITERATOR_DISCRIMINANT, SYMBOL_DISCRIMINANT,
 };
 #[cfg(feature = "array-buffer")]
 pub(crate) use value::{
@@ -69,6 +68,8 @@ pub(crate) use value::{
     UINT_16_ARRAY_DISCRIMINANT, UINT_32_ARRAY_DISCRIMINANT, UINT_8_ARRAY_DISCRIMINANT,
     UINT_8_CLAMPED_ARRAY_DISCRIMINANT,
 };
+#[cfg(feature = "regexp")]
+pub(crate) use value::{REGEXP_DISCRIMINANT, REGEXP_STRING_ITERATOR_DISCRIMINANT};
 #[cfg(feature = "set")]
 pub(crate) use value::{SET_DISCRIMINANT, SET_ITERATOR_DISCRIMINANT};
 #[cfg(feature = "weak-refs")]
@@ -5,6 +5,7 @@
 mod data;
 use super::{
     into_numeric::IntoNumeric,
+    number::radix::{big_integer_to_radix_string, integer_to_radix_string},
     numeric::Numeric,
     value::{BIGINT_DISCRIMINANT, SMALL_BIGINT_DISCRIMINANT},
     IntoPrimitive, IntoValue, Primitive, String, Value,
@@ -14,10 +15,12 @@ use crate::{
     engine::{
         context::NoGcScope,
         rootable::{HeapRootData, HeapRootRef, Rootable},
+        Scoped,
     },
     heap::{
         indexes::BigIntIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep,
-        PrimitiveHeap, WorkQueues,
+        OwnedAllocations, PrimitiveHeap, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WorkQueues,
     },
     SmallInteger,
 };
@@ -240,6 +243,16 @@ impl TryFrom<u64> for SmallBigInt {
     }
 }
 
+impl SnapshotData for SmallBigInt {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl TryFrom<&num_bigint::BigInt> for SmallBigInt {
     type Error = ();
 
@@ -291,6 +304,14 @@ impl<'a> BigInt<'a> {
         unsafe { std::mem::transmute::<Self, BigInt<'gc>>(self) }
     }
 
+    pub fn scope<'scope>(
+        self,
+        agent: &mut Agent,
+        gc: NoGcScope<'_, 'scope>,
+    ) -> Scoped<'scope, BigInt<'static>> {
+        Scoped::new(agent, self.unbind(), gc)
+    }
+
     pub const fn zero() -> Self {
         Self::SmallBigInt(SmallBigInt::zero())
     }
@@ -645,6 +666,31 @@ impl<'a> BigInt<'a> {
         )
     }
 
+    // ### [6.1.6.2.21 BigInt::toString ( x, radix )](https://tc39.es/ecma262/#sec-numeric-types-bigint-tostring)
+    pub(crate) fn to_string_radix<'gc>(
+        agent: &mut Agent,
+        x: Self,
+        radix: u32,
+        gc: NoGcScope<'gc, '_>,
+    ) -> String<'gc> {
+        if radix == 10 {
+            return Self::to_string_radix_10(agent, x, gc);
+        }
+        // 1. If x < 0ℤ, return the string-concatenation of "-" and BigInt::toString(-x, radix).
+        // 2. Return the String value consisting of the representation of x using radix radix.
+        let result = match x {
+            BigInt::SmallBigInt(x) => {
+                let x = x.into_i64();
+                integer_to_radix_string(x < 0, x.unsigned_abs(), radix)
+            }
+            BigInt::BigInt(x) => {
+                let data = &agent[x].data;
+                big_integer_to_radix_string(data.sign() == Sign::Minus, data.magnitude(), radix)
+            }
+        };
+        String::from_string(agent, result, gc)
+    }
+
     pub(crate) fn to_real(self, agent: &mut Agent) -> f64 {
         match self {
             BigInt::BigInt(heap_big_int) => {
@@ -792,6 +838,7 @@ impl IndexMut<HeapBigInt<'_>> for Vec<Option<BigIntHeapData>> {
 
 impl CreateHeapData<BigIntHeapData, BigInt<'static>> for Heap {
     fn create(&mut self, data: BigIntHeapData) -> BigInt<'static> {
+        self.owned_bytes += data.owned_bytes();
         self.bigints.push(Some(data));
         BigInt::BigInt(HeapBigInt(BigIntIndex::last(&self.bigints)))
     }
@@ -807,6 +854,16 @@ impl HeapMarkAndSweep for HeapBigInt<'static> {
     }
 }
 
+impl SnapshotData for HeapBigInt<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl Rootable for BigInt<'static> {
     type RootRepr = BigIntRootRepr;
 
@@ -4,7 +4,10 @@
 
 use std::{cmp::Ordering, hint::unreachable_unchecked};
 
-use crate::heap::{CompactionLists, HeapMarkAndSweep, WorkQueues};
+use crate::heap::{
+    CompactionLists, HeapMarkAndSweep, OwnedAllocations, SnapshotData, SnapshotReader,
+    SnapshotResult, SnapshotWriter, WorkQueues,
+};
 use num_bigint::{BigInt, Sign};
 
 #[derive(Debug, Clone)]
@@ -12,6 +15,12 @@ pub struct BigIntHeapData {
     pub(crate) data: BigInt,
 }
 
+impl OwnedAllocations for BigIntHeapData {
+    fn owned_bytes(&self) -> usize {
+        self.data.bits().div_ceil(8) as usize
+    }
+}
+
 impl HeapMarkAndSweep for BigIntHeapData {
     #[inline(always)]
     fn mark_values(&self, _queues: &mut WorkQueues) {
@@ -24,6 +33,19 @@ impl HeapMarkAndSweep for BigIntHeapData {
     }
 }
 
+impl SnapshotData for BigIntHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.data.to_signed_bytes_le().serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let bytes = Vec::<u8>::deserialize(reader)?;
+        Ok(Self {
+            data: BigInt::from_signed_bytes_le(&bytes),
+        })
+    }
+}
+
 impl PartialEq<f64> for BigIntHeapData {
     fn eq(&self, other: &f64) -> bool {
         if other.trunc() != *other {
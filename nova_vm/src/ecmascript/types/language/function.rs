@@ -18,11 +18,11 @@ use crate::engine::{context::{GcScope, NoGcScope}, Scoped, TryResult};
 use crate::{
     ecmascript::{
         builtins::{
-            bound_function::BoundFunction, control_abstraction_objects::promise_objects::promise_abstract_operations::promise_resolving_functions::BuiltinPromiseResolvingFunction, ArgumentsList, BuiltinConstructorFunction, BuiltinFunction, ECMAScriptFunction
+            bound_function::BoundFunction, control_abstraction_objects::promise_objects::promise_abstract_operations::promise_resolving_functions::BuiltinPromiseResolvingFunction, proxy::revoker::BuiltinProxyRevokerFunction, ArgumentsList, BuiltinConstructorFunction, BuiltinFunction, ECMAScriptFunction
         },
         execution::{Agent, JsResult, ProtoIntrinsics},
         types::PropertyDescriptor,
-    }, engine::rootable::{HeapRootData, HeapRootRef, Rootable}, heap::{CompactionLists, HeapMarkAndSweep, WorkQueues}
+    }, engine::rootable::{HeapRootData, HeapRootRef, Rootable}, heap::{CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues}
 };
 
 pub(crate) use data::*;
@@ -47,7 +47,7 @@ pub enum Function<'a> {
     BuiltinPromiseResolvingFunction(BuiltinPromiseResolvingFunction<'a>) =
         BUILTIN_PROMISE_RESOLVING_FUNCTION_DISCRIMINANT,
     BuiltinPromiseCollectorFunction = BUILTIN_PROMISE_COLLECTOR_FUNCTION_DISCRIMINANT,
-    BuiltinProxyRevokerFunction = BUILTIN_PROXY_REVOKER_FUNCTION,
+    BuiltinProxyRevokerFunction(BuiltinProxyRevokerFunction<'a>) = BUILTIN_PROXY_REVOKER_FUNCTION,
 }
 
 impl std::fmt::Debug for Function<'_> {
@@ -64,7 +64,9 @@ impl std::fmt::Debug for Function<'_> {
                 write!(f, "BuiltinPromiseResolvingFunction({:?})", d)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(d) => {
+                write!(f, "BuiltinProxyRevokerFunction({:?})", d)
+            }
         }
     }
 }
@@ -111,7 +113,9 @@ impl<'a> TryFrom<Object<'a>> for Function<'a> {
             Object::BuiltinPromiseCollectorFunction => {
                 Ok(Function::BuiltinPromiseCollectorFunction)
             }
-            Object::BuiltinProxyRevokerFunction => Ok(Function::BuiltinProxyRevokerFunction),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                Ok(Function::BuiltinProxyRevokerFunction(data))
+            }
             _ => Err(()),
         }
     }
@@ -132,7 +136,9 @@ impl TryFrom<Value> for Function<'_> {
                 Ok(Function::BuiltinPromiseResolvingFunction(data))
             }
             Value::BuiltinPromiseCollectorFunction => Ok(Function::BuiltinPromiseCollectorFunction),
-            Value::BuiltinProxyRevokerFunction => Ok(Function::BuiltinProxyRevokerFunction),
+            Value::BuiltinProxyRevokerFunction(data) => {
+                Ok(Function::BuiltinProxyRevokerFunction(data))
+            }
             _ => Err(()),
         }
     }
@@ -152,7 +158,9 @@ impl<'a> From<Function<'a>> for Object<'a> {
                 Object::BuiltinPromiseResolvingFunction(data.unbind())
             }
             Function::BuiltinPromiseCollectorFunction => Object::BuiltinPromiseCollectorFunction,
-            Function::BuiltinProxyRevokerFunction => Object::BuiltinProxyRevokerFunction,
+            Function::BuiltinProxyRevokerFunction(data) => {
+                Object::BuiltinProxyRevokerFunction(data.unbind())
+            }
         }
     }
 }
@@ -171,7 +179,9 @@ impl From<Function<'_>> for Value {
                 Value::BuiltinPromiseResolvingFunction(data.unbind())
             }
             Function::BuiltinPromiseCollectorFunction => Value::BuiltinPromiseCollectorFunction,
-            Function::BuiltinProxyRevokerFunction => Value::BuiltinProxyRevokerFunction,
+            Function::BuiltinProxyRevokerFunction(data) => {
+                Value::BuiltinProxyRevokerFunction(data.unbind())
+            }
         }
     }
 }
@@ -214,7 +224,7 @@ impl<'a> Function<'a> {
             Function::BuiltinGeneratorFunction => todo!(),
             Function::BuiltinConstructorFunction(_) => true,
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(_) => false,
         }
     }
 }
@@ -240,7 +250,7 @@ impl<'a> InternalSlots<'a> for Function<'a> {
             Function::BuiltinConstructorFunction(d) => agent[d].object_index,
             Function::BuiltinPromiseResolvingFunction(d) => agent[d].object_index,
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(d) => agent[d].object_index,
         }
     }
 
@@ -285,7 +295,7 @@ impl<'a> InternalMethods<'a> for Function<'a> {
             Function::BuiltinConstructorFunction(x) => x.try_get_prototype_of(agent, gc),
             Function::BuiltinPromiseResolvingFunction(x) => x.try_get_prototype_of(agent, gc),
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => x.try_get_prototype_of(agent, gc),
         }
     }
 
@@ -305,7 +315,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.try_set_prototype_of(agent, prototype, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.try_set_prototype_of(agent, prototype, gc)
+            }
         }
     }
 
@@ -318,7 +330,7 @@ impl<'a> InternalMethods<'a> for Function<'a> {
             Function::BuiltinConstructorFunction(x) => x.try_is_extensible(agent, gc),
             Function::BuiltinPromiseResolvingFunction(x) => x.try_is_extensible(agent, gc),
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => x.try_is_extensible(agent, gc),
         }
     }
 
@@ -331,7 +343,7 @@ impl<'a> InternalMethods<'a> for Function<'a> {
             Function::BuiltinConstructorFunction(x) => x.try_prevent_extensions(agent, gc),
             Function::BuiltinPromiseResolvingFunction(x) => x.try_prevent_extensions(agent, gc),
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => x.try_prevent_extensions(agent, gc),
         }
     }
 
@@ -353,7 +365,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.try_get_own_property(agent, property_key, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.try_get_own_property(agent, property_key, gc)
+            }
         }
     }
 
@@ -382,7 +396,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.try_define_own_property(agent, property_key, property_descriptor, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.try_define_own_property(agent, property_key, property_descriptor, gc)
+            }
         }
     }
 
@@ -402,7 +418,7 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.try_has_property(agent, property_key, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => x.try_has_property(agent, property_key, gc),
         }
     }
 
@@ -424,7 +440,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.internal_has_property(agent, property_key, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.internal_has_property(agent, property_key, gc)
+            }
         }
     }
 
@@ -445,7 +463,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.try_get(agent, property_key, receiver, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.try_get(agent, property_key, receiver, gc)
+            }
         }
     }
 
@@ -468,7 +488,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.internal_get(agent, property_key, receiver, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.internal_get(agent, property_key, receiver, gc)
+            }
         }
     }
 
@@ -492,7 +514,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.try_set(agent, property_key, value, receiver, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.try_set(agent, property_key, value, receiver, gc)
+            }
         }
     }
 
@@ -520,7 +544,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.internal_set(agent, property_key, value, receiver, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.internal_set(agent, property_key, value, receiver, gc)
+            }
         }
     }
 
@@ -538,7 +564,7 @@ impl<'a> InternalMethods<'a> for Function<'a> {
             Function::BuiltinConstructorFunction(x) => x.try_delete(agent, property_key, gc),
             Function::BuiltinPromiseResolvingFunction(x) => x.try_delete(agent, property_key, gc),
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => x.try_delete(agent, property_key, gc),
         }
     }
 
@@ -555,7 +581,7 @@ impl<'a> InternalMethods<'a> for Function<'a> {
             Function::BuiltinConstructorFunction(x) => x.try_own_property_keys(agent, gc),
             Function::BuiltinPromiseResolvingFunction(x) => x.try_own_property_keys(agent, gc),
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => x.try_own_property_keys(agent, gc),
         }
     }
 
@@ -582,7 +608,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.internal_call(agent, this_argument, arguments_list, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.internal_call(agent, this_argument, arguments_list, gc)
+            }
         }
     }
 
@@ -611,7 +639,9 @@ impl<'a> InternalMethods<'a> for Function<'a> {
                 x.internal_construct(agent, arguments_list, new_target, gc)
             }
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => {
+                x.internal_construct(agent, arguments_list, new_target, gc)
+            }
         }
     }
 }
@@ -626,7 +656,7 @@ impl HeapMarkAndSweep for Function<'static> {
             Function::BuiltinConstructorFunction(x) => x.mark_values(queues),
             Function::BuiltinPromiseResolvingFunction(x) => x.mark_values(queues),
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => x.mark_values(queues),
         }
     }
 
@@ -639,11 +669,21 @@ impl HeapMarkAndSweep for Function<'static> {
             Function::BuiltinConstructorFunction(x) => x.sweep_values(compactions),
             Function::BuiltinPromiseResolvingFunction(x) => x.sweep_values(compactions),
             Function::BuiltinPromiseCollectorFunction => todo!(),
-            Function::BuiltinProxyRevokerFunction => todo!(),
+            Function::BuiltinProxyRevokerFunction(x) => x.sweep_values(compactions),
         }
     }
 }
 
+impl SnapshotData for Function<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        Value::from(*self).serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Self::try_from(Value::deserialize(reader)?).map_err(|_| SnapshotError::Malformed)
+    }
+}
+
 impl Function<'_> {
     pub fn call(
         self,
@@ -674,7 +714,9 @@ impl Rootable for Function<'_> {
             Self::BuiltinPromiseCollectorFunction => {
                 Err(HeapRootData::BuiltinPromiseCollectorFunction)
             }
-            Self::BuiltinProxyRevokerFunction => Err(HeapRootData::BuiltinProxyRevokerFunction),
+            Self::BuiltinProxyRevokerFunction(d) => {
+                Err(HeapRootData::BuiltinProxyRevokerFunction(d.unbind()))
+            }
         }
     }
 
@@ -711,7 +753,9 @@ impl Rootable for Function<'_> {
             HeapRootData::BuiltinPromiseCollectorFunction => {
                 Some(Self::BuiltinPromiseCollectorFunction)
             }
-            HeapRootData::BuiltinProxyRevokerFunction => Some(Self::BuiltinProxyRevokerFunction),
+            HeapRootData::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function) => Some(
+                Self::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function),
+            ),
             // Note: We use a catch-all here as we expect function variant
             // additions to be rare.
             _ => None,
@@ -10,8 +10,8 @@ use crate::{
         },
         execution::{Agent, JsResult},
         types::{
-            language::IntoObject, InternalMethods, InternalSlots, ObjectHeapData, OrdinaryObject,
-            PropertyDescriptor, PropertyKey, String, Value, BUILTIN_STRING_MEMORY,
+            language::IntoObject, InternalMethods, InternalSlots, ObjectHeapData, ObjectShape,
+            OrdinaryObject, PropertyDescriptor, PropertyKey, String, Value, BUILTIN_STRING_MEMORY,
         },
     },
     engine::{
@@ -65,16 +65,14 @@ pub(crate) fn function_create_backing_object<'a>(
             configurable: true,
         },
     };
-    let (keys, values) = agent
-        .heap
-        .elements
-        .create_object_entries(&[length_entry, name_entry]);
-    let backing_object = agent.heap.create(ObjectHeapData {
-        extensible: true,
+    let entries = [length_entry, name_entry];
+    let shape = ObjectShape::from_keys(
+        &mut agent.heap,
         prototype,
-        keys,
-        values,
-    });
+        entries.iter().map(|entry| entry.key),
+    );
+    let values = agent.heap.elements.create_object_entry_values(&entries);
+    let backing_object = agent.heap.create(ObjectHeapData::new(true, shape, values));
     func.set_backing_object(agent, backing_object);
     backing_object
 }
@@ -3,6 +3,7 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 mod data;
+pub(crate) mod radix;
 
 use std::ops::{Index, IndexMut};
 
@@ -22,7 +23,7 @@ use crate::{
     },
     heap::{
         indexes::NumberIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep,
-        PrimitiveHeap, WorkQueues,
+        PrimitiveHeap, SnapshotData, SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
     },
     SmallInteger,
 };
@@ -1375,6 +1376,44 @@ impl<'a> Number<'a> {
         }
     }
 
+    /// ### [6.1.6.1.20 Number::toString ( x, radix )](https://tc39.es/ecma262/#sec-numeric-types-number-tostring)
+    ///
+    /// For radices other than 10 the digits are chosen by an
+    /// implementation-approximated generalisation of the radix 10 algorithm.
+    pub(crate) fn to_string_radix<'gc>(
+        agent: &mut Agent,
+        x: Self,
+        radix: u32,
+        gc: NoGcScope<'gc, '_>,
+    ) -> String<'gc> {
+        if radix == 10 {
+            return Self::to_string_radix_10(agent, x, gc);
+        }
+        if let Number::Integer(x) = x {
+            let x = x.into_i64();
+            let result = radix::integer_to_radix_string(x < 0, x.unsigned_abs(), radix);
+            return String::from_string(agent, result, gc);
+        }
+        let x = x.into_f64(agent);
+        // 1. If x is NaN, return "NaN".
+        if x.is_nan() {
+            return String::from_static_str(agent, "NaN", gc);
+        }
+        // 2. If x is either +0𝔽 or -0𝔽, return "0".
+        if x == 0.0 {
+            return String::from_static_str(agent, "0", gc);
+        }
+        // 3. If x < -0𝔽, return the string-concatenation of "-" and Number::toString(-x, radix).
+        // 4. If x is +∞𝔽, return "Infinity".
+        if x == f64::INFINITY {
+            return String::from_static_str(agent, "Infinity", gc);
+        }
+        if x == f64::NEG_INFINITY {
+            return String::from_static_str(agent, "-Infinity", gc);
+        }
+        String::from_string(agent, radix::double_to_radix_string(x, radix), gc)
+    }
+
     /// ### [ℝ](https://tc39.es/ecma262/#%E2%84%9D)
     pub(crate) fn to_real(self, agent: &impl Index<HeapNumber<'static>, Output = f64>) -> f64 {
         match self {
@@ -1495,6 +1534,16 @@ impl HeapMarkAndSweep for HeapNumber<'static> {
     }
 }
 
+impl SnapshotData for HeapNumber<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl Rootable for Number<'static> {
     type RootRepr = NumberRootRepr;
 
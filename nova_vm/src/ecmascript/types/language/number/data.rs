@@ -2,7 +2,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::heap::{CompactionLists, HeapMarkAndSweep, WorkQueues};
+use crate::heap::{
+    CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotReader, SnapshotResult,
+    SnapshotWriter, WorkQueues,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct NumberHeapData {
@@ -31,3 +34,16 @@ impl HeapMarkAndSweep for NumberHeapData {
         let Self { data: _ } = self;
     }
 }
+
+impl SnapshotData for NumberHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self { data } = self;
+        data.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            data: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
@@ -0,0 +1,194 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Digit generation for `Number::toString` and `BigInt::toString` with a
+//! radix other than 10.
+
+use num_bigint::BigUint;
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Pushes the digits of `value` in `radix` onto `buf` in reverse order,
+/// least significant digit first. At least `min_digits` digits are pushed,
+/// padding with zeroes as necessary.
+fn push_u64_digits_reversed(buf: &mut Vec<u8>, mut value: u64, radix: u32, min_digits: usize) {
+    let radix = u64::from(radix);
+    let start = buf.len();
+    loop {
+        buf.push(DIGITS[(value % radix) as usize]);
+        value /= radix;
+        if value == 0 {
+            break;
+        }
+    }
+    while buf.len() - start < min_digits {
+        buf.push(b'0');
+    }
+}
+
+/// Pushes the digits of `value` in `radix` onto `buf` in reverse order,
+/// least significant digit first.
+///
+/// The value is divided by the largest power of `radix` that fits in a u64,
+/// so that each division produces a whole chunk of digits.
+fn push_big_uint_digits_reversed(buf: &mut Vec<u8>, value: &BigUint, radix: u32) {
+    let mut chunk_divisor = u64::from(radix);
+    let mut chunk_digits = 1;
+    while let Some(next) = chunk_divisor.checked_mul(u64::from(radix)) {
+        chunk_divisor = next;
+        chunk_digits += 1;
+    }
+    let mut value = value.clone();
+    loop {
+        let chunk = (&value % chunk_divisor).to_u64().unwrap();
+        value /= chunk_divisor;
+        if value.is_zero() {
+            push_u64_digits_reversed(buf, chunk, radix, 1);
+            break;
+        }
+        push_u64_digits_reversed(buf, chunk, radix, chunk_digits);
+    }
+}
+
+/// Formats an integer with the given sign and magnitude in `radix`.
+pub(crate) fn integer_to_radix_string(negative: bool, magnitude: u64, radix: u32) -> String {
+    debug_assert!((2..=36).contains(&radix));
+    let mut buf = Vec::with_capacity(66);
+    push_u64_digits_reversed(&mut buf, magnitude, radix, 1);
+    if negative {
+        buf.push(b'-');
+    }
+    buf.reverse();
+    // SAFETY: All pushed bytes are ASCII.
+    unsafe { String::from_utf8_unchecked(buf) }
+}
+
+/// Formats an arbitrarily large integer with the given sign and magnitude in
+/// `radix`.
+pub(crate) fn big_integer_to_radix_string(
+    negative: bool,
+    magnitude: &BigUint,
+    radix: u32,
+) -> String {
+    debug_assert!((2..=36).contains(&radix));
+    let mut buf = Vec::with_capacity(magnitude.bits() as usize + 1);
+    push_big_uint_digits_reversed(&mut buf, magnitude, radix);
+    if negative {
+        buf.push(b'-');
+    }
+    buf.reverse();
+    // SAFETY: All pushed bytes are ASCII.
+    unsafe { String::from_utf8_unchecked(buf) }
+}
+
+/// Formats a finite, non-zero double in `radix`.
+///
+/// The integer part is written out exactly, as SpiderMonkey does. The
+/// fractional part is written out digit by digit until the remaining fraction
+/// is smaller than half the distance to the next double, which produces the
+/// shortest digit string that rounds back to the same value. This follows
+/// V8's DoubleToRadixCString.
+pub(crate) fn double_to_radix_string(value: f64, radix: u32) -> String {
+    debug_assert!(value.is_finite() && value != 0.0);
+    debug_assert!((2..=36).contains(&radix));
+    let negative = value < 0.0;
+    let value = value.abs();
+    let radix_f64 = f64::from(radix);
+
+    let mut integer = value.floor();
+    let mut fraction = value - integer;
+    // We only compute fractional digits up to the input double's precision.
+    let next_double = f64::from_bits(value.to_bits() + 1);
+    let mut delta = (0.5 * (next_double - value)).max(f64::from_bits(1));
+
+    let mut fraction_digits = Vec::new();
+    if fraction >= delta {
+        loop {
+            // Shift up by one digit.
+            fraction *= radix_f64;
+            delta *= radix_f64;
+            // Write digit.
+            let digit = fraction as u32;
+            fraction_digits.push(DIGITS[digit as usize]);
+            // Calculate remainder.
+            fraction -= f64::from(digit);
+            // Round to even.
+            if (fraction > 0.5 || (fraction == 0.5 && digit & 1 == 1)) && fraction + delta > 1.0 {
+                // Propagate the carry through the already written digits.
+                loop {
+                    let Some(last) = fraction_digits.pop() else {
+                        // Carry over to the integer part.
+                        integer += 1.0;
+                        break;
+                    };
+                    let digit = DIGITS.iter().position(|&c| c == last).unwrap() as u32;
+                    if digit + 1 < radix {
+                        fraction_digits.push(DIGITS[digit as usize + 1]);
+                        break;
+                    }
+                }
+                break;
+            }
+            if fraction < delta {
+                break;
+            }
+        }
+    }
+
+    let mut buf = Vec::with_capacity(fraction_digits.len() + 66);
+    if integer < u64::MAX as f64 {
+        push_u64_digits_reversed(&mut buf, integer as u64, radix, 1);
+    } else {
+        push_big_uint_digits_reversed(&mut buf, &BigUint::from_f64(integer).unwrap(), radix);
+    }
+    if negative {
+        buf.push(b'-');
+    }
+    buf.reverse();
+    if !fraction_digits.is_empty() {
+        buf.push(b'.');
+        buf.extend_from_slice(&fraction_digits);
+    }
+    // SAFETY: All pushed bytes are ASCII.
+    unsafe { String::from_utf8_unchecked(buf) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(integer_to_radix_string(false, 255, 16), "ff");
+        assert_eq!(integer_to_radix_string(true, 255, 2), "-11111111");
+        assert_eq!(integer_to_radix_string(false, 0, 36), "0");
+        assert_eq!(
+            integer_to_radix_string(false, u64::MAX, 36),
+            "3w5e11264sgsf"
+        );
+        assert_eq!(
+            big_integer_to_radix_string(false, &(BigUint::from(u64::MAX) + 1u32), 16),
+            "10000000000000000"
+        );
+    }
+
+    #[test]
+    fn doubles() {
+        assert_eq!(double_to_radix_string(0.5, 2), "0.1");
+        assert_eq!(double_to_radix_string(-255.5, 16), "-ff.8");
+        assert_eq!(
+            double_to_radix_string(0.1, 2),
+            "0.0001100110011001100110011001100110011001100110011001101"
+        );
+        assert_eq!(
+            double_to_radix_string(0.1, 3),
+            "0.0022002200220022002200220022002201"
+        );
+        assert_eq!(
+            double_to_radix_string(2f64.powi(70), 2),
+            format!("1{}", "0".repeat(70))
+        );
+    }
+}
@@ -8,6 +8,7 @@ mod internal_slots;
 mod into_object;
 mod property_key;
 mod property_storage;
+mod shape;
 
 use std::hash::Hash;
 
@@ -16,7 +17,7 @@ use super::value::DATE_DISCRIMINANT;
 #[cfg(feature = "proposal-float16array")]
 use super::value::FLOAT_16_ARRAY_DISCRIMINANT;
 #[cfg(feature = "regexp")]
-use super::value::REGEXP_DISCRIMINANT;
+use super::value::{REGEXP_DISCRIMINANT, REGEXP_STRING_ITERATOR_DISCRIMINANT};
 #[cfg(feature = "shared-array-buffer")]
 use super::value::SHARED_ARRAY_BUFFER_DISCRIMINANT;
 #[cfg(feature = "array-buffer")]
@@ -41,6 +42,7 @@ use super::{
         FINALIZATION_REGISTRY_DISCRIMINANT, GENERATOR_DISCRIMINANT, ITERATOR_DISCRIMINANT,
         MAP_DISCRIMINANT, MAP_ITERATOR_DISCRIMINANT, MODULE_DISCRIMINANT, OBJECT_DISCRIMINANT,
         PRIMITIVE_OBJECT_DISCRIMINANT, PROMISE_DISCRIMINANT, PROXY_DISCRIMINANT,
+        STRING_ITERATOR_DISCRIMINANT,
     },
     Function, IntoValue, Value,
 };
@@ -48,6 +50,8 @@ use super::{
 use crate::ecmascript::builtins::date::Date;
 #[cfg(feature = "regexp")]
 use crate::ecmascript::builtins::regexp::RegExp;
+#[cfg(feature = "regexp")]
+use crate::ecmascript::builtins::text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIterator;
 #[cfg(feature = "shared-array-buffer")]
 use crate::ecmascript::builtins::shared_array_buffer::SharedArrayBuffer;
 #[cfg(feature = "weak-refs")]
@@ -83,7 +87,8 @@ use crate::{
             module::Module,
             primitive_objects::PrimitiveObject,
             promise::Promise,
-            proxy::Proxy,
+            proxy::{revoker::BuiltinProxyRevokerFunction, Proxy},
+            text_processing::string_objects::string_iterator_objects::string_iterator::StringIterator,
             ArgumentsList, Array, BuiltinConstructorFunction, BuiltinFunction, ECMAScriptFunction,
         },
         execution::{Agent, JsResult},
@@ -91,7 +96,8 @@ use crate::{
     },
     engine::{context::GcScope, rootable::HeapRootData, TryResult},
     heap::{
-        indexes::ObjectIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, WorkQueues,
+        indexes::ObjectIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep,
+        SnapshotData, SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
     },
 };
 
@@ -103,6 +109,7 @@ pub use property_key::{
     bind_property_keys, scope_property_keys, unbind_property_keys, PropertyKey,
 };
 pub use property_storage::PropertyStorage;
+pub(crate) use shape::{sweep_object_shape_roots, ObjectShape, ObjectShapeRecord};
 
 /// ### [6.1.7 The Object Type](https://tc39.es/ecma262/#sec-object-type)
 ///
@@ -120,7 +127,7 @@ pub enum Object<'a> {
     BuiltinPromiseResolvingFunction(BuiltinPromiseResolvingFunction<'a>) =
         BUILTIN_PROMISE_RESOLVING_FUNCTION_DISCRIMINANT,
     BuiltinPromiseCollectorFunction = BUILTIN_PROMISE_COLLECTOR_FUNCTION_DISCRIMINANT,
-    BuiltinProxyRevokerFunction = BUILTIN_PROXY_REVOKER_FUNCTION,
+    BuiltinProxyRevokerFunction(BuiltinProxyRevokerFunction<'a>) = BUILTIN_PROXY_REVOKER_FUNCTION,
     PrimitiveObject(PrimitiveObject<'a>) = PRIMITIVE_OBJECT_DISCRIMINANT,
     Arguments(OrdinaryObject<'a>) = ARGUMENTS_DISCRIMINANT,
     Array(Array<'a>) = ARRAY_DISCRIMINANT,
@@ -178,12 +185,15 @@ pub enum Object<'a> {
     #[cfg(feature = "set")]
     SetIterator(SetIterator<'a>) = SET_ITERATOR_DISCRIMINANT,
     MapIterator(MapIterator<'a>) = MAP_ITERATOR_DISCRIMINANT,
+    StringIterator(StringIterator<'a>) = STRING_ITERATOR_DISCRIMINANT,
+    #[cfg(feature = "regexp")]
+    RegExpStringIterator(RegExpStringIterator<'a>) = REGEXP_STRING_ITERATOR_DISCRIMINANT,
     Generator(Generator<'a>) = GENERATOR_DISCRIMINANT,
     Module(Module<'a>) = MODULE_DISCRIMINANT,
     EmbedderObject(EmbedderObject<'a>) = EMBEDDER_OBJECT_DISCRIMINANT,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct OrdinaryObject<'a>(pub(crate) ObjectIndex<'a>);
 
 impl IntoValue for Object<'_> {
@@ -201,7 +211,9 @@ impl IntoValue for Object<'_> {
                 Value::BuiltinPromiseResolvingFunction(data.unbind())
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                Value::BuiltinProxyRevokerFunction(data.unbind())
+            }
             Object::PrimitiveObject(data) => Value::PrimitiveObject(data.unbind()),
             Object::Arguments(data) => Value::Arguments(data.unbind()),
             Object::Array(data) => Value::Array(data.unbind()),
@@ -259,6 +271,9 @@ impl IntoValue for Object<'_> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => Value::SetIterator(data.unbind()),
             Object::MapIterator(data) => Value::MapIterator(data.unbind()),
+            Object::StringIterator(data) => Value::StringIterator(data.unbind()),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => Value::RegExpStringIterator(data.unbind()),
             Object::Generator(data) => Value::Generator(data.unbind()),
             Object::Module(data) => Value::Module(data.unbind()),
             Object::EmbedderObject(data) => Value::EmbedderObject(data.unbind()),
@@ -349,11 +364,12 @@ impl<'a> InternalSlots<'a> for OrdinaryObject<'a> {
     }
 
     fn internal_prototype(self, agent: &Agent) -> Option<Object<'static>> {
-        agent[self.unbind()].prototype
+        agent[self.unbind()].shape.prototype(agent)
     }
 
     fn internal_set_prototype(self, agent: &mut Agent, prototype: Option<Object>) {
-        agent[self.unbind()].prototype = prototype.map(|p| p.unbind());
+        let shape = agent[self.unbind()].shape;
+        agent[self.unbind()].shape = shape.set_prototype(&mut agent.heap, prototype);
     }
 }
 
@@ -426,7 +442,9 @@ impl From<Object<'_>> for Value {
                 Value::BuiltinPromiseResolvingFunction(data.unbind())
             }
             Object::BuiltinPromiseCollectorFunction => Value::BuiltinPromiseCollectorFunction,
-            Object::BuiltinProxyRevokerFunction => Value::BuiltinProxyRevokerFunction,
+            Object::BuiltinProxyRevokerFunction(data) => {
+                Value::BuiltinProxyRevokerFunction(data.unbind())
+            }
             Object::PrimitiveObject(data) => Value::PrimitiveObject(data.unbind()),
             Object::Arguments(data) => Value::Arguments(data.unbind()),
             Object::Array(data) => Value::Array(data.unbind()),
@@ -484,6 +502,9 @@ impl From<Object<'_>> for Value {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => Value::SetIterator(data.unbind()),
             Object::MapIterator(data) => Value::MapIterator(data.unbind()),
+            Object::StringIterator(data) => Value::StringIterator(data.unbind()),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => Value::RegExpStringIterator(data.unbind()),
             Object::Generator(data) => Value::Generator(data.unbind()),
             Object::Module(data) => Value::Module(data.unbind()),
             Object::EmbedderObject(data) => Value::EmbedderObject(data.unbind()),
@@ -520,7 +541,9 @@ impl TryFrom<Value> for Object<'_> {
                 Ok(Object::BuiltinPromiseResolvingFunction(data))
             }
             Value::BuiltinPromiseCollectorFunction => Ok(Object::BuiltinPromiseCollectorFunction),
-            Value::BuiltinProxyRevokerFunction => Ok(Object::BuiltinProxyRevokerFunction),
+            Value::BuiltinProxyRevokerFunction(data) => {
+                Ok(Object::BuiltinProxyRevokerFunction(data))
+            }
             Value::PrimitiveObject(data) => Ok(Object::PrimitiveObject(data)),
             Value::Arguments(data) => Ok(Object::Arguments(data)),
             #[cfg(feature = "array-buffer")]
@@ -574,6 +597,9 @@ impl TryFrom<Value> for Object<'_> {
             #[cfg(feature = "set")]
             Value::SetIterator(data) => Ok(Object::SetIterator(data)),
             Value::MapIterator(data) => Ok(Object::MapIterator(data)),
+            Value::StringIterator(data) => Ok(Object::StringIterator(data)),
+            #[cfg(feature = "regexp")]
+            Value::RegExpStringIterator(data) => Ok(Object::RegExpStringIterator(data)),
             Value::Generator(data) => Ok(Object::Generator(data)),
             Value::Module(data) => Ok(Object::Module(data)),
             Value::EmbedderObject(data) => Ok(Object::EmbedderObject(data)),
@@ -631,7 +657,7 @@ impl Hash for Object<'_> {
             Object::BuiltinConstructorFunction(data) => data.get_index().hash(state),
             Object::BuiltinPromiseResolvingFunction(data) => data.get_index().hash(state),
             Object::BuiltinPromiseCollectorFunction => {}
-            Object::BuiltinProxyRevokerFunction => {}
+            Object::BuiltinProxyRevokerFunction(data) => data.get_index().hash(state),
             Object::PrimitiveObject(data) => data.get_index().hash(state),
             Object::Arguments(data) => data.get_index().hash(state),
             Object::Array(data) => data.get_index().hash(state),
@@ -689,6 +715,9 @@ impl Hash for Object<'_> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.get_index().hash(state),
             Object::MapIterator(data) => data.get_index().hash(state),
+            Object::StringIterator(data) => data.get_index().hash(state),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.get_index().hash(state),
             Object::Generator(data) => data.get_index().hash(state),
             Object::Module(data) => data.get_index().hash(state),
             Object::EmbedderObject(data) => data.get_index().hash(state),
@@ -725,7 +754,7 @@ impl<'a> InternalSlots<'a> for Object<'a> {
             Object::BuiltinConstructorFunction(data) => data.internal_extensible(agent),
             Object::BuiltinPromiseResolvingFunction(data) => data.internal_extensible(agent),
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.internal_extensible(agent),
             Object::PrimitiveObject(data) => data.internal_extensible(agent),
             Object::Arguments(data) => data.internal_extensible(agent),
             #[cfg(feature = "array-buffer")]
@@ -783,6 +812,9 @@ impl<'a> InternalSlots<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_extensible(agent),
             Object::MapIterator(data) => data.internal_extensible(agent),
+            Object::StringIterator(data) => data.internal_extensible(agent),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.internal_extensible(agent),
             Object::Generator(data) => data.internal_extensible(agent),
             Object::Module(data) => data.internal_extensible(agent),
             Object::EmbedderObject(data) => data.internal_extensible(agent),
@@ -807,7 +839,7 @@ impl<'a> InternalSlots<'a> for Object<'a> {
                 data.internal_set_extensible(agent, value)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.internal_set_extensible(agent, value),
             Object::PrimitiveObject(data) => data.internal_set_extensible(agent, value),
             Object::Arguments(data) => data.internal_set_extensible(agent, value),
             #[cfg(feature = "array-buffer")]
@@ -883,6 +915,9 @@ impl<'a> InternalSlots<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_set_extensible(agent, value),
             Object::MapIterator(data) => data.internal_set_extensible(agent, value),
+            Object::StringIterator(data) => data.internal_set_extensible(agent, value),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.internal_set_extensible(agent, value),
             Object::Generator(data) => data.internal_set_extensible(agent, value),
             Object::Module(data) => data.internal_set_extensible(agent, value),
             Object::EmbedderObject(data) => data.internal_set_extensible(agent, value),
@@ -905,7 +940,7 @@ impl<'a> InternalSlots<'a> for Object<'a> {
             Object::BuiltinConstructorFunction(data) => data.internal_prototype(agent),
             Object::BuiltinPromiseResolvingFunction(data) => data.internal_prototype(agent),
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.internal_prototype(agent),
             Object::PrimitiveObject(data) => data.internal_prototype(agent),
             Object::Arguments(data) => data.internal_prototype(agent),
             #[cfg(feature = "array-buffer")]
@@ -963,6 +998,9 @@ impl<'a> InternalSlots<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_prototype(agent),
             Object::MapIterator(data) => data.internal_prototype(agent),
+            Object::StringIterator(data) => data.internal_prototype(agent),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.internal_prototype(agent),
             Object::Generator(data) => data.internal_prototype(agent),
             Object::Module(data) => data.internal_prototype(agent),
             Object::EmbedderObject(data) => data.internal_prototype(agent),
@@ -989,7 +1027,9 @@ impl<'a> InternalSlots<'a> for Object<'a> {
                 data.internal_set_prototype(agent, prototype)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.internal_set_prototype(agent, prototype)
+            }
             Object::PrimitiveObject(data) => data.internal_set_prototype(agent, prototype),
             Object::Arguments(data) => data.internal_set_prototype(agent, prototype),
             #[cfg(feature = "array-buffer")]
@@ -1065,6 +1105,9 @@ impl<'a> InternalSlots<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_set_prototype(agent, prototype),
             Object::MapIterator(data) => data.internal_set_prototype(agent, prototype),
+            Object::StringIterator(data) => data.internal_set_prototype(agent, prototype),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.internal_set_prototype(agent, prototype),
             Object::Generator(data) => data.internal_set_prototype(agent, prototype),
             Object::Module(data) => data.internal_set_prototype(agent, prototype),
             Object::EmbedderObject(data) => data.internal_set_prototype(agent, prototype),
@@ -1093,7 +1136,7 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             Object::BuiltinConstructorFunction(data) => data.try_get_prototype_of(agent, gc),
             Object::BuiltinPromiseResolvingFunction(data) => data.try_get_prototype_of(agent, gc),
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.try_get_prototype_of(agent, gc),
             Object::PrimitiveObject(data) => data.try_get_prototype_of(agent, gc),
             Object::Arguments(data) => data.try_get_prototype_of(agent, gc),
             #[cfg(feature = "array-buffer")]
@@ -1167,6 +1210,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_get_prototype_of(agent, gc),
             Object::MapIterator(data) => data.try_get_prototype_of(agent, gc),
+            Object::StringIterator(data) => data.try_get_prototype_of(agent, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.try_get_prototype_of(agent, gc),
             Object::Generator(data) => data.try_get_prototype_of(agent, gc),
             Object::Module(data) => data.try_get_prototype_of(agent, gc),
             Object::EmbedderObject(data) => data.try_get_prototype_of(agent, gc),
@@ -1195,7 +1241,7 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_get_prototype_of(agent, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.internal_get_prototype_of(agent, gc),
             Object::PrimitiveObject(data) => data.internal_get_prototype_of(agent, gc),
             Object::Arguments(data) => data.internal_get_prototype_of(agent, gc),
             #[cfg(feature = "array-buffer")]
@@ -1271,6 +1317,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_get_prototype_of(agent, gc),
             Object::MapIterator(data) => data.internal_get_prototype_of(agent, gc),
+            Object::StringIterator(data) => data.internal_get_prototype_of(agent, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.internal_get_prototype_of(agent, gc),
             Object::Generator(data) => data.internal_get_prototype_of(agent, gc),
             Object::Module(data) => data.internal_get_prototype_of(agent, gc),
             Object::EmbedderObject(data) => data.internal_get_prototype_of(agent, gc),
@@ -1302,7 +1351,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.try_set_prototype_of(agent, prototype, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.try_set_prototype_of(agent, prototype, gc)
+            }
             Object::PrimitiveObject(data) => data.try_set_prototype_of(agent, prototype, gc),
             Object::Arguments(data) => data.try_set_prototype_of(agent, prototype, gc),
             #[cfg(feature = "array-buffer")]
@@ -1378,6 +1429,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_set_prototype_of(agent, prototype, gc),
             Object::MapIterator(data) => data.try_set_prototype_of(agent, prototype, gc),
+            Object::StringIterator(data) => data.try_set_prototype_of(agent, prototype, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.try_set_prototype_of(agent, prototype, gc),
             Object::Generator(data) => data.try_set_prototype_of(agent, prototype, gc),
             Object::Module(data) => data.try_set_prototype_of(agent, prototype, gc),
             Object::EmbedderObject(data) => data.try_set_prototype_of(agent, prototype, gc),
@@ -1411,7 +1465,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_set_prototype_of(agent, prototype, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.internal_set_prototype_of(agent, prototype, gc)
+            }
             Object::PrimitiveObject(data) => data.internal_set_prototype_of(agent, prototype, gc),
             Object::Arguments(data) => data.internal_set_prototype_of(agent, prototype, gc),
             #[cfg(feature = "array-buffer")]
@@ -1489,6 +1545,11 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_set_prototype_of(agent, prototype, gc),
             Object::MapIterator(data) => data.internal_set_prototype_of(agent, prototype, gc),
+            Object::StringIterator(data) => data.internal_set_prototype_of(agent, prototype, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => {
+                data.internal_set_prototype_of(agent, prototype, gc)
+            }
             Object::Generator(data) => data.internal_set_prototype_of(agent, prototype, gc),
             Object::Module(data) => data.internal_set_prototype_of(agent, prototype, gc),
             Object::EmbedderObject(data) => data.internal_set_prototype_of(agent, prototype, gc),
@@ -1511,7 +1572,7 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             Object::BuiltinConstructorFunction(data) => data.try_is_extensible(agent, gc),
             Object::BuiltinPromiseResolvingFunction(data) => data.try_is_extensible(agent, gc),
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.try_is_extensible(agent, gc),
             Object::PrimitiveObject(data) => data.try_is_extensible(agent, gc),
             Object::Arguments(data) => data.try_is_extensible(agent, gc),
             #[cfg(feature = "array-buffer")]
@@ -1575,6 +1636,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_is_extensible(agent, gc),
             Object::MapIterator(data) => data.try_is_extensible(agent, gc),
+            Object::StringIterator(data) => data.try_is_extensible(agent, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.try_is_extensible(agent, gc),
             Object::Generator(data) => data.try_is_extensible(agent, gc),
             Object::Module(data) => data.try_is_extensible(agent, gc),
             Object::EmbedderObject(data) => data.try_is_extensible(agent, gc),
@@ -1597,7 +1661,7 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             Object::BuiltinConstructorFunction(data) => data.internal_is_extensible(agent, gc),
             Object::BuiltinPromiseResolvingFunction(data) => data.internal_is_extensible(agent, gc),
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.internal_is_extensible(agent, gc),
             Object::PrimitiveObject(data) => data.internal_is_extensible(agent, gc),
             Object::Arguments(data) => data.internal_is_extensible(agent, gc),
             #[cfg(feature = "array-buffer")]
@@ -1673,6 +1737,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_is_extensible(agent, gc),
             Object::MapIterator(data) => data.internal_is_extensible(agent, gc),
+            Object::StringIterator(data) => data.internal_is_extensible(agent, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.internal_is_extensible(agent, gc),
             Object::Generator(data) => data.internal_is_extensible(agent, gc),
             Object::Module(data) => data.internal_is_extensible(agent, gc),
             Object::EmbedderObject(data) => data.internal_is_extensible(agent, gc),
@@ -1695,7 +1762,7 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             Object::BuiltinConstructorFunction(data) => data.try_prevent_extensions(agent, gc),
             Object::BuiltinPromiseResolvingFunction(data) => data.try_prevent_extensions(agent, gc),
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.try_prevent_extensions(agent, gc),
             Object::PrimitiveObject(data) => data.try_prevent_extensions(agent, gc),
             Object::Arguments(data) => data.try_prevent_extensions(agent, gc),
             #[cfg(feature = "array-buffer")]
@@ -1771,6 +1838,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_prevent_extensions(agent, gc),
             Object::MapIterator(data) => data.try_prevent_extensions(agent, gc),
+            Object::StringIterator(data) => data.try_prevent_extensions(agent, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.try_prevent_extensions(agent, gc),
             Object::Generator(data) => data.try_prevent_extensions(agent, gc),
             Object::Module(data) => data.try_prevent_extensions(agent, gc),
             Object::EmbedderObject(data) => data.try_prevent_extensions(agent, gc),
@@ -1795,7 +1865,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_prevent_extensions(agent, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.internal_prevent_extensions(agent, gc)
+            }
             Object::PrimitiveObject(data) => data.internal_prevent_extensions(agent, gc),
             Object::Arguments(data) => data.internal_prevent_extensions(agent, gc),
             #[cfg(feature = "array-buffer")]
@@ -1871,6 +1943,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_prevent_extensions(agent, gc),
             Object::MapIterator(data) => data.internal_prevent_extensions(agent, gc),
+            Object::StringIterator(data) => data.internal_prevent_extensions(agent, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.internal_prevent_extensions(agent, gc),
             Object::Generator(data) => data.internal_prevent_extensions(agent, gc),
             Object::Module(data) => data.internal_prevent_extensions(agent, gc),
             Object::EmbedderObject(data) => data.internal_prevent_extensions(agent, gc),
@@ -1902,7 +1977,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.try_get_own_property(agent, property_key, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.try_get_own_property(agent, property_key, gc)
+            }
             Object::PrimitiveObject(data) => data.try_get_own_property(agent, property_key, gc),
             Object::Arguments(data) => data.try_get_own_property(agent, property_key, gc),
             #[cfg(feature = "array-buffer")]
@@ -1980,6 +2057,11 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_get_own_property(agent, property_key, gc),
             Object::MapIterator(data) => data.try_get_own_property(agent, property_key, gc),
+            Object::StringIterator(data) => data.try_get_own_property(agent, property_key, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => {
+                data.try_get_own_property(agent, property_key, gc)
+            }
             Object::Generator(data) => data.try_get_own_property(agent, property_key, gc),
             Object::Module(data) => data.try_get_own_property(agent, property_key, gc),
             Object::EmbedderObject(data) => data.try_get_own_property(agent, property_key, gc),
@@ -2015,7 +2097,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_get_own_property(agent, property_key, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.internal_get_own_property(agent, property_key, gc)
+            }
             Object::PrimitiveObject(data) => {
                 data.internal_get_own_property(agent, property_key, gc)
             }
@@ -2096,6 +2180,11 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_get_own_property(agent, property_key, gc),
             Object::MapIterator(data) => data.internal_get_own_property(agent, property_key, gc),
+            Object::StringIterator(data) => data.internal_get_own_property(agent, property_key, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => {
+                data.internal_get_own_property(agent, property_key, gc)
+            }
             Object::Generator(data) => data.internal_get_own_property(agent, property_key, gc),
             Object::Module(data) => data.internal_get_own_property(agent, property_key, gc),
             Object::EmbedderObject(data) => data.internal_get_own_property(agent, property_key, gc),
@@ -2144,7 +2233,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.try_define_own_property(agent, property_key, property_descriptor, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.try_define_own_property(agent, property_key, property_descriptor, gc)
+            }
             Object::PrimitiveObject(data) => {
                 data.try_define_own_property(agent, property_key, property_descriptor, gc)
             }
@@ -2282,6 +2373,13 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             Object::MapIterator(data) => {
                 data.try_define_own_property(agent, property_key, property_descriptor, gc)
             }
+            Object::StringIterator(data) => {
+                data.try_define_own_property(agent, property_key, property_descriptor, gc)
+            }
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => {
+                data.try_define_own_property(agent, property_key, property_descriptor, gc)
+            }
             Object::Generator(data) => {
                 data.try_define_own_property(agent, property_key, property_descriptor, gc)
             }
@@ -2336,7 +2434,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_define_own_property(agent, property_key, property_descriptor, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.internal_define_own_property(agent, property_key, property_descriptor, gc)
+            }
             Object::PrimitiveObject(data) => {
                 data.internal_define_own_property(agent, property_key, property_descriptor, gc)
             }
@@ -2450,6 +2550,13 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             Object::MapIterator(data) => {
                 data.internal_define_own_property(agent, property_key, property_descriptor, gc)
             }
+            Object::StringIterator(data) => {
+                data.internal_define_own_property(agent, property_key, property_descriptor, gc)
+            }
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => {
+                data.internal_define_own_property(agent, property_key, property_descriptor, gc)
+            }
             Object::Generator(data) => {
                 data.internal_define_own_property(agent, property_key, property_descriptor, gc)
             }
@@ -2487,7 +2594,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.try_has_property(agent, property_key, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.try_has_property(agent, property_key, gc)
+            }
             Object::PrimitiveObject(data) => data.try_has_property(agent, property_key, gc),
             Object::Arguments(data) => data.try_has_property(agent, property_key, gc),
             #[cfg(feature = "array-buffer")]
@@ -2563,6 +2672,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_has_property(agent, property_key, gc),
             Object::MapIterator(data) => data.try_has_property(agent, property_key, gc),
+            Object::StringIterator(data) => data.try_has_property(agent, property_key, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.try_has_property(agent, property_key, gc),
             Object::Generator(data) => data.try_has_property(agent, property_key, gc),
             Object::Module(data) => data.try_has_property(agent, property_key, gc),
             Object::EmbedderObject(data) => data.try_has_property(agent, property_key, gc),
@@ -2594,7 +2706,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_has_property(agent, property_key, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.internal_has_property(agent, property_key, gc)
+            }
             Object::PrimitiveObject(data) => data.internal_has_property(agent, property_key, gc),
             Object::Arguments(data) => data.internal_has_property(agent, property_key, gc),
             #[cfg(feature = "array-buffer")]
@@ -2672,6 +2786,11 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_has_property(agent, property_key, gc),
             Object::MapIterator(data) => data.internal_has_property(agent, property_key, gc),
+            Object::StringIterator(data) => data.internal_has_property(agent, property_key, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => {
+                data.internal_has_property(agent, property_key, gc)
+            }
             Object::Generator(data) => data.internal_has_property(agent, property_key, gc),
             Object::Module(data) => data.internal_has_property(agent, property_key, gc),
             Object::EmbedderObject(data) => data.internal_has_property(agent, property_key, gc),
@@ -2704,7 +2823,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.try_get(agent, property_key, receiver, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.try_get(agent, property_key, receiver, gc)
+            }
             Object::PrimitiveObject(data) => data.try_get(agent, property_key, receiver, gc),
             Object::Arguments(data) => data.try_get(agent, property_key, receiver, gc),
             #[cfg(feature = "array-buffer")]
@@ -2780,6 +2901,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_get(agent, property_key, receiver, gc),
             Object::MapIterator(data) => data.try_get(agent, property_key, receiver, gc),
+            Object::StringIterator(data) => data.try_get(agent, property_key, receiver, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.try_get(agent, property_key, receiver, gc),
             Object::Generator(data) => data.try_get(agent, property_key, receiver, gc),
             Object::Module(data) => data.try_get(agent, property_key, receiver, gc),
             Object::EmbedderObject(data) => data.try_get(agent, property_key, receiver, gc),
@@ -2814,7 +2938,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_get(agent, property_key, receiver, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.internal_get(agent, property_key, receiver, gc)
+            }
             Object::PrimitiveObject(data) => data.internal_get(agent, property_key, receiver, gc),
             Object::Arguments(data) => data.internal_get(agent, property_key, receiver, gc),
             #[cfg(feature = "array-buffer")]
@@ -2892,6 +3018,11 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_get(agent, property_key, receiver, gc),
             Object::MapIterator(data) => data.internal_get(agent, property_key, receiver, gc),
+            Object::StringIterator(data) => data.internal_get(agent, property_key, receiver, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => {
+                data.internal_get(agent, property_key, receiver, gc)
+            }
             Object::Generator(data) => data.internal_get(agent, property_key, receiver, gc),
             Object::Module(data) => data.internal_get(agent, property_key, receiver, gc),
             Object::EmbedderObject(data) => data.internal_get(agent, property_key, receiver, gc),
@@ -2927,7 +3058,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.try_set(agent, property_key, value, receiver, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.try_set(agent, property_key, value, receiver, gc)
+            }
             Object::PrimitiveObject(data) => data.try_set(agent, property_key, value, receiver, gc),
             Object::Arguments(data) => data.try_set(agent, property_key, value, receiver, gc),
             #[cfg(feature = "array-buffer")]
@@ -3011,6 +3144,11 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_set(agent, property_key, value, receiver, gc),
             Object::MapIterator(data) => data.try_set(agent, property_key, value, receiver, gc),
+            Object::StringIterator(data) => data.try_set(agent, property_key, value, receiver, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => {
+                data.try_set(agent, property_key, value, receiver, gc)
+            }
             Object::Generator(data) => data.try_set(agent, property_key, value, receiver, gc),
             Object::Module(data) => data.try_set(agent, property_key, value, receiver, gc),
             Object::EmbedderObject(data) => data.try_set(agent, property_key, value, receiver, gc),
@@ -3052,7 +3190,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_set(agent, property_key, value, receiver, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.internal_set(agent, property_key, value, receiver, gc)
+            }
             Object::PrimitiveObject(data) => {
                 data.internal_set(agent, property_key, value, receiver, gc)
             }
@@ -3166,6 +3306,13 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             Object::MapIterator(data) => {
                 data.internal_set(agent, property_key, value, receiver, gc)
             }
+            Object::StringIterator(data) => {
+                data.internal_set(agent, property_key, value, receiver, gc)
+            }
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => {
+                data.internal_set(agent, property_key, value, receiver, gc)
+            }
             Object::Generator(data) => data.internal_set(agent, property_key, value, receiver, gc),
             Object::Module(data) => data.internal_set(agent, property_key, value, receiver, gc),
             Object::EmbedderObject(data) => {
@@ -3197,7 +3344,7 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.try_delete(agent, property_key, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.try_delete(agent, property_key, gc),
             Object::PrimitiveObject(data) => data.try_delete(agent, property_key, gc),
             Object::Arguments(data) => data.try_delete(agent, property_key, gc),
             #[cfg(feature = "array-buffer")]
@@ -3273,6 +3420,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_delete(agent, property_key, gc),
             Object::MapIterator(data) => data.try_delete(agent, property_key, gc),
+            Object::StringIterator(data) => data.try_delete(agent, property_key, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.try_delete(agent, property_key, gc),
             Object::Generator(data) => data.try_delete(agent, property_key, gc),
             Object::Module(data) => data.try_delete(agent, property_key, gc),
             Object::EmbedderObject(data) => data.try_delete(agent, property_key, gc),
@@ -3304,7 +3454,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_delete(agent, property_key, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => {
+                data.internal_delete(agent, property_key, gc)
+            }
             Object::PrimitiveObject(data) => data.internal_delete(agent, property_key, gc),
             Object::Arguments(data) => data.internal_delete(agent, property_key, gc),
             #[cfg(feature = "array-buffer")]
@@ -3380,6 +3532,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_delete(agent, property_key, gc),
             Object::MapIterator(data) => data.internal_delete(agent, property_key, gc),
+            Object::StringIterator(data) => data.internal_delete(agent, property_key, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.internal_delete(agent, property_key, gc),
             Object::Generator(data) => data.internal_delete(agent, property_key, gc),
             Object::Module(data) => data.internal_delete(agent, property_key, gc),
             Object::EmbedderObject(data) => data.internal_delete(agent, property_key, gc),
@@ -3406,7 +3561,7 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             Object::BuiltinConstructorFunction(data) => data.try_own_property_keys(agent, gc),
             Object::BuiltinPromiseResolvingFunction(data) => data.try_own_property_keys(agent, gc),
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.try_own_property_keys(agent, gc),
             Object::PrimitiveObject(data) => data.try_own_property_keys(agent, gc),
             Object::Arguments(data) => data.try_own_property_keys(agent, gc),
             #[cfg(feature = "array-buffer")]
@@ -3480,6 +3635,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.try_own_property_keys(agent, gc),
             Object::MapIterator(data) => data.try_own_property_keys(agent, gc),
+            Object::StringIterator(data) => data.try_own_property_keys(agent, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.try_own_property_keys(agent, gc),
             Object::Generator(data) => data.try_own_property_keys(agent, gc),
             Object::Module(data) => data.try_own_property_keys(agent, gc),
             Object::EmbedderObject(data) => data.try_own_property_keys(agent, gc),
@@ -3508,7 +3666,7 @@ impl<'a> InternalMethods<'a> for Object<'a> {
                 data.internal_own_property_keys(agent, gc)
             }
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.internal_own_property_keys(agent, gc),
             Object::PrimitiveObject(data) => data.internal_own_property_keys(agent, gc),
             Object::Arguments(data) => data.internal_own_property_keys(agent, gc),
             #[cfg(feature = "array-buffer")]
@@ -3584,6 +3742,9 @@ impl<'a> InternalMethods<'a> for Object<'a> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.internal_own_property_keys(agent, gc),
             Object::MapIterator(data) => data.internal_own_property_keys(agent, gc),
+            Object::StringIterator(data) => data.internal_own_property_keys(agent, gc),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.internal_own_property_keys(agent, gc),
             Object::Generator(data) => data.internal_own_property_keys(agent, gc),
             Object::Module(data) => data.internal_own_property_keys(agent, gc),
             Object::EmbedderObject(data) => data.internal_own_property_keys(agent, gc),
@@ -3651,7 +3812,7 @@ impl HeapMarkAndSweep for Object<'static> {
             Object::BuiltinConstructorFunction(data) => data.mark_values(queues),
             Object::BuiltinPromiseResolvingFunction(data) => data.mark_values(queues),
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.mark_values(queues),
             Object::PrimitiveObject(data) => data.mark_values(queues),
             Object::Arguments(data) => data.mark_values(queues),
             #[cfg(feature = "array-buffer")]
@@ -3703,6 +3864,9 @@ impl HeapMarkAndSweep for Object<'static> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.mark_values(queues),
             Object::MapIterator(data) => data.mark_values(queues),
+            Object::StringIterator(data) => data.mark_values(queues),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.mark_values(queues),
             Object::Generator(data) => data.mark_values(queues),
             Object::Module(data) => data.mark_values(queues),
             Object::EmbedderObject(data) => data.mark_values(queues),
@@ -3719,7 +3883,7 @@ impl HeapMarkAndSweep for Object<'static> {
             Object::BuiltinConstructorFunction(data) => data.sweep_values(compactions),
             Object::BuiltinPromiseResolvingFunction(data) => data.sweep_values(compactions),
             Object::BuiltinPromiseCollectorFunction => todo!(),
-            Object::BuiltinProxyRevokerFunction => todo!(),
+            Object::BuiltinProxyRevokerFunction(data) => data.sweep_values(compactions),
             Object::PrimitiveObject(data) => data.sweep_values(compactions),
             Object::Arguments(data) => data.sweep_values(compactions),
             Object::Array(data) => data.sweep_values(compactions),
@@ -3777,6 +3941,9 @@ impl HeapMarkAndSweep for Object<'static> {
             #[cfg(feature = "set")]
             Object::SetIterator(data) => data.sweep_values(compactions),
             Object::MapIterator(data) => data.sweep_values(compactions),
+            Object::StringIterator(data) => data.sweep_values(compactions),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(data) => data.sweep_values(compactions),
             Object::Generator(data) => data.sweep_values(compactions),
             Object::Module(data) => data.sweep_values(compactions),
             Object::EmbedderObject(data) => data.sweep_values(compactions),
@@ -3784,6 +3951,16 @@ impl HeapMarkAndSweep for Object<'static> {
     }
 }
 
+impl SnapshotData for Object<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        Value::from(*self).serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Self::try_from(Value::deserialize(reader)?).map_err(|_| SnapshotError::Malformed)
+    }
+}
+
 impl CreateHeapData<ObjectHeapData, OrdinaryObject<'static>> for Heap {
     fn create(&mut self, data: ObjectHeapData) -> OrdinaryObject<'static> {
         self.objects.push(Some(data));
@@ -3832,7 +4009,9 @@ impl TryFrom<HeapRootData> for Object<'_> {
             HeapRootData::BuiltinPromiseCollectorFunction => {
                 Ok(Self::BuiltinPromiseCollectorFunction)
             }
-            HeapRootData::BuiltinProxyRevokerFunction => Ok(Self::BuiltinProxyRevokerFunction),
+            HeapRootData::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function) => Ok(
+                Self::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function),
+            ),
             HeapRootData::PrimitiveObject(primitive_object) => {
                 Ok(Self::PrimitiveObject(primitive_object))
             }
@@ -3896,6 +4075,13 @@ impl TryFrom<HeapRootData> for Object<'_> {
             #[cfg(feature = "set")]
             HeapRootData::SetIterator(set_iterator) => Ok(Self::SetIterator(set_iterator)),
             HeapRootData::MapIterator(map_iterator) => Ok(Self::MapIterator(map_iterator)),
+            HeapRootData::StringIterator(string_iterator) => {
+                Ok(Self::StringIterator(string_iterator))
+            }
+            #[cfg(feature = "regexp")]
+            HeapRootData::RegExpStringIterator(regexp_string_iterator) => {
+                Ok(Self::RegExpStringIterator(regexp_string_iterator))
+            }
             HeapRootData::Generator(generator) => Ok(Self::Generator(generator)),
             HeapRootData::Module(module) => Ok(Self::Module(module)),
             HeapRootData::EmbedderObject(embedder_object) => {
@@ -2,33 +2,26 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use super::Object;
+use super::ObjectShape;
 use crate::{
     ecmascript::{execution::Agent, types::Value},
-    heap::{element_array::ElementsVector, CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        element_array::ElementsVector, CompactionLists, HeapMarkAndSweep, SnapshotData,
+        SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
+    },
 };
 
 #[derive(Debug, Clone, Copy)]
 pub struct ObjectHeapData {
     pub extensible: bool,
-    pub prototype: Option<Object<'static>>,
-    pub keys: ElementsVector,
+    /// The \[\[Prototype]] and property keys of the object.
+    pub(crate) shape: ObjectShape,
+    /// Property values of the object, in the order of the shape's keys.
     pub values: ElementsVector,
 }
 
 impl ObjectHeapData {
-    pub fn new(
-        extensible: bool,
-        prototype: Value,
-        keys: ElementsVector,
-        values: ElementsVector,
-    ) -> Self {
-        let prototype = if prototype.is_null() {
-            None
-        } else {
-            // TODO: Throw error.
-            Some(Object::try_from(prototype).unwrap())
-        };
+    pub(crate) fn new(extensible: bool, shape: ObjectShape, values: ElementsVector) -> Self {
         Self {
             extensible,
             // TODO: Number, Boolean, etc. objects exist. These can all be
@@ -36,16 +29,14 @@ impl ObjectHeapData {
             // a [[PrimitiveValue]] field to objects: Normally this field is None
             // to signal that the object is its own primitive value. For
             // Number objects etc the field is Some(Value).
-            // TODO: Move prototype and key vector into shapes
-            prototype,
-            keys,
+            shape,
             values,
         }
     }
 
     pub fn has(&self, agent: &Agent, key: Value) -> bool {
         debug_assert!(key.is_string() || key.is_number() || key.is_symbol());
-        agent.heap.elements.has(self.keys, key)
+        agent.heap.elements.has(self.shape.keys(agent), key)
     }
 }
 
@@ -53,25 +44,42 @@ impl HeapMarkAndSweep for ObjectHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
             extensible: _,
-            prototype,
-            keys,
+            shape,
             values,
         } = self;
 
-        keys.mark_values(queues);
+        shape.mark_values(queues);
         values.mark_values(queues);
-        prototype.mark_values(queues);
     }
 
     fn sweep_values(&mut self, compactions: &CompactionLists) {
         let Self {
             extensible: _,
-            prototype,
-            keys,
+            shape,
             values,
         } = self;
-        keys.sweep_values(compactions);
+        shape.sweep_values(compactions);
         values.sweep_values(compactions);
-        prototype.sweep_values(compactions);
+    }
+}
+
+impl SnapshotData for ObjectHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            extensible,
+            shape,
+            values,
+        } = self;
+        extensible.serialize(writer)?;
+        shape.serialize(writer)?;
+        values.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            extensible: SnapshotData::deserialize(reader)?,
+            shape: SnapshotData::deserialize(reader)?,
+            values: SnapshotData::deserialize(reader)?,
+        })
     }
 }
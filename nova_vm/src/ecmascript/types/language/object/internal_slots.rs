@@ -2,7 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use super::{IntoObject, Object, ObjectHeapData, OrdinaryObject};
+use super::{IntoObject, Object, ObjectHeapData, ObjectShape, OrdinaryObject};
 use crate::{
     ecmascript::execution::{Agent, ProtoIntrinsics},
     heap::CreateHeapData,
@@ -40,12 +40,11 @@ where
     fn create_backing_object(self, agent: &mut Agent) -> OrdinaryObject<'static> {
         assert!(self.get_backing_object(agent).is_none());
         let prototype = self.internal_prototype(agent);
-        let backing_object = agent.heap.create(ObjectHeapData {
-            extensible: true,
-            prototype,
-            keys: Default::default(),
-            values: Default::default(),
-        });
+        let shape = ObjectShape::get_root(&mut agent.heap, prototype);
+        let backing_object =
+            agent
+                .heap
+                .create(ObjectHeapData::new(true, shape, Default::default()));
         self.set_backing_object(agent, backing_object);
         backing_object
     }
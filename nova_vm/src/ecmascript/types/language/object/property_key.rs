@@ -22,7 +22,10 @@ use crate::{
         rootable::{HeapRootData, HeapRootRef, Rootable},
         Scoped,
     },
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
+    },
     SmallInteger, SmallString,
 };
 
@@ -365,6 +368,22 @@ impl HeapMarkAndSweep for PropertyKey<'static> {
     }
 }
 
+impl SnapshotData for PropertyKey<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        Value::from(*self).serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        match Value::deserialize(reader)? {
+            Value::Integer(data) => Ok(PropertyKey::Integer(data)),
+            Value::SmallString(data) => Ok(PropertyKey::SmallString(data)),
+            Value::String(data) => Ok(PropertyKey::String(data)),
+            Value::Symbol(data) => Ok(PropertyKey::Symbol(data)),
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 #[repr(u8)]
 pub enum PropertyKeyRootRepr {
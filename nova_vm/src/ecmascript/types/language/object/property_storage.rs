@@ -37,7 +37,7 @@ impl<'a> PropertyStorage<'a> {
         // SAFETY: Key is only used to compare with other keys.
         let key = unsafe { key.into_value_unchecked() };
         match object {
-            Value::Object(object) => agent.heap.elements.has(agent[object].keys, key),
+            Value::Object(object) => agent[object].has(agent, key),
             Value::Array(array) => {
                 if key == BUILTIN_STRING_MEMORY.length.into_value() {
                     return true;
@@ -50,7 +50,7 @@ impl<'a> PropertyStorage<'a> {
                 }
 
                 if let Some(object) = array.object_index {
-                    agent.heap.elements.has(agent[object].keys, key)
+                    agent[object].has(agent, key)
                 } else {
                     false
                 }
@@ -65,18 +65,8 @@ impl<'a> PropertyStorage<'a> {
     pub fn get(self, agent: &Agent, key: PropertyKey) -> Option<PropertyDescriptor> {
         match self.0 {
             Object::Object(object) => {
-                let ObjectHeapData { keys, values, .. } = agent[object];
-                // SAFETY: Key is only used to compare with other keys.
-                let key = unsafe { key.into_value_unchecked() };
-                let result = agent
-                    .heap
-                    .elements
-                    .get(keys)
-                    .iter()
-                    .enumerate()
-                    .find(|(_, element_key)| element_key.unwrap() == key)
-                    .map(|res| res.0);
-                result.map(|index| {
+                let ObjectHeapData { shape, values, .. } = agent[object];
+                shape.get_property_index(agent, key).map(|index| {
                     let value = *agent.heap.elements.get(values).get(index).unwrap();
                     let descriptor = agent.heap.elements.get_descriptor(values, index);
                     ElementDescriptor::to_property_descriptor(descriptor, value)
@@ -89,24 +79,12 @@ impl<'a> PropertyStorage<'a> {
     pub fn set(self, agent: &mut Agent, property_key: PropertyKey, descriptor: PropertyDescriptor) {
         match self.0 {
             Object::Object(object) => {
-                let ObjectHeapData { keys, values, .. } = agent[object];
-                // SAFETY: Key is only used to compare with other keys.
-                let property_key = unsafe { property_key.into_value_unchecked() };
+                let ObjectHeapData { shape, values, .. } = agent[object];
 
                 let value = descriptor.value;
                 let element_descriptor = ElementDescriptor::from_property_descriptor(descriptor);
 
-                let result = agent
-                    .heap
-                    .elements
-                    .get(keys)
-                    .iter()
-                    .enumerate()
-                    .find(|(_, element_key)| element_key.unwrap() == property_key)
-                    .map(|res| res.0);
-                if let Some(index) = result {
-                    let key_entry = agent.heap.elements.get_mut(keys).get_mut(index).unwrap();
-                    *key_entry = Some(property_key);
+                if let Some(index) = shape.get_property_index(agent, property_key) {
                     let value_entry = agent.heap.elements.get_mut(values).get_mut(index).unwrap();
                     *value_entry = value;
                     agent
@@ -114,6 +92,7 @@ impl<'a> PropertyStorage<'a> {
                         .elements
                         .set_descriptor(values, index, element_descriptor);
                 } else {
+                    let shape = shape.add_key(&mut agent.heap, property_key);
                     let Heap {
                         elements, objects, ..
                     } = &mut agent.heap;
@@ -122,9 +101,7 @@ impl<'a> PropertyStorage<'a> {
                         .expect("Invalid ObjectIndex")
                         .as_mut()
                         .expect("Invalid ObjectIndex");
-                    object_heap_data
-                        .keys
-                        .push(elements, Some(property_key), None);
+                    object_heap_data.shape = shape;
                     object_heap_data
                         .values
                         .push(elements, value, element_descriptor);
@@ -137,28 +114,14 @@ impl<'a> PropertyStorage<'a> {
     pub fn remove(self, agent: &mut Agent, property_key: PropertyKey) {
         match self.0 {
             Object::Object(object) => {
-                // SAFETY: Key is only used to compare with other keys.
-                let property_key = unsafe { property_key.into_value_unchecked() };
-
-                let result = agent
-                    .heap
-                    .elements
-                    .get(agent[object].keys)
-                    .iter()
-                    .enumerate()
-                    .find(|(_, element_key)| element_key.unwrap() == property_key)
-                    .map(|res| res.0);
-                if let Some(index) = result {
-                    let Heap {
-                        elements, objects, ..
-                    } = &mut agent.heap;
-                    let object_heap_data = objects
-                        .get_mut(object.get_index())
-                        .expect("Invalid ObjectIndex")
-                        .as_mut()
-                        .expect("Invalid ObjectIndex");
-                    object_heap_data.keys.remove(elements, index);
-                    object_heap_data.values.remove(elements, index);
+                let ObjectHeapData {
+                    shape, mut values, ..
+                } = agent[object];
+                if let Some(index) = shape.get_property_index(agent, property_key) {
+                    let shape = shape.remove_key(&mut agent.heap, &mut values, index);
+                    let object_heap_data = &mut agent[object];
+                    object_heap_data.shape = shape;
+                    object_heap_data.values = values;
                 }
             }
             _ => todo!(),
@@ -0,0 +1,677 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! ## Object shapes
+//!
+//! An object shape holds the \[\[Prototype]] and the ordered property keys
+//! of an ordinary object; the object itself only holds its property values.
+//!
+//! Shapes come in two kinds:
+//!
+//! 1. Shared shapes form transition trees rooted at an empty shape per
+//!    prototype. Adding a property to an object with a shared shape moves the
+//!    object to the child shape for that key, creating it if necessary, so
+//!    that objects created the same way end up with the same shape. Shared
+//!    shapes are never mutated after creation.
+//! 2. Unique shapes belong to a single object and are mutated in place. An
+//!    object moves to a unique shape when a property is removed or its
+//!    prototype changes, and intrinsic objects are created with one. Every
+//!    in-place mutation bumps the shape's version.
+//!
+//! Only ordinary objects can act as the prototype of a shared shape; objects
+//! created with an exotic prototype always get a unique shape.
+//!
+//! ### Dictionary mode
+//!
+//! Objects used as hash maps would otherwise grow huge transition trees and
+//! pay for a linear key search on every access. A shape enters dictionary mode
+//! when it grows past [`DICTIONARY_MODE_KEY_THRESHOLD`] keys or when a unique
+//! shape has had [`DICTIONARY_MODE_REMOVAL_THRESHOLD`] keys removed from it.
+//! A dictionary shape is a unique shape that additionally keeps a hash table
+//! from property key to key index. The keys vector stays the source of truth
+//! for property order, so nothing outside this module needs to know about the
+//! table.
+//!
+//! Removing a key from a dictionary shape does not shift the following keys
+//! and values down. The key and the object's value are left behind as
+//! tombstones, `None` in both vectors, until tombstones make up half of the
+//! keys and the object's vectors are compacted.
+
+use std::{
+    num::NonZeroU32,
+    ops::{Index, IndexMut},
+};
+
+use ahash::AHashMap;
+
+use super::{Object, OrdinaryObject, PropertyKey};
+use crate::{
+    ecmascript::execution::Agent,
+    heap::{
+        element_array::{ElementArrays, ElementsVector},
+        CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, SnapshotData, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
+    },
+};
+
+/// Reference to a heap-allocated object shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub(crate) struct ObjectShape(NonZeroU32);
+
+const OBJECT_SHAPE_OPTION_SIZE_IS_U32: () =
+    assert!(size_of::<ObjectShape>() == size_of::<Option<ObjectShape>>());
+
+/// Number of keys after which a shape switches to dictionary mode.
+pub(crate) const DICTIONARY_MODE_KEY_THRESHOLD: u32 = 64;
+
+/// Number of key removals after which a unique shape switches to dictionary
+/// mode.
+pub(crate) const DICTIONARY_MODE_REMOVAL_THRESHOLD: u32 = 16;
+
+#[derive(Debug)]
+pub(crate) struct ObjectShapeRecord {
+    /// \[\[Prototype]] of objects with this shape.
+    prototype: Option<Object<'static>>,
+    /// Property keys of objects with this shape, in property creation order.
+    keys: ElementsVector,
+    /// Transitions to child shapes that extend this shape by a single key.
+    /// This is `None` for unique shapes.
+    transitions: Option<Vec<(PropertyKey<'static>, ObjectShape)>>,
+    /// Index of each key in `keys`. This is only present for unique shapes in
+    /// dictionary mode.
+    dictionary: Option<AHashMap<PropertyKey<'static>, u32>>,
+    /// Number of keys removed from this shape while it was unique.
+    removed_keys: u32,
+    /// Number of tombstones left in the keys of this dictionary mode shape.
+    tombstones: u32,
+    /// Number of in-place mutations of this shape, wrapping around. Shared
+    /// shapes are never mutated and stay at zero.
+    version: u32,
+}
+
+impl ObjectShapeRecord {
+    /// Create the data of a unique shape. The keys vector is moved into the
+    /// shape.
+    pub(crate) fn new(prototype: Option<Object>, keys: ElementsVector) -> Self {
+        Self {
+            prototype: prototype.map(|p| p.unbind()),
+            keys,
+            transitions: None,
+            dictionary: None,
+            removed_keys: 0,
+            tombstones: 0,
+            version: 0,
+        }
+    }
+}
+
+/// Copies the keys of a shape into a new vector with room for one more key.
+fn copy_keys(elements: &mut ElementArrays, keys: ElementsVector) -> ElementsVector {
+    let mut new_keys = elements.allocate_elements_with_capacity(keys.len() as usize + 1);
+    let old_keys = elements.get(keys).to_vec();
+    for key in old_keys {
+        new_keys.push(elements, key, None);
+    }
+    new_keys
+}
+
+/// Builds the key to index table of a dictionary mode shape.
+fn build_dictionary(
+    elements: &ElementArrays,
+    keys: ElementsVector,
+) -> AHashMap<PropertyKey<'static>, u32> {
+    elements
+        .get(keys)
+        .iter()
+        .enumerate()
+        .filter_map(|(index, key)| {
+            // SAFETY: Keys are all PropertyKeys reinterpreted as Values
+            // without conversion.
+            let key = unsafe { PropertyKey::from_value_unchecked((*key)?) };
+            Some((key, index as u32))
+        })
+        .collect()
+}
+
+/// Copies the keys of a dictionary mode shape and the values of its object
+/// into new vectors without the tombstones.
+fn compact_dictionary(
+    elements: &mut ElementArrays,
+    keys: &mut ElementsVector,
+    values: &mut ElementsVector,
+) {
+    let entries = elements
+        .get(*keys)
+        .iter()
+        .zip(elements.get(*values))
+        .enumerate()
+        .filter(|(_, (key, _))| key.is_some())
+        .map(|(index, (key, value))| (*key, *value, elements.get_descriptor(*values, index)))
+        .collect::<Vec<_>>();
+    let mut new_keys = elements.allocate_elements_with_capacity(entries.len());
+    let mut new_values = elements.allocate_elements_with_capacity(entries.len());
+    for (key, value, descriptor) in entries {
+        new_keys.push(elements, key, None);
+        new_values.push(elements, value, descriptor);
+    }
+    *keys = new_keys;
+    *values = new_values;
+}
+
+impl ObjectShape {
+    pub(crate) fn get_index(self) -> usize {
+        self.0.get() as usize - 1
+    }
+
+    /// Get the empty root shape of objects with the given prototype.
+    pub(crate) fn get_root(heap: &mut Heap, prototype: Option<Object>) -> Self {
+        let root_key = match prototype {
+            None => None,
+            Some(Object::Object(prototype)) => Some(prototype.unbind()),
+            Some(prototype) => {
+                return heap.create(ObjectShapeRecord::new(Some(prototype), Default::default()))
+            }
+        };
+        if let Some(shape) = heap.object_shape_roots.get(&root_key) {
+            return *shape;
+        }
+        let shape = heap.create(ObjectShapeRecord {
+            prototype: prototype.map(|p| p.unbind()),
+            keys: Default::default(),
+            transitions: Some(Vec::new()),
+            dictionary: None,
+            removed_keys: 0,
+            tombstones: 0,
+            version: 0,
+        });
+        heap.object_shape_roots.insert(root_key, shape);
+        shape
+    }
+
+    /// Get the shape of an object with the given prototype and the given
+    /// property keys, in order.
+    pub(crate) fn from_keys<'a>(
+        heap: &mut Heap,
+        prototype: Option<Object>,
+        keys: impl IntoIterator<Item = PropertyKey<'a>>,
+    ) -> Self {
+        let mut shape = Self::get_root(heap, prototype);
+        for key in keys {
+            shape = shape.add_key(heap, key);
+        }
+        shape
+    }
+
+    pub(crate) fn prototype(self, agent: &Agent) -> Option<Object<'static>> {
+        agent[self].prototype
+    }
+
+    pub(crate) fn keys(self, agent: &Agent) -> ElementsVector {
+        agent[self].keys
+    }
+
+    pub(crate) fn is_shared(self, agent: &Agent) -> bool {
+        agent[self].transitions.is_some()
+    }
+
+    /// Get the version of this shape, which changes whenever the shape is
+    /// mutated in place.
+    pub(crate) fn version(self, agent: &Agent) -> u32 {
+        agent[self].version
+    }
+
+    /// Find the index of a property key in this shape. The same index holds
+    /// the property's value in the object's values vector.
+    pub(crate) fn get_property_index(self, agent: &Agent, key: PropertyKey) -> Option<usize> {
+        if let Some(dictionary) = &agent[self].dictionary {
+            return dictionary.get(&key.unbind()).map(|index| *index as usize);
+        }
+        // SAFETY: Key is only used to compare with other keys.
+        let key = unsafe { key.into_value_unchecked() };
+        agent
+            .heap
+            .elements
+            .get(agent[self].keys)
+            .iter()
+            .position(|element_key| *element_key == Some(key))
+    }
+
+    /// Get the shape that results from adding a key to the end of this
+    /// shape's keys.
+    ///
+    /// A unique shape is updated in place and returned as-is. Extending a
+    /// shared shape past [`DICTIONARY_MODE_KEY_THRESHOLD`] keys results in a
+    /// new dictionary mode shape instead of a new transition.
+    pub(crate) fn add_key(self, heap: &mut Heap, key: PropertyKey) -> Self {
+        let Heap {
+            elements,
+            object_shapes,
+            ..
+        } = heap;
+        let record = &mut object_shapes[self.get_index()];
+        // SAFETY: The key is stored in a key vector, never read as a value.
+        let key_value = unsafe { key.into_value_unchecked() };
+        let Some(transitions) = &record.transitions else {
+            record.keys.push(elements, Some(key_value), None);
+            if let Some(dictionary) = &mut record.dictionary {
+                dictionary.insert(key.unbind(), record.keys.len() - 1);
+            } else if record.keys.len() > DICTIONARY_MODE_KEY_THRESHOLD {
+                record.dictionary = Some(build_dictionary(elements, record.keys));
+            }
+            record.version = record.version.wrapping_add(1);
+            return self;
+        };
+        if let Some((_, child)) = transitions.iter().find(|(k, _)| *k == key.unbind()) {
+            return *child;
+        }
+        let prototype = record.prototype;
+        let mut keys = copy_keys(elements, record.keys);
+        keys.push(elements, Some(key_value), None);
+        if keys.len() > DICTIONARY_MODE_KEY_THRESHOLD {
+            let mut record = ObjectShapeRecord::new(prototype, keys);
+            record.dictionary = Some(build_dictionary(elements, keys));
+            return heap.create(record);
+        }
+        let child = heap.create(ObjectShapeRecord {
+            prototype,
+            keys,
+            transitions: Some(Vec::new()),
+            dictionary: None,
+            removed_keys: 0,
+            tombstones: 0,
+            version: 0,
+        });
+        heap.object_shapes[self.get_index()]
+            .transitions
+            .as_mut()
+            .unwrap()
+            .push((key.unbind(), child));
+        child
+    }
+
+    /// Get the shape that results from removing the key at `index` from this
+    /// shape, and remove the value at `index` from `values`, the values of an
+    /// object with this shape. The result is always a unique shape, and
+    /// switches to dictionary mode after [`DICTIONARY_MODE_REMOVAL_THRESHOLD`]
+    /// removals.
+    ///
+    /// In dictionary mode the key and value are replaced with tombstones, and
+    /// both vectors are compacted once half of the keys are tombstones.
+    pub(crate) fn remove_key(
+        self,
+        heap: &mut Heap,
+        values: &mut ElementsVector,
+        index: usize,
+    ) -> Self {
+        let shape = self.make_unique(heap);
+        let Heap {
+            elements,
+            object_shapes,
+            ..
+        } = heap;
+        let record = &mut object_shapes[shape.get_index()];
+        if let Some(dictionary) = &mut record.dictionary {
+            // SAFETY: Keys are all PropertyKeys reinterpreted as Values
+            // without conversion.
+            let key = unsafe {
+                PropertyKey::from_value_unchecked(
+                    elements.get_mut(record.keys)[index].take().unwrap(),
+                )
+            };
+            dictionary.remove(&key);
+            elements.get_mut(*values)[index] = None;
+            elements.set_descriptor(*values, index, None);
+            record.tombstones += 1;
+            if record.tombstones >= record.keys.len() / 2 {
+                compact_dictionary(elements, &mut record.keys, values);
+                record.dictionary = Some(build_dictionary(elements, record.keys));
+                record.tombstones = 0;
+            }
+        } else {
+            record.keys.remove(elements, index);
+            values.remove(elements, index);
+            record.removed_keys = record.removed_keys.saturating_add(1);
+            if record.removed_keys >= DICTIONARY_MODE_REMOVAL_THRESHOLD {
+                record.dictionary = Some(build_dictionary(elements, record.keys));
+            }
+        }
+        record.version = record.version.wrapping_add(1);
+        shape
+    }
+
+    /// Get the shape that results from changing the prototype of this shape.
+    pub(crate) fn set_prototype(self, heap: &mut Heap, prototype: Option<Object>) -> Self {
+        let record = &heap.object_shapes[self.get_index()];
+        if record.prototype == prototype.map(|p| p.unbind()) {
+            return self;
+        }
+        if record.transitions.is_some() && record.keys.is_empty() {
+            return Self::get_root(heap, prototype);
+        }
+        let shape = self.make_unique(heap);
+        let record = &mut heap.object_shapes[shape.get_index()];
+        record.prototype = prototype.map(|p| p.unbind());
+        record.version = record.version.wrapping_add(1);
+        shape
+    }
+
+    /// Reserve space for `new_len` keys in a unique shape. Shared shapes copy
+    /// their keys when extended, so this does nothing for them.
+    pub(crate) fn reserve(self, heap: &mut Heap, new_len: u32) {
+        let Heap {
+            elements,
+            object_shapes,
+            ..
+        } = heap;
+        let record = &mut object_shapes[self.get_index()];
+        if record.transitions.is_none() {
+            record.keys.reserve(elements, new_len);
+        }
+    }
+
+    /// Get a unique shape equal to this shape.
+    fn make_unique(self, heap: &mut Heap) -> Self {
+        let record = &heap.object_shapes[self.get_index()];
+        if record.transitions.is_none() {
+            return self;
+        }
+        let prototype = record.prototype;
+        let keys = copy_keys(&mut heap.elements, record.keys);
+        heap.create(ObjectShapeRecord::new(prototype, keys))
+    }
+}
+
+impl Index<ObjectShape> for Agent {
+    type Output = ObjectShapeRecord;
+
+    fn index(&self, index: ObjectShape) -> &Self::Output {
+        self.heap
+            .object_shapes
+            .get(index.get_index())
+            .expect("ObjectShape out of bounds")
+    }
+}
+
+impl IndexMut<ObjectShape> for Agent {
+    fn index_mut(&mut self, index: ObjectShape) -> &mut Self::Output {
+        self.heap
+            .object_shapes
+            .get_mut(index.get_index())
+            .expect("ObjectShape out of bounds")
+    }
+}
+
+impl CreateHeapData<ObjectShapeRecord, ObjectShape> for Heap {
+    fn create(&mut self, data: ObjectShapeRecord) -> ObjectShape {
+        self.object_shapes.push(data);
+        let index = u32::try_from(self.object_shapes.len()).expect("ObjectShapes overflowed");
+        // SAFETY: After pushing to object_shapes, the vector cannot be empty.
+        ObjectShape(unsafe { NonZeroU32::new_unchecked(index) })
+    }
+}
+
+impl HeapMarkAndSweep for ObjectShape {
+    fn mark_values(&self, queues: &mut WorkQueues) {
+        queues.object_shapes.push(*self);
+    }
+
+    fn sweep_values(&mut self, compactions: &CompactionLists) {
+        compactions
+            .object_shapes
+            .shift_non_zero_u32_index(&mut self.0);
+    }
+}
+
+impl SnapshotData for ObjectShape {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
+impl HeapMarkAndSweep for ObjectShapeRecord {
+    fn mark_values(&self, queues: &mut WorkQueues) {
+        let Self {
+            prototype,
+            keys,
+            transitions,
+            // The dictionary holds the same keys as the keys vector.
+            dictionary: _,
+            removed_keys: _,
+            tombstones: _,
+            version: _,
+        } = self;
+        prototype.mark_values(queues);
+        keys.mark_values(queues);
+        if let Some(transitions) = transitions {
+            for (key, child) in transitions {
+                key.mark_values(queues);
+                child.mark_values(queues);
+            }
+        }
+    }
+
+    fn sweep_values(&mut self, compactions: &CompactionLists) {
+        let Self {
+            prototype,
+            keys,
+            transitions,
+            dictionary,
+            removed_keys: _,
+            tombstones: _,
+            version: _,
+        } = self;
+        prototype.sweep_values(compactions);
+        keys.sweep_values(compactions);
+        if let Some(transitions) = transitions {
+            for (key, child) in transitions {
+                key.sweep_values(compactions);
+                child.sweep_values(compactions);
+            }
+        }
+        if let Some(dictionary) = dictionary {
+            // Compaction changes the hash of the keys, so the table has to be
+            // rebuilt.
+            *dictionary = dictionary
+                .drain()
+                .map(|(mut key, index)| {
+                    key.sweep_values(compactions);
+                    (key, index)
+                })
+                .collect();
+        }
+    }
+}
+
+impl SnapshotData for ObjectShapeRecord {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            prototype,
+            keys,
+            transitions,
+            dictionary,
+            removed_keys,
+            tombstones,
+            version,
+        } = self;
+        prototype.serialize(writer)?;
+        keys.serialize(writer)?;
+        transitions.serialize(writer)?;
+        dictionary.serialize(writer)?;
+        removed_keys.serialize(writer)?;
+        tombstones.serialize(writer)?;
+        version.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            prototype: SnapshotData::deserialize(reader)?,
+            keys: SnapshotData::deserialize(reader)?,
+            transitions: SnapshotData::deserialize(reader)?,
+            dictionary: SnapshotData::deserialize(reader)?,
+            removed_keys: SnapshotData::deserialize(reader)?,
+            tombstones: SnapshotData::deserialize(reader)?,
+            version: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
+
+/// Drop the root shapes that were not marked and compact the rest. A root
+/// shape keeps its prototype alive, so the prototype of a retained entry is
+/// always alive as well.
+pub(crate) fn sweep_object_shape_roots(
+    roots: &mut AHashMap<Option<OrdinaryObject<'static>>, ObjectShape>,
+    compactions: &CompactionLists,
+    marks: &[bool],
+) {
+    *roots = roots
+        .drain()
+        .filter(|(_, shape)| marks[shape.get_index()])
+        .map(|(mut prototype, mut shape)| {
+            prototype.sweep_values(compactions);
+            shape.sweep_values(compactions);
+            (prototype, shape)
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DICTIONARY_MODE_KEY_THRESHOLD, DICTIONARY_MODE_REMOVAL_THRESHOLD};
+    use crate::{
+        ecmascript::types::{PropertyKey, Value},
+        test_utils::{eval_script, TestAgent},
+    };
+
+    /// Evaluate a script resulting in an object and return whether its shape
+    /// is in dictionary mode, checking that the table agrees with the keys.
+    fn is_dictionary_mode(agent: &mut TestAgent, source_text: &str) -> bool {
+        agent.run(|agent, gc| {
+            let Value::Object(object) = eval_script(agent, source_text, gc).unwrap() else {
+                panic!("Expected an ordinary object");
+            };
+            let shape = agent[object].shape;
+            let Some(dictionary) = &agent[shape].dictionary else {
+                return false;
+            };
+            assert!(!shape.is_shared(agent));
+            let keys = agent.heap.elements.get(shape.keys(agent));
+            let tombstones = keys.iter().filter(|key| key.is_none()).count();
+            assert_eq!(tombstones, agent[shape].tombstones as usize);
+            assert_eq!(dictionary.len() + tombstones, keys.len());
+            for (index, key) in keys.iter().enumerate() {
+                let Some(key) = key else {
+                    continue;
+                };
+                // SAFETY: Keys are all PropertyKeys reinterpreted as Values.
+                let key = unsafe { PropertyKey::from_value_unchecked(*key) };
+                assert_eq!(shape.get_property_index(agent, key), Some(index));
+            }
+            true
+        })
+    }
+
+    #[test]
+    fn dictionary_mode_past_key_threshold() {
+        assert_eq!(DICTIONARY_MODE_KEY_THRESHOLD, 64);
+        let mut agent = TestAgent::new();
+        agent.eval("function make(n) { const o = {}; for (let i = 0; i < n; i++) o['k' + i] = i; return o; }");
+        assert!(!is_dictionary_mode(&mut agent, "var a = make(64); a"));
+        assert!(is_dictionary_mode(&mut agent, "var b = make(65); b"));
+        assert!(is_dictionary_mode(&mut agent, "b.extra = 'x'; b"));
+        assert_eq!(agent.eval("b.k0 + b.k64 + b.extra"), "64x");
+        assert_eq!(agent.eval("a.k63 + Object.keys(b).length"), "129");
+        // Shared shapes stop growing the transition tree at the threshold.
+        assert_eq!(agent.eval("make(10).k9 + make(10).k9"), "18");
+        assert!(is_dictionary_mode(&mut agent, "make(100)"));
+    }
+
+    #[test]
+    fn dictionary_mode_after_removals() {
+        assert_eq!(DICTIONARY_MODE_REMOVAL_THRESHOLD, 16);
+        let mut agent = TestAgent::new();
+        agent.eval("var o = {}; for (let i = 0; i < 20; i++) o['k' + i] = i;");
+        assert!(!is_dictionary_mode(
+            &mut agent,
+            "for (let i = 0; i < 15; i++) delete o['k' + i]; o"
+        ));
+        assert!(is_dictionary_mode(&mut agent, "delete o.k15; o"));
+        assert_eq!(agent.eval("Object.keys(o).join()"), "k16,k17,k18,k19");
+        assert!(is_dictionary_mode(
+            &mut agent,
+            "delete o.k17; o.k0 = 'new'; o"
+        ));
+        assert_eq!(agent.eval("Object.keys(o).join()"), "k16,k18,k19,k0");
+        assert_eq!(
+            agent.eval("[o.k16, o.k17, o.k19, o.k0].join()"),
+            "16,,19,new"
+        );
+    }
+
+    #[test]
+    fn dictionary_removals_leave_tombstones() {
+        let mut agent = TestAgent::new();
+        agent.eval("var o = {}; for (let i = 0; i < 100; i++) o['k' + i] = i;");
+        let tombstones = |agent: &mut TestAgent| {
+            agent.run(|agent, gc| {
+                let Value::Object(object) = eval_script(agent, "o", gc).unwrap() else {
+                    unreachable!()
+                };
+                let shape = agent[object].shape;
+                (agent[shape].tombstones, shape.keys(agent).len())
+            })
+        };
+        assert!(is_dictionary_mode(
+            &mut agent,
+            "for (let i = 0; i < 49; i++) delete o['k' + i * 2]; o"
+        ));
+        assert_eq!(tombstones(&mut agent), (49, 100));
+        assert_eq!(
+            agent.eval("Object.keys(o).slice(0, 3).join() + ' ' + Object.keys(o).length"),
+            "k1,k3,k5 51"
+        );
+        assert_eq!(agent.eval("[o.k0, o.k1, o.k98, o.k99].join()"), ",1,98,99");
+        // Tombstones are compacted away once they make up half of the keys.
+        assert!(is_dictionary_mode(&mut agent, "delete o.k98; o"));
+        assert_eq!(tombstones(&mut agent), (0, 50));
+        assert!(is_dictionary_mode(
+            &mut agent,
+            "o.k0 = 'new'; delete o.k1; o"
+        ));
+        assert_eq!(
+            agent.eval("Object.keys(o).slice(-2).join() + ' ' + [o.k0, o.k1, o.k99].join()"),
+            "k99,k0 new,,99"
+        );
+        assert_eq!(
+            agent.eval(
+                "Object.defineProperty(o, 'k3', { get() { return 'get'; } }); delete o.k5; o.k3"
+            ),
+            "get"
+        );
+    }
+
+    #[test]
+    fn dictionary_survives_garbage_collection() {
+        let mut agent = TestAgent::new();
+        agent.eval(
+            "var garbage = [];
+            for (let i = 0; i < 100; i++) garbage.push('garbage' + i);
+            var o = {};
+            // Long enough to be heap strings that move in compaction.
+            for (let i = 0; i < 100; i++) o['heap_string_key' + i] = i;
+            garbage = undefined;",
+        );
+        agent.gc();
+        assert!(is_dictionary_mode(&mut agent, "o"));
+        assert_eq!(agent.eval("o.heap_string_key0 + o.heap_string_key99"), "99");
+        assert_eq!(
+            agent.eval("o.heap_string_key42 = 'x'; Object.keys(o).length + o.heap_string_key42"),
+            "100x"
+        );
+    }
+}
@@ -8,6 +8,7 @@ mod data;
 use std::{
     hash::Hash,
     ops::{Index, IndexMut},
+    sync::Arc,
 };
 
 use super::{
@@ -23,13 +24,15 @@ use crate::{
     },
     heap::{
         indexes::{GetBaseIndexMut, IntoBaseIndex, StringIndex},
-        CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, PrimitiveHeap, WorkQueues,
+        CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, OwnedAllocations, PrimitiveHeap,
+        SnapshotData, SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
     },
     SmallInteger, SmallString,
 };
 
 pub use data::StringHeapData;
-use wtf8::Wtf8Buf;
+pub(crate) use data::{RopeNode, RopeString};
+use wtf8::{Wtf8, Wtf8Buf};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
@@ -328,6 +331,23 @@ impl<'a> String<'a> {
         let strings = strings.as_ref();
         let mut status = if strings.len() > 1 {
             let len = strings.iter().fold(0usize, |a, s| a + s.len(agent));
+            if len >= RopeString::MIN_LENGTH
+                && strings.iter().filter(|s| !s.is_empty_string()).count() > 1
+            {
+                // Long results are built lazily, so that repeatedly appending
+                // to a string does not copy it every time.
+                let pieces = strings
+                    .iter()
+                    .filter(|s| !s.is_empty_string())
+                    .map(|string| match string {
+                        String::String(heap_string) => agent[*heap_string].as_rope_node(),
+                        String::SmallString(small_string) => {
+                            Arc::new(RopeNode::Owned(Wtf8Buf::from_str(small_string.as_str())))
+                        }
+                    })
+                    .collect();
+                return agent.heap.create(RopeString::new(pieces)).bind(gc);
+            }
             if len > 7 {
                 Status::String(Wtf8Buf::with_capacity(len))
             } else {
@@ -402,7 +422,7 @@ impl<'a> String<'a> {
                 let str_slice = unsafe { std::str::from_utf8_unchecked(&data[..len]) };
                 SmallString::from_str_unchecked(str_slice).into()
             }
-            Status::String(string) => agent.heap.create(string.into_string().unwrap()).bind(gc),
+            Status::String(string) => agent.heap.create(string).bind(gc),
         }
     }
 
@@ -429,6 +449,14 @@ impl<'a> String<'a> {
         }
     }
 
+    /// The UTF-16 code units of the string.
+    pub fn to_utf16(
+        self,
+        agent: &impl Index<HeapString<'static>, Output = StringHeapData>,
+    ) -> Vec<u16> {
+        self.as_wtf8(agent).to_ill_formed_utf16().collect()
+    }
+
     // TODO: This should return a wtf8::CodePoint.
     pub fn utf16_char(
         self,
@@ -488,6 +516,18 @@ impl<'a> String<'a> {
         }
     }
 
+    /// The WTF-8 contents of the string. Unlike `as_str`, this does not
+    /// assume that the string contains no lone surrogates.
+    pub fn as_wtf8<'string, 'agent: 'string>(
+        &'string self,
+        agent: &'agent impl Index<HeapString<'static>, Output = StringHeapData>,
+    ) -> &'string Wtf8 {
+        match self {
+            String::String(s) => agent[s.unbind()].as_wtf8(),
+            String::SmallString(s) => Wtf8::from_str(s.as_str()),
+        }
+    }
+
     /// If x and y have the same length and the same code units in the same
     /// positions, return true; otherwise, return false.
     pub fn eq(
@@ -558,6 +598,18 @@ impl<'gc> String<'gc> {
         agent.heap.create(string).bind(gc)
     }
 
+    /// Create a String from WTF-8 data. Unlike [`String::from_string`], the
+    /// data may contain lone surrogates.
+    pub fn from_wtf8_buf(agent: &mut Agent, buf: Wtf8Buf, gc: NoGcScope<'gc, '_>) -> Self {
+        agent.heap.create(buf).bind(gc)
+    }
+
+    /// Create a String from UTF-16 code units. Lone surrogates are replaced
+    /// with U+FFFD REPLACEMENT CHARACTER.
+    pub fn from_utf16_lossy(agent: &mut Agent, units: &[u16], gc: NoGcScope<'gc, '_>) -> Self {
+        Self::from_string(agent, std::string::String::from_utf16_lossy(units), gc)
+    }
+
     pub fn from_static_str(agent: &mut Agent, str: &'static str, _gc: NoGcScope<'gc, '_>) -> Self {
         if let Ok(value) = String::try_from(str) {
             value
@@ -566,6 +618,18 @@ impl<'gc> String<'gc> {
             unsafe { agent.heap.alloc_static_str(str) }
         }
     }
+
+    /// Get the interned string equal to this string. Rope strings are not
+    /// interned, so they must go through this before they can be compared by
+    /// identity, eg. as property keys.
+    pub(crate) fn intern(self, agent: &mut Agent, gc: NoGcScope<'gc, '_>) -> Self {
+        match self {
+            String::String(heap_string) => {
+                String::String(agent.heap.intern_rope(heap_string.unbind()).bind(gc))
+            }
+            String::SmallString(_) => self,
+        }
+    }
 }
 
 impl Scoped<'_, String<'static>> {
@@ -584,6 +648,7 @@ impl Scoped<'_, String<'static>> {
 
 impl CreateHeapData<(StringHeapData, u64), String<'static>> for Heap {
     fn create(&mut self, (data, hash): (StringHeapData, u64)) -> String<'static> {
+        self.owned_bytes += data.owned_bytes();
         self.strings.push(Some(data));
         let index = StringIndex::last(&self.strings);
         let heap_string = HeapString(index);
@@ -593,6 +658,17 @@ impl CreateHeapData<(StringHeapData, u64), String<'static>> for Heap {
     }
 }
 
+impl CreateHeapData<RopeString, String<'static>> for Heap {
+    fn create(&mut self, data: RopeString) -> String<'static> {
+        // Ropes are not added to the lookup table: They are interned when
+        // used as property keys.
+        let data = StringHeapData::from_rope(data);
+        self.owned_bytes += data.owned_bytes();
+        self.strings.push(Some(data));
+        String::String(HeapString(StringIndex::last(&self.strings)))
+    }
+}
+
 impl HeapMarkAndSweep for String<'static> {
     #[inline(always)]
     fn mark_values(&self, queues: &mut WorkQueues) {
@@ -609,6 +685,31 @@ impl HeapMarkAndSweep for String<'static> {
     }
 }
 
+impl SnapshotData for String<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        Value::from(*self).serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Self::try_from(Value::deserialize(reader)?).map_err(|_| SnapshotError::Malformed)
+    }
+}
+
+impl SnapshotData for SmallString {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        (self.len() as u8).serialize(writer)?;
+        writer.write_bytes(self.as_bytes());
+        Ok(())
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let len = u8::deserialize(reader)?;
+        let bytes = reader.read_bytes(len as usize)?;
+        let str = std::str::from_utf8(bytes).map_err(|_| SnapshotError::Malformed)?;
+        Self::try_from(str).map_err(|_| SnapshotError::Malformed)
+    }
+}
+
 impl HeapMarkAndSweep for HeapString<'static> {
     fn mark_values(&self, queues: &mut WorkQueues) {
         queues.strings.push(*self);
@@ -619,6 +720,16 @@ impl HeapMarkAndSweep for HeapString<'static> {
     }
 }
 
+impl SnapshotData for HeapString<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl Rootable for String<'static> {
     type RootRepr = StringRootRepr;
 
@@ -2,11 +2,14 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use std::{cell::OnceCell, hash::Hash, num::NonZeroUsize};
+use std::{cell::OnceCell, hash::Hash, sync::Arc};
 
-use wtf8::{Wtf8, Wtf8Buf};
+use wtf8::{CodePoint, Wtf8, Wtf8Buf};
 
-use crate::heap::{CompactionLists, HeapMarkAndSweep, WorkQueues};
+use crate::heap::{
+    CompactionLists, HeapMarkAndSweep, OwnedAllocations, SnapshotData, SnapshotError,
+    SnapshotReader, SnapshotResult, SnapshotWriter, WorkQueues,
+};
 
 #[derive(Debug, Clone)]
 pub struct StringHeapData {
@@ -24,12 +27,7 @@ impl PartialEq for StringHeapData {
                 return true;
             }
         }
-        match (&self.data, &other.data) {
-            (StringBuffer::Owned(a), StringBuffer::Owned(b)) => a == b,
-            (StringBuffer::Owned(a), StringBuffer::Static(b)) => a == b,
-            (StringBuffer::Static(a), StringBuffer::Owned(b)) => a == b,
-            (StringBuffer::Static(a), StringBuffer::Static(b)) => a == b,
-        }
+        self.as_wtf8() == other.as_wtf8()
     }
 }
 impl Eq for StringHeapData {}
@@ -38,18 +36,49 @@ impl Eq for StringHeapData {}
 pub(crate) enum IndexMapping {
     Ascii,
     NonAscii {
-        /// Mapping from UTF-16 indices to indices in the UTF-8 representation.
-        /// When the UTF-16 character would be the second character in a
-        /// surrogate pair, it maps to None because there is no corresponding
-        /// UTF-8 index.
-        mapping: Box<[Option<NonZeroUsize>]>,
+        /// UTF-16 length of the string.
+        utf16_len: usize,
+        /// Code point boundaries sampled every [`INDEX_SAMPLE_STRIDE`] UTF-16
+        /// code units. Sample `n` is at the first code point starting at or
+        /// after UTF-16 index `n * INDEX_SAMPLE_STRIDE`; indices between
+        /// samples are found by walking the UTF-8 data from a sample.
+        samples: Box<[IndexSample]>,
     },
 }
 
+/// Number of UTF-16 code units between two samples of an index mapping. This
+/// bounds the work of a UTF-16 index lookup while keeping the mapping at a
+/// fraction of the size of the string itself.
+const INDEX_SAMPLE_STRIDE: usize = 32;
+
+/// Matching UTF-16 and UTF-8 indices of a code point boundary.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IndexSample {
+    utf16: usize,
+    utf8: usize,
+}
+
+/// UTF-8 and UTF-16 lengths of each code point of a WTF-8 string.
+///
+/// NOTE: We iterate over WTF-8 code points rather than chars so that lone
+/// surrogates, which take up three bytes and a single UTF-16 code unit, are
+/// mapped as well.
+fn code_point_lengths(wtf8: &Wtf8) -> impl Iterator<Item = (usize, usize)> + '_ {
+    wtf8.code_points().map(|cp| match cp.to_u32() {
+        0..0x80 => (1, 1),
+        0x80..0x800 => (2, 1),
+        0x800..0x10000 => (3, 1),
+        _ => (4, 2),
+    })
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum StringBuffer {
     Owned(Wtf8Buf),
     Static(&'static Wtf8),
+    /// Lazy concatenation of other strings. Rope strings are not interned in
+    /// the heap's string lookup table.
+    Rope(RopeString),
 }
 
 impl Hash for StringBuffer {
@@ -57,8 +86,111 @@ impl Hash for StringBuffer {
         match self {
             StringBuffer::Owned(wtf8_buf) => wtf8_buf.hash(state),
             StringBuffer::Static(wtf8) => wtf8.hash(state),
+            StringBuffer::Rope(rope) => rope.as_wtf8().hash(state),
+        }
+    }
+}
+
+/// A string built by concatenation whose contents are only copied into a
+/// contiguous buffer when they are first needed.
+///
+/// The pieces of a rope own their data rather than referring to other heap
+/// strings, so a rope can be flattened without access to the heap and never
+/// keeps other strings alive.
+#[derive(Debug, Clone)]
+pub(crate) struct RopeString {
+    node: Arc<RopeNode>,
+    flat: OnceCell<Wtf8Buf>,
+}
+
+/// Heap strings are swept on a separate thread, so rope pieces must be
+/// shareable across threads.
+const _STRING_HEAP_DATA_IS_SEND: () = {
+    const fn assert_send<T: Send>() {}
+    assert_send::<StringHeapData>()
+};
+
+#[derive(Debug)]
+pub(crate) enum RopeNode {
+    Owned(Wtf8Buf),
+    Static(&'static Wtf8),
+    Concat {
+        pieces: Vec<Arc<RopeNode>>,
+        len: usize,
+    },
+}
+
+impl RopeNode {
+    fn len(&self) -> usize {
+        match self {
+            RopeNode::Owned(buf) => buf.len(),
+            RopeNode::Static(buf) => buf.len(),
+            RopeNode::Concat { len, .. } => *len,
+        }
+    }
+}
+
+impl Drop for RopeNode {
+    fn drop(&mut self) {
+        // Repeated concatenation builds very deep ropes: Drop them without
+        // recursion.
+        let RopeNode::Concat { pieces, .. } = self else {
+            return;
+        };
+        let mut stack = std::mem::take(pieces);
+        while let Some(piece) = stack.pop() {
+            if let Ok(RopeNode::Concat { pieces, .. }) = Arc::try_unwrap(piece).as_mut() {
+                stack.append(pieces);
+            }
+        }
+    }
+}
+
+impl RopeString {
+    /// The minimum byte length of a concatenation that is stored as a rope.
+    /// Shorter results are copied eagerly.
+    pub(crate) const MIN_LENGTH: usize = 256;
+
+    /// Create a rope out of the given pieces, in order.
+    pub(crate) fn new(pieces: Vec<Arc<RopeNode>>) -> Self {
+        let len = pieces.iter().map(|piece| piece.len()).sum();
+        assert!(
+            len <= StringHeapData::MAX_UTF8_LENGTH,
+            "String is too long."
+        );
+        Self {
+            node: Arc::new(RopeNode::Concat { pieces, len }),
+            flat: OnceCell::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.node.len()
+    }
+
+    fn as_wtf8(&self) -> &Wtf8 {
+        match &*self.node {
+            RopeNode::Owned(buf) => buf,
+            RopeNode::Static(buf) => buf,
+            RopeNode::Concat { len, .. } => self.flat.get_or_init(|| {
+                let mut buf = Wtf8Buf::with_capacity(*len);
+                let mut stack = vec![&self.node];
+                while let Some(node) = stack.pop() {
+                    match &**node {
+                        RopeNode::Owned(piece) => buf.push_wtf8(piece),
+                        RopeNode::Static(piece) => buf.push_wtf8(piece),
+                        RopeNode::Concat { pieces, .. } => stack.extend(pieces.iter().rev()),
+                    }
+                }
+                buf
+            }),
         }
     }
+
+    /// Take the flattened contents of this rope, if any.
+    fn take_flat(&mut self) -> Option<Wtf8Buf> {
+        self.flat.take()
+    }
 }
 
 impl StringHeapData {
@@ -72,42 +204,64 @@ impl StringHeapData {
         match &self.data {
             StringBuffer::Owned(buf) => buf.len(),
             StringBuffer::Static(buf) => buf.len(),
+            StringBuffer::Rope(rope) => rope.len(),
         }
     }
 
-    fn index_mapping(&self) -> &IndexMapping {
-        self.mapping.get_or_init(|| {
-            let mut iter = self.as_str().char_indices();
+    /// Returns true if this is a rope string, which is not interned.
+    pub(crate) fn is_rope(&self) -> bool {
+        matches!(self.data, StringBuffer::Rope(_))
+    }
 
-            let Some((idx, ch)) = iter.find(|(_, ch)| !ch.is_ascii()) else {
-                return IndexMapping::Ascii;
-            };
+    /// Get a rope piece holding the contents of this string. Concatenating a
+    /// rope shares its pieces instead of copying them.
+    pub(crate) fn as_rope_node(&self) -> Arc<RopeNode> {
+        match &self.data {
+            StringBuffer::Owned(buf) => Arc::new(RopeNode::Owned(buf.clone())),
+            StringBuffer::Static(buf) => Arc::new(RopeNode::Static(buf)),
+            StringBuffer::Rope(rope) => rope.node.clone(),
+        }
+    }
 
-            // All indices less than `idx` map to ASCII bytes, so all UTF-16
-            // indices less *or equal* than `idx` map to that same UTF-8 index
-            let mut mapping: Vec<Option<NonZeroUsize>> = (0..=idx).map(NonZeroUsize::new).collect();
+    /// Turn a rope string into a flat string. This is done before the string
+    /// is interned.
+    pub(crate) fn flatten(&mut self) {
+        let StringBuffer::Rope(rope) = &mut self.data else {
+            return;
+        };
+        rope.as_wtf8();
+        let flat = rope.take_flat().unwrap_or_else(|| {
+            // A rope with a single piece is not flattened, copy the piece.
+            let mut flat = Wtf8Buf::with_capacity(rope.len());
+            flat.push_wtf8(rope.as_wtf8());
+            flat
+        });
+        self.data = StringBuffer::Owned(flat);
+    }
 
-            if ch.len_utf16() != 1 {
-                debug_assert_eq!(ch.len_utf16(), 2);
-                mapping.push(None);
+    fn index_mapping(&self) -> &IndexMapping {
+        self.mapping.get_or_init(|| {
+            let wtf8 = self.as_wtf8();
+            if wtf8.as_str().is_some_and(str::is_ascii) {
+                return IndexMapping::Ascii;
             }
 
-            for (idx, ch) in iter {
-                assert_ne!(idx, 0);
-                mapping.push(NonZeroUsize::new(idx));
-                if ch.len_utf16() != 1 {
-                    debug_assert_eq!(ch.len_utf16(), 2);
-                    mapping.push(None);
+            let mut samples = Vec::with_capacity(wtf8.len() / INDEX_SAMPLE_STRIDE + 1);
+            let mut utf8 = 0;
+            let mut utf16 = 0;
+            for (utf8_len, utf16_len) in code_point_lengths(wtf8) {
+                if utf16 >= samples.len() * INDEX_SAMPLE_STRIDE {
+                    samples.push(IndexSample { utf16, utf8 });
                 }
+                utf8 += utf8_len;
+                utf16 += utf16_len;
             }
 
-            assert!(
-                mapping.len() <= Self::MAX_UTF16_LENGTH,
-                "String is too long."
-            );
+            assert!(utf16 <= Self::MAX_UTF16_LENGTH, "String is too long.");
 
             IndexMapping::NonAscii {
-                mapping: mapping.into_boxed_slice(),
+                utf16_len: utf16,
+                samples: samples.into_boxed_slice(),
             }
         })
     }
@@ -115,23 +269,14 @@ impl StringHeapData {
     pub fn utf16_len(&self) -> usize {
         match self.index_mapping() {
             IndexMapping::Ascii => self.len(),
-            IndexMapping::NonAscii { mapping } => mapping.len(),
+            IndexMapping::NonAscii { utf16_len, .. } => *utf16_len,
         }
     }
 
     // TODO: This should return a wtf8::CodePoint.
     pub fn utf16_char(&self, idx: usize) -> char {
-        let utf8_idx = if idx != 0 {
-            match self.index_mapping() {
-                IndexMapping::Ascii => idx,
-                IndexMapping::NonAscii { mapping } => {
-                    // TODO: Deal with surrogates.
-                    mapping[idx].unwrap().get()
-                }
-            }
-        } else {
-            0
-        };
+        // TODO: Deal with surrogates.
+        let utf8_idx = self.utf8_index(idx).unwrap();
         let ch = self.as_str()[utf8_idx..].chars().next().unwrap();
         // TODO: Deal with surrogates.
         assert_eq!(ch.len_utf16(), 1);
@@ -140,97 +285,83 @@ impl StringHeapData {
 
     pub fn utf8_index(&self, utf16_idx: usize) -> Option<usize> {
         if utf16_idx == 0 {
-            Some(0)
-        } else {
-            match self.index_mapping() {
-                IndexMapping::Ascii => {
-                    assert!(utf16_idx <= self.len());
-                    Some(utf16_idx)
+            return Some(0);
+        }
+        match self.index_mapping() {
+            IndexMapping::Ascii => {
+                assert!(utf16_idx <= self.len());
+                Some(utf16_idx)
+            }
+            IndexMapping::NonAscii { utf16_len, samples } => {
+                assert!(utf16_idx <= *utf16_len);
+                if utf16_idx == *utf16_len {
+                    return Some(self.len());
                 }
-                IndexMapping::NonAscii { mapping } => {
-                    if utf16_idx == mapping.len() {
-                        Some(self.len())
-                    } else {
-                        mapping[utf16_idx].map(NonZeroUsize::get)
+                // If the stride boundary splits a surrogate pair, the sample
+                // of the stride starts one code unit late or, at the end of
+                // the string, is missing.
+                let stride = utf16_idx / INDEX_SAMPLE_STRIDE;
+                let sample = match samples.get(stride) {
+                    Some(sample) if sample.utf16 <= utf16_idx => *sample,
+                    _ => samples[stride - 1],
+                };
+                let mut utf8 = sample.utf8;
+                let mut utf16 = sample.utf16;
+                for (utf8_len, utf16_len) in code_point_lengths(self.as_wtf8().slice_from(utf8)) {
+                    if utf16 == utf16_idx {
+                        return Some(utf8);
+                    }
+                    utf8 += utf8_len;
+                    utf16 += utf16_len;
+                    if utf16 > utf16_idx {
+                        // The index is the second code unit of a surrogate
+                        // pair.
+                        return None;
                     }
                 }
+                unreachable!()
             }
         }
     }
 
     pub fn utf16_index(&self, utf8_idx: usize) -> usize {
         if utf8_idx == 0 {
-            0
-        } else {
-            assert!(utf8_idx <= self.len());
-            match self.index_mapping() {
-                IndexMapping::Ascii => utf8_idx,
-                IndexMapping::NonAscii { mapping } => {
-                    if utf8_idx == self.len() {
-                        return mapping.len();
-                    }
-
-                    // Binary search `mapping`. We start at `utf8_idx` though,
-                    // if it's in range.
-                    let mut range = if utf8_idx >= mapping.len() {
-                        0..mapping.len()
-                    } else {
-                        let mut pivot = utf8_idx;
-                        if mapping[pivot].is_none() {
-                            pivot -= 1;
-                        }
-                        match mapping[pivot].unwrap().get().cmp(&utf8_idx) {
-                            std::cmp::Ordering::Less => pivot..mapping.len(),
-                            std::cmp::Ordering::Equal => return pivot,
-                            std::cmp::Ordering::Greater => 0..pivot,
-                        }
-                    };
-
-                    loop {
-                        let mut pivot = (range.start + range.end) / 2;
-                        if mapping[pivot].is_none() {
-                            pivot -= 1;
-                        }
-                        debug_assert!(range.contains(&pivot));
-
-                        // Since we're adjusting the pivot due to None elements
-                        // (i.e. surrogates), we might get stuck in an infinite
-                        // loop if pivot is the start of the range. In this
-                        // case, we walk through the range we know is valid.
-                        if pivot == range.start {
-                            for i in range {
-                                if mapping[i].is_some() && mapping[i].unwrap().get() == utf8_idx {
-                                    return i;
-                                }
-                            }
-                            unreachable!();
+            return 0;
+        }
+        assert!(utf8_idx <= self.len());
+        match self.index_mapping() {
+            IndexMapping::Ascii => utf8_idx,
+            IndexMapping::NonAscii { utf16_len, samples } => {
+                if utf8_idx == self.len() {
+                    return *utf16_len;
+                }
+                let sample = samples[samples.partition_point(|sample| sample.utf8 <= utf8_idx) - 1];
+                let mut utf8 = sample.utf8;
+                let mut utf16 = sample.utf16;
+                for (utf8_len, utf16_len) in code_point_lengths(self.as_wtf8().slice_from(utf8)) {
+                    match utf8.cmp(&utf8_idx) {
+                        std::cmp::Ordering::Less => {
+                            utf8 += utf8_len;
+                            utf16 += utf16_len;
                         }
-
-                        let new_range = match mapping[pivot].unwrap().get().cmp(&utf8_idx) {
-                            std::cmp::Ordering::Less => pivot..range.end,
-                            std::cmp::Ordering::Equal => return pivot,
-                            std::cmp::Ordering::Greater => range.start..pivot,
-                        };
-                        assert_ne!(range, new_range);
-
-                        range = new_range;
+                        std::cmp::Ordering::Equal => return utf16,
+                        std::cmp::Ordering::Greater => break,
                     }
                 }
+                panic!("UTF-8 index is not at a code point boundary");
             }
         }
     }
 
     pub fn as_str(&self) -> &str {
-        match &self.data {
-            StringBuffer::Owned(buf) => buf.as_str().unwrap(),
-            StringBuffer::Static(buf) => buf.as_str().unwrap(),
-        }
+        self.as_wtf8().as_str().unwrap()
     }
 
     pub fn as_wtf8(&self) -> &Wtf8 {
         match &self.data {
             StringBuffer::Owned(buf) => buf,
             StringBuffer::Static(buf) => buf,
+            StringBuffer::Rope(rope) => rope.as_wtf8(),
         }
     }
 
@@ -252,6 +383,13 @@ impl StringHeapData {
         }
     }
 
+    pub(crate) fn from_rope(rope: RopeString) -> Self {
+        StringHeapData {
+            data: StringBuffer::Rope(rope),
+            mapping: OnceCell::new(),
+        }
+    }
+
     pub fn from_string(str: String) -> Self {
         debug_assert!(str.len() > 7);
         assert!(str.len() <= Self::MAX_UTF8_LENGTH, "String is too long.");
@@ -260,6 +398,34 @@ impl StringHeapData {
             mapping: OnceCell::new(),
         }
     }
+
+    /// Create string heap data from a WTF-8 buffer which may contain lone
+    /// surrogates.
+    pub fn from_wtf8_buf(buf: Wtf8Buf) -> Self {
+        debug_assert!(buf.len() > 7 || buf.as_str().is_none());
+        assert!(buf.len() <= Self::MAX_UTF8_LENGTH, "String is too long.");
+        StringHeapData {
+            data: StringBuffer::Owned(buf),
+            mapping: OnceCell::new(),
+        }
+    }
+}
+
+impl OwnedAllocations for StringHeapData {
+    fn owned_bytes(&self) -> usize {
+        let data = match &self.data {
+            StringBuffer::Owned(buf) => buf.len(),
+            StringBuffer::Static(_) => 0,
+            // Rope pieces may be shared with other ropes, but each rope can
+            // keep all of its pieces alive.
+            StringBuffer::Rope(rope) => rope.len() + rope.flat.get().map_or(0, |flat| flat.len()),
+        };
+        let mapping = match self.mapping.get() {
+            Some(IndexMapping::NonAscii { samples, .. }) => std::mem::size_of_val(&**samples),
+            _ => 0,
+        };
+        data + mapping
+    }
 }
 
 impl HeapMarkAndSweep for StringHeapData {
@@ -271,9 +437,233 @@ impl HeapMarkAndSweep for StringHeapData {
     }
 
     fn sweep_values(&mut self, _compactions: &CompactionLists) {
-        let Self {
-            data: _,
-            mapping: _,
-        } = self;
+        let Self { data, mapping: _ } = self;
+        // Release the pieces of ropes that have already been flattened.
+        if let StringBuffer::Rope(rope) = data {
+            if let Some(flat) = rope.take_flat() {
+                rope.node = Arc::new(RopeNode::Owned(flat));
+            }
+        }
+    }
+}
+
+impl SnapshotData for StringHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        // WTF-8 may contain lone surrogates, so the data is written as a list
+        // of code points.
+        let code_points = self
+            .as_wtf8()
+            .code_points()
+            .map(|cp| cp.to_u32())
+            .collect::<Vec<u32>>();
+        self.is_rope().serialize(writer)?;
+        code_points.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let is_rope = bool::deserialize(reader)?;
+        let code_points = Vec::<u32>::deserialize(reader)?;
+        let mut buf = Wtf8Buf::with_capacity(code_points.len());
+        for code_point in code_points {
+            buf.push(CodePoint::from_u32(code_point).ok_or(SnapshotError::Malformed)?);
+        }
+        if buf.len() <= 7 || buf.len() > Self::MAX_UTF8_LENGTH {
+            return Err(SnapshotError::Malformed);
+        }
+        // Ropes are restored flat, but stay out of the string lookup table.
+        let data = if is_rope {
+            StringBuffer::Rope(RopeString {
+                node: Arc::new(RopeNode::Owned(buf)),
+                flat: OnceCell::new(),
+            })
+        } else {
+            StringBuffer::Owned(buf)
+        };
+        Ok(StringHeapData {
+            data,
+            mapping: OnceCell::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wtf8::{CodePoint, Wtf8Buf};
+
+    use super::{StringHeapData, INDEX_SAMPLE_STRIDE};
+    use crate::{
+        ecmascript::types::Value,
+        test_utils::{eval_script, TestAgent},
+    };
+
+    /// Check every UTF-16 index of the string against a mapping built by
+    /// walking the whole string.
+    fn assert_index_mapping(data: &StringHeapData) {
+        let mut expected = vec![];
+        let mut utf8 = 0;
+        for code_point in data.as_wtf8().code_points() {
+            let ch = code_point.to_char();
+            expected.push(Some(utf8));
+            if ch.is_some_and(|ch| ch.len_utf16() == 2) {
+                expected.push(None);
+            }
+            utf8 += ch.map_or(3, char::len_utf8);
+        }
+        assert_eq!(data.utf16_len(), expected.len());
+        assert_eq!(data.utf8_index(expected.len()), Some(data.len()));
+        assert_eq!(data.utf16_index(data.len()), expected.len());
+        for (utf16, utf8) in expected.into_iter().enumerate() {
+            assert_eq!(data.utf8_index(utf16), utf8, "UTF-16 index {utf16}");
+            if let Some(utf8) = utf8 {
+                assert_eq!(data.utf16_index(utf8), utf16, "UTF-8 index {utf8}");
+            }
+        }
+    }
+
+    #[test]
+    fn surrogate_pairs_at_sample_stride_boundaries() {
+        assert_eq!(INDEX_SAMPLE_STRIDE, 32);
+        // The pair starts one code unit before the boundary, so the sample of
+        // the second stride starts one code unit late.
+        let straddling = format!("{}\u{1f600}{}", "\u{e9}".repeat(31), "\u{fc}".repeat(40));
+        let data = StringHeapData::from_string(straddling);
+        assert_eq!(data.utf16_len(), 73);
+        assert_eq!(data.utf8_index(31), Some(62));
+        assert_eq!(data.utf8_index(32), None);
+        assert_eq!(data.utf8_index(33), Some(66));
+        assert_index_mapping(&data);
+        // The pair straddling the boundary ends the string, so the sample of
+        // the second stride is missing.
+        let data = StringHeapData::from_string(format!("{}\u{1f600}", "\u{e9}".repeat(31)));
+        assert_eq!(data.utf8_index(32), None);
+        assert_index_mapping(&data);
+        // The pair starts exactly at the boundary.
+        let data = StringHeapData::from_string(format!("{}\u{1f600}", "\u{e9}".repeat(32)));
+        assert_eq!(data.utf8_index(32), Some(64));
+        assert_eq!(data.utf8_index(33), None);
+        assert_index_mapping(&data);
+        // Only surrogate pairs: Every other stride boundary splits a pair.
+        assert_index_mapping(&StringHeapData::from_string("\u{1f600}".repeat(100)));
+    }
+
+    #[test]
+    fn lone_surrogates_take_one_code_unit() {
+        let mut buf = Wtf8Buf::from_str(&"\u{e9}".repeat(30));
+        buf.push(CodePoint::from_u32(0xd800).unwrap());
+        buf.push_str("a\u{1f600}");
+        buf.push(CodePoint::from_u32(0xdfff).unwrap());
+        buf.push_str(&"b".repeat(40));
+        let data = StringHeapData::from_wtf8_buf(buf);
+        assert_eq!(data.utf16_len(), 30 + 1 + 1 + 2 + 1 + 40);
+        assert_eq!(data.utf8_index(31), Some(63));
+        assert_index_mapping(&data);
+    }
+
+    #[test]
+    fn non_ascii_string_indexing() {
+        let mut agent = TestAgent::new();
+        agent.eval("var s = '\u{e9}'.repeat(31) + '\u{1f600}' + '\u{fc}'.repeat(40);");
+        assert_eq!(agent.eval("s.length"), "73");
+        assert_eq!(
+            agent.eval("s.charCodeAt(30) + ',' + s.charCodeAt(33)"),
+            "233,252"
+        );
+        assert_eq!(
+            agent.eval("s.slice(30, 34) === '\u{e9}\u{1f600}\u{fc}'"),
+            "true"
+        );
+        assert_eq!(agent.eval("s.slice(33).length + s.indexOf('\u{fc}')"), "73");
+    }
+
+    fn is_rope(agent: &mut TestAgent, source_text: &str) -> bool {
+        agent.run(
+            |agent, gc| match eval_script(agent, source_text, gc).unwrap() {
+                Value::String(string) => agent[string].is_rope(),
+                _ => false,
+            },
+        )
+    }
+
+    #[test]
+    fn long_concatenations_are_ropes() {
+        let mut agent = TestAgent::new();
+        agent.eval("var s = ''; for (let i = 0; i < 1000; i++) s += 'abcdefgh';");
+        assert!(is_rope(&mut agent, "s"));
+        assert!(!is_rope(&mut agent, "'abcdefgh' + 'abcdefgh'"));
+        assert!(!is_rope(&mut agent, "'abcdefgh'.repeat(1000)"));
+        assert_eq!(agent.eval("s === 'abcdefgh'.repeat(1000)"), "true");
+        assert_eq!(
+            agent.eval("s.length + s[7999] + s.slice(-9)"),
+            "8000hhabcdefgh"
+        );
+        assert_eq!(
+            agent.eval("new Set([s, 'abcdefgh'.repeat(1000)]).size"),
+            "1"
+        );
+    }
+
+    #[test]
+    fn ropes_are_interned_as_property_keys() {
+        let mut agent = TestAgent::new();
+        agent.eval(
+            "var key = 'k'.repeat(300);
+            var rope = 'k'.repeat(100) + 'k'.repeat(200);
+            var o = {};
+            o[rope] = 1;",
+        );
+        assert_eq!(agent.eval("o[key]"), "1");
+        assert_eq!(agent.eval("Object.keys(o)[0] === key"), "true");
+        assert_eq!(
+            agent.eval("[rope in o, o.hasOwnProperty(rope), Reflect.get(o, rope)].join()"),
+            "true,true,1"
+        );
+        assert_eq!(agent.eval("Object.fromEntries([[rope, 2]])[key]"), "2");
+        // Without an equal interned string, the rope itself is interned.
+        agent.eval("var fresh = 'x'.repeat(200) + 'y'.repeat(100); o[fresh] = 3;");
+        assert!(!is_rope(&mut agent, "fresh"));
+        assert_eq!(agent.eval("o['x'.repeat(200) + 'y'.repeat(100)]"), "3");
+    }
+
+    #[test]
+    fn ropes_as_dictionary_mode_keys() {
+        let mut agent = TestAgent::new();
+        agent.eval(
+            "var prefix = 'a long property key prefix '.repeat(10);
+            var o = {};
+            for (let i = 0; i < 100; i++) o[prefix + i] = i;",
+        );
+        assert_eq!(agent.eval("o[prefix + 42] + o[prefix + 99]"), "141");
+        assert_eq!(agent.eval("Object.keys(o).length"), "100");
+    }
+
+    #[test]
+    fn ropes_survive_garbage_collection() {
+        let mut agent = TestAgent::new();
+        agent.eval(
+            "var garbage = [];
+            for (let i = 0; i < 100; i++) garbage.push('garbage string ' + i);
+            var deep = '';
+            for (let i = 0; i < 20000; i++) deep += 'piece ' + i;
+            var kept = deep + '!';
+            var flattened = 'x'.repeat(200) + 'y'.repeat(200);
+            flattened.slice(0, 1);
+            garbage = deep = undefined;",
+        );
+        agent.gc();
+        assert!(is_rope(&mut agent, "kept"));
+        assert_eq!(
+            agent.eval("kept.slice(0, 14) + kept.slice(-6)"),
+            "piece 0piece 119999!"
+        );
+        assert_eq!(
+            agent.eval("flattened === 'x'.repeat(200) + 'y'.repeat(200)"),
+            "true"
+        );
+        assert_eq!(
+            agent.eval("var o = {}; o[kept] = 1; kept = undefined; o[Object.keys(o)[0]]"),
+            "1"
+        );
+        agent.gc();
+        assert_eq!(agent.eval("Object.keys(o)[0].length"), "208891");
     }
 }
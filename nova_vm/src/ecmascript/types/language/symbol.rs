@@ -17,7 +17,8 @@ use crate::{
     },
     heap::{
         indexes::SymbolIndex, CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep,
-        WellKnownSymbolIndexes, WorkQueues, LAST_WELL_KNOWN_SYMBOL_INDEX,
+        SnapshotData, SnapshotReader, SnapshotResult, SnapshotWriter, WellKnownSymbolIndexes,
+        WorkQueues, LAST_WELL_KNOWN_SYMBOL_INDEX,
     },
 };
 
@@ -184,6 +185,16 @@ impl HeapMarkAndSweep for Symbol<'static> {
     }
 }
 
+impl SnapshotData for Symbol<'static> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self(SnapshotData::deserialize(reader)?))
+    }
+}
+
 impl CreateHeapData<SymbolHeapData, Symbol<'static>> for Heap {
     fn create(&mut self, data: SymbolHeapData) -> Symbol<'static> {
         self.symbols.push(Some(data));
@@ -4,7 +4,10 @@
 
 use crate::{
     ecmascript::types::String,
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotReader, SnapshotResult,
+        SnapshotWriter, WorkQueues,
+    },
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -23,3 +26,16 @@ impl HeapMarkAndSweep for SymbolHeapData {
         descriptor.sweep_values(compactions);
     }
 }
+
+impl SnapshotData for SymbolHeapData {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self { descriptor } = self;
+        descriptor.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            descriptor: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
@@ -15,6 +15,8 @@ use super::{
 use crate::ecmascript::builtins::date::Date;
 #[cfg(feature = "regexp")]
 use crate::ecmascript::builtins::regexp::RegExp;
+#[cfg(feature = "regexp")]
+use crate::ecmascript::builtins::text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIterator;
 #[cfg(feature = "shared-array-buffer")]
 use crate::ecmascript::builtins::shared_array_buffer::SharedArrayBuffer;
 #[cfg(feature = "set")]
@@ -50,7 +52,8 @@ use crate::{
             module::Module,
             primitive_objects::PrimitiveObject,
             promise::Promise,
-            proxy::Proxy,
+            proxy::{revoker::BuiltinProxyRevokerFunction, Proxy},
+            text_processing::string_objects::string_iterator_objects::string_iterator::StringIterator,
             Array, BuiltinConstructorFunction, BuiltinFunction, ECMAScriptFunction,
         },
         execution::{Agent, JsResult},
@@ -62,7 +65,10 @@ use crate::{
         small_f64::SmallF64,
         Scoped, TryResult,
     },
-    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+    heap::{
+        CompactionLists, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader,
+        SnapshotResult, SnapshotWriter, WorkQueues,
+    },
     SmallInteger, SmallString,
 };
 
@@ -141,7 +147,7 @@ pub enum Value {
     BuiltinConstructorFunction(BuiltinConstructorFunction<'static>),
     BuiltinPromiseResolvingFunction(BuiltinPromiseResolvingFunction<'static>),
     BuiltinPromiseCollectorFunction,
-    BuiltinProxyRevokerFunction,
+    BuiltinProxyRevokerFunction(BuiltinProxyRevokerFunction<'static>),
 
     // Boolean, Number, String, Symbol, BigInt objects
     PrimitiveObject(PrimitiveObject<'static>),
@@ -217,6 +223,9 @@ pub enum Value {
     #[cfg(feature = "set")]
     SetIterator(SetIterator<'static>),
     MapIterator(MapIterator<'static>),
+    StringIterator(StringIterator<'static>),
+    #[cfg(feature = "regexp")]
+    RegExpStringIterator(RegExpStringIterator<'static>),
     Generator(Generator<'static>),
 
     // ECMAScript Module
@@ -287,8 +296,9 @@ pub(crate) const BUILTIN_PROMISE_RESOLVING_FUNCTION_DISCRIMINANT: u8 = value_dis
 );
 pub(crate) const BUILTIN_PROMISE_COLLECTOR_FUNCTION_DISCRIMINANT: u8 =
     value_discriminant(Value::BuiltinPromiseCollectorFunction);
-pub(crate) const BUILTIN_PROXY_REVOKER_FUNCTION: u8 =
-    value_discriminant(Value::BuiltinProxyRevokerFunction);
+pub(crate) const BUILTIN_PROXY_REVOKER_FUNCTION: u8 = value_discriminant(
+    Value::BuiltinProxyRevokerFunction(BuiltinProxyRevokerFunction::_def()),
+);
 pub(crate) const PRIMITIVE_OBJECT_DISCRIMINANT: u8 =
     value_discriminant(Value::PrimitiveObject(PrimitiveObject::_def()));
 pub(crate) const ARGUMENTS_DISCRIMINANT: u8 =
@@ -359,6 +369,11 @@ pub(crate) const SET_ITERATOR_DISCRIMINANT: u8 =
     value_discriminant(Value::SetIterator(SetIterator::_def()));
 pub(crate) const MAP_ITERATOR_DISCRIMINANT: u8 =
     value_discriminant(Value::MapIterator(MapIterator::_def()));
+pub(crate) const STRING_ITERATOR_DISCRIMINANT: u8 =
+    value_discriminant(Value::StringIterator(StringIterator::_def()));
+#[cfg(feature = "regexp")]
+pub(crate) const REGEXP_STRING_ITERATOR_DISCRIMINANT: u8 =
+    value_discriminant(Value::RegExpStringIterator(RegExpStringIterator::_def()));
 pub(crate) const GENERATOR_DISCRIMINANT: u8 =
     value_discriminant(Value::Generator(Generator::_def()));
 pub(crate) const MODULE_DISCRIMINANT: u8 = value_discriminant(Value::Module(Module::_def()));
@@ -642,7 +657,7 @@ impl Value {
             }
             Value::String(data) => {
                 // Skip discriminant hashing in strings
-                arena[data].data.hash(hasher);
+                arena[data].as_wtf8().hash(hasher);
             }
             Value::SmallString(data) => {
                 Wtf8::from_str(data.as_str()).hash(hasher);
@@ -694,7 +709,10 @@ impl Value {
                 data.get_index().hash(hasher);
             }
             Value::BuiltinPromiseCollectorFunction => todo!(),
-            Value::BuiltinProxyRevokerFunction => todo!(),
+            Value::BuiltinProxyRevokerFunction(data) => {
+                discriminant.hash(hasher);
+                data.get_index().hash(hasher);
+            }
             Value::PrimitiveObject(data) => {
                 discriminant.hash(hasher);
                 data.get_index().hash(hasher);
@@ -851,6 +869,15 @@ impl Value {
                 discriminant.hash(hasher);
                 data.get_index().hash(hasher);
             }
+            Value::StringIterator(data) => {
+                discriminant.hash(hasher);
+                data.get_index().hash(hasher);
+            }
+            #[cfg(feature = "regexp")]
+            Value::RegExpStringIterator(data) => {
+                discriminant.hash(hasher);
+                data.get_index().hash(hasher);
+            }
             Value::Generator(data) => {
                 discriminant.hash(hasher);
                 data.get_index().hash(hasher);
@@ -925,7 +952,10 @@ impl Value {
                 data.get_index().hash(hasher);
             }
             Value::BuiltinPromiseCollectorFunction => todo!(),
-            Value::BuiltinProxyRevokerFunction => todo!(),
+            Value::BuiltinProxyRevokerFunction(data) => {
+                discriminant.hash(hasher);
+                data.get_index().hash(hasher);
+            }
             Value::PrimitiveObject(data) => {
                 discriminant.hash(hasher);
                 data.get_index().hash(hasher);
@@ -1082,6 +1112,15 @@ impl Value {
                 discriminant.hash(hasher);
                 data.get_index().hash(hasher);
             }
+            Value::StringIterator(data) => {
+                discriminant.hash(hasher);
+                data.get_index().hash(hasher);
+            }
+            #[cfg(feature = "regexp")]
+            Value::RegExpStringIterator(data) => {
+                discriminant.hash(hasher);
+                data.get_index().hash(hasher);
+            }
             Value::Generator(data) => {
                 discriminant.hash(hasher);
                 data.get_index().hash(hasher);
@@ -1220,7 +1259,9 @@ impl Rootable for Value {
             Self::BuiltinPromiseCollectorFunction => {
                 Err(HeapRootData::BuiltinPromiseCollectorFunction)
             }
-            Self::BuiltinProxyRevokerFunction => Err(HeapRootData::BuiltinProxyRevokerFunction),
+            Self::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function) => Err(
+                HeapRootData::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function),
+            ),
             Self::PrimitiveObject(primitive_object) => {
                 Err(HeapRootData::PrimitiveObject(primitive_object))
             }
@@ -1284,6 +1325,13 @@ impl Rootable for Value {
             #[cfg(feature = "set")]
             Self::SetIterator(set_iterator) => Err(HeapRootData::SetIterator(set_iterator)),
             Self::MapIterator(map_iterator) => Err(HeapRootData::MapIterator(map_iterator)),
+            Self::StringIterator(string_iterator) => {
+                Err(HeapRootData::StringIterator(string_iterator))
+            }
+            #[cfg(feature = "regexp")]
+            Self::RegExpStringIterator(regexp_string_iterator) => {
+                Err(HeapRootData::RegExpStringIterator(regexp_string_iterator))
+            }
             Self::Generator(generator) => Err(HeapRootData::Generator(generator)),
             Self::Module(module) => Err(HeapRootData::Module(module)),
             Self::EmbedderObject(embedder_object) => {
@@ -1338,7 +1386,9 @@ impl Rootable for Value {
             HeapRootData::BuiltinPromiseCollectorFunction => {
                 Some(Self::BuiltinPromiseCollectorFunction)
             }
-            HeapRootData::BuiltinProxyRevokerFunction => Some(Self::BuiltinProxyRevokerFunction),
+            HeapRootData::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function) => Some(
+                Self::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function),
+            ),
             HeapRootData::PrimitiveObject(primitive_object) => {
                 Some(Self::PrimitiveObject(primitive_object))
             }
@@ -1406,6 +1456,13 @@ impl Rootable for Value {
             #[cfg(feature = "set")]
             HeapRootData::SetIterator(set_iterator) => Some(Self::SetIterator(set_iterator)),
             HeapRootData::MapIterator(map_iterator) => Some(Self::MapIterator(map_iterator)),
+            HeapRootData::StringIterator(string_iterator) => {
+                Some(Self::StringIterator(string_iterator))
+            }
+            #[cfg(feature = "regexp")]
+            HeapRootData::RegExpStringIterator(regexp_string_iterator) => {
+                Some(Self::RegExpStringIterator(regexp_string_iterator))
+            }
             HeapRootData::Generator(generator) => Some(Self::Generator(generator)),
             HeapRootData::Module(module) => Some(Self::Module(module)),
             HeapRootData::EmbedderObject(embedder_object) => {
@@ -1505,7 +1562,7 @@ impl HeapMarkAndSweep for Value {
             Value::BuiltinConstructorFunction(data) => data.mark_values(queues),
             Value::BuiltinPromiseResolvingFunction(data) => data.mark_values(queues),
             Value::BuiltinPromiseCollectorFunction => todo!(),
-            Value::BuiltinProxyRevokerFunction => todo!(),
+            Value::BuiltinProxyRevokerFunction(data) => data.mark_values(queues),
             Value::AsyncFromSyncIterator => todo!(),
             Value::AsyncGenerator(data) => data.mark_values(queues),
             Value::Iterator => todo!(),
@@ -1513,6 +1570,9 @@ impl HeapMarkAndSweep for Value {
             #[cfg(feature = "set")]
             Value::SetIterator(data) => data.mark_values(queues),
             Value::MapIterator(data) => data.mark_values(queues),
+            Value::StringIterator(data) => data.mark_values(queues),
+            #[cfg(feature = "regexp")]
+            Value::RegExpStringIterator(data) => data.mark_values(queues),
             Value::Generator(data) => data.mark_values(queues),
             Value::Module(data) => data.mark_values(queues),
             Value::EmbedderObject(data) => data.mark_values(queues),
@@ -1592,7 +1652,7 @@ impl HeapMarkAndSweep for Value {
             Value::BuiltinConstructorFunction(data) => data.sweep_values(compactions),
             Value::BuiltinPromiseResolvingFunction(data) => data.sweep_values(compactions),
             Value::BuiltinPromiseCollectorFunction => todo!(),
-            Value::BuiltinProxyRevokerFunction => todo!(),
+            Value::BuiltinProxyRevokerFunction(data) => data.sweep_values(compactions),
             Value::AsyncFromSyncIterator => todo!(),
             Value::AsyncGenerator(data) => data.sweep_values(compactions),
             Value::Iterator => todo!(),
@@ -1600,9 +1660,78 @@ impl HeapMarkAndSweep for Value {
             #[cfg(feature = "set")]
             Value::SetIterator(data) => data.sweep_values(compactions),
             Value::MapIterator(data) => data.sweep_values(compactions),
+            Value::StringIterator(data) => data.sweep_values(compactions),
+            #[cfg(feature = "regexp")]
+            Value::RegExpStringIterator(data) => data.sweep_values(compactions),
             Value::Generator(data) => data.sweep_values(compactions),
             Value::Module(data) => data.sweep_values(compactions),
             Value::EmbedderObject(data) => data.sweep_values(compactions),
         }
     }
 }
+
+impl SnapshotData for Value {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        value_discriminant(*self).serialize(writer)?;
+        match self {
+            Value::Undefined | Value::Null => Ok(()),
+            Value::Boolean(data) => data.serialize(writer),
+            Value::String(data) => data.serialize(writer),
+            Value::SmallString(data) => data.serialize(writer),
+            Value::Symbol(data) => data.serialize(writer),
+            Value::Number(data) => data.serialize(writer),
+            Value::Integer(data) => data.serialize(writer),
+            Value::SmallF64(data) => data.serialize(writer),
+            Value::BigInt(data) => data.serialize(writer),
+            Value::SmallBigInt(data) => data.serialize(writer),
+            Value::Object(data) | Value::Arguments(data) => data.serialize(writer),
+            Value::BoundFunction(data) => data.serialize(writer),
+            Value::BuiltinFunction(data) => data.serialize(writer),
+            Value::ECMAScriptFunction(data) => data.serialize(writer),
+            Value::PrimitiveObject(data) => data.serialize(writer),
+            Value::Array(data) => data.serialize(writer),
+            #[cfg(feature = "date")]
+            Value::Date(data) => data.serialize(writer),
+            Value::Error(data) => data.serialize(writer),
+            Value::Proxy(data) => data.serialize(writer),
+            // The heap vectors of all other kinds of values are checked to be
+            // empty before anything is serialized.
+            _ => Err(SnapshotError::UnsupportedHeapData("values of this type")),
+        }
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let value = match u8::deserialize(reader)? {
+            UNDEFINED_DISCRIMINANT => Value::Undefined,
+            NULL_DISCRIMINANT => Value::Null,
+            BOOLEAN_DISCRIMINANT => Value::Boolean(SnapshotData::deserialize(reader)?),
+            STRING_DISCRIMINANT => Value::String(SnapshotData::deserialize(reader)?),
+            SMALL_STRING_DISCRIMINANT => Value::SmallString(SnapshotData::deserialize(reader)?),
+            SYMBOL_DISCRIMINANT => Value::Symbol(SnapshotData::deserialize(reader)?),
+            NUMBER_DISCRIMINANT => Value::Number(SnapshotData::deserialize(reader)?),
+            INTEGER_DISCRIMINANT => Value::Integer(SnapshotData::deserialize(reader)?),
+            FLOAT_DISCRIMINANT => Value::SmallF64(SnapshotData::deserialize(reader)?),
+            BIGINT_DISCRIMINANT => Value::BigInt(SnapshotData::deserialize(reader)?),
+            SMALL_BIGINT_DISCRIMINANT => Value::SmallBigInt(SnapshotData::deserialize(reader)?),
+            OBJECT_DISCRIMINANT => Value::Object(SnapshotData::deserialize(reader)?),
+            ARGUMENTS_DISCRIMINANT => Value::Arguments(SnapshotData::deserialize(reader)?),
+            BOUND_FUNCTION_DISCRIMINANT => Value::BoundFunction(SnapshotData::deserialize(reader)?),
+            BUILTIN_FUNCTION_DISCRIMINANT => {
+                Value::BuiltinFunction(SnapshotData::deserialize(reader)?)
+            }
+            ECMASCRIPT_FUNCTION_DISCRIMINANT => {
+                Value::ECMAScriptFunction(SnapshotData::deserialize(reader)?)
+            }
+            PRIMITIVE_OBJECT_DISCRIMINANT => {
+                Value::PrimitiveObject(SnapshotData::deserialize(reader)?)
+            }
+            ARRAY_DISCRIMINANT => Value::Array(SnapshotData::deserialize(reader)?),
+            #[cfg(feature = "date")]
+            DATE_DISCRIMINANT => Value::Date(SnapshotData::deserialize(reader)?),
+            ERROR_DISCRIMINANT => Value::Error(SnapshotData::deserialize(reader)?),
+            PROXY_DISCRIMINANT => Value::Proxy(SnapshotData::deserialize(reader)?),
+            _ => return Err(SnapshotError::Malformed),
+        };
+        Ok(value)
+    }
+}
@@ -4,7 +4,7 @@
 
 use crate::ecmascript::abstract_operations::operations_on_objects::try_set;
 use crate::engine::context::{GcScope, NoGcScope};
-use crate::engine::TryResult;
+use crate::engine::{IndexType, TryResult};
 use crate::{
     ecmascript::{
         abstract_operations::{operations_on_objects::set, type_conversion::to_object},
@@ -52,6 +52,10 @@ pub struct Reference<'a> {
     /// that case, the \[\[ThisValue]] field holds the this value at the time
     /// the Reference Record was created.
     pub(crate) this_value: Option<Value>,
+
+    /// Inline cache slot of the bytecode instruction that created this
+    /// property Reference, if any.
+    pub(crate) property_cache: Option<IndexType>,
 }
 
 impl<'a> Reference<'a> {
@@ -86,8 +90,8 @@ pub(crate) fn is_property_reference(reference: &Reference) -> bool {
         Base::Unresolvable => false,
 
         // 2. If V.[[Base]] is an Environment Record, return false; otherwise return true.
-        Base::Environment(_) => false,
-        _ => true,
+        Base::Environment(_) | Base::Register { .. } => false,
+        Base::Value(_) => true,
     }
 }
 
@@ -223,6 +227,8 @@ pub(crate) fn get_value(
             };
             Ok(env.get_binding_value(agent, referenced_name, reference.strict, gc.reborrow())?)
         }
+        // Register references are only ever consumed by the VM.
+        Base::Register { .. } => unreachable!(),
         Base::Unresolvable => {
             // 2. If IsUnresolvableReference(V) is true, throw a ReferenceError exception.
             let error_message = format!(
@@ -272,7 +278,7 @@ pub(crate) fn put_value(
         // a. Let baseObj be ? ToObject(V.[[Base]]).
         let base = match v.base {
             Base::Value(value) => value,
-            Base::Environment(_) | Base::Unresolvable => unreachable!(),
+            Base::Environment(_) | Base::Register { .. } | Base::Unresolvable => unreachable!(),
         };
         let base_obj = to_object(agent, base, gc.nogc())?;
         // b. If IsPrivateReference(V) is true, then
@@ -363,7 +369,7 @@ pub(crate) fn try_put_value<'a>(
         // a. Let baseObj be ? ToObject(V.[[Base]]).
         let base = match v.base {
             Base::Value(value) => value,
-            Base::Environment(_) | Base::Unresolvable => unreachable!(),
+            Base::Environment(_) | Base::Register { .. } | Base::Unresolvable => unreachable!(),
         };
         let base_obj = match to_object(agent, base, gc) {
             Ok(base_obj) => base_obj,
@@ -479,7 +485,7 @@ pub(crate) fn get_this_value(reference: &Reference) -> Value {
         .this_value
         .unwrap_or_else(|| match reference.base {
             Base::Value(value) => value,
-            Base::Environment(_) | Base::Unresolvable => unreachable!(),
+            Base::Environment(_) | Base::Register { .. } | Base::Unresolvable => unreachable!(),
         })
 }
 
@@ -487,6 +493,13 @@ pub(crate) fn get_this_value(reference: &Reference) -> Value {
 pub(crate) enum Base {
     Value(Value),
     Environment(EnvironmentIndex),
+    /// A binding held in a register of the running VM frame rather than in an
+    /// Environment Record. Such references are created and consumed by the
+    /// VM only.
+    Register {
+        index: IndexType,
+        mutable: bool,
+    },
     Unresolvable,
 }
 
@@ -496,6 +509,7 @@ impl HeapMarkAndSweep for Reference<'static> {
             base,
             referenced_name,
             strict: _,
+            property_cache: _,
             this_value,
         } = self;
         base.mark_values(queues);
@@ -508,6 +522,7 @@ impl HeapMarkAndSweep for Reference<'static> {
             base,
             referenced_name,
             strict: _,
+            property_cache: _,
             this_value,
         } = self;
         base.sweep_values(compactions);
@@ -521,7 +536,7 @@ impl HeapMarkAndSweep for Base {
         match self {
             Base::Value(value) => value.mark_values(queues),
             Base::Environment(idx) => idx.mark_values(queues),
-            Base::Unresolvable => {}
+            Base::Register { .. } | Base::Unresolvable => {}
         }
     }
 
@@ -529,7 +544,7 @@ impl HeapMarkAndSweep for Base {
         match self {
             Base::Value(value) => value.sweep_values(compactions),
             Base::Environment(idx) => idx.sweep_values(compactions),
-            Base::Unresolvable => {}
+            Base::Register { .. } | Base::Unresolvable => {}
         }
     }
 }
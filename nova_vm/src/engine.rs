@@ -11,6 +11,7 @@ pub mod small_integer;
 
 use std::ops::ControlFlow;
 
+pub use bytecode::CodeCache;
 pub(crate) use bytecode::*;
 pub use rootable::{Global, Scoped};
 
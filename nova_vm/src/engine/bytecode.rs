@@ -3,14 +3,18 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 mod bytecode_compiler;
+mod code_cache;
 mod executable;
 mod instructions;
 pub(super) mod iterator;
+mod property_cache;
 mod vm;
 
 pub(crate) use bytecode_compiler::{
     is_reference, CompileContext, CompileEvaluation, NamedEvaluationParameter,
 };
+pub use code_cache::CodeCache;
+pub(crate) use code_cache::FunctionNodes;
 pub(crate) use executable::{
     Executable, ExecutableHeapData, FunctionExpression, IndexType, SendableRef,
 };
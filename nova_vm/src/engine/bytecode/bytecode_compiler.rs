@@ -6,13 +6,15 @@ mod block_declaration_instantiation;
 mod class_definition_evaluation;
 mod for_in_of_statement;
 mod function_declaration_instantiation;
+mod register_allocation;
 
 use super::{
-    executable::ArrowFunctionExpression, Executable, ExecutableHeapData, FunctionExpression,
-    Instruction, SendableRef,
+    executable::{running_source_code, ArrowFunctionExpression},
+    property_cache::PropertyCache,
+    Executable, ExecutableHeapData, FunctionExpression, Instruction, SendableRef,
 };
 #[cfg(feature = "regexp")]
-use crate::ecmascript::builtins::regexp::reg_exp_create_literal;
+use crate::ecmascript::builtins::regexp::reg_exp_create;
 use crate::{
     ecmascript::{
         execution::Agent,
@@ -23,7 +25,9 @@ use crate::{
         types::{BigInt, IntoValue, Number, PropertyKey, String, Value, BUILTIN_STRING_MEMORY},
     },
     engine::context::NoGcScope,
-    heap::CreateHeapData,
+    heap::{
+        CreateHeapData, SnapshotData, SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter,
+    },
 };
 use num_traits::Num;
 use oxc_ast::ast::{
@@ -32,6 +36,7 @@ use oxc_ast::ast::{
 use oxc_ecmascript::BoundNames;
 use oxc_span::Atom;
 use oxc_syntax::operator::{BinaryOperator, UnaryOperator};
+use register_allocation::FunctionRegisters;
 
 pub type IndexType = u16;
 
@@ -47,6 +52,28 @@ pub(crate) enum NamedEvaluationParameter {
     ReferenceStack,
 }
 
+impl SnapshotData for NamedEvaluationParameter {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let tag: u8 = match self {
+            NamedEvaluationParameter::Result => 0,
+            NamedEvaluationParameter::Stack => 1,
+            NamedEvaluationParameter::Reference => 2,
+            NamedEvaluationParameter::ReferenceStack => 3,
+        };
+        tag.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        match u8::deserialize(reader)? {
+            0 => Ok(NamedEvaluationParameter::Result),
+            1 => Ok(NamedEvaluationParameter::Stack),
+            2 => Ok(NamedEvaluationParameter::Reference),
+            3 => Ok(NamedEvaluationParameter::ReferenceStack),
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+}
+
 pub(crate) struct CompileContext<'agent, 'gc, 'scope> {
     pub(crate) agent: &'agent mut Agent,
     pub(crate) gc: NoGcScope<'gc, 'scope>,
@@ -76,6 +103,11 @@ pub(crate) struct CompileContext<'agent, 'gc, 'scope> {
     /// In a `(a?.b)?.()` chain the evaluation of `(a?.b)` must be considered a
     /// reference.
     is_call_optional_chain_this: bool,
+    /// Register allocation state of the function being compiled, if any of
+    /// its bindings can be placed in registers.
+    registers: Option<FunctionRegisters>,
+    /// Number of inline cache slots used by property access instructions.
+    property_cache_count: usize,
 }
 
 impl<'a, 'gc, 'scope> CompileContext<'a, 'gc, 'scope> {
@@ -98,6 +130,8 @@ impl<'a, 'gc, 'scope> CompileContext<'a, 'gc, 'scope> {
             current_break: None,
             optional_chains: None,
             is_call_optional_chain_this: false,
+            registers: None,
+            property_cache_count: 0,
         }
     }
 
@@ -197,6 +231,8 @@ impl<'a, 'gc, 'scope> CompileContext<'a, 'gc, 'scope> {
             eprintln!();
         }
 
+        self.registers = FunctionRegisters::analyze(data.params, data.body);
+
         function_declaration_instantiation::instantiation(
             self,
             data.params,
@@ -229,15 +265,70 @@ impl<'a, 'gc, 'scope> CompileContext<'a, 'gc, 'scope> {
     }
 
     pub(super) fn finish(self) -> Executable {
+        let source_code = running_source_code(self.agent);
         self.agent.heap.create(ExecutableHeapData {
             instructions: self.instructions.into_boxed_slice(),
             constants: self.constants.into_boxed_slice(),
             function_expressions: self.function_expressions.into_boxed_slice(),
             arrow_function_expressions: self.arrow_function_expressions.into_boxed_slice(),
             class_initializer_bytecodes: self.class_initializer_bytecodes.into_boxed_slice(),
+            register_count: self
+                .registers
+                .as_ref()
+                .map_or(0, FunctionRegisters::register_count),
+            property_caches: (0..self.property_cache_count)
+                .map(|_| PropertyCache::default())
+                .collect(),
+            source_code,
         })
     }
 
+    /// Try to place a binding of the function being compiled in a register.
+    ///
+    /// On success the binding is in scope until the surrounding
+    /// [`Self::exit_register_scope`] call, and any `ResolveBinding` of its
+    /// name resolves to the register.
+    fn declare_register(&mut self, name: &str, mutable: bool) -> Option<IndexType> {
+        let registers = self.registers.as_mut()?;
+        if !registers.is_candidate(name) {
+            return None;
+        }
+        Some(registers.declare(name, mutable))
+    }
+
+    /// Try to place a lexical binding declared in a scope that may be entered
+    /// multiple times in a register.
+    ///
+    /// If the binding can be read before its declaration ends at
+    /// `declaration_end`, the register is reset to uninitialized so that
+    /// reads from a previous entry into the scope do not leak through.
+    fn declare_scoped_register(&mut self, name: &str, mutable: bool, declaration_end: u32) -> bool {
+        let Some(register) = self.declare_register(name, mutable) else {
+            return false;
+        };
+        if self
+            .registers
+            .as_ref()
+            .unwrap()
+            .is_referenced_before(name, declaration_end)
+        {
+            self.add_instruction_with_immediate(Instruction::ClearRegister, register.into());
+        }
+        true
+    }
+
+    fn enter_register_scope(&self) -> usize {
+        self.registers
+            .as_ref()
+            .map_or(0, FunctionRegisters::enter_scope)
+    }
+
+    fn exit_register_scope(&mut self, scope: usize) {
+        if let Some(registers) = self.registers.as_mut() {
+            registers.exit_scope(scope);
+        }
+    }
+
     pub(crate) fn create_identifier(&mut self, atom: &Atom<'_>) -> String<'gc> {
         let existing = self.constants.iter().find_map(|constant| {
             if let Ok(existing_identifier) = String::try_from(*constant) {
@@ -356,8 +447,37 @@ impl<'a, 'gc, 'scope> CompileContext<'a, 'gc, 'scope> {
         instruction: Instruction,
         identifier: String<'gc>,
     ) {
-        debug_assert_eq!(instruction.argument_count(), 1);
         debug_assert!(instruction.has_identifier_index());
+        if instruction == Instruction::EvaluatePropertyAccessWithIdentifierKey {
+            // Property accesses get an inline cache slot.
+            debug_assert_eq!(instruction.argument_count(), 2);
+            self._push_instruction(instruction);
+            let identifier = self.add_identifier(identifier);
+            self.add_index(identifier);
+            self.add_index(self.property_cache_count);
+            self.property_cache_count += 1;
+            return;
+        }
+        debug_assert_eq!(instruction.argument_count(), 1);
+        if instruction == Instruction::ResolveBinding {
+            if let Some(register) = self
+                .registers
+                .as_ref()
+                .and_then(|registers| registers.lookup(identifier.as_str(self.agent)))
+            {
+                // The binding lives in a register of the current frame.
+                let instruction = if register.mutable {
+                    Instruction::ResolveRegister
+                } else {
+                    Instruction::ResolveImmutableRegister
+                };
+                self._push_instruction(instruction);
+                let identifier = self.add_identifier(identifier);
+                self.add_index(identifier);
+                self.add_index(register.index.into());
+                return;
+            }
+        }
         self._push_instruction(instruction);
         let identifier = self.add_identifier(identifier);
         self.add_index(identifier);
@@ -940,6 +1060,7 @@ impl CompileEvaluation for ast::ArrowFunctionExpression<'_> {
                     &'static ast::ArrowFunctionExpression<'static>,
                 >(self)
             }),
+            span: self.span,
             identifier,
         });
     }
@@ -955,6 +1076,7 @@ impl CompileEvaluation for ast::Function<'_> {
                 expression: SendableRef::new(unsafe {
                     std::mem::transmute::<&ast::Function<'_>, &'static ast::Function<'static>>(self)
                 }),
+                span: self.span,
                 identifier,
                 compiled_bytecode: None,
             },
@@ -1096,6 +1218,7 @@ impl CompileEvaluation for ast::ObjectExpression<'_> {
                                             function_expression
                                         )
                                     }),
+                                    span: function_expression.span,
                                     identifier: None,
                                     compiled_bytecode: None,
                                 },
@@ -1584,7 +1707,7 @@ impl CompileEvaluation for ast::RegExpLiteral<'_> {
             ast::RegExpPattern::Pattern(_) => unreachable!(),
         };
         let pattern = String::from_str(ctx.agent, pattern, ctx.gc);
-        let regexp = reg_exp_create_literal(ctx.agent, pattern, Some(self.regex.flags), ctx.gc);
+        let regexp = reg_exp_create(ctx.agent, pattern, Some(self.regex.flags), ctx.gc);
         ctx.add_instruction_with_constant(Instruction::StoreConstant, regexp);
     }
 }
@@ -2306,8 +2429,11 @@ impl CompileEvaluation for ast::VariableDeclaration<'_> {
                     // 2. Let lhs be ? ResolveBinding(bindingId).
                     let identifier_string =
                         String::from_str(ctx.agent, identifier.name.as_str(), ctx.gc);
+                    ctx.add_instruction_with_identifier(
+                        Instruction::ResolveBinding,
+                        identifier_string,
+                    );
                     let identifier = ctx.add_identifier(identifier_string);
-                    ctx.add_instruction_with_immediate(Instruction::ResolveBinding, identifier);
                     ctx.add_instruction(Instruction::PushReference);
 
                     // 3. If IsAnonymousFunctionDefinition(Initializer) is true, then
@@ -2363,8 +2489,11 @@ impl CompileEvaluation for ast::VariableDeclaration<'_> {
                     // 1. Let lhs be ! ResolveBinding(StringValue of BindingIdentifier).
                     let identifier_string =
                         String::from_str(ctx.agent, identifier.name.as_str(), ctx.gc);
+                    ctx.add_instruction_with_identifier(
+                        Instruction::ResolveBinding,
+                        identifier_string,
+                    );
                     let identifier = ctx.add_identifier(identifier_string);
-                    ctx.add_instruction_with_immediate(Instruction::ResolveBinding, identifier);
 
                     let Some(init) = &decl.init else {
                         // LexicalBinding : BindingIdentifier
@@ -2430,8 +2559,9 @@ impl CompileEvaluation for ast::BlockStatement<'_> {
             // 1. Return EMPTY.
             return;
         }
+        let register_scope = ctx.enter_register_scope();
         let did_enter_declarative_environment =
-            block_declaration_instantiation::instantiation(ctx, self);
+            block_declaration_instantiation::instantiation(ctx, self, true);
         for ele in &self.body {
             ele.compile(ctx);
         }
@@ -2445,6 +2575,7 @@ impl CompileEvaluation for ast::BlockStatement<'_> {
                 *i -= 1;
             }
         }
+        ctx.exit_register_scope(register_scope);
     }
 }
 
@@ -2455,7 +2586,8 @@ impl CompileEvaluation for ast::ForStatement<'_> {
         let previous_break = ctx.current_break.replace(vec![]);
 
         let mut per_iteration_lets: Vec<String<'_>> = vec![];
-        let mut is_lexical = false;
+        let mut has_loop_environment = false;
+        let register_scope = ctx.enter_register_scope();
 
         if let Some(init) = &self.init {
             match init {
@@ -2497,44 +2629,65 @@ impl CompileEvaluation for ast::ForStatement<'_> {
                 ast::ForStatementInit::UnaryExpression(init) => init.compile(ctx),
                 ast::ForStatementInit::UpdateExpression(init) => init.compile(ctx),
                 ast::ForStatementInit::VariableDeclaration(init) => {
-                    is_lexical = init.kind.is_lexical();
-                    if is_lexical {
-                        // 1. Let oldEnv be the running execution context's LexicalEnvironment.
-                        // 2. Let loopEnv be NewDeclarativeEnvironment(oldEnv).
-                        // Note: This declaration environment is not something
-                        // that continue/break statements should care about. We
-                        // take care of tearing this one down.
-                        ctx.add_instruction(Instruction::EnterDeclarativeEnvironment);
+                    if init.kind.is_lexical() {
                         // 3. Let isConst be IsConstantDeclaration of LexicalDeclaration.
                         let is_const = init.kind.is_const();
                         // 4. Let boundNames be the BoundNames of LexicalDeclaration.
+                        // NOTE: Bindings that are only observable from this
+                        // function are placed in registers. As no closure can
+                        // capture them, they need neither the loop environment
+                        // nor per-iteration copies.
+                        let mut bound_names = vec![];
+                        for decl in &init.declarations {
+                            if let ast::BindingPatternKind::BindingIdentifier(identifier) =
+                                &decl.id.kind
+                            {
+                                if ctx.declare_scoped_register(
+                                    &identifier.name,
+                                    !is_const,
+                                    decl.span.end,
+                                ) {
+                                    continue;
+                                }
+                            }
+                            decl.id.bound_names(&mut |dn| {
+                                bound_names.push(String::from_str(
+                                    ctx.agent,
+                                    dn.name.as_str(),
+                                    ctx.gc,
+                                ))
+                            });
+                        }
+                        has_loop_environment = !bound_names.is_empty();
+                        if has_loop_environment {
+                            // 1. Let oldEnv be the running execution context's LexicalEnvironment.
+                            // 2. Let loopEnv be NewDeclarativeEnvironment(oldEnv).
+                            // Note: This declaration environment is not something
+                            // that continue/break statements should care about. We
+                            // take care of tearing this one down.
+                            ctx.add_instruction(Instruction::EnterDeclarativeEnvironment);
+                        }
                         // 5. For each element dn of boundNames, do
-                        // a. If isConst is true, then
-                        if is_const {
-                            init.bound_names(&mut |dn| {
+                        for identifier in bound_names {
+                            // a. If isConst is true, then
+                            if is_const {
                                 // i. Perform ! loopEnv.CreateImmutableBinding(dn, true).
-                                let identifier =
-                                    String::from_str(ctx.agent, dn.name.as_str(), ctx.gc);
                                 ctx.add_instruction_with_identifier(
                                     Instruction::CreateImmutableBinding,
                                     identifier,
                                 )
-                            });
-                        } else {
-                            // b. Else,
-                            // i. Perform ! loopEnv.CreateMutableBinding(dn, false).
-                            init.bound_names(&mut |dn| {
-                                let identifier =
-                                    String::from_str(ctx.agent, dn.name.as_str(), ctx.gc);
+                            } else {
+                                // b. Else,
                                 // 9. If isConst is false, let perIterationLets
                                 // be boundNames; otherwise let perIterationLets
                                 // be a new empty List.
                                 per_iteration_lets.push(identifier);
+                                // i. Perform ! loopEnv.CreateMutableBinding(dn, false).
                                 ctx.add_instruction_with_identifier(
                                     Instruction::CreateMutableBinding,
                                     identifier,
                                 )
-                            });
+                            }
                         }
                         // 6. Set the running execution context's LexicalEnvironment to loopEnv.
                     }
@@ -2558,11 +2711,8 @@ impl CompileEvaluation for ast::ForStatement<'_> {
                     // binding. We do not need to push and pop from the stack
                     // in this case but can use the result register directly.
                     // There are rather easy further optimizations available as
-                    // well around creating a sibling environment directly,
-                    // creating an initialized mutable binding directly, and
-                    // importantly: The whole loop environment is unnecessary
-                    // if the loop contains no closures (that capture the
-                    // per-iteration lets).
+                    // well around creating a sibling environment directly and
+                    // creating an initialized mutable binding directly.
 
                     let binding = *per_iteration_lets.first().unwrap();
                     // Get value of binding from lastIterationEnv.
@@ -2630,11 +2780,12 @@ impl CompileEvaluation for ast::ForStatement<'_> {
         for break_entry in own_breaks {
             ctx.set_jump_target_here(break_entry);
         }
-        if is_lexical {
+        if has_loop_environment {
             // Lexical binding loops have an extra declarative environment that
             // we need to exit from once we exit the loop.
             ctx.add_instruction(Instruction::ExitDeclarativeEnvironment);
         }
+        ctx.exit_register_scope(register_scope);
         ctx.current_break = previous_break;
         ctx.current_continue = previous_continue;
         ctx.current_depth_of_loop_scope = previous_depth_of_loop;
@@ -2655,8 +2806,10 @@ impl CompileEvaluation for ast::SwitchStatement<'_> {
         // 4. Let blockEnv be NewDeclarativeEnvironment(oldEnv).
         // 6. Set the running execution context's LexicalEnvironment to blockEnv.
         // 5. Perform BlockDeclarationInstantiation(CaseBlock, blockEnv).
+        // NOTE: Case clauses can jump past declarations, so case block
+        // bindings always live in the environment.
         let did_enter_declarative_environment =
-            block_declaration_instantiation::instantiation(ctx, self);
+            block_declaration_instantiation::instantiation(ctx, self, false);
 
         // 7. Let R be Completion(CaseBlockEvaluation of CaseBlock with argument switchValue).
         let mut has_default = false;
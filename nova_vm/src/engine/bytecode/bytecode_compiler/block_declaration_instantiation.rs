@@ -2,6 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use oxc_ast::ast::BindingPatternKind;
 use oxc_ecmascript::BoundNames;
 
 use crate::ecmascript::types::String;
@@ -24,15 +25,34 @@ use super::{
 /// > Record is created and bindings for each block scoped variable, constant,
 /// > function, or class declared in the block are instantiated in the
 /// > Environment Record.
+///
+/// If `use_registers` is true, `let` and `const` declarations that are only
+/// observable from the current function are placed in registers instead. The
+/// Declarative Environment Record is only entered if some binding still needs
+/// it; the return value tells if it was.
 pub(super) fn instantiation<'a>(
     ctx: &mut CompileContext,
     code: &'a impl LexicallyScopedDeclarations<'a>,
+    use_registers: bool,
 ) -> bool {
     let mut did_enter_declarative_environment = false;
     // 1. Let declarations be the LexicallyScopedDeclarations of code.
     // 2. Let privateEnv be the running execution context's PrivateEnvironment.
     // 3. For each element d of declarations, do
     code.lexically_scoped_declarations(&mut |d| {
+        if use_registers {
+            if let LexicallyScopedDeclaration::Variable(decl) = d {
+                if let BindingPatternKind::BindingIdentifier(identifier) = &decl.id.kind {
+                    if ctx.declare_scoped_register(
+                        &identifier.name,
+                        !decl.kind.is_const(),
+                        decl.span.end,
+                    ) {
+                        return;
+                    }
+                }
+            }
+        }
         if !did_enter_declarative_environment {
             did_enter_declarative_environment = true;
             ctx.add_instruction(Instruction::EnterDeclarativeEnvironment);
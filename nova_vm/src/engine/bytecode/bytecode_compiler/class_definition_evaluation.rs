@@ -466,6 +466,8 @@ impl CompileEvaluation for ast::Class<'_> {
                     is_lexical: false,
                     // Class code is always strict.
                     is_strict: true,
+                    // The constructor is cached with the class's code.
+                    source_text: None,
                 };
                 constructor_ctx.compile_function_body(constructor_data);
                 let executable = constructor_ctx.finish();
@@ -593,6 +595,7 @@ fn define_constructor_method(
                     &class_element.value,
                 )
             }),
+            span: class_element.value.span,
             // CompileContext holds a name identifier for us if this is NamedEvaluation.
             identifier: None,
             compiled_bytecode: None,
@@ -655,6 +658,7 @@ fn define_method(class_element: &ast::MethodDefinition, ctx: &mut CompileContext
                     &class_element.value,
                 )
             }),
+            span: class_element.value.span,
             identifier,
             compiled_bytecode: None,
         },
@@ -3,8 +3,8 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use ahash::{AHashMap, AHashSet};
-use oxc_ast::ast::{FormalParameters, FunctionBody};
-use oxc_ecmascript::BoundNames;
+use oxc_ast::ast::{BindingPatternKind, FormalParameters, FunctionBody};
+use oxc_ecmascript::{BoundNames, IsSimpleParameterList};
 use oxc_span::Atom;
 
 use crate::{
@@ -99,10 +99,22 @@ pub(crate) fn instantiation(
         }
     }
 
+    // NOTE: Parameters of a simple parameter list can be placed in registers.
+    // A mapped arguments object aliases the parameters through the
+    // environment, so non-strict functions that need one keep them there.
+    let may_use_parameter_registers = formals.is_simple_parameter_list()
+        && !has_duplicates
+        && (strict || !arguments_object_needed);
+    let mut has_parameter_registers = false;
+
     // 21. For each String paramName of parameterNames, do
     // NOTE: The behavior should not depend on the order in which the parameter
     // names are iterated, so it's fine for `parameter_names` to be a set.
     for param_name in &parameter_names {
+        if may_use_parameter_registers && ctx.declare_register(param_name, true).is_some() {
+            has_parameter_registers = true;
+            continue;
+        }
         // a. Let alreadyDeclared be ! env.HasBinding(paramName).
         // b. NOTE: Early errors ensure that duplicate parameter names can only occur in non-strict functions that do not have parameter default values or rest parameters.
         // c. If alreadyDeclared is false, then
@@ -168,7 +180,9 @@ pub(crate) fn instantiation(
     if !formals.has_parameter() {
         // Remove the arguments iterator from the iterator stack.
         ctx.add_instruction(Instruction::IteratorClose)
-    } else if has_parameter_expressions {
+    } else if has_parameter_expressions || has_parameter_registers {
+        // NOTE: The simple array binding pattern binds through the
+        // environment, so it cannot initialize registers.
         complex_array_pattern(
             ctx,
             formals.items.iter().map(|param| Some(&param.pattern)),
@@ -279,6 +293,18 @@ pub(crate) fn instantiation(
     // 33. Let lexDeclarations be the LexicallyScopedDeclarations of code.
     // 34. For each element d of lexDeclarations, do
     for d in function_body_lexically_scoped_decarations(body) {
+        // NOTE: Variables that are only observable from this function are
+        // placed in registers instead. They start out uninitialized.
+        if let LexicallyScopedDeclaration::Variable(decl) = &d {
+            if let BindingPatternKind::BindingIdentifier(identifier) = &decl.id.kind {
+                if ctx
+                    .declare_register(&identifier.name, !decl.kind.is_const())
+                    .is_some()
+                {
+                    continue;
+                }
+            }
+        }
         // a. NOTE: A lexically declared name cannot be the same as a function/generator declaration, formal parameter, or a var name. Lexically declared names are only instantiated here but not initialized.
         // b. For each element dn of the BoundNames of d, do
         match d {
@@ -0,0 +1,255 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! ## Register allocation for function-local bindings
+//!
+//! A `let`, `const` or parameter binding can be kept in a register of the
+//! function's VM frame instead of a Declarative Environment Record when
+//! nothing but the function's own code can observe it. This requires that:
+//!
+//! 1. The binding is not referenced from a nested function, arrow function or
+//!    class, as those close over the environment and outlive the frame.
+//! 2. The function contains no `with` statements or direct `eval` calls, as
+//!    those resolve names dynamically.
+//! 3. The name is declared exactly once in the function. This keeps scoping
+//!    trivial: a register binding never shadows, nor is it shadowed by,
+//!    another binding of the same function, so any reference to the name
+//!    within the declaring scope refers to the register.
+//!
+//! Every other binding falls back to environment records.
+
+use ahash::AHashMap;
+use oxc_ast::ast::{FormalParameters, FunctionBody};
+
+use crate::ecmascript::syntax_directed_operations::scope_analysis::{
+    function_name_usage, NameUsage,
+};
+
+use super::IndexType;
+
+#[derive(Debug)]
+struct RegisterBinding {
+    name: Box<str>,
+    register: IndexType,
+    mutable: bool,
+}
+
+/// Register allocation state of a function being compiled.
+#[derive(Debug)]
+pub(super) struct FunctionRegisters {
+    names: AHashMap<Box<str>, NameUsage>,
+    /// Register bindings currently in scope, innermost last.
+    bindings: Vec<RegisterBinding>,
+    /// Number of registers allocated so far. Registers are not reused between
+    /// sibling scopes.
+    register_count: usize,
+}
+
+/// A register binding in scope, as found by [`FunctionRegisters::lookup`].
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Register {
+    pub(super) index: IndexType,
+    pub(super) mutable: bool,
+}
+
+impl FunctionRegisters {
+    /// Analyse a function's parameters and body. Returns `None` if no
+    /// binding of the function can be placed in a register.
+    pub(super) fn analyze(params: &FormalParameters, body: &FunctionBody) -> Option<Self> {
+        let usage = function_name_usage(params, body);
+        if usage.has_dynamic_scope {
+            return None;
+        }
+        Some(Self {
+            names: usage.names,
+            bindings: Vec::new(),
+            register_count: 0,
+        })
+    }
+
+    /// Returns true if the binding `name` may be placed in a register.
+    pub(super) fn is_candidate(&self, name: &str) -> bool {
+        name != "arguments"
+            && self
+                .names
+                .get(name)
+                .is_some_and(|usage| usage.declarations == 1 && !usage.captured)
+    }
+
+    /// Returns true if the name is referenced before source position `end`.
+    ///
+    /// A register binding declared in a scope that can be entered multiple
+    /// times needs to be reset to uninitialized on entry if it can be read
+    /// before its declaration is evaluated.
+    pub(super) fn is_referenced_before(&self, name: &str, end: u32) -> bool {
+        self.names
+            .get(name)
+            .and_then(|usage| usage.first_reference)
+            .is_some_and(|start| start < end)
+    }
+
+    /// Allocate a register for a binding and bring it into scope.
+    pub(super) fn declare(&mut self, name: &str, mutable: bool) -> IndexType {
+        debug_assert!(self.is_candidate(name));
+        let register =
+            IndexType::try_from(self.register_count).expect("Too many registers in function");
+        self.register_count += 1;
+        self.bindings.push(RegisterBinding {
+            name: name.into(),
+            register,
+            mutable,
+        });
+        register
+    }
+
+    /// Find the register binding for a name, if one is in scope.
+    pub(super) fn lookup(&self, name: &str) -> Option<Register> {
+        self.bindings
+            .iter()
+            .rev()
+            .find(|binding| &*binding.name == name)
+            .map(|binding| Register {
+                index: binding.register,
+                mutable: binding.mutable,
+            })
+    }
+
+    /// Marker for the start of a scope; pass to [`Self::exit_scope`].
+    pub(super) fn enter_scope(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Bring all register bindings declared since `scope` out of scope.
+    pub(super) fn exit_scope(&mut self, scope: usize) {
+        self.bindings.truncate(scope);
+    }
+
+    pub(super) fn register_count(&self) -> usize {
+        self.register_count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::ast::Statement;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use super::FunctionRegisters;
+    use crate::test_utils::TestAgent;
+
+    /// Analyse the function declared by the source text.
+    fn analyze(source_text: &str) -> Option<FunctionRegisters> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_script(true);
+        let program = Parser::new(&allocator, source_text, source_type)
+            .parse()
+            .program;
+        let Some(Statement::FunctionDeclaration(function)) = program.body.first() else {
+            panic!("Expected a function declaration:\n{source_text}");
+        };
+        FunctionRegisters::analyze(&function.params, function.body.as_ref().unwrap())
+    }
+
+    #[test]
+    fn register_bindings() {
+        let registers =
+            analyze("function f(a, b) { let c = a; const d = b; return c + d; }").unwrap();
+        assert!(["a", "b", "c", "d"]
+            .iter()
+            .all(|name| registers.is_candidate(name)));
+        // Names declared more than once stay in environments.
+        let registers = analyze("function f(a) { { let b; } { let b; } var a; }").unwrap();
+        assert!(!registers.is_candidate("a"));
+        assert!(!registers.is_candidate("b"));
+
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval(
+                "(function (a, b) { let s = 0; for (let i = a; i < b; i++) { const x = i * 2; s += x; } return s; })(0, 10)"
+            ),
+            "90"
+        );
+        assert_eq!(
+            agent.eval_err("(function () { x; let x = 1; })()"),
+            "ReferenceError: Identifier 'x' has not been initialized."
+        );
+        assert!(agent
+            .eval_err("(function () { const c = 1; c = 2; })()")
+            .starts_with("TypeError"));
+    }
+
+    #[test]
+    fn closure_captured_bindings() {
+        let registers = analyze(
+            "function f(a, b) { let c; let d; class C { m() { return d; } } return () => b; }",
+        )
+        .unwrap();
+        assert!(registers.is_candidate("a"));
+        assert!(!registers.is_candidate("b"));
+        assert!(registers.is_candidate("c"));
+        assert!(!registers.is_candidate("d"));
+
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval(
+                "(function (a) { let b = 1; const get = () => a + b; a = 10; b = 20; return get(); })(1)"
+            ),
+            "30"
+        );
+        assert_eq!(
+            agent.eval(
+                "(function () { const fns = []; for (let i = 0; i < 3; i++) fns.push(function () { return i; }); return fns.map(f => f()).join(); })()"
+            ),
+            "0,1,2"
+        );
+    }
+
+    #[test]
+    fn eval_captured_bindings() {
+        assert!(analyze("function f(a) { let b; eval('a + b'); }").is_none());
+        // A direct eval in a nested function can see every binding of the
+        // enclosing function.
+        assert!(analyze("function f(a) { let b; return () => eval('b'); }").is_none());
+
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval("(function (a) { let b = 2; return eval('a + b'); })(1)"),
+            "3"
+        );
+        assert_eq!(
+            agent.eval("(function () { let b = 2; eval('b = 5'); return b; })()"),
+            "5"
+        );
+    }
+
+    #[test]
+    fn with_captured_bindings() {
+        assert!(analyze("function f(a) { let b; with (a) { b; } }").is_none());
+    }
+
+    #[test]
+    fn arguments_captured_bindings() {
+        let registers = analyze("function f(a) { let arguments; return a; }").unwrap();
+        assert!(registers.is_candidate("a"));
+        assert!(!registers.is_candidate("arguments"));
+
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            agent.eval(
+                "(function (a) { 'use strict'; arguments[0] = 2; return a + arguments[0]; })(1)"
+            ),
+            "3"
+        );
+        assert_eq!(
+            agent.eval("(function (a = 0) { a = 2; return a + arguments[0]; })(1)"),
+            "3"
+        );
+        assert_eq!(
+            agent.eval("(function () { let arguments = 1; return arguments; })()"),
+            "1"
+        );
+    }
+}
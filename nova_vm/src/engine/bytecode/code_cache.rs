@@ -0,0 +1,693 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! ## Bytecode cache
+//!
+//! The bytecode of Scripts and function bodies can be stored in a
+//! [`CodeCache`] that the host provides through [`HostHooks::code_cache`],
+//! keyed by a hash of the compiled source text. When the cache has bytecode
+//! for a source text, it is read from the cache instead of being compiled
+//! again.
+//!
+//! The source text is hashed with SHA-256, which is stable across builds and
+//! platforms. Cached bytecode stores the full hash and the length of its
+//! source text, and is only used when both match: A host may key its cache
+//! by the first bytes of the hash alone. As the VM trusts the bytecode it
+//! runs, the operands of cached instructions are validated when they are
+//! read.
+//!
+//! Parsing is not skipped: Function objects and declaration instantiation
+//! work on the parsed code. The function expressions that cached bytecode
+//! refers to are re-linked to the parsed code by their span, relative to the
+//! start of the compiled source text. This also lets equal functions in
+//! different sources share their cached bytecode.
+//!
+//! Constants are stored by their contents. Bytecode with constants that are
+//! not primitive values, such as RegExp literals, is not cached.
+//!
+//! [`HostHooks::code_cache`]: crate::ecmascript::execution::agent::HostHooks::code_cache
+
+use ahash::AHashMap;
+use oxc_ast::{
+    ast::{ArrowFunctionExpression, FormalParameters, Function, FunctionBody, Program},
+    visit::walk,
+    Visit,
+};
+use oxc_span::{SourceType, Span};
+use oxc_syntax::scope::ScopeFlags;
+use sha2::{Digest, Sha256};
+
+use super::{
+    executable::{running_source_code, ArrowFunctionExpression as ArrowFunctionExpressionData},
+    property_cache::PropertyCache,
+    Executable, ExecutableHeapData, FunctionExpression, IndexType, Instruction, SendableRef,
+};
+use crate::{
+    ecmascript::{
+        execution::Agent,
+        syntax_directed_operations::function_definitions::CompileFunctionBodyData,
+        types::{BigInt, IntoValue, Number, String, Value},
+    },
+    engine::context::NoGcScope,
+    heap::{
+        CreateHeapData, SnapshotData, SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter,
+    },
+};
+
+/// Storage for compiled bytecode, provided by the host.
+///
+/// The cache is keyed by a hash of the compiled source text, the kind of
+/// code and the version of the engine. Entries may be evicted at any time;
+/// bytecode that is missing or cannot be read is compiled again.
+pub trait CodeCache {
+    /// Get the bytecode stored under the key, if any.
+    fn get(&self, key: u64) -> Option<Vec<u8>>;
+
+    /// Store bytecode under the key.
+    fn set(&self, key: u64, bytecode: &[u8]);
+}
+
+/// Identifies the bytes as cached bytecode.
+const CODE_CACHE_MAGIC: &[u8; 8] = b"NOVACODE";
+
+/// Version of the cached bytecode format; bumped on every incompatible
+/// change, including changes to the instruction set.
+const CODE_CACHE_FORMAT_VERSION: u32 = 1;
+
+/// Identifies the source text that bytecode is compiled from, and the flags
+/// that affect the compilation.
+pub(super) struct CodeCacheSource {
+    /// SHA-256 hash of the source text, the flags and the engine version.
+    hash: [u8; 32],
+    /// Length of the source text.
+    len: u32,
+    /// Offset of the source text in its source code.
+    offset: u32,
+}
+
+impl CodeCacheSource {
+    /// The source text of a Script.
+    pub(super) fn script(text: &str, source_type: SourceType) -> Self {
+        let flags = [true, source_type.is_module(), source_type.is_typescript()];
+        Self::new(text, 0, flags)
+    }
+
+    /// The source text of a function, starting at the given offset in its
+    /// source code.
+    pub(super) fn function(text: &str, offset: u32, data: &CompileFunctionBodyData) -> Self {
+        let flags = [false, data.is_strict, data.is_lexical, data.is_concise_body];
+        Self::new(text, offset, flags)
+    }
+
+    fn new<const N: usize>(text: &str, offset: u32, flags: [bool; N]) -> Self {
+        let version = env!("CARGO_PKG_VERSION");
+        let mut hasher = Sha256::new();
+        hasher.update(CODE_CACHE_FORMAT_VERSION.to_le_bytes());
+        hasher.update((version.len() as u32).to_le_bytes());
+        hasher.update(version);
+        hasher.update(flags.map(u8::from));
+        hasher.update(text);
+        Self {
+            hash: hasher.finalize().into(),
+            len: text.len() as u32,
+            offset,
+        }
+    }
+
+    /// The key of the source text in the host's code cache.
+    fn key(&self) -> u64 {
+        u64::from_le_bytes(self.hash[..8].try_into().unwrap())
+    }
+
+    fn relative_span(&self, span: Span) -> Span {
+        Span::new(span.start - self.offset, span.end - self.offset)
+    }
+
+    fn absolute_span(&self, span: Span) -> Span {
+        Span::new(span.start + self.offset, span.end + self.offset)
+    }
+}
+
+/// The function nodes of parsed code, keyed by span.
+#[derive(Default)]
+pub(crate) struct FunctionNodes {
+    functions: AHashMap<Span, &'static Function<'static>>,
+    arrow_functions: AHashMap<Span, &'static ArrowFunctionExpression<'static>>,
+}
+
+impl FunctionNodes {
+    /// Collect the function nodes of a Script.
+    pub(crate) fn of_program(program: &Program<'static>) -> Self {
+        let mut nodes = Self::default();
+        nodes.visit_program(program);
+        nodes
+    }
+
+    /// Collect the function nodes of a function.
+    pub(super) fn of_function(
+        params: &FormalParameters<'static>,
+        body: &FunctionBody<'static>,
+    ) -> Self {
+        let mut nodes = Self::default();
+        nodes.visit_formal_parameters(params);
+        nodes.visit_function_body(body);
+        nodes
+    }
+
+    /// Find the function with the given span.
+    pub(crate) fn function(&self, span: Span) -> Option<&'static Function<'static>> {
+        self.functions.get(&span).copied()
+    }
+
+    /// Find the arrow function with the given span.
+    pub(crate) fn arrow_function(
+        &self,
+        span: Span,
+    ) -> Option<&'static ArrowFunctionExpression<'static>> {
+        self.arrow_functions.get(&span).copied()
+    }
+}
+
+impl<'a> Visit<'a> for FunctionNodes {
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        // SAFETY: The nodes are only used while the SourceCode that owns them
+        // is alive, like the nodes referred to by compiled bytecode.
+        let node = unsafe { std::mem::transmute::<&Function<'a>, &'static Function<'static>>(it) };
+        self.functions.insert(it.span, node);
+        walk::walk_function(self, it, flags);
+    }
+
+    fn visit_arrow_function_expression(&mut self, it: &ArrowFunctionExpression<'a>) {
+        // SAFETY: See above.
+        let node = unsafe {
+            std::mem::transmute::<
+                &ArrowFunctionExpression<'a>,
+                &'static ArrowFunctionExpression<'static>,
+            >(it)
+        };
+        self.arrow_functions.insert(it.span, node);
+        walk::walk_arrow_function_expression(self, it);
+    }
+}
+
+/// Load the bytecode compiled from the source from the host's code cache.
+pub(super) fn load_cached_executable(
+    agent: &mut Agent,
+    source: &CodeCacheSource,
+    nodes: impl FnOnce(&Agent) -> FunctionNodes,
+    gc: NoGcScope,
+) -> Option<Executable> {
+    let bytes = agent.host_hooks.code_cache()?.get(source.key())?;
+    let mut reader = SnapshotReader::new(&bytes);
+    if reader.read_bytes(CODE_CACHE_MAGIC.len()).ok()? != CODE_CACHE_MAGIC
+        || u32::deserialize(&mut reader).ok()? != CODE_CACHE_FORMAT_VERSION
+        || u32::deserialize(&mut reader).ok()? != source.len
+        || reader.read_bytes(source.hash.len()).ok()? != source.hash
+    {
+        return None;
+    }
+    let nodes = nodes(agent);
+    let executable = read_executable(agent, &mut reader, source, &nodes, gc).ok()?;
+    if !reader.is_empty() {
+        return None;
+    }
+    Some(executable)
+}
+
+/// Store the bytecode compiled from the source in the host's code cache.
+pub(super) fn store_cached_executable(
+    agent: &Agent,
+    source: &CodeCacheSource,
+    executable: Executable,
+) {
+    let Some(code_cache) = agent.host_hooks.code_cache() else {
+        return;
+    };
+    let mut writer = SnapshotWriter::default();
+    writer.write_bytes(CODE_CACHE_MAGIC);
+    let header = CODE_CACHE_FORMAT_VERSION
+        .serialize(&mut writer)
+        .and_then(|_| source.len.serialize(&mut writer));
+    writer.write_bytes(&source.hash);
+    if header.is_err() || write_executable(agent, &mut writer, source, executable).is_err() {
+        // The bytecode cannot be cached.
+        return;
+    }
+    code_cache.set(source.key(), &writer.into_bytes());
+}
+
+fn write_executable(
+    agent: &Agent,
+    writer: &mut SnapshotWriter,
+    source: &CodeCacheSource,
+    executable: Executable,
+) -> SnapshotResult<()> {
+    let ExecutableHeapData {
+        instructions,
+        constants,
+        function_expressions,
+        arrow_function_expressions,
+        class_initializer_bytecodes,
+        register_count,
+        property_caches,
+        source_code: _,
+    } = &agent[executable];
+    instructions.serialize(writer)?;
+    (constants.len() as u32).serialize(writer)?;
+    for constant in constants.iter() {
+        write_constant(agent, writer, *constant)?;
+    }
+    (function_expressions.len() as u32).serialize(writer)?;
+    for FunctionExpression {
+        expression: _,
+        span,
+        identifier,
+        compiled_bytecode,
+    } in function_expressions.iter()
+    {
+        source.relative_span(*span).serialize(writer)?;
+        identifier.serialize(writer)?;
+        write_optional_executable(agent, writer, source, *compiled_bytecode)?;
+    }
+    (arrow_function_expressions.len() as u32).serialize(writer)?;
+    for ArrowFunctionExpressionData {
+        expression: _,
+        span,
+        identifier,
+    } in arrow_function_expressions.iter()
+    {
+        source.relative_span(*span).serialize(writer)?;
+        identifier.serialize(writer)?;
+    }
+    (class_initializer_bytecodes.len() as u32).serialize(writer)?;
+    for (bytecode, has_constructor_parent) in class_initializer_bytecodes.iter() {
+        write_optional_executable(agent, writer, source, *bytecode)?;
+        has_constructor_parent.serialize(writer)?;
+    }
+    (*register_count as u32).serialize(writer)?;
+    // Inline caches start out empty.
+    (property_caches.len() as u32).serialize(writer)
+}
+
+fn write_optional_executable(
+    agent: &Agent,
+    writer: &mut SnapshotWriter,
+    source: &CodeCacheSource,
+    executable: Option<Executable>,
+) -> SnapshotResult<()> {
+    executable.is_some().serialize(writer)?;
+    match executable {
+        Some(executable) => write_executable(agent, writer, source, executable),
+        None => Ok(()),
+    }
+}
+
+fn read_executable(
+    agent: &mut Agent,
+    reader: &mut SnapshotReader,
+    source: &CodeCacheSource,
+    nodes: &FunctionNodes,
+    gc: NoGcScope,
+) -> SnapshotResult<Executable> {
+    let instructions = Box::<[u8]>::deserialize(reader)?;
+    let mut constants = Vec::new();
+    for _ in 0..u32::deserialize(reader)? {
+        constants.push(read_constant(agent, reader, gc)?);
+    }
+    let mut function_expressions = Vec::new();
+    for _ in 0..u32::deserialize(reader)? {
+        let span = source.absolute_span(Span::deserialize(reader)?);
+        let expression = *nodes.functions.get(&span).ok_or(SnapshotError::Malformed)?;
+        function_expressions.push(FunctionExpression {
+            expression: SendableRef::new(expression),
+            span,
+            identifier: SnapshotData::deserialize(reader)?,
+            compiled_bytecode: read_optional_executable(agent, reader, source, nodes, gc)?,
+        });
+    }
+    let mut arrow_function_expressions = Vec::new();
+    for _ in 0..u32::deserialize(reader)? {
+        let span = source.absolute_span(Span::deserialize(reader)?);
+        let expression = *nodes
+            .arrow_functions
+            .get(&span)
+            .ok_or(SnapshotError::Malformed)?;
+        arrow_function_expressions.push(ArrowFunctionExpressionData {
+            expression: SendableRef::new(expression),
+            span,
+            identifier: SnapshotData::deserialize(reader)?,
+        });
+    }
+    let mut class_initializer_bytecodes = Vec::new();
+    for _ in 0..u32::deserialize(reader)? {
+        let bytecode = read_optional_executable(agent, reader, source, nodes, gc)?;
+        class_initializer_bytecodes.push((bytecode, bool::deserialize(reader)?));
+    }
+    let register_count = u32::deserialize(reader)? as usize;
+    let property_cache_count = u32::deserialize(reader)?;
+    let source_code = running_source_code(agent);
+    let data = ExecutableHeapData {
+        instructions,
+        constants: constants.into_boxed_slice(),
+        function_expressions: function_expressions.into_boxed_slice(),
+        arrow_function_expressions: arrow_function_expressions.into_boxed_slice(),
+        class_initializer_bytecodes: class_initializer_bytecodes.into_boxed_slice(),
+        register_count,
+        property_caches: (0..property_cache_count)
+            .map(|_| PropertyCache::default())
+            .collect(),
+        source_code,
+    };
+    validate_instructions(&data)?;
+    Ok(agent.heap.create(data))
+}
+
+/// Check that the instructions are well formed and that their operands refer
+/// to existing constants, function expressions, registers, inline caches and
+/// instructions.
+fn validate_instructions(data: &ExecutableHeapData) -> SnapshotResult<()> {
+    let instructions = &data.instructions[..];
+    let is_constant = |index: usize| index < data.constants.len();
+    let is_identifier = |index: usize| {
+        data.constants
+            .get(index)
+            .is_some_and(|constant| String::try_from(*constant).is_ok())
+    };
+    // Jumps must land at the start of an instruction, or at the end of the
+    // bytecode.
+    let mut instruction_starts = vec![false; instructions.len() + 1];
+    instruction_starts[instructions.len()] = true;
+    let mut jump_targets = Vec::new();
+    let mut ip = 0;
+    while ip < instructions.len() {
+        instruction_starts[ip] = true;
+        let kind = Instruction::from_u8(instructions[ip]).ok_or(SnapshotError::Malformed)?;
+        ip += 1;
+        let mut args = [0; 2];
+        for arg in args.iter_mut().take(kind.argument_count() as usize) {
+            let bytes = instructions
+                .get(ip..ip + 2)
+                .ok_or(SnapshotError::Malformed)?;
+            *arg = IndexType::from_ne_bytes([bytes[0], bytes[1]]) as usize;
+            ip += 2;
+        }
+        let valid = match kind {
+            _ if kind.has_jump_slot() => {
+                jump_targets.push(args[0]);
+                true
+            }
+            Instruction::LoadConstant
+            | Instruction::StoreConstant
+            | Instruction::BindingPatternGetValueNamed => is_constant(args[0]),
+            Instruction::BindingPatternBindNamed => is_identifier(args[0]) && is_constant(args[1]),
+            Instruction::ResolveRegister | Instruction::ResolveImmutableRegister => {
+                is_identifier(args[0]) && args[1] < data.register_count
+            }
+            Instruction::EvaluatePropertyAccessWithIdentifierKey => {
+                is_identifier(args[0]) && args[1] < data.property_caches.len()
+            }
+            Instruction::ClearRegister => args[0] < data.register_count,
+            Instruction::InstantiateArrowFunctionExpression => {
+                args[0] < data.arrow_function_expressions.len()
+            }
+            Instruction::ClassDefineDefaultConstructor => {
+                args[0] < data.class_initializer_bytecodes.len()
+            }
+            _ if kind.has_identifier_index() => is_identifier(args[0]),
+            _ if kind.has_function_expression_index() => args[0] < data.function_expressions.len(),
+            _ => true,
+        };
+        if !valid {
+            return Err(SnapshotError::Malformed);
+        }
+    }
+    if jump_targets
+        .into_iter()
+        .all(|target| instruction_starts.get(target) == Some(&true))
+    {
+        Ok(())
+    } else {
+        Err(SnapshotError::Malformed)
+    }
+}
+
+fn read_optional_executable(
+    agent: &mut Agent,
+    reader: &mut SnapshotReader,
+    source: &CodeCacheSource,
+    nodes: &FunctionNodes,
+    gc: NoGcScope,
+) -> SnapshotResult<Option<Executable>> {
+    if bool::deserialize(reader)? {
+        Ok(Some(read_executable(agent, reader, source, nodes, gc)?))
+    } else {
+        Ok(None)
+    }
+}
+
+const UNDEFINED_CONSTANT: u8 = 0;
+const NULL_CONSTANT: u8 = 1;
+const BOOLEAN_CONSTANT: u8 = 2;
+const STRING_CONSTANT: u8 = 3;
+const NUMBER_CONSTANT: u8 = 4;
+const BIGINT_CONSTANT: u8 = 5;
+
+fn write_constant(
+    agent: &Agent,
+    writer: &mut SnapshotWriter,
+    constant: Value,
+) -> SnapshotResult<()> {
+    if let Ok(string) = String::try_from(constant) {
+        let text = string
+            .as_wtf8(agent)
+            .as_str()
+            .ok_or(SnapshotError::UnsupportedHeapData(
+                "strings with lone surrogates",
+            ))?;
+        STRING_CONSTANT.serialize(writer)?;
+        return text.to_owned().serialize(writer);
+    }
+    if let Ok(number) = Number::try_from(constant) {
+        NUMBER_CONSTANT.serialize(writer)?;
+        return number.into_f64(agent).serialize(writer);
+    }
+    if let Ok(bigint) = BigInt::try_from(constant) {
+        let data = match bigint {
+            BigInt::BigInt(heap_bigint) => agent[heap_bigint].data.clone(),
+            BigInt::SmallBigInt(small_bigint) => small_bigint.into_i64().into(),
+        };
+        BIGINT_CONSTANT.serialize(writer)?;
+        return data.to_signed_bytes_le().serialize(writer);
+    }
+    match constant {
+        Value::Undefined => UNDEFINED_CONSTANT.serialize(writer),
+        Value::Null => NULL_CONSTANT.serialize(writer),
+        Value::Boolean(value) => {
+            BOOLEAN_CONSTANT.serialize(writer)?;
+            value.serialize(writer)
+        }
+        _ => Err(SnapshotError::UnsupportedHeapData("object constants")),
+    }
+}
+
+fn read_constant(
+    agent: &mut Agent,
+    reader: &mut SnapshotReader,
+    gc: NoGcScope,
+) -> SnapshotResult<Value> {
+    let value = match u8::deserialize(reader)? {
+        UNDEFINED_CONSTANT => Value::Undefined,
+        NULL_CONSTANT => Value::Null,
+        BOOLEAN_CONSTANT => Value::Boolean(bool::deserialize(reader)?),
+        STRING_CONSTANT => {
+            let text = std::string::String::deserialize(reader)?;
+            String::from_string(agent, text, gc).into_value()
+        }
+        NUMBER_CONSTANT => Number::from_f64(agent, f64::deserialize(reader)?, gc).into_value(),
+        BIGINT_CONSTANT => {
+            let bytes = Vec::<u8>::deserialize(reader)?;
+            let data = num_bigint::BigInt::from_signed_bytes_le(&bytes);
+            BigInt::from_num_bigint(agent, data).into_value()
+        }
+        _ => return Err(SnapshotError::Malformed),
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    use ahash::AHashMap;
+
+    use super::{validate_instructions, CodeCache};
+    use crate::{
+        ecmascript::{
+            execution::agent::{HostHooks, Job, Options},
+            scripts_and_modules::{script::parse_script, source_code::SourceCode},
+            types::{String, Value},
+        },
+        engine::bytecode::{ExecutableHeapData, IndexType, Instruction},
+        test_utils::TestAgent,
+    };
+
+    #[derive(Debug, Default)]
+    struct MemoryCodeCache {
+        entries: Mutex<AHashMap<u64, Vec<u8>>>,
+        stores: AtomicUsize,
+    }
+
+    impl CodeCache for MemoryCodeCache {
+        fn get(&self, key: u64) -> Option<Vec<u8>> {
+            self.entries.lock().unwrap().get(&key).cloned()
+        }
+
+        fn set(&self, key: u64, bytecode: &[u8]) {
+            self.stores.fetch_add(1, Ordering::Relaxed);
+            self.entries.lock().unwrap().insert(key, bytecode.to_vec());
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct CachingHostHooks {
+        cache: MemoryCodeCache,
+    }
+
+    impl HostHooks for CachingHostHooks {
+        fn enqueue_promise_job(&self, _job: Job) {}
+
+        fn code_cache(&self) -> Option<&dyn CodeCache> {
+            Some(&self.cache)
+        }
+    }
+
+    const SCRIPT: &str = "
+        function fib(n) { return n < 2 ? n : fib(n - 1) + fib(n - 2); }
+        var double = (x) => x * 2;
+        var o = { get big() { return 10n ** 3n; }, name: 'cached' };
+        var result = [fib(10), double(4.5), o.big, o.name, null, true].join();
+        result";
+    const RESULT: &str = "55,9,1000,cached,,true";
+
+    fn eval(hooks: &'static CachingHostHooks) -> std::string::String {
+        TestAgent::with_host_hooks(Options::default(), hooks).eval(SCRIPT)
+    }
+
+    #[test]
+    fn cached_bytecode_is_used() {
+        let hooks: &'static CachingHostHooks = Box::leak(Box::default());
+        assert_eq!(eval(hooks), RESULT);
+        let stores = hooks.cache.stores.load(Ordering::Relaxed);
+        // The script and the functions that were called.
+        assert!(stores >= 4);
+        assert_eq!(eval(hooks), RESULT);
+        assert_eq!(hooks.cache.stores.load(Ordering::Relaxed), stores);
+    }
+
+    #[test]
+    fn mismatched_cache_entries_are_compiled_again() {
+        let hooks: &'static CachingHostHooks = Box::leak(Box::default());
+        assert_eq!(eval(hooks), RESULT);
+        let stores = hooks.cache.stores.load(Ordering::Relaxed);
+
+        // An entry for different source text with the same key.
+        for entry in hooks.cache.entries.lock().unwrap().values_mut() {
+            // Last byte of the source hash after the magic, version and
+            // length.
+            entry[8 + 4 + 4 + 31] ^= 1;
+        }
+        assert_eq!(eval(hooks), RESULT);
+        assert_eq!(hooks.cache.stores.load(Ordering::Relaxed), 2 * stores);
+
+        // Truncated entries.
+        for entry in hooks.cache.entries.lock().unwrap().values_mut() {
+            entry.pop();
+        }
+        assert_eq!(eval(hooks), RESULT);
+        assert_eq!(hooks.cache.stores.load(Ordering::Relaxed), 3 * stores);
+    }
+
+    fn executable_data(
+        instructions: &[u8],
+        constants: Vec<Value>,
+        source_code: SourceCode,
+    ) -> ExecutableHeapData {
+        ExecutableHeapData {
+            instructions: instructions.into(),
+            constants: constants.into_boxed_slice(),
+            function_expressions: Box::default(),
+            arrow_function_expressions: Box::default(),
+            class_initializer_bytecodes: Box::default(),
+            register_count: 1,
+            property_caches: Box::default(),
+            source_code,
+        }
+    }
+
+    fn instruction(kind: Instruction, args: &[IndexType]) -> Vec<u8> {
+        let mut bytes = vec![kind.as_u8()];
+        for arg in args {
+            bytes.extend_from_slice(&arg.to_ne_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn cached_instructions_are_validated() {
+        let mut agent = TestAgent::new();
+        let (identifier, source_code) = agent.run(|agent, gc| {
+            let identifier = String::from_static_str(agent, "identifier", gc.nogc())
+                .unbind()
+                .into_value();
+            let source_text = String::from_static_str(agent, "identifier", gc.nogc());
+            let realm = agent.current_realm_id();
+            let script = parse_script(agent, source_text, realm, false, None, gc.nogc()).unwrap();
+            (identifier, script.source_code)
+        });
+        let validate = |instructions: &[u8]| {
+            let data = executable_data(instructions, vec![identifier, 1.into()], source_code);
+            validate_instructions(&data).is_ok()
+        };
+
+        let load = instruction(Instruction::LoadConstant, &[1]);
+        assert!(validate(&load));
+        assert!(!validate(&instruction(Instruction::LoadConstant, &[2])));
+        // Truncated operand.
+        assert!(!validate(&load[..2]));
+        assert!(!validate(&[u8::MAX]));
+
+        assert!(validate(&instruction(Instruction::ResolveBinding, &[0])));
+        // Identifiers must be strings.
+        assert!(!validate(&instruction(Instruction::ResolveBinding, &[1])));
+        assert!(validate(&instruction(
+            Instruction::ResolveRegister,
+            &[0, 0]
+        )));
+        assert!(!validate(&instruction(
+            Instruction::ResolveRegister,
+            &[0, 1]
+        )));
+        assert!(!validate(&instruction(
+            Instruction::InstantiateOrdinaryFunctionExpression,
+            &[0]
+        )));
+        assert!(!validate(&instruction(
+            Instruction::EvaluatePropertyAccessWithIdentifierKey,
+            &[0, 0]
+        )));
+
+        // Jumps land at the start of an instruction or the end of the
+        // bytecode.
+        let mut jumps = instruction(Instruction::Jump, &[3]);
+        jumps.extend(instruction(Instruction::Jump, &[6]));
+        assert!(validate(&jumps));
+        let mut jump_into_operand = instruction(Instruction::Jump, &[4]);
+        jump_into_operand.extend(instruction(Instruction::Jump, &[6]));
+        assert!(!validate(&jump_into_operand));
+        assert!(!validate(&instruction(Instruction::Jump, &[4])));
+    }
+}
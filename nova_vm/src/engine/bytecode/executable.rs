@@ -8,19 +8,26 @@ use std::{
 };
 
 use super::{
-    instructions::Instr, CompileContext, CompileEvaluation, Instruction, NamedEvaluationParameter,
+    code_cache::{load_cached_executable, store_cached_executable, CodeCacheSource, FunctionNodes},
+    instructions::Instr,
+    property_cache::PropertyCache,
+    CompileContext, CompileEvaluation, Instruction, NamedEvaluationParameter,
 };
 use crate::{
     ecmascript::{
         execution::Agent,
-        scripts_and_modules::script::ScriptIdentifier,
+        scripts_and_modules::{
+            script::ScriptIdentifier,
+            source_code::{ParsedCodeDependent, SourceCode},
+        },
         syntax_directed_operations::function_definitions::CompileFunctionBodyData,
         types::{String, Value},
     },
     engine::context::NoGcScope,
-    heap::{CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, WorkQueues},
+    heap::{CompactionLists, CreateHeapData, Heap, HeapMarkAndSweep, OwnedAllocations, WorkQueues},
 };
 use oxc_ast::ast::{self, Program, Statement};
+use oxc_span::Span;
 
 #[derive(Debug)]
 /// A `Send` and `Sync` wrapper over a `&'static T` where `T` might not itself
@@ -75,6 +82,9 @@ pub type IndexType = u16;
 #[derive(Debug, Clone)]
 pub(crate) struct FunctionExpression {
     pub(crate) expression: SendableRef<ast::Function<'static>>,
+    /// Span of the function in its source code, by which the expression is
+    /// found again if the source code is parsed again.
+    pub(crate) span: Span,
     pub(crate) identifier: Option<NamedEvaluationParameter>,
     /// Optionally eagerly compile the FunctionExpression into bytecode.
     pub(crate) compiled_bytecode: Option<Executable>,
@@ -83,6 +93,8 @@ pub(crate) struct FunctionExpression {
 #[derive(Debug, Clone)]
 pub(crate) struct ArrowFunctionExpression {
     pub(crate) expression: SendableRef<ast::ArrowFunctionExpression<'static>>,
+    /// Span of the arrow function in its source code.
+    pub(crate) span: Span,
     pub(crate) identifier: Option<NamedEvaluationParameter>,
 }
 
@@ -105,6 +117,12 @@ pub(crate) struct ExecutableHeapData {
     pub(crate) function_expressions: Box<[FunctionExpression]>,
     pub(crate) arrow_function_expressions: Box<[ArrowFunctionExpression]>,
     pub(crate) class_initializer_bytecodes: Box<[(Option<Executable>, bool)]>,
+    /// Number of registers needed by a frame executing this bytecode.
+    pub(crate) register_count: usize,
+    /// Inline caches of the property access instructions in this bytecode.
+    pub(crate) property_caches: Box<[PropertyCache]>,
+    /// The source code whose parsed code the function expressions refer to.
+    pub(crate) source_code: SourceCode,
 }
 
 impl Executable {
@@ -118,6 +136,17 @@ impl Executable {
             eprintln!("=== Compiling Script ===");
             eprintln!();
         }
+        let cache_source = agent.host_hooks.code_cache().map(|_| {
+            let program = &agent[script].ecmascript_code;
+            let source_text = agent[script].source_code.get_source_text(agent);
+            CodeCacheSource::script(source_text, program.source_type)
+        });
+        if let Some(cache_source) = &cache_source {
+            let nodes = |agent: &Agent| FunctionNodes::of_program(&agent[script].ecmascript_code);
+            if let Some(executable) = load_cached_executable(agent, cache_source, nodes, gc) {
+                return executable;
+            }
+        }
         // SAFETY: Script uniquely owns the Program and the body buffer does
         // not move under any circumstances during heap operations.
         let body: &[Statement] =
@@ -126,7 +155,11 @@ impl Executable {
 
         ctx.compile_statements(body);
         ctx.do_implicit_return();
-        ctx.finish()
+        let executable = ctx.finish();
+        if let Some(cache_source) = &cache_source {
+            store_cached_executable(agent, cache_source, executable);
+        }
+        executable
     }
 
     pub(crate) fn compile_function_body(
@@ -134,6 +167,21 @@ impl Executable {
         data: CompileFunctionBodyData<'_>,
         gc: NoGcScope,
     ) -> Self {
+        let cache_source = data
+            .source_text
+            .filter(|_| agent.host_hooks.code_cache().is_some())
+            .map(|(source_code, span)| {
+                let source_text =
+                    &source_code.get_source_text(agent)[span.start as usize..span.end as usize];
+                CodeCacheSource::function(source_text, span.start, &data)
+            });
+        if let Some(cache_source) = &cache_source {
+            let nodes = |_: &Agent| FunctionNodes::of_function(data.params, data.body);
+            if let Some(executable) = load_cached_executable(agent, cache_source, nodes, gc) {
+                return executable;
+            }
+        }
+
         let mut ctx = CompileContext::new(agent, gc);
 
         let is_concise = data.is_concise_body;
@@ -144,7 +192,11 @@ impl Executable {
             ctx.do_implicit_return();
         }
 
-        ctx.finish()
+        let executable = ctx.finish();
+        if let Some(cache_source) = &cache_source {
+            store_cached_executable(agent, cache_source, executable);
+        }
+        executable
     }
 
     pub(crate) fn compile_eval_body(agent: &mut Agent, program: &Program, gc: NoGcScope) -> Self {
@@ -183,7 +235,9 @@ impl Executable {
             // This bytecode was the last-allocated bytecode, and we can drop
             // it from the Heap without affecting any other indexes. The caller
             // guarantees that the Executable will not be used anymore.
-            let _ = agent.heap.executables.pop().unwrap();
+            let data = agent.heap.executables.pop().unwrap();
+            data.source_code
+                .remove_dependent(&mut agent.heap, ParsedCodeDependent::Executable(self));
         }
     }
 
@@ -207,6 +261,11 @@ impl Executable {
         get_instruction(&agent[self].instructions[..], ip)
     }
 
+    #[inline]
+    pub(super) fn get_register_count(self, agent: &Agent) -> usize {
+        agent[self].register_count
+    }
+
     #[inline]
     pub(super) fn get_constants(self, agent: &Agent) -> &[Value] {
         &agent[self].constants[..]
@@ -264,6 +323,47 @@ impl Executable {
     }
 }
 
+impl ExecutableHeapData {
+    /// Point the function expressions at the nodes of the source code's new
+    /// parsed code after the source code was parsed again. Returns None and
+    /// leaves the bytecode as is if any of the nodes are not found.
+    pub(crate) fn relink_parsed_code(&mut self, nodes: &FunctionNodes) -> Option<()> {
+        let functions = self
+            .function_expressions
+            .iter()
+            .map(|function_expression| nodes.function(function_expression.span))
+            .collect::<Option<Vec<_>>>()?;
+        let arrow_functions = self
+            .arrow_function_expressions
+            .iter()
+            .map(|arrow_function_expression| nodes.arrow_function(arrow_function_expression.span))
+            .collect::<Option<Vec<_>>>()?;
+        for (function_expression, node) in self.function_expressions.iter_mut().zip(functions) {
+            function_expression.expression = SendableRef::new(node);
+        }
+        for (arrow_function_expression, node) in self
+            .arrow_function_expressions
+            .iter_mut()
+            .zip(arrow_functions)
+        {
+            arrow_function_expression.expression = SendableRef::new(node);
+        }
+        Some(())
+    }
+}
+
+/// Get the source code of the running execution context. Bytecode is always
+/// compiled and run in an execution context of the source code it was
+/// compiled from.
+pub(super) fn running_source_code(agent: &Agent) -> SourceCode {
+    agent
+        .running_execution_context()
+        .ecmascript_code
+        .as_ref()
+        .expect("No ECMAScript code is running")
+        .source_code
+}
+
 pub(super) fn get_instruction(instructions: &[u8], ip: &mut usize) -> Option<Instr> {
     let len = instructions.len();
     let cur_ip = *ip;
@@ -329,10 +429,14 @@ impl IndexMut<Executable> for Agent {
 
 impl CreateHeapData<ExecutableHeapData, Executable> for Heap {
     fn create(&mut self, data: ExecutableHeapData) -> Executable {
+        self.owned_bytes += data.owned_bytes();
+        let source_code = data.source_code;
         self.executables.push(data);
         let index = u32::try_from(self.executables.len()).expect("Executables overflowed");
         // SAFETY: After pushing to executables, the vector cannot be empty.
-        Executable(unsafe { NonZeroU32::new_unchecked(index) })
+        let executable = Executable(unsafe { NonZeroU32::new_unchecked(index) });
+        source_code.add_dependent(self, ParsedCodeDependent::Executable(executable));
+        executable
     }
 }
 
@@ -348,6 +452,27 @@ impl HeapMarkAndSweep for Executable {
     }
 }
 
+impl OwnedAllocations for ExecutableHeapData {
+    fn owned_bytes(&self) -> usize {
+        let Self {
+            instructions,
+            constants,
+            function_expressions,
+            arrow_function_expressions,
+            class_initializer_bytecodes,
+            register_count: _,
+            property_caches,
+            source_code: _,
+        } = self;
+        std::mem::size_of_val(&**instructions)
+            + std::mem::size_of_val(&**constants)
+            + std::mem::size_of_val(&**function_expressions)
+            + std::mem::size_of_val(&**arrow_function_expressions)
+            + std::mem::size_of_val(&**class_initializer_bytecodes)
+            + std::mem::size_of_val(&**property_caches)
+    }
+}
+
 impl HeapMarkAndSweep for ExecutableHeapData {
     fn mark_values(&self, queues: &mut WorkQueues) {
         let Self {
@@ -356,8 +481,15 @@ impl HeapMarkAndSweep for ExecutableHeapData {
             function_expressions: _,
             arrow_function_expressions: _,
             class_initializer_bytecodes,
+            register_count: _,
+            property_caches,
+            source_code,
         } = self;
         constants.mark_values(queues);
+        source_code.mark_values(queues);
+        for cache in property_caches.iter() {
+            cache.mark_values(queues);
+        }
         for ele in class_initializer_bytecodes {
             ele.0.mark_values(queues);
         }
@@ -370,8 +502,15 @@ impl HeapMarkAndSweep for ExecutableHeapData {
             function_expressions: _,
             arrow_function_expressions: _,
             class_initializer_bytecodes,
+            register_count: _,
+            property_caches,
+            source_code,
         } = self;
         constants.sweep_values(compactions);
+        source_code.sweep_values(compactions);
+        for cache in property_caches.iter_mut() {
+            cache.sweep_values(compactions);
+        }
         for ele in class_initializer_bytecodes {
             ele.0.sweep_values(compactions);
         }
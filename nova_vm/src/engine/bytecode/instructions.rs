@@ -37,6 +37,9 @@ pub enum Instruction {
     Await,
     /// Performs steps 2-4 from the [UnaryExpression ~ Runtime Semantics](https://tc39.es/ecma262/#sec-bitwise-not-operator-runtime-semantics-evaluation).
     BitwiseNot,
+    /// Set the register of the current frame given as an immediate to
+    /// uninitialized.
+    ClearRegister,
     /// Create a catch binding for the given name and populate it with the
     /// stored exception.
     CreateCatchBinding,
@@ -86,6 +89,9 @@ pub enum Instruction {
     /// Store EvaluatePropertyAccessWithExpressionKey() as the result value.
     EvaluatePropertyAccessWithExpressionKey,
     /// Store EvaluatePropertyAccessWithIdentifierKey() as the result value.
+    ///
+    /// The second argument is the index of the inline cache slot used by the
+    /// reference.
     EvaluatePropertyAccessWithIdentifierKey,
     /// Store [GetValue()](https://tc39.es/ecma262/#sec-getvalue) as the result
     /// value.
@@ -198,6 +204,14 @@ pub enum Instruction {
     PutValue,
     /// Store ResolveBinding() as the result value.
     ResolveBinding,
+    /// Store a reference to a mutable binding held in a register of the
+    /// current frame as the result value.
+    ///
+    /// The first argument is the identifier of the binding, the second is the
+    /// register index.
+    ResolveRegister,
+    /// Same as ResolveRegister, for an immutable binding.
+    ResolveImmutableRegister,
     /// Store ResolveThisBinding() as the result value.
     ResolveThisBinding,
     /// Rethrow the stored exception, if any.
@@ -370,10 +384,13 @@ impl Instruction {
             Self::BeginSimpleArrayBindingPattern
             | Self::BindingPatternBindNamed
             | Self::ClassDefineConstructor
+            | Self::EvaluatePropertyAccessWithIdentifierKey
             | Self::InitializeVariableEnvironment
             | Self::ObjectDefineGetter
             | Self::ObjectDefineMethod
-            | Self::ObjectDefineSetter => 2,
+            | Self::ObjectDefineSetter
+            | Self::ResolveImmutableRegister
+            | Self::ResolveRegister => 2,
             Self::ArrayCreate
             | Self::ArraySetValue
             | Self::BeginSimpleObjectBindingPattern
@@ -381,6 +398,7 @@ impl Instruction {
             | Self::BindingPatternBindRest
             | Self::BindingPatternGetValueNamed
             | Self::ClassDefineDefaultConstructor
+            | Self::ClearRegister
             | Self::CopyDataPropertiesIntoObject
             | Self::CreateCatchBinding
             | Self::CreateImmutableBinding
@@ -389,7 +407,6 @@ impl Instruction {
             | Self::EvaluateCall
             | Self::EvaluateNew
             | Self::EvaluateSuper
-            | Self::EvaluatePropertyAccessWithIdentifierKey
             | Self::InstantiateArrowFunctionExpression
             | Self::InstantiateOrdinaryFunctionExpression
             | Self::IteratorStepValue
@@ -427,6 +444,8 @@ impl Instruction {
                 | Self::BindingPatternBind
                 | Self::BindingPatternBindNamed
                 | Self::BindingPatternBindRest
+                | Self::ResolveRegister
+                | Self::ResolveImmutableRegister
         )
     }
 
@@ -455,6 +474,17 @@ impl Instruction {
     pub fn as_u8(self) -> u8 {
         unsafe { std::mem::transmute::<Self, u8>(self) }
     }
+
+    /// Get the instruction that a byte encodes, if any.
+    pub(crate) fn from_u8(byte: u8) -> Option<Self> {
+        if byte <= Self::AsyncIteratorClose.as_u8() {
+            // SAFETY: The instructions are numbered from zero up to the last
+            // one, AsyncIteratorClose.
+            Some(unsafe { std::mem::transmute::<u8, Self>(byte) })
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -574,12 +604,26 @@ impl Instr {
                     "constructor()".to_string()
                 }
             }
+            Instruction::EvaluatePropertyAccessWithIdentifierKey => {
+                format!(
+                    "{} in ic{}",
+                    debug_print_identifier(agent, exe, index0, gc),
+                    arg1
+                )
+            }
             Instruction::InitializeVariableEnvironment => {
                 format!("{{ var count: {}, strict: {} }}", arg0, arg1 == 1)
             }
             Instruction::ObjectDefineGetter => "get function() {}".to_string(),
             Instruction::ObjectDefineMethod => "function() {}".to_string(),
             Instruction::ObjectDefineSetter => "set function() {}".to_string(),
+            Instruction::ResolveImmutableRegister | Instruction::ResolveRegister => {
+                format!(
+                    "{} in r{}",
+                    debug_print_identifier(agent, exe, index0, gc),
+                    arg1
+                )
+            }
             _ => unreachable!(),
         }
     }
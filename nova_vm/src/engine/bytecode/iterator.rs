@@ -25,7 +25,13 @@ pub(super) enum VmIterator {
     ObjectProperties(ObjectPropertiesIterator),
     ArrayValues(ArrayValuesIterator),
     GenericIterator(IteratorRecord),
+    /// Iterator over a list of values borrowed from the caller, such as the
+    /// arguments of a function call. Garbage collection cannot update the
+    /// values, so this is only used where it cannot happen.
     SliceIterator(SendableRef<[Value]>),
+    /// Iterator over a list of values owned by the VM, such as the copied
+    /// arguments of a function call that may perform garbage collection.
+    ListIterator(std::vec::IntoIter<Value>),
 }
 
 impl VmIterator {
@@ -123,6 +129,7 @@ impl VmIterator {
                     Ok(Some(ret))
                 }
             }
+            VmIterator::ListIterator(values) => Ok(values.next()),
         }
     }
 
@@ -134,6 +141,7 @@ impl VmIterator {
             }
             VmIterator::GenericIterator(_) => None,
             VmIterator::SliceIterator(slice) => Some(slice.get().len()),
+            VmIterator::ListIterator(values) => Some(values.len()),
         }
     }
 
@@ -279,7 +287,7 @@ impl ArrayValuesIterator {
         }
         // viii. Set index to index + 1.
         self.index += 1;
-        if let Some(element_value) = array.as_slice(agent)[index as usize] {
+        if let Some(element_value) = array.get_element_value(agent, index) {
             // Fast path: If the element at this index has a Value, then it is
             // not an accessor nor a hole. Yield the result as-is.
             return Ok(Some(element_value));
@@ -341,6 +349,7 @@ impl HeapMarkAndSweep for VmIterator {
             VmIterator::ArrayValues(iter) => iter.mark_values(queues),
             VmIterator::GenericIterator(iter) => iter.mark_values(queues),
             VmIterator::SliceIterator(slice) => slice.get().mark_values(queues),
+            VmIterator::ListIterator(values) => values.as_slice().mark_values(queues),
         }
     }
 
@@ -350,6 +359,10 @@ impl HeapMarkAndSweep for VmIterator {
             VmIterator::ArrayValues(iter) => iter.sweep_values(compactions),
             VmIterator::GenericIterator(iter) => iter.sweep_values(compactions),
             VmIterator::SliceIterator(slice) => slice.get().sweep_values(compactions),
+            VmIterator::ListIterator(values) => values
+                .as_mut_slice()
+                .iter_mut()
+                .for_each(|value| value.sweep_values(compactions)),
         }
     }
 }
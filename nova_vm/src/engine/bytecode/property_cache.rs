@@ -0,0 +1,474 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! ## Inline caches for property access
+//!
+//! Each `EvaluatePropertyAccessWithIdentifierKey` instruction owns a cache
+//! slot in its Executable. The property reference created by the instruction
+//! carries the slot index, and the `GetValue`, `GetValueKeepReference` and
+//! `PutValue` instructions consuming the reference consult the slot before
+//! falling back to the generic \[\[Get]] and \[\[Set]] paths.
+//!
+//! Cache entries are keyed on the shape of an ordinary object receiver and
+//! resolve directly to the index of the property's value in the object that
+//! holds it, which is either the receiver itself or an ordinary object on its
+//! prototype chain. Only data properties are cached, and only own writable
+//! properties are cached for `PutValue`. A slot starts out uninitialized,
+//! becomes monomorphic on its first miss and polymorphic when it sees a
+//! second shape. Past [`MAX_POLYMORPHIC_ENTRIES`] shapes the slot goes
+//! megamorphic and is not consulted anymore.
+//!
+//! An entry stays valid for as long as the receiver and the objects on the
+//! path to the holder keep their shapes and the shapes keep their versions.
+//! Shared shapes never change. Unique shapes are mutated in place instead;
+//! every such mutation, including any prototype change, bumps the version of
+//! that shape only, which invalidates the entries that walk through it.
+
+use super::Executable;
+use crate::{
+    ecmascript::{
+        execution::Agent,
+        types::{Base, Object, ObjectShape, OrdinaryObject, PropertyKey, Reference, Value},
+    },
+    heap::{CompactionLists, HeapMarkAndSweep, WorkQueues},
+};
+
+/// Number of shapes a polymorphic cache slot can hold.
+const MAX_POLYMORPHIC_ENTRIES: usize = 4;
+
+/// Maximum number of prototypes walked when filling a cache entry.
+const MAX_PROTOTYPE_CHAIN_LENGTH: usize = 8;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PropertyCacheEntry {
+    /// Shape of the receiver.
+    shape: ObjectShape,
+    /// Version of the receiver's shape at the time of caching.
+    version: u32,
+    /// Shapes and versions of the objects on the receiver's prototype chain,
+    /// up to and including the holder of the property. Empty if the receiver
+    /// holds the property itself.
+    prototype_shapes: Box<[(ObjectShape, u32)]>,
+    /// Index of the property in the holder's values.
+    index: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) enum PropertyCache {
+    #[default]
+    Uninitialized,
+    Monomorphic(PropertyCacheEntry),
+    Polymorphic(Vec<PropertyCacheEntry>),
+    Megamorphic,
+}
+
+impl PropertyCacheEntry {
+    /// Find the holder of the cached property if the entry applies to the
+    /// object.
+    fn holder(&self, agent: &Agent, object: OrdinaryObject) -> Option<OrdinaryObject<'static>> {
+        if agent[object].shape != self.shape || self.shape.version(agent) != self.version {
+            return None;
+        }
+        let mut holder = object.unbind();
+        let mut shape = self.shape;
+        for &(expected_shape, expected_version) in self.prototype_shapes.iter() {
+            // The shape and its version determine the prototype, and the
+            // prototype was an ordinary object when the entry was created.
+            let Some(Object::Object(prototype)) = shape.prototype(agent) else {
+                unreachable!()
+            };
+            if agent[prototype].shape != expected_shape
+                || expected_shape.version(agent) != expected_version
+            {
+                return None;
+            }
+            holder = prototype;
+            shape = expected_shape;
+        }
+        Some(holder)
+    }
+
+    /// Create an entry for a property lookup on the object.
+    fn new(
+        agent: &Agent,
+        object: OrdinaryObject,
+        key: PropertyKey,
+        own_only: bool,
+    ) -> Option<Self> {
+        let receiver_shape = agent[object].shape;
+        let mut shape = receiver_shape;
+        let mut prototype_shapes = Vec::new();
+        let index = loop {
+            if let Some(index) = shape.get_property_index(agent, key) {
+                break index;
+            }
+            if own_only || prototype_shapes.len() == MAX_PROTOTYPE_CHAIN_LENGTH {
+                return None;
+            }
+            let Some(Object::Object(prototype)) = shape.prototype(agent) else {
+                return None;
+            };
+            shape = agent[prototype].shape;
+            prototype_shapes.push((shape, shape.version(agent)));
+        };
+        Some(Self {
+            shape: receiver_shape,
+            version: receiver_shape.version(agent),
+            prototype_shapes: prototype_shapes.into_boxed_slice(),
+            index: u32::try_from(index).ok()?,
+        })
+    }
+}
+
+impl PropertyCache {
+    fn entries(&self) -> &[PropertyCacheEntry] {
+        match self {
+            PropertyCache::Monomorphic(entry) => std::slice::from_ref(entry),
+            PropertyCache::Polymorphic(entries) => entries,
+            PropertyCache::Uninitialized | PropertyCache::Megamorphic => &[],
+        }
+    }
+
+    fn find(
+        &self,
+        agent: &Agent,
+        object: OrdinaryObject,
+    ) -> Option<(OrdinaryObject<'static>, u32)> {
+        self.entries().iter().find_map(|entry| {
+            entry
+                .holder(agent, object)
+                .map(|holder| (holder, entry.index))
+        })
+    }
+
+    fn insert(&mut self, entry: PropertyCacheEntry) {
+        *self = match std::mem::take(self) {
+            PropertyCache::Uninitialized => PropertyCache::Monomorphic(entry),
+            PropertyCache::Monomorphic(first) => {
+                if first.shape == entry.shape {
+                    // The previous entry for the shape was invalidated.
+                    PropertyCache::Monomorphic(entry)
+                } else {
+                    PropertyCache::Polymorphic(vec![first, entry])
+                }
+            }
+            PropertyCache::Polymorphic(mut entries) => {
+                entries.retain(|e| e.shape != entry.shape);
+                if entries.len() == MAX_POLYMORPHIC_ENTRIES {
+                    PropertyCache::Megamorphic
+                } else {
+                    entries.push(entry);
+                    PropertyCache::Polymorphic(entries)
+                }
+            }
+            PropertyCache::Megamorphic => PropertyCache::Megamorphic,
+        };
+    }
+}
+
+/// Returns the receiver and cache slot of a property reference that may use
+/// the inline cache.
+fn cacheable_reference(reference: &Reference) -> Option<(OrdinaryObject<'static>, usize)> {
+    let slot = reference.property_cache?;
+    match reference.base {
+        Base::Value(Value::Object(object)) if reference.this_value.is_none() => {
+            Some((object, slot as usize))
+        }
+        _ => None,
+    }
+}
+
+/// Try to perform GetValue on a property reference through its inline cache.
+///
+/// On a cache miss the cache is updated for the next execution and `None` is
+/// returned; the caller must then perform the generic GetValue.
+pub(super) fn get_value_cached(
+    agent: &mut Agent,
+    executable: Executable,
+    reference: &Reference,
+) -> Option<Value> {
+    let (object, slot) = cacheable_reference(reference)?;
+    let cache = &agent[executable].property_caches[slot];
+    if let PropertyCache::Megamorphic = cache {
+        return None;
+    }
+    if let Some((holder, index)) = cache.find(agent, object) {
+        let values = agent[holder].values;
+        // Accessor properties have no value and are never cached; the
+        // property may have been redefined as one after caching.
+        if let Some(value) = agent.heap.elements.get(values)[index as usize] {
+            return Some(value);
+        }
+        return None;
+    }
+    if let Some(entry) = PropertyCacheEntry::new(agent, object, reference.referenced_name, false) {
+        agent[executable].property_caches[slot].insert(entry);
+    }
+    None
+}
+
+/// Try to perform PutValue on a property reference through its inline cache.
+///
+/// Returns false on a cache miss, after updating the cache for the next
+/// execution; the caller must then perform the generic PutValue.
+pub(super) fn put_value_cached(
+    agent: &mut Agent,
+    executable: Executable,
+    reference: &Reference,
+    value: Value,
+) -> bool {
+    let Some((object, slot)) = cacheable_reference(reference) else {
+        return false;
+    };
+    let cache = &agent[executable].property_caches[slot];
+    if let PropertyCache::Megamorphic = cache {
+        return false;
+    }
+    if let Some((holder, index)) = cache.find(agent, object) {
+        // Only own properties are useful for PutValue: Writing to a
+        // prototype's property defines a new property on the receiver.
+        if holder != object {
+            return false;
+        }
+        let values = agent[holder].values;
+        let index = index as usize;
+        let writable_data = agent.heap.elements.get(values)[index].is_some()
+            && agent
+                .heap
+                .elements
+                .get_descriptor(values, index)
+                .map_or(true, |descriptor| descriptor.is_writable() == Some(true));
+        if writable_data {
+            agent.heap.elements.get_mut(values)[index] = Some(value);
+        }
+        return writable_data;
+    }
+    if let Some(entry) = PropertyCacheEntry::new(agent, object, reference.referenced_name, true) {
+        agent[executable].property_caches[slot].insert(entry);
+    }
+    false
+}
+
+impl HeapMarkAndSweep for PropertyCacheEntry {
+    fn mark_values(&self, queues: &mut WorkQueues) {
+        let Self {
+            shape,
+            version: _,
+            prototype_shapes,
+            index: _,
+        } = self;
+        shape.mark_values(queues);
+        for (shape, _) in prototype_shapes.iter() {
+            shape.mark_values(queues);
+        }
+    }
+
+    fn sweep_values(&mut self, compactions: &CompactionLists) {
+        let Self {
+            shape,
+            version: _,
+            prototype_shapes,
+            index: _,
+        } = self;
+        shape.sweep_values(compactions);
+        for (shape, _) in prototype_shapes.iter_mut() {
+            shape.sweep_values(compactions);
+        }
+    }
+}
+
+impl HeapMarkAndSweep for PropertyCache {
+    fn mark_values(&self, queues: &mut WorkQueues) {
+        for entry in self.entries() {
+            entry.mark_values(queues);
+        }
+    }
+
+    fn sweep_values(&mut self, compactions: &CompactionLists) {
+        match self {
+            PropertyCache::Monomorphic(entry) => entry.sweep_values(compactions),
+            PropertyCache::Polymorphic(entries) => {
+                for entry in entries {
+                    entry.sweep_values(compactions);
+                }
+            }
+            PropertyCache::Uninitialized | PropertyCache::Megamorphic => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PropertyCache, PropertyCacheEntry, MAX_POLYMORPHIC_ENTRIES};
+    use crate::{
+        ecmascript::{
+            execution::Agent,
+            types::{OrdinaryObject, PropertyKey, Value},
+        },
+        engine::context::GcScope,
+        test_utils::{eval_script, TestAgent},
+    };
+
+    fn object(agent: &mut Agent, source_text: &str, gc: GcScope) -> OrdinaryObject<'static> {
+        match eval_script(agent, source_text, gc) {
+            Ok(Value::Object(object)) => object,
+            _ => panic!("Expected an ordinary object:\n{source_text}"),
+        }
+    }
+
+    fn cache_for(
+        agent: &mut Agent,
+        object: OrdinaryObject,
+        key: PropertyKey,
+        own_only: bool,
+    ) -> PropertyCache {
+        let mut cache = PropertyCache::default();
+        cache.insert(PropertyCacheEntry::new(agent, object, key, own_only).unwrap());
+        cache
+    }
+
+    #[test]
+    fn cache_hit() {
+        let mut agent = TestAgent::new();
+        agent.run(|agent, mut gc| {
+            let a = object(agent, "({ x: 1, y: 2 })", gc.reborrow());
+            let b = object(agent, "({ x: 3, y: 4 })", gc.reborrow());
+            let y = PropertyKey::from_static_str(agent, "y", gc.nogc()).unbind();
+            let cache = cache_for(agent, a, y, false);
+            assert!(matches!(cache, PropertyCache::Monomorphic(_)));
+            assert_eq!(cache.find(agent, a), Some((a, 1)));
+            // Objects created the same way share their shape.
+            assert_eq!(cache.find(agent, b), Some((b, 1)));
+
+            // Properties found on the prototype chain resolve to the holder.
+            let p = object(agent, "var p = { y: 1 }; p", gc.reborrow());
+            let o = object(agent, "var o = Object.create(p); o", gc.reborrow());
+            let cache = cache_for(agent, o, y, false);
+            assert_eq!(cache.find(agent, o), Some((p, 0)));
+            assert!(PropertyCacheEntry::new(agent, o, y, true).is_none());
+        });
+    }
+
+    #[test]
+    fn cache_miss() {
+        let mut agent = TestAgent::new();
+        agent.run(|agent, mut gc| {
+            let x = PropertyKey::from_static_str(agent, "x", gc.nogc()).unbind();
+            let a = object(agent, "({ x: 1 })", gc.reborrow());
+            let mut cache = cache_for(agent, a, x, false);
+            let mut objects = vec![a];
+            for i in 0..MAX_POLYMORPHIC_ENTRIES {
+                let source_text = format!("({{ a{i}: 0, x: 1 }})");
+                let object = object(agent, &source_text, gc.reborrow());
+                // A new shape misses until it is inserted.
+                assert_eq!(cache.find(agent, object), None);
+                cache.insert(PropertyCacheEntry::new(agent, object, x, false).unwrap());
+                if objects.len() < MAX_POLYMORPHIC_ENTRIES {
+                    assert!(matches!(cache, PropertyCache::Polymorphic(_)));
+                    assert_eq!(cache.find(agent, object), Some((object, 1)));
+                }
+                objects.push(object);
+            }
+            assert!(matches!(cache, PropertyCache::Megamorphic));
+            assert!(objects.iter().all(|&o| cache.find(agent, o).is_none()));
+        });
+    }
+
+    #[test]
+    fn shape_transition_invalidation() {
+        let mut agent = TestAgent::new();
+        agent.run(|agent, mut gc| {
+            let x = PropertyKey::from_static_str(agent, "x", gc.nogc()).unbind();
+            let p = object(agent, "var p = { x: 1 }; p", gc.reborrow());
+            let o = object(agent, "var o = Object.create(p); o", gc.reborrow());
+            let cache = cache_for(agent, o, x, false);
+            assert_eq!(cache.find(agent, o), Some((p, 0)));
+
+            // Shadowing the property moves the receiver to a new shape.
+            eval_script(agent, "o.x = 2", gc.reborrow()).unwrap();
+            assert_eq!(cache.find(agent, o), None);
+            let cache = cache_for(agent, o, x, false);
+            assert_eq!(cache.find(agent, o), Some((o, 0)));
+
+            // Changing the prototype of an object on the chain invalidates
+            // entries that walk through it.
+            let q = object(agent, "var q = Object.create(p); q", gc.reborrow());
+            let cache = cache_for(agent, q, x, false);
+            eval_script(agent, "Object.setPrototypeOf(q, { x: 3 })", gc.reborrow()).unwrap();
+            assert_eq!(cache.find(agent, q), None);
+
+            // Unique shapes are mutated in place, which bumps the shape's
+            // version instead of changing the shape.
+            let u = object(
+                agent,
+                "var u = { a: 1, x: 2 }; delete u.a; u",
+                gc.reborrow(),
+            );
+            let cache = cache_for(agent, u, x, false);
+            assert_eq!(cache.find(agent, u), Some((u, 0)));
+            let shape = agent[u].shape;
+            eval_script(agent, "u.b = 3", gc.reborrow()).unwrap();
+            assert_eq!(agent[u].shape, shape);
+            assert_eq!(cache.find(agent, u), None);
+        });
+    }
+
+    #[test]
+    fn unrelated_unique_shape_mutation_keeps_entries() {
+        let mut agent = TestAgent::new();
+        agent.run(|agent, mut gc| {
+            let has_own_property =
+                PropertyKey::from_static_str(agent, "hasOwnProperty", gc.nogc()).unbind();
+            let x = PropertyKey::from_static_str(agent, "x", gc.nogc()).unbind();
+            // Builtin prototypes have unique shapes.
+            let o = object(agent, "({ a: 1 })", gc.reborrow());
+            let cache = cache_for(agent, o, has_own_property, false);
+            let u = object(
+                agent,
+                "var u = { a: 1, x: 2 }; delete u.a; u",
+                gc.reborrow(),
+            );
+            let unique_cache = cache_for(agent, u, x, false);
+            let holders = (cache.find(agent, o), unique_cache.find(agent, u));
+            assert!(holders.0.is_some() && holders.1 == Some((u, 0)));
+
+            eval_script(
+                agent,
+                "Array.prototype.extra = 1; var v = { a: 1 }; delete v.a; v.b = 2;",
+                gc.reborrow(),
+            )
+            .unwrap();
+            assert_eq!((cache.find(agent, o), unique_cache.find(agent, u)), holders);
+
+            // Mutating a prototype on the path still invalidates.
+            eval_script(agent, "Object.prototype.extra = 1", gc.reborrow()).unwrap();
+            assert_eq!(cache.find(agent, o), None);
+            assert_eq!(unique_cache.find(agent, u), holders.1);
+        });
+    }
+
+    #[test]
+    fn cached_property_access() {
+        let mut agent = TestAgent::new();
+        agent.eval("function get(o) { return o.x; } function set(o, v) { o.x = v; }");
+        assert_eq!(
+            agent.eval(
+                "var p = { x: 1 }; var o = Object.create(p); var r = [get(o), get(o)]; \
+                 p.x = 2; r.push(get(o)); o.x = 3; r.push(get(o), p.x); \
+                 Object.setPrototypeOf(o, { y: 0 }); delete o.x; r.push(get(o)); r.join()"
+            ),
+            "1,1,2,3,2,"
+        );
+        assert_eq!(
+            agent.eval("var a = { x: 1 }; set(a, 2); set(a, 3); Object.freeze(a); set(a, 4); a.x"),
+            "3"
+        );
+        assert_eq!(
+            agent.eval(
+                "var b = { x: 1 }; get(b); Object.defineProperty(b, 'x', { get() { return 5; } }); get(b)"
+            ),
+            "5"
+        );
+    }
+}
@@ -12,8 +12,6 @@ use oxc_ast::ast;
 use oxc_span::Span;
 use oxc_syntax::operator::BinaryOperator;
 
-#[cfg(feature = "interleaved-gc")]
-use crate::{ecmascript::execution::RealmIdentifier, heap::heap_gc::heap_gc};
 use crate::{
     ecmascript::{
         abstract_operations::{
@@ -45,7 +43,7 @@ use crate::{
             agent::{resolve_binding, ExceptionType, JsError},
             get_this_environment, new_class_static_element_environment,
             new_declarative_environment, Agent, ECMAScriptCodeEvaluationState, EnvironmentIndex,
-            JsResult, ProtoIntrinsics,
+            JsResult, ProtoIntrinsics, RealmIdentifier,
         },
         types::{
             get_this_value, get_value, initialize_referenced_binding, is_private_reference,
@@ -56,16 +54,19 @@ use crate::{
     },
     engine::{
         bytecode::{
-            executable::{ArrowFunctionExpression, SendableRef},
+            executable::{running_source_code, ArrowFunctionExpression, SendableRef},
             instructions::Instr,
             iterator::{ObjectPropertiesIterator, VmIterator},
+            property_cache::{get_value_cached, put_value_cached},
             Executable, FunctionExpression, IndexType, Instruction, InstructionIter,
             NamedEvaluationParameter,
         },
-        context::GcScope,
+        context::{GcScope, NoGcScope},
         unwrap_try, TryResult,
     },
-    heap::{CompactionLists, HeapMarkAndSweep, WellKnownSymbolIndexes, WorkQueues},
+    heap::{
+        heap_gc::heap_gc, CompactionLists, HeapMarkAndSweep, WellKnownSymbolIndexes, WorkQueues,
+    },
 };
 
 use super::executable::get_instruction;
@@ -130,6 +131,13 @@ pub(crate) struct Vm {
     exception_jump_target_stack: Vec<ExceptionJumpTarget>,
     result: Option<Value>,
     reference: Option<Reference<'static>>,
+    /// Registers holding the frame's register allocated bindings. An empty
+    /// register is an uninitialized binding.
+    registers: Vec<Option<Value>>,
+    /// The executable being run. This is only set while the VM performs
+    /// garbage collection, keeping the executable alive and updating its
+    /// index.
+    executable: Option<Executable>,
 }
 
 #[derive(Debug)]
@@ -151,6 +159,9 @@ pub(crate) struct SuspendedVm {
     /// Note: Exception jump stack is non-empty only if the code awaits inside
     /// a try block. This means that often no heap data clone is required.
     exception_jump_target_stack: Box<[ExceptionJumpTarget]>,
+    /// Note: Registers are non-empty only if the function has bindings
+    /// placed in registers.
+    registers: Box<[Option<Value>]>,
 }
 
 impl SuspendedVm {
@@ -184,7 +195,7 @@ impl SuspendedVm {
 }
 
 impl<'a> Vm {
-    fn new() -> Self {
+    fn new(register_count: usize) -> Self {
         Self {
             ip: 0,
             stack: Vec::with_capacity(32),
@@ -193,6 +204,8 @@ impl<'a> Vm {
             exception_jump_target_stack: Vec::new(),
             result: None,
             reference: None,
+            registers: vec![None; register_count],
+            executable: None,
         }
     }
 
@@ -207,6 +220,7 @@ impl<'a> Vm {
             },
             iterator_stack: self.iterator_stack.into_boxed_slice(),
             exception_jump_target_stack: self.exception_jump_target_stack.into_boxed_slice(),
+            registers: self.registers.into_boxed_slice(),
         }
     }
 
@@ -219,6 +233,8 @@ impl<'a> Vm {
             exception_jump_target_stack: suspended.exception_jump_target_stack.into_vec(),
             result: None,
             reference: None,
+            registers: suspended.registers.into_vec(),
+            executable: None,
         }
     }
 
@@ -229,17 +245,25 @@ impl<'a> Vm {
         arguments: Option<&[Value]>,
         gc: GcScope,
     ) -> ExecutionResult {
-        let mut vm = Vm::new();
+        let mut vm = Vm::new(executable.get_register_count(agent));
 
         if let Some(arguments) = arguments {
-            // SAFETY: awaits and yields are invalid syntax inside an arguments
-            // list, so this reference shouldn't remain alive after this
-            // function returns.
-            let arguments = unsafe { SendableRef::new_as_static(arguments) };
-            vm.iterator_stack.push(VmIterator::SliceIterator(arguments));
+            if may_collect_garbage(agent) {
+                // The caller's arguments are not rooted: Copy them so that
+                // garbage collection can update them.
+                vm.iterator_stack
+                    .push(VmIterator::ListIterator(Vec::from(arguments).into_iter()));
+            } else {
+                // SAFETY: awaits and yields are invalid syntax inside an
+                // arguments list, so this reference shouldn't remain alive
+                // after this function returns.
+                let arguments = unsafe { SendableRef::new_as_static(arguments) };
+                vm.iterator_stack.push(VmIterator::SliceIterator(arguments));
+            }
         }
 
         if agent.options.print_internals {
+            running_source_code(agent).ensure_parsed(agent);
             eprintln!();
             eprintln!("=== Executing Executable ===");
             eprintln!("Constants: {:?}", executable.get_constants(agent));
@@ -284,41 +308,39 @@ impl<'a> Vm {
     fn inner_execute(
         mut self,
         agent: &mut Agent,
-        executable: Executable,
+        mut executable: Executable,
         mut gc: GcScope<'a, '_>,
     ) -> ExecutionResult {
-        #[cfg(feature = "interleaved-gc")]
-        let do_gc = !agent.options.disable_gc;
-        #[cfg(feature = "interleaved-gc")]
+        if let Err(err) = agent.check_interrupts() {
+            return ExecutionResult::Throw(err);
+        }
+        // The function expressions of the executable refer to the parsed code
+        // of the running source code, which may have been discarded while the
+        // code was not being run.
+        running_source_code(agent).ensure_parsed(agent);
         let mut instr_count = 0u8;
 
         let instructions = executable.get_instructions(agent);
+        let mut instr_ip = self.ip;
         while let Some(instr) = get_instruction(instructions, &mut self.ip) {
-            #[cfg(feature = "interleaved-gc")]
-            if do_gc {
-                instr_count = instr_count.wrapping_add(1);
-                if instr_count == 0 {
-                    let mut root_realms = agent
-                        .heap
-                        .realms
-                        .iter()
-                        .enumerate()
-                        .map(|(i, _)| Some(RealmIdentifier::from_index(i)))
-                        .collect::<Vec<_>>();
-                    let vm = unsafe { NonNull::new_unchecked(&mut self) };
-                    agent
-                        .vm_stack
-                        // SAFETY: Pointer to self is never null.
-                        .push(vm);
-                    heap_gc(agent, &mut root_realms, gc.reborrow());
-                    let return_vm = agent.vm_stack.pop().unwrap();
-                    assert_eq!(vm, return_vm, "VM Stack was misused");
+            agent.executed_instructions += 1;
+            instr_count = instr_count.wrapping_add(1);
+            if instr_count == 0 || agent.options.gc_stress {
+                if let Err(err) = self.safepoint(agent, &mut executable, gc.reborrow()) {
+                    return ExecutionResult::Throw(err);
                 }
             }
             let temp = &mut self;
             let temp_self = unsafe { std::mem::transmute::<&mut Vm, &mut Vm>(temp) };
             match Self::execute_instruction(agent, temp_self, executable, &instr, gc.reborrow()) {
-                Ok(ContinuationKind::Normal) => {}
+                Ok(ContinuationKind::Normal) => {
+                    if self.ip <= instr_ip {
+                        // Loop back-edge.
+                        if let Err(err) = agent.check_interrupts() {
+                            return ExecutionResult::Throw(err);
+                        }
+                    }
+                }
                 Ok(ContinuationKind::Return) => {
                     let result = self.result.unwrap_or(Value::Undefined);
                     return ExecutionResult::Return(result);
@@ -343,13 +365,73 @@ impl<'a> Vm {
                     }
                 }
             }
+            instr_ip = self.ip;
         }
 
         ExecutionResult::Return(Value::Undefined)
     }
 
+    /// Periodic check between instructions: Collects garbage when the heap
+    /// has grown enough and enforces the heap size limit. An error returned
+    /// from here terminates execution.
+    ///
+    /// With [`Options::gc_stress`](crate::ecmascript::execution::agent::Options::gc_stress)
+    /// this is called before every instruction and collects whenever the VM
+    /// is at a garbage collection safepoint.
+    fn safepoint(
+        &mut self,
+        agent: &mut Agent,
+        executable: &mut Executable,
+        mut gc: GcScope,
+    ) -> JsResult<()> {
+        if agent.is_terminating() {
+            // Code that handles errors natively, eg. async functions, may
+            // have swallowed the termination error.
+            return Err(agent.terminate());
+        }
+        let do_gc = !agent.options.disable_gc;
+        if do_gc
+            && (cfg!(feature = "interleaved-gc")
+                || ((agent.options.gc_stress || agent.heap.needs_gc()) && is_gc_safepoint(agent)))
+        {
+            self.collect_garbage(agent, executable, gc.reborrow());
+        }
+        let over_limit = agent
+            .options
+            .max_heap_size
+            .is_some_and(|heap_limit| agent.heap.allocated_bytes() >= heap_limit);
+        if over_limit && do_gc && is_gc_safepoint(agent) {
+            // Try to get back under the limit before asking the host.
+            self.collect_garbage(agent, executable, gc);
+        }
+        agent.check_heap_limit(0)
+    }
+
+    /// Perform garbage collection with this VM rooted on the VM stack. The
+    /// running executable is kept alive and its index is updated.
+    fn collect_garbage(&mut self, agent: &mut Agent, executable: &mut Executable, gc: GcScope) {
+        let mut root_realms = agent
+            .heap
+            .realms
+            .iter()
+            .enumerate()
+            .map(|(i, _)| Some(RealmIdentifier::from_index(i)))
+            .collect::<Vec<_>>();
+        self.executable = Some(*executable);
+        let vm = NonNull::from(&mut *self);
+        agent.vm_stack.push(vm);
+        heap_gc(agent, &mut root_realms, gc);
+        let return_vm = agent.vm_stack.pop().unwrap();
+        assert_eq!(vm, return_vm, "VM Stack was misused");
+        *executable = self.executable.take().unwrap();
+    }
+
     #[must_use]
     fn handle_error(&mut self, agent: &mut Agent, err: JsError) -> bool {
+        if err.is_termination() || agent.is_terminating() {
+            // Termination cannot be caught.
+            return false;
+        }
         if let Some(ejt) = self.exception_jump_target_stack.pop() {
             self.ip = ejt.ip;
             agent
@@ -439,6 +521,24 @@ impl<'a> Vm {
 
                 vm.reference = Some(reference.unbind());
             }
+            Instruction::ResolveRegister | Instruction::ResolveImmutableRegister => {
+                let identifier =
+                    executable.fetch_identifier(agent, instr.args[0].unwrap() as usize, gc.nogc());
+
+                vm.reference = Some(Reference {
+                    base: Base::Register {
+                        index: instr.args[1].unwrap(),
+                        mutable: instr.kind == Instruction::ResolveRegister,
+                    },
+                    referenced_name: identifier.unbind().into(),
+                    strict: true,
+                    this_value: None,
+                    property_cache: None,
+                });
+            }
+            Instruction::ClearRegister => {
+                vm.registers[instr.args[0].unwrap() as usize] = None;
+            }
             Instruction::ResolveThisBinding => {
                 // 1. Let envRec be GetThisEnvironment().
                 let env_rec = get_this_environment(agent);
@@ -811,19 +911,35 @@ impl<'a> Vm {
             Instruction::PutValue => {
                 let value = vm.result.take().unwrap();
                 let reference = vm.reference.take().unwrap();
-                put_value(agent, &reference, value, gc.reborrow())?;
+                if let Base::Register { .. } = reference.base {
+                    vm.put_register_value(agent, &reference, value, gc.nogc())?;
+                } else if !put_value_cached(agent, executable, &reference, value) {
+                    put_value(agent, &reference, value, gc.reborrow())?;
+                }
             }
             Instruction::GetValue => {
                 // 1. If V is not a Reference Record, return V.
                 let reference = vm.reference.take().unwrap();
 
-                vm.result = Some(get_value(agent, &reference, gc.reborrow())?);
+                vm.result = Some(if let Base::Register { .. } = reference.base {
+                    vm.get_register_value(agent, &reference, gc.nogc())?
+                } else if let Some(value) = get_value_cached(agent, executable, &reference) {
+                    value
+                } else {
+                    get_value(agent, &reference, gc.reborrow())?
+                });
             }
             Instruction::GetValueKeepReference => {
                 // 1. If V is not a Reference Record, return V.
                 let reference = vm.reference.as_ref().unwrap();
 
-                vm.result = Some(get_value(agent, reference, gc.reborrow())?);
+                vm.result = Some(if let Base::Register { .. } = reference.base {
+                    vm.get_register_value(agent, reference, gc.nogc())?
+                } else if let Some(value) = get_value_cached(agent, executable, reference) {
+                    value
+                } else {
+                    get_value(agent, reference, gc.reborrow())?
+                });
             }
             Instruction::Typeof => {
                 // 2. If val is a Reference Record, then
@@ -831,6 +947,8 @@ impl<'a> Vm {
                     if reference.base == Base::Unresolvable {
                         // a. If IsUnresolvableReference(val) is true, return "undefined".
                         Value::Undefined
+                    } else if let Base::Register { .. } = reference.base {
+                        vm.get_register_value(agent, &reference, gc.nogc())?
                     } else {
                         // 3. Set val to ? GetValue(val).
                         get_value(agent, &reference, gc.reborrow())?
@@ -890,6 +1008,7 @@ impl<'a> Vm {
                 let ArrowFunctionExpression {
                     expression,
                     identifier,
+                    ..
                 } = executable
                     .fetch_arrow_function_expression(agent, instr.args[0].unwrap() as usize);
                 let function_expression = expression.get();
@@ -964,6 +1083,7 @@ impl<'a> Vm {
                     expression,
                     identifier,
                     compiled_bytecode,
+                    ..
                 } = executable.fetch_function_expression(agent, instr.args[0].unwrap() as usize);
                 let function_expression = expression.get();
                 let identifier = *identifier;
@@ -1310,6 +1430,8 @@ impl<'a> Vm {
                                 .with_base_object(agent)
                                 .map_or(Value::Undefined, |object| object.into_value())
                         }
+                        // A register binding never has a base object.
+                        Base::Register { .. } => Value::Undefined,
                         // ii. Assert: refEnv is an Environment Record.
                         Base::Unresolvable => unreachable!(),
                     }
@@ -1454,6 +1576,7 @@ impl<'a> Vm {
                     referenced_name: property_key.unbind(),
                     strict,
                     this_value: None,
+                    property_cache: None,
                 });
             }
             Instruction::EvaluatePropertyAccessWithIdentifierKey => {
@@ -1471,6 +1594,7 @@ impl<'a> Vm {
                     referenced_name: property_name_string.unbind().into(),
                     strict,
                     this_value: None,
+                    property_cache: Some(instr.args[1].unwrap()),
                 });
             }
             Instruction::Jump => {
@@ -1621,7 +1745,11 @@ impl<'a> Vm {
             Instruction::InitializeReferencedBinding => {
                 let v = vm.reference.take().unwrap();
                 let w = vm.result.take().unwrap();
-                initialize_referenced_binding(agent, v, w, gc.reborrow())?;
+                if let Base::Register { index, .. } = v.base {
+                    vm.registers[index as usize] = Some(w);
+                } else {
+                    initialize_referenced_binding(agent, v, w, gc.reborrow())?;
+                }
             }
             Instruction::InitializeVariableEnvironment => {
                 let num_variables = instr.args[0].unwrap();
@@ -1931,6 +2059,11 @@ impl<'a> Vm {
                         // c. Return ? base.DeleteBinding(ref.[[ReferencedName]]).
                         vm.result = Some(base.delete_binding(agent, referenced_name, gc)?.into());
                     }
+                    // Register bindings are lexical declarations, which
+                    // cannot be deleted.
+                    Base::Register { .. } => {
+                        vm.result = Some(false.into());
+                    }
                 }
 
                 // Note 1
@@ -1993,7 +2126,7 @@ impl<'a> Vm {
                     if result.is_none() {
                         // We have exhausted the iterator; replace it with an empty VmIterator so
                         // further instructions aren't observable.
-                        *iterator = VmIterator::SliceIterator(SendableRef::new(&[]));
+                        *iterator = VmIterator::ListIterator(Vec::new().into_iter());
                     }
                 } else {
                     vm.iterator_stack.pop();
@@ -2033,11 +2166,13 @@ impl<'a> Vm {
             }
             Instruction::Yield => return Ok(ContinuationKind::Yield),
             Instruction::CreateUnmappedArgumentsObject => {
-                let Some(VmIterator::SliceIterator(slice)) = vm.iterator_stack.last() else {
-                    unreachable!()
+                let arguments = match vm.iterator_stack.last() {
+                    Some(VmIterator::SliceIterator(slice)) => slice.get(),
+                    Some(VmIterator::ListIterator(values)) => values.as_slice(),
+                    _ => unreachable!(),
                 };
                 vm.result = Some(
-                    create_unmapped_arguments_object(agent, slice.get(), gc.nogc()).into_value(),
+                    create_unmapped_arguments_object(agent, arguments, gc.nogc()).into_value(),
                 );
             }
             other => todo!("{other:?}"),
@@ -2061,6 +2196,91 @@ impl<'a> Vm {
         assert!(self.stack.len() >= arg_count);
         self.stack.split_off(self.stack.len() - arg_count)
     }
+
+    /// Perform GetValue on a reference to a register of the current frame.
+    fn get_register_value(
+        &self,
+        agent: &mut Agent,
+        reference: &Reference,
+        gc: NoGcScope,
+    ) -> JsResult<Value> {
+        let Base::Register { index, .. } = reference.base else {
+            unreachable!()
+        };
+        match self.registers[index as usize] {
+            Some(value) => Ok(value),
+            None => Err(throw_uninitialized_register(agent, reference, gc)),
+        }
+    }
+
+    /// Perform PutValue on a reference to a register of the current frame.
+    fn put_register_value(
+        &mut self,
+        agent: &mut Agent,
+        reference: &Reference,
+        value: Value,
+        gc: NoGcScope,
+    ) -> JsResult<()> {
+        let Base::Register { index, mutable } = reference.base else {
+            unreachable!()
+        };
+        let register = &mut self.registers[index as usize];
+        if register.is_none() {
+            return Err(throw_uninitialized_register(agent, reference, gc));
+        }
+        if !mutable {
+            // Immutable register bindings are always strict bindings.
+            let error_message = format!(
+                "Cannot assign to immutable identifier '{}'.",
+                reference.referenced_name.as_display(agent)
+            );
+            return Err(agent.throw_exception(ExceptionType::TypeError, error_message, gc));
+        }
+        *register = Some(value.unbind());
+        Ok(())
+    }
+}
+
+/// Returns true if garbage collection can run at the current instruction
+/// boundary without the `interleaved-gc` feature.
+///
+/// Native code may hold unrooted Values while it calls back into ECMAScript
+/// code, so this requires that no execution context on the stack belongs to
+/// a built-in function and that the VMs of all ECMAScript callers are rooted
+/// on the VM stack. Callers are only rooted with the `interleaved-gc`
+/// feature, so without it this only holds in the outermost ECMAScript frame.
+/// Contexts with neither code nor a function are pushed by the embedder,
+/// which roots its Values across any call that may perform garbage
+/// collection.
+fn is_gc_safepoint(agent: &Agent) -> bool {
+    let mut ecmascript_contexts = 0;
+    for ctx in agent.execution_context_stack.iter() {
+        if ctx.ecmascript_code.is_some() {
+            ecmascript_contexts += 1;
+        } else if ctx.function.is_some() {
+            return false;
+        }
+    }
+    ecmascript_contexts == agent.vm_stack.len() + 1
+}
+
+/// Returns true if garbage collection may happen while the running
+/// ECMAScript frame is alive. Frames below it do not change while it runs, so
+/// this holds for the whole frame if it holds when the frame is entered.
+fn may_collect_garbage(agent: &Agent) -> bool {
+    !agent.options.disable_gc && (cfg!(feature = "interleaved-gc") || is_gc_safepoint(agent))
+}
+
+fn throw_uninitialized_register(
+    agent: &mut Agent,
+    reference: &Reference,
+    gc: NoGcScope,
+) -> JsError {
+    let error_message = format!(
+        "Identifier '{}' has not been initialized.",
+        reference.referenced_name.as_display(agent)
+    );
+    agent.throw_exception(ExceptionType::ReferenceError, error_message, gc)
 }
 
 /// ### [13.15.3 ApplyStringOrNumericBinaryOperator ( lval, opText, rval )](https://tc39.es/ecma262/#sec-applystringornumericbinaryoperator)
@@ -2119,21 +2339,26 @@ fn apply_string_or_numeric_binary_operator(
         match (String::try_from(lprim), String::try_from(rprim)) {
             (Ok(lstr), Ok(rstr)) => {
                 // iii. Return the string-concatenation of lstr and rstr.
+                agent.check_heap_limit(lstr.len(agent) + rstr.len(agent))?;
                 return Ok(String::concat(agent, [lstr, rstr], gc).into_value());
             }
             (Ok(lstr), Err(_)) => {
                 let lstr = lstr.bind(gc).scope(agent, gc);
                 // ii. Let rstr be ? ToString(rprim).
                 let rstr = to_string_primitive(agent, rprim, gc)?;
+                let lstr = lstr.get(agent).bind(gc);
                 // iii. Return the string-concatenation of lstr and rstr.
-                return Ok(String::concat(agent, [lstr.get(agent).bind(gc), rstr], gc).into_value());
+                agent.check_heap_limit(lstr.len(agent) + rstr.len(agent))?;
+                return Ok(String::concat(agent, [lstr, rstr], gc).into_value());
             }
             (Err(_), Ok(rstr)) => {
                 let rstr = rstr.bind(gc).scope(agent, gc);
                 // i. Let lstr be ? ToString(lprim).
                 let lstr = to_string_primitive(agent, lprim, gc)?;
+                let rstr = rstr.get(agent).bind(gc);
                 // iii. Return the string-concatenation of lstr and rstr.
-                return Ok(String::concat(agent, [lstr, rstr.get(agent).bind(gc)], gc).into_value());
+                agent.check_heap_limit(lstr.len(agent) + rstr.len(agent))?;
+                return Ok(String::concat(agent, [lstr, rstr], gc).into_value());
             }
             (Err(_), Err(_)) => {}
         }
@@ -2245,7 +2470,7 @@ fn apply_string_or_numeric_binary_operator(
 
 /// ### [13.5.3 The typeof operator](https://tc39.es/ecma262/#sec-typeof-operator)
 #[inline]
-fn typeof_operator(_: &mut Agent, val: Value) -> String {
+fn typeof_operator(agent: &mut Agent, val: Value) -> String {
     match val {
         // 4. If val is undefined, return "undefined".
         Value::Undefined => BUILTIN_STRING_MEMORY.undefined,
@@ -2281,11 +2506,12 @@ fn typeof_operator(_: &mut Agent, val: Value) -> String {
         Value::Iterator |
         Value::ArrayIterator(_) |
         Value::MapIterator(_) |
+        Value::StringIterator(_) |
         Value::Generator(_) |
         Value::Module(_) |
         Value::EmbedderObject(_) => BUILTIN_STRING_MEMORY.object,
         #[cfg(feature = "regexp")]
-        Value::RegExp(_) => BUILTIN_STRING_MEMORY.object,
+        Value::RegExp(_) | Value::RegExpStringIterator(_) => BUILTIN_STRING_MEMORY.object,
         #[cfg(feature = "weak-refs")]
         Value::WeakMap(_) |
         Value::WeakRef(_) |
@@ -2319,9 +2545,14 @@ fn typeof_operator(_: &mut Agent, val: Value) -> String {
         Value::BuiltinConstructorFunction(_) |
         Value::BuiltinPromiseResolvingFunction(_) |
         Value::BuiltinPromiseCollectorFunction |
-        Value::BuiltinProxyRevokerFunction => BUILTIN_STRING_MEMORY.function,
-        // TODO: Check [[Call]] slot for Proxy
-        Value::Proxy(_) => todo!(),
+        Value::BuiltinProxyRevokerFunction(_) => BUILTIN_STRING_MEMORY.function,
+        Value::Proxy(proxy) => {
+            if agent[proxy].callable {
+                BUILTIN_STRING_MEMORY.function
+            } else {
+                BUILTIN_STRING_MEMORY.object
+            }
+        }
     }
 }
 
@@ -2420,6 +2651,8 @@ impl HeapMarkAndSweep for Vm {
             exception_jump_target_stack,
             result,
             reference,
+            registers,
+            executable,
         } = self;
         stack.as_slice().mark_values(queues);
         reference_stack.as_slice().mark_values(queues);
@@ -2427,6 +2660,8 @@ impl HeapMarkAndSweep for Vm {
         exception_jump_target_stack.as_slice().mark_values(queues);
         result.mark_values(queues);
         reference.mark_values(queues);
+        registers.as_slice().mark_values(queues);
+        executable.mark_values(queues);
     }
 
     fn sweep_values(&mut self, compactions: &CompactionLists) {
@@ -2438,6 +2673,8 @@ impl HeapMarkAndSweep for Vm {
             exception_jump_target_stack,
             result,
             reference,
+            registers,
+            executable,
         } = self;
         stack.as_mut_slice().sweep_values(compactions);
         reference_stack.as_mut_slice().sweep_values(compactions);
@@ -2447,6 +2684,8 @@ impl HeapMarkAndSweep for Vm {
             .sweep_values(compactions);
         result.sweep_values(compactions);
         reference.sweep_values(compactions);
+        registers.as_mut_slice().sweep_values(compactions);
+        executable.sweep_values(compactions);
     }
 }
 
@@ -2458,11 +2697,13 @@ impl HeapMarkAndSweep for SuspendedVm {
             reference_stack,
             iterator_stack,
             exception_jump_target_stack,
+            registers,
         } = self;
         stack.mark_values(queues);
         reference_stack.mark_values(queues);
         iterator_stack.mark_values(queues);
         exception_jump_target_stack.mark_values(queues);
+        registers.mark_values(queues);
     }
 
     fn sweep_values(&mut self, compactions: &CompactionLists) {
@@ -2472,10 +2713,12 @@ impl HeapMarkAndSweep for SuspendedVm {
             reference_stack,
             iterator_stack,
             exception_jump_target_stack,
+            registers,
         } = self;
         stack.sweep_values(compactions);
         reference_stack.sweep_values(compactions);
         iterator_stack.sweep_values(compactions);
         exception_jump_target_stack.sweep_values(compactions);
+        registers.sweep_values(compactions);
     }
 }
@@ -11,6 +11,8 @@ use private::RootableSealed;
 use crate::ecmascript::builtins::date::Date;
 #[cfg(feature = "regexp")]
 use crate::ecmascript::builtins::regexp::RegExp;
+#[cfg(feature = "regexp")]
+use crate::ecmascript::builtins::text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIterator;
 #[cfg(feature = "shared-array-buffer")]
 use crate::ecmascript::builtins::shared_array_buffer::SharedArrayBuffer;
 #[cfg(feature = "array-buffer")]
@@ -22,7 +24,7 @@ use crate::ecmascript::types::DATE_DISCRIMINANT;
 #[cfg(feature = "proposal-float16array")]
 use crate::ecmascript::types::FLOAT_16_ARRAY_DISCRIMINANT;
 #[cfg(feature = "regexp")]
-use crate::ecmascript::types::REGEXP_DISCRIMINANT;
+use crate::ecmascript::types::{REGEXP_DISCRIMINANT, REGEXP_STRING_ITERATOR_DISCRIMINANT};
 #[cfg(feature = "shared-array-buffer")]
 use crate::ecmascript::types::SHARED_ARRAY_BUFFER_DISCRIMINANT;
 #[cfg(feature = "array-buffer")]
@@ -65,7 +67,8 @@ use crate::{
                 promise_reaction_records::PromiseReaction,
                 promise_resolving_functions::BuiltinPromiseResolvingFunction,
             },
-            proxy::Proxy,
+            proxy::{revoker::BuiltinProxyRevokerFunction, Proxy},
+            text_processing::string_objects::string_iterator_objects::string_iterator::StringIterator,
             Array, BuiltinConstructorFunction, BuiltinFunction, ECMAScriptFunction,
         },
         types::{
@@ -81,7 +84,7 @@ use crate::{
             FINALIZATION_REGISTRY_DISCRIMINANT, GENERATOR_DISCRIMINANT, ITERATOR_DISCRIMINANT,
             MAP_DISCRIMINANT, MAP_ITERATOR_DISCRIMINANT, MODULE_DISCRIMINANT, NUMBER_DISCRIMINANT,
             OBJECT_DISCRIMINANT, PROMISE_DISCRIMINANT, PROXY_DISCRIMINANT, STRING_DISCRIMINANT,
-            SYMBOL_DISCRIMINANT,
+            STRING_ITERATOR_DISCRIMINANT, SYMBOL_DISCRIMINANT,
         },
     },
     heap::HeapMarkAndSweep,
@@ -92,6 +95,8 @@ mod private {
     use crate::ecmascript::builtins::date::Date;
     #[cfg(feature = "regexp")]
     use crate::ecmascript::builtins::regexp::RegExp;
+    #[cfg(feature = "regexp")]
+    use crate::ecmascript::builtins::text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIterator;
     #[cfg(feature = "shared-array-buffer")]
     use crate::ecmascript::builtins::shared_array_buffer::SharedArrayBuffer;
     #[cfg(feature = "array-buffer")]
@@ -120,7 +125,8 @@ mod private {
                 promise_reaction_records::PromiseReaction,
                 promise_resolving_functions::BuiltinPromiseResolvingFunction,
             },
-            proxy::Proxy,
+            proxy::{revoker::BuiltinProxyRevokerFunction, Proxy},
+            text_processing::string_objects::string_iterator_objects::string_iterator::StringIterator,
             Array, BuiltinConstructorFunction, BuiltinFunction, ECMAScriptFunction,
         },
         types::{
@@ -141,6 +147,7 @@ mod private {
     impl RootableSealed for BuiltinConstructorFunction<'_> {}
     impl RootableSealed for BuiltinFunction<'_> {}
     impl RootableSealed for BuiltinPromiseResolvingFunction<'_> {}
+    impl RootableSealed for BuiltinProxyRevokerFunction<'_> {}
     #[cfg(feature = "array-buffer")]
     impl RootableSealed for DataView<'_> {}
     #[cfg(feature = "date")]
@@ -153,6 +160,9 @@ mod private {
     impl RootableSealed for Generator<'_> {}
     impl RootableSealed for Map<'_> {}
     impl RootableSealed for MapIterator<'_> {}
+    impl RootableSealed for StringIterator<'_> {}
+    #[cfg(feature = "regexp")]
+    impl RootableSealed for RegExpStringIterator<'_> {}
     impl RootableSealed for Module<'_> {}
     impl RootableSealed for Number<'_> {}
     impl RootableSealed for Numeric<'_> {}
@@ -268,7 +278,8 @@ pub enum HeapRootData {
     BuiltinPromiseResolvingFunction(BuiltinPromiseResolvingFunction<'static>) =
         BUILTIN_PROMISE_RESOLVING_FUNCTION_DISCRIMINANT,
     BuiltinPromiseCollectorFunction = BUILTIN_PROMISE_COLLECTOR_FUNCTION_DISCRIMINANT,
-    BuiltinProxyRevokerFunction = BUILTIN_PROXY_REVOKER_FUNCTION,
+    BuiltinProxyRevokerFunction(BuiltinProxyRevokerFunction<'static>) =
+        BUILTIN_PROXY_REVOKER_FUNCTION,
     PrimitiveObject(PrimitiveObject<'static>),
     Arguments(OrdinaryObject<'static>) = ARGUMENTS_DISCRIMINANT,
     Array(Array<'static>) = ARRAY_DISCRIMINANT,
@@ -326,6 +337,9 @@ pub enum HeapRootData {
     #[cfg(feature = "set")]
     SetIterator(SetIterator<'static>) = SET_ITERATOR_DISCRIMINANT,
     MapIterator(MapIterator<'static>) = MAP_ITERATOR_DISCRIMINANT,
+    StringIterator(StringIterator<'static>) = STRING_ITERATOR_DISCRIMINANT,
+    #[cfg(feature = "regexp")]
+    RegExpStringIterator(RegExpStringIterator<'static>) = REGEXP_STRING_ITERATOR_DISCRIMINANT,
     Generator(Generator<'static>) = GENERATOR_DISCRIMINANT,
     Module(Module<'static>) = MODULE_DISCRIMINANT,
     EmbedderObject(EmbedderObject<'static>) = EMBEDDER_OBJECT_DISCRIMINANT,
@@ -357,7 +371,9 @@ impl From<Object<'static>> for HeapRootData {
                 Self::BuiltinPromiseResolvingFunction(builtin_promise_resolving_function)
             }
             Object::BuiltinPromiseCollectorFunction => Self::BuiltinPromiseCollectorFunction,
-            Object::BuiltinProxyRevokerFunction => Self::BuiltinProxyRevokerFunction,
+            Object::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function) => {
+                Self::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function)
+            }
             Object::PrimitiveObject(primitive_object) => Self::PrimitiveObject(primitive_object),
             Object::Arguments(ordinary_object) => Self::Arguments(ordinary_object),
             Object::Array(array) => Self::Array(array),
@@ -371,6 +387,7 @@ impl From<Object<'static>> for HeapRootData {
             Object::Map(map) => Self::Map(map),
             Object::Promise(promise) => Self::Promise(promise),
             Object::Proxy(proxy) => Self::Proxy(proxy),
+            #[cfg(feature = "regexp")]
             Object::RegExp(reg_exp) => Self::RegExp(reg_exp),
             #[cfg(feature = "set")]
             Object::Set(set) => Self::Set(set),
@@ -400,6 +417,11 @@ impl From<Object<'static>> for HeapRootData {
             #[cfg(feature = "set")]
             Object::SetIterator(set_iterator) => Self::SetIterator(set_iterator),
             Object::MapIterator(map_iterator) => Self::MapIterator(map_iterator),
+            Object::StringIterator(string_iterator) => Self::StringIterator(string_iterator),
+            #[cfg(feature = "regexp")]
+            Object::RegExpStringIterator(regexp_string_iterator) => {
+                Self::RegExpStringIterator(regexp_string_iterator)
+            }
             Object::Generator(generator) => Self::Generator(generator),
             Object::Module(module) => Self::Module(module),
             Object::EmbedderObject(embedder_object) => Self::EmbedderObject(embedder_object),
@@ -465,7 +487,9 @@ impl HeapMarkAndSweep for HeapRootData {
                 builtin_promise_resolving_function.mark_values(queues)
             }
             HeapRootData::BuiltinPromiseCollectorFunction => todo!(),
-            HeapRootData::BuiltinProxyRevokerFunction => todo!(),
+            HeapRootData::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function) => {
+                builtin_proxy_revoker_function.mark_values(queues)
+            }
             HeapRootData::PrimitiveObject(primitive_object) => primitive_object.mark_values(queues),
             HeapRootData::Arguments(ordinary_object) => ordinary_object.mark_values(queues),
             HeapRootData::Array(array) => array.mark_values(queues),
@@ -527,6 +551,11 @@ impl HeapMarkAndSweep for HeapRootData {
             #[cfg(feature = "set")]
             HeapRootData::SetIterator(set_iterator) => set_iterator.mark_values(queues),
             HeapRootData::MapIterator(map_iterator) => map_iterator.mark_values(queues),
+            HeapRootData::StringIterator(string_iterator) => string_iterator.mark_values(queues),
+            #[cfg(feature = "regexp")]
+            HeapRootData::RegExpStringIterator(regexp_string_iterator) => {
+                regexp_string_iterator.mark_values(queues)
+            }
             HeapRootData::Generator(generator) => generator.mark_values(queues),
             HeapRootData::Module(module) => module.mark_values(queues),
             HeapRootData::EmbedderObject(embedder_object) => embedder_object.mark_values(queues),
@@ -556,7 +585,9 @@ impl HeapMarkAndSweep for HeapRootData {
                 builtin_promise_resolving_function.sweep_values(compactions)
             }
             HeapRootData::BuiltinPromiseCollectorFunction => todo!(),
-            HeapRootData::BuiltinProxyRevokerFunction => todo!(),
+            HeapRootData::BuiltinProxyRevokerFunction(builtin_proxy_revoker_function) => {
+                builtin_proxy_revoker_function.sweep_values(compactions)
+            }
             HeapRootData::PrimitiveObject(primitive_object) => {
                 primitive_object.sweep_values(compactions)
             }
@@ -620,6 +651,13 @@ impl HeapMarkAndSweep for HeapRootData {
             #[cfg(feature = "set")]
             HeapRootData::SetIterator(set_iterator) => set_iterator.sweep_values(compactions),
             HeapRootData::MapIterator(map_iterator) => map_iterator.sweep_values(compactions),
+            HeapRootData::StringIterator(string_iterator) => {
+                string_iterator.sweep_values(compactions)
+            }
+            #[cfg(feature = "regexp")]
+            HeapRootData::RegExpStringIterator(regexp_string_iterator) => {
+                regexp_string_iterator.sweep_values(compactions)
+            }
             HeapRootData::Generator(generator) => generator.sweep_values(compactions),
             HeapRootData::Module(module) => module.sweep_values(compactions),
             HeapRootData::EmbedderObject(embedder_object) => {
@@ -2,7 +2,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::SmallInteger;
+use crate::{
+    heap::{SnapshotData, SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter},
+    SmallInteger,
+};
 
 /// 56-bit double, the implied bottom 8 bits are zero.
 #[derive(Clone, Copy, PartialEq)]
@@ -165,3 +168,13 @@ fn invalid_small_integers() {
     assert_eq!(SmallF64::try_from(f64::MAX), Err(()));
     assert_eq!(SmallF64::try_from(f64::MIN), Err(()));
 }
+
+impl SnapshotData for SmallF64 {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.into_f64().serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Self::try_from(f64::deserialize(reader)?).map_err(|_| SnapshotError::Malformed)
+    }
+}
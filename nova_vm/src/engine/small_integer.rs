@@ -2,6 +2,8 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::heap::{SnapshotData, SnapshotError, SnapshotReader, SnapshotResult, SnapshotWriter};
+
 /// 56-bit signed integer.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct SmallInteger {
@@ -230,3 +232,13 @@ fn invalid_small_integers() {
     );
     assert_eq!(SmallInteger::try_from(i64::MIN), Err(()));
 }
+
+impl SnapshotData for SmallInteger {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.into_i64().serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Self::try_from(i64::deserialize(reader)?).map_err(|_| SnapshotError::Malformed)
+    }
+}
@@ -6,8 +6,11 @@ pub mod element_array;
 mod heap_bits;
 mod heap_constants;
 pub(crate) mod heap_gc;
+mod heap_snapshot;
+mod heap_statistics;
 pub mod indexes;
 mod object_entry;
+mod startup_snapshot;
 
 use std::{cell::RefCell, ops::Index};
 
@@ -32,6 +35,8 @@ use self::{
 use crate::ecmascript::builtins::date::data::DateHeapData;
 #[cfg(feature = "regexp")]
 use crate::ecmascript::builtins::regexp::RegExpHeapData;
+#[cfg(feature = "regexp")]
+use crate::ecmascript::builtins::text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIteratorHeapData;
 #[cfg(feature = "shared-array-buffer")]
 use crate::ecmascript::builtins::shared_array_buffer::data::SharedArrayBufferHeapData;
 #[cfg(feature = "array-buffer")]
@@ -72,29 +77,37 @@ use crate::{
             module::data::ModuleHeapData,
             primitive_objects::PrimitiveObjectHeapData,
             promise::data::PromiseHeapData,
-            proxy::data::ProxyHeapData,
+            proxy::{data::ProxyHeapData, revoker::ProxyRevokerFunctionHeapData},
+            text_processing::string_objects::string_iterator_objects::string_iterator::StringIteratorHeapData,
             ArrayBuffer, ArrayHeapData,
         },
         execution::{Environments, Realm, RealmIdentifier},
         scripts_and_modules::{
             module::ModuleIdentifier,
             script::{Script, ScriptIdentifier},
-            source_code::SourceCodeHeapData,
+            source_code::{ParsedCodeDependent, SourceCodeHeapData},
         },
         types::{
             bigint::HeapBigInt, BigIntHeapData, BoundFunctionHeapData, BuiltinConstructorHeapData,
             BuiltinFunctionHeapData, ECMAScriptFunctionHeapData, HeapNumber, HeapString,
-            NumberHeapData, Object, ObjectHeapData, OrdinaryObject, String, StringHeapData,
-            SymbolHeapData, BUILTIN_STRINGS_LIST,
+            NumberHeapData, Object, ObjectHeapData, ObjectShape, ObjectShapeRecord, OrdinaryObject,
+            String, StringHeapData, SymbolHeapData, BUILTIN_STRINGS_LIST,
         },
     },
     engine::{rootable::HeapRootData, ExecutableHeapData},
 };
-#[cfg(feature = "array-buffer")]
 use ahash::AHashMap;
 use hashbrown::HashTable;
 pub(crate) use heap_bits::{CompactionLists, HeapMarkAndSweep, WorkQueues};
-use wtf8::Wtf8;
+pub(crate) use heap_snapshot::write_heap_snapshot;
+pub(crate) use heap_statistics::{GcStatistics, HeapVectorVisitor};
+pub use heap_statistics::{HeapKindStatistics, HeapStatistics};
+pub use startup_snapshot::SnapshotError;
+pub(crate) use startup_snapshot::{
+    read_startup_snapshot, write_startup_snapshot, SnapshotData, SnapshotReader, SnapshotResult,
+    SnapshotWriter,
+};
+use wtf8::{Wtf8, Wtf8Buf};
 
 #[derive(Debug)]
 pub struct Heap {
@@ -135,10 +148,23 @@ pub struct Heap {
     pub map_iterators: Vec<Option<MapIteratorHeapData>>,
     pub numbers: Vec<Option<NumberHeapData>>,
     pub objects: Vec<Option<ObjectHeapData>>,
+    pub(crate) object_shapes: Vec<ObjectShapeRecord>,
+    /// Root shapes of the shape transition trees, keyed by prototype. Entries
+    /// are dropped when their shape is garbage collected.
+    pub(crate) object_shape_roots: AHashMap<Option<OrdinaryObject<'static>>, ObjectShape>,
+    /// Size of the heap in bytes, as given by [`Heap::allocated_bytes`], at
+    /// which the VM triggers the next garbage collection.
+    pub(crate) gc_threshold: usize,
+    /// Number of bytes owned by heap data outside of the heap vectors, as
+    /// given by [`OwnedAllocations`]. This is recounted during garbage
+    /// collection and grows as data is allocated.
+    pub(crate) owned_bytes: usize,
+    pub(crate) gc_statistics: GcStatistics,
     pub primitive_objects: Vec<Option<PrimitiveObjectHeapData>>,
     pub promise_reaction_records: Vec<Option<PromiseReactionRecord>>,
     pub promise_resolving_functions: Vec<Option<PromiseResolvingFunctionHeapData>>,
     pub promises: Vec<Option<PromiseHeapData>>,
+    pub proxy_revoker_functions: Vec<Option<ProxyRevokerFunctionHeapData>>,
     pub proxys: Vec<Option<ProxyHeapData>>,
     pub realms: Vec<Option<Realm>>,
     #[cfg(feature = "regexp")]
@@ -149,6 +175,9 @@ pub struct Heap {
     pub set_iterators: Vec<Option<SetIteratorHeapData>>,
     #[cfg(feature = "shared-array-buffer")]
     pub shared_array_buffers: Vec<Option<SharedArrayBufferHeapData>>,
+    pub string_iterators: Vec<Option<StringIteratorHeapData>>,
+    #[cfg(feature = "regexp")]
+    pub regexp_string_iterators: Vec<Option<RegExpStringIteratorHeapData>>,
     pub symbols: Vec<Option<SymbolHeapData>>,
     #[cfg(feature = "array-buffer")]
     pub typed_arrays: Vec<Option<TypedArrayHeapData>>,
@@ -176,6 +205,20 @@ pub struct Heap {
     pub string_hasher: ahash::RandomState,
 }
 
+/// Heap size in bytes below which garbage collection is never triggered
+/// automatically.
+const MIN_GC_THRESHOLD: usize = 8 * 1024 * 1024;
+
+/// Default factor by which the heap may grow after a garbage collection
+/// before the next one is triggered.
+pub(crate) const DEFAULT_GC_GROWTH_FACTOR: f64 = 2.0;
+
+/// Heap data that owns allocations outside of its slot in a heap vector.
+pub(crate) trait OwnedAllocations {
+    /// Approximate number of bytes owned by the data outside of its slot.
+    fn owned_bytes(&self) -> usize;
+}
+
 pub trait CreateHeapData<T, F> {
     /// Creates a [`Value`] from the given data. Allocating the data is **not**
     /// guaranteed.
@@ -204,6 +247,17 @@ impl CreateHeapData<std::string::String, String<'static>> for Heap {
     }
 }
 
+impl CreateHeapData<Wtf8Buf, String<'static>> for Heap {
+    fn create(&mut self, data: Wtf8Buf) -> String<'static> {
+        match data.into_string() {
+            Ok(data) => self.create(data),
+            // SAFETY: Strings containing lone surrogates cannot be
+            // represented as SmallStrings.
+            Err(data) => unsafe { self.alloc_wtf8_buf(data) },
+        }
+    }
+}
+
 impl Heap {
     pub fn new() -> Heap {
         let mut heap = Heap {
@@ -237,6 +291,8 @@ impl Heap {
                 e2pow16: ElementArray2Pow16::default(),
                 e2pow24: ElementArray2Pow24::default(),
                 e2pow32: ElementArray2Pow32::default(),
+                sparse: Vec::with_capacity(0),
+                float64: Vec::with_capacity(0),
             },
             embedder_objects: Vec::with_capacity(0),
             environments: Default::default(),
@@ -251,10 +307,16 @@ impl Heap {
             modules: Vec::with_capacity(0),
             numbers: Vec::with_capacity(1024),
             objects: Vec::with_capacity(1024),
+            object_shapes: Vec::with_capacity(1024),
+            object_shape_roots: AHashMap::with_capacity(128),
+            gc_threshold: MIN_GC_THRESHOLD,
+            owned_bytes: 0,
+            gc_statistics: Default::default(),
             primitive_objects: Vec::with_capacity(0),
             promise_reaction_records: Vec::with_capacity(0),
             promise_resolving_functions: Vec::with_capacity(0),
             promises: Vec::with_capacity(0),
+            proxy_revoker_functions: Vec::with_capacity(0),
             proxys: Vec::with_capacity(0),
             realms: Vec::with_capacity(1),
             #[cfg(feature = "regexp")]
@@ -267,6 +329,9 @@ impl Heap {
             #[cfg(feature = "shared-array-buffer")]
             shared_array_buffers: Vec::with_capacity(0),
             strings: Vec::with_capacity(1024),
+            string_iterators: Vec::with_capacity(0),
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators: Vec::with_capacity(0),
             string_lookup_table: HashTable::with_capacity(1024),
             string_hasher: ahash::RandomState::new(),
             symbols: Vec::with_capacity(1024),
@@ -293,6 +358,45 @@ impl Heap {
         heap
     }
 
+    /// Approximate number of bytes taken up by the heap's data.
+    ///
+    /// This counts the slots of all heap vectors and element arrays,
+    /// including slots of not yet compacted dead data, and the allocations
+    /// owned by strings, BigInts, buffers, collections and code. Growth of
+    /// owned allocations after their data was created, such as Map and Set
+    /// entries, is only seen after the next garbage collection.
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        struct ByteCounter(usize);
+        impl HeapVectorVisitor for ByteCounter {
+            fn visit<T>(
+                &mut self,
+                _: &'static str,
+                slots: &[T],
+                _: impl FnOnce() -> usize,
+                _: impl FnOnce() -> usize,
+            ) {
+                self.0 += size_of_val(slots);
+            }
+        }
+        let mut counter = ByteCounter(0);
+        self.visit_vectors(&mut counter);
+        self.owned_bytes + counter.0
+    }
+
+    /// Returns true if the heap has grown past the garbage collection
+    /// threshold.
+    pub(crate) fn needs_gc(&self) -> bool {
+        self.allocated_bytes() >= self.gc_threshold
+    }
+
+    /// Set the next garbage collection threshold relative to the current
+    /// heap size. Called after each garbage collection.
+    pub(crate) fn update_gc_threshold(&mut self, growth_factor: f64) {
+        self.owned_bytes = self.count_owned_bytes();
+        let threshold = (self.allocated_bytes() as f64 * growth_factor) as usize;
+        self.gc_threshold = threshold.max(MIN_GC_THRESHOLD);
+    }
+
     pub(crate) fn add_module(&mut self, module: ModuleHeapData) -> ModuleIdentifier<'static> {
         self.modules.push(Some(module));
         ModuleIdentifier::last(&self.modules)
@@ -304,8 +408,11 @@ impl Heap {
     }
 
     pub(crate) fn add_script(&mut self, script: Script) -> ScriptIdentifier {
+        let source_code = script.source_code;
         self.scripts.push(Some(script));
-        ScriptIdentifier::last(&self.scripts)
+        let script = ScriptIdentifier::last(&self.scripts);
+        source_code.add_dependent(self, ParsedCodeDependent::Script(script));
+        script
     }
 
     /// Allocate a string onto the Agent heap
@@ -321,7 +428,7 @@ impl Heap {
     /// comparison between heap allocated strings and SmallStrings can be
     /// guaranteed to never equal true.
     pub(crate) unsafe fn alloc_str(&mut self, message: &str) -> String<'static> {
-        let found = self.find_equal_string(message);
+        let found = self.find_equal_string(Wtf8::from_str(message));
         match found {
             Ok(string) => string,
             Err(hash) => {
@@ -344,7 +451,7 @@ impl Heap {
     /// comparison between heap allocated strings and SmallStrings can be
     /// guaranteed to never equal true.
     unsafe fn alloc_string(&mut self, message: std::string::String) -> String<'static> {
-        let found = self.find_equal_string(message.as_str());
+        let found = self.find_equal_string(Wtf8::from_str(message.as_str()));
         match found {
             Ok(string) => string,
             Err(hash) => {
@@ -354,6 +461,29 @@ impl Heap {
         }
     }
 
+    /// Allocate a WTF-8 string onto the Agent heap
+    ///
+    /// This method will currently iterate through all heap strings to look for
+    /// a possible matching string and if found will return its HeapString
+    /// instead of allocating a copy.
+    ///
+    /// # Safety
+    ///
+    /// The string being allocated must not be representable as a
+    /// SmallString. All SmallStrings must be kept on the stack to ensure that
+    /// comparison between heap allocated strings and SmallStrings can be
+    /// guaranteed to never equal true.
+    unsafe fn alloc_wtf8_buf(&mut self, message: Wtf8Buf) -> String<'static> {
+        let found = self.find_equal_string(&message);
+        match found {
+            Ok(string) => string,
+            Err(hash) => {
+                let data = StringHeapData::from_wtf8_buf(message);
+                self.create((data, hash))
+            }
+        }
+    }
+
     /// Allocate a static string onto the Agent heap
     ///
     /// This method will currently iterate through all heap strings to look for
@@ -367,7 +497,7 @@ impl Heap {
     /// comparison between heap allocated strings and SmallStrings can be
     /// guaranteed to never equal true.
     pub(crate) unsafe fn alloc_static_str(&mut self, message: &'static str) -> String<'static> {
-        let found = self.find_equal_string(message);
+        let found = self.find_equal_string(Wtf8::from_str(message));
         match found {
             Ok(string) => string,
             Err(hash) => {
@@ -378,9 +508,8 @@ impl Heap {
     }
 
     /// Find existing heap String or return the strings hash.
-    fn find_equal_string(&self, message: &str) -> Result<String<'static>, u64> {
-        debug_assert!(message.len() > 7);
-        let message = Wtf8::from_str(message);
+    fn find_equal_string(&self, message: &Wtf8) -> Result<String<'static>, u64> {
+        debug_assert!(message.len() > 7 || message.as_str().is_none());
         let hash = self.string_hasher.hash_one(message);
         self.string_lookup_table
             .find(hash, |heap_string| {
@@ -394,6 +523,33 @@ impl Heap {
             .ok_or(hash)
     }
 
+    /// Intern a rope string. If an equal string is already interned, that
+    /// string is returned; otherwise the rope is flattened and added to the
+    /// lookup table. Other heap strings are returned as-is.
+    pub(crate) fn intern_rope(&mut self, string: HeapString<'static>) -> HeapString<'static> {
+        let data = &self.strings[string];
+        if !data.is_rope() {
+            return string;
+        }
+        match self.find_equal_string(data.as_wtf8()) {
+            Ok(String::String(existing)) => existing,
+            Ok(String::SmallString(_)) => unreachable!(),
+            Err(hash) => {
+                let Heap {
+                    strings,
+                    string_lookup_table,
+                    string_hasher,
+                    ..
+                } = self;
+                strings[string].flatten();
+                string_lookup_table.insert_unique(hash, string, |heap_string| {
+                    string_hasher.hash_one(strings[*heap_string].as_wtf8())
+                });
+                string
+            }
+        }
+    }
+
     /// Allocate a 64-bit floating point number onto the Agent heap
     ///
     /// # Safety
@@ -411,15 +567,7 @@ impl Heap {
         &mut self,
         entries: &[ObjectEntry],
     ) -> OrdinaryObject<'static> {
-        let (keys, values) = self.elements.create_object_entries(entries);
-        let object_data = ObjectHeapData {
-            extensible: true,
-            keys,
-            values,
-            prototype: None,
-        };
-        self.objects.push(Some(object_data));
-        ObjectIndex::last(&self.objects).into()
+        self.create_object_from_entries(None, entries)
     }
 
     pub(crate) fn create_object_with_prototype(
@@ -427,14 +575,18 @@ impl Heap {
         prototype: Object,
         entries: &[ObjectEntry],
     ) -> OrdinaryObject<'static> {
-        let (keys, values) = self.elements.create_object_entries(entries);
-        let object_data = ObjectHeapData {
-            extensible: true,
-            keys,
-            values,
-            prototype: Some(prototype.unbind()),
-        };
-        self.objects.push(Some(object_data));
+        self.create_object_from_entries(Some(prototype), entries)
+    }
+
+    fn create_object_from_entries(
+        &mut self,
+        prototype: Option<Object>,
+        entries: &[ObjectEntry],
+    ) -> OrdinaryObject<'static> {
+        let shape = ObjectShape::from_keys(self, prototype, entries.iter().map(|entry| entry.key));
+        let values = self.elements.create_object_entry_values(entries);
+        self.objects
+            .push(Some(ObjectHeapData::new(true, shape, values)));
         ObjectIndex::last(&self.objects).into()
     }
 }
@@ -482,3 +634,36 @@ fn init_heap() {
     let heap = Heap::new();
     println!("{:#?}", heap);
 }
+
+#[cfg(test)]
+mod test {
+    use super::MIN_GC_THRESHOLD;
+    use crate::test_utils::TestAgent;
+
+    #[test]
+    fn allocation_triggers_garbage_collection() {
+        let mut agent = TestAgent::new();
+        let initial_strings = agent.run(|agent, _| agent.heap.strings.len());
+        // Each iteration leaves a 16 KiB string behind as garbage, which adds
+        // up to well past the minimum collection threshold.
+        let iterations = 2 * MIN_GC_THRESHOLD / (16 * 1024);
+        agent.eval(&format!(
+            "var s = 'x'.repeat(16 * 1024 - 8); for (let i = 0; i < {iterations}; i++) {{ s.slice(i % 8) + i; }}"
+        ));
+        agent.run(|agent, _| {
+            assert!(agent.heap.gc_statistics.collections > 0);
+            assert!(agent.heap.strings.len() < initial_strings + iterations);
+            assert!(agent.heap.owned_bytes < 2 * MIN_GC_THRESHOLD);
+            assert!(agent.heap.allocated_bytes() < agent.heap.gc_threshold);
+        });
+    }
+
+    #[test]
+    fn allocated_bytes_counts_owned_buffers() {
+        let mut agent = TestAgent::new();
+        let before = agent.run(|agent, _| agent.heap.allocated_bytes());
+        agent.eval("var b = new ArrayBuffer(1024 * 1024)");
+        let after = agent.run(|agent, _| agent.heap.allocated_bytes());
+        assert!(after - before >= 1024 * 1024);
+    }
+}
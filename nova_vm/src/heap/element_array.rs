@@ -7,21 +7,32 @@ use ahash::AHashMap;
 use super::{
     indexes::ElementIndex,
     object_entry::{ObjectEntry, ObjectEntryPropertyDescriptor},
-    CompactionLists, HeapMarkAndSweep, WorkQueues,
+    CompactionLists, Heap, HeapMarkAndSweep, SnapshotData, SnapshotError, SnapshotReader,
+    SnapshotResult, SnapshotWriter, WorkQueues,
 };
 use crate::{
     ecmascript::{
         builtins::SealableElementsVector,
         execution::Agent,
-        types::{Function, PropertyDescriptor, PropertyKey, Value},
+        types::{Function, HeapNumber, PropertyDescriptor, PropertyKey, Value},
     },
     engine::context::NoGcScope,
 };
 use std::{
+    collections::BTreeMap,
     mem::MaybeUninit,
     ops::{Index, IndexMut},
 };
 
+/// Elements vectors of up to this length always use a dense backing store.
+const DENSE_ELEMENTS_MIN_LENGTH: u32 = 2u32.pow(16);
+/// Elements vectors do not grow or move into a dense backing store past this
+/// length.
+const DENSE_ELEMENTS_MAX_LENGTH: u32 = 2u32.pow(24);
+/// Bit pattern marking a hole in double elements. NaNs stored as double
+/// elements are canonicalised, so no element value has this bit pattern.
+const FLOAT64_HOLE: u64 = 0x7FF8_0000_0000_0001;
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ElementArrayKey {
     #[default]
@@ -42,6 +53,10 @@ pub enum ElementArrayKey {
     E24,
     /// up to 4294967296 elements
     E32,
+    /// any number of elements, stored sparsely
+    Sparse,
+    /// any number of numbers, stored as raw doubles
+    Float64,
 }
 
 impl ElementArrayKey {
@@ -55,7 +70,7 @@ impl ElementArrayKey {
             ElementArrayKey::E12 => 2u32.pow(12),
             ElementArrayKey::E16 => 2u32.pow(16),
             ElementArrayKey::E24 => 2u32.pow(24),
-            ElementArrayKey::E32 => u32::MAX,
+            ElementArrayKey::E32 | ElementArrayKey::Sparse | ElementArrayKey::Float64 => u32::MAX,
         }
     }
 }
@@ -132,8 +147,52 @@ impl ElementsVector {
         self.len == self.cap()
     }
 
+    /// An elements vector is sparse if its elements are stored in a map
+    /// instead of a dense backing store. Sparse elements vectors have no
+    /// slice of values.
+    pub fn is_sparse(&self) -> bool {
+        self.cap == ElementArrayKey::Sparse
+    }
+
+    /// An elements vector is double if its elements are numbers stored as
+    /// raw doubles. Double elements are always writable, enumerable and
+    /// configurable data properties, so double elements vectors have no
+    /// descriptors.
+    pub fn is_float64(&self) -> bool {
+        self.cap == ElementArrayKey::Float64
+    }
+
+    /// Sparse and double elements vectors have no slice of values.
+    pub(crate) fn has_slice(&self) -> bool {
+        !self.is_sparse() && !self.is_float64()
+    }
+
+    /// Returns true if a vector of `length` elements with only
+    /// `element_count` elements present should use sparse storage.
+    pub(crate) fn should_be_sparse(length: u32, element_count: u32) -> bool {
+        length > DENSE_ELEMENTS_MIN_LENGTH && element_count < length / 4
+    }
+
+    /// Returns true if a vector of `length` elements with `element_count`
+    /// elements present should use a dense backing store. This leaves a gap
+    /// with [`ElementsVector::should_be_sparse`] so that vectors do not
+    /// switch back and forth on every change.
+    pub(crate) fn should_be_dense(length: u32, element_count: u32) -> bool {
+        length <= DENSE_ELEMENTS_MAX_LENGTH
+            && (length <= DENSE_ELEMENTS_MIN_LENGTH || element_count >= length / 2)
+    }
+
+    /// Returns true if growing the vector to `new_len` with holes should
+    /// move its elements into sparse storage.
+    pub(crate) fn should_grow_sparse(&self, new_len: u32) -> bool {
+        new_len > DENSE_ELEMENTS_MAX_LENGTH || Self::should_be_sparse(new_len, self.len)
+    }
+
     /// An elements vector is simple if it contains no accessor descriptors.
     pub(crate) fn is_simple(&self, arena: &impl AsRef<ElementArrays>) -> bool {
+        if !self.has_slice() {
+            return false;
+        }
         let backing_store = arena.as_ref().get_descriptors_and_slice(*self);
         backing_store.0.map_or(true, |hashmap| {
             !hashmap
@@ -144,11 +203,17 @@ impl ElementsVector {
 
     /// An elements vector is trivial if it contains no descriptors.
     pub(crate) fn is_trivial(&self, arena: &impl AsRef<ElementArrays>) -> bool {
+        if !self.has_slice() {
+            return false;
+        }
         let backing_store = arena.as_ref().get_descriptors_and_slice(*self);
         backing_store.0.is_none()
     }
 
     pub(crate) fn is_dense(&self, arena: &impl AsRef<ElementArrays>) -> bool {
+        if !self.has_slice() {
+            return false;
+        }
         let (descriptors, elements) = arena.as_ref().get_descriptors_and_slice(*self);
         if let Some(descriptors) = descriptors {
             for (index, ele) in elements.iter().enumerate() {
@@ -185,17 +250,75 @@ impl ElementsVector {
         elements.reserve(self, new_len);
     }
 
+    /// Reserve room for growing the vector to `new_len` with holes. If a
+    /// dense backing store would then be mostly holes or too large, the
+    /// elements are moved into sparse storage instead. Double elements
+    /// must be made generic before they can be moved into sparse storage,
+    /// and otherwise need no room for holes.
+    pub(crate) fn reserve_with_holes(&mut self, elements: &mut ElementArrays, new_len: u32) {
+        if self.is_sparse() {
+            return;
+        }
+        if self.should_grow_sparse(new_len) {
+            elements.make_sparse(self);
+        } else if !self.is_float64() {
+            self.reserve(elements, new_len);
+        }
+    }
+
+    /// Move sparse elements back into a dense backing store if enough of
+    /// them are present.
+    pub(crate) fn densify(&mut self, elements: &mut ElementArrays) {
+        if self.is_sparse()
+            && Self::should_be_dense(
+                self.len,
+                elements.sparse[self.elements_index].element_count(),
+            )
+        {
+            elements.make_dense(self);
+        }
+    }
+
+    /// Drop sparse or double elements at or past the length of the vector,
+    /// moving remaining sparse elements back into a dense backing store if
+    /// appropriate.
+    ///
+    /// A non-configurable sparse element cannot be deleted: The truncation
+    /// stops there, the length is set to one past its index and false is
+    /// returned.
+    pub(crate) fn truncate_unsliced(&mut self, elements: &mut ElementArrays) -> bool {
+        if self.is_float64() {
+            elements.float64[self.elements_index].truncate(self.len);
+            return true;
+        }
+        if !self.is_sparse() {
+            return true;
+        }
+        let len = elements.sparse[self.elements_index].truncate(self.len);
+        let deleted_all = len == self.len;
+        self.len = len;
+        self.densify(elements);
+        deleted_all
+    }
+
     pub fn push(
         &mut self,
         elements: &mut ElementArrays,
         value: Option<Value>,
         descriptor: Option<ElementDescriptor>,
     ) {
+        if self.is_sparse() {
+            elements.sparse[self.elements_index].set(self.len, value, descriptor);
+            self.len += 1;
+            return;
+        }
         if self.is_full() {
             self.reserve(elements, self.len() + 1);
         }
         let next_over_end = match self.cap {
-            ElementArrayKey::Empty => unreachable!(),
+            ElementArrayKey::Empty | ElementArrayKey::Sparse | ElementArrayKey::Float64 => {
+                unreachable!()
+            }
             ElementArrayKey::E4 => {
                 &mut elements.e2pow4.values[self.elements_index][self.len as usize]
             }
@@ -224,7 +347,9 @@ impl ElementsVector {
         *next_over_end = value;
         if let Some(descriptor) = descriptor {
             let descriptors_map = match self.cap {
-                ElementArrayKey::Empty => unreachable!(),
+                ElementArrayKey::Empty | ElementArrayKey::Sparse | ElementArrayKey::Float64 => {
+                    unreachable!()
+                }
                 ElementArrayKey::E4 => &mut elements.e2pow4.descriptors,
                 ElementArrayKey::E6 => &mut elements.e2pow6.descriptors,
                 ElementArrayKey::E8 => &mut elements.e2pow8.descriptors,
@@ -246,8 +371,21 @@ impl ElementsVector {
         let len = usize::try_from(self.len()).unwrap();
         assert!(index < len);
 
+        if self.is_sparse() {
+            elements.sparse[self.elements_index].remove(index as u32);
+            self.len -= 1;
+            return;
+        }
+        if self.is_float64() {
+            elements.float64[self.elements_index].remove(index as u32);
+            self.len -= 1;
+            return;
+        }
+
         let (values, descriptors) = match self.cap {
-            ElementArrayKey::Empty => unreachable!(),
+            ElementArrayKey::Empty | ElementArrayKey::Sparse | ElementArrayKey::Float64 => {
+                unreachable!()
+            }
             ElementArrayKey::E4 => (
                 &mut elements.e2pow4.values[self.elements_index][..],
                 elements.e2pow4.descriptors.get_mut(&self.elements_index),
@@ -316,6 +454,8 @@ impl HeapMarkAndSweep for ElementsVector {
             ElementArrayKey::E16 => queues.e_2_16.push((self.elements_index, self.len)),
             ElementArrayKey::E24 => queues.e_2_24.push((self.elements_index, self.len)),
             ElementArrayKey::E32 => queues.e_2_32.push((self.elements_index, self.len)),
+            ElementArrayKey::Sparse => queues.e_sparse.push(self.elements_index),
+            ElementArrayKey::Float64 => queues.e_float64.push(self.elements_index),
         }
     }
 
@@ -333,11 +473,57 @@ impl HeapMarkAndSweep for ElementsVector {
             ElementArrayKey::E16 => compactions.e_2_16.get_shift_for_index(self_index),
             ElementArrayKey::E24 => compactions.e_2_24.get_shift_for_index(self_index),
             ElementArrayKey::E32 => compactions.e_2_32.get_shift_for_index(self_index),
+            ElementArrayKey::Sparse => compactions.e_sparse.get_shift_for_index(self_index),
+            ElementArrayKey::Float64 => compactions.e_float64.get_shift_for_index(self_index),
         };
         self.elements_index = ElementIndex::from_u32_index(self_index - shift);
     }
 }
 
+impl SnapshotData for ElementArrayKey {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        (*self as u8).serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(match u8::deserialize(reader)? {
+            0 => ElementArrayKey::Empty,
+            1 => ElementArrayKey::E4,
+            2 => ElementArrayKey::E6,
+            3 => ElementArrayKey::E8,
+            4 => ElementArrayKey::E10,
+            5 => ElementArrayKey::E12,
+            6 => ElementArrayKey::E16,
+            7 => ElementArrayKey::E24,
+            8 => ElementArrayKey::E32,
+            9 => ElementArrayKey::Sparse,
+            10 => ElementArrayKey::Float64,
+            _ => return Err(SnapshotError::Malformed),
+        })
+    }
+}
+
+impl SnapshotData for ElementsVector {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            elements_index,
+            cap,
+            len,
+        } = self;
+        elements_index.serialize(writer)?;
+        cap.serialize(writer)?;
+        len.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            elements_index: SnapshotData::deserialize(reader)?,
+            cap: SnapshotData::deserialize(reader)?,
+            len: SnapshotData::deserialize(reader)?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 pub enum ElementDescriptor {
@@ -1114,6 +1300,298 @@ impl ElementArray2Pow32 {
     }
 }
 
+/// Elements stored sparsely, for element vectors that are huge or mostly
+/// holes. Like in the dense element arrays, an element with a descriptor but
+/// no value is an accessor property and an element with neither is a hole.
+#[derive(Debug, Default, Clone)]
+pub struct SparseElementArray {
+    values: BTreeMap<u32, Value>,
+    descriptors: AHashMap<u32, ElementDescriptor>,
+    /// Number of indexes with a value, a descriptor or both.
+    element_count: u32,
+}
+
+impl SparseElementArray {
+    fn new(values: BTreeMap<u32, Value>, descriptors: AHashMap<u32, ElementDescriptor>) -> Self {
+        let accessors = descriptors
+            .keys()
+            .filter(|index| !values.contains_key(*index))
+            .count();
+        let element_count = (values.len() + accessors) as u32;
+        Self {
+            values,
+            descriptors,
+            element_count,
+        }
+    }
+
+    /// Number of elements present, ie. not holes.
+    pub fn element_count(&self) -> u32 {
+        self.element_count
+    }
+
+    fn has_element(&self, index: u32) -> bool {
+        self.values.contains_key(&index) || self.descriptors.contains_key(&index)
+    }
+
+    /// Indexes of the elements present in ascending order.
+    pub fn keys(&self) -> Vec<u32> {
+        let mut keys = self.values.keys().copied().collect::<Vec<_>>();
+        keys.extend(
+            self.descriptors
+                .keys()
+                .filter(|index| !self.values.contains_key(*index)),
+        );
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Values of the elements present in ascending index order.
+    pub fn values(&self) -> impl ExactSizeIterator<Item = &Value> {
+        self.values.values()
+    }
+
+    /// Indexes and values of the elements present in ascending index order.
+    pub fn entries(&self) -> impl ExactSizeIterator<Item = (u32, Value)> + '_ {
+        self.values.iter().map(|(index, value)| (*index, *value))
+    }
+
+    pub fn descriptors(&self) -> &AHashMap<u32, ElementDescriptor> {
+        &self.descriptors
+    }
+
+    pub fn get(&self, index: u32) -> Option<Value> {
+        self.values.get(&index).copied()
+    }
+
+    pub fn get_descriptor(&self, index: u32) -> Option<ElementDescriptor> {
+        self.descriptors.get(&index).copied()
+    }
+
+    pub fn set(&mut self, index: u32, value: Option<Value>, descriptor: Option<ElementDescriptor>) {
+        self.set_value(index, value);
+        self.set_descriptor(index, descriptor);
+    }
+
+    pub fn set_value(&mut self, index: u32, value: Option<Value>) {
+        let had_element = self.has_element(index);
+        if let Some(value) = value {
+            self.values.insert(index, value);
+        } else {
+            self.values.remove(&index);
+        }
+        self.update_element_count(index, had_element);
+    }
+
+    pub fn set_descriptor(&mut self, index: u32, descriptor: Option<ElementDescriptor>) {
+        let had_element = self.has_element(index);
+        if let Some(descriptor) = descriptor {
+            self.descriptors.insert(index, descriptor);
+        } else {
+            self.descriptors.remove(&index);
+        }
+        self.update_element_count(index, had_element);
+    }
+
+    fn update_element_count(&mut self, index: u32, had_element: bool) {
+        match (had_element, self.has_element(index)) {
+            (false, true) => self.element_count += 1,
+            (true, false) => self.element_count -= 1,
+            _ => {}
+        }
+    }
+
+    /// Remove the element at `index`, shifting all following elements down
+    /// by one.
+    pub fn remove(&mut self, index: u32) {
+        if self.has_element(index) {
+            self.element_count -= 1;
+        }
+        self.values.remove(&index);
+        let tail = self.values.split_off(&index);
+        self.values
+            .extend(tail.into_iter().map(|(key, value)| (key - 1, value)));
+        self.descriptors.remove(&index);
+        if self.descriptors.keys().any(|key| *key > index) {
+            self.descriptors = self
+                .descriptors
+                .drain()
+                .map(|(key, descriptor)| {
+                    if key > index {
+                        (key - 1, descriptor)
+                    } else {
+                        (key, descriptor)
+                    }
+                })
+                .collect();
+        }
+    }
+
+    /// Drop all elements at or past `len` in descending index order, stopping
+    /// at the first non-configurable element. Returns the length after the
+    /// truncation: `len` if every element was dropped, otherwise one past the
+    /// index of the non-configurable element.
+    pub fn truncate(&mut self, len: u32) -> u32 {
+        let new_len = self
+            .descriptors
+            .iter()
+            .filter(|(index, descriptor)| **index >= len && !descriptor.is_configurable())
+            .map(|(index, _)| index + 1)
+            .max()
+            .unwrap_or(len);
+        let dropped_values = self.values.split_off(&new_len);
+        let mut dropped_accessors = 0;
+        self.descriptors.retain(|index, _| {
+            if *index < new_len {
+                return true;
+            }
+            if !dropped_values.contains_key(index) {
+                dropped_accessors += 1;
+            }
+            false
+        });
+        self.element_count -= dropped_values.len() as u32 + dropped_accessors;
+        new_len
+    }
+}
+
+impl HeapMarkAndSweep for SparseElementArray {
+    fn mark_values(&self, queues: &mut WorkQueues) {
+        self.values
+            .values()
+            .for_each(|value| value.mark_values(queues));
+        self.descriptors
+            .values()
+            .for_each(|descriptor| descriptor.mark_values(queues));
+    }
+
+    fn sweep_values(&mut self, compactions: &CompactionLists) {
+        self.values
+            .values_mut()
+            .for_each(|value| value.sweep_values(compactions));
+        self.descriptors
+            .values_mut()
+            .for_each(|descriptor| descriptor.sweep_values(compactions));
+    }
+}
+
+impl SnapshotData for SparseElementArray {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self {
+            values,
+            descriptors,
+            element_count: _,
+        } = self;
+        values.serialize(writer)?;
+        descriptors.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let values = SnapshotData::deserialize(reader)?;
+        let descriptors = SnapshotData::deserialize(reader)?;
+        Ok(Self::new(values, descriptors))
+    }
+}
+
+/// Elements stored as raw doubles, for element vectors holding only numbers.
+/// All elements are writable, enumerable and configurable data properties.
+/// Holes are marked with a reserved NaN bit pattern, and all elements past the
+/// end of the values are holes.
+#[derive(Debug, Default, Clone)]
+pub struct Float64ElementArray {
+    pub values: Vec<f64>,
+    /// Heap numbers boxed for elements that do not fit a stack-allocated
+    /// number, so that reading such an element again does not allocate. The
+    /// numbers are not kept alive by the cache: Garbage collection clears it.
+    boxed: AHashMap<u32, HeapNumber<'static>>,
+}
+
+impl Float64ElementArray {
+    pub fn get(&self, index: u32) -> Option<f64> {
+        let value = *self.values.get(index as usize)?;
+        (value.to_bits() != FLOAT64_HOLE).then_some(value)
+    }
+
+    pub fn set(&mut self, index: u32, value: Option<f64>) {
+        let index = index as usize;
+        let value = match value {
+            // Canonicalise NaNs so that no value is mistaken for a hole.
+            Some(value) if value.is_nan() => f64::NAN,
+            Some(value) => value,
+            None if index >= self.values.len() => return,
+            None => f64::from_bits(FLOAT64_HOLE),
+        };
+        if !self.boxed.is_empty() {
+            self.boxed.remove(&(index as u32));
+        }
+        if index >= self.values.len() {
+            self.values.resize(index + 1, f64::from_bits(FLOAT64_HOLE));
+        }
+        self.values[index] = value;
+    }
+
+    /// Indexes of the elements present in ascending order.
+    pub fn keys(&self) -> Vec<u32> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.to_bits() != FLOAT64_HOLE)
+            .map(|(index, _)| index as u32)
+            .collect()
+    }
+
+    /// Remove the element at `index`, shifting all following elements down
+    /// by one.
+    pub fn remove(&mut self, index: u32) {
+        if (index as usize) < self.values.len() {
+            self.values_mut().remove(index as usize);
+        }
+    }
+
+    /// Drop all elements at or past `len`.
+    pub fn truncate(&mut self, len: u32) {
+        self.values_mut().truncate(len as usize);
+    }
+
+    /// Get the values for modification. Values written here must not be
+    /// holes or non-canonical NaNs unless they are meant as holes.
+    pub fn values_mut(&mut self) -> &mut Vec<f64> {
+        self.boxed.clear();
+        &mut self.values
+    }
+
+    fn get_boxed(&self, index: u32) -> Option<HeapNumber<'static>> {
+        self.boxed.get(&index).copied()
+    }
+
+    fn set_boxed(&mut self, index: u32, number: HeapNumber) {
+        self.boxed.insert(index, number.unbind());
+    }
+}
+
+impl HeapMarkAndSweep for Float64ElementArray {
+    fn mark_values(&self, _queues: &mut WorkQueues) {}
+
+    fn sweep_values(&mut self, _compactions: &CompactionLists) {
+        // The boxed numbers were not marked through the cache.
+        self.boxed.clear();
+    }
+}
+
+impl SnapshotData for Float64ElementArray {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Self { values, boxed: _ } = self;
+        values.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Self {
+            values: SnapshotData::deserialize(reader)?,
+            boxed: Default::default(),
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct ElementArrays {
     /// up to 16 elements
@@ -1132,6 +1610,10 @@ pub struct ElementArrays {
     pub e2pow24: ElementArray2Pow24,
     /// up to 4294967296 elements
     pub e2pow32: ElementArray2Pow32,
+    /// any number of elements, stored sparsely
+    pub sparse: Vec<Option<SparseElementArray>>,
+    /// any number of numbers, stored as raw doubles
+    pub float64: Vec<Option<Float64ElementArray>>,
 }
 
 impl Index<ElementsVector> for ElementArrays {
@@ -1527,6 +2009,7 @@ impl ElementArrays {
                 }
                 index
             }
+            ElementArrayKey::Sparse | ElementArrayKey::Float64 => unreachable!(),
         }
     }
 
@@ -1552,6 +2035,8 @@ impl ElementArrays {
             e2pow16,
             e2pow24,
             e2pow32,
+            sparse: _,
+            float64: _,
         } = self;
         debug_assert_eq!(
             std::mem::size_of::<Option<[Option<Value>; 1]>>(),
@@ -1842,6 +2327,7 @@ impl ElementArrays {
                 }
                 index
             }
+            ElementArrayKey::Sparse | ElementArrayKey::Float64 => unreachable!(),
         };
         elements_vector.cap = new_key;
         elements_vector.elements_index = new_index;
@@ -1856,6 +2342,26 @@ impl ElementArrays {
         }
     }
 
+    /// Allocates double storage for a vector of `len` holes.
+    pub(crate) fn allocate_float64_elements(&mut self, len: u32) -> ElementsVector {
+        self.float64.push(Some(Float64ElementArray::default()));
+        ElementsVector {
+            elements_index: ElementIndex::last_element_index(&self.float64),
+            cap: ElementArrayKey::Float64,
+            len,
+        }
+    }
+
+    /// Allocates sparse storage for a vector of `len` holes.
+    pub(crate) fn allocate_sparse_elements(&mut self, len: u32) -> ElementsVector {
+        self.sparse.push(Some(SparseElementArray::default()));
+        ElementsVector {
+            elements_index: ElementIndex::last_element_index(&self.sparse),
+            cap: ElementArrayKey::Sparse,
+            len,
+        }
+    }
+
     pub(crate) fn create_with_stuff(
         &mut self,
         mut entries: Vec<(
@@ -1906,25 +2412,18 @@ impl ElementArrays {
         )
     }
 
-    pub(crate) fn create_object_entries(
+    /// Creates the values vector of an object with the given entries. The
+    /// keys of the entries belong in the object's shape.
+    pub(crate) fn create_object_entry_values(
         &mut self,
         entries: &[ObjectEntry<'_>],
-    ) -> (ElementsVector, ElementsVector) {
+    ) -> ElementsVector {
         let length = entries.len();
-        let mut keys: Vec<Option<Value>> = Vec::with_capacity(length);
         let mut values: Vec<Option<Value>> = Vec::with_capacity(length);
         let mut descriptors: Option<AHashMap<u32, ElementDescriptor>> = None;
         for (index, entry) in entries.iter().enumerate() {
-            let ObjectEntry { key, value } = entry;
             let (maybe_descriptor, maybe_value) =
-                ElementDescriptor::from_object_entry_property_descriptor(value);
-            let key = match *key {
-                PropertyKey::Integer(data) => Value::Integer(data),
-                PropertyKey::SmallString(data) => Value::SmallString(data),
-                PropertyKey::String(data) => Value::String(data.unbind()),
-                PropertyKey::Symbol(data) => Value::Symbol(data.unbind()),
-            };
-            keys.push(Some(key));
+                ElementDescriptor::from_object_entry_property_descriptor(&entry.value);
             values.push(maybe_value);
             if let Some(descriptor) = maybe_descriptor {
                 if descriptors.is_none() {
@@ -1938,25 +2437,19 @@ impl ElementArrays {
         }
         let cap = ElementArrayKey::from(length);
         let len = length as u32;
-        let key_elements_index = self.push_with_key(cap, &keys, None);
         let value_elements_index = self.push_with_key(cap, &values, descriptors);
-        (
-            ElementsVector {
-                elements_index: key_elements_index,
-                cap,
-                len,
-            },
-            ElementsVector {
-                elements_index: value_elements_index,
-                cap,
-                len,
-            },
-        )
+        ElementsVector {
+            elements_index: value_elements_index,
+            cap,
+            len,
+        }
     }
 
     pub fn get(&self, vector: ElementsVector) -> &[Option<Value>] {
         match vector.cap {
             ElementArrayKey::Empty => &[],
+            ElementArrayKey::Sparse => unreachable!("Sparse elements have no slice"),
+            ElementArrayKey::Float64 => unreachable!("Double elements have no slice"),
             ElementArrayKey::E4 => {
                 &self.e2pow4.values[vector.elements_index].as_slice()[0..vector.len as usize]
             }
@@ -1987,6 +2480,8 @@ impl ElementArrays {
     pub fn get_mut(&mut self, vector: ElementsVector) -> &mut [Option<Value>] {
         match vector.cap {
             ElementArrayKey::Empty => &mut [],
+            ElementArrayKey::Sparse => unreachable!("Sparse elements have no slice"),
+            ElementArrayKey::Float64 => unreachable!("Double elements have no slice"),
             ElementArrayKey::E4 => &mut self.e2pow4.values[vector.elements_index].as_mut_slice()
                 [0..vector.len as usize],
             ElementArrayKey::E6 => &mut self.e2pow6.values[vector.elements_index].as_mut_slice()
@@ -2013,6 +2508,8 @@ impl ElementArrays {
         let usize_index = vector.elements_index.into_index();
         match vector.cap {
             ElementArrayKey::Empty => (None, &[]),
+            ElementArrayKey::Sparse => unreachable!("Sparse elements have no slice"),
+            ElementArrayKey::Float64 => unreachable!("Double elements have no slice"),
             ElementArrayKey::E4 => {
                 let epow = &self.e2pow4;
                 (
@@ -2130,6 +2627,8 @@ impl ElementArrays {
         let usize_index = vector.elements_index.into_index();
         match vector.cap {
             ElementArrayKey::Empty => (None, &mut []),
+            ElementArrayKey::Sparse => unreachable!("Sparse elements have no slice"),
+            ElementArrayKey::Float64 => unreachable!("Double elements have no slice"),
             ElementArrayKey::E4 => {
                 let epow = &mut self.e2pow4;
                 (
@@ -2246,7 +2745,10 @@ impl ElementArrays {
             return None;
         };
         let descriptors = match vector.cap {
-            ElementArrayKey::Empty => return None,
+            ElementArrayKey::Empty | ElementArrayKey::Float64 => return None,
+            ElementArrayKey::Sparse => {
+                return self.sparse[vector.elements_index].get_descriptor(index);
+            }
             ElementArrayKey::E4 => &self.e2pow4.descriptors,
             ElementArrayKey::E6 => &self.e2pow6.descriptors,
             ElementArrayKey::E8 => &self.e2pow8.descriptors,
@@ -2272,6 +2774,14 @@ impl ElementArrays {
         assert!(index < vector.len);
         let descriptors = match vector.cap {
             ElementArrayKey::Empty => unreachable!(),
+            ElementArrayKey::Sparse => {
+                self.sparse[vector.elements_index].set_descriptor(index, descriptor);
+                return;
+            }
+            ElementArrayKey::Float64 => {
+                assert!(descriptor.is_none(), "Double elements have no descriptors");
+                return;
+            }
             ElementArrayKey::E4 => &mut self.e2pow4.descriptors,
             ElementArrayKey::E6 => &mut self.e2pow6.descriptors,
             ElementArrayKey::E8 => &mut self.e2pow8.descriptors,
@@ -2297,6 +2807,21 @@ impl ElementArrays {
     pub fn has(&self, vector: ElementsVector, element: Value) -> bool {
         match vector.cap {
             ElementArrayKey::Empty => false,
+            ElementArrayKey::Sparse => self.sparse[vector.elements_index]
+                .values()
+                .any(|value| *value == element),
+            ElementArrayKey::Float64 => {
+                // Heap numbers are never identical to a double element.
+                let element = match element {
+                    Value::Integer(element) => element.into_i64() as f64,
+                    Value::SmallF64(element) => element.into_f64(),
+                    _ => return false,
+                };
+                let values = &self.float64[vector.elements_index].values;
+                values[..values.len().min(vector.len as usize)]
+                    .iter()
+                    .any(|value| value.to_bits() == element.to_bits())
+            }
             ElementArrayKey::E4 => self.e2pow4.values[vector.elements_index].as_slice()
                 [0..vector.len as usize]
                 .contains(&Some(element)),
@@ -2324,6 +2849,143 @@ impl ElementArrays {
         }
     }
 
+    /// Get the value of an element. Holes and accessor properties have no
+    /// value. Double elements are read through [`Heap::get_element_value`].
+    pub fn get_value(&self, vector: ElementsVector, index: u32) -> Option<Value> {
+        if vector.is_sparse() {
+            self.sparse[vector.elements_index].get(index)
+        } else if vector.is_float64() {
+            unreachable!("Double elements must be read through Heap::get_element_value")
+        } else {
+            self.get(vector).get(index as usize).copied().flatten()
+        }
+    }
+
+    /// Set the value of an element within the vector's length. Double
+    /// elements can only be emptied here and are otherwise set through
+    /// [`ElementArrays::set_f64`].
+    pub fn set_value(&mut self, vector: ElementsVector, index: u32, value: Option<Value>) {
+        assert!(index < vector.len);
+        if vector.is_sparse() {
+            self.sparse[vector.elements_index].set_value(index, value);
+        } else if vector.is_float64() {
+            assert!(value.is_none(), "Double elements are set through set_f64");
+            self.float64[vector.elements_index].set(index, None);
+        } else {
+            self.get_mut(vector)[index as usize] = value;
+        }
+    }
+
+    /// Get the double of an element of a double vector. Holes have no value.
+    pub(crate) fn get_f64(&self, vector: ElementsVector, index: u32) -> Option<f64> {
+        debug_assert!(vector.is_float64());
+        if index >= vector.len {
+            return None;
+        }
+        self.float64[vector.elements_index].get(index)
+    }
+
+    /// Set the double of an element of a double vector within the vector's
+    /// length.
+    pub(crate) fn set_f64(&mut self, vector: ElementsVector, index: u32, value: Option<f64>) {
+        debug_assert!(vector.is_float64());
+        assert!(index < vector.len);
+        self.float64[vector.elements_index].set(index, value);
+    }
+
+    /// Get the doubles of a double vector if it has no holes.
+    pub(crate) fn get_f64_slice(&self, vector: ElementsVector) -> Option<&[f64]> {
+        debug_assert!(vector.is_float64());
+        let values = self.float64[vector.elements_index]
+            .values
+            .get(..vector.len as usize)?;
+        values
+            .iter()
+            .all(|value| value.to_bits() != FLOAT64_HOLE)
+            .then_some(values)
+    }
+
+    /// Get the doubles of a double vector for modification, padded with
+    /// holes up to the vector's length. Holes may be moved around but NaNs
+    /// written into the slice must be canonical.
+    pub(crate) fn get_f64_slice_mut(&mut self, vector: ElementsVector) -> &mut [f64] {
+        debug_assert!(vector.is_float64());
+        let values = self.float64[vector.elements_index].values_mut();
+        values.resize(vector.len as usize, f64::from_bits(FLOAT64_HOLE));
+        values
+    }
+
+    /// Move the elements of a dense vector into sparse storage.
+    fn make_sparse(&mut self, elements_vector: &mut ElementsVector) {
+        debug_assert!(elements_vector.has_slice());
+        let (descriptors, slice) = self.get_descriptors_and_slice(*elements_vector);
+        let sparse = SparseElementArray::new(
+            slice
+                .iter()
+                .enumerate()
+                .filter_map(|(index, value)| Some((index as u32, (*value)?)))
+                .collect(),
+            descriptors.cloned().unwrap_or_default(),
+        );
+        self.sparse.push(Some(sparse));
+        elements_vector.cap = ElementArrayKey::Sparse;
+        elements_vector.elements_index = ElementIndex::last_element_index(&self.sparse);
+    }
+
+    /// Move the elements of a sparse vector back into a dense backing store.
+    fn make_dense(&mut self, elements_vector: &mut ElementsVector) {
+        debug_assert!(elements_vector.is_sparse());
+        let sparse = std::mem::take(&mut self.sparse[elements_vector.elements_index]);
+        let mut values = vec![None; elements_vector.len as usize];
+        for (index, value) in sparse.values {
+            values[index as usize] = Some(value);
+        }
+        let descriptors = if sparse.descriptors.is_empty() {
+            None
+        } else {
+            Some(sparse.descriptors)
+        };
+        let cap = ElementArrayKey::from(elements_vector.len);
+        elements_vector.elements_index = self.push_with_key(cap, &values, descriptors);
+        elements_vector.cap = cap;
+    }
+
+    /// Read the element arrays of one size from a startup snapshot, keeping
+    /// their indexes.
+    fn deserialize_element_array(
+        &mut self,
+        key: ElementArrayKey,
+        reader: &mut SnapshotReader,
+    ) -> SnapshotResult<()> {
+        let len = usize::deserialize(reader)?;
+        for _ in 0..len {
+            // The garbage collection before snapshot creation compacts the
+            // element arrays, so a snapshot never contains holes.
+            let Some(values) = Option::<Vec<Option<Value>>>::deserialize(reader)? else {
+                return Err(SnapshotError::Malformed);
+            };
+            if values.len() > key.cap() as usize {
+                return Err(SnapshotError::Malformed);
+            }
+            self.push_with_key(key, &values, None);
+        }
+        let descriptors = SnapshotData::deserialize(reader)?;
+        match key {
+            ElementArrayKey::Empty | ElementArrayKey::Sparse | ElementArrayKey::Float64 => {
+                unreachable!()
+            }
+            ElementArrayKey::E4 => self.e2pow4.descriptors = descriptors,
+            ElementArrayKey::E6 => self.e2pow6.descriptors = descriptors,
+            ElementArrayKey::E8 => self.e2pow8.descriptors = descriptors,
+            ElementArrayKey::E10 => self.e2pow10.descriptors = descriptors,
+            ElementArrayKey::E12 => self.e2pow12.descriptors = descriptors,
+            ElementArrayKey::E16 => self.e2pow16.descriptors = descriptors,
+            ElementArrayKey::E24 => self.e2pow24.descriptors = descriptors,
+            ElementArrayKey::E32 => self.e2pow32.descriptors = descriptors,
+        }
+        Ok(())
+    }
+
     /// This method creates a "shallow clone" of the elements of a trivial/dense array.
     /// It does not do anything with descriptors and assumes there is a previous validation in place.
     pub fn shallow_clone(&mut self, elements_vector: ElementsVector) -> SealableElementsVector {
@@ -2337,9 +2999,19 @@ impl ElementArrays {
             e2pow16,
             e2pow24,
             e2pow32,
+            sparse,
+            float64,
         } = self;
         let new_index = match elements_vector.cap {
             ElementArrayKey::Empty => ElementIndex::from_u32_index(0),
+            ElementArrayKey::Sparse => {
+                sparse.extend_from_within(index..index + 1);
+                ElementIndex::last_element_index(sparse)
+            }
+            ElementArrayKey::Float64 => {
+                float64.extend_from_within(index..index + 1);
+                ElementIndex::last_element_index(float64)
+            }
             ElementArrayKey::E4 => {
                 let elements = e2pow4;
                 elements.values.extend_from_within(index..index + 1);
@@ -2457,8 +3129,465 @@ impl HeapMarkAndSweep for ElementDescriptor {
     }
 }
 
+impl SnapshotData for ElementDescriptor {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let PropertyDescriptor {
+            value: _,
+            writable,
+            get,
+            set,
+            enumerable,
+            configurable,
+        } = ElementDescriptor::to_property_descriptor(Some(*self), None);
+        writable.serialize(writer)?;
+        get.serialize(writer)?;
+        set.serialize(writer)?;
+        enumerable.serialize(writer)?;
+        configurable.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let descriptor = PropertyDescriptor {
+            value: None,
+            writable: SnapshotData::deserialize(reader)?,
+            get: SnapshotData::deserialize(reader)?,
+            set: SnapshotData::deserialize(reader)?,
+            enumerable: SnapshotData::deserialize(reader)?,
+            configurable: SnapshotData::deserialize(reader)?,
+        };
+        Ok(ElementDescriptor::from_property_descriptor(descriptor)
+            .unwrap_or(ElementDescriptor::WritableEnumerableConfigurableData))
+    }
+}
+
+/// Serialize the element arrays of one size. Trailing empty slots of each
+/// element array are not written.
+fn serialize_element_array<const N: usize>(
+    values: &[Option<[Option<Value>; N]>],
+    descriptors: &AHashMap<ElementIndex, AHashMap<u32, ElementDescriptor>>,
+    writer: &mut SnapshotWriter,
+) -> SnapshotResult<()> {
+    values.len().serialize(writer)?;
+    for slot in values {
+        let slot = slot.as_ref().map(|slot| {
+            let len = slot
+                .iter()
+                .rposition(Option::is_some)
+                .map_or(0, |index| index + 1);
+            slot[..len].to_vec()
+        });
+        slot.serialize(writer)?;
+    }
+    descriptors.serialize(writer)
+}
+
+impl SnapshotData for ElementArrays {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let ElementArrays {
+            e2pow4,
+            e2pow6,
+            e2pow8,
+            e2pow10,
+            e2pow12,
+            e2pow16,
+            e2pow24,
+            e2pow32,
+            sparse,
+            float64,
+        } = self;
+        serialize_element_array(&e2pow4.values, &e2pow4.descriptors, writer)?;
+        serialize_element_array(&e2pow6.values, &e2pow6.descriptors, writer)?;
+        serialize_element_array(&e2pow8.values, &e2pow8.descriptors, writer)?;
+        serialize_element_array(&e2pow10.values, &e2pow10.descriptors, writer)?;
+        serialize_element_array(&e2pow12.values, &e2pow12.descriptors, writer)?;
+        serialize_element_array(&e2pow16.values, &e2pow16.descriptors, writer)?;
+        serialize_element_array(&e2pow24.values, &e2pow24.descriptors, writer)?;
+        serialize_element_array(&e2pow32.values, &e2pow32.descriptors, writer)?;
+        sparse.serialize(writer)?;
+        float64.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let mut elements = ElementArrays {
+            e2pow4: Default::default(),
+            e2pow6: Default::default(),
+            e2pow8: Default::default(),
+            e2pow10: Default::default(),
+            e2pow12: Default::default(),
+            e2pow16: Default::default(),
+            e2pow24: Default::default(),
+            e2pow32: Default::default(),
+            sparse: Default::default(),
+            float64: Default::default(),
+        };
+        for key in [
+            ElementArrayKey::E4,
+            ElementArrayKey::E6,
+            ElementArrayKey::E8,
+            ElementArrayKey::E10,
+            ElementArrayKey::E12,
+            ElementArrayKey::E16,
+            ElementArrayKey::E24,
+            ElementArrayKey::E32,
+        ] {
+            elements.deserialize_element_array(key, reader)?;
+        }
+        elements.sparse = SnapshotData::deserialize(reader)?;
+        elements.float64 = SnapshotData::deserialize(reader)?;
+        Ok(elements)
+    }
+}
+
+impl Heap {
+    /// Get the value of an element. Holes and accessor properties have no
+    /// value. Double elements not representable as stack-allocated numbers
+    /// are boxed into heap numbers, which are reused for later reads of the
+    /// same element until the next garbage collection.
+    pub(crate) fn get_element_value(
+        &mut self,
+        vector: ElementsVector,
+        index: u32,
+    ) -> Option<Value> {
+        if !vector.is_float64() {
+            return self.elements.get_value(vector, index);
+        }
+        let value = self.elements.get_f64(vector, index)?;
+        if let Ok(value) = Value::try_from(value) {
+            return Some(value);
+        }
+        let doubles = &self.elements.float64[vector.elements_index];
+        if let Some(number) = doubles.get_boxed(index) {
+            return Some(Value::Number(number));
+        }
+        // SAFETY: The double was not representable as a stack-allocated
+        // number.
+        let number = unsafe { self.alloc_number(value) };
+        self.elements.float64[vector.elements_index].set_boxed(index, number);
+        Some(Value::Number(number))
+    }
+
+    /// Move the elements of a double vector into a generic dense backing
+    /// store. This is done before anything but a number is stored in the
+    /// elements, or before they grow sparse.
+    pub(crate) fn make_elements_generic(&mut self, vector: &mut ElementsVector) {
+        debug_assert!(vector.is_float64());
+        let doubles = std::mem::take(&mut self.elements.float64[vector.elements_index]);
+        let mut values = vec![None; vector.len as usize];
+        for (slot, value) in values.iter_mut().zip(doubles.values) {
+            if value.to_bits() != FLOAT64_HOLE {
+                *slot = Some(self.box_f64_element(value));
+            }
+        }
+        let cap = ElementArrayKey::from(vector.len);
+        vector.elements_index = self.elements.push_with_key(cap, &values, None);
+        vector.cap = cap;
+    }
+
+    fn box_f64_element(&mut self, value: f64) -> Value {
+        Value::try_from(value).unwrap_or_else(|_| {
+            // SAFETY: The double was not representable as a stack-allocated
+            // number.
+            Value::Number(unsafe { self.alloc_number(value) })
+        })
+    }
+}
+
 impl AsRef<ElementArrays> for Agent {
     fn as_ref(&self) -> &ElementArrays {
         &self.heap.elements
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        ElementDescriptor, ElementsVector, Float64ElementArray, SparseElementArray, FLOAT64_HOLE,
+    };
+    use crate::{
+        ecmascript::types::Value,
+        test_utils::{eval_script, TestAgent},
+    };
+
+    fn elements_of(agent: &mut TestAgent, source_text: &str) -> ElementsVector {
+        agent.run(|agent, gc| {
+            let Value::Array(array) = eval_script(agent, source_text, gc).unwrap() else {
+                panic!("Expected an array");
+            };
+            agent[array].elements.into()
+        })
+    }
+
+    fn sparse_of(agent: &mut TestAgent, source_text: &str) -> (bool, u32) {
+        let elements = elements_of(agent, source_text);
+        (elements.is_sparse(), elements.len())
+    }
+
+    #[test]
+    fn float64_holes_and_nans() {
+        let mut doubles = Float64ElementArray::default();
+        doubles.set(2, Some(0.5));
+        assert_eq!(doubles.get(0), None);
+        assert_eq!(doubles.get(2), Some(0.5));
+        assert_eq!(doubles.get(3), None);
+        // A NaN with the hole's bit pattern is canonicalised.
+        doubles.set(0, Some(f64::from_bits(FLOAT64_HOLE)));
+        assert!(doubles.get(0).unwrap().is_nan());
+        doubles.set(1, Some(-0.0));
+        assert!(doubles.get(1).unwrap().is_sign_negative());
+        assert_eq!(doubles.keys(), [0, 1, 2]);
+        // Holes past the end are not stored.
+        doubles.set(10, None);
+        assert_eq!(doubles.values.len(), 3);
+        doubles.set(1, None);
+        assert_eq!(doubles.keys(), [0, 2]);
+        doubles.remove(0);
+        assert_eq!(doubles.keys(), [1]);
+        doubles.truncate(1);
+        assert!(doubles.keys().is_empty());
+    }
+
+    #[test]
+    fn numeric_arrays_stay_double() {
+        let mut agent = TestAgent::new();
+        let source_text =
+            "var a = new Array(100).fill(0.5); a[1] = 1e300; a[2] = -0; a[3] = NaN; a";
+        let elements = elements_of(&mut agent, source_text);
+        assert!(elements.is_float64());
+        assert_eq!(elements.len(), 100);
+        assert_eq!(
+            agent.eval("a.slice(0, 3).reduce((sum, x) => sum + x, 0) === 1e300"),
+            "true"
+        );
+        assert_eq!(
+            agent.eval("Number.isNaN(a[3]) && Object.is(a[2], -0)"),
+            "true"
+        );
+        assert_eq!(agent.eval("a.includes(NaN) && a.indexOf(0.5)"), "0");
+        assert!(elements_of(&mut agent, "a.length = 50; a").is_float64());
+        assert_eq!(
+            agent.eval("a.length + ',' + (60 in a) + ',' + a[49]"),
+            "50,false,0.5"
+        );
+    }
+
+    #[test]
+    fn double_arrays_survive_garbage_collection() {
+        let mut agent = TestAgent::new();
+        agent.eval(
+            "var garbage = [];
+            for (let i = 0; i < 10; i++) garbage.push(new Array(10).fill(i));
+            var kept = new Array(10).fill(0.25);
+            kept[5] = 1e300;
+            delete kept[6];
+            garbage = undefined;",
+        );
+        agent.gc();
+        assert!(elements_of(&mut agent, "kept").is_float64());
+        assert_eq!(
+            agent.eval("kept[0] + ',' + kept[5] + ',' + (6 in kept) + ',' + kept.length"),
+            "0.25,1e+300,false,10"
+        );
+    }
+
+    #[test]
+    fn boxed_double_reads_are_cached() {
+        let mut agent = TestAgent::new();
+        agent.eval("var a = new Array(3).fill(0.5); a[1] = 1e300; a[1];");
+        let numbers = agent.run(|agent, _| agent.heap.numbers.len());
+        agent.eval("for (let i = 0; i < 100; i++) a[1];");
+        assert_eq!(agent.run(|agent, _| agent.heap.numbers.len()), numbers);
+        assert_eq!(
+            agent.eval("a[1] = 2e300; a[1] + ',' + a[1]"),
+            "2e+300,2e+300"
+        );
+        agent.gc();
+        assert_eq!(agent.eval("a[1]"), "2e+300");
+    }
+
+    #[test]
+    fn double_arrays_take_fast_paths() {
+        let mut agent = TestAgent::new();
+        agent.eval("var a = new Array(4).fill(1.5); a[1] = 1e300; a[2] = NaN; a[3] = -0;");
+        assert_eq!(
+            agent.eval("[a.includes(NaN), a.includes(0), a.includes('1.5')].join()"),
+            "true,true,false"
+        );
+        assert_eq!(
+            agent.eval(
+                "[a.indexOf(NaN), a.indexOf(0), a.indexOf(1e300, 2), a.lastIndexOf(1.5)].join()"
+            ),
+            "-1,3,-1,0"
+        );
+        assert!(elements_of(&mut agent, "a.reverse()").is_float64());
+        assert_eq!(agent.eval("a.join()"), "0,NaN,1e+300,1.5");
+        assert!(elements_of(&mut agent, "a.toReversed()").is_float64());
+        assert!(elements_of(&mut agent, "a.with(-1, 2.5)").is_float64());
+        assert_eq!(agent.eval("a.with(-1, 2.5).join()"), "0,NaN,1e+300,2.5");
+        assert!(elements_of(&mut agent, "a.copyWithin(0, 2); a").is_float64());
+        assert!(elements_of(&mut agent, "a.fill(0.5, 1, 2); a").is_float64());
+        assert_eq!(agent.eval("a.join()"), "1e+300,0.5,1e+300,1.5");
+        assert_eq!(
+            agent.eval("[a.pop(), a.shift(), a.unshift(1, 2.5), a].join(';')"),
+            "1.5;1e+300;4;1,2.5,0.5,1e+300"
+        );
+        assert!(elements_of(&mut agent, "a").is_float64());
+        // Anything but a number makes the elements generic.
+        assert!(!elements_of(&mut agent, "a.unshift('x'); a").is_float64());
+        assert_eq!(agent.eval("a.join()"), "x,1,2.5,0.5,1e+300");
+    }
+
+    #[test]
+    fn double_arrays_keep_holes() {
+        let mut agent = TestAgent::new();
+        let elements = elements_of(&mut agent, "var b = new Array(3); b[1] = 1.5; b");
+        assert!(elements.is_float64());
+        assert_eq!(agent.eval("Object.keys(b).join()"), "1");
+        assert_eq!(agent.eval("Array.prototype[0] = 'proto'; b[0]"), "proto");
+        assert_eq!(
+            agent.eval("delete b[1]; (1 in b) + ',' + b.length"),
+            "false,3"
+        );
+    }
+
+    #[test]
+    fn double_arrays_become_generic() {
+        let mut agent = TestAgent::new();
+        agent.eval("var a = new Array(10).fill(0.5); a[3] = 1.5;");
+        let elements = elements_of(&mut agent, "a[4] = 'x'; a");
+        assert!(!elements.is_float64() && !elements.is_sparse());
+        assert_eq!(
+            agent.eval("a[3] + a[4] + a[5] + ',' + a.length"),
+            "1.5x0.5,10"
+        );
+        // Non-default attributes need descriptors.
+        agent.eval("var b = new Array(10).fill(1.5);");
+        let elements = elements_of(
+            &mut agent,
+            "Object.defineProperty(b, 2, { value: 2.5, writable: false }); b",
+        );
+        assert!(!elements.is_float64());
+        assert_eq!(agent.eval("b[2] = 0; b[1] + b[2]"), "4");
+        // Double elements cannot be stored sparsely.
+        agent.eval("var c = new Array(10).fill(2.5);");
+        let elements = elements_of(&mut agent, "c[2 ** 31] = 3.5; c");
+        assert!(elements.is_sparse());
+        assert_eq!(
+            agent.eval("c[9] + c[2 ** 31] + ',' + c.length"),
+            "6,2147483649"
+        );
+    }
+
+    #[test]
+    fn sparse_element_count_is_maintained() {
+        let mut sparse = SparseElementArray::default();
+        sparse.set_value(7, Some(Value::Null));
+        sparse.set_value(7, Some(Value::Undefined));
+        assert_eq!(sparse.element_count(), 1);
+        // Accessors have a descriptor but no value: They count too.
+        sparse.set_descriptor(
+            1_000_000,
+            Some(ElementDescriptor::WritableUnenumerableConfigurableData),
+        );
+        sparse.set_descriptor(
+            7,
+            Some(ElementDescriptor::ReadOnlyEnumerableConfigurableData),
+        );
+        assert_eq!(sparse.element_count(), 2);
+        sparse.set_value(7, None);
+        assert_eq!(sparse.element_count(), 2);
+        sparse.set_descriptor(7, None);
+        assert_eq!(sparse.element_count(), 1);
+        sparse.set_value(3, Some(Value::Null));
+        sparse.remove(3);
+        assert_eq!(sparse.element_count(), 1);
+        assert_eq!(sparse.keys(), [999_999]);
+        assert_eq!(sparse.truncate(0), 0);
+        assert_eq!(sparse.element_count(), 0);
+    }
+
+    #[test]
+    fn sparse_truncation_stops_at_non_configurable_element() {
+        let mut sparse = SparseElementArray::default();
+        for index in [1, 10, 100, u32::MAX - 1] {
+            sparse.set_value(index, Some(Value::Null));
+        }
+        sparse.set_descriptor(
+            10,
+            Some(ElementDescriptor::WritableEnumerableUnconfigurableData),
+        );
+        sparse.set_descriptor(
+            100,
+            Some(ElementDescriptor::ReadOnlyEnumerableConfigurableData),
+        );
+        assert_eq!(sparse.truncate(5), 11);
+        assert_eq!(sparse.keys(), [1, 10]);
+        assert_eq!(sparse.element_count(), 2);
+        assert_eq!(sparse.truncate(11), 11);
+        assert_eq!(sparse.element_count(), 2);
+    }
+
+    #[test]
+    fn huge_arrays_are_sparse() {
+        let mut agent = TestAgent::new();
+        assert_eq!(
+            sparse_of(&mut agent, "new Array(2 ** 32 - 1)"),
+            (true, u32::MAX)
+        );
+        assert_eq!(
+            sparse_of(&mut agent, "var a = []; a[4294967294] = 1; a"),
+            (true, u32::MAX)
+        );
+        assert_eq!(agent.eval("a[4294967294] + a.length"), "4294967296");
+        assert_eq!(sparse_of(&mut agent, "[1, 2, 3]"), (false, 3));
+    }
+
+    #[test]
+    fn sparse_arrays_keep_holes() {
+        let mut agent = TestAgent::new();
+        agent.eval("var a = []; a[100000] = 'last'; a[5] = 'first'; a[6] = undefined;");
+        assert_eq!(
+            agent.eval("[0 in a, 5 in a, 6 in a, 7 in a, 100000 in a].join()"),
+            "false,true,true,false,true"
+        );
+        assert_eq!(agent.eval("Object.keys(a).join()"), "5,6,100000");
+        assert_eq!(agent.eval("var n = 0; a.forEach(() => n++); n"), "3");
+        assert_eq!(agent.eval("a.shift(); Object.keys(a).join()"), "4,5,99999");
+        assert_eq!(agent.eval("a[99999]"), "last");
+    }
+
+    #[test]
+    fn truncating_sparse_arrays() {
+        let mut agent = TestAgent::new();
+        agent.eval("var a = []; a[4294967294] = 1; a[3] = 2;");
+        assert_eq!(agent.eval("Object.keys(a).join()"), "3,4294967294");
+        // Few enough elements remain to move them back to a dense store.
+        assert_eq!(sparse_of(&mut agent, "a.length = 4; a"), (false, 4));
+        assert_eq!(
+            agent.eval("[a[3], 4294967294 in a, Object.keys(a).join()].join()"),
+            "2,false,3"
+        );
+        assert_eq!(agent.eval("a.length = 0; a[3]"), "undefined");
+    }
+
+    #[test]
+    fn truncating_sparse_arrays_keeps_non_configurable_elements() {
+        let mut agent = TestAgent::new();
+        agent.eval(
+            "var a = []; a[4294967294] = 1; a[7] = 2;
+            Object.defineProperty(a, 1000000, { value: 3, configurable: false }).length",
+        );
+        assert_eq!(agent.eval("a.length = 0; a.length"), "1000001");
+        assert_eq!(
+            agent.eval("[a[7], a[1000000], 4294967294 in a].join()"),
+            "2,3,false"
+        );
+        assert!(agent
+            .eval_err("(function () { 'use strict'; a.length = 0; })()")
+            .starts_with("TypeError: Could not set property 'length'"));
+        assert_eq!(
+            agent.eval_err("Object.defineProperty(a, 'length', { value: 0 });"),
+            "TypeError: Failed to defined property on object"
+        );
+        assert_eq!(agent.eval("a.length"), "1000001");
+    }
+}
@@ -1,4 +1,8 @@
-use std::{hash::Hash, num::NonZeroU32};
+use std::{
+    hash::Hash,
+    num::NonZeroU32,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use ahash::AHashMap;
 use hashbrown::HashTable;
@@ -17,6 +21,8 @@ use super::{
 use crate::ecmascript::builtins::date::Date;
 #[cfg(feature = "regexp")]
 use crate::ecmascript::builtins::regexp::RegExp;
+#[cfg(feature = "regexp")]
+use crate::ecmascript::builtins::text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIterator;
 #[cfg(feature = "shared-array-buffer")]
 use crate::ecmascript::builtins::shared_array_buffer::SharedArrayBuffer;
 #[cfg(feature = "array-buffer")]
@@ -48,7 +54,8 @@ use crate::ecmascript::{
         module::Module,
         primitive_objects::PrimitiveObject,
         promise::Promise,
-        proxy::Proxy,
+        proxy::{revoker::BuiltinProxyRevokerFunction, Proxy},
+        text_processing::string_objects::string_iterator_objects::string_iterator::StringIterator,
         Array, BuiltinConstructorFunction, BuiltinFunction, ECMAScriptFunction,
     },
     execution::{
@@ -57,7 +64,7 @@ use crate::ecmascript::{
     },
     scripts_and_modules::{script::ScriptIdentifier, source_code::SourceCode},
     types::{
-        bigint::HeapBigInt, HeapNumber, HeapString, OrdinaryObject, Symbol, Value,
+        bigint::HeapBigInt, HeapNumber, HeapString, ObjectShape, OrdinaryObject, Symbol, Value,
         BUILTIN_STRINGS_LIST,
     },
 };
@@ -67,7 +74,8 @@ use crate::engine::Executable;
 pub struct HeapBits {
     #[cfg(feature = "array-buffer")]
     pub array_buffers: Box<[bool]>,
-    pub arrays: Box<[bool]>,
+    /// Arrays and objects are marked on multiple threads.
+    pub arrays: Box<[AtomicBool]>,
     pub array_iterators: Box<[bool]>,
     pub async_generators: Box<[bool]>,
     pub await_reactions: Box<[bool]>,
@@ -88,6 +96,8 @@ pub struct HeapBits {
     pub e_2_4: Box<[(bool, u8)]>,
     pub e_2_6: Box<[(bool, u8)]>,
     pub e_2_8: Box<[(bool, u8)]>,
+    pub e_sparse: Box<[bool]>,
+    pub e_float64: Box<[bool]>,
     pub ecmascript_functions: Box<[bool]>,
     pub embedder_objects: Box<[bool]>,
     pub errors: Box<[bool]>,
@@ -102,11 +112,13 @@ pub struct HeapBits {
     pub modules: Box<[bool]>,
     pub numbers: Box<[bool]>,
     pub object_environments: Box<[bool]>,
-    pub objects: Box<[bool]>,
+    pub objects: Box<[AtomicBool]>,
+    pub object_shapes: Box<[bool]>,
     pub primitive_objects: Box<[bool]>,
     pub promise_reaction_records: Box<[bool]>,
     pub promise_resolving_functions: Box<[bool]>,
     pub promises: Box<[bool]>,
+    pub proxy_revoker_functions: Box<[bool]>,
     pub proxys: Box<[bool]>,
     pub realms: Box<[bool]>,
     #[cfg(feature = "regexp")]
@@ -119,6 +131,9 @@ pub struct HeapBits {
     #[cfg(feature = "shared-array-buffer")]
     pub shared_array_buffers: Box<[bool]>,
     pub strings: Box<[bool]>,
+    pub string_iterators: Box<[bool]>,
+    #[cfg(feature = "regexp")]
+    pub regexp_string_iterators: Box<[bool]>,
     pub symbols: Box<[bool]>,
     #[cfg(feature = "array-buffer")]
     pub typed_arrays: Box<[bool]>,
@@ -130,7 +145,7 @@ pub struct HeapBits {
     pub weak_sets: Box<[bool]>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub(crate) struct WorkQueues {
     #[cfg(feature = "array-buffer")]
     pub array_buffers: Vec<ArrayBuffer<'static>>,
@@ -155,6 +170,8 @@ pub(crate) struct WorkQueues {
     pub e_2_4: Vec<(ElementIndex, u32)>,
     pub e_2_6: Vec<(ElementIndex, u32)>,
     pub e_2_8: Vec<(ElementIndex, u32)>,
+    pub e_sparse: Vec<ElementIndex>,
+    pub e_float64: Vec<ElementIndex>,
     pub ecmascript_functions: Vec<ECMAScriptFunction<'static>>,
     pub embedder_objects: Vec<EmbedderObject<'static>>,
     pub source_codes: Vec<SourceCode>,
@@ -170,10 +187,12 @@ pub(crate) struct WorkQueues {
     pub numbers: Vec<HeapNumber<'static>>,
     pub object_environments: Vec<ObjectEnvironmentIndex>,
     pub objects: Vec<OrdinaryObject<'static>>,
+    pub object_shapes: Vec<ObjectShape>,
     pub primitive_objects: Vec<PrimitiveObject<'static>>,
     pub promises: Vec<Promise<'static>>,
     pub promise_reaction_records: Vec<PromiseReaction>,
     pub promise_resolving_functions: Vec<BuiltinPromiseResolvingFunction<'static>>,
+    pub proxy_revoker_functions: Vec<BuiltinProxyRevokerFunction<'static>>,
     pub proxys: Vec<Proxy<'static>>,
     pub realms: Vec<RealmIdentifier>,
     #[cfg(feature = "regexp")]
@@ -186,6 +205,9 @@ pub(crate) struct WorkQueues {
     #[cfg(feature = "shared-array-buffer")]
     pub shared_array_buffers: Vec<SharedArrayBuffer<'static>>,
     pub strings: Vec<HeapString<'static>>,
+    pub string_iterators: Vec<StringIterator<'static>>,
+    #[cfg(feature = "regexp")]
+    pub regexp_string_iterators: Vec<RegExpStringIterator<'static>>,
     pub symbols: Vec<Symbol<'static>>,
     #[cfg(feature = "array-buffer")]
     pub typed_arrays: Vec<TypedArrayIndex<'static>>,
@@ -197,11 +219,32 @@ pub(crate) struct WorkQueues {
     pub weak_sets: Vec<WeakSet<'static>>,
 }
 
+/// A mark bit of a heap vector entry.
+pub(crate) trait MarkBit {
+    fn is_marked(&self) -> bool;
+}
+
+impl MarkBit for bool {
+    fn is_marked(&self) -> bool {
+        *self
+    }
+}
+
+impl MarkBit for AtomicBool {
+    fn is_marked(&self) -> bool {
+        self.load(Ordering::Relaxed)
+    }
+}
+
+fn atomic_bits(len: usize) -> Box<[AtomicBool]> {
+    (0..len).map(|_| AtomicBool::new(false)).collect()
+}
+
 impl HeapBits {
     pub fn new(heap: &Heap) -> Self {
         #[cfg(feature = "array-buffer")]
         let array_buffers = vec![false; heap.array_buffers.len()];
-        let arrays = vec![false; heap.arrays.len()];
+        let arrays = atomic_bits(heap.arrays.len());
         let array_iterators = vec![false; heap.array_iterators.len()];
         let async_generators = vec![false; heap.async_generators.len()];
         let await_reactions = vec![false; heap.await_reactions.len()];
@@ -222,6 +265,8 @@ impl HeapBits {
         let e_2_4 = vec![(false, 0u8); heap.elements.e2pow4.values.len()];
         let e_2_6 = vec![(false, 0u8); heap.elements.e2pow6.values.len()];
         let e_2_8 = vec![(false, 0u8); heap.elements.e2pow8.values.len()];
+        let e_sparse = vec![false; heap.elements.sparse.len()];
+        let e_float64 = vec![false; heap.elements.float64.len()];
         let ecmascript_functions = vec![false; heap.ecmascript_functions.len()];
         let embedder_objects = vec![false; heap.embedder_objects.len()];
         let errors = vec![false; heap.errors.len()];
@@ -236,11 +281,13 @@ impl HeapBits {
         let modules = vec![false; heap.modules.len()];
         let numbers = vec![false; heap.numbers.len()];
         let object_environments = vec![false; heap.environments.object.len()];
-        let objects = vec![false; heap.objects.len()];
+        let objects = atomic_bits(heap.objects.len());
+        let object_shapes = vec![false; heap.object_shapes.len()];
         let primitive_objects = vec![false; heap.primitive_objects.len()];
         let promise_reaction_records = vec![false; heap.promise_reaction_records.len()];
         let promise_resolving_functions = vec![false; heap.promise_resolving_functions.len()];
         let promises = vec![false; heap.promises.len()];
+        let proxy_revoker_functions = vec![false; heap.proxy_revoker_functions.len()];
         let proxys = vec![false; heap.proxys.len()];
         let realms = vec![false; heap.realms.len()];
         #[cfg(feature = "regexp")]
@@ -253,6 +300,9 @@ impl HeapBits {
         #[cfg(feature = "shared-array-buffer")]
         let shared_array_buffers = vec![false; heap.shared_array_buffers.len()];
         let strings = vec![false; heap.strings.len()];
+        let string_iterators = vec![false; heap.string_iterators.len()];
+        #[cfg(feature = "regexp")]
+        let regexp_string_iterators = vec![false; heap.regexp_string_iterators.len()];
         let symbols = vec![false; heap.symbols.len()];
         #[cfg(feature = "array-buffer")]
         let typed_arrays = vec![false; heap.typed_arrays.len()];
@@ -265,7 +315,7 @@ impl HeapBits {
         Self {
             #[cfg(feature = "array-buffer")]
             array_buffers: array_buffers.into_boxed_slice(),
-            arrays: arrays.into_boxed_slice(),
+            arrays,
             array_iterators: array_iterators.into_boxed_slice(),
             async_generators: async_generators.into_boxed_slice(),
             await_reactions: await_reactions.into_boxed_slice(),
@@ -286,6 +336,8 @@ impl HeapBits {
             e_2_4: e_2_4.into_boxed_slice(),
             e_2_6: e_2_6.into_boxed_slice(),
             e_2_8: e_2_8.into_boxed_slice(),
+            e_sparse: e_sparse.into_boxed_slice(),
+            e_float64: e_float64.into_boxed_slice(),
             ecmascript_functions: ecmascript_functions.into_boxed_slice(),
             embedder_objects: embedder_objects.into_boxed_slice(),
             errors: errors.into_boxed_slice(),
@@ -300,11 +352,13 @@ impl HeapBits {
             modules: modules.into_boxed_slice(),
             numbers: numbers.into_boxed_slice(),
             object_environments: object_environments.into_boxed_slice(),
-            objects: objects.into_boxed_slice(),
+            objects,
+            object_shapes: object_shapes.into_boxed_slice(),
             primitive_objects: primitive_objects.into_boxed_slice(),
             promise_reaction_records: promise_reaction_records.into_boxed_slice(),
             promise_resolving_functions: promise_resolving_functions.into_boxed_slice(),
             promises: promises.into_boxed_slice(),
+            proxy_revoker_functions: proxy_revoker_functions.into_boxed_slice(),
             proxys: proxys.into_boxed_slice(),
             realms: realms.into_boxed_slice(),
             #[cfg(feature = "regexp")]
@@ -317,6 +371,9 @@ impl HeapBits {
             #[cfg(feature = "shared-array-buffer")]
             shared_array_buffers: shared_array_buffers.into_boxed_slice(),
             strings: strings.into_boxed_slice(),
+            string_iterators: string_iterators.into_boxed_slice(),
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators: regexp_string_iterators.into_boxed_slice(),
             symbols: symbols.into_boxed_slice(),
             #[cfg(feature = "array-buffer")]
             typed_arrays: typed_arrays.into_boxed_slice(),
@@ -330,6 +387,9 @@ impl HeapBits {
     }
 }
 
+// SAFETY: The queues only hold heap indexes, which are plain integers.
+unsafe impl Send for WorkQueues {}
+
 impl WorkQueues {
     pub fn new(heap: &Heap) -> Self {
         Self {
@@ -356,6 +416,8 @@ impl WorkQueues {
             e_2_4: Vec::with_capacity(heap.elements.e2pow4.values.len() / 4),
             e_2_6: Vec::with_capacity(heap.elements.e2pow6.values.len() / 4),
             e_2_8: Vec::with_capacity(heap.elements.e2pow8.values.len() / 4),
+            e_sparse: Vec::with_capacity(heap.elements.sparse.len() / 4),
+            e_float64: Vec::with_capacity(heap.elements.float64.len() / 4),
             ecmascript_functions: Vec::with_capacity(heap.ecmascript_functions.len() / 4),
             embedder_objects: Vec::with_capacity(heap.embedder_objects.len() / 4),
             errors: Vec::with_capacity(heap.errors.len() / 4),
@@ -371,12 +433,14 @@ impl WorkQueues {
             numbers: Vec::with_capacity(heap.numbers.len() / 4),
             object_environments: Vec::with_capacity(heap.environments.object.len() / 4),
             objects: Vec::with_capacity(heap.objects.len() / 4),
+            object_shapes: Vec::with_capacity(heap.object_shapes.len() / 4),
             primitive_objects: Vec::with_capacity(heap.primitive_objects.len() / 4),
             promise_reaction_records: Vec::with_capacity(heap.promise_reaction_records.len() / 4),
             promise_resolving_functions: Vec::with_capacity(
                 heap.promise_resolving_functions.len() / 4,
             ),
             promises: Vec::with_capacity(heap.promises.len() / 4),
+            proxy_revoker_functions: Vec::with_capacity(heap.proxy_revoker_functions.len() / 4),
             proxys: Vec::with_capacity(heap.proxys.len() / 4),
             realms: Vec::with_capacity(heap.realms.len() / 4),
             #[cfg(feature = "regexp")]
@@ -389,6 +453,9 @@ impl WorkQueues {
             #[cfg(feature = "shared-array-buffer")]
             shared_array_buffers: Vec::with_capacity(heap.shared_array_buffers.len() / 4),
             strings: Vec::with_capacity((heap.strings.len() / 4).max(BUILTIN_STRINGS_LIST.len())),
+            string_iterators: Vec::with_capacity(heap.string_iterators.len() / 4),
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators: Vec::with_capacity(heap.regexp_string_iterators.len() / 4),
             symbols: Vec::with_capacity((heap.symbols.len() / 4).max(13)),
             #[cfg(feature = "array-buffer")]
             typed_arrays: Vec::with_capacity(heap.typed_arrays.len() / 4),
@@ -401,6 +468,155 @@ impl WorkQueues {
         }
     }
 
+    /// Move all work from another set of queues into these queues.
+    pub fn append(&mut self, other: &mut Self) {
+        let Self {
+            #[cfg(feature = "array-buffer")]
+            array_buffers,
+            arrays,
+            array_iterators,
+            async_generators,
+            await_reactions,
+            bigints,
+            bound_functions,
+            builtin_constructors,
+            builtin_functions,
+            #[cfg(feature = "array-buffer")]
+            data_views,
+            #[cfg(feature = "date")]
+            dates,
+            declarative_environments,
+            e_2_10,
+            e_2_12,
+            e_2_16,
+            e_2_24,
+            e_2_32,
+            e_2_4,
+            e_2_6,
+            e_2_8,
+            e_sparse,
+            e_float64,
+            ecmascript_functions,
+            embedder_objects,
+            source_codes,
+            errors,
+            executables,
+            finalization_registrys,
+            function_environments,
+            generators,
+            global_environments,
+            maps,
+            map_iterators,
+            modules,
+            numbers,
+            object_environments,
+            objects,
+            object_shapes,
+            primitive_objects,
+            promises,
+            promise_reaction_records,
+            promise_resolving_functions,
+            proxy_revoker_functions,
+            proxys,
+            realms,
+            #[cfg(feature = "regexp")]
+            regexps,
+            scripts,
+            #[cfg(feature = "set")]
+            sets,
+            #[cfg(feature = "set")]
+            set_iterators,
+            #[cfg(feature = "shared-array-buffer")]
+            shared_array_buffers,
+            strings,
+            string_iterators,
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators,
+            symbols,
+            #[cfg(feature = "array-buffer")]
+            typed_arrays,
+            #[cfg(feature = "weak-refs")]
+            weak_maps,
+            #[cfg(feature = "weak-refs")]
+            weak_refs,
+            #[cfg(feature = "weak-refs")]
+            weak_sets,
+        } = other;
+        #[cfg(feature = "array-buffer")]
+        self.array_buffers.append(array_buffers);
+        self.arrays.append(arrays);
+        self.array_iterators.append(array_iterators);
+        self.async_generators.append(async_generators);
+        self.await_reactions.append(await_reactions);
+        self.bigints.append(bigints);
+        self.bound_functions.append(bound_functions);
+        self.builtin_constructors.append(builtin_constructors);
+        self.builtin_functions.append(builtin_functions);
+        #[cfg(feature = "array-buffer")]
+        self.data_views.append(data_views);
+        #[cfg(feature = "date")]
+        self.dates.append(dates);
+        self.declarative_environments
+            .append(declarative_environments);
+        self.e_2_10.append(e_2_10);
+        self.e_2_12.append(e_2_12);
+        self.e_2_16.append(e_2_16);
+        self.e_2_24.append(e_2_24);
+        self.e_2_32.append(e_2_32);
+        self.e_2_4.append(e_2_4);
+        self.e_2_6.append(e_2_6);
+        self.e_2_8.append(e_2_8);
+        self.e_sparse.append(e_sparse);
+        self.e_float64.append(e_float64);
+        self.ecmascript_functions.append(ecmascript_functions);
+        self.embedder_objects.append(embedder_objects);
+        self.source_codes.append(source_codes);
+        self.errors.append(errors);
+        self.executables.append(executables);
+        self.finalization_registrys.append(finalization_registrys);
+        self.function_environments.append(function_environments);
+        self.generators.append(generators);
+        self.global_environments.append(global_environments);
+        self.maps.append(maps);
+        self.map_iterators.append(map_iterators);
+        self.modules.append(modules);
+        self.numbers.append(numbers);
+        self.object_environments.append(object_environments);
+        self.objects.append(objects);
+        self.object_shapes.append(object_shapes);
+        self.primitive_objects.append(primitive_objects);
+        self.promises.append(promises);
+        self.promise_reaction_records
+            .append(promise_reaction_records);
+        self.promise_resolving_functions
+            .append(promise_resolving_functions);
+        self.proxy_revoker_functions.append(proxy_revoker_functions);
+        self.proxys.append(proxys);
+        self.realms.append(realms);
+        #[cfg(feature = "regexp")]
+        self.regexps.append(regexps);
+        self.scripts.append(scripts);
+        #[cfg(feature = "set")]
+        self.sets.append(sets);
+        #[cfg(feature = "set")]
+        self.set_iterators.append(set_iterators);
+        #[cfg(feature = "shared-array-buffer")]
+        self.shared_array_buffers.append(shared_array_buffers);
+        self.strings.append(strings);
+        self.string_iterators.append(string_iterators);
+        #[cfg(feature = "regexp")]
+        self.regexp_string_iterators.append(regexp_string_iterators);
+        self.symbols.append(symbols);
+        #[cfg(feature = "array-buffer")]
+        self.typed_arrays.append(typed_arrays);
+        #[cfg(feature = "weak-refs")]
+        self.weak_maps.append(weak_maps);
+        #[cfg(feature = "weak-refs")]
+        self.weak_refs.append(weak_refs);
+        #[cfg(feature = "weak-refs")]
+        self.weak_sets.append(weak_sets);
+    }
+
     pub fn push_elements_vector(&mut self, vec: &ElementsVector) {
         match vec.cap {
             ElementArrayKey::Empty => {}
@@ -412,6 +628,8 @@ impl WorkQueues {
             ElementArrayKey::E16 => self.e_2_16.push((vec.elements_index, vec.len)),
             ElementArrayKey::E24 => self.e_2_24.push((vec.elements_index, vec.len)),
             ElementArrayKey::E32 => self.e_2_32.push((vec.elements_index, vec.len)),
+            ElementArrayKey::Sparse => self.e_sparse.push(vec.elements_index),
+            ElementArrayKey::Float64 => self.e_float64.push(vec.elements_index),
         }
     }
 
@@ -449,6 +667,8 @@ impl WorkQueues {
             e_2_4,
             e_2_6,
             e_2_8,
+            e_sparse,
+            e_float64,
             ecmascript_functions,
             embedder_objects,
             source_codes,
@@ -464,10 +684,12 @@ impl WorkQueues {
             numbers,
             object_environments,
             objects,
+            object_shapes,
             primitive_objects,
             promises,
             promise_reaction_records,
             promise_resolving_functions,
+            proxy_revoker_functions,
             proxys,
             realms,
             #[cfg(feature = "regexp")]
@@ -480,6 +702,9 @@ impl WorkQueues {
             #[cfg(feature = "shared-array-buffer")]
             shared_array_buffers,
             strings,
+            string_iterators,
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators,
             symbols,
             #[cfg(feature = "array-buffer")]
             typed_arrays,
@@ -509,6 +734,8 @@ impl WorkQueues {
         let weak_sets: &[bool; 0] = &[];
         #[cfg(not(feature = "regexp"))]
         let regexps: &[bool; 0] = &[];
+        #[cfg(not(feature = "regexp"))]
+        let regexp_string_iterators: &[bool; 0] = &[];
         #[cfg(not(feature = "set"))]
         let sets: &[bool; 0] = &[];
         #[cfg(not(feature = "set"))]
@@ -533,6 +760,8 @@ impl WorkQueues {
             && e_2_4.is_empty()
             && e_2_6.is_empty()
             && e_2_8.is_empty()
+            && e_sparse.is_empty()
+            && e_float64.is_empty()
             && ecmascript_functions.is_empty()
             && embedder_objects.is_empty()
             && errors.is_empty()
@@ -548,18 +777,22 @@ impl WorkQueues {
             && numbers.is_empty()
             && object_environments.is_empty()
             && objects.is_empty()
+            && object_shapes.is_empty()
             && primitive_objects.is_empty()
             && promise_reaction_records.is_empty()
             && promise_resolving_functions.is_empty()
             && promises.is_empty()
+            && proxy_revoker_functions.is_empty()
             && proxys.is_empty()
             && realms.is_empty()
             && regexps.is_empty()
+            && regexp_string_iterators.is_empty()
             && scripts.is_empty()
             && sets.is_empty()
             && set_iterators.is_empty()
             && shared_array_buffers.is_empty()
             && strings.is_empty()
+            && string_iterators.is_empty()
             && symbols.is_empty()
             && typed_arrays.is_empty()
             && weak_maps.is_empty()
@@ -585,6 +818,11 @@ impl CompactionList {
             .unwrap_or(0)
     }
 
+    /// A compaction list that leaves every index in place.
+    pub(crate) fn identity() -> Self {
+        Self::build(Vec::new(), Vec::new())
+    }
+
     pub(crate) fn shift_index<T: ?Sized>(&self, index: &mut BaseIndex<T>) {
         let base_index = index.into_u32_index();
         *index = BaseIndex::from_u32_index(base_index - self.get_shift_for_index(base_index));
@@ -612,10 +850,10 @@ impl CompactionList {
         }
     }
 
-    pub(crate) fn from_mark_bits(marks: &[bool]) -> Self {
+    pub(crate) fn from_mark_bits(marks: &[impl MarkBit]) -> Self {
         let mut builder = CompactionListBuilder::default();
         marks.iter().for_each(|bit| {
-            if *bit {
+            if bit.is_marked() {
                 builder.mark_used();
             } else {
                 builder.mark_unused();
@@ -721,6 +959,8 @@ impl Default for CompactionListBuilder {
 }
 
 pub(crate) struct CompactionLists {
+    /// If true, unmarked heap vector slots are poisoned instead of compacted.
+    pub poison: bool,
     #[cfg(feature = "array-buffer")]
     pub array_buffers: CompactionList,
     pub arrays: CompactionList,
@@ -744,6 +984,8 @@ pub(crate) struct CompactionLists {
     pub e_2_4: CompactionList,
     pub e_2_6: CompactionList,
     pub e_2_8: CompactionList,
+    pub e_sparse: CompactionList,
+    pub e_float64: CompactionList,
     pub ecmascript_functions: CompactionList,
     pub embedder_objects: CompactionList,
     pub source_codes: CompactionList,
@@ -759,10 +1001,12 @@ pub(crate) struct CompactionLists {
     pub numbers: CompactionList,
     pub object_environments: CompactionList,
     pub objects: CompactionList,
+    pub object_shapes: CompactionList,
     pub primitive_objects: CompactionList,
     pub promise_reaction_records: CompactionList,
     pub promise_resolving_functions: CompactionList,
     pub promises: CompactionList,
+    pub proxy_revoker_functions: CompactionList,
     pub proxys: CompactionList,
     pub realms: CompactionList,
     #[cfg(feature = "regexp")]
@@ -775,6 +1019,9 @@ pub(crate) struct CompactionLists {
     #[cfg(feature = "shared-array-buffer")]
     pub shared_array_buffers: CompactionList,
     pub strings: CompactionList,
+    pub string_iterators: CompactionList,
+    #[cfg(feature = "regexp")]
+    pub regexp_string_iterators: CompactionList,
     pub symbols: CompactionList,
     #[cfg(feature = "array-buffer")]
     pub typed_arrays: CompactionList,
@@ -798,6 +1045,7 @@ impl CompactionLists {
         // areas can exist. We can use this mathematical bound to estimate a good
         // vector allocation.
         Self {
+            poison: false,
             modules: CompactionList::from_mark_bits(&bits.modules),
             scripts: CompactionList::from_mark_bits(&bits.scripts),
             realms: CompactionList::from_mark_bits(&bits.realms),
@@ -810,6 +1058,8 @@ impl CompactionLists {
             e_2_4: CompactionList::from_mark_u8s(&bits.e_2_4),
             e_2_6: CompactionList::from_mark_u8s(&bits.e_2_6),
             e_2_8: CompactionList::from_mark_u8s(&bits.e_2_8),
+            e_sparse: CompactionList::from_mark_bits(&bits.e_sparse),
+            e_float64: CompactionList::from_mark_bits(&bits.e_float64),
             e_2_10: CompactionList::from_mark_u16s(&bits.e_2_10),
             e_2_12: CompactionList::from_mark_u16s(&bits.e_2_12),
             e_2_16: CompactionList::from_mark_u16s(&bits.e_2_16),
@@ -837,6 +1087,7 @@ impl CompactionLists {
             map_iterators: CompactionList::from_mark_bits(&bits.map_iterators),
             numbers: CompactionList::from_mark_bits(&bits.numbers),
             objects: CompactionList::from_mark_bits(&bits.objects),
+            object_shapes: CompactionList::from_mark_bits(&bits.object_shapes),
             promise_reaction_records: CompactionList::from_mark_bits(
                 &bits.promise_reaction_records,
             ),
@@ -852,12 +1103,16 @@ impl CompactionLists {
             #[cfg(feature = "set")]
             set_iterators: CompactionList::from_mark_bits(&bits.set_iterators),
             strings: CompactionList::from_mark_bits(&bits.strings),
+            string_iterators: CompactionList::from_mark_bits(&bits.string_iterators),
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators: CompactionList::from_mark_bits(&bits.regexp_string_iterators),
             #[cfg(feature = "shared-array-buffer")]
             shared_array_buffers: CompactionList::from_mark_bits(&bits.shared_array_buffers),
             symbols: CompactionList::from_mark_bits(&bits.symbols),
             #[cfg(feature = "array-buffer")]
             data_views: CompactionList::from_mark_bits(&bits.data_views),
             finalization_registrys: CompactionList::from_mark_bits(&bits.finalization_registrys),
+            proxy_revoker_functions: CompactionList::from_mark_bits(&bits.proxy_revoker_functions),
             proxys: CompactionList::from_mark_bits(&bits.proxys),
             #[cfg(feature = "weak-refs")]
             weak_maps: CompactionList::from_mark_bits(&bits.weak_maps),
@@ -869,6 +1124,89 @@ impl CompactionLists {
             typed_arrays: CompactionList::from_mark_bits(&bits.typed_arrays),
         }
     }
+
+    /// Create compaction lists for a poisoning sweep, used in GC stress mode.
+    ///
+    /// Heap vectors of optional slots are not compacted: their unmarked slots
+    /// are instead set to `None`, so that a stale index into them panics on
+    /// its next use rather than silently aliasing some other heap data.
+    /// Executables and object shapes have no empty slot representation and
+    /// are compacted as usual.
+    pub fn create_poisoning(bits: &HeapBits) -> Self {
+        Self {
+            poison: true,
+            modules: CompactionList::identity(),
+            scripts: CompactionList::identity(),
+            realms: CompactionList::identity(),
+            declarative_environments: CompactionList::identity(),
+            function_environments: CompactionList::identity(),
+            global_environments: CompactionList::identity(),
+            object_environments: CompactionList::identity(),
+            e_2_4: CompactionList::identity(),
+            e_2_6: CompactionList::identity(),
+            e_2_8: CompactionList::identity(),
+            e_sparse: CompactionList::identity(),
+            e_float64: CompactionList::identity(),
+            e_2_10: CompactionList::identity(),
+            e_2_12: CompactionList::identity(),
+            e_2_16: CompactionList::identity(),
+            e_2_24: CompactionList::identity(),
+            e_2_32: CompactionList::identity(),
+            arrays: CompactionList::identity(),
+            #[cfg(feature = "array-buffer")]
+            array_buffers: CompactionList::identity(),
+            array_iterators: CompactionList::identity(),
+            async_generators: CompactionList::identity(),
+            await_reactions: CompactionList::identity(),
+            bigints: CompactionList::identity(),
+            bound_functions: CompactionList::identity(),
+            builtin_constructors: CompactionList::identity(),
+            builtin_functions: CompactionList::identity(),
+            ecmascript_functions: CompactionList::identity(),
+            embedder_objects: CompactionList::identity(),
+            generators: CompactionList::identity(),
+            source_codes: CompactionList::identity(),
+            #[cfg(feature = "date")]
+            dates: CompactionList::identity(),
+            errors: CompactionList::identity(),
+            executables: CompactionList::from_mark_bits(&bits.executables),
+            maps: CompactionList::identity(),
+            map_iterators: CompactionList::identity(),
+            numbers: CompactionList::identity(),
+            objects: CompactionList::identity(),
+            object_shapes: CompactionList::from_mark_bits(&bits.object_shapes),
+            promise_reaction_records: CompactionList::identity(),
+            promise_resolving_functions: CompactionList::identity(),
+            promises: CompactionList::identity(),
+            primitive_objects: CompactionList::identity(),
+            #[cfg(feature = "regexp")]
+            regexps: CompactionList::identity(),
+            #[cfg(feature = "set")]
+            sets: CompactionList::identity(),
+            #[cfg(feature = "set")]
+            set_iterators: CompactionList::identity(),
+            strings: CompactionList::identity(),
+            string_iterators: CompactionList::identity(),
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators: CompactionList::identity(),
+            #[cfg(feature = "shared-array-buffer")]
+            shared_array_buffers: CompactionList::identity(),
+            symbols: CompactionList::identity(),
+            #[cfg(feature = "array-buffer")]
+            data_views: CompactionList::identity(),
+            finalization_registrys: CompactionList::identity(),
+            proxy_revoker_functions: CompactionList::identity(),
+            proxys: CompactionList::identity(),
+            #[cfg(feature = "weak-refs")]
+            weak_maps: CompactionList::identity(),
+            #[cfg(feature = "weak-refs")]
+            weak_refs: CompactionList::identity(),
+            #[cfg(feature = "weak-refs")]
+            weak_sets: CompactionList::identity(),
+            #[cfg(feature = "array-buffer")]
+            typed_arrays: CompactionList::identity(),
+        }
+    }
 }
 
 pub(crate) trait HeapMarkAndSweep {
@@ -997,15 +1335,36 @@ fn sweep_array_with_u32_length<T: HeapMarkAndSweep, const N: usize>(
 }
 
 pub(crate) fn sweep_heap_vector_values<T: HeapMarkAndSweep + std::fmt::Debug>(
+    vec: &mut Vec<Option<T>>,
+    compactions: &CompactionLists,
+    bits: &[impl MarkBit],
+) {
+    if compactions.poison {
+        assert_eq!(vec.len(), bits.len());
+        vec.iter_mut().zip(bits).for_each(|(item, mark)| {
+            if mark.is_marked() {
+                item.sweep_values(compactions);
+            } else {
+                *item = None;
+            }
+        });
+    } else {
+        compact_heap_vector_values(vec, compactions, bits);
+    }
+}
+
+/// Sweep a heap vector that has no empty slot representation: It is always
+/// compacted, even in a poisoning sweep.
+pub(crate) fn compact_heap_vector_values<T: HeapMarkAndSweep + std::fmt::Debug>(
     vec: &mut Vec<T>,
     compactions: &CompactionLists,
-    bits: &[bool],
+    bits: &[impl MarkBit],
 ) {
     assert_eq!(vec.len(), bits.len());
     let mut iter = bits.iter();
     vec.retain_mut(|item| {
         let do_retain = iter.next().unwrap();
-        if *do_retain {
+        if do_retain.is_marked() {
             item.sweep_values(compactions);
             true
         } else {
@@ -1020,6 +1379,16 @@ pub(crate) fn sweep_heap_u8_elements_vector_values<const N: usize>(
     u8s: &[(bool, u8)],
 ) {
     assert_eq!(vec.len(), u8s.len());
+    if compactions.poison {
+        vec.iter_mut().zip(u8s).for_each(|(item, (mark, length))| {
+            if *mark {
+                sweep_array_with_u32_length(item, compactions, *length as u32);
+            } else {
+                *item = None;
+            }
+        });
+        return;
+    }
     let mut iter = u8s.iter();
     vec.retain_mut(|item| {
         let (mark, length) = iter.next().unwrap();
@@ -1038,6 +1407,16 @@ pub(crate) fn sweep_heap_u16_elements_vector_values<const N: usize>(
     u16s: &[(bool, u16)],
 ) {
     assert_eq!(vec.len(), u16s.len());
+    if compactions.poison {
+        vec.iter_mut().zip(u16s).for_each(|(item, (mark, length))| {
+            if *mark {
+                sweep_array_with_u32_length(item, compactions, *length as u32);
+            } else {
+                *item = None;
+            }
+        });
+        return;
+    }
     let mut iter = u16s.iter();
     vec.retain_mut(|item| {
         let (mark, length) = iter.next().unwrap();
@@ -1056,6 +1435,16 @@ pub(crate) fn sweep_heap_u32_elements_vector_values<const N: usize>(
     u32s: &[(bool, u32)],
 ) {
     assert_eq!(vec.len(), u32s.len());
+    if compactions.poison {
+        vec.iter_mut().zip(u32s).for_each(|(item, (mark, length))| {
+            if *mark {
+                sweep_array_with_u32_length(item, compactions, *length);
+            } else {
+                *item = None;
+            }
+        });
+        return;
+    }
     let mut iter = u32s.iter();
     vec.retain_mut(|item| {
         let (mark, length) = iter.next().unwrap();
@@ -1142,14 +1531,20 @@ pub(crate) fn sweep_side_table_values<'a, T, K, V>(
     }
 }
 
+/// Drop the lookup table entries of unmarked heap data and compact the rest.
+/// `unlisted_count` is the number of heap data items that are not in the
+/// lookup table by design, eg. rope strings.
 pub(crate) fn sweep_lookup_table<'a, T, U>(
     lookup_table: &mut HashTable<T>,
     compactions: &CompactionLists,
     bits: &[bool],
+    unlisted_count: usize,
 ) where
     T: GetBaseIndexMut<'a, U>,
 {
-    assert_eq!(lookup_table.len(), bits.len());
+    // A poisoning sweep leaves `None` slots behind in the heap vector, which
+    // have no lookup table entries.
+    assert!(compactions.poison || lookup_table.len() + unlisted_count == bits.len());
     lookup_table.retain(|entry| {
         let base_index = entry.get_base_index_mut();
         let do_retain = bits[base_index.into_index()];
@@ -257,6 +257,8 @@ pub(crate) enum IntrinsicFunctionIndexes {
     ParseInt,
     #[cfg(feature = "regexp")]
     RegExpPrototypeExec,
+    SetIteratorPrototypeNext,
+    SetPrototypeHas,
     SetPrototypeValues,
     StringPrototypeTrimEnd,
     StringPrototypeTrimStart,
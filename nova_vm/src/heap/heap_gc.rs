@@ -2,15 +2,23 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use std::thread;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Instant,
+};
 
 use super::{
     element_array::ElementArrays,
     heap_bits::{
-        mark_array_with_u32_length, mark_descriptors, sweep_heap_elements_vector_descriptors,
-        sweep_heap_u16_elements_vector_values, sweep_heap_u32_elements_vector_values,
-        sweep_heap_u8_elements_vector_values, sweep_heap_vector_values, sweep_lookup_table,
-        CompactionLists, HeapBits, HeapMarkAndSweep, WorkQueues,
+        compact_heap_vector_values, mark_array_with_u32_length, mark_descriptors,
+        sweep_heap_elements_vector_descriptors, sweep_heap_u16_elements_vector_values,
+        sweep_heap_u32_elements_vector_values, sweep_heap_u8_elements_vector_values,
+        sweep_heap_vector_values, sweep_lookup_table, CompactionLists, HeapBits, HeapMarkAndSweep,
+        WorkQueues,
     },
     indexes::{ElementIndex, StringIndex},
     Heap, WellKnownSymbolIndexes,
@@ -21,6 +29,8 @@ use super::{heap_bits::sweep_side_table_values, indexes::TypedArrayIndex};
 use crate::ecmascript::builtins::date::Date;
 #[cfg(feature = "regexp")]
 use crate::ecmascript::builtins::regexp::RegExp;
+#[cfg(feature = "regexp")]
+use crate::ecmascript::builtins::text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIterator;
 #[cfg(feature = "shared-array-buffer")]
 use crate::ecmascript::builtins::shared_array_buffer::SharedArrayBuffer;
 #[cfg(feature = "array-buffer")]
@@ -53,23 +63,29 @@ use crate::{
             module::Module,
             primitive_objects::PrimitiveObject,
             promise::Promise,
-            proxy::Proxy,
-            Array, BuiltinConstructorFunction, BuiltinFunction, ECMAScriptFunction,
+            proxy::{revoker::BuiltinProxyRevokerFunction, Proxy},
+            text_processing::string_objects::string_iterator_objects::string_iterator::StringIterator,
+            BuiltinConstructorFunction, BuiltinFunction, ECMAScriptFunction,
         },
         execution::{
             Agent, DeclarativeEnvironmentIndex, Environments, FunctionEnvironmentIndex,
             GlobalEnvironmentIndex, ObjectEnvironmentIndex, RealmIdentifier,
         },
-        scripts_and_modules::{script::ScriptIdentifier, source_code::SourceCode},
+        scripts_and_modules::{
+            script::ScriptIdentifier,
+            source_code::{discard_parsed_code, SourceCode},
+        },
         types::{
-            bigint::HeapBigInt, HeapNumber, HeapString, OrdinaryObject, Symbol,
-            BUILTIN_STRINGS_LIST,
+            bigint::HeapBigInt, sweep_object_shape_roots, HeapNumber, HeapString, ObjectShape,
+            StringHeapData, Symbol, BUILTIN_STRINGS_LIST,
         },
     },
     engine::{context::GcScope, Executable},
 };
 
 pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], gc: GcScope) {
+    let start = Instant::now();
+    let slot_count = agent.heap.slot_count();
     let Agent {
         heap,
         execution_context_stack,
@@ -79,6 +95,10 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
         symbol_id: _,
         global_symbol_registry: _,
         host_hooks: _,
+        terminating: _,
+        interrupts: _,
+        executed_instructions: _,
+        stack_base: _,
     } = agent;
     let mut bits = HeapBits::new(heap);
     let mut queues = WorkQueues::new(heap);
@@ -136,6 +156,35 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
         WellKnownSymbolIndexes::Unscopables.into(),
     ]);
 
+    let thread_count = thread::available_parallelism().map_or(1, |count| count.get());
+    thread::scope(|scope| {
+        let mut workers = MarkWorkers::new(scope, thread_count);
+        mark(heap, &mut bits, queues, &mut workers);
+    });
+
+    sweep(agent, &bits, root_realms, gc);
+
+    if agent.options.discard_parsed_code {
+        discard_parsed_code(agent);
+    }
+
+    agent
+        .heap
+        .update_gc_threshold(agent.options.gc_growth_factor);
+    let collected_count = slot_count.saturating_sub(agent.heap.slot_count());
+    agent
+        .heap
+        .gc_statistics
+        .record(start.elapsed(), collected_count);
+}
+
+/// Mark everything reachable from the work queues.
+fn mark<'scope>(
+    heap: &'scope Heap,
+    bits: &'scope mut HeapBits,
+    mut queues: WorkQueues,
+    workers: &mut MarkWorkers<'scope, '_>,
+) {
     while !queues.is_empty() {
         let Heap {
             #[cfg(feature = "array-buffer")]
@@ -173,14 +222,22 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
             modules,
             numbers,
             objects,
+            object_shapes,
+            object_shape_roots: _,
+            gc_threshold: _,
+            owned_bytes: _,
+            gc_statistics: _,
             primitive_objects,
             promise_reaction_records,
             promise_resolving_functions,
             promises,
+            proxy_revoker_functions,
             proxys,
             realms,
             #[cfg(feature = "regexp")]
             regexps,
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators,
             scripts,
             #[cfg(feature = "set")]
             sets,
@@ -189,6 +246,7 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
             #[cfg(feature = "shared-array-buffer")]
             shared_array_buffers,
             strings,
+            string_iterators,
             string_lookup_table: _,
             string_hasher: _,
             symbols,
@@ -223,6 +281,8 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
             e2pow16,
             e2pow24,
             e2pow32,
+            sparse,
+            float64: _,
         } = elements;
         let mut module_marks: Box<[Module]> = queues.modules.drain(..).collect();
         module_marks.sort();
@@ -321,19 +381,10 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
             }
         });
 
-        let mut array_marks: Box<[Array]> = queues.arrays.drain(..).collect();
+        let mut array_marks: Box<[usize]> =
+            queues.arrays.drain(..).map(|idx| idx.get_index()).collect();
         array_marks.sort();
-        array_marks.iter().for_each(|&idx| {
-            let index = idx.get_index();
-            if let Some(marked) = bits.arrays.get_mut(index) {
-                if *marked {
-                    // Already marked, ignore
-                    return;
-                }
-                *marked = true;
-                arrays.get(index).mark_values(&mut queues);
-            }
-        });
+        mark_entries(arrays, &bits.arrays, &array_marks, &mut queues, workers);
         #[cfg(feature = "array-buffer")]
         {
             let mut array_buffer_marks: Box<[ArrayBuffer]> =
@@ -574,17 +625,24 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
                 generators.get(index).mark_values(&mut queues);
             }
         });
-        let mut object_marks: Box<[OrdinaryObject]> = queues.objects.drain(..).collect();
+        let mut object_marks: Box<[usize]> = queues
+            .objects
+            .drain(..)
+            .map(|idx| idx.get_index())
+            .collect();
         object_marks.sort();
-        object_marks.iter().for_each(|&idx| {
+        mark_entries(objects, &bits.objects, &object_marks, &mut queues, workers);
+        let mut object_shape_marks: Box<[ObjectShape]> = queues.object_shapes.drain(..).collect();
+        object_shape_marks.sort();
+        object_shape_marks.iter().for_each(|&idx| {
             let index = idx.get_index();
-            if let Some(marked) = bits.objects.get_mut(index) {
+            if let Some(marked) = bits.object_shapes.get_mut(index) {
                 if *marked {
                     // Already marked, ignore
                     return;
                 }
                 *marked = true;
-                objects.get(index).mark_values(&mut queues);
+                object_shapes.get(index).mark_values(&mut queues);
             }
         });
         let mut promise_marks: Box<[Promise]> = queues.promises.drain(..).collect();
@@ -630,6 +688,20 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
                     .mark_values(&mut queues);
             }
         });
+        let mut proxy_revoker_function_marks: Box<[BuiltinProxyRevokerFunction]> =
+            queues.proxy_revoker_functions.drain(..).collect();
+        proxy_revoker_function_marks.sort();
+        proxy_revoker_function_marks.iter().for_each(|&idx| {
+            let index = idx.get_index();
+            if let Some(marked) = bits.proxy_revoker_functions.get_mut(index) {
+                if *marked {
+                    // Already marked, ignore
+                    return;
+                }
+                *marked = true;
+                proxy_revoker_functions.get(index).mark_values(&mut queues);
+            }
+        });
         let mut proxy_marks: Box<[Proxy]> = queues.proxys.drain(..).collect();
         proxy_marks.sort();
         proxy_marks.iter().for_each(|&idx| {
@@ -712,6 +784,23 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
                 }
             });
         }
+        #[cfg(feature = "regexp")]
+        {
+            let mut regexp_string_iterator_marks: Box<[RegExpStringIterator]> =
+                queues.regexp_string_iterators.drain(..).collect();
+            regexp_string_iterator_marks.sort();
+            regexp_string_iterator_marks.iter().for_each(|&idx| {
+                let index = idx.get_index();
+                if let Some(marked) = bits.regexp_string_iterators.get_mut(index) {
+                    if *marked {
+                        // Already marked, ignore
+                        return;
+                    }
+                    *marked = true;
+                    regexp_string_iterators.get(index).mark_values(&mut queues);
+                }
+            });
+        }
         #[cfg(feature = "set")]
         {
             let mut set_marks: Box<[Set]> = queues.sets.drain(..).collect();
@@ -773,6 +862,20 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
                 strings.get(index).mark_values(&mut queues);
             }
         });
+        let mut string_iterator_marks: Box<[StringIterator]> =
+            queues.string_iterators.drain(..).collect();
+        string_iterator_marks.sort();
+        string_iterator_marks.iter().for_each(|&idx| {
+            let index = idx.get_index();
+            if let Some(marked) = bits.string_iterators.get_mut(index) {
+                if *marked {
+                    // Already marked, ignore
+                    return;
+                }
+                *marked = true;
+                string_iterators.get(index).mark_values(&mut queues);
+            }
+        });
         let mut symbol_marks: Box<[Symbol]> = queues.symbols.drain(..).collect();
         symbol_marks.sort();
         symbol_marks.iter().for_each(|&idx| {
@@ -1014,9 +1117,141 @@ pub fn heap_gc(agent: &mut Agent, root_realms: &mut [Option<RealmIdentifier>], g
                 }
             }
         });
+        let mut e_sparse_marks: Box<[ElementIndex]> = queues.e_sparse.drain(..).collect();
+        e_sparse_marks.sort();
+        e_sparse_marks.iter().for_each(|&idx| {
+            let index = idx.into_index();
+            if let Some(marked) = bits.e_sparse.get_mut(index) {
+                if *marked {
+                    // Already marked, panic: Elements are uniquely owned
+                    // and any other reference existing to this entry is a sign of
+                    // a GC algorithm bug.
+                    panic!("ElementsVector was not unique");
+                }
+                *marked = true;
+                sparse.get(index).mark_values(&mut queues);
+            }
+        });
+        let mut e_float64_marks: Box<[ElementIndex]> = queues.e_float64.drain(..).collect();
+        e_float64_marks.sort();
+        e_float64_marks.iter().for_each(|&idx| {
+            let index = idx.into_index();
+            if let Some(marked) = bits.e_float64.get_mut(index) {
+                if *marked {
+                    // Already marked, panic: Elements are uniquely owned
+                    // and any other reference existing to this entry is a sign of
+                    // a GC algorithm bug.
+                    panic!("ElementsVector was not unique");
+                }
+                // Double elements hold no references to mark.
+                *marked = true;
+            }
+        });
     }
+}
 
-    sweep(agent, &bits, root_realms, gc);
+/// Number of entries of a single heap vector to mark at once above which
+/// their tracing is split across threads.
+const PARALLEL_MARK_THRESHOLD: usize = 4096;
+
+/// A range of entries to trace on a marking worker thread.
+type MarkJob<'scope> = Box<dyn FnOnce() + Send + 'scope>;
+
+/// Worker threads that trace large batches of entries during marking. The
+/// workers are started when the first large batch is marked and live until
+/// the end of the mark phase.
+struct MarkWorkers<'scope, 'env> {
+    scope: &'scope thread::Scope<'scope, 'env>,
+    thread_count: usize,
+    jobs: Vec<mpsc::Sender<MarkJob<'scope>>>,
+}
+
+impl<'scope, 'env> MarkWorkers<'scope, 'env> {
+    fn new(scope: &'scope thread::Scope<'scope, 'env>, thread_count: usize) -> Self {
+        Self {
+            scope,
+            thread_count,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Get the job queues of the workers, starting the workers if needed.
+    /// The marking thread is one of the threads tracing a batch, so there is
+    /// one worker less than there are threads.
+    fn start(&mut self) -> &[mpsc::Sender<MarkJob<'scope>>] {
+        if self.jobs.is_empty() {
+            self.jobs = (1..self.thread_count)
+                .map(|_| {
+                    let (sender, receiver) = mpsc::channel::<MarkJob<'scope>>();
+                    self.scope
+                        .spawn(move || receiver.into_iter().for_each(|job| job()));
+                    sender
+                })
+                .collect();
+        }
+        &self.jobs
+    }
+}
+
+/// Mark the entries at the given sorted indexes of a heap vector and trace the
+/// entries that were not yet marked.
+///
+/// Large batches are split into ranges of indexes that are traced in parallel
+/// by the marking thread and the workers, each pushing into its own work
+/// queues which are appended to `queues` at the end.
+fn mark_entries<'scope, T: HeapMarkAndSweep + Sync>(
+    data: &'scope [T],
+    bits: &'scope [AtomicBool],
+    indexes: &[usize],
+    queues: &mut WorkQueues,
+    workers: &mut MarkWorkers<'scope, '_>,
+) {
+    if indexes.len() < PARALLEL_MARK_THRESHOLD || workers.thread_count == 1 {
+        mark_range(data, bits, indexes, queues);
+        return;
+    }
+    let jobs = workers.start();
+    let chunk_size = indexes.len().div_ceil(jobs.len() + 1);
+    let mut ranges = indexes.chunks(chunk_size);
+    let own_range = ranges.next().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    for (job, range) in jobs.iter().zip(ranges) {
+        let range = range.to_vec();
+        let sender = sender.clone();
+        job.send(Box::new(move || {
+            let mut thread_queues = WorkQueues::default();
+            mark_range(data, bits, &range, &mut thread_queues);
+            // The receiver is alive until every sender is dropped.
+            sender.send(thread_queues).unwrap();
+        }))
+        .unwrap();
+    }
+    drop(sender);
+    mark_range(data, bits, own_range, queues);
+    // This ends when every job is done. A job that panicked ends it early,
+    // but the panic is raised again when the workers are joined at the end
+    // of the mark phase.
+    for mut thread_queues in receiver {
+        queues.append(&mut thread_queues);
+    }
+}
+
+fn mark_range<T: HeapMarkAndSweep>(
+    data: &[T],
+    bits: &[AtomicBool],
+    indexes: &[usize],
+    queues: &mut WorkQueues,
+) {
+    for &index in indexes {
+        let Some(marked) = bits.get(index) else {
+            continue;
+        };
+        // Duplicate indexes may be split across ranges: only the thread that
+        // sets the bit traces the entry.
+        if !marked.swap(true, Ordering::Relaxed) {
+            data[index].mark_values(queues);
+        }
+    }
 }
 
 fn sweep(
@@ -1025,7 +1260,11 @@ fn sweep(
     root_realms: &mut [Option<RealmIdentifier>],
     _: GcScope,
 ) {
-    let compactions = CompactionLists::create_from_bits(bits);
+    let compactions = if agent.options.gc_stress {
+        CompactionLists::create_poisoning(bits)
+    } else {
+        CompactionLists::create_from_bits(bits)
+    };
 
     for realm in root_realms {
         realm.sweep_values(&compactions);
@@ -1040,6 +1279,10 @@ fn sweep(
         symbol_id: _,
         global_symbol_registry: _,
         host_hooks: _,
+        terminating: _,
+        interrupts: _,
+        executed_instructions: _,
+        stack_base: _,
     } = agent;
 
     let Heap {
@@ -1078,14 +1321,22 @@ fn sweep(
         modules,
         numbers,
         objects,
+        object_shapes,
+        object_shape_roots,
+        gc_threshold: _,
+        owned_bytes: _,
+        gc_statistics: _,
         primitive_objects,
         promise_reaction_records,
         promise_resolving_functions,
         promises,
+        proxy_revoker_functions,
         proxys,
         realms,
         #[cfg(feature = "regexp")]
         regexps,
+        #[cfg(feature = "regexp")]
+        regexp_string_iterators,
         scripts,
         #[cfg(feature = "set")]
         sets,
@@ -1094,6 +1345,7 @@ fn sweep(
         #[cfg(feature = "shared-array-buffer")]
         shared_array_buffers,
         strings,
+        string_iterators,
         string_lookup_table,
         string_hasher: _,
         symbols,
@@ -1128,6 +1380,8 @@ fn sweep(
         e2pow16,
         e2pow24,
         e2pow32,
+        sparse,
+        float64,
     } = elements;
 
     let mut globals = globals.borrow_mut();
@@ -1213,6 +1467,16 @@ fn sweep(
                 );
             });
         }
+        if !sparse.is_empty() {
+            s.spawn(|| {
+                sweep_heap_vector_values(sparse, &compactions, &bits.e_sparse);
+            });
+        }
+        if !float64.is_empty() {
+            s.spawn(|| {
+                sweep_heap_vector_values(float64, &compactions, &bits.e_float64);
+            });
+        }
         if !e2pow4.values.is_empty() {
             s.spawn(|| {
                 sweep_heap_elements_vector_descriptors(
@@ -1349,7 +1613,7 @@ fn sweep(
         }
         if !executables.is_empty() {
             s.spawn(|| {
-                sweep_heap_vector_values(executables, &compactions, &bits.executables);
+                compact_heap_vector_values(executables, &compactions, &bits.executables);
             });
         }
         if !finalization_registrys.is_empty() {
@@ -1406,6 +1670,12 @@ fn sweep(
                 sweep_heap_vector_values(objects, &compactions, &bits.objects);
             });
         }
+        if !object_shapes.is_empty() {
+            s.spawn(|| {
+                compact_heap_vector_values(object_shapes, &compactions, &bits.object_shapes);
+                sweep_object_shape_roots(object_shape_roots, &compactions, &bits.object_shapes);
+            });
+        }
         if !primitive_objects.is_empty() {
             s.spawn(|| {
                 sweep_heap_vector_values(primitive_objects, &compactions, &bits.primitive_objects);
@@ -1434,6 +1704,15 @@ fn sweep(
                 sweep_heap_vector_values(promises, &compactions, &bits.promises);
             });
         }
+        if !proxy_revoker_functions.is_empty() {
+            s.spawn(|| {
+                sweep_heap_vector_values(
+                    proxy_revoker_functions,
+                    &compactions,
+                    &bits.proxy_revoker_functions,
+                );
+            });
+        }
         if !proxys.is_empty() {
             s.spawn(|| {
                 sweep_heap_vector_values(proxys, &compactions, &bits.proxys);
@@ -1450,6 +1729,16 @@ fn sweep(
                 sweep_heap_vector_values(regexps, &compactions, &bits.regexps);
             });
         }
+        #[cfg(feature = "regexp")]
+        if !regexp_string_iterators.is_empty() {
+            s.spawn(|| {
+                sweep_heap_vector_values(
+                    regexp_string_iterators,
+                    &compactions,
+                    &bits.regexp_string_iterators,
+                );
+            });
+        }
         if !scripts.is_empty() {
             s.spawn(|| {
                 sweep_heap_vector_values(scripts, &compactions, &bits.scripts);
@@ -1479,13 +1768,30 @@ fn sweep(
         }
         if !source_codes.is_empty() {
             s.spawn(|| {
+                for source_code in source_codes.iter_mut().flatten() {
+                    source_code.retain_marked_dependents(
+                        &bits.ecmascript_functions,
+                        &bits.executables,
+                        &bits.scripts,
+                    );
+                }
                 sweep_heap_vector_values(source_codes, &compactions, &bits.source_codes);
             });
         }
         if !strings.is_empty() {
             s.spawn(|| {
+                // Rope strings are not interned.
+                let rope_count = strings
+                    .iter()
+                    .filter(|string| string.as_ref().is_some_and(StringHeapData::is_rope))
+                    .count();
                 sweep_heap_vector_values(strings, &compactions, &bits.strings);
-                sweep_lookup_table(string_lookup_table, &compactions, &bits.strings);
+                sweep_lookup_table(string_lookup_table, &compactions, &bits.strings, rope_count);
+            });
+        }
+        if !string_iterators.is_empty() {
+            s.spawn(|| {
+                sweep_heap_vector_values(string_iterators, &compactions, &bits.string_iterators);
             });
         }
         if !symbols.is_empty() {
@@ -1579,3 +1885,194 @@ fn test_heap_gc() {
         agent.heap.globals.borrow().last().unwrap()
     );
 }
+
+#[test]
+fn test_heap_gc_stress_poisons_dead_slots() {
+    use crate::engine::context::GcScope;
+    use crate::{
+        ecmascript::execution::{agent::Options, DefaultHostHooks},
+        engine::rootable::HeapRootData,
+    };
+
+    let options = Options {
+        gc_stress: true,
+        ..Default::default()
+    };
+    let mut agent = Agent::new(options, &DefaultHostHooks);
+
+    let (mut gc, mut scope) = unsafe { GcScope::create_root() };
+    let mut gc = GcScope::new(&mut gc, &mut scope);
+    let _dead = agent.heap.create_null_object(&[]);
+    let live = HeapRootData::Object(agent.heap.create_null_object(&[]));
+    agent.heap.globals.borrow_mut().push(Some(live));
+    heap_gc(&mut agent, &mut [], gc.reborrow());
+    // The dead object's slot is poisoned and the live object did not move.
+    assert_eq!(agent.heap.objects.len(), 2);
+    assert!(agent.heap.objects[0].is_none());
+    assert!(agent.heap.objects[1].is_some());
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::atomic::Ordering, thread};
+
+    use super::{mark_entries, MarkWorkers, PARALLEL_MARK_THRESHOLD};
+    use crate::{
+        ecmascript::{
+            abstract_operations::operations_on_objects::call_function,
+            builtins::ArgumentsList,
+            execution::agent::Options,
+            types::{Function, String, Value},
+        },
+        heap::{
+            heap_bits::{HeapBits, WorkQueues},
+            Heap,
+        },
+        test_utils::{eval_script, TestAgent},
+    };
+
+    /// Mark the arrays at the indexes in batches of the given size.
+    fn mark_arrays(
+        heap: &Heap,
+        indexes: &[usize],
+        batch_size: usize,
+        thread_count: usize,
+    ) -> (Vec<bool>, WorkQueues) {
+        let bits = HeapBits::new(heap);
+        let mut queues = WorkQueues::new(heap);
+        thread::scope(|scope| {
+            let mut workers = MarkWorkers::new(scope, thread_count);
+            for batch in indexes.chunks(batch_size) {
+                mark_entries(&heap.arrays, &bits.arrays, batch, &mut queues, &mut workers);
+            }
+            // The workers are started once and then reused.
+            assert_eq!(workers.jobs.len(), thread_count - 1);
+        });
+        let bits = bits
+            .arrays
+            .iter()
+            .map(|marked| marked.load(Ordering::Relaxed))
+            .collect();
+        (bits, queues)
+    }
+
+    #[test]
+    fn parallel_marking_matches_serial_marking() {
+        let mut agent = TestAgent::new();
+        agent.eval(&format!(
+            "var a = []; for (let i = 0; i < {PARALLEL_MARK_THRESHOLD}; i++) a.push([{{ v: i }}]);"
+        ));
+        agent.run(|agent, _| {
+            let heap = &agent.heap;
+            // Every index is given twice so that duplicates end up in
+            // different ranges.
+            let mut indexes = (0..heap.arrays.len())
+                .chain(0..heap.arrays.len())
+                .collect::<Vec<_>>();
+            indexes.sort();
+            let batch_size = indexes.len() / 2;
+            assert!(batch_size >= PARALLEL_MARK_THRESHOLD);
+
+            let (serial_bits, serial_queues) = mark_arrays(heap, &indexes, batch_size, 1);
+            let (parallel_bits, parallel_queues) = mark_arrays(heap, &indexes, batch_size, 4);
+
+            assert!(parallel_bits.iter().all(|&marked| marked));
+            assert_eq!(serial_bits, parallel_bits);
+            let mut serial_objects = serial_queues.objects;
+            let mut parallel_objects = parallel_queues.objects;
+            serial_objects.sort();
+            parallel_objects.sort();
+            assert_eq!(serial_objects, parallel_objects);
+            assert_eq!(serial_queues.e_2_4.len(), parallel_queues.e_2_4.len());
+        });
+    }
+
+    #[test]
+    fn large_graph_survives_garbage_collection() {
+        let mut agent = TestAgent::new();
+        let count = 4 * PARALLEL_MARK_THRESHOLD;
+        agent.eval(&format!(
+            "var a = []; for (let i = 0; i < {count}; i++) a.push([{{ v: i }}]);"
+        ));
+        agent.gc();
+        assert_eq!(
+            agent.eval("var sum = 0; for (const x of a) sum += x[0].v; sum"),
+            (count * (count - 1) / 2).to_string()
+        );
+    }
+
+    fn stress_agent() -> TestAgent {
+        TestAgent::with_options(Options {
+            gc_stress: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn gc_stress_runs_scripts() {
+        let mut agent = stress_agent();
+        assert_eq!(
+            agent.eval(
+                "function make(n) {
+                    let s = '';
+                    for (let i = 0; i < n; i++) s += String.fromCharCode(97 + i);
+                    return { s, list: [s, s.length], upper: () => s.toUpperCase() };
+                }
+                var made = make(5);
+                made = make(5);
+                made.s + made.list[1] + made.upper() + [1, 2, 3].map((x) => x * 2).join()"
+            ),
+            "abcde5ABCDE2,4,6"
+        );
+        // Collections between scripts keep the global bindings.
+        agent.gc();
+        assert_eq!(agent.eval("made.upper() + made.list[0]"), "ABCDEabcde");
+    }
+
+    #[test]
+    fn gc_stress_keeps_arguments_of_embedder_calls() {
+        let mut agent = stress_agent();
+        agent.eval("function greet(a, b = a + '!') { return arguments.length + a + b; }");
+        let result = agent.run(|agent, mut gc| {
+            let greet = eval_script(agent, "greet", gc.reborrow()).unwrap();
+            let greet = Function::try_from(greet).unwrap();
+            let name = String::from_string(agent, "name".repeat(2), gc.nogc()).into_value();
+            let result = call_function(
+                agent,
+                greet,
+                Value::Undefined,
+                Some(ArgumentsList(&[name])),
+                gc.reborrow(),
+            )
+            .unwrap();
+            result.string_repr(agent, gc).as_str(agent).to_owned()
+        });
+        assert_eq!(result, "1namenamenamename!");
+    }
+
+    #[test]
+    fn gc_stress_keeps_arguments_of_nested_calls() {
+        let mut agent = stress_agent();
+        assert_eq!(
+            agent.eval(
+                "function greet(a, b = a + '!') { return arguments.length + a + b; }
+                function outer(name) { return greet(name + name) + greet(...[name]); }
+                outer('name')"
+            ),
+            "1namenamenamename!1namename!"
+        );
+    }
+
+    #[test]
+    fn gc_stress_poisons_dead_slots_between_instructions() {
+        let mut agent = stress_agent();
+        agent.eval("var live = {}; for (let i = 0; i < 10; i++) ({ i });");
+        agent.run(|agent, _| {
+            // Dead objects leave their slots behind as holes, which a
+            // compacting collection would have removed.
+            assert!(agent.heap.objects.iter().any(Option::is_none));
+            assert!(agent.heap.objects.iter().any(Option::is_some));
+        });
+        assert_eq!(agent.eval("typeof live"), "object");
+    }
+}
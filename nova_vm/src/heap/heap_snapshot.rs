@@ -0,0 +1,1248 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! ## Heap snapshots
+//!
+//! Export of the heap in the `.heapsnapshot` JSON format that the Memory tab
+//! of Chrome DevTools reads. Each live heap data entry becomes a node, and
+//! the edges of a node are the heap references that marking the entry in
+//! [`heap_gc`](super::heap_gc::heap_gc) would push into the work queues. A
+//! snapshot therefore shows exactly what the garbage collector keeps alive.
+
+use std::io::{self, BufWriter, Write};
+
+use ahash::AHashMap;
+
+use super::{
+    element_array::{ElementArrays, ElementDescriptor, Float64ElementArray, SparseElementArray},
+    heap_bits::mark_descriptors,
+    indexes::ElementIndex,
+    Heap, HeapMarkAndSweep, WorkQueues, LAST_WELL_KNOWN_SYMBOL_INDEX,
+};
+use crate::ecmascript::{
+    execution::{Agent, Environments, RealmIdentifier},
+    types::{Object, PropertyKey, String, Value, BUILTIN_STRINGS_LIST, BUILTIN_STRING_MEMORY},
+};
+
+/// Number of fields per node in the `nodes` array; see `NODE_FIELDS`.
+const NODE_FIELD_COUNT: usize = 7;
+
+const NODE_FIELDS: [&str; NODE_FIELD_COUNT] = [
+    "type",
+    "name",
+    "id",
+    "self_size",
+    "edge_count",
+    "trace_node_id",
+    "detachedness",
+];
+
+const NODE_TYPES: [&str; 15] = [
+    "hidden",
+    "array",
+    "string",
+    "object",
+    "code",
+    "closure",
+    "regexp",
+    "number",
+    "native",
+    "synthetic",
+    "concatenated string",
+    "sliced string",
+    "symbol",
+    "bigint",
+    "object shape",
+];
+
+const EDGE_FIELDS: [&str; 3] = ["type", "name_or_index", "to_node"];
+
+const EDGE_TYPES: [&str; 7] = [
+    "context", "element", "property", "internal", "hidden", "shortcut", "weak",
+];
+
+/// Longest string content used as the name of a string node.
+const MAX_STRING_NAME_LENGTH: usize = 1024;
+
+/// Node types, in the order of `NODE_TYPES`.
+#[derive(Debug, Clone, Copy)]
+enum NodeType {
+    Hidden,
+    Array,
+    String,
+    Object,
+    Code,
+    Closure,
+    RegExp,
+    Number,
+    Synthetic = 9,
+    Symbol = 12,
+    BigInt,
+    ObjectShape,
+}
+
+/// Edge types, in the order of `EDGE_TYPES`.
+#[derive(Debug, Clone, Copy)]
+enum EdgeType {
+    Element = 1,
+    Property,
+    Internal,
+}
+
+/// A heap data entry: The name of its heap vector and its index in it. The
+/// names are those of the [`WorkQueues`] fields.
+type NodeKey = (&'static str, usize);
+
+const ROOT_KEY: NodeKey = ("(GC roots)", 0);
+
+struct Node {
+    node_type: NodeType,
+    name: usize,
+    self_size: usize,
+    edge_count: usize,
+}
+
+struct Edge {
+    edge_type: EdgeType,
+    /// String table index for named edges, element index otherwise.
+    name_or_index: usize,
+    to: NodeKey,
+}
+
+struct SnapshotBuilder<'a> {
+    agent: &'a Agent,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    node_indexes: AHashMap<NodeKey, usize>,
+    strings: Vec<std::string::String>,
+    string_ids: AHashMap<std::string::String, usize>,
+    /// Lengths of element arrays, as seen by their owners.
+    element_lengths: AHashMap<NodeKey, u32>,
+}
+
+/// Write a `.heapsnapshot` of the Agent's heap. `root_realms` are the Realms
+/// kept alive by the embedder.
+pub(crate) fn write_heap_snapshot(
+    agent: &Agent,
+    root_realms: &[Option<RealmIdentifier>],
+    writer: impl Write,
+) -> io::Result<()> {
+    let mut builder = SnapshotBuilder {
+        agent,
+        nodes: Vec::new(),
+        edges: Vec::new(),
+        node_indexes: AHashMap::default(),
+        strings: Vec::new(),
+        string_ids: AHashMap::default(),
+        element_lengths: AHashMap::default(),
+    };
+    // V8 reserves the first string for a dummy name.
+    builder.string_id("<dummy>");
+    builder.add_roots(root_realms);
+    builder.add_heap_entries();
+    builder.write(&mut BufWriter::new(writer))
+}
+
+impl<'a> SnapshotBuilder<'a> {
+    fn string_id(&mut self, string: &str) -> usize {
+        if let Some(&id) = self.string_ids.get(string) {
+            return id;
+        }
+        let id = self.strings.len();
+        self.strings.push(string.to_owned());
+        self.string_ids.insert(string.to_owned(), id);
+        id
+    }
+
+    fn begin_node(&mut self, key: NodeKey, node_type: NodeType, name: &str, self_size: usize) {
+        let name = self.string_id(name);
+        self.node_indexes.insert(key, self.nodes.len());
+        self.nodes.push(Node {
+            node_type,
+            name,
+            self_size,
+            edge_count: 0,
+        });
+    }
+
+    fn add_edge(&mut self, edge_type: EdgeType, name_or_index: usize, to: NodeKey) {
+        self.nodes.last_mut().unwrap().edge_count += 1;
+        self.edges.push(Edge {
+            edge_type,
+            name_or_index,
+            to,
+        });
+    }
+
+    /// Drain the work queues into internal edges of the current node, named
+    /// after the kind of the target.
+    fn add_marked_edges(&mut self, queues: &mut WorkQueues) {
+        let mut targets = Vec::new();
+        drain_queues(queues, &mut targets, &mut self.element_lengths);
+        for target in targets {
+            let name = self.string_id(kind_name(target.0));
+            self.add_edge(EdgeType::Internal, name, target);
+        }
+    }
+
+    /// Get the heap entry that a Value refers to, if any.
+    fn value_key(&mut self, value: Value) -> Option<NodeKey> {
+        let mut queues = WorkQueues::default();
+        value.mark_values(&mut queues);
+        let mut targets = Vec::new();
+        drain_queues(&mut queues, &mut targets, &mut self.element_lengths);
+        targets.pop()
+    }
+
+    fn add_roots(&mut self, root_realms: &[Option<RealmIdentifier>]) {
+        let agent = self.agent;
+        self.begin_node(ROOT_KEY, NodeType::Synthetic, ROOT_KEY.0, 0);
+        let mut targets = root_realms
+            .iter()
+            .flatten()
+            .map(|realm| ("realms", realm.into_index()))
+            .collect::<Vec<_>>();
+        let mut queues = WorkQueues::default();
+        agent
+            .execution_context_stack
+            .iter()
+            .for_each(|ctx| ctx.mark_values(&mut queues));
+        agent
+            .stack_refs
+            .borrow()
+            .iter()
+            .for_each(|value| value.mark_values(&mut queues));
+        agent
+            .heap
+            .globals
+            .borrow()
+            .iter()
+            .flatten()
+            .for_each(|value| value.mark_values(&mut queues));
+        drain_queues(&mut queues, &mut targets, &mut self.element_lengths);
+        targets.extend((0..BUILTIN_STRINGS_LIST.len()).map(|index| ("strings", index)));
+        targets.extend((0..=LAST_WELL_KNOWN_SYMBOL_INDEX as usize).map(|index| ("symbols", index)));
+        for (index, target) in targets.into_iter().enumerate() {
+            self.add_edge(EdgeType::Element, index, target);
+        }
+    }
+
+    fn add_entries<T: HeapMarkAndSweep + 'a>(
+        &mut self,
+        kind: &'static str,
+        entries: impl Iterator<Item = Option<&'a T>>,
+    ) {
+        for (index, entry) in entries.enumerate() {
+            let Some(entry) = entry else {
+                continue;
+            };
+            let key = (kind, index);
+            let name = self.node_name(key);
+            self.begin_node(key, node_type(kind), &name, size_of::<T>());
+            if kind == "objects" {
+                self.add_property_edges(index);
+            }
+            let mut queues = WorkQueues::default();
+            entry.mark_values(&mut queues);
+            self.add_marked_edges(&mut queues);
+        }
+    }
+
+    /// Add property edges from an ordinary object to the heap values of its
+    /// data properties.
+    fn add_property_edges(&mut self, index: usize) {
+        let agent = self.agent;
+        let Some(data) = &agent.heap.objects[index] else {
+            return;
+        };
+        let keys = agent.heap.elements.get(data.shape.keys(agent));
+        let values = agent.heap.elements.get(data.values);
+        for (key, value) in keys.iter().zip(values) {
+            let (Some(key), Some(value)) = (key, value) else {
+                continue;
+            };
+            let Some(target) = self.value_key(*value) else {
+                continue;
+            };
+            // SAFETY: Shape keys are property keys.
+            let key = unsafe { PropertyKey::from_value_unchecked(*key) };
+            let name = self.string_id(&key.as_display(agent).to_string());
+            self.add_edge(EdgeType::Property, name, target);
+        }
+    }
+
+    fn add_element_arrays<const N: usize>(
+        &mut self,
+        kind: &'static str,
+        values: &'a [Option<[Option<Value>; N]>],
+        descriptors: &'a AHashMap<ElementIndex, AHashMap<u32, ElementDescriptor>>,
+    ) {
+        for (index, array) in values.iter().enumerate() {
+            let Some(array) = array else {
+                continue;
+            };
+            let key = (kind, index);
+            self.begin_node(
+                key,
+                NodeType::Array,
+                kind_name(kind),
+                size_of::<[Option<Value>; N]>(),
+            );
+            // Element arrays not owned by anything have no meaningful length.
+            let len = self.element_lengths.get(&key).copied().unwrap_or(0) as usize;
+            for (element_index, value) in array[..len.min(N)].iter().enumerate() {
+                let Some(target) = (*value).and_then(|value| self.value_key(value)) else {
+                    continue;
+                };
+                self.add_edge(EdgeType::Element, element_index, target);
+            }
+            if let Some(descriptors) = descriptors.get(&ElementIndex::from_index(index)) {
+                let mut queues = WorkQueues::default();
+                mark_descriptors(descriptors, &mut queues);
+                self.add_marked_edges(&mut queues);
+            }
+        }
+    }
+
+    fn add_sparse_element_arrays(&mut self, sparse: &'a [Option<SparseElementArray>]) {
+        for (index, array) in sparse.iter().enumerate() {
+            let Some(array) = array else {
+                continue;
+            };
+            let key = ("e_sparse", index);
+            self.begin_node(
+                key,
+                NodeType::Array,
+                kind_name("e_sparse"),
+                size_of::<SparseElementArray>() + array.entries().len() * size_of::<(u32, Value)>(),
+            );
+            for (element_index, value) in array.entries() {
+                let Some(target) = self.value_key(value) else {
+                    continue;
+                };
+                self.add_edge(EdgeType::Element, element_index as usize, target);
+            }
+            let mut queues = WorkQueues::default();
+            mark_descriptors(array.descriptors(), &mut queues);
+            self.add_marked_edges(&mut queues);
+        }
+    }
+
+    fn add_float64_element_arrays(&mut self, float64: &'a [Option<Float64ElementArray>]) {
+        for (index, array) in float64.iter().enumerate() {
+            let Some(array) = array else {
+                continue;
+            };
+            // Double elements hold no references.
+            self.begin_node(
+                ("e_float64", index),
+                NodeType::Array,
+                kind_name("e_float64"),
+                size_of::<Float64ElementArray>() + array.values.len() * size_of::<f64>(),
+            );
+        }
+    }
+
+    fn add_environments(&mut self, environments: &'a Environments) {
+        let Environments {
+            declarative,
+            function,
+            global,
+            object,
+            private: _,
+        } = environments;
+        self.add_entries(
+            "declarative_environments",
+            declarative.iter().map(Option::as_ref),
+        );
+        self.add_entries("function_environments", function.iter().map(Option::as_ref));
+        self.add_entries("global_environments", global.iter().map(Option::as_ref));
+        self.add_entries("object_environments", object.iter().map(Option::as_ref));
+    }
+
+    fn add_elements(&mut self, elements: &'a ElementArrays) {
+        let ElementArrays {
+            e2pow4,
+            e2pow6,
+            e2pow8,
+            e2pow10,
+            e2pow12,
+            e2pow16,
+            e2pow24,
+            e2pow32,
+            sparse,
+            float64,
+        } = elements;
+        self.add_element_arrays("e_2_4", &e2pow4.values, &e2pow4.descriptors);
+        self.add_element_arrays("e_2_6", &e2pow6.values, &e2pow6.descriptors);
+        self.add_element_arrays("e_2_8", &e2pow8.values, &e2pow8.descriptors);
+        self.add_element_arrays("e_2_10", &e2pow10.values, &e2pow10.descriptors);
+        self.add_element_arrays("e_2_12", &e2pow12.values, &e2pow12.descriptors);
+        self.add_element_arrays("e_2_16", &e2pow16.values, &e2pow16.descriptors);
+        self.add_element_arrays("e_2_24", &e2pow24.values, &e2pow24.descriptors);
+        self.add_element_arrays("e_2_32", &e2pow32.values, &e2pow32.descriptors);
+        self.add_sparse_element_arrays(sparse);
+        self.add_float64_element_arrays(float64);
+    }
+
+    fn add_heap_entries(&mut self) {
+        let Heap {
+            #[cfg(feature = "array-buffer")]
+            array_buffers,
+            #[cfg(feature = "array-buffer")]
+                array_buffer_detach_keys: _,
+            arrays,
+            array_iterators,
+            async_generators,
+            await_reactions,
+            bigints,
+            bound_functions,
+            builtin_constructors,
+            builtin_functions,
+            #[cfg(feature = "array-buffer")]
+            data_views,
+            #[cfg(feature = "array-buffer")]
+                data_view_byte_lengths: _,
+            #[cfg(feature = "array-buffer")]
+                data_view_byte_offsets: _,
+            #[cfg(feature = "date")]
+            dates,
+            ecmascript_functions,
+            elements,
+            embedder_objects,
+            environments,
+            errors,
+            executables,
+            finalization_registrys,
+            generators,
+            globals: _,
+            maps,
+            map_iterators,
+            numbers,
+            objects,
+            object_shapes,
+            object_shape_roots: _,
+            gc_threshold: _,
+            owned_bytes: _,
+            gc_statistics: _,
+            primitive_objects,
+            promise_reaction_records,
+            promise_resolving_functions,
+            promises,
+            proxy_revoker_functions,
+            proxys,
+            realms,
+            #[cfg(feature = "regexp")]
+            regexps,
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators,
+            #[cfg(feature = "set")]
+            sets,
+            #[cfg(feature = "set")]
+            set_iterators,
+            #[cfg(feature = "shared-array-buffer")]
+            shared_array_buffers,
+            string_iterators,
+            symbols,
+            #[cfg(feature = "array-buffer")]
+            typed_arrays,
+            #[cfg(feature = "array-buffer")]
+                typed_array_byte_lengths: _,
+            #[cfg(feature = "array-buffer")]
+                typed_array_byte_offsets: _,
+            #[cfg(feature = "array-buffer")]
+                typed_array_array_lengths: _,
+            #[cfg(feature = "weak-refs")]
+            weak_maps,
+            #[cfg(feature = "weak-refs")]
+            weak_refs,
+            #[cfg(feature = "weak-refs")]
+            weak_sets,
+            modules,
+            scripts,
+            source_codes,
+            strings,
+            string_lookup_table: _,
+            string_hasher: _,
+        } = &self.agent.heap;
+        #[cfg(feature = "array-buffer")]
+        self.add_entries("array_buffers", array_buffers.iter().map(Option::as_ref));
+        self.add_entries("arrays", arrays.iter().map(Option::as_ref));
+        self.add_entries(
+            "array_iterators",
+            array_iterators.iter().map(Option::as_ref),
+        );
+        self.add_entries(
+            "async_generators",
+            async_generators.iter().map(Option::as_ref),
+        );
+        self.add_entries(
+            "await_reactions",
+            await_reactions.iter().map(Option::as_ref),
+        );
+        self.add_entries("bigints", bigints.iter().map(Option::as_ref));
+        self.add_entries(
+            "bound_functions",
+            bound_functions.iter().map(Option::as_ref),
+        );
+        self.add_entries(
+            "builtin_constructors",
+            builtin_constructors.iter().map(Option::as_ref),
+        );
+        self.add_entries(
+            "builtin_functions",
+            builtin_functions.iter().map(Option::as_ref),
+        );
+        #[cfg(feature = "array-buffer")]
+        self.add_entries("data_views", data_views.iter().map(Option::as_ref));
+        #[cfg(feature = "date")]
+        self.add_entries("dates", dates.iter().map(Option::as_ref));
+        self.add_entries(
+            "ecmascript_functions",
+            ecmascript_functions.iter().map(Option::as_ref),
+        );
+        self.add_entries(
+            "embedder_objects",
+            embedder_objects.iter().map(Option::as_ref),
+        );
+        self.add_entries("errors", errors.iter().map(Option::as_ref));
+        self.add_entries("executables", executables.iter().map(Some));
+        self.add_entries(
+            "finalization_registrys",
+            finalization_registrys.iter().map(Option::as_ref),
+        );
+        self.add_entries("generators", generators.iter().map(Option::as_ref));
+        self.add_entries("maps", maps.iter().map(Option::as_ref));
+        self.add_entries("map_iterators", map_iterators.iter().map(Option::as_ref));
+        self.add_entries("numbers", numbers.iter().map(Option::as_ref));
+        self.add_entries("objects", objects.iter().map(Option::as_ref));
+        self.add_entries("object_shapes", object_shapes.iter().map(Some));
+        self.add_entries(
+            "primitive_objects",
+            primitive_objects.iter().map(Option::as_ref),
+        );
+        self.add_entries(
+            "promise_reaction_records",
+            promise_reaction_records.iter().map(Option::as_ref),
+        );
+        self.add_entries(
+            "promise_resolving_functions",
+            promise_resolving_functions.iter().map(Option::as_ref),
+        );
+        self.add_entries("promises", promises.iter().map(Option::as_ref));
+        self.add_entries(
+            "proxy_revoker_functions",
+            proxy_revoker_functions.iter().map(Option::as_ref),
+        );
+        self.add_entries("proxys", proxys.iter().map(Option::as_ref));
+        self.add_entries("realms", realms.iter().map(Option::as_ref));
+        #[cfg(feature = "regexp")]
+        self.add_entries("regexps", regexps.iter().map(Option::as_ref));
+        #[cfg(feature = "regexp")]
+        self.add_entries(
+            "regexp_string_iterators",
+            regexp_string_iterators.iter().map(Option::as_ref),
+        );
+        #[cfg(feature = "set")]
+        self.add_entries("sets", sets.iter().map(Option::as_ref));
+        #[cfg(feature = "set")]
+        self.add_entries("set_iterators", set_iterators.iter().map(Option::as_ref));
+        #[cfg(feature = "shared-array-buffer")]
+        self.add_entries(
+            "shared_array_buffers",
+            shared_array_buffers.iter().map(Option::as_ref),
+        );
+        self.add_entries(
+            "string_iterators",
+            string_iterators.iter().map(Option::as_ref),
+        );
+        self.add_entries("symbols", symbols.iter().map(Option::as_ref));
+        #[cfg(feature = "array-buffer")]
+        self.add_entries("typed_arrays", typed_arrays.iter().map(Option::as_ref));
+        #[cfg(feature = "weak-refs")]
+        self.add_entries("weak_maps", weak_maps.iter().map(Option::as_ref));
+        #[cfg(feature = "weak-refs")]
+        self.add_entries("weak_refs", weak_refs.iter().map(Option::as_ref));
+        #[cfg(feature = "weak-refs")]
+        self.add_entries("weak_sets", weak_sets.iter().map(Option::as_ref));
+        self.add_entries("modules", modules.iter().map(Option::as_ref));
+        self.add_entries("scripts", scripts.iter().map(Option::as_ref));
+        self.add_entries("source_codes", source_codes.iter().map(Option::as_ref));
+        self.add_entries("strings", strings.iter().map(Option::as_ref));
+        self.add_environments(environments);
+        // Element arrays go last so that the lengths recorded by their owners
+        // are known.
+        self.add_elements(elements);
+    }
+
+    fn node_name(&self, (kind, index): NodeKey) -> std::string::String {
+        let heap = &self.agent.heap;
+        match kind {
+            "strings" => heap.strings[index]
+                .as_ref()
+                .unwrap()
+                .as_wtf8()
+                .to_string_lossy()
+                .chars()
+                .take(MAX_STRING_NAME_LENGTH)
+                .collect(),
+            "symbols" => {
+                let descriptor = heap.symbols[index]
+                    .as_ref()
+                    .and_then(|symbol| symbol.descriptor)
+                    .map(|descriptor| self.string_content(descriptor));
+                format!("Symbol({})", descriptor.unwrap_or_default())
+            }
+            "ecmascript_functions" | "builtin_functions" | "builtin_constructors" => {
+                self.function_name((kind, index)).unwrap_or_default()
+            }
+            "objects" => self
+                .constructor_name(index)
+                .unwrap_or_else(|| "Object".to_owned()),
+            _ => kind_name(kind).to_owned(),
+        }
+    }
+
+    fn string_content(&self, string: String) -> std::string::String {
+        match string {
+            String::String(string) => self.agent[string].as_wtf8().to_string_lossy().into_owned(),
+            String::SmallString(string) => string.as_str().to_owned(),
+        }
+    }
+
+    /// Get the value of an own data property of an ordinary object.
+    fn own_data_property(&self, index: usize, key: String) -> Option<Value> {
+        let agent = self.agent;
+        let data = agent.heap.objects.get(index)?.as_ref()?;
+        let keys = agent.heap.elements.get(data.shape.keys(agent));
+        let position = keys
+            .iter()
+            .position(|&entry| entry == Some(Value::from(key)))?;
+        agent.heap.elements.get(data.values)[position]
+    }
+
+    /// Get the name of a function: Its \[\[InitialName]] or the value of its
+    /// "name" property.
+    fn function_name(&self, (kind, index): NodeKey) -> Option<std::string::String> {
+        let heap = &self.agent.heap;
+        let (name, object_index) = match kind {
+            "ecmascript_functions" => {
+                let data = heap.ecmascript_functions[index].as_ref()?;
+                (data.name, data.object_index)
+            }
+            "builtin_functions" => {
+                let data = heap.builtin_functions[index].as_ref()?;
+                (data.initial_name, data.object_index)
+            }
+            "builtin_constructors" => {
+                let data = heap.builtin_constructors[index].as_ref()?;
+                (None, data.object_index)
+            }
+            _ => return None,
+        };
+        let name = match name {
+            Some(name) => name,
+            None => {
+                let name =
+                    self.own_data_property(object_index?.get_index(), BUILTIN_STRING_MEMORY.name)?;
+                String::try_from(name).ok()?
+            }
+        };
+        Some(self.string_content(name))
+    }
+
+    /// Get the name of the "constructor" of an ordinary object's prototype.
+    fn constructor_name(&self, index: usize) -> Option<std::string::String> {
+        let agent = self.agent;
+        let data = agent.heap.objects[index].as_ref()?;
+        let Some(Object::Object(prototype)) = data.shape.prototype(agent) else {
+            return None;
+        };
+        let constructor =
+            self.own_data_property(prototype.get_index(), BUILTIN_STRING_MEMORY.constructor)?;
+        let key = match constructor {
+            Value::ECMAScriptFunction(function) => ("ecmascript_functions", function.get_index()),
+            Value::BuiltinFunction(function) => ("builtin_functions", function.get_index()),
+            Value::BuiltinConstructorFunction(function) => {
+                ("builtin_constructors", function.get_index())
+            }
+            _ => return None,
+        };
+        self.function_name(key).filter(|name| !name.is_empty())
+    }
+
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        // Resolve edge targets to node offsets, dropping edges to entries
+        // that are not in the heap vectors.
+        let mut edges = self.edges.iter();
+        let mut edge_counts = Vec::with_capacity(self.nodes.len());
+        let mut resolved_edges = Vec::with_capacity(self.edges.len());
+        for node in &self.nodes {
+            let resolved_count = resolved_edges.len();
+            for edge in edges.by_ref().take(node.edge_count) {
+                if let Some(&to_node) = self.node_indexes.get(&edge.to) {
+                    resolved_edges.push((edge, to_node));
+                }
+            }
+            edge_counts.push(resolved_edges.len() - resolved_count);
+        }
+
+        write!(writer, "{{\"snapshot\":{{\"meta\":{{\"node_fields\":")?;
+        write_json_strings(writer, &NODE_FIELDS)?;
+        write!(writer, ",\"node_types\":[")?;
+        write_json_strings(writer, &NODE_TYPES)?;
+        write!(
+            writer,
+            ",\"string\",\"number\",\"number\",\"number\",\"number\",\"number\"],\"edge_fields\":"
+        )?;
+        write_json_strings(writer, &EDGE_FIELDS)?;
+        write!(writer, ",\"edge_types\":[")?;
+        write_json_strings(writer, &EDGE_TYPES)?;
+        write!(
+            writer,
+            ",\"string_or_number\",\"node\"],\
+            \"trace_function_info_fields\":[\"function_id\",\"name\",\"script_name\",\"script_id\",\"line\",\"column\"],\
+            \"trace_node_fields\":[\"id\",\"function_info_index\",\"count\",\"size\",\"children\"],\
+            \"sample_fields\":[\"timestamp_us\",\"last_assigned_id\"],\
+            \"location_fields\":[\"object_index\",\"script_id\",\"line\",\"column\"]}},\
+            \"node_count\":{},\"edge_count\":{},\"trace_function_count\":0}},",
+            self.nodes.len(),
+            resolved_edges.len()
+        )?;
+
+        write!(writer, "\n\"nodes\":[")?;
+        for (index, (node, edge_count)) in self.nodes.iter().zip(edge_counts).enumerate() {
+            if index > 0 {
+                writeln!(writer, ",")?;
+            }
+            // Odd ids are used for heap objects in V8.
+            write!(
+                writer,
+                "{},{},{},{},{},0,0",
+                node.node_type as u8,
+                node.name,
+                index * 2 + 1,
+                node.self_size,
+                edge_count
+            )?;
+        }
+
+        write!(writer, "],\n\"edges\":[")?;
+        for (index, (edge, to_node)) in resolved_edges.into_iter().enumerate() {
+            if index > 0 {
+                writeln!(writer, ",")?;
+            }
+            write!(
+                writer,
+                "{},{},{}",
+                edge.edge_type as u8,
+                edge.name_or_index,
+                to_node * NODE_FIELD_COUNT
+            )?;
+        }
+
+        write!(
+            writer,
+            "],\n\"trace_function_infos\":[],\n\"trace_tree\":[],\n\"samples\":[],\n\"locations\":[],\n\"strings\":["
+        )?;
+        for (index, string) in self.strings.iter().enumerate() {
+            if index > 0 {
+                writeln!(writer, ",")?;
+            }
+            write_json_string(writer, string)?;
+        }
+        writeln!(writer, "]}}")?;
+        writer.flush()
+    }
+}
+
+fn node_type(kind: &str) -> NodeType {
+    match kind {
+        "strings" => NodeType::String,
+        "symbols" => NodeType::Symbol,
+        "numbers" => NodeType::Number,
+        "bigints" => NodeType::BigInt,
+        "regexps" => NodeType::RegExp,
+        "object_shapes" => NodeType::ObjectShape,
+        "bound_functions"
+        | "builtin_constructors"
+        | "builtin_functions"
+        | "ecmascript_functions"
+        | "promise_resolving_functions"
+        | "proxy_revoker_functions" => NodeType::Closure,
+        "executables" | "modules" | "scripts" | "source_codes" => NodeType::Code,
+        "await_reactions"
+        | "declarative_environments"
+        | "function_environments"
+        | "global_environments"
+        | "object_environments"
+        | "promise_reaction_records"
+        | "realms" => NodeType::Hidden,
+        _ => NodeType::Object,
+    }
+}
+
+/// Get the class name shown for the kind of heap data.
+fn kind_name(kind: &'static str) -> &'static str {
+    match kind {
+        "array_buffers" => "ArrayBuffer",
+        "arrays" => "Array",
+        "array_iterators" => "Array Iterator",
+        "async_generators" => "AsyncGenerator",
+        "await_reactions" => "(await reaction)",
+        "bigints" => "(bigint)",
+        "bound_functions" => "(bound function)",
+        "builtin_constructors" | "builtin_functions" | "ecmascript_functions" => "Function",
+        "data_views" => "DataView",
+        "dates" => "Date",
+        "declarative_environments"
+        | "function_environments"
+        | "global_environments"
+        | "object_environments" => "(context)",
+        "e_2_4" | "e_2_6" | "e_2_8" | "e_2_10" | "e_2_12" | "e_2_16" | "e_2_24" | "e_2_32"
+        | "e_sparse" | "e_float64" => "(elements)",
+        "embedder_objects" => "(embedder object)",
+        "errors" => "Error",
+        "executables" => "(bytecode)",
+        "finalization_registrys" => "FinalizationRegistry",
+        "generators" => "Generator",
+        "maps" => "Map",
+        "map_iterators" => "Map Iterator",
+        "modules" => "(module)",
+        "numbers" => "(heap number)",
+        "objects" => "Object",
+        "object_shapes" => "(object shape)",
+        "primitive_objects" => "(primitive object)",
+        "promise_reaction_records" => "(promise reaction)",
+        "promise_resolving_functions" => "(promise resolving function)",
+        "promises" => "Promise",
+        "proxy_revoker_functions" => "(proxy revoker function)",
+        "proxys" => "Proxy",
+        "realms" => "(realm)",
+        "regexps" => "RegExp",
+        "regexp_string_iterators" => "RegExp String Iterator",
+        "scripts" => "(script)",
+        "sets" => "Set",
+        "set_iterators" => "Set Iterator",
+        "shared_array_buffers" => "SharedArrayBuffer",
+        "source_codes" => "(source code)",
+        "strings" => "(string)",
+        "string_iterators" => "String Iterator",
+        "symbols" => "(symbol)",
+        "typed_arrays" => "TypedArray",
+        "weak_maps" => "WeakMap",
+        "weak_refs" => "WeakRef",
+        "weak_sets" => "WeakSet",
+        _ => kind,
+    }
+}
+
+fn write_json_strings(writer: &mut impl Write, strings: &[&str]) -> io::Result<()> {
+    write!(writer, "[")?;
+    for (index, string) in strings.iter().enumerate() {
+        if index > 0 {
+            write!(writer, ",")?;
+        }
+        write_json_string(writer, string)?;
+    }
+    write!(writer, "]")
+}
+
+fn write_json_string(writer: &mut impl Write, string: &str) -> io::Result<()> {
+    write!(writer, "\"")?;
+    for character in string.chars() {
+        match character {
+            '"' => write!(writer, "\\\"")?,
+            '\\' => write!(writer, "\\\\")?,
+            '\n' => write!(writer, "\\n")?,
+            '\r' => write!(writer, "\\r")?,
+            '\t' => write!(writer, "\\t")?,
+            character if (character as u32) < 0x20 => {
+                write!(writer, "\\u{:04x}", character as u32)?
+            }
+            character => write!(writer, "{}", character)?,
+        }
+    }
+    write!(writer, "\"")
+}
+
+/// Drain the work queues into a list of the queued heap entries. The lengths
+/// of queued element arrays are recorded in `element_lengths`.
+fn drain_queues(
+    queues: &mut WorkQueues,
+    targets: &mut Vec<NodeKey>,
+    element_lengths: &mut AHashMap<NodeKey, u32>,
+) {
+    let WorkQueues {
+        #[cfg(feature = "array-buffer")]
+        array_buffers,
+        arrays,
+        array_iterators,
+        async_generators,
+        await_reactions,
+        bigints,
+        bound_functions,
+        builtin_constructors,
+        builtin_functions,
+        #[cfg(feature = "array-buffer")]
+        data_views,
+        #[cfg(feature = "date")]
+        dates,
+        declarative_environments,
+        e_2_10,
+        e_2_12,
+        e_2_16,
+        e_2_24,
+        e_2_32,
+        e_2_4,
+        e_2_6,
+        e_2_8,
+        e_sparse,
+        e_float64,
+        ecmascript_functions,
+        embedder_objects,
+        source_codes,
+        errors,
+        executables,
+        finalization_registrys,
+        function_environments,
+        generators,
+        global_environments,
+        maps,
+        map_iterators,
+        modules,
+        numbers,
+        object_environments,
+        objects,
+        object_shapes,
+        primitive_objects,
+        promises,
+        promise_reaction_records,
+        promise_resolving_functions,
+        proxy_revoker_functions,
+        proxys,
+        realms,
+        #[cfg(feature = "regexp")]
+        regexps,
+        scripts,
+        #[cfg(feature = "set")]
+        sets,
+        #[cfg(feature = "set")]
+        set_iterators,
+        #[cfg(feature = "shared-array-buffer")]
+        shared_array_buffers,
+        strings,
+        string_iterators,
+        #[cfg(feature = "regexp")]
+        regexp_string_iterators,
+        symbols,
+        #[cfg(feature = "array-buffer")]
+        typed_arrays,
+        #[cfg(feature = "weak-refs")]
+        weak_maps,
+        #[cfg(feature = "weak-refs")]
+        weak_refs,
+        #[cfg(feature = "weak-refs")]
+        weak_sets,
+    } = queues;
+    #[cfg(feature = "array-buffer")]
+    for idx in array_buffers.drain(..) {
+        targets.push(("array_buffers", idx.get_index()));
+    }
+    for idx in arrays.drain(..) {
+        targets.push(("arrays", idx.get_index()));
+    }
+    for idx in array_iterators.drain(..) {
+        targets.push(("array_iterators", idx.get_index()));
+    }
+    for idx in async_generators.drain(..) {
+        targets.push(("async_generators", idx.get_index()));
+    }
+    for idx in await_reactions.drain(..) {
+        targets.push(("await_reactions", idx.into_index()));
+    }
+    for idx in bigints.drain(..) {
+        targets.push(("bigints", idx.get_index()));
+    }
+    for idx in bound_functions.drain(..) {
+        targets.push(("bound_functions", idx.get_index()));
+    }
+    for idx in builtin_constructors.drain(..) {
+        targets.push(("builtin_constructors", idx.get_index()));
+    }
+    for idx in builtin_functions.drain(..) {
+        targets.push(("builtin_functions", idx.get_index()));
+    }
+    #[cfg(feature = "array-buffer")]
+    for idx in data_views.drain(..) {
+        targets.push(("data_views", idx.get_index()));
+    }
+    #[cfg(feature = "date")]
+    for idx in dates.drain(..) {
+        targets.push(("dates", idx.get_index()));
+    }
+    for idx in declarative_environments.drain(..) {
+        targets.push(("declarative_environments", idx.into_index()));
+    }
+    for (idx, len) in e_2_10.drain(..) {
+        let key = ("e_2_10", idx.into_index());
+        element_lengths.insert(key, len);
+        targets.push(key);
+    }
+    for (idx, len) in e_2_12.drain(..) {
+        let key = ("e_2_12", idx.into_index());
+        element_lengths.insert(key, len);
+        targets.push(key);
+    }
+    for (idx, len) in e_2_16.drain(..) {
+        let key = ("e_2_16", idx.into_index());
+        element_lengths.insert(key, len);
+        targets.push(key);
+    }
+    for (idx, len) in e_2_24.drain(..) {
+        let key = ("e_2_24", idx.into_index());
+        element_lengths.insert(key, len);
+        targets.push(key);
+    }
+    for (idx, len) in e_2_32.drain(..) {
+        let key = ("e_2_32", idx.into_index());
+        element_lengths.insert(key, len);
+        targets.push(key);
+    }
+    for (idx, len) in e_2_4.drain(..) {
+        let key = ("e_2_4", idx.into_index());
+        element_lengths.insert(key, len);
+        targets.push(key);
+    }
+    for (idx, len) in e_2_6.drain(..) {
+        let key = ("e_2_6", idx.into_index());
+        element_lengths.insert(key, len);
+        targets.push(key);
+    }
+    for (idx, len) in e_2_8.drain(..) {
+        let key = ("e_2_8", idx.into_index());
+        element_lengths.insert(key, len);
+        targets.push(key);
+    }
+    for idx in e_sparse.drain(..) {
+        targets.push(("e_sparse", idx.into_index()));
+    }
+    for idx in e_float64.drain(..) {
+        targets.push(("e_float64", idx.into_index()));
+    }
+    for idx in ecmascript_functions.drain(..) {
+        targets.push(("ecmascript_functions", idx.get_index()));
+    }
+    for idx in embedder_objects.drain(..) {
+        targets.push(("embedder_objects", idx.get_index()));
+    }
+    for idx in source_codes.drain(..) {
+        targets.push(("source_codes", idx.get_index()));
+    }
+    for idx in errors.drain(..) {
+        targets.push(("errors", idx.get_index()));
+    }
+    for idx in executables.drain(..) {
+        targets.push(("executables", idx.get_index()));
+    }
+    for idx in finalization_registrys.drain(..) {
+        targets.push(("finalization_registrys", idx.get_index()));
+    }
+    for idx in function_environments.drain(..) {
+        targets.push(("function_environments", idx.into_index()));
+    }
+    for idx in generators.drain(..) {
+        targets.push(("generators", idx.get_index()));
+    }
+    for idx in global_environments.drain(..) {
+        targets.push(("global_environments", idx.into_index()));
+    }
+    for idx in maps.drain(..) {
+        targets.push(("maps", idx.get_index()));
+    }
+    for idx in map_iterators.drain(..) {
+        targets.push(("map_iterators", idx.get_index()));
+    }
+    for idx in modules.drain(..) {
+        targets.push(("modules", idx.get_index()));
+    }
+    for idx in numbers.drain(..) {
+        targets.push(("numbers", idx.get_index()));
+    }
+    for idx in object_environments.drain(..) {
+        targets.push(("object_environments", idx.into_index()));
+    }
+    for idx in objects.drain(..) {
+        targets.push(("objects", idx.get_index()));
+    }
+    for idx in object_shapes.drain(..) {
+        targets.push(("object_shapes", idx.get_index()));
+    }
+    for idx in primitive_objects.drain(..) {
+        targets.push(("primitive_objects", idx.get_index()));
+    }
+    for idx in promises.drain(..) {
+        targets.push(("promises", idx.get_index()));
+    }
+    for idx in promise_reaction_records.drain(..) {
+        targets.push(("promise_reaction_records", idx.get_index()));
+    }
+    for idx in promise_resolving_functions.drain(..) {
+        targets.push(("promise_resolving_functions", idx.get_index()));
+    }
+    for idx in proxy_revoker_functions.drain(..) {
+        targets.push(("proxy_revoker_functions", idx.get_index()));
+    }
+    for idx in proxys.drain(..) {
+        targets.push(("proxys", idx.get_index()));
+    }
+    for idx in realms.drain(..) {
+        targets.push(("realms", idx.into_index()));
+    }
+    #[cfg(feature = "regexp")]
+    for idx in regexps.drain(..) {
+        targets.push(("regexps", idx.get_index()));
+    }
+    for idx in scripts.drain(..) {
+        targets.push(("scripts", idx.into_index()));
+    }
+    #[cfg(feature = "set")]
+    for idx in sets.drain(..) {
+        targets.push(("sets", idx.get_index()));
+    }
+    #[cfg(feature = "set")]
+    for idx in set_iterators.drain(..) {
+        targets.push(("set_iterators", idx.get_index()));
+    }
+    #[cfg(feature = "shared-array-buffer")]
+    for idx in shared_array_buffers.drain(..) {
+        targets.push(("shared_array_buffers", idx.get_index()));
+    }
+    for idx in strings.drain(..) {
+        targets.push(("strings", idx.get_index()));
+    }
+    for idx in string_iterators.drain(..) {
+        targets.push(("string_iterators", idx.get_index()));
+    }
+    #[cfg(feature = "regexp")]
+    for idx in regexp_string_iterators.drain(..) {
+        targets.push(("regexp_string_iterators", idx.get_index()));
+    }
+    for idx in symbols.drain(..) {
+        targets.push(("symbols", idx.get_index()));
+    }
+    #[cfg(feature = "array-buffer")]
+    for idx in typed_arrays.drain(..) {
+        targets.push(("typed_arrays", idx.into_index()));
+    }
+    #[cfg(feature = "weak-refs")]
+    for idx in weak_maps.drain(..) {
+        targets.push(("weak_maps", idx.get_index()));
+    }
+    #[cfg(feature = "weak-refs")]
+    for idx in weak_refs.drain(..) {
+        targets.push(("weak_refs", idx.get_index()));
+    }
+    #[cfg(feature = "weak-refs")]
+    for idx in weak_sets.drain(..) {
+        targets.push(("weak_sets", idx.get_index()));
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod test {
+    use sonic_rs::{JsonContainerTrait, JsonValueTrait};
+
+    use super::{EDGE_FIELDS, NODE_FIELDS, NODE_FIELD_COUNT};
+    use crate::{
+        ecmascript::execution::{
+            agent::{GcAgent, Options},
+            DefaultHostHooks,
+        },
+        test_utils::eval_script,
+    };
+
+    fn numbers(value: &sonic_rs::Value) -> Vec<usize> {
+        value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|number| number.as_u64().unwrap() as usize)
+            .collect()
+    }
+
+    #[test]
+    fn heap_snapshot_layout() {
+        let mut agent = GcAgent::new(Options::default(), &DefaultHostHooks);
+        let realm = agent.create_default_realm();
+        agent.run_in_realm(&realm, |agent, gc| {
+            eval_script(
+                agent,
+                "globalThis.leak = { payload: 'snapshot payload ' + 1 };",
+                gc,
+            )
+            .unwrap();
+        });
+        agent.gc();
+        let mut snapshot = Vec::new();
+        agent.write_heap_snapshot(&mut snapshot).unwrap();
+        let snapshot =
+            sonic_rs::from_slice::<sonic_rs::Value>(&snapshot).expect("snapshot is valid JSON");
+
+        let meta = &snapshot["snapshot"]["meta"];
+        let node_fields = meta["node_fields"].as_array().unwrap();
+        assert_eq!(node_fields.len(), NODE_FIELDS.len());
+        assert_eq!(node_fields.len(), NODE_FIELD_COUNT);
+        assert_eq!(
+            meta["edge_fields"].as_array().unwrap().len(),
+            EDGE_FIELDS.len()
+        );
+        let node_types = meta["node_types"][0].as_array().unwrap();
+        let edge_types = meta["edge_types"][0].as_array().unwrap();
+
+        let nodes = numbers(&snapshot["nodes"]);
+        let edges = numbers(&snapshot["edges"]);
+        let strings = snapshot["strings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|string| string.as_str().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        let node_count = snapshot["snapshot"]["node_count"].as_u64().unwrap() as usize;
+        let edge_count = snapshot["snapshot"]["edge_count"].as_u64().unwrap() as usize;
+        assert_eq!(nodes.len(), node_count * NODE_FIELD_COUNT);
+        assert_eq!(edges.len(), edge_count * EDGE_FIELDS.len());
+        assert_eq!(strings[0], "<dummy>");
+
+        // Nodes list their edges in order: The edge counts add up to the
+        // number of edges, and edges point to the start of a node.
+        let nodes = nodes.chunks(NODE_FIELD_COUNT).collect::<Vec<_>>();
+        let edges = edges.chunks(EDGE_FIELDS.len()).collect::<Vec<_>>();
+        assert_eq!(nodes.iter().map(|node| node[4]).sum::<usize>(), edge_count);
+        assert_eq!(strings[nodes[0][1]], "(GC roots)");
+        for node in &nodes {
+            assert!(node[0] < node_types.len());
+            assert!(node[1] < strings.len());
+        }
+        for edge in &edges {
+            assert!(edge[0] < edge_types.len());
+            assert_eq!(edge[2] % NODE_FIELD_COUNT, 0);
+            assert!(edge[2] / NODE_FIELD_COUNT < node_count);
+        }
+
+        // The object's property edge leads to the payload string.
+        let payload = strings.iter().position(|s| s == "payload").unwrap();
+        let property = edge_types
+            .iter()
+            .position(|edge_type| edge_type.as_str() == Some("property"))
+            .unwrap();
+        let target = edges
+            .iter()
+            .find(|edge| edge[0] == property && edge[1] == payload)
+            .map(|edge| nodes[edge[2] / NODE_FIELD_COUNT])
+            .expect("payload property edge");
+        assert_eq!(node_types[target[0]].as_str(), Some("string"));
+        assert_eq!(strings[target[1]], "snapshot payload 1");
+    }
+}
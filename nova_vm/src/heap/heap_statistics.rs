@@ -0,0 +1,464 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::time::Duration;
+
+use super::{
+    element_array::{ElementArrayKey, ElementArrays},
+    Heap, OwnedAllocations,
+};
+use crate::ecmascript::execution::Environments;
+
+/// Visitor over every vector of heap data.
+pub(crate) trait HeapVectorVisitor {
+    /// Visit the slots of a heap vector. `live_count` counts the slots that
+    /// hold data and `owned_bytes` the bytes that the data owns outside of
+    /// the slots, see [`OwnedAllocations`]. They are only called if needed as
+    /// they may walk all slots.
+    fn visit<T>(
+        &mut self,
+        name: &'static str,
+        slots: &[T],
+        live_count: impl FnOnce() -> usize,
+        owned_bytes: impl FnOnce() -> usize,
+    );
+}
+
+fn visit_option_vector<T>(
+    visitor: &mut impl HeapVectorVisitor,
+    name: &'static str,
+    slots: &[Option<T>],
+) {
+    visitor.visit(
+        name,
+        slots,
+        || slots.iter().filter(|slot| slot.is_some()).count(),
+        || 0,
+    );
+}
+
+fn visit_owning_vector<T: OwnedAllocations>(
+    visitor: &mut impl HeapVectorVisitor,
+    name: &'static str,
+    slots: &[Option<T>],
+) {
+    visitor.visit(
+        name,
+        slots,
+        || slots.iter().filter(|slot| slot.is_some()).count(),
+        || slots.iter().flatten().map(T::owned_bytes).sum(),
+    );
+}
+
+/// Garbage collection statistics of a heap.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct GcStatistics {
+    pub(crate) collections: u64,
+    pub(crate) collected_count: u64,
+    pub(crate) total_pause: Duration,
+    pub(crate) max_pause: Duration,
+}
+
+/// Statistics of a single heap vector.
+#[derive(Debug, Clone, Copy)]
+pub struct HeapKindStatistics {
+    /// Name of the kind of heap data.
+    pub name: &'static str,
+    /// Number of slots holding data.
+    pub live_count: usize,
+    /// Number of slots in the vector, including empty ones.
+    pub slot_count: usize,
+    /// Number of bytes taken up by the slots.
+    pub bytes: usize,
+    /// Approximate number of bytes owned by the data outside of the slots,
+    /// such as string contents and ArrayBuffer data blocks.
+    pub owned_bytes: usize,
+}
+
+/// Statistics of an Agent's heap, see
+/// [`GcAgent::heap_statistics`](crate::ecmascript::execution::agent::GcAgent::heap_statistics).
+#[derive(Debug, Clone)]
+pub struct HeapStatistics {
+    /// Statistics of each heap vector, in alphabetical order.
+    pub kinds: Vec<HeapKindStatistics>,
+    /// Total number of bytes taken up by heap vector slots and the data they
+    /// own.
+    pub total_bytes: usize,
+    /// Number of garbage collections performed.
+    pub gc_count: u64,
+    /// Total number of heap data entries freed by garbage collection.
+    pub gc_collected_count: u64,
+    /// Total time spent in garbage collection.
+    pub gc_total_pause: Duration,
+    /// Longest single garbage collection pause.
+    pub gc_max_pause: Duration,
+}
+
+impl GcStatistics {
+    pub(crate) fn record(&mut self, pause: Duration, collected_count: usize) {
+        self.collections += 1;
+        self.collected_count += collected_count as u64;
+        self.total_pause += pause;
+        self.max_pause = self.max_pause.max(pause);
+    }
+}
+
+impl HeapStatistics {
+    pub(crate) fn new(heap: &Heap) -> Self {
+        struct Collector(Vec<HeapKindStatistics>);
+        impl HeapVectorVisitor for Collector {
+            fn visit<T>(
+                &mut self,
+                name: &'static str,
+                slots: &[T],
+                live_count: impl FnOnce() -> usize,
+                owned_bytes: impl FnOnce() -> usize,
+            ) {
+                self.0.push(HeapKindStatistics {
+                    name,
+                    live_count: live_count(),
+                    slot_count: slots.len(),
+                    bytes: size_of_val(slots),
+                    owned_bytes: owned_bytes(),
+                });
+            }
+        }
+        let mut collector = Collector(Vec::new());
+        heap.visit_vectors(&mut collector);
+        let mut kinds = collector.0;
+        kinds.sort_by_key(|kind| kind.name);
+        let GcStatistics {
+            collections,
+            collected_count,
+            total_pause,
+            max_pause,
+        } = heap.gc_statistics;
+        Self {
+            total_bytes: kinds.iter().map(|kind| kind.bytes + kind.owned_bytes).sum(),
+            kinds,
+            gc_count: collections,
+            gc_collected_count: collected_count,
+            gc_total_pause: total_pause,
+            gc_max_pause: max_pause,
+        }
+    }
+}
+
+impl Heap {
+    /// Visit every vector of heap data.
+    pub(crate) fn visit_vectors(&self, visitor: &mut impl HeapVectorVisitor) {
+        let Heap {
+            #[cfg(feature = "array-buffer")]
+            array_buffers,
+            #[cfg(feature = "array-buffer")]
+                array_buffer_detach_keys: _,
+            arrays,
+            array_iterators,
+            async_generators,
+            await_reactions,
+            bigints,
+            bound_functions,
+            builtin_constructors,
+            builtin_functions,
+            #[cfg(feature = "array-buffer")]
+            data_views,
+            #[cfg(feature = "array-buffer")]
+                data_view_byte_lengths: _,
+            #[cfg(feature = "array-buffer")]
+                data_view_byte_offsets: _,
+            #[cfg(feature = "date")]
+            dates,
+            ecmascript_functions,
+            elements,
+            embedder_objects,
+            environments,
+            errors,
+            executables,
+            finalization_registrys,
+            generators,
+            globals,
+            maps,
+            map_iterators,
+            numbers,
+            objects,
+            object_shapes,
+            object_shape_roots: _,
+            gc_threshold: _,
+            owned_bytes: _,
+            gc_statistics: _,
+            primitive_objects,
+            promise_reaction_records,
+            promise_resolving_functions,
+            promises,
+            proxy_revoker_functions,
+            proxys,
+            realms,
+            #[cfg(feature = "regexp")]
+            regexps,
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators,
+            #[cfg(feature = "set")]
+            sets,
+            #[cfg(feature = "set")]
+            set_iterators,
+            #[cfg(feature = "shared-array-buffer")]
+            shared_array_buffers,
+            string_iterators,
+            symbols,
+            #[cfg(feature = "array-buffer")]
+            typed_arrays,
+            #[cfg(feature = "array-buffer")]
+                typed_array_byte_lengths: _,
+            #[cfg(feature = "array-buffer")]
+                typed_array_byte_offsets: _,
+            #[cfg(feature = "array-buffer")]
+                typed_array_array_lengths: _,
+            #[cfg(feature = "weak-refs")]
+            weak_maps,
+            #[cfg(feature = "weak-refs")]
+            weak_refs,
+            #[cfg(feature = "weak-refs")]
+            weak_sets,
+            modules,
+            scripts,
+            source_codes,
+            strings,
+            string_lookup_table: _,
+            string_hasher: _,
+        } = self;
+        #[cfg(feature = "array-buffer")]
+        visit_owning_vector(visitor, "array_buffers", array_buffers);
+        visit_option_vector(visitor, "arrays", arrays);
+        visit_option_vector(visitor, "array_iterators", array_iterators);
+        visit_option_vector(visitor, "async_generators", async_generators);
+        visit_option_vector(visitor, "await_reactions", await_reactions);
+        visit_owning_vector(visitor, "bigints", bigints);
+        visit_option_vector(visitor, "bound_functions", bound_functions);
+        visit_option_vector(visitor, "builtin_constructors", builtin_constructors);
+        visit_option_vector(visitor, "builtin_functions", builtin_functions);
+        #[cfg(feature = "array-buffer")]
+        visit_option_vector(visitor, "data_views", data_views);
+        #[cfg(feature = "date")]
+        visit_option_vector(visitor, "dates", dates);
+        visit_option_vector(visitor, "ecmascript_functions", ecmascript_functions);
+        elements.visit_vectors(visitor);
+        visit_option_vector(visitor, "embedder_objects", embedder_objects);
+        environments.visit_vectors(visitor);
+        visit_option_vector(visitor, "errors", errors);
+        visitor.visit(
+            "executables",
+            executables,
+            || executables.len(),
+            || executables.iter().map(OwnedAllocations::owned_bytes).sum(),
+        );
+        visit_option_vector(visitor, "finalization_registrys", finalization_registrys);
+        visit_option_vector(visitor, "generators", generators);
+        visit_option_vector(visitor, "globals", &globals.borrow());
+        visit_owning_vector(visitor, "maps", maps);
+        visit_option_vector(visitor, "map_iterators", map_iterators);
+        visit_option_vector(visitor, "numbers", numbers);
+        visit_option_vector(visitor, "objects", objects);
+        visitor.visit("object_shapes", object_shapes, || object_shapes.len(), || 0);
+        visit_option_vector(visitor, "primitive_objects", primitive_objects);
+        visit_option_vector(
+            visitor,
+            "promise_reaction_records",
+            promise_reaction_records,
+        );
+        visit_option_vector(
+            visitor,
+            "promise_resolving_functions",
+            promise_resolving_functions,
+        );
+        visit_option_vector(visitor, "promises", promises);
+        visit_option_vector(visitor, "proxy_revoker_functions", proxy_revoker_functions);
+        visit_option_vector(visitor, "proxys", proxys);
+        visit_option_vector(visitor, "realms", realms);
+        #[cfg(feature = "regexp")]
+        visit_option_vector(visitor, "regexps", regexps);
+        #[cfg(feature = "regexp")]
+        visit_option_vector(visitor, "regexp_string_iterators", regexp_string_iterators);
+        #[cfg(feature = "set")]
+        visit_owning_vector(visitor, "sets", sets);
+        #[cfg(feature = "set")]
+        visit_option_vector(visitor, "set_iterators", set_iterators);
+        #[cfg(feature = "shared-array-buffer")]
+        visit_option_vector(visitor, "shared_array_buffers", shared_array_buffers);
+        visit_option_vector(visitor, "string_iterators", string_iterators);
+        visit_option_vector(visitor, "symbols", symbols);
+        #[cfg(feature = "array-buffer")]
+        visit_option_vector(visitor, "typed_arrays", typed_arrays);
+        #[cfg(feature = "weak-refs")]
+        visit_option_vector(visitor, "weak_maps", weak_maps);
+        #[cfg(feature = "weak-refs")]
+        visit_option_vector(visitor, "weak_refs", weak_refs);
+        #[cfg(feature = "weak-refs")]
+        visit_option_vector(visitor, "weak_sets", weak_sets);
+        visit_option_vector(visitor, "modules", modules);
+        visit_option_vector(visitor, "scripts", scripts);
+        visit_owning_vector(visitor, "source_codes", source_codes);
+        visit_owning_vector(visitor, "strings", strings);
+    }
+
+    /// Count the bytes owned by heap data outside of the heap vectors.
+    pub(crate) fn count_owned_bytes(&self) -> usize {
+        struct OwnedByteCounter(usize);
+        impl HeapVectorVisitor for OwnedByteCounter {
+            fn visit<T>(
+                &mut self,
+                _: &'static str,
+                _: &[T],
+                _: impl FnOnce() -> usize,
+                owned_bytes: impl FnOnce() -> usize,
+            ) {
+                self.0 += owned_bytes();
+            }
+        }
+        let mut counter = OwnedByteCounter(0);
+        self.visit_vectors(&mut counter);
+        counter.0
+    }
+
+    /// Total number of slots in all heap vectors.
+    pub(crate) fn slot_count(&self) -> usize {
+        struct SlotCounter(usize);
+        impl HeapVectorVisitor for SlotCounter {
+            fn visit<T>(
+                &mut self,
+                _: &'static str,
+                slots: &[T],
+                _: impl FnOnce() -> usize,
+                _: impl FnOnce() -> usize,
+            ) {
+                self.0 += slots.len();
+            }
+        }
+        let mut counter = SlotCounter(0);
+        self.visit_vectors(&mut counter);
+        counter.0
+    }
+}
+
+impl ElementArrays {
+    fn visit_vectors(&self, visitor: &mut impl HeapVectorVisitor) {
+        let Self {
+            e2pow4,
+            e2pow6,
+            e2pow8,
+            e2pow10,
+            e2pow12,
+            e2pow16,
+            e2pow24,
+            e2pow32,
+            sparse,
+            float64,
+        } = self;
+        visit_option_vector(visitor, ElementArrayKey::E4.name(), &e2pow4.values);
+        visit_option_vector(visitor, ElementArrayKey::E6.name(), &e2pow6.values);
+        visit_option_vector(visitor, ElementArrayKey::E8.name(), &e2pow8.values);
+        visit_option_vector(visitor, ElementArrayKey::E10.name(), &e2pow10.values);
+        visit_option_vector(visitor, ElementArrayKey::E12.name(), &e2pow12.values);
+        visit_option_vector(visitor, ElementArrayKey::E16.name(), &e2pow16.values);
+        visit_option_vector(visitor, ElementArrayKey::E24.name(), &e2pow24.values);
+        visit_option_vector(visitor, ElementArrayKey::E32.name(), &e2pow32.values);
+        visit_option_vector(visitor, ElementArrayKey::Sparse.name(), sparse);
+        visit_option_vector(visitor, ElementArrayKey::Float64.name(), float64);
+    }
+}
+
+impl ElementArrayKey {
+    fn name(self) -> &'static str {
+        match self {
+            ElementArrayKey::Empty => "elements_empty",
+            ElementArrayKey::E4 => "elements_2pow4",
+            ElementArrayKey::E6 => "elements_2pow6",
+            ElementArrayKey::E8 => "elements_2pow8",
+            ElementArrayKey::E10 => "elements_2pow10",
+            ElementArrayKey::E12 => "elements_2pow12",
+            ElementArrayKey::E16 => "elements_2pow16",
+            ElementArrayKey::E24 => "elements_2pow24",
+            ElementArrayKey::E32 => "elements_2pow32",
+            ElementArrayKey::Sparse => "elements_sparse",
+            ElementArrayKey::Float64 => "elements_float64",
+        }
+    }
+}
+
+impl Environments {
+    fn visit_vectors(&self, visitor: &mut impl HeapVectorVisitor) {
+        let Self {
+            declarative,
+            function,
+            global,
+            object,
+            private,
+        } = self;
+        visit_option_vector(visitor, "declarative_environments", declarative);
+        visit_option_vector(visitor, "function_environments", function);
+        visit_option_vector(visitor, "global_environments", global);
+        visit_option_vector(visitor, "object_environments", object);
+        visit_option_vector(visitor, "private_environments", private);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ecmascript::execution::{
+            agent::{GcAgent, Options},
+            DefaultHostHooks,
+        },
+        test_utils::eval_script,
+    };
+
+    fn eval(agent: &mut GcAgent, source_text: &str) {
+        let realm = agent.create_default_realm();
+        agent.run_in_realm(&realm, |agent, gc| {
+            eval_script(agent, source_text, gc).unwrap();
+        });
+        agent.remove_realm(realm);
+    }
+
+    #[test]
+    fn heap_statistics_count_slots_and_owned_bytes() {
+        let mut agent = GcAgent::new(Options::default(), &DefaultHostHooks);
+        eval(
+            &mut agent,
+            "globalThis.s = 'x'.repeat(100000); globalThis.b = new ArrayBuffer(65536);",
+        );
+        let stats = agent.heap_statistics();
+        assert!(stats
+            .kinds
+            .windows(2)
+            .all(|pair| pair[0].name < pair[1].name));
+        let kind = |name| stats.kinds.iter().find(|kind| kind.name == name).unwrap();
+        let strings = kind("strings");
+        assert!(strings.live_count > 0);
+        assert!(strings.live_count <= strings.slot_count);
+        assert!(strings.owned_bytes >= 100000);
+        assert!(kind("array_buffers").owned_bytes >= 65536);
+        assert_eq!(kind("numbers").owned_bytes, 0);
+        assert!(kind("elements_2pow4").slot_count > 0);
+        assert_eq!(
+            stats.total_bytes,
+            stats
+                .kinds
+                .iter()
+                .map(|kind| kind.bytes + kind.owned_bytes)
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn heap_statistics_record_collections() {
+        let mut agent = GcAgent::new(Options::default(), &DefaultHostHooks);
+        assert_eq!(agent.heap_statistics().gc_count, 0);
+        eval(&mut agent, "for (let i = 0; i < 100; i++) { ({ i }); }");
+        agent.gc();
+        let stats = agent.heap_statistics();
+        assert_eq!(stats.gc_count, 1);
+        assert!(stats.gc_collected_count >= 100);
+        assert!(stats.gc_max_pause <= stats.gc_total_pause);
+    }
+}
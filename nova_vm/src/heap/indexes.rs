@@ -6,6 +6,8 @@
 use crate::ecmascript::builtins::date::data::DateHeapData;
 #[cfg(feature = "regexp")]
 use crate::ecmascript::builtins::regexp::RegExpHeapData;
+#[cfg(feature = "regexp")]
+use crate::ecmascript::builtins::text_processing::regexp_objects::regexp_string_iterator_objects::regexp_string_iterator::RegExpStringIteratorHeapData;
 #[cfg(feature = "shared-array-buffer")]
 use crate::ecmascript::builtins::shared_array_buffer::data::SharedArrayBufferHeapData;
 #[cfg(feature = "array-buffer")]
@@ -32,7 +34,9 @@ use crate::{
             indexed_collections::array_objects::array_iterator_objects::array_iterator::ArrayIteratorHeapData,
             keyed_collections::map_objects::map_iterator_objects::map_iterator::MapIteratorHeapData,
             map::data::MapHeapData, primitive_objects::PrimitiveObjectHeapData,
-            promise::data::PromiseHeapData, proxy::data::ProxyHeapData, ArrayHeapData,
+            promise::data::PromiseHeapData, proxy::data::ProxyHeapData,
+            text_processing::string_objects::string_iterator_objects::string_iterator::StringIteratorHeapData,
+            ArrayHeapData,
         },
         types::{
             BigIntHeapData, BoundFunctionHeapData, BuiltinConstructorHeapData,
@@ -41,6 +45,7 @@ use crate::{
         },
     },
     engine::context::{GcToken, NoGcScope},
+    heap::element_array::{Float64ElementArray, SparseElementArray},
 };
 use core::fmt::Debug;
 use std::{
@@ -55,7 +60,10 @@ use std::{marker::PhantomData, mem::size_of, num::NonZeroU32};
 ///
 /// This index implies a tracing reference count from this
 /// struct to T at the given index.
-pub struct BaseIndex<'a, T: ?Sized>(NonZeroU32, PhantomData<T>, PhantomData<&'a GcToken>);
+///
+/// The index does not own a T: It is Send and Sync regardless of T, like the
+/// integer it is.
+pub struct BaseIndex<'a, T: ?Sized>(NonZeroU32, PhantomData<fn() -> T>, PhantomData<&'a GcToken>);
 
 const _INDEX_SIZE_IS_U32: () = assert!(size_of::<BaseIndex<()>>() == size_of::<u32>());
 const _OPTION_INDEX_SIZE_IS_U32: () =
@@ -222,6 +230,9 @@ pub type SetIteratorIndex<'a> = BaseIndex<'a, SetIteratorHeapData>;
 #[cfg(feature = "shared-array-buffer")]
 pub type SharedArrayBufferIndex<'a> = BaseIndex<'a, SharedArrayBufferHeapData>;
 pub type StringIndex<'a> = BaseIndex<'a, StringHeapData>;
+pub type StringIteratorIndex<'a> = BaseIndex<'a, StringIteratorHeapData>;
+#[cfg(feature = "regexp")]
+pub type RegExpStringIteratorIndex<'a> = BaseIndex<'a, RegExpStringIteratorHeapData>;
 pub type SymbolIndex<'a> = BaseIndex<'a, SymbolHeapData>;
 #[cfg(feature = "array-buffer")]
 pub type TypedArrayIndex<'a> = BaseIndex<'a, TypedArrayHeapData>;
@@ -267,7 +278,7 @@ impl Default for ElementIndex {
 }
 
 impl ElementIndex {
-    pub fn last_element_index<const N: usize>(vec: &[Option<[Option<Value>; N]>]) -> Self {
+    pub fn last_element_index<T>(vec: &[Option<T>]) -> Self {
         assert!(!vec.is_empty());
         Self::from_usize(vec.len())
     }
@@ -292,3 +303,43 @@ impl<const N: usize> IndexMut<ElementIndex> for Vec<Option<[Option<Value>; N]>>
             .expect("Invalid ElementsVector: Found None at index")
     }
 }
+
+impl Index<ElementIndex> for Vec<Option<SparseElementArray>> {
+    type Output = SparseElementArray;
+
+    fn index(&self, index: ElementIndex) -> &Self::Output {
+        self.get(index.into_index())
+            .expect("Invalid ElementsVector: No item at index")
+            .as_ref()
+            .expect("Invalid ElementsVector: Found None at index")
+    }
+}
+
+impl IndexMut<ElementIndex> for Vec<Option<SparseElementArray>> {
+    fn index_mut(&mut self, index: ElementIndex) -> &mut Self::Output {
+        self.get_mut(index.into_index())
+            .expect("Invalid ElementsVector: No item at index")
+            .as_mut()
+            .expect("Invalid ElementsVector: Found None at index")
+    }
+}
+
+impl Index<ElementIndex> for Vec<Option<Float64ElementArray>> {
+    type Output = Float64ElementArray;
+
+    fn index(&self, index: ElementIndex) -> &Self::Output {
+        self.get(index.into_index())
+            .expect("Invalid ElementsVector: No item at index")
+            .as_ref()
+            .expect("Invalid ElementsVector: Found None at index")
+    }
+}
+
+impl IndexMut<ElementIndex> for Vec<Option<Float64ElementArray>> {
+    fn index_mut(&mut self, index: ElementIndex) -> &mut Self::Output {
+        self.get_mut(index.into_index())
+            .expect("Invalid ElementsVector: No item at index")
+            .as_mut()
+            .expect("Invalid ElementsVector: Found None at index")
+    }
+}
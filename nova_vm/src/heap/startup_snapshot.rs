@@ -0,0 +1,955 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! ## Startup snapshots
+//!
+//! A startup snapshot is the serialized heap of an Agent, taken after its
+//! Realms have been created and optionally after some warm-up code has run.
+//! Booting an Agent from a snapshot skips creating the intrinsics of its
+//! Realms, which is the bulk of the startup cost of a new Agent.
+//!
+//! Heap references are serialized as their indexes: A snapshot is always
+//! taken right after a garbage collection, so that the heap vectors are
+//! compacted and indexes stay the same when the vectors are read back.
+//!
+//! Data that cannot be serialized as-is is re-linked on load:
+//!
+//! - Builtin functions are serialized as a stable index into the list of
+//!   builtin behaviours that Realm creation creates, in creation order,
+//!   followed by the behaviours the embedder passes in as external builtins.
+//!   A snapshot is only compatible with the build of the engine, and the
+//!   external builtins, that created it.
+//! - Source code is re-parsed on load. ECMAScript functions find their
+//!   function body and formal parameters in the new AST by their span.
+//! - Compiled bytecode is dropped; functions recompile on their next call.
+//!
+//! Only the kinds of heap data that Realm creation and typical warm-up code
+//! create are supported. Others, such as Maps, Promises or ArrayBuffers,
+//! make snapshot creation fail with [`SnapshotError::UnsupportedHeapData`].
+
+use std::{collections::BTreeMap, hash::Hash, num::NonZeroU32, ptr::NonNull, sync::OnceLock};
+
+use ahash::{AHashMap, AHashSet};
+use oxc_ast::{
+    ast::{FormalParameters, FunctionBody, Program},
+    visit::walk,
+    Visit,
+};
+use oxc_span::Span;
+
+use super::{indexes::BaseIndex, CreateHeapData, Heap};
+use crate::ecmascript::{
+    builtins::Behaviour,
+    execution::{agent::Options, Agent, DefaultHostHooks, RealmIdentifier},
+    scripts_and_modules::source_code::{add_snapshot_dependents, SourceCodeHeapData},
+    types::{String, StringHeapData, Symbol, BUILTIN_STRINGS_LIST},
+};
+
+/// Identifies the byte blob as a startup snapshot.
+const SNAPSHOT_MAGIC: &[u8; 8] = b"NOVASNAP";
+
+/// Version of the snapshot format; bumped on every incompatible change.
+const SNAPSHOT_FORMAT_VERSION: u32 = 5;
+
+/// Errors of startup snapshot creation and loading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The heap contains a kind of data that startup snapshots do not
+    /// support.
+    UnsupportedHeapData(&'static str),
+    /// A builtin function's behaviour is neither created by Realm creation
+    /// nor one of the external builtins.
+    UnknownBuiltin,
+    /// A Realm or Script has host defined data, which cannot be serialized.
+    HostDefined,
+    /// The snapshot was created by a different build of the engine or with
+    /// different external builtins.
+    Incompatible,
+    /// The snapshot data is malformed.
+    Malformed,
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::UnsupportedHeapData(kind) => {
+                write!(f, "Startup snapshots do not support {}", kind)
+            }
+            SnapshotError::UnknownBuiltin => {
+                write!(f, "Builtin function is not a Realm or external builtin")
+            }
+            SnapshotError::HostDefined => {
+                write!(f, "Host defined data cannot be included in a snapshot")
+            }
+            SnapshotError::Incompatible => write!(f, "Incompatible startup snapshot"),
+            SnapshotError::Malformed => write!(f, "Malformed startup snapshot"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+pub(crate) type SnapshotResult<T> = Result<T, SnapshotError>;
+
+/// Data that can be written into and read back from a startup snapshot.
+pub(crate) trait SnapshotData: Sized {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()>;
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self>;
+}
+
+#[derive(Default)]
+pub(crate) struct SnapshotWriter {
+    bytes: Vec<u8>,
+    /// Indexes of the known builtin behaviours.
+    builtins: AHashMap<(bool, usize), u32>,
+}
+
+impl SnapshotWriter {
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Get the stable index of a builtin behaviour.
+    pub(crate) fn builtin_index(&self, behaviour: Behaviour) -> SnapshotResult<u32> {
+        self.builtins
+            .get(&behaviour_key(behaviour))
+            .copied()
+            .ok_or(SnapshotError::UnknownBuiltin)
+    }
+}
+
+pub(crate) struct SnapshotReader<'a> {
+    bytes: &'a [u8],
+    builtins: Vec<Behaviour>,
+    /// Function bodies of the re-parsed source codes, keyed by source code
+    /// index and span.
+    function_bodies: AHashMap<(usize, Span), NonNull<FunctionBody<'static>>>,
+    /// Formal parameters of the re-parsed source codes, keyed by source code
+    /// index and span.
+    formal_parameters: AHashMap<(usize, Span), NonNull<FormalParameters<'static>>>,
+    /// Re-parsed Programs, waiting to be taken by their Scripts.
+    programs: AHashMap<usize, Program<'static>>,
+}
+
+impl<'a> SnapshotReader<'a> {
+    /// Create a reader of data that refers to no builtin functions or
+    /// source codes.
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            builtins: Vec::new(),
+            function_bodies: AHashMap::default(),
+            formal_parameters: AHashMap::default(),
+            programs: AHashMap::default(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub(crate) fn read_bytes(&mut self, len: usize) -> SnapshotResult<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(SnapshotError::Malformed);
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> SnapshotResult<[u8; N]> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    /// Get the builtin behaviour with the given stable index.
+    pub(crate) fn builtin(&self, index: u32) -> SnapshotResult<Behaviour> {
+        self.builtins
+            .get(index as usize)
+            .copied()
+            .ok_or(SnapshotError::Malformed)
+    }
+
+    /// Register the function nodes of a re-parsed source code's Program.
+    pub(crate) fn add_program(&mut self, source_code: usize, program: Program<'static>) {
+        let mut collector = FunctionNodeCollector {
+            source_code,
+            function_bodies: &mut self.function_bodies,
+            formal_parameters: &mut self.formal_parameters,
+        };
+        collector.visit_program(&program);
+        self.programs.insert(source_code, program);
+    }
+
+    /// Take the re-parsed Program of a source code.
+    pub(crate) fn take_program(&mut self, source_code: usize) -> SnapshotResult<Program<'static>> {
+        self.programs
+            .remove(&source_code)
+            .ok_or(SnapshotError::Malformed)
+    }
+
+    /// Find a function body in a re-parsed source code.
+    pub(crate) fn function_body(
+        &self,
+        source_code: usize,
+        span: Span,
+    ) -> SnapshotResult<NonNull<FunctionBody<'static>>> {
+        self.function_bodies
+            .get(&(source_code, span))
+            .copied()
+            .ok_or(SnapshotError::Incompatible)
+    }
+
+    /// Find formal parameters in a re-parsed source code.
+    pub(crate) fn formal_parameters(
+        &self,
+        source_code: usize,
+        span: Span,
+    ) -> SnapshotResult<NonNull<FormalParameters<'static>>> {
+        self.formal_parameters
+            .get(&(source_code, span))
+            .copied()
+            .ok_or(SnapshotError::Incompatible)
+    }
+}
+
+struct FunctionNodeCollector<'r> {
+    source_code: usize,
+    function_bodies: &'r mut AHashMap<(usize, Span), NonNull<FunctionBody<'static>>>,
+    formal_parameters: &'r mut AHashMap<(usize, Span), NonNull<FormalParameters<'static>>>,
+}
+
+impl<'a> Visit<'a> for FunctionNodeCollector<'_> {
+    fn visit_function_body(&mut self, it: &FunctionBody<'a>) {
+        // SAFETY: The Program's nodes live in the SourceCode's allocator,
+        // which outlives all functions referring to them.
+        let node = unsafe {
+            std::mem::transmute::<NonNull<FunctionBody<'a>>, NonNull<FunctionBody<'static>>>(
+                NonNull::from(it),
+            )
+        };
+        self.function_bodies
+            .insert((self.source_code, it.span), node);
+        walk::walk_function_body(self, it);
+    }
+
+    fn visit_formal_parameters(&mut self, it: &FormalParameters<'a>) {
+        // SAFETY: See above.
+        let node = unsafe {
+            std::mem::transmute::<NonNull<FormalParameters<'a>>, NonNull<FormalParameters<'static>>>(
+                NonNull::from(it),
+            )
+        };
+        self.formal_parameters
+            .insert((self.source_code, it.span), node);
+        walk::walk_formal_parameters(self, it);
+    }
+}
+
+fn behaviour_key(behaviour: Behaviour) -> (bool, usize) {
+    match behaviour {
+        Behaviour::Regular(function) => (false, function as usize),
+        Behaviour::Constructor(function) => (true, function as usize),
+    }
+}
+
+/// Builtin behaviours created by Realm creation, in creation order.
+///
+/// Realm creation is deterministic, so the index of a behaviour in this list
+/// is stable across processes running the same build of the engine. The list
+/// is computed by creating a Realm in a scratch Agent, once per process.
+fn realm_builtins() -> &'static [Behaviour] {
+    static REALM_BUILTINS: OnceLock<Box<[Behaviour]>> = OnceLock::new();
+    REALM_BUILTINS.get_or_init(|| {
+        let mut agent = Agent::new(Options::default(), &DefaultHostHooks);
+        agent.create_default_realm();
+        agent
+            .heap
+            .builtin_functions
+            .iter()
+            .flatten()
+            .map(|function| function.behaviour)
+            .collect()
+    })
+}
+
+fn builtin_list(external_builtins: &[Behaviour]) -> Vec<Behaviour> {
+    let mut builtins = realm_builtins().to_vec();
+    builtins.extend_from_slice(external_builtins);
+    builtins
+}
+
+/// Serialize the Agent's heap into a startup snapshot. The heap should be
+/// freshly garbage collected.
+pub(crate) fn write_startup_snapshot(
+    agent: &Agent,
+    realm_roots: &[Option<RealmIdentifier>],
+    external_builtins: &[Behaviour],
+) -> SnapshotResult<Vec<u8>> {
+    let builtins = builtin_list(external_builtins);
+    let mut writer = SnapshotWriter {
+        bytes: Vec::new(),
+        builtins: AHashMap::with_capacity(builtins.len()),
+    };
+    // Keep the first index of behaviours that appear multiple times.
+    for (index, &behaviour) in builtins.iter().enumerate().rev() {
+        writer
+            .builtins
+            .insert(behaviour_key(behaviour), index as u32);
+    }
+    writer.write_bytes(SNAPSHOT_MAGIC);
+    SNAPSHOT_FORMAT_VERSION.serialize(&mut writer)?;
+    (builtins.len() as u32).serialize(&mut writer)?;
+    (BUILTIN_STRINGS_LIST.len() as u32).serialize(&mut writer)?;
+
+    let Agent {
+        heap,
+        symbol_id,
+        global_symbol_registry,
+        execution_context_stack,
+        vm_stack,
+        options: _,
+        host_hooks: _,
+        stack_refs: _,
+        terminating: _,
+        interrupts: _,
+        executed_instructions: _,
+        stack_base: _,
+    } = agent;
+    assert!(execution_context_stack.is_empty() && vm_stack.is_empty());
+    symbol_id.serialize(&mut writer)?;
+    let registry = global_symbol_registry
+        .iter()
+        .map(|(&key, &symbol)| (key.to_owned(), symbol))
+        .collect::<Vec<(std::string::String, Symbol<'static>)>>();
+    registry.serialize(&mut writer)?;
+    realm_roots.to_vec().serialize(&mut writer)?;
+    heap.serialize_snapshot(&mut writer)?;
+    Ok(writer.bytes)
+}
+
+/// Replace the Agent's heap with the heap of a startup snapshot. Returns the
+/// Realm roots of the snapshot.
+pub(crate) fn read_startup_snapshot(
+    agent: &mut Agent,
+    snapshot: &[u8],
+    external_builtins: &[Behaviour],
+) -> SnapshotResult<Vec<Option<RealmIdentifier>>> {
+    let mut reader = SnapshotReader {
+        bytes: snapshot,
+        builtins: builtin_list(external_builtins),
+        function_bodies: AHashMap::default(),
+        formal_parameters: AHashMap::default(),
+        programs: AHashMap::default(),
+    };
+    if reader.read_bytes(SNAPSHOT_MAGIC.len())? != SNAPSHOT_MAGIC {
+        return Err(SnapshotError::Malformed);
+    }
+    if u32::deserialize(&mut reader)? != SNAPSHOT_FORMAT_VERSION
+        || u32::deserialize(&mut reader)? as usize != reader.builtins.len()
+        || u32::deserialize(&mut reader)? as usize != BUILTIN_STRINGS_LIST.len()
+    {
+        return Err(SnapshotError::Incompatible);
+    }
+
+    let symbol_id = usize::deserialize(&mut reader)?;
+    let registry = Vec::<(std::string::String, Symbol<'static>)>::deserialize(&mut reader)?;
+    let realm_roots = Vec::<Option<RealmIdentifier>>::deserialize(&mut reader)?;
+    let heap = Heap::deserialize_snapshot(&mut reader)?;
+    if !reader.bytes.is_empty() {
+        return Err(SnapshotError::Malformed);
+    }
+    agent.heap = heap;
+    agent.symbol_id = symbol_id;
+    // The registry's keys are leaked like the keys of registered Symbols.
+    agent.global_symbol_registry = registry
+        .into_iter()
+        .map(|(key, symbol)| (&*Box::leak(key.into_boxed_str()), symbol))
+        .collect();
+    Ok(realm_roots)
+}
+
+/// Return an error if a heap vector of an unsupported kind has live data.
+fn check_unsupported<T>(kind: &'static str, vector: &[Option<T>]) -> SnapshotResult<()> {
+    if vector.iter().any(Option::is_some) {
+        Err(SnapshotError::UnsupportedHeapData(kind))
+    } else {
+        Ok(())
+    }
+}
+
+impl Heap {
+    fn serialize_snapshot(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        let Heap {
+            #[cfg(feature = "array-buffer")]
+            array_buffers,
+            #[cfg(feature = "array-buffer")]
+                array_buffer_detach_keys: _,
+            arrays,
+            array_iterators,
+            async_generators,
+            await_reactions,
+            bigints,
+            bound_functions,
+            builtin_constructors,
+            builtin_functions,
+            #[cfg(feature = "array-buffer")]
+            data_views,
+            #[cfg(feature = "array-buffer")]
+                data_view_byte_lengths: _,
+            #[cfg(feature = "array-buffer")]
+                data_view_byte_offsets: _,
+            #[cfg(feature = "date")]
+            dates,
+            ecmascript_functions,
+            elements,
+            embedder_objects,
+            environments,
+            errors,
+            // Functions recompile their bytecode on their next call.
+            executables: _,
+            finalization_registrys,
+            generators,
+            // Embedder roots do not carry over to the new Agent.
+            globals: _,
+            maps,
+            map_iterators,
+            numbers,
+            objects,
+            object_shapes,
+            object_shape_roots,
+            gc_threshold: _,
+            gc_statistics: _,
+            owned_bytes: _,
+            primitive_objects,
+            promise_reaction_records,
+            promise_resolving_functions,
+            promises,
+            proxy_revoker_functions,
+            proxys,
+            realms,
+            #[cfg(feature = "regexp")]
+            regexps,
+            #[cfg(feature = "regexp")]
+            regexp_string_iterators,
+            #[cfg(feature = "set")]
+            sets,
+            #[cfg(feature = "set")]
+            set_iterators,
+            #[cfg(feature = "shared-array-buffer")]
+            shared_array_buffers,
+            string_iterators,
+            symbols,
+            #[cfg(feature = "array-buffer")]
+            typed_arrays,
+            #[cfg(feature = "array-buffer")]
+                typed_array_byte_lengths: _,
+            #[cfg(feature = "array-buffer")]
+                typed_array_byte_offsets: _,
+            #[cfg(feature = "array-buffer")]
+                typed_array_array_lengths: _,
+            #[cfg(feature = "weak-refs")]
+            weak_maps,
+            #[cfg(feature = "weak-refs")]
+            weak_refs,
+            #[cfg(feature = "weak-refs")]
+            weak_sets,
+            modules,
+            scripts,
+            source_codes,
+            strings,
+            string_lookup_table: _,
+            string_hasher: _,
+        } = self;
+        #[cfg(feature = "array-buffer")]
+        check_unsupported("ArrayBuffers", array_buffers)?;
+        check_unsupported("Array Iterators", array_iterators)?;
+        check_unsupported("AsyncGenerators", async_generators)?;
+        check_unsupported("suspended async functions", await_reactions)?;
+        check_unsupported("class constructors", builtin_constructors)?;
+        #[cfg(feature = "array-buffer")]
+        check_unsupported("DataViews", data_views)?;
+        check_unsupported("embedder objects", embedder_objects)?;
+        check_unsupported("FinalizationRegistries", finalization_registrys)?;
+        check_unsupported("Generators", generators)?;
+        check_unsupported("Maps", maps)?;
+        check_unsupported("Map Iterators", map_iterators)?;
+        check_unsupported("Promise reactions", promise_reaction_records)?;
+        check_unsupported("Promise resolving functions", promise_resolving_functions)?;
+        check_unsupported("Promises", promises)?;
+        check_unsupported("Proxy revoker functions", proxy_revoker_functions)?;
+        #[cfg(feature = "regexp")]
+        check_unsupported("RegExps", regexps)?;
+        #[cfg(feature = "regexp")]
+        check_unsupported("RegExp String Iterators", regexp_string_iterators)?;
+        #[cfg(feature = "set")]
+        check_unsupported("Sets", sets)?;
+        #[cfg(feature = "set")]
+        check_unsupported("Set Iterators", set_iterators)?;
+        #[cfg(feature = "shared-array-buffer")]
+        check_unsupported("SharedArrayBuffers", shared_array_buffers)?;
+        check_unsupported("String Iterators", string_iterators)?;
+        #[cfg(feature = "array-buffer")]
+        check_unsupported("TypedArrays", typed_arrays)?;
+        #[cfg(feature = "weak-refs")]
+        check_unsupported("WeakMaps", weak_maps)?;
+        #[cfg(feature = "weak-refs")]
+        check_unsupported("WeakRefs", weak_refs)?;
+        #[cfg(feature = "weak-refs")]
+        check_unsupported("WeakSets", weak_sets)?;
+        check_unsupported("modules", modules)?;
+
+        // Strings and source codes come first: Source codes are parsed from
+        // the strings when read, and ECMAScript functions and Scripts refer
+        // to the parsed source codes.
+        strings.serialize(writer)?;
+        (source_codes.len() as u64).serialize(writer)?;
+        for source_code in source_codes {
+            match source_code {
+                Some(source_code) => {
+                    true.serialize(writer)?;
+                    source_code.serialize_snapshot(writer)?;
+                }
+                None => false.serialize(writer)?,
+            }
+        }
+        scripts.serialize(writer)?;
+        ecmascript_functions.serialize(writer)?;
+        arrays.serialize(writer)?;
+        bigints.serialize(writer)?;
+        bound_functions.serialize(writer)?;
+        builtin_functions.serialize(writer)?;
+        #[cfg(feature = "date")]
+        dates.serialize(writer)?;
+        elements.serialize(writer)?;
+        environments.serialize(writer)?;
+        errors.serialize(writer)?;
+        numbers.serialize(writer)?;
+        objects.serialize(writer)?;
+        object_shapes.serialize(writer)?;
+        object_shape_roots.serialize(writer)?;
+        primitive_objects.serialize(writer)?;
+        proxys.serialize(writer)?;
+        realms.serialize(writer)?;
+        symbols.serialize(writer)
+    }
+
+    fn deserialize_snapshot(reader: &mut SnapshotReader) -> SnapshotResult<Heap> {
+        // The builtin strings are already in a new heap; the rest of the
+        // heap is read in the order it was written.
+        let mut heap = Heap::new();
+        let string_count = u64::deserialize(reader)? as usize;
+        if string_count < BUILTIN_STRINGS_LIST.len() {
+            return Err(SnapshotError::Malformed);
+        }
+        for index in 0..string_count {
+            let Some(data) = Option::<StringHeapData>::deserialize(reader)? else {
+                // Heap strings are never holes: They are all in the lookup
+                // table, or are ropes.
+                return Err(SnapshotError::Malformed);
+            };
+            if index < BUILTIN_STRINGS_LIST.len() {
+                let builtin = heap.strings.get(index).and_then(Option::as_ref);
+                if builtin.map(StringHeapData::as_wtf8) != Some(data.as_wtf8()) {
+                    return Err(SnapshotError::Incompatible);
+                }
+                continue;
+            }
+            if data.is_rope() {
+                heap.strings.push(Some(data));
+                continue;
+            }
+            let hash = heap.string_hasher.hash_one(data.as_wtf8());
+            let _: String = heap.create((data, hash));
+        }
+        let source_code_count = u64::deserialize(reader)? as usize;
+        heap.source_codes.reserve(source_code_count);
+        for index in 0..source_code_count {
+            let source_code = if bool::deserialize(reader)? {
+                Some(SourceCodeHeapData::deserialize_snapshot(
+                    reader,
+                    &heap.strings,
+                    index,
+                )?)
+            } else {
+                None
+            };
+            heap.source_codes.push(source_code);
+        }
+        heap.scripts = SnapshotData::deserialize(reader)?;
+        heap.ecmascript_functions = SnapshotData::deserialize(reader)?;
+        add_snapshot_dependents(&mut heap)?;
+        heap.arrays = SnapshotData::deserialize(reader)?;
+        heap.bigints = SnapshotData::deserialize(reader)?;
+        heap.bound_functions = SnapshotData::deserialize(reader)?;
+        heap.builtin_functions = SnapshotData::deserialize(reader)?;
+        #[cfg(feature = "date")]
+        {
+            heap.dates = SnapshotData::deserialize(reader)?;
+        }
+        heap.elements = SnapshotData::deserialize(reader)?;
+        heap.environments = SnapshotData::deserialize(reader)?;
+        heap.errors = SnapshotData::deserialize(reader)?;
+        heap.numbers = SnapshotData::deserialize(reader)?;
+        heap.objects = SnapshotData::deserialize(reader)?;
+        heap.object_shapes = SnapshotData::deserialize(reader)?;
+        heap.object_shape_roots = SnapshotData::deserialize(reader)?;
+        heap.primitive_objects = SnapshotData::deserialize(reader)?;
+        heap.proxys = SnapshotData::deserialize(reader)?;
+        heap.realms = SnapshotData::deserialize(reader)?;
+        heap.symbols = SnapshotData::deserialize(reader)?;
+        heap.update_gc_threshold(super::DEFAULT_GC_GROWTH_FACTOR);
+        Ok(heap)
+    }
+}
+
+impl SnapshotData for bool {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        (*self as u8).serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        match u8::deserialize(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+}
+
+macro_rules! snapshot_data_for_number {
+    ($($ty: ty),*) => {
+        $(
+            impl SnapshotData for $ty {
+                fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+                    writer.write_bytes(&self.to_le_bytes());
+                    Ok(())
+                }
+
+                fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+                    Ok(Self::from_le_bytes(reader.read_array()?))
+                }
+            }
+        )*
+    };
+}
+
+snapshot_data_for_number!(u8, u16, u32, u64, i64, f64);
+
+impl SnapshotData for usize {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        (*self as u64).serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        usize::try_from(u64::deserialize(reader)?).map_err(|_| SnapshotError::Malformed)
+    }
+}
+
+impl SnapshotData for NonZeroU32 {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.get().serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        NonZeroU32::new(u32::deserialize(reader)?).ok_or(SnapshotError::Malformed)
+    }
+}
+
+impl SnapshotData for std::string::String {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.len().serialize(writer)?;
+        writer.write_bytes(self.as_bytes());
+        Ok(())
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let len = usize::deserialize(reader)?;
+        let bytes = reader.read_bytes(len)?;
+        std::string::String::from_utf8(bytes.to_vec()).map_err(|_| SnapshotError::Malformed)
+    }
+}
+
+impl SnapshotData for Span {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.start.serialize(writer)?;
+        self.end.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Span::new(
+            u32::deserialize(reader)?,
+            u32::deserialize(reader)?,
+        ))
+    }
+}
+
+impl<T: SnapshotData> SnapshotData for Option<T> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        match self {
+            Some(data) => {
+                true.serialize(writer)?;
+                data.serialize(writer)
+            }
+            None => false.serialize(writer),
+        }
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        if bool::deserialize(reader)? {
+            Ok(Some(T::deserialize(reader)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: SnapshotData> SnapshotData for Vec<T> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.len().serialize(writer)?;
+        for item in self {
+            item.serialize(writer)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let len = usize::deserialize(reader)?;
+        // Do not trust the length for preallocation beyond the data left.
+        let mut vec = Vec::with_capacity(len.min(reader.bytes.len()));
+        for _ in 0..len {
+            vec.push(T::deserialize(reader)?);
+        }
+        Ok(vec)
+    }
+}
+
+impl<T: SnapshotData> SnapshotData for Box<[T]> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.len().serialize(writer)?;
+        for item in self.iter() {
+            item.serialize(writer)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok(Vec::deserialize(reader)?.into_boxed_slice())
+    }
+}
+
+impl<A: SnapshotData, B: SnapshotData> SnapshotData for (A, B) {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.0.serialize(writer)?;
+        self.1.serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        Ok((A::deserialize(reader)?, B::deserialize(reader)?))
+    }
+}
+
+impl<K: SnapshotData + Eq + Hash, V: SnapshotData> SnapshotData for AHashMap<K, V> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.len().serialize(writer)?;
+        for (key, value) in self {
+            key.serialize(writer)?;
+            value.serialize(writer)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let len = usize::deserialize(reader)?;
+        let mut map = AHashMap::with_capacity(len.min(reader.bytes.len()));
+        for _ in 0..len {
+            let key = K::deserialize(reader)?;
+            map.insert(key, V::deserialize(reader)?);
+        }
+        Ok(map)
+    }
+}
+
+impl<K: SnapshotData + Ord, V: SnapshotData> SnapshotData for BTreeMap<K, V> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.len().serialize(writer)?;
+        for (key, value) in self {
+            key.serialize(writer)?;
+            value.serialize(writer)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let len = usize::deserialize(reader)?;
+        let mut map = BTreeMap::new();
+        for _ in 0..len {
+            let key = K::deserialize(reader)?;
+            map.insert(key, V::deserialize(reader)?);
+        }
+        Ok(map)
+    }
+}
+
+impl<T: SnapshotData + Eq + Hash> SnapshotData for AHashSet<T> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.len().serialize(writer)?;
+        for item in self {
+            item.serialize(writer)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let len = usize::deserialize(reader)?;
+        let mut set = AHashSet::with_capacity(len.min(reader.bytes.len()));
+        for _ in 0..len {
+            set.insert(T::deserialize(reader)?);
+        }
+        Ok(set)
+    }
+}
+
+impl<T: ?Sized> SnapshotData for BaseIndex<'static, T> {
+    fn serialize(&self, writer: &mut SnapshotWriter) -> SnapshotResult<()> {
+        self.into_u32_index().serialize(writer)
+    }
+
+    fn deserialize(reader: &mut SnapshotReader) -> SnapshotResult<Self> {
+        let index = u32::deserialize(reader)?;
+        if index == u32::MAX {
+            return Err(SnapshotError::Malformed);
+        }
+        Ok(Self::from_u32_index(index))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SnapshotError;
+    use crate::{
+        ecmascript::execution::{
+            agent::{GcAgent, Options, RealmRoot},
+            DefaultHostHooks,
+        },
+        test_utils::eval_script,
+    };
+
+    fn eval(agent: &mut GcAgent, realm: &RealmRoot, source_text: &str) -> String {
+        agent.run_in_realm(realm, |agent, mut gc| {
+            let value = eval_script(agent, source_text, gc.reborrow()).unwrap();
+            value.string_repr(agent, gc).as_str(agent).to_owned()
+        })
+    }
+
+    fn boot(snapshot: &[u8]) -> Result<(GcAgent, Vec<RealmRoot>), SnapshotError> {
+        GcAgent::from_startup_snapshot(Options::default(), &DefaultHostHooks, snapshot, &[])
+    }
+
+    #[test]
+    fn realm_snapshot_round_trip() {
+        let mut agent = GcAgent::new(Options::default(), &DefaultHostHooks);
+        let realm = agent.create_default_realm();
+        eval(
+            &mut agent,
+            &realm,
+            "function add(a, b) { return a + b; }
+            var counter = (function () { let n = 0; return function () { return ++n; }; })();
+            counter();
+            var config = { name: 'snap' + 'shot', values: [1, 2.5, 10n, Symbol('s')] };",
+        );
+        let snapshot = agent.create_startup_snapshot(&[]).unwrap();
+
+        let (mut agent, realms) = boot(&snapshot).unwrap();
+        assert_eq!(realms.len(), 1);
+        let realm = &realms[0];
+        // Functions recompile, closures keep their environment and
+        // intrinsics are still linked to their builtin behaviours.
+        assert_eq!(
+            eval(
+                &mut agent,
+                realm,
+                "[add(config.values[0], 40), config.name, counter(), typeof config.values[3],
+                config.values[1] * 2, config.values[2] + 1n, Math.max(3, 7), [3, 1, 2].sort()].join()"
+            ),
+            "41,snapshot,2,symbol,5,11,7,1,2,3"
+        );
+        // The booted Agent can itself be snapshotted again.
+        eval(&mut agent, realm, "config.name = 'again';");
+        let snapshot = agent.create_startup_snapshot(&[]).unwrap();
+        let (mut agent, realms) = boot(&snapshot).unwrap();
+        assert_eq!(
+            eval(&mut agent, &realms[0], "config.name + counter()"),
+            "again3"
+        );
+    }
+
+    #[test]
+    fn snapshot_of_discarded_parsed_code() {
+        let options = || Options {
+            discard_parsed_code: true,
+            ..Default::default()
+        };
+        let mut agent = GcAgent::new(options(), &DefaultHostHooks);
+        let realm = agent.create_default_realm();
+        eval(
+            &mut agent,
+            &realm,
+            "function add(a, b) { return a + b; } var double = x => add(x, x); double(1);",
+        );
+        agent.gc();
+        let snapshot = agent.create_startup_snapshot(&[]).unwrap();
+        let (mut agent, realms) =
+            GcAgent::from_startup_snapshot(options(), &DefaultHostHooks, &snapshot, &[]).unwrap();
+        // The booted functions are dependents of their parsed again source
+        // code, and are found again after it is discarded once more.
+        agent.gc();
+        assert_eq!(eval(&mut agent, &realms[0], "double(21)"), "42");
+    }
+
+    #[test]
+    fn snapshot_in_gc_stress_mode() {
+        let options = Options {
+            gc_stress: true,
+            ..Default::default()
+        };
+        let mut agent = GcAgent::new(options, &DefaultHostHooks);
+        let realm = agent.create_default_realm();
+        eval(
+            &mut agent,
+            &realm,
+            "var garbage = [{}, {}, 'a' + 1]; garbage = null; var kept = { value: 'b' + 2 };",
+        );
+        let snapshot = agent.create_startup_snapshot(&[]).unwrap();
+        let (mut agent, realms) = boot(&snapshot).unwrap();
+        assert_eq!(
+            eval(&mut agent, &realms[0], "kept.value + garbage"),
+            "b2null"
+        );
+    }
+
+    #[test]
+    fn unsupported_heap_data_fails_snapshot() {
+        let mut agent = GcAgent::new(Options::default(), &DefaultHostHooks);
+        let realm = agent.create_default_realm();
+        eval(&mut agent, &realm, "globalThis.map = new Map();");
+        assert_eq!(
+            agent.create_startup_snapshot(&[]).unwrap_err(),
+            SnapshotError::UnsupportedHeapData("Maps")
+        );
+    }
+
+    #[test]
+    fn malformed_snapshot_fails_to_boot() {
+        let mut agent = GcAgent::new(Options::default(), &DefaultHostHooks);
+        let _realm = agent.create_default_realm();
+        let snapshot = agent.create_startup_snapshot(&[]).unwrap();
+        assert!(boot(&snapshot[..snapshot.len() / 2]).is_err());
+        assert!(boot(b"not a snapshot").is_err());
+    }
+}
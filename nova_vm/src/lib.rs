@@ -8,6 +8,8 @@
 pub mod ecmascript;
 pub mod engine;
 pub mod heap;
+#[cfg(test)]
+mod test_utils;
 pub use engine::small_integer::SmallInteger;
 use heap::Heap;
 pub use small_string::SmallString;
@@ -0,0 +1,107 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Helpers for unit tests that evaluate JavaScript source text.
+
+use crate::{
+    ecmascript::{
+        execution::{
+            agent::{GcAgent, HostHooks, Options, RealmRoot},
+            Agent, DefaultHostHooks, InterruptHandle, JsResult,
+        },
+        scripts_and_modules::script::{parse_script, script_evaluation},
+        types::{String, Value},
+    },
+    engine::context::GcScope,
+};
+
+/// An agent with a default realm in which test scripts are evaluated.
+pub(crate) struct TestAgent {
+    agent: GcAgent,
+    realm: RealmRoot,
+}
+
+impl TestAgent {
+    pub(crate) fn new() -> Self {
+        Self::with_options(Options::default())
+    }
+
+    pub(crate) fn with_options(options: Options) -> Self {
+        Self::with_host_hooks(options, &DefaultHostHooks)
+    }
+
+    pub(crate) fn with_host_hooks(options: Options, host_hooks: &'static dyn HostHooks) -> Self {
+        let mut agent = GcAgent::new(options, host_hooks);
+        let realm = agent.create_default_realm();
+        Self { agent, realm }
+    }
+
+    /// Evaluate the source text as a script and return the string
+    /// representation of its completion value.
+    ///
+    /// Panics if the script throws.
+    #[track_caller]
+    pub(crate) fn eval(&mut self, source_text: &str) -> std::string::String {
+        match self.evaluate(source_text) {
+            Ok(result) => result,
+            Err(error) => panic!("Script threw {error}:\n{source_text}"),
+        }
+    }
+
+    /// Evaluate the source text as a script and return the string
+    /// representation of the value it throws.
+    ///
+    /// Panics if the script does not throw.
+    #[track_caller]
+    pub(crate) fn eval_err(&mut self, source_text: &str) -> std::string::String {
+        match self.evaluate(source_text) {
+            Ok(result) => panic!("Script returned {result} instead of throwing:\n{source_text}"),
+            Err(error) => error,
+        }
+    }
+
+    /// Run a closure in the realm of the test agent.
+    pub(crate) fn run<R>(
+        &mut self,
+        func: impl for<'agent, 'gc, 'scope> FnOnce(&'agent mut Agent, GcScope<'gc, 'scope>) -> R,
+    ) -> R {
+        self.agent.run_in_realm(&self.realm, func)
+    }
+
+    /// Get a handle for interrupting the test agent from another thread.
+    pub(crate) fn interrupt_handle(&self) -> InterruptHandle {
+        self.agent.interrupt_handle()
+    }
+
+    /// Perform a garbage collection.
+    pub(crate) fn gc(&mut self) {
+        self.agent.gc();
+    }
+
+    fn evaluate(&mut self, source_text: &str) -> Result<std::string::String, std::string::String> {
+        self.run(|agent, mut gc| {
+            let (value, threw) = match eval_script(agent, source_text, gc.reborrow()) {
+                Ok(value) => (value, false),
+                Err(error) => (error.value(), true),
+            };
+            let string = value.string_repr(agent, gc).as_str(agent).to_owned();
+            if threw {
+                Err(string)
+            } else {
+                Ok(string)
+            }
+        })
+    }
+}
+
+/// Evaluate the source text as a script in the current realm. This is meant
+/// for use within [`TestAgent::run`] when a test needs the resulting value
+/// itself.
+pub(crate) fn eval_script(agent: &mut Agent, source_text: &str, gc: GcScope) -> JsResult<Value> {
+    let realm = agent.current_realm_id();
+    let source_text = String::from_str(agent, source_text, gc.nogc());
+    let script = parse_script(agent, source_text, realm, false, None, gc.nogc())
+        .expect("Test script should parse");
+    script_evaluation(agent, script, gc)
+}